@@ -34,17 +34,18 @@ func TestShowReplicationLsnStats(t *testing.T) {
 		assert.NoError(t, err)
 
 		type ReplicationRow struct {
-			ID             int64
-			Database       string
-			User           string
-			Addr           string
-			Port           int64
-			Shard          int64
-			Role           string
-			ReplicaLag     *string
-			PgLsn          *string
-			LsnAge         *string
-			PgIsInRecovery *string
+			ID              int64
+			Database        string
+			User            string
+			Addr            string
+			Port            int64
+			Shard           int64
+			Role            string
+			ReplicaLag      *string
+			PgLsn           *string
+			LsnAge          *string
+			PgIsInRecovery  *string
+			ReplicaLagBytes *string
 		}
 
 		var results []ReplicationRow
@@ -62,6 +63,7 @@ func TestShowReplicationLsnStats(t *testing.T) {
 				&row.PgLsn,
 				&row.LsnAge,
 				&row.PgIsInRecovery,
+				&row.ReplicaLagBytes,
 			)
 			assert.NoError(t, err)
 			if row.Database == "postgres" {
@@ -139,17 +141,18 @@ func TestShowReplicationAutoRoleDetection(t *testing.T) {
 	defer conn.Close(context.Background())
 
 	type ReplicationRow struct {
-		ID             int64
-		Database       string
-		User           string
-		Addr           string
-		Port           int64
-		Shard          int64
-		Role           string
-		ReplicaLag     *string
-		PgLsn          *string
-		LsnAge         *string
-		PgIsInRecovery *string
+		ID              int64
+		Database        string
+		User            string
+		Addr            string
+		Port            int64
+		Shard           int64
+		Role            string
+		ReplicaLag      *string
+		PgLsn           *string
+		LsnAge          *string
+		PgIsInRecovery  *string
+		ReplicaLagBytes *string
 	}
 
 	const expectedPrimaryPort = int64(45000)
@@ -175,6 +178,7 @@ func TestShowReplicationAutoRoleDetection(t *testing.T) {
 				&row.PgLsn,
 				&row.LsnAge,
 				&row.PgIsInRecovery,
+				&row.ReplicaLagBytes,
 			)
 			assert.NoError(t, err)
 			if row.Database == "postgres_auto" {
@@ -49,6 +49,8 @@ pub enum AuthType {
     Trust,
     /// Plaintext password.
     Plain,
+    /// GSSAPI (Kerberos); the client principal, without the realm, must match the user name. The realm must be listed in `gssapi_realms`.
+    Gssapi,
 }
 
 impl Display for AuthType {
@@ -58,6 +60,7 @@ impl Display for AuthType {
             Self::Scram => write!(f, "scram"),
             Self::Trust => write!(f, "trust"),
             Self::Plain => write!(f, "plain"),
+            Self::Gssapi => write!(f, "gssapi"),
         }
     }
 }
@@ -74,6 +77,10 @@ impl AuthType {
     pub fn trust(&self) -> bool {
         matches!(self, Self::Trust)
     }
+
+    pub fn gssapi(&self) -> bool {
+        matches!(self, Self::Gssapi)
+    }
 }
 
 impl FromStr for AuthType {
@@ -85,6 +92,7 @@ impl FromStr for AuthType {
             "scram" => Ok(Self::Scram),
             "trust" => Ok(Self::Trust),
             "plain" => Ok(Self::Plain),
+            "gssapi" => Ok(Self::Gssapi),
             _ => Err(format!("Invalid auth type: {}", s)),
         }
     }
@@ -617,6 +617,7 @@ impl Config {
                 queue_length: m.queue_length.unwrap_or(self.general.mirror_queue),
                 exposure: m.exposure.unwrap_or(self.general.mirror_exposure),
                 level: m.level,
+                checksum: m.checksum,
             })
     }
 
@@ -629,6 +630,7 @@ impl Config {
                 queue_length: mirror.queue_length.unwrap_or(self.general.mirror_queue),
                 exposure: mirror.exposure.unwrap_or(self.general.mirror_exposure),
                 level: mirror.level,
+                checksum: mirror.checksum,
             };
 
             result
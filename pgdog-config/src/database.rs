@@ -50,6 +50,8 @@ pub enum LoadBalancingStrategy {
     LeastActiveConnections,
     /// Weighted round-robin, distributing requests proportionally to configured weights.
     WeightedRoundRobin,
+    /// Prefer replicas with the least replication lag, without excluding the ones behind.
+    Freshness,
 }
 
 impl FromStr for LoadBalancingStrategy {
@@ -61,6 +63,7 @@ impl FromStr for LoadBalancingStrategy {
             "roundrobin" => Ok(Self::RoundRobin),
             "leastactiveconnections" => Ok(Self::LeastActiveConnections),
             "weightedroundrobin" => Ok(Self::WeightedRoundRobin),
+            "freshness" => Ok(Self::Freshness),
             _ => Err(format!("Invalid load balancing strategy: {}", s)),
         }
     }
@@ -193,6 +196,22 @@ pub struct Database {
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#read_only>
     pub read_only: Option<bool>,
+    /// Sets the `default_transaction_isolation` connection parameter on all server connections to this database. Clients can still override it with `SET` or `BEGIN ISOLATION LEVEL`.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#default_transaction_isolation>
+    pub default_transaction_isolation: Option<IsolationLevel>,
+    /// Maximum number of queries per second clients can send to this database, per user.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#query_rate_limit>
+    pub query_rate_limit: Option<u32>,
+    /// Maximum number of transactions per second clients can start on this database, per user.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#transaction_rate_limit>
+    pub transaction_rate_limit: Option<u32>,
+    /// Overrides `filter` in the `[multi_tenant]` section for this database.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#multi_tenant_filter>
+    pub multi_tenant_filter: Option<bool>,
     /// Overrides the `server_lifetime` setting. Server connections older than this will be closed when returned to the pool.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#server_lifetime>
@@ -201,12 +220,30 @@ pub struct Database {
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#server_lifetime_jitter>
     pub server_lifetime_jitter: Option<u64>,
+    /// Query used to health check connections to this database, instead of the default empty query. When it's set, the database role is detected as well, using `SELECT pg_is_in_recovery()`.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#healthcheck_query>
+    pub healthcheck_query: Option<String>,
     /// Used for resharding only; this database will not serve regular traffic.
     #[serde(default)]
     pub resharding_only: bool,
     /// Used for weighted load balancing.
     #[serde(default = "Database::lb_weight")]
     pub lb_weight: u8,
+    /// Type of queries this database serves. Reads with an estimated cost at or above [`analytics_query_cost`](https://docs.pgdog.dev/configuration/pgdog.toml/general/#analytics_query_cost) are sent to `analytics` replicas, while cheaper reads prefer `oltp` ones.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#workload>
+    #[serde(default)]
+    pub workload: Workload,
+    /// Use `host` as the name of a DNS SRV record, e.g. `_postgres._tcp.shards.svc`, and create a shard for each of its targets, up to [`srv_shards`](https://docs.pgdog.dev/configuration/pgdog.toml/databases/#srv_shards). The shard number is the ordinal at the end of the target's first label, e.g. `postgres-1.shards.svc` is shard 1. `port` is taken from the record. The record is resolved again every [`srv_refresh_interval`](https://docs.pgdog.dev/configuration/pgdog.toml/general/#srv_refresh_interval).
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#srv>
+    #[serde(default)]
+    pub srv: bool,
+    /// Number of shards the SRV record of a database with `srv` enabled resolves to. Required with `srv`: the database is only created once targets for shards `0` to `srv_shards - 1` are all resolved, and targets for other shards are ignored.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#srv_shards>
+    pub srv_shards: Option<usize>,
 }
 
 impl Database {
@@ -297,6 +334,85 @@ impl FromStr for Role {
     }
 }
 
+/// Type of queries a database serves.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#workload>
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Eq,
+    Hash,
+    Copy,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Workload {
+    /// Short transactional queries, e.g. point lookups (default).
+    #[default]
+    Oltp,
+    /// Expensive reporting queries, e.g. large joins and aggregates.
+    Analytics,
+}
+
+impl std::fmt::Display for Workload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Oltp => write!(f, "oltp"),
+            Self::Analytics => write!(f, "analytics"),
+        }
+    }
+}
+
+/// Transaction isolation level.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/databases/#default_transaction_isolation>
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Eq,
+    Hash,
+    Copy,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationLevel {
+    /// `READ UNCOMMITTED`, which behaves like `READ COMMITTED` in PostgreSQL.
+    #[serde(alias = "read uncommitted")]
+    ReadUncommitted,
+    /// `READ COMMITTED` (PostgreSQL default).
+    #[default]
+    #[serde(alias = "read committed")]
+    ReadCommitted,
+    /// `REPEATABLE READ`.
+    #[serde(alias = "repeatable read")]
+    RepeatableRead,
+    /// `SERIALIZABLE`.
+    Serializable,
+}
+
+impl std::fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadUncommitted => write!(f, "read uncommitted"),
+            Self::ReadCommitted => write!(f, "read committed"),
+            Self::RepeatableRead => write!(f, "repeatable read"),
+            Self::Serializable => write!(f, "serializable"),
+        }
+    }
+}
+
 /// Database with a unique number, identifying it
 /// in the config.
 #[derive(Debug, Clone)]
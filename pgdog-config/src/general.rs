@@ -9,10 +9,13 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use crate::UniqueIdFunction;
-use crate::pooling::ConnectionRecovery;
+use crate::pooling::{
+    CheckoutFairness, ConnectionRecovery, PreparedStatementsSchemaChange, TemporaryTables,
+};
 use crate::{
-    CopyFormat, CutoverTimeoutAction, LoadSchema, QueryParserEngine, QueryParserLevel,
-    SystemCatalogsBehavior,
+    CopyFormat, CutoverTimeoutAction, LoadSchema, MultiStatementQueries, ParseFailures,
+    QueryParserEngine, QueryParserLevel, SearchPathConflict, SystemCatalogsBehavior,
+    UnknownStatements,
 };
 
 use super::auth::{AuthType, PassthroughAuth};
@@ -69,6 +72,34 @@ pub enum QuerySizeLimitAction {
     Block,
 }
 
+/// Action to take when a client tries to `SET` a parameter
+/// that isn't allowed to reach the server.
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema, FromStr,
+)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum DeniedParameterAction {
+    /// Return an error to the client (default).
+    #[default]
+    Error,
+    /// Ignore the `SET` and send the client a notice.
+    Notice,
+}
+
+/// Action to take when a client prepares more statements
+/// than `prepared_statements_client_limit` allows.
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema, FromStr,
+)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PreparedStatementsClientLimitAction {
+    /// Close the client's least recently used prepared statement (default).
+    #[default]
+    Evict,
+    /// Return an error to the client.
+    Error,
+}
+
 /// General settings are relevant to the operations of the pooler itself, or apply to all database pools.
 ///
 /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/>
@@ -123,6 +154,14 @@ pub struct General {
     #[serde(default = "General::min_pool_size")]
     pub min_pool_size: usize,
 
+    /// Additional server connections per database pool held back from regular clients. They are only handed out to internal maintenance tasks and admin commands, e.g. health checks, schema loading and two-phase commit recovery, so those can still run when the pool is saturated.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#reserve_pool_size>
+    #[serde(default = "General::reserve_pool_size")]
+    pub reserve_pool_size: usize,
+
     /// Default pooler mode to use for database pools.
     ///
     /// _Default:_ `transaction`
@@ -156,6 +195,25 @@ pub struct General {
     #[serde(default = "General::idle_healthcheck_delay")]
     pub idle_healthcheck_delay: u64,
 
+    /// Healthcheck connections that have been idle for longer than this before giving them to clients,
+    /// regardless of `healthcheck_interval`. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#test_on_checkout_after_idle>
+    #[serde(default = "General::test_on_checkout_after_idle")]
+    pub test_on_checkout_after_idle: u64,
+
+    /// Send a lightweight query to connections that have been idle for longer than this, to keep them open
+    /// through NAT gateways and load balancers that drop inactive connections. Pinging a connection doesn't
+    /// count as using it, so it's still closed after `idle_timeout`. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#idle_ping_interval>
+    #[serde(default = "General::idle_ping_interval")]
+    pub idle_ping_interval: u64,
+
     /// Maximum amount of time to wait for a healthcheck query to complete.
     ///
     /// _Default:_ `5000`
@@ -169,6 +227,14 @@ pub struct General {
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#healthcheck_port>
     pub healthcheck_port: Option<u16>,
 
+    /// The IP address of the local network interface the HTTP health check server will bind to.
+    ///
+    /// _Default:_ `0.0.0.0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#healthcheck_host>
+    #[serde(default = "General::healthcheck_host")]
+    pub healthcheck_host: String,
+
     /// Connection pools blocked from serving traffic due to an error will be placed back into active rotation after this long.
     ///
     /// _Default:_ `300000`
@@ -177,6 +243,23 @@ pub struct General {
     #[serde(default = "General::ban_timeout")]
     pub ban_timeout: u64,
 
+    /// Pools banned again shortly after being placed back into rotation are banned for twice as long
+    /// as the previous time, up to this duration. Set to `0` to always use `ban_timeout`.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#ban_timeout_max>
+    #[serde(default = "General::ban_timeout_max")]
+    pub ban_timeout_max: u64,
+
+    /// Ban duration is reset back to `ban_timeout` after the pool serves traffic without being banned for this long.
+    ///
+    /// _Default:_ `600000`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#ban_backoff_window>
+    #[serde(default = "General::ban_backoff_window")]
+    pub ban_backoff_window: u64,
+
     /// Ban a replica from serving read queries if its replication lag (in milliseconds) exceeds this threshold.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#ban_replica_lag>
@@ -221,6 +304,30 @@ pub struct General {
     #[serde(default)]
     pub read_write_split: ReadWriteSplit,
 
+    /// Reads with an estimated cost at or above this value are sent to replicas with `workload = "analytics"`. Other reads avoid those replicas. The cost is a heuristic computed from the query: each join, subquery and CTE adds one, and so do aggregates or `GROUP BY`, `DISTINCT` and a missing `WHERE` clause. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#analytics_query_cost>
+    #[serde(default = "General::analytics_query_cost")]
+    pub analytics_query_cost: usize,
+
+    /// Send this many reads after a client connects to the primary instead of the replicas. Helps with replicas lagging behind when many clients connect at once, e.g. right after a deploy. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#primary_reads_after_connect>
+    #[serde(default = "General::primary_reads_after_connect")]
+    pub primary_reads_after_connect: usize,
+
+    /// Send reads to the primary instead of the replicas for this many milliseconds after a client connects. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#primary_reads_after_connect_window>
+    #[serde(default = "General::primary_reads_after_connect_window")]
+    pub primary_reads_after_connect_window: u64,
+
     /// Path to the TLS certificate PgDog will use to setup TLS connections with clients.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#tls_certificate>
@@ -288,6 +395,19 @@ pub struct General {
     #[serde(default = "General::query_log_stdout")]
     pub query_log_stdout: bool,
 
+    /// Record where each query was routed, as one JSON object per line. Set to a file path,
+    /// or to `syslog` to send records to the local syslog daemon. Queries are normalized first,
+    /// so literal values don't end up in the log.
+    ///
+    /// Records are written in the background. If writing falls behind, new records are dropped
+    /// instead of slowing down queries, and counted in the `audit_log_dropped_total` metric.
+    ///
+    /// _Default:_ `None` (disabled)
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#audit_log>
+    #[serde(default = "General::audit_log")]
+    pub audit_log: Option<PathBuf>,
+
     /// Minimum parse duration in milliseconds that triggers a warning log with the query text.
     /// Queries whose parsing takes longer than this value are logged at WARN level.
     /// Set to `0` or omit to disable.
@@ -295,6 +415,12 @@ pub struct General {
     /// _Default:_ `None` (disabled)
     pub log_min_duration_parse: Option<u64>,
 
+    /// Minimum end-to-end query duration in milliseconds that triggers a warning log.
+    /// The query is normalized before logging, so literal values are not included.
+    ///
+    /// _Default:_ `None` (disabled)
+    pub log_slow_queries_ms: Option<u64>,
+
     /// Maximum number of characters of the query text included in log messages.
     ///
     /// _Default:_ `1000`
@@ -322,6 +448,40 @@ pub struct General {
     #[serde(default = "General::query_size_limit_action")]
     pub query_size_limit_action: QuerySizeLimitAction,
 
+    /// Parameters clients are allowed to pass to the server, either in the startup packet or with `SET`. If set, all other parameters are removed from the startup packet and rejected with `SET` and `set_config()`.
+    ///
+    /// _Default:_ `[]` (all parameters allowed)
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#allowed_parameters>
+    #[serde(default)]
+    pub allowed_parameters: Vec<String>,
+
+    /// Parameters clients are not allowed to pass to the server, e.g., `session_preload_libraries`. Takes precedence over `allowed_parameters`.
+    ///
+    /// This is a guard rail, not a security boundary. Startup parameters (including `options`) are always checked, but `SET` and `set_config()` are only checked when the query parser looks at them, so not with `query_parser = "off"`. `set_config()` calls with arguments that aren't constants, and parameters changed from inside functions or with `ALTER ROLE ... SET`, are not checked. Use Postgres permissions to enforce it.
+    ///
+    /// _Default:_ `[]`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#denied_parameters>
+    #[serde(default)]
+    pub denied_parameters: Vec<String>,
+
+    /// Action to take when a client tries to `SET` a parameter that isn't allowed.
+    ///
+    /// _Default:_ `error`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#denied_parameter_action>
+    #[serde(default = "General::denied_parameter_action")]
+    pub denied_parameter_action: DeniedParameterAction,
+
+    /// Override the `server_version` reported to clients when they connect. Useful when shards run different Postgres versions and clients (e.g., ORMs) change their behavior depending on the version.
+    ///
+    /// _Default:_ `None` (use the version reported by the server)
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#server_version>
+    #[serde(default = "General::server_version")]
+    pub server_version: Option<String>,
+
     /// The port used for the OpenMetrics HTTP endpoint.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#openmetrics_port>
@@ -370,6 +530,30 @@ pub struct General {
     #[serde(default = "General::prepared_statements_limit")]
     pub prepared_statements_limit: usize,
 
+    /// What to do when a prepared statement fails because the schema of a table it references changed, e.g. `cached plan must not change result type`. Set to `retry` to prepare the statement again and retry it once, if it's safe to do so.
+    ///
+    /// _Default:_ `error`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#prepared_statements_schema_change>
+    #[serde(default = "General::prepared_statements_schema_change")]
+    pub prepared_statements_schema_change: PreparedStatementsSchemaChange,
+
+    /// Maximum number of prepared statements a single client can have open at the same time. Protects PgDog from clients that never close their prepared statements.
+    ///
+    /// _Default:_ `None` (unlimited)
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#prepared_statements_client_limit>
+    #[serde(default = "General::prepared_statements_client_limit")]
+    pub prepared_statements_client_limit: Option<usize>,
+
+    /// Action to take when a client prepares more statements than `prepared_statements_client_limit` allows.
+    ///
+    /// _Default:_ `evict`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#prepared_statements_client_limit_action>
+    #[serde(default = "General::prepared_statements_client_limit_action")]
+    pub prepared_statements_client_limit_action: PreparedStatementsClientLimitAction,
+
     /// Limit on the number of statements saved in the statement cache used to accelerate query parsing.
     ///
     /// _Default:_ `50000`
@@ -378,6 +562,12 @@ pub struct General {
     #[serde(default = "General::query_cache_limit")]
     pub query_cache_limit: usize,
 
+    /// How long (in milliseconds) statements stay in the statement cache before they are parsed again. If not set, statements are kept until evicted by `query_cache_limit`.
+    ///
+    /// _Default:_ `None` (disabled)
+    #[serde(default = "General::default_query_cache_ttl")]
+    pub query_cache_ttl: Option<u64>,
+
     /// Toggle automatic creation of connection pools given the user name, database and password.
     ///
     /// _Default:_ `disabled`
@@ -410,6 +600,15 @@ pub struct General {
     #[serde(default = "General::default_connect_attempt_delay")]
     pub connect_attempt_delay: u64,
 
+    /// Maximum number of server connections PgDog will be opening at the same time, across all pools.
+    /// Prevents a reconnect storm, e.g. after all databases restart, from overwhelming them. Set to `0` for no limit.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#max_concurrent_connects>
+    #[serde(default = "General::max_concurrent_connects")]
+    pub max_concurrent_connects: usize,
+
     /// Maximum amount of time to wait for a Postgres query to finish executing.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#query_timeout>
@@ -424,6 +623,14 @@ pub struct General {
     #[serde(default = "General::checkout_timeout")]
     pub checkout_timeout: u64,
 
+    /// Maximum amount of time a client is allowed to wait when it exceeds its `query_rate_limit` or `transaction_rate_limit`. Set to `0` to reject over-limit requests immediately.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#rate_limit_timeout>
+    #[serde(default = "General::rate_limit_timeout")]
+    pub rate_limit_timeout: u64,
+
     /// Maximum amount of time new clients have to complete authentication.
     ///
     /// _Default:_ `60000`
@@ -432,6 +639,22 @@ pub struct General {
     #[serde(default = "General::client_login_timeout")]
     pub client_login_timeout: u64,
 
+    /// Quarantine a client address after its connections were closed this many times for sending malformed messages or violating the protocol. Quarantined addresses can't connect until `client_quarantine_duration` expires. Set to `0` to disable.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#client_quarantine_errors>
+    #[serde(default = "General::client_quarantine_errors")]
+    pub client_quarantine_errors: usize,
+
+    /// How long a quarantined client address can't connect, in milliseconds. Errors older than this don't count towards `client_quarantine_errors`.
+    ///
+    /// _Default:_ `60000`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#client_quarantine_duration>
+    #[serde(default = "General::client_quarantine_duration")]
+    pub client_quarantine_duration: u64,
+
     /// Enable the query parser in single-shard deployments and record its decisions.
     ///
     /// _Default:_ `false`
@@ -455,11 +678,20 @@ pub struct General {
     pub client_idle_timeout: u64,
 
     /// Close client connections that have been idle inside a transaction for this amount of time.
+    /// Enforced by PgDog, independently of `idle_in_transaction_session_timeout` set on the server;
+    /// the server transaction is rolled back and the connection returned to the pool.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#client_idle_in_transaction_timeout>
     #[serde(default = "General::default_client_idle_in_transaction_timeout")]
     pub client_idle_in_transaction_timeout: u64,
 
+    /// Close client connections with a transaction open for longer than this amount of time, including time spent running queries.
+    /// The server transaction is rolled back and the connection returned to the pool.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#max_transaction_duration>
+    #[serde(default = "General::default_max_transaction_duration")]
+    pub max_transaction_duration: u64,
+
     /// Maximum amount of time a server connection is allowed to exist.
     ///
     /// _Default:_ `86400000`
@@ -504,16 +736,96 @@ pub struct General {
     #[serde(default)]
     pub auth_type: AuthType,
 
+    /// Kerberos realms clients authenticating with `gssapi` can belong to, e.g., `EXAMPLE.COM`. The realm is removed from the client principal, and the rest must match the user name. Principals from other realms are rejected.
+    ///
+    /// _Default:_ `[]`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#gssapi_realms>
+    #[serde(default)]
+    pub gssapi_realms: Vec<String>,
+
     /// Disable cross-shard queries globally. When enabled, queries touching more than one shard are rejected.
     #[serde(default)]
     pub cross_shard_disabled: bool,
 
+    /// Synchronize snapshots between shards in cross-shard read-only transactions (`BEGIN READ ONLY`). The snapshot is exported from the first shard and imported on the others, and the transaction runs with `REPEATABLE READ` isolation. `SERIALIZABLE` transactions are rejected.
+    ///
+    /// **Note:** Postgres can only import a snapshot exported from the same database, so this requires all shards to live in one database, e.g., when sharding by schema.
+    ///
+    /// _Default:_ `false`
+    #[serde(default)]
+    pub cross_shard_snapshot: bool,
+
+    /// Reject statements inside a transaction that would go to a different shard than the one the transaction is already using, or to more than one shard. Transactions stay on a single shard and don't need two-phase commit.
+    ///
+    /// _Default:_ `false`
+    #[serde(default = "General::cross_shard_transactions_disabled")]
+    pub cross_shard_transactions_disabled: bool,
+
+    /// Run `TRUNCATE` statements that go to more than one shard inside a transaction on each shard. The table is truncated on all shards or, if any of them fails, on none of them.
+    ///
+    /// _Default:_ `false`
+    #[serde(default = "General::atomic_truncate")]
+    pub atomic_truncate: bool,
+
+    /// Reject `INSERT`, `UPDATE` and `DELETE` statements on sharded tables that don't specify the sharding key, instead of sending them to all shards.
+    ///
+    /// _Default:_ `false`
+    #[serde(default = "General::require_sharding_key")]
+    pub require_sharding_key: bool,
+
+    /// How to route simple queries containing more than one statement. By default, they are routed using the first statement; `same_shard` requires all statements to target the same shard and `reject` returns an error instead. Multi-statement `SET` commands and session mode are not affected.
+    ///
+    /// _Default:_ `first_statement`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#multi_statement_queries>
+    #[serde(default = "General::multi_statement_queries")]
+    pub multi_statement_queries: MultiStatementQueries,
+
+    /// How to route statements the query router doesn't recognize, e.g. syntax added in a newer version of PostgreSQL. By default, they are sent to all shards like DDL; `primary` sends them to the first shard only and `reject` returns an error instead.
+    ///
+    /// _Default:_ `broadcast`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#unknown_statements>
+    #[serde(default = "General::unknown_statements")]
+    pub unknown_statements: UnknownStatements,
+
+    /// How to route queries the query parser fails to parse, e.g. valid syntax added in a newer version of PostgreSQL than the parser supports. By default, the client receives a syntax error; `primary` sends them to the primary of the first shard and `round_robin` to the primary of any shard instead.
+    ///
+    /// _Default:_ `reject`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#parse_failures>
+    #[serde(default = "General::parse_failures")]
+    pub parse_failures: ParseFailures,
+
+    /// Request binary results from Postgres, which are smaller and faster to parse for numeric and timestamp-heavy queries. Currently, this only applies to queries where the client also requests all results in binary format, which are passed through as-is.
+    ///
+    /// _Default:_ `false`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#backend_binary_results>
+    #[serde(default = "General::backend_binary_results")]
+    pub backend_binary_results: bool,
+
+    /// Name of a session parameter, e.g. `app.current_tenant`, whose value is used as the sharding key for all queries in the session, just like `pgdog.sharding_key`. `SET LOCAL` and `RESET` are supported. The parameter is still sent to the server, so it can be used by row-level security policies.
+    ///
+    /// _Default:_ none
+    #[serde(default = "General::sharding_key_parameter")]
+    pub sharding_key_parameter: Option<String>,
+
     /// Overrides the TTL set on DNS records received from DNS servers.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#dns_ttl>
     #[serde(default)]
     pub dns_ttl: Option<u64>,
 
+    /// How often to resolve SRV records of databases with `srv` enabled again, in milliseconds. Hosts and ports of existing shards are updated when the records change; shards are never added. Set to `0` to stop resolving records once all their shards were found.
+    ///
+    /// _Default:_ `30000`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#srv_refresh_interval>
+    #[serde(default = "General::default_srv_refresh_interval")]
+    pub srv_refresh_interval: u64,
+
     /// Enables support for pub/sub and configures the size of the background task queue.
     ///
     /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#pub_sub_channel_size>
@@ -552,6 +864,32 @@ pub struct General {
     #[serde(default = "General::log_disconnections")]
     pub log_disconnections: bool,
 
+    /// If enabled, send a `NOTICE` to the client explaining notable routing decisions, e.g. a query sent to all shards, a shard picked by a plugin, or a read sent to the primary because all replicas are banned. Useful for debugging during development.
+    ///
+    /// _Default:_ `false`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#routing_notices>
+    #[serde(default = "General::routing_notices")]
+    pub routing_notices: bool,
+
+    /// If enabled, identical reads executed concurrently on the same shard are sent to the database only once, and the result is returned to all clients that requested it. Reduces database load when many clients run the same query at the same time, e.g. after a cache expires.
+    ///
+    /// _Default:_ `false`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#coalesce_reads>
+    #[serde(default = "General::coalesce_reads")]
+    pub coalesce_reads: bool,
+
+    /// If enabled, simple protocol queries sent to the database are prefixed with a comment identifying the client and the shard, e.g. `/* pgdog: client=10.0.0.1:51234 shard=0 */`, so queries in `pg_stat_activity` can be traced back to the client that sent them. Routing decisions are made before the comment is added.
+    ///
+    /// **Note:** This changes the query text seen by the database, so statistics like `pg_stat_statements` will see a different query for each client.
+    ///
+    /// _Default:_ `false`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#query_labels>
+    #[serde(default = "General::query_labels")]
+    pub query_labels: bool,
+
     /// Window, in milliseconds, over which to deduplicate identical log messages. Set to `0` to disable throttling.
     ///
     /// **Note:** When enabled, identical messages (same level, target, and body) that exceed `log_dedup_threshold` within this window are suppressed and replaced with a single summary line at the end of the window.
@@ -588,7 +926,7 @@ pub struct General {
 
     /// Directory where the two-phase commit write-ahead log is stored.
     ///
-    /// **Note:** This setting cannot be changed at runtime. PgDog acquires an exclusive `flock` on `<dir>/.lock` at startup. If the directory cannot be created or written to, or another PgDog process already holds the lock, the WAL is disabled and a warning is logged: 2PC will continue to function but will not be durable across restarts.
+    /// **Note:** This setting cannot be changed at runtime. PgDog acquires an exclusive `flock` on `<dir>/.lock` at startup. If the directory cannot be created or written to, or another PgDog process already holds the lock, the WAL is disabled and a warning is logged: 2PC will continue to function but will not be durable across restarts. Prepared transactions left on the shards by a previous run are only rolled back at startup when the WAL is enabled; without it, they must be resolved manually with `ROLLBACK PREPARED`.
     ///
     /// _Default:_ `./pgdog_wal`
     ///
@@ -652,6 +990,22 @@ pub struct General {
     #[serde(default = "General::client_connection_recovery")]
     pub client_connection_recovery: ConnectionRecovery,
 
+    /// Order in which clients waiting for a server connection are given one. Set to `fifo` to serve clients in the order they asked for a connection, for more predictable tail latency.
+    ///
+    /// _Default:_ `barging`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#checkout_fairness>
+    #[serde(default = "General::checkout_fairness")]
+    pub checkout_fairness: CheckoutFairness,
+
+    /// What to do with temporary tables created in transaction mode, where the next transaction can use a different server connection that doesn't have them. Set to `pin` to keep the client on the connection that created the table until it disconnects, or `reject` to return an error instead.
+    ///
+    /// _Default:_ `allow`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#temporary_tables>
+    #[serde(default = "General::temporary_tables")]
+    pub temporary_tables: TemporaryTables,
+
     /// How frequently to run the replication delay check.
     ///
     /// _Default:_ `5000`
@@ -710,6 +1064,30 @@ pub struct General {
     #[serde(default = "General::resharding_parallel_copies")]
     pub resharding_parallel_copies: usize,
 
+    /// Maximum number of tables copied at the same time during resharding, across all shards and replicas. Use it to limit the load on the source database. `0` means no limit other than `resharding_parallel_copies`.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#resharding_max_parallel_copies>
+    #[serde(default = "General::resharding_max_parallel_copies")]
+    pub resharding_max_parallel_copies: usize,
+
+    /// Number of rows sharded and sent to the destination shards at a time during resharding `COPY`. Larger batches use more memory but fewer writes.
+    ///
+    /// _Default:_ `3`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#resharding_copy_batch_size>
+    #[serde(default = "General::resharding_copy_batch_size")]
+    pub resharding_copy_batch_size: usize,
+
+    /// Number of rows copied from a table between checkpoints during resharding `COPY`. If the copy is interrupted, it resumes from the last checkpoint instead of copying the table again. Set to `0` to copy each table in one go.
+    ///
+    /// _Default:_ `1000000`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#resharding_copy_checkpoint_rows>
+    #[serde(default = "General::resharding_copy_checkpoint_rows")]
+    pub resharding_copy_checkpoint_rows: usize,
+
     /// Maximum number of retries for a failed table copy during resharding (per-table).
     /// Retries use exponential backoff starting at `resharding_copy_retry_min_delay`.
     /// _Default:_ `5`
@@ -751,7 +1129,7 @@ pub struct General {
     #[serde(default = "General::reload_schema_on_ddl")]
     pub reload_schema_on_ddl: bool,
 
-    /// Controls whether PgDog loads the database schema at startup for query routing.
+    /// Controls whether PgDog loads the database schema at startup for query routing. The schema is also used to check that hash partitions of sharded tables match the shard count, so that check doesn't run when this is `off`.
     ///
     /// _Default:_ `auto`
     ///
@@ -759,6 +1137,14 @@ pub struct General {
     #[serde(default = "General::load_schema")]
     pub load_schema: LoadSchema,
 
+    /// What to do when `search_path` lists schemas that are mapped to different shards: use the first one, return an error, or send the query to all of their shards.
+    ///
+    /// _Default:_ `first`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#search_path_conflict>
+    #[serde(default = "General::search_path_conflict")]
+    pub search_path_conflict: SearchPathConflict,
+
     /// Replication lag threshold (in bytes) at which PgDog will pause traffic automatically during a traffic cutover.
     ///
     /// _Default:_ `1000000`
@@ -816,19 +1202,28 @@ impl Default for General {
             workers: Self::workers(),
             default_pool_size: Self::default_pool_size(),
             min_pool_size: Self::min_pool_size(),
+            reserve_pool_size: Self::reserve_pool_size(),
             pooler_mode: Self::pooler_mode(),
             healthcheck_interval: Self::healthcheck_interval(),
             idle_healthcheck_interval: Self::idle_healthcheck_interval(),
             idle_healthcheck_delay: Self::idle_healthcheck_delay(),
+            test_on_checkout_after_idle: Self::test_on_checkout_after_idle(),
+            idle_ping_interval: Self::idle_ping_interval(),
             healthcheck_timeout: Self::healthcheck_timeout(),
             healthcheck_port: Self::healthcheck_port(),
+            healthcheck_host: Self::healthcheck_host(),
             ban_timeout: Self::ban_timeout(),
+            ban_timeout_max: Self::ban_timeout_max(),
+            ban_backoff_window: Self::ban_backoff_window(),
             ban_replica_lag: Self::ban_replica_lag(),
             ban_replica_lag_bytes: Self::ban_replica_lag_bytes(),
             rollback_timeout: Self::rollback_timeout(),
             load_balancing_strategy: Self::load_balancing_strategy(),
             read_write_strategy: Self::read_write_strategy(),
             read_write_split: Self::read_write_split(),
+            analytics_query_cost: Self::analytics_query_cost(),
+            primary_reads_after_connect: Self::primary_reads_after_connect(),
+            primary_reads_after_connect_window: Self::primary_reads_after_connect_window(),
             tls_certificate: Self::tls_certificate(),
             tls_private_key: Self::tls_private_key(),
             tls_client_required: bool::default(),
@@ -841,10 +1236,16 @@ impl Default for General {
             broadcast_port: Self::broadcast_port(),
             query_log: Self::query_log(),
             query_log_stdout: Self::query_log_stdout(),
+            audit_log: Self::audit_log(),
             log_min_duration_parse: Self::default_log_min_duration_parse(),
+            log_slow_queries_ms: Self::default_log_slow_queries_ms(),
             log_query_sample_length: Self::log_query_sample_length(),
             query_size_limit: Self::default_query_size_limit(),
             query_size_limit_action: Self::query_size_limit_action(),
+            allowed_parameters: Vec::default(),
+            denied_parameters: Vec::default(),
+            denied_parameter_action: Self::denied_parameter_action(),
+            server_version: Self::server_version(),
             openmetrics_port: Self::openmetrics_port(),
             openmetrics_namespace: Self::openmetrics_namespace(),
             prepared_statements: Self::prepared_statements(),
@@ -853,28 +1254,52 @@ impl Default for General {
             regex_parser_limit: Self::regex_parser_limit(),
             query_parser_engine: QueryParserEngine::default(),
             prepared_statements_limit: Self::prepared_statements_limit(),
+            prepared_statements_schema_change: Self::prepared_statements_schema_change(),
+            prepared_statements_client_limit: Self::prepared_statements_client_limit(),
+            prepared_statements_client_limit_action: Self::prepared_statements_client_limit_action(
+            ),
             query_cache_limit: Self::query_cache_limit(),
+            query_cache_ttl: Self::default_query_cache_ttl(),
             passthrough_auth: Self::default_passthrough_auth(),
             connect_timeout: Self::default_connect_timeout(),
             connect_attempt_delay: Self::default_connect_attempt_delay(),
             connect_attempts: Self::connect_attempts(),
+            max_concurrent_connects: Self::max_concurrent_connects(),
             query_timeout: Self::default_query_timeout(),
             checkout_timeout: Self::checkout_timeout(),
+            rate_limit_timeout: Self::rate_limit_timeout(),
             client_login_timeout: Self::client_login_timeout(),
+            client_quarantine_errors: Self::client_quarantine_errors(),
+            client_quarantine_duration: Self::client_quarantine_duration(),
             dry_run: Self::dry_run(),
             idle_timeout: Self::idle_timeout(),
             client_idle_timeout: Self::default_client_idle_timeout(),
             client_idle_in_transaction_timeout: Self::default_client_idle_in_transaction_timeout(),
+            max_transaction_duration: Self::default_max_transaction_duration(),
             mirror_queue: Self::mirror_queue(),
             mirror_exposure: Self::mirror_exposure(),
             auth_type: Self::auth_type(),
+            gssapi_realms: Vec::default(),
             cross_shard_disabled: Self::cross_shard_disabled(),
+            cross_shard_snapshot: bool::default(),
+            cross_shard_transactions_disabled: Self::cross_shard_transactions_disabled(),
+            atomic_truncate: Self::atomic_truncate(),
+            require_sharding_key: Self::require_sharding_key(),
+            multi_statement_queries: Self::multi_statement_queries(),
+            unknown_statements: Self::unknown_statements(),
+            parse_failures: Self::parse_failures(),
+            backend_binary_results: Self::backend_binary_results(),
+            sharding_key_parameter: Self::sharding_key_parameter(),
             dns_ttl: Self::default_dns_ttl(),
+            srv_refresh_interval: Self::default_srv_refresh_interval(),
             pub_sub_channel_size: Self::pub_sub_channel_size(),
             log_format: Self::log_format(),
             log_level: Self::log_level(),
             log_connections: Self::log_connections(),
             log_disconnections: Self::log_disconnections(),
+            routing_notices: Self::routing_notices(),
+            coalesce_reads: Self::coalesce_reads(),
+            query_labels: Self::query_labels(),
             log_dedup_window: 0,
             log_dedup_threshold: 0,
             two_phase_commit: bool::default(),
@@ -890,6 +1315,8 @@ impl Default for General {
             stats_period: Self::stats_period(),
             connection_recovery: Self::connection_recovery(),
             client_connection_recovery: Self::client_connection_recovery(),
+            checkout_fairness: Self::checkout_fairness(),
+            temporary_tables: Self::temporary_tables(),
             lsn_check_interval: Self::lsn_check_interval(),
             lsn_check_timeout: Self::lsn_check_timeout(),
             lsn_check_delay: Self::lsn_check_delay(),
@@ -898,6 +1325,9 @@ impl Default for General {
             omnisharded_sticky: bool::default(),
             resharding_copy_format: CopyFormat::default(),
             resharding_parallel_copies: Self::resharding_parallel_copies(),
+            resharding_max_parallel_copies: Self::resharding_max_parallel_copies(),
+            resharding_copy_batch_size: Self::resharding_copy_batch_size(),
+            resharding_copy_checkpoint_rows: Self::resharding_copy_checkpoint_rows(),
             resharding_copy_retry_max_attempts: Self::resharding_copy_retry_max_attempts(),
             resharding_copy_retry_min_delay: Self::resharding_copy_retry_min_delay(),
             resharding_replication_retry_max_attempts:
@@ -905,6 +1335,7 @@ impl Default for General {
             resharding_replication_retry_min_delay: Self::resharding_replication_retry_min_delay(),
             reload_schema_on_ddl: Self::reload_schema_on_ddl(),
             load_schema: Self::load_schema(),
+            search_path_conflict: Self::search_path_conflict(),
             cutover_replication_lag_threshold: Self::cutover_replication_lag_threshold(),
             cutover_traffic_stop_threshold: Self::cutover_traffic_stop_threshold(),
             cutover_last_transaction_delay: Self::cutover_last_transaction_delay(),
@@ -974,6 +1405,10 @@ impl General {
         Self::env_or_default("PGDOG_MIN_POOL_SIZE", 1)
     }
 
+    fn reserve_pool_size() -> usize {
+        Self::env_or_default("PGDOG_RESERVE_POOL_SIZE", 0)
+    }
+
     fn healthcheck_interval() -> u64 {
         Self::env_or_default("PGDOG_HEALTHCHECK_INTERVAL", 30_000)
     }
@@ -990,10 +1425,22 @@ impl General {
         Self::env_or_default("PGDOG_IDLE_HEALTHCHECK_DELAY", 5_000)
     }
 
+    fn test_on_checkout_after_idle() -> u64 {
+        Self::env_or_default("PGDOG_TEST_ON_CHECKOUT_AFTER_IDLE", 0)
+    }
+
+    fn idle_ping_interval() -> u64 {
+        Self::env_or_default("PGDOG_IDLE_PING_INTERVAL", 0)
+    }
+
     fn healthcheck_port() -> Option<u16> {
         Self::env_option("PGDOG_HEALTHCHECK_PORT")
     }
 
+    fn healthcheck_host() -> String {
+        Self::env_string_or_default("PGDOG_HEALTHCHECK_HOST", "0.0.0.0")
+    }
+
     pub fn regex_parser_limit() -> usize {
         1_000
     }
@@ -1005,6 +1452,17 @@ impl General {
         )
     }
 
+    fn ban_timeout_max() -> u64 {
+        Self::env_or_default("PGDOG_BAN_TIMEOUT_MAX", 0)
+    }
+
+    fn ban_backoff_window() -> u64 {
+        Self::env_or_default(
+            "PGDOG_BAN_BACKOFF_WINDOW",
+            Duration::from_secs(600).as_millis() as u64,
+        )
+    }
+
     fn ban_replica_lag() -> u64 {
         // Use i64::MAX to ensure TOML serialization compatibility (TOML only supports i64)
         Self::env_or_default("PGDOG_BAN_REPLICA_LAG", i64::MAX as u64)
@@ -1076,6 +1534,17 @@ impl General {
         )
     }
 
+    fn client_quarantine_errors() -> usize {
+        Self::env_or_default("PGDOG_CLIENT_QUARANTINE_ERRORS", 0)
+    }
+
+    fn client_quarantine_duration() -> u64 {
+        Self::env_or_default(
+            "PGDOG_CLIENT_QUARANTINE_DURATION",
+            Duration::from_secs(60).as_millis() as u64,
+        )
+    }
+
     fn default_client_idle_timeout() -> u64 {
         Self::env_or_default(
             "PGDOG_CLIENT_IDLE_TIMEOUT",
@@ -1090,6 +1559,13 @@ impl General {
         )
     }
 
+    fn default_max_transaction_duration() -> u64 {
+        Self::env_or_default(
+            "PGDOG_MAX_TRANSACTION_DURATION",
+            crate::MAX_DURATION.as_millis() as u64,
+        )
+    }
+
     fn default_query_timeout() -> u64 {
         Self::env_or_default(
             "PGDOG_QUERY_TIMEOUT",
@@ -1105,6 +1581,10 @@ impl General {
         self.dns_ttl.map(Duration::from_millis)
     }
 
+    pub fn srv_refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.srv_refresh_interval)
+    }
+
     pub fn client_idle_timeout(&self) -> Duration {
         Duration::from_millis(self.client_idle_timeout)
     }
@@ -1117,6 +1597,10 @@ impl General {
         Duration::from_millis(self.client_idle_in_transaction_timeout)
     }
 
+    pub fn max_transaction_duration(&self) -> Duration {
+        Duration::from_millis(self.max_transaction_duration)
+    }
+
     fn load_balancing_strategy() -> LoadBalancingStrategy {
         Self::env_enum_or_default("PGDOG_LOAD_BALANCING_STRATEGY")
     }
@@ -1140,6 +1624,18 @@ impl General {
         1
     }
 
+    fn resharding_max_parallel_copies() -> usize {
+        0
+    }
+
+    fn resharding_copy_batch_size() -> usize {
+        3
+    }
+
+    fn resharding_copy_checkpoint_rows() -> usize {
+        1_000_000
+    }
+
     fn resharding_copy_retry_max_attempts() -> usize {
         5
     }
@@ -1172,6 +1668,10 @@ impl General {
         Self::env_or_default("PGDOG_CONNECT_ATTEMPTS", 1)
     }
 
+    fn max_concurrent_connects() -> usize {
+        Self::env_or_default("PGDOG_MAX_CONCURRENT_CONNECTS", 0)
+    }
+
     fn pooler_mode() -> PoolerMode {
         Self::env_enum_or_default("PGDOG_POOLER_MODE")
     }
@@ -1203,6 +1703,18 @@ impl General {
         Self::env_enum_or_default("PGDOG_READ_WRITE_SPLIT")
     }
 
+    fn analytics_query_cost() -> usize {
+        Self::env_or_default("PGDOG_ANALYTICS_QUERY_COST", 0)
+    }
+
+    fn primary_reads_after_connect() -> usize {
+        Self::env_or_default("PGDOG_PRIMARY_READS_AFTER_CONNECT", 0)
+    }
+
+    fn primary_reads_after_connect_window() -> u64 {
+        Self::env_or_default("PGDOG_PRIMARY_READS_AFTER_CONNECT_WINDOW", 0)
+    }
+
     fn prepared_statements() -> PreparedStatements {
         Self::env_enum_or_default("PGDOG_PREPARED_STATEMENTS")
     }
@@ -1239,6 +1751,10 @@ impl General {
         Self::env_bool_or_default("PGDOG_QUERY_LOG_STDOUT", false)
     }
 
+    fn audit_log() -> Option<PathBuf> {
+        Self::env_option_string("PGDOG_AUDIT_LOG").map(PathBuf::from)
+    }
+
     fn default_log_min_duration_parse() -> Option<u64> {
         Self::env_option("PGDOG_LOG_MIN_DURATION_PARSE")
     }
@@ -1247,6 +1763,14 @@ impl General {
         self.log_min_duration_parse.map(Duration::from_millis)
     }
 
+    fn default_log_slow_queries_ms() -> Option<u64> {
+        Self::env_option("PGDOG_LOG_SLOW_QUERIES_MS")
+    }
+
+    pub fn log_slow_queries(&self) -> Option<Duration> {
+        self.log_slow_queries_ms.map(Duration::from_millis)
+    }
+
     pub fn log_query_sample_length() -> usize {
         Self::env_or_default("PGDOG_LOG_QUERY_SAMPLE_LENGTH", 1000)
     }
@@ -1259,6 +1783,14 @@ impl General {
         Self::env_enum_or_default("PGDOG_QUERY_SIZE_LIMIT_ACTION")
     }
 
+    fn denied_parameter_action() -> DeniedParameterAction {
+        Self::env_enum_or_default("PGDOG_DENIED_PARAMETER_ACTION")
+    }
+
+    fn server_version() -> Option<String> {
+        Self::env_option_string("PGDOG_SERVER_VERSION")
+    }
+
     pub fn openmetrics_port() -> Option<u16> {
         Self::env_option("PGDOG_OPENMETRICS_PORT")
     }
@@ -1267,6 +1799,14 @@ impl General {
         Self::env_option_string("PGDOG_OPENMETRICS_NAMESPACE")
     }
 
+    fn sharding_key_parameter() -> Option<String> {
+        Self::env_option_string("PGDOG_SHARDING_KEY_PARAMETER")
+    }
+
+    fn default_srv_refresh_interval() -> u64 {
+        Self::env_or_default("PGDOG_SRV_REFRESH_INTERVAL", 30_000)
+    }
+
     fn default_dns_ttl() -> Option<u64> {
         Self::env_option("PGDOG_DNS_TTL")
     }
@@ -1283,6 +1823,34 @@ impl General {
         Self::env_bool_or_default("PGDOG_CROSS_SHARD_DISABLED", false)
     }
 
+    pub fn cross_shard_transactions_disabled() -> bool {
+        Self::env_bool_or_default("PGDOG_CROSS_SHARD_TRANSACTIONS_DISABLED", false)
+    }
+
+    pub fn atomic_truncate() -> bool {
+        Self::env_bool_or_default("PGDOG_ATOMIC_TRUNCATE", false)
+    }
+
+    pub fn require_sharding_key() -> bool {
+        Self::env_bool_or_default("PGDOG_REQUIRE_SHARDING_KEY", false)
+    }
+
+    pub fn backend_binary_results() -> bool {
+        Self::env_bool_or_default("PGDOG_BACKEND_BINARY_RESULTS", false)
+    }
+
+    pub fn multi_statement_queries() -> MultiStatementQueries {
+        Self::env_enum_or_default("PGDOG_MULTI_STATEMENT_QUERIES")
+    }
+
+    pub fn unknown_statements() -> UnknownStatements {
+        Self::env_enum_or_default("PGDOG_UNKNOWN_STATEMENTS")
+    }
+
+    pub fn parse_failures() -> ParseFailures {
+        Self::env_enum_or_default("PGDOG_PARSE_FAILURES")
+    }
+
     pub fn broadcast_address() -> Option<Ipv4Addr> {
         Self::env_option("PGDOG_BROADCAST_ADDRESS")
     }
@@ -1309,6 +1877,10 @@ impl General {
         Self::env_enum_or_default("PGDOG_LOAD_SCHEMA")
     }
 
+    fn search_path_conflict() -> SearchPathConflict {
+        Self::env_enum_or_default("PGDOG_SEARCH_PATH_CONFLICT")
+    }
+
     pub fn mirror_queue() -> usize {
         Self::env_or_default("PGDOG_MIRROR_QUEUE", 128)
     }
@@ -1321,10 +1893,30 @@ impl General {
         Self::env_or_default("PGDOG_PREPARED_STATEMENTS_LIMIT", i64::MAX as usize)
     }
 
+    pub fn prepared_statements_schema_change() -> PreparedStatementsSchemaChange {
+        Self::env_enum_or_default("PGDOG_PREPARED_STATEMENTS_SCHEMA_CHANGE")
+    }
+
+    fn prepared_statements_client_limit() -> Option<usize> {
+        Self::env_option("PGDOG_PREPARED_STATEMENTS_CLIENT_LIMIT")
+    }
+
+    fn prepared_statements_client_limit_action() -> PreparedStatementsClientLimitAction {
+        Self::env_enum_or_default("PGDOG_PREPARED_STATEMENTS_CLIENT_LIMIT_ACTION")
+    }
+
     pub fn query_cache_limit() -> usize {
         Self::env_or_default("PGDOG_QUERY_CACHE_LIMIT", 1_000)
     }
 
+    fn default_query_cache_ttl() -> Option<u64> {
+        Self::env_option("PGDOG_QUERY_CACHE_TTL")
+    }
+
+    pub fn query_cache_ttl(&self) -> Option<Duration> {
+        self.query_cache_ttl.map(Duration::from_millis)
+    }
+
     pub fn log_format() -> LogFormat {
         Self::env_enum_or_default("PGDOG_LOG_FORMAT")
     }
@@ -1341,6 +1933,18 @@ impl General {
         Self::env_bool_or_default("PGDOG_LOG_DISCONNECTIONS", true)
     }
 
+    pub fn routing_notices() -> bool {
+        Self::env_bool_or_default("PGDOG_ROUTING_NOTICES", false)
+    }
+
+    pub fn coalesce_reads() -> bool {
+        Self::env_bool_or_default("PGDOG_COALESCE_READS", false)
+    }
+
+    pub fn query_labels() -> bool {
+        Self::env_bool_or_default("PGDOG_QUERY_LABELS", false)
+    }
+
     pub fn expanded_explain() -> bool {
         Self::env_bool_or_default("PGDOG_EXPANDED_EXPLAIN", false)
     }
@@ -1356,6 +1960,10 @@ impl General {
         Self::env_or_default("PGDOG_SERVER_LIFETIME_JITTER", 0)
     }
 
+    fn rate_limit_timeout() -> u64 {
+        Self::env_or_default("PGDOG_RATE_LIMIT_TIMEOUT", 0)
+    }
+
     pub fn connection_recovery() -> ConnectionRecovery {
         Self::env_enum_or_default("PGDOG_CONNECTION_RECOVERY")
     }
@@ -1364,6 +1972,14 @@ impl General {
         Self::env_option("PGDOG_CLIENT_CONNECTION_RECOVERY").unwrap_or(ConnectionRecovery::Drop)
     }
 
+    pub fn checkout_fairness() -> CheckoutFairness {
+        Self::env_enum_or_default("PGDOG_CHECKOUT_FAIRNESS")
+    }
+
+    pub fn temporary_tables() -> TemporaryTables {
+        Self::env_enum_or_default("PGDOG_TEMPORARY_TABLES")
+    }
+
     fn stats_period() -> u64 {
         Self::env_or_default("PGDOG_STATS_PERIOD", 15_000)
     }
@@ -1427,6 +2043,22 @@ impl General {
             None
         }
     }
+
+    /// Parameters clients can pass to the server are restricted.
+    pub fn parameters_restricted(&self) -> bool {
+        !self.allowed_parameters.is_empty() || !self.denied_parameters.is_empty()
+    }
+
+    /// Client is allowed to pass this parameter to the server.
+    pub fn parameter_allowed(&self, name: &str) -> bool {
+        let matches = |list: &[String]| list.iter().any(|p| p.eq_ignore_ascii_case(name));
+
+        if matches(&self.denied_parameters) {
+            false
+        } else {
+            self.allowed_parameters.is_empty() || matches(&self.allowed_parameters)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1457,6 +2089,22 @@ mod tests {
         assert_eq!(g.frontend_query_size_limit_block(), None);
     }
 
+    #[test]
+    fn test_parameter_allowed() {
+        let mut g = General::default();
+        assert!(g.parameter_allowed("session_preload_libraries"));
+
+        g.denied_parameters = vec!["session_preload_libraries".into()];
+        assert!(!g.parameter_allowed("session_preload_libraries"));
+        assert!(!g.parameter_allowed("SESSION_PRELOAD_LIBRARIES"));
+        assert!(g.parameter_allowed("timezone"));
+
+        g.allowed_parameters = vec!["TimeZone".into(), "session_preload_libraries".into()];
+        assert!(g.parameter_allowed("timezone"));
+        assert!(!g.parameter_allowed("session_preload_libraries"));
+        assert!(!g.parameter_allowed("statement_timeout"));
+    }
+
     #[test]
     fn test_env_query_size_limit() {
         let _guard = set_env_var("PGDOG_QUERY_SIZE_LIMIT", "4096");
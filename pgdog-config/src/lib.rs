@@ -26,10 +26,14 @@ pub use auth::{AuthType, PassthroughAuth};
 pub use core::{Config, ConfigAndUsers};
 pub use data_types::*;
 pub use database::{
-    Database, EnumeratedDatabase, LoadBalancingStrategy, ReadWriteSplit, ReadWriteStrategy, Role,
+    Database, EnumeratedDatabase, IsolationLevel, LoadBalancingStrategy, ReadWriteSplit,
+    ReadWriteStrategy, Role, Workload,
 };
 pub use error::Error;
-pub use general::{General, LogFormat, QuerySizeLimitAction};
+pub use general::{
+    DeniedParameterAction, General, LogFormat, PreparedStatementsClientLimitAction,
+    QuerySizeLimitAction,
+};
 pub use memory::*;
 pub use networking::{MultiTenant, Tcp, TlsVerifyMode};
 pub use otel::Otel;
@@ -16,6 +16,7 @@ pub struct Memory {
     pub net_buffer: usize,
 
     /// Size of the message buffer in bytes. This buffer is used for assembling PostgreSQL protocol messages.
+    /// Messages larger than this cause the buffer to be reallocated, which is counted by the `buffer_reallocs_total` metric.
     ///
     /// _Default:_ `4096`
     ///
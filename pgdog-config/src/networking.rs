@@ -156,4 +156,9 @@ impl Tcp {
 pub struct MultiTenant {
     /// Name of the column carrying the tenant identifier used to route queries.
     pub column: String,
+    /// Add a filter on the tenant column to reads that don't have one, instead of rejecting them. The tenant is the session's `pgdog.sharding_key` or the parameter set in [`sharding_key_parameter`](https://docs.pgdog.dev/configuration/pgdog.toml/general/#sharding_key_parameter). Only single-statement queries sent with the simple protocol are filtered. Prepared statements and other extended protocol queries are still rejected.
+    ///
+    /// _Default:_ `false`
+    #[serde(default)]
+    pub filter: bool,
 }
@@ -129,6 +129,67 @@ impl ConnectionRecovery {
     }
 }
 
+/// Order in which clients waiting for a server connection are given one.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#checkout_fairness>
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckoutFairness {
+    /// Idle connections are given to whoever asks for one first, even if other clients are already waiting (default).
+    #[default]
+    Barging,
+    /// Clients are given connections in the order they asked for them.
+    Fifo,
+}
+
+impl FromStr for CheckoutFairness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "barging" => Ok(Self::Barging),
+            "fifo" => Ok(Self::Fifo),
+            _ => Err(format!("Invalid checkout fairness: {}", s)),
+        }
+    }
+}
+
+/// What to do when a prepared statement fails because
+/// the schema of a table it references changed.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#prepared_statements_schema_change>
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PreparedStatementsSchemaChange {
+    /// Return the error to the client (default).
+    #[default]
+    Error,
+    /// Prepare the statement again and retry it once before returning the error.
+    Retry,
+}
+
+impl PreparedStatementsSchemaChange {
+    pub fn retry(&self) -> bool {
+        matches!(self, Self::Retry)
+    }
+}
+
+impl FromStr for PreparedStatementsSchemaChange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "retry" => Ok(Self::Retry),
+            _ => Err(format!("Invalid prepared statements schema change: {}", s)),
+        }
+    }
+}
+
 impl FromStr for ConnectionRecovery {
     type Err = String;
 
@@ -141,3 +202,34 @@ impl FromStr for ConnectionRecovery {
         }
     }
 }
+
+/// What to do with temporary tables created in transaction mode. They only exist
+/// on the connection that created them, which other transactions might not get.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#temporary_tables>
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporaryTables {
+    /// Create them like any other table (default).
+    #[default]
+    Allow,
+    /// Keep the client on the same connection until it disconnects.
+    Pin,
+    /// Return an error to the client.
+    Reject,
+}
+
+impl FromStr for TemporaryTables {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow" => Ok(Self::Allow),
+            "pin" => Ok(Self::Pin),
+            "reject" => Ok(Self::Reject),
+            _ => Err(format!("Invalid temporary tables: {}", s)),
+        }
+    }
+}
@@ -160,6 +160,12 @@ pub struct Mirroring {
     /// What kind of statements to replicate.
     #[serde(default)]
     pub level: MirroringLevel,
+
+    /// Compare a checksum of the rows returned by the mirror with the rows returned by the source database. Mismatches are counted in the mirror stats.
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/mirroring/#checksum>
+    #[serde(default)]
+    pub checksum: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, Copy)]
@@ -206,6 +212,7 @@ impl FromStr for Mirroring {
         let mut queue_length = None;
         let mut exposure = None;
         let mut level = MirroringLevel::default();
+        let mut checksum = false;
 
         for pair in s.split('&') {
             let parts: Vec<&str> = pair.split('=').collect();
@@ -231,6 +238,11 @@ impl FromStr for Mirroring {
                     );
                 }
                 "level" => level = MirroringLevel::from_str(parts[1]).unwrap_or_default(),
+                "checksum" => {
+                    checksum = parts[1]
+                        .parse::<bool>()
+                        .map_err(|_| format!("Invalid checksum: {}", parts[1]))?;
+                }
                 _ => return Err(format!("Unknown parameter: {}", parts[0])),
             }
         }
@@ -244,6 +256,7 @@ impl FromStr for Mirroring {
             queue_length,
             exposure,
             level,
+            checksum,
         })
     }
 }
@@ -257,4 +270,6 @@ pub struct MirrorConfig {
     pub exposure: f32,
     /// What kind of statements to mirror.
     pub level: MirroringLevel,
+    /// Compare result checksums with the source database.
+    pub checksum: bool,
 }
@@ -80,6 +80,14 @@ pub struct ShardedTableConfig {
     #[serde(default)]
     pub hasher: Hasher,
 
+    /// Seed for the `xxhash` hasher. Must match the seed used by the application.
+    ///
+    /// _Default:_ `0`
+    ///
+    /// <https://docs.pgdog.dev/configuration/pgdog.toml/sharded_tables/#hasher_seed>
+    #[serde(default)]
+    pub hasher_seed: u64,
+
     /// Explicit value-to-shard routing rules for the column. When omitted (the
     /// default), PgDog shards by hashing the column value instead. Each entry is
     /// a [`ShardedMappingConfig`]; see it for the list/range/default forms.
@@ -142,14 +150,25 @@ pub enum ShardedMappingConfig {
 /// Hash function used to map a sharding key value to a shard number.
 ///
 /// <https://docs.pgdog.dev/configuration/pgdog.toml/sharded_tables/#hasher>
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, derive_more::Display,
+)]
 #[serde(rename_all = "snake_case")]
+#[display(rename_all = "snake_case")]
 pub enum Hasher {
     /// Uses the same hash function as PostgreSQL's `hashint8` / `hashtext` (default).
     #[default]
     Postgres,
     /// SHA-1 based hashing.
     Sha1,
+    /// Integer value modulo the number of shards, so shard `N` gets all values
+    /// where `value % shards = N`. Sequences are split between shards accordingly
+    /// by `SETUP SCHEMA`. Other data types use the `postgres` hash.
+    Modulo,
+    /// CRC-32 (IEEE), as computed by zlib's `crc32`. Integers and UUIDs are hashed as text.
+    Crc32,
+    /// xxHash64, seeded with `hasher_seed`. Integers and UUIDs are hashed as text.
+    Xxhash,
 }
 
 /// Data type of the sharding column.
@@ -180,6 +199,10 @@ pub enum DataType {
     Vector,
     /// Variable-length text.
     Varchar,
+    /// One-dimensional array of 64-bit integers, e.g. `BIGINT[]`.
+    BigintArray,
+    /// One-dimensional array of text, e.g. `TEXT[]` or `VARCHAR[]`.
+    VarcharArray,
 }
 
 /// Explicit routing rule mapping specific column values or ranges to a shard.
@@ -473,6 +496,88 @@ impl FromStr for SystemCatalogsBehavior {
     }
 }
 
+/// How to route simple queries that contain more than one statement, e.g. `SELECT 1; SELECT 2`.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#multi_statement_queries>
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum MultiStatementQueries {
+    /// Route the whole query using the first statement (default).
+    #[default]
+    FirstStatement,
+    /// Route the whole query to one shard; return an error if the statements target different shards.
+    SameShard,
+    /// Return an error for all multi-statement queries.
+    Reject,
+}
+
+impl FromStr for MultiStatementQueries {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "first_statement" => Self::FirstStatement,
+            "same_shard" => Self::SameShard,
+            "reject" => Self::Reject,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// How to route statements the query router doesn't recognize, e.g. syntax added in a newer version of PostgreSQL.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#unknown_statements>
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum UnknownStatements {
+    /// Send the statement to all shards, like DDL (default).
+    #[default]
+    Broadcast,
+    /// Send the statement to the first shard only.
+    Primary,
+    /// Return an error to the client.
+    Reject,
+}
+
+impl FromStr for UnknownStatements {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "broadcast" => Self::Broadcast,
+            "primary" => Self::Primary,
+            "reject" => Self::Reject,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// How to route queries the query parser fails to parse, e.g. valid syntax
+/// added in a newer version of PostgreSQL than the one bundled with the parser.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#parse_failures>
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum ParseFailures {
+    /// Return a syntax error to the client (default).
+    #[default]
+    Reject,
+    /// Send the query to the primary of the first shard.
+    Primary,
+    /// Send the query to the primary of a shard, picked round robin.
+    RoundRobin,
+}
+
+impl FromStr for ParseFailures {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "reject" => Self::Reject,
+            "primary" => Self::Primary,
+            "round_robin" => Self::RoundRobin,
+            _ => return Err(()),
+        })
+    }
+}
+
 /// Format used for `COPY` statements during resharding.
 ///
 /// **Note:** Text format is required when migrating from `INTEGER` to `BIGINT` primary keys during resharding.
@@ -524,6 +629,33 @@ impl FromStr for LoadSchema {
     }
 }
 
+/// What to do when `search_path` lists schemas mapped to different shards.
+///
+/// <https://docs.pgdog.dev/configuration/pgdog.toml/general/#search_path_conflict>
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SearchPathConflict {
+    /// Route to the shard of the first sharded schema in `search_path` (default).
+    #[default]
+    First,
+    /// Return an error for queries routed by `search_path`.
+    Error,
+    /// Send the query to all shards the schemas are mapped to.
+    Broadcast,
+}
+
+impl FromStr for SearchPathConflict {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "first" => Self::First,
+            "error" => Self::Error,
+            "broadcast" => Self::Broadcast,
+            _ => return Err(()),
+        })
+    }
+}
+
 /// Action to take when the cutover timeout is reached during online resharding.
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
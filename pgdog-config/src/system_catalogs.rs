@@ -18,6 +18,13 @@ const CATALOGS: &[&str] = &[
     "pg_range",
     "pg_authid",
     "pg_am",
+    "pg_collation",
+    "pg_description",
+    "pg_inherits",
+    "pg_sequence",
+    "pg_depend",
+    "pg_trigger",
+    "pg_extension",
 ];
 
 static SYSTEM_CATALOGS: Lazy<HashSet<&'static str>> =
@@ -7,6 +7,7 @@ use tracing::warn;
 
 use super::core::Config;
 use super::pooling::PoolerMode;
+use super::sharding::QueryParserLevel;
 use crate::util::random_string;
 use schemars::JsonSchema;
 
@@ -288,6 +289,7 @@ pub struct User {
     pub passwords: Vec<String>,
     /// Passwords hash. Can be used to validate user logins without storing passwords in users.toml.
     /// Server authentication must use RDS IAM or some other passwordless authentication, e.g. trust.
+    /// With passthrough authentication, a `SCRAM-SHA-256$...` verifier is used to check the client's password.
     pub password_hash: Option<String>,
     /// Overrides [`default_pool_size`](https://docs.pgdog.dev/configuration/pgdog.toml/general/) for this user. No more than this many server connections will be open at any given time to serve requests for this connection pool.
     ///
@@ -372,6 +374,12 @@ pub struct User {
     pub schema_admin: bool,
     /// Disable cross-shard queries for this user.
     pub cross_shard_disabled: Option<bool>,
+    /// Overrides [`query_parser`](https://docs.pgdog.dev/configuration/pgdog.toml/general/#query_parser) for this user. Set to `"off"` to skip SQL parsing entirely and send all queries to the primary of a single shard, for workloads that are known to be single-shard and latency-sensitive.
+    ///
+    /// **Note:** Takes priority over the database's `[[query_parsers]]` entry.
+    ///
+    /// <https://docs.pgdog.dev/configuration/users.toml/users/#query_parser>
+    pub query_parser: Option<QueryParserLevel>,
     /// Overrides [`two_phase_commit`](https://docs.pgdog.dev/configuration/pgdog.toml/general/#two_phase_commit) for this user.
     ///
     /// <https://docs.pgdog.dev/configuration/users.toml/users/#two_phase_commit>
@@ -385,6 +393,18 @@ pub struct User {
     /// Maximum random adjustment applied to `server_lifetime` per backend connection (milliseconds).
     /// Overrides the database-level and general-level `server_lifetime_jitter` setting for this user.
     pub server_lifetime_jitter: Option<u64>,
+    /// Maximum number of queries per second this user can send. Overrides the database's `query_rate_limit`.
+    ///
+    /// <https://docs.pgdog.dev/configuration/users.toml/users/#query_rate_limit>
+    pub query_rate_limit: Option<u32>,
+    /// Maximum number of transactions per second this user can start. Overrides the database's `transaction_rate_limit`.
+    ///
+    /// <https://docs.pgdog.dev/configuration/users.toml/users/#transaction_rate_limit>
+    pub transaction_rate_limit: Option<u32>,
+    /// Overrides `filter` in the `[multi_tenant]` section for this user. Takes priority over the database's `multi_tenant_filter`.
+    ///
+    /// <https://docs.pgdog.dev/configuration/users.toml/users/#multi_tenant_filter>
+    pub multi_tenant_filter: Option<bool>,
 }
 
 impl User {
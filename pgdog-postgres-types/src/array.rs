@@ -26,6 +26,11 @@ impl Default for Dimension {
 }
 
 impl Array {
+    /// Array elements.
+    pub fn elements(&self) -> &[Datum] {
+        &self.elements
+    }
+
     /// Decode an array with a known element type OID.
     ///
     /// For binary format, the element OID is also present in the wire header
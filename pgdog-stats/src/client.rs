@@ -21,6 +21,8 @@ pub struct Stats {
     pub queries: usize,
     /// Errors.
     pub errors: usize,
+    /// Queries slower than `log_slow_queries_ms`.
+    pub slow_queries: usize,
     /// Total transaction time.
     pub transaction_time: Duration,
     /// Last transaction time.
@@ -57,6 +59,7 @@ impl Stats {
             transactions_2pc: 0,
             queries: 0,
             errors: 0,
+            slow_queries: 0,
             transaction_time: Duration::from_secs(0),
             last_transaction_time: Duration::from_secs(0),
             query_time: Duration::from_secs(0),
@@ -81,6 +84,7 @@ impl Add for Stats {
             transactions_2pc: self.transactions_2pc.saturating_add(rhs.transactions_2pc),
             queries: self.queries.saturating_add(rhs.queries),
             errors: self.errors.saturating_add(rhs.errors),
+            slow_queries: self.slow_queries.saturating_add(rhs.slow_queries),
             transaction_time: self.transaction_time.saturating_add(rhs.transaction_time),
             last_transaction_time: self.last_transaction_time.max(rhs.last_transaction_time),
             query_time: self.query_time.saturating_add(rhs.query_time),
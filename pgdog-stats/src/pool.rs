@@ -3,7 +3,10 @@ use std::{
     time::Duration,
 };
 
-use pgdog_config::{PoolerMode, PreparedStatements, pooling::ConnectionRecovery};
+use pgdog_config::{
+    IsolationLevel, PoolerMode, PreparedStatements, Workload,
+    pooling::{CheckoutFairness, ConnectionRecovery},
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -63,6 +66,8 @@ pub struct Counts {
     pub writes: usize,
     /// Password attempts.
     pub auth_attempts: usize,
+    /// Number of server connections refused because Postgres reached `max_connections`.
+    pub out_of_connections: usize,
 }
 
 impl Sub for Counts {
@@ -95,6 +100,9 @@ impl Sub for Counts {
             reads: self.reads.saturating_sub(rhs.reads),
             writes: self.writes.saturating_sub(rhs.writes),
             auth_attempts: self.auth_attempts.saturating_sub(rhs.auth_attempts),
+            out_of_connections: self
+                .out_of_connections
+                .saturating_sub(rhs.out_of_connections),
         }
     }
 }
@@ -129,6 +137,9 @@ impl Add for Counts {
             reads: self.reads.saturating_add(rhs.reads),
             writes: self.writes.saturating_add(rhs.writes),
             auth_attempts: self.auth_attempts.saturating_add(rhs.auth_attempts),
+            out_of_connections: self
+                .out_of_connections
+                .saturating_add(rhs.out_of_connections),
         }
     }
 }
@@ -167,6 +178,7 @@ impl Div<usize> for Counts {
             reads: self.reads.checked_div(rhs).unwrap_or(0),
             writes: self.writes.checked_div(rhs).unwrap_or(0),
             auth_attempts: self.auth_attempts.checked_div(rhs).unwrap_or(0),
+            out_of_connections: self.out_of_connections.checked_div(rhs).unwrap_or(0),
         }
     }
 }
@@ -275,6 +287,8 @@ pub struct Config {
     pub min: usize,
     /// Maximum connections allowed in the pool.
     pub max: usize,
+    /// Connections allowed above `max`, only for reserved requests.
+    pub reserve_pool_size: usize,
     /// How long to wait for a connection before giving up.
     pub checkout_timeout: Duration, // ms
     /// Interval duration of DNS cache refresh.
@@ -287,6 +301,8 @@ pub struct Config {
     pub connect_attempts: u64,
     /// How long to wait between connection attempts.
     pub connect_attempt_delay: Duration,
+    /// How many connections can be opened at the same time, across all pools.
+    pub max_concurrent_connects: usize,
     /// How long a connection can be open.
     pub max_age: Duration,
     /// Maximum random adjustment applied to `max_age` per connection.
@@ -304,6 +320,10 @@ pub struct Config {
     pub idle_healthcheck_interval: Duration, // ms
     /// Idle healthcheck delay.
     pub idle_healthcheck_delay: Duration, // ms
+    /// Healthcheck connections idle for longer than this on checkout.
+    pub test_on_checkout_after_idle: Option<Duration>, // ms
+    /// Ping connections idle for longer than this.
+    pub idle_ping_interval: Option<Duration>, // ms
     /// Read timeout (dangerous).
     pub read_timeout: Duration, // ms
     /// Write timeout (dangerous).
@@ -312,6 +332,10 @@ pub struct Config {
     pub query_timeout: Duration, // ms
     /// Max ban duration.
     pub ban_timeout: Duration, // ms
+    /// Max ban duration when backing off repeated bans.
+    pub ban_timeout_max: Duration, // ms
+    /// Reset ban backoff after being unbanned for this long.
+    pub ban_backoff_window: Duration, // ms
     /// Rollback timeout for dirty connections.
     pub rollback_timeout: Duration,
     /// Statement timeout
@@ -324,12 +348,16 @@ pub struct Config {
     pub pooler_mode: PoolerMode,
     /// Read only mode.
     pub read_only: bool,
+    /// Default transaction isolation level.
+    pub default_transaction_isolation: Option<IsolationLevel>,
     /// Maximum prepared statements per connection.
     pub prepared_statements_limit: usize,
     /// Stats averaging period.
     pub stats_period: Duration,
     /// Recovery algo.
     pub connection_recovery: ConnectionRecovery,
+    /// Order in which waiting clients get connections.
+    pub checkout_fairness: CheckoutFairness,
     /// LSN check interval.
     pub lsn_check_interval: Duration,
     /// LSN check timeout.
@@ -342,6 +370,8 @@ pub struct Config {
     pub resharding_only: bool,
     /// LB weight.
     pub lb_weight: u8,
+    /// Type of queries this pool serves.
+    pub workload: Workload,
     /// Prepared statements level.
     pub prepared_statements_level: PreparedStatements,
 }
@@ -351,11 +381,13 @@ impl Default for Config {
         Self {
             min: 1,
             max: 10,
+            reserve_pool_size: 0,
             checkout_timeout: Duration::from_millis(5_000),
             idle_timeout: Duration::from_millis(60_000),
             connect_timeout: Duration::from_millis(5_000),
             connect_attempts: 1,
             connect_attempt_delay: Duration::from_millis(10),
+            max_concurrent_connects: 0,
             max_age: Duration::from_millis(24 * 3600 * 1000),
             max_age_jitter: Duration::ZERO,
             bannable: true,
@@ -363,26 +395,33 @@ impl Default for Config {
             healthcheck_interval: Duration::from_millis(30_000),
             idle_healthcheck_interval: Duration::from_millis(5_000),
             idle_healthcheck_delay: Duration::from_millis(5_000),
+            test_on_checkout_after_idle: None,
+            idle_ping_interval: None,
             read_timeout: Duration::MAX,
             write_timeout: Duration::MAX,
             query_timeout: Duration::MAX,
             ban_timeout: Duration::from_secs(300),
+            ban_timeout_max: Duration::ZERO,
+            ban_backoff_window: Duration::from_secs(600),
             rollback_timeout: Duration::from_secs(5),
             statement_timeout: None,
             lock_timeout: None,
             replication_mode: false,
             pooler_mode: PoolerMode::default(),
             read_only: false,
+            default_transaction_isolation: None,
             prepared_statements_limit: usize::MAX,
             stats_period: Duration::from_millis(15_000),
             dns_ttl: Duration::from_millis(60_000),
             connection_recovery: ConnectionRecovery::Recover,
+            checkout_fairness: CheckoutFairness::Barging,
             lsn_check_interval: Duration::from_millis(5_000),
             lsn_check_timeout: Duration::from_millis(5_000),
             lsn_check_delay: Duration::from_millis(5_000),
             role_detection: false,
             resharding_only: false,
             lb_weight: 255,
+            workload: Workload::default(),
             prepared_statements_level: PreparedStatements::default(),
         }
     }
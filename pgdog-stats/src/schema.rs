@@ -76,6 +76,10 @@ pub struct Relation {
     pub parent_table_schema: Option<String>,
     /// Name of the parent (partitioned) table, if this is a partition.
     pub parent_table_name: Option<String>,
+    /// Partition bound, e.g. `FOR VALUES WITH (modulus 3, remainder 0)`, if this is a partition.
+    pub partition_bound: Option<String>,
+    /// Partition key, e.g. `HASH (customer_id)`, if this is a partitioned table.
+    pub partition_key: Option<String>,
     /// Columns indexed by name, ordered by ordinal position.
     pub columns: IndexMap<String, Column>,
 }
@@ -92,6 +96,8 @@ impl Hash for Relation {
         self.oid.hash(state);
         self.parent_table_schema.hash(state);
         self.parent_table_name.hash(state);
+        self.partition_bound.hash(state);
+        self.partition_key.hash(state);
         for (key, value) in &self.columns {
             key.hash(state);
             value.hash(state);
@@ -137,6 +143,37 @@ impl Relation {
     pub fn has_column(&self, name: &str) -> bool {
         self.columns.contains_key(name)
     }
+
+    /// Modulus of the hash partition, if this is one.
+    pub fn hash_partition_modulus(&self) -> Option<usize> {
+        let bound = self.partition_bound.as_deref()?;
+        let (_, rest) = bound.split_once("modulus ")?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest, |end| &rest[..end]);
+        digits.parse().ok()
+    }
+
+    /// Column the table is hash partitioned by, if the key is a single column.
+    pub fn hash_partition_column(&self) -> Option<String> {
+        let key = self
+            .partition_key
+            .as_deref()?
+            .strip_prefix("HASH (")?
+            .strip_suffix(')')?;
+
+        // Quoted identifiers are returned as written, e.g. `"CustomerId"`.
+        if let Some(quoted) = key.strip_prefix('"') {
+            return Some(quoted.strip_suffix('"')?.replace("\"\"", "\""));
+        }
+
+        // A non-default operator class follows the column.
+        let column = key.split_once(' ').map_or(key, |(column, _)| column);
+        column
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+            .then(|| column.to_owned())
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
@@ -58,6 +58,7 @@ impl Add<Counts> for PoolCounts {
             writes: self.writes,
             reads: self.reads,
             auth_attempts: self.auth_attempts,
+            out_of_connections: self.out_of_connections,
         }
     }
 }
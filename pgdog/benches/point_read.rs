@@ -0,0 +1,73 @@
+use brunch::{Bench, benches};
+use pgdog::backend::Cluster;
+use pgdog::backend::databases::from_config;
+use pgdog::config::{ConfigAndUsers, Database, User};
+use pgdog::frontend::router::QueryParser;
+use pgdog::frontend::router::parser::{AstContext, Cache};
+use pgdog::frontend::{ClientRequest, PreparedStatements, RouterContext};
+use pgdog::net::{Parameters, Query};
+
+const QUERY: &str = "SELECT * FROM sharded WHERE id = 1 ORDER BY id LIMIT 1";
+
+/// Single-shard cluster with (`fast_path`) or without the point read fast path.
+///
+/// The fast path is disabled when routing to analytics replicas, since it
+/// needs the cost of every query. It also needs the schema to find the primary key,
+/// so this loads it from the local test database.
+fn setup(fast_path: bool) -> (Cluster, ClientRequest) {
+    let mut config = ConfigAndUsers::default();
+    config.config.databases = vec![Database {
+        name: "pgdog".into(),
+        host: "127.0.0.1".into(),
+        port: 5432,
+        ..Default::default()
+    }];
+    config.users.users = vec![User {
+        name: "pgdog".into(),
+        database: "pgdog".into(),
+        password: Some("pgdog".into()),
+        ..Default::default()
+    }];
+    config.config.general.analytics_query_cost = if fast_path { 0 } else { usize::MAX };
+
+    let cluster = from_config(&config).cluster(("pgdog", "pgdog")).unwrap();
+    let shard = &cluster.shards()[0];
+    shard.launch();
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(shard.load_schema())
+        .unwrap();
+    let params = Parameters::default();
+
+    // Parse the query once, like the AST cache does for clients.
+    let mut request = ClientRequest::from(vec![Query::new(QUERY).into()]);
+    let query = request.query().unwrap().unwrap();
+    request.ast = Some(
+        Cache::get()
+            .query(
+                &query,
+                &AstContext::from_cluster(&cluster, &params),
+                &mut PreparedStatements::new(),
+            )
+            .unwrap(),
+    );
+
+    (cluster, request)
+}
+
+fn route(cluster: &Cluster, request: &ClientRequest) {
+    let params = Parameters::default();
+    let context = RouterContext::new(request, cluster, &params, None, Default::default()).unwrap();
+    QueryParser::default().parse(context).unwrap();
+}
+
+benches!(
+    {
+        let (cluster, request) = setup(false);
+        Bench::new("point read (normal)").run(move || route(&cluster, &request))
+    },
+    {
+        let (cluster, request) = setup(true);
+        Bench::new("point read (fast path)").run(move || route(&cluster, &request))
+    },
+);
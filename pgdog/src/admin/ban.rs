@@ -2,10 +2,20 @@ use std::time::Duration;
 
 use super::prelude::*;
 use crate::backend::{databases::databases, pool};
+use crate::config::Role;
+
+/// Pool identified by its database, shard and role.
+#[derive(Debug, Clone, PartialEq)]
+struct Target {
+    database: String,
+    shard: usize,
+    role: Role,
+}
 
 #[derive(Default)]
 pub struct Ban {
     id: Option<u64>,
+    target: Option<Target>,
     unban: bool,
 }
 
@@ -36,6 +46,17 @@ impl Command for Ban {
             ["unban", id] => Ok(Self {
                 id: Some(id.parse()?),
                 unban: true,
+                ..Default::default()
+            }),
+
+            ["unban", database, shard, role] => Ok(Self {
+                target: Some(Target {
+                    database: database.to_string(),
+                    shard: shard.parse()?,
+                    role: role.parse().map_err(|_| Error::Syntax)?,
+                }),
+                unban: true,
+                ..Default::default()
             }),
 
             _ => Err(Error::Syntax),
@@ -43,15 +64,23 @@ impl Command for Ban {
     }
 
     async fn execute(&self) -> Result<Vec<Message>, Error> {
-        for database in databases().all().values() {
-            for shard in database.shards() {
-                for (_role, ban, pool) in shard.pools_with_roles_and_bans() {
+        for (user, cluster) in databases().all() {
+            for (shard_num, shard) in cluster.shards().iter().enumerate() {
+                for (role, ban, pool) in shard.pools_with_roles_and_bans() {
                     if let Some(id) = self.id
                         && id != pool.id()
                     {
                         continue;
                     }
 
+                    if let Some(ref target) = self.target
+                        && (target.database != user.database
+                            || target.shard != shard_num
+                            || target.role != role)
+                    {
+                        continue;
+                    }
+
                     if self.unban {
                         ban.unban(false, pool::lb::UnbanReason::Manual);
                     } else {
@@ -63,3 +92,25 @@ impl Command for Ban {
         Ok(vec![])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_unban_target() {
+        let unban = Ban::parse("unban app 1 replica").unwrap();
+        assert!(unban.unban);
+        assert_eq!(
+            unban.target,
+            Some(Target {
+                database: "app".into(),
+                shard: 1,
+                role: Role::Replica,
+            })
+        );
+
+        assert!(Ban::parse("unban app 1 leader").is_err());
+        assert!(Ban::parse("unban app one replica").is_err());
+    }
+}
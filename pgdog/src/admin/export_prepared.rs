@@ -0,0 +1,30 @@
+//! EXPORT PREPARED.
+use crate::frontend::PreparedStatements;
+
+use super::prelude::*;
+
+/// Export the global prepared statements cache as JSON,
+/// so it can be loaded by another instance with `IMPORT PREPARED`.
+pub struct ExportPrepared;
+
+#[async_trait]
+impl Command for ExportPrepared {
+    fn name(&self) -> String {
+        "EXPORT PREPARED".into()
+    }
+
+    fn parse(_: &str) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let statements = PreparedStatements::global().read().export();
+        let mut dr = DataRow::new();
+        dr.add(serde_json::to_string(&statements)?);
+
+        Ok(vec![
+            RowDescription::new(&[Field::text("prepared_statements")]).message()?,
+            dr.message()?,
+        ])
+    }
+}
@@ -0,0 +1,94 @@
+//! IMPORT PREPARED '<json>'.
+use crate::frontend::{PreparedStatements, prepared_statements::ExportedStatement};
+
+use super::prelude::*;
+
+/// Load prepared statements exported by `EXPORT PREPARED`
+/// into the global prepared statements cache.
+pub struct ImportPrepared {
+    statements: Vec<ExportedStatement>,
+}
+
+#[async_trait]
+impl Command for ImportPrepared {
+    fn name(&self) -> String {
+        "IMPORT PREPARED".into()
+    }
+
+    /// Expects the original query, since the statements are case-sensitive.
+    fn parse(sql: &str) -> Result<Self, Error> {
+        let mut parts = sql.trim().splitn(3, char::is_whitespace);
+
+        match (parts.next(), parts.next()) {
+            (Some(import), Some(prepared))
+                if import.eq_ignore_ascii_case("import")
+                    && prepared.eq_ignore_ascii_case("prepared") => {}
+            _ => return Err(Error::Syntax),
+        }
+
+        let json = parts
+            .next()
+            .map(|json| json.trim().trim_end_matches(';').trim_end())
+            .and_then(|json| json.strip_prefix('\''))
+            .and_then(|json| json.strip_suffix('\''))
+            .ok_or(Error::Syntax)?
+            .replace("''", "'");
+
+        Ok(Self {
+            statements: serde_json::from_str(&json)?,
+        })
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let imported = PreparedStatements::global()
+            .write()
+            .import(&self.statements);
+        let mut dr = DataRow::new();
+        dr.add(imported);
+
+        Ok(vec![
+            RowDescription::new(&[Field::numeric("imported")]).message()?,
+            dr.message()?,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frontend::prepared_statements::GlobalCache;
+    use crate::net::Parse;
+
+    use super::*;
+
+    #[test]
+    fn test_import_prepared_parse() {
+        let cmd = ImportPrepared::parse(
+            r#"IMPORT PREPARED '[{"query":"SELECT ''Abc''","data_types":[25]}]';"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cmd.statements,
+            vec![ExportedStatement {
+                query: "SELECT 'Abc'".into(),
+                data_types: vec![25],
+            }]
+        );
+
+        assert!(ImportPrepared::parse("IMPORT PREPARED").is_err());
+        assert!(ImportPrepared::parse("IMPORT PREPARED []").is_err());
+        assert!(ImportPrepared::parse("IMPORT PREPARED 'not json'").is_err());
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut cache = GlobalCache::default();
+        cache.insert(&Parse::named("test", "SELECT $1 /* Export */").with_data_types(&[20]));
+        let json = serde_json::to_string(&cache.export()).unwrap();
+
+        let cmd = ImportPrepared::parse(&format!("IMPORT PREPARED '{}'", json.replace('\'', "''")))
+            .unwrap();
+        let mut imported = GlobalCache::default();
+        assert_eq!(imported.import(&cmd.statements), 1);
+        assert_eq!(imported.export(), cache.export());
+    }
+}
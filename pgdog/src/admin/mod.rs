@@ -8,7 +8,9 @@ pub mod ban;
 pub mod copy_data;
 pub mod cutover;
 pub mod error;
+pub mod export_prepared;
 pub mod healthcheck;
+pub mod import_prepared;
 pub mod maintenance_mode;
 pub mod named_row;
 pub mod parser;
@@ -21,6 +23,7 @@ pub mod replicate;
 pub mod reset_prepared;
 pub mod reset_query_cache;
 pub mod reshard;
+pub mod route;
 pub mod schema_sync;
 pub mod server;
 pub mod set;
@@ -29,6 +32,7 @@ pub mod show_bans;
 pub mod show_client_memory;
 pub mod show_clients;
 pub mod show_config;
+pub mod show_errors;
 pub mod show_instance_id;
 pub mod show_listeners;
 pub mod show_lists;
@@ -42,8 +46,10 @@ pub mod show_replication_slots;
 pub mod show_schema_sync;
 pub mod show_server_memory;
 pub mod show_servers;
+pub mod show_shards;
 pub mod show_stats;
 pub mod show_table_copies;
+pub mod show_tags;
 pub mod show_tasks;
 pub mod show_transactions;
 pub mod show_version;
@@ -54,7 +60,9 @@ pub use ban::*;
 pub use copy_data::*;
 pub use cutover::*;
 pub use error::Error;
+pub use export_prepared::*;
 pub use healthcheck::*;
+pub use import_prepared::*;
 pub use maintenance_mode::*;
 pub use named_row::*;
 pub use parser::*;
@@ -66,6 +74,7 @@ pub use replicate::*;
 pub use reset_prepared::*;
 pub use reset_query_cache::*;
 pub use reshard::*;
+pub use route::*;
 pub use schema_sync::*;
 pub use server::*;
 pub use set::*;
@@ -74,6 +83,7 @@ pub use show_bans::*;
 pub use show_client_memory::*;
 pub use show_clients::*;
 pub use show_config::*;
+pub use show_errors::*;
 pub use show_instance_id::*;
 pub use show_listeners::*;
 pub use show_lists::*;
@@ -87,8 +97,10 @@ pub use show_replication_slots::*;
 pub use show_schema_sync::*;
 pub use show_server_memory::*;
 pub use show_servers::*;
+pub use show_shards::*;
 pub use show_stats::*;
 pub use show_table_copies::*;
+pub use show_tags::*;
 pub use show_tasks::*;
 pub use show_transactions::*;
 pub use show_version::*;
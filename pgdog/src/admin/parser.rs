@@ -12,6 +12,7 @@ pub enum ParseResult {
     Reload(Reload),
     ShowPools(ShowPools),
     ShowBans(ShowBans),
+    ShowErrors(ShowErrors),
     ShowConfig(ShowConfig),
     ShowServers(ShowServers),
     ShowPeers(ShowPeers),
@@ -21,6 +22,8 @@ pub enum ParseResult {
     ShowStats(ShowStats),
     ShowTransactions(ShowTransactions),
     ShowMirrors(ShowMirrors),
+    ShowShards(ShowShards),
+    ShowTags(ShowTags),
     ShowVersion(ShowVersion),
     ShowInstanceId(ShowInstanceId),
     SetupSchema(SetupSchema),
@@ -46,6 +49,9 @@ pub enum ParseResult {
     ShowTasks(ShowTasks),
     StopTask(StopTask),
     Cutover(Cutover),
+    ExportPrepared(ExportPrepared),
+    ImportPrepared(ImportPrepared),
+    Route(RouteQuery),
 }
 
 impl ParseResult {
@@ -60,6 +66,7 @@ impl ParseResult {
             Reload(reload) => reload.execute().await,
             ShowPools(show_pools) => show_pools.execute().await,
             ShowBans(show_bans) => show_bans.execute().await,
+            ShowErrors(show_errors) => show_errors.execute().await,
             ShowConfig(show_config) => show_config.execute().await,
             ShowServers(show_servers) => show_servers.execute().await,
             ShowPeers(show_peers) => show_peers.execute().await,
@@ -69,6 +76,8 @@ impl ParseResult {
             ShowStats(show_stats) => show_stats.execute().await,
             ShowTransactions(show_transactions) => show_transactions.execute().await,
             ShowMirrors(show_mirrors) => show_mirrors.execute().await,
+            ShowShards(show_shards) => show_shards.execute().await,
+            ShowTags(show_tags) => show_tags.execute().await,
             ShowVersion(show_version) => show_version.execute().await,
             ShowInstanceId(show_instance_id) => show_instance_id.execute().await,
             SetupSchema(setup_schema) => setup_schema.execute().await,
@@ -94,6 +103,9 @@ impl ParseResult {
             ShowTasks(cmd) => cmd.execute().await,
             StopTask(cmd) => cmd.execute().await,
             Cutover(cmd) => cmd.execute().await,
+            ExportPrepared(cmd) => cmd.execute().await,
+            ImportPrepared(cmd) => cmd.execute().await,
+            Route(cmd) => cmd.execute().await,
         }
     }
 
@@ -108,6 +120,7 @@ impl ParseResult {
             Reload(reload) => reload.name(),
             ShowPools(show_pools) => show_pools.name(),
             ShowBans(show_bans) => show_bans.name(),
+            ShowErrors(show_errors) => show_errors.name(),
             ShowConfig(show_config) => show_config.name(),
             ShowServers(show_servers) => show_servers.name(),
             ShowPeers(show_peers) => show_peers.name(),
@@ -117,6 +130,8 @@ impl ParseResult {
             ShowStats(show_stats) => show_stats.name(),
             ShowTransactions(show_transactions) => show_transactions.name(),
             ShowMirrors(show_mirrors) => show_mirrors.name(),
+            ShowShards(show_shards) => show_shards.name(),
+            ShowTags(show_tags) => show_tags.name(),
             ShowVersion(show_version) => show_version.name(),
             ShowInstanceId(show_instance_id) => show_instance_id.name(),
             SetupSchema(setup_schema) => setup_schema.name(),
@@ -142,6 +157,9 @@ impl ParseResult {
             ShowTasks(cmd) => cmd.name(),
             StopTask(cmd) => cmd.name(),
             Cutover(cmd) => cmd.name(),
+            ExportPrepared(cmd) => cmd.name(),
+            ImportPrepared(cmd) => cmd.name(),
+            Route(cmd) => cmd.name(),
         }
     }
 }
@@ -152,6 +170,7 @@ pub struct Parser;
 impl Parser {
     /// Parse the query and return a command we can execute.
     pub fn parse(sql: &str) -> Result<ParseResult, Error> {
+        let original = sql;
         let sql = sql.trim().replace(";", "").to_lowercase();
         let mut iter = sql.split(" ");
 
@@ -166,6 +185,7 @@ impl Parser {
                 "clients" => ParseResult::ShowClients(ShowClients::parse(&sql)?),
                 "pools" => ParseResult::ShowPools(ShowPools::parse(&sql)?),
                 "bans" => ParseResult::ShowBans(ShowBans::parse(&sql)?),
+                "errors" => ParseResult::ShowErrors(ShowErrors::parse(&sql)?),
                 "config" => ParseResult::ShowConfig(ShowConfig::parse(&sql)?),
                 "servers" => ParseResult::ShowServers(ShowServers::parse(&sql)?),
                 "server" => match iter.next().ok_or(Error::Syntax)?.trim() {
@@ -187,6 +207,8 @@ impl Parser {
                 "stats" => ParseResult::ShowStats(ShowStats::parse(&sql)?),
                 "transactions" => ParseResult::ShowTransactions(ShowTransactions::parse(&sql)?),
                 "mirrors" => ParseResult::ShowMirrors(ShowMirrors::parse(&sql)?),
+                "shards" => ParseResult::ShowShards(ShowShards::parse(&sql)?),
+                "tags" => ParseResult::ShowTags(ShowTags::parse(&sql)?),
                 "version" => ParseResult::ShowVersion(ShowVersion::parse(&sql)?),
                 "instance_id" => ParseResult::ShowInstanceId(ShowInstanceId::parse(&sql)?),
                 "lists" => ParseResult::ShowLists(ShowLists::parse(&sql)?),
@@ -225,6 +247,17 @@ impl Parser {
             "replicate" => ParseResult::Replicate(Replicate::parse(&sql)?),
             "stop_task" => ParseResult::StopTask(StopTask::parse(&sql)?),
             "cutover" => ParseResult::Cutover(Cutover::parse(&sql)?),
+            "export" => match iter.next().ok_or(Error::Syntax)?.trim() {
+                "prepared" => ParseResult::ExportPrepared(ExportPrepared::parse(&sql)?),
+                command => {
+                    debug!("unknown admin export command: '{}'", command);
+                    return Err(Error::Syntax);
+                }
+            },
+            // Statements are case-sensitive.
+            "import" => ParseResult::ImportPrepared(ImportPrepared::parse(original)?),
+            // Identifiers and values in the query are case-sensitive.
+            "route" => ParseResult::Route(RouteQuery::parse(original)?),
             "probe" => ParseResult::Probe(Probe::parse(&sql)?),
             "maintenance" => ParseResult::MaintenanceMode(MaintenanceMode::parse(&sql)?),
             // TODO: This is not ready yet. We have a race and
@@ -285,6 +318,46 @@ mod tests {
         assert!(matches!(result, Ok(ParseResult::ShowBans(_))));
     }
 
+    #[test]
+    fn parses_show_errors_command() {
+        let result = Parser::parse("SHOW ERRORS;");
+        assert!(matches!(result, Ok(ParseResult::ShowErrors(_))));
+    }
+
+    #[test]
+    fn parses_show_tags_command() {
+        let result = Parser::parse("SHOW TAGS;");
+        assert!(matches!(result, Ok(ParseResult::ShowTags(_))));
+    }
+
+    #[test]
+    fn parses_export_import_prepared_commands() {
+        assert!(matches!(
+            Parser::parse("EXPORT PREPARED;"),
+            Ok(ParseResult::ExportPrepared(_))
+        ));
+        assert!(matches!(
+            Parser::parse(
+                r#"IMPORT PREPARED '[{"query":"SELECT \"Id\" FROM t;","data_types":[]}]';"#
+            ),
+            Ok(ParseResult::ImportPrepared(_))
+        ));
+        assert!(matches!(
+            Parser::parse("IMPORT QUERY_CACHE '[]'"),
+            Err(Error::Syntax)
+        ));
+    }
+
+    #[test]
+    fn parses_route_command() {
+        assert!(matches!(
+            Parser::parse("ROUTE SELECT * FROM \"Users\" WHERE id = 1;"),
+            Ok(ParseResult::Route(_))
+        ));
+        assert!(matches!(Parser::parse("ROUTE"), Err(Error::Syntax)));
+        assert!(matches!(Parser::parse("ROUTE ;"), Err(Error::Syntax)));
+    }
+
     #[test]
     fn parses_cutover_command() {
         assert!(matches!(
@@ -0,0 +1,131 @@
+//! ROUTE <query>.
+use crate::{
+    backend::{Cluster, databases::databases},
+    frontend::{
+        BufferedQuery, ClientRequest, Command as RouterCommand, PreparedStatements, RouterContext,
+        client::Sticky,
+        router::{
+            QueryParser,
+            parser::{AstContext, Cache},
+        },
+    },
+    net::{Parameters, Query},
+};
+
+use super::prelude::*;
+
+/// Show where a query would be sent, for each database,
+/// without executing it.
+pub struct RouteQuery {
+    query: String,
+}
+
+impl RouteQuery {
+    /// Route the query the same way it would be for a client connected
+    /// to the cluster.
+    fn route(
+        cluster: &Cluster,
+        query: &str,
+    ) -> Result<RouterCommand, crate::frontend::router::Error> {
+        let params = Parameters::default();
+        let ctx = AstContext::from_cluster(cluster, &params);
+        let buffered = BufferedQuery::Query(Query::new(query));
+        let ast = Cache::get().query(&buffered, &ctx, &mut PreparedStatements::default())?;
+
+        let mut request = ClientRequest::from(vec![Query::new(query).into()]);
+        request.ast = Some(ast);
+
+        let mut context = RouterContext::new(&request, cluster, &params, None, Sticky::new())?;
+        // Show the real routing decision and keep the query
+        // out of the shared cache.
+        context.dry_run = false;
+
+        Ok(QueryParser::default().parse(context)?)
+    }
+
+    fn command_name(command: &RouterCommand) -> &'static str {
+        match command {
+            RouterCommand::Query(_) => "query",
+            RouterCommand::Copy(_) => "copy",
+            RouterCommand::StartTransaction { .. } => "begin",
+            RouterCommand::CommitTransaction { .. } => "commit",
+            RouterCommand::RollbackTransaction { .. } => "rollback",
+            RouterCommand::ReplicationMeta => "replication",
+            RouterCommand::Set { .. } => "set",
+            RouterCommand::ResetAll => "reset",
+            RouterCommand::InternalField { .. } => "internal",
+            RouterCommand::Deallocate { .. } => "deallocate",
+            RouterCommand::Discard { .. } => "discard",
+            RouterCommand::Listen { .. } => "listen",
+            RouterCommand::Notify { .. } => "notify",
+            RouterCommand::Unlisten(_) => "unlisten",
+            RouterCommand::UniqueId => "unique_id",
+        }
+    }
+}
+
+#[async_trait]
+impl Command for RouteQuery {
+    fn name(&self) -> String {
+        "ROUTE".into()
+    }
+
+    /// Expects the original query, since identifiers and values are case-sensitive.
+    fn parse(sql: &str) -> Result<Self, Error> {
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        let mut parts = sql.splitn(2, char::is_whitespace);
+
+        match parts.next() {
+            Some(route) if route.eq_ignore_ascii_case("route") => (),
+            _ => return Err(Error::Syntax),
+        }
+
+        let query = parts.next().map(|query| query.trim()).unwrap_or_default();
+        if query.is_empty() {
+            return Err(Error::Syntax);
+        }
+
+        Ok(Self {
+            query: query.to_string(),
+        })
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let rd = RowDescription::new(&[
+            Field::text("database"),
+            Field::text("user"),
+            Field::text("command"),
+            Field::text("shard"),
+            Field::text("role"),
+            Field::text("error"),
+        ]);
+
+        let mut messages = vec![rd.message()?];
+
+        let databases = databases();
+        let mut clusters = databases.all().iter().collect::<Vec<_>>();
+        clusters.sort_by(|(a, _), (b, _)| (&a.database, &a.user).cmp(&(&b.database, &b.user)));
+
+        for (user, cluster) in clusters {
+            let mut row = DataRow::new();
+            row.add(user.database.as_str()).add(user.user.as_str());
+
+            match Self::route(cluster, &self.query) {
+                Ok(command) => {
+                    let route = command.route();
+                    row.add(Self::command_name(&command))
+                        .add(route.shard().to_string())
+                        .add(if route.is_read() { "read" } else { "write" })
+                        .add("");
+                }
+                Err(err) => {
+                    row.add("").add("").add("").add(err.to_string());
+                }
+            }
+
+            messages.push(row.message()?);
+        }
+
+        Ok(messages)
+    }
+}
@@ -115,6 +115,18 @@ impl Command for Set {
                 config.config.general.load_balancing_strategy = Self::from_json(&self.value)?;
             }
 
+            "analytics_query_cost" => {
+                config.config.general.analytics_query_cost = self.value.parse()?;
+            }
+
+            "primary_reads_after_connect" => {
+                config.config.general.primary_reads_after_connect = self.value.parse()?;
+            }
+
+            "primary_reads_after_connect_window" => {
+                config.config.general.primary_reads_after_connect_window = self.value.parse()?;
+            }
+
             "prepared_statements_limit" => {
                 config.config.general.prepared_statements_limit = self.value.parse()?;
                 PreparedStatements::global()
@@ -130,6 +142,15 @@ impl Command for Set {
                 config.config.general.cross_shard_disabled = Self::from_json(&self.value)?;
             }
 
+            "cross_shard_transactions_disabled" => {
+                config.config.general.cross_shard_transactions_disabled =
+                    Self::from_json(&self.value)?;
+            }
+
+            "atomic_truncate" => {
+                config.config.general.atomic_truncate = Self::from_json(&self.value)?;
+            }
+
             "two_phase_commit" => {
                 config.config.general.two_phase_commit = Self::from_json(&self.value)?;
             }
@@ -175,10 +196,26 @@ impl Command for Set {
                 config.config.general.idle_healthcheck_delay = self.value.parse()?;
             }
 
+            "test_on_checkout_after_idle" => {
+                config.config.general.test_on_checkout_after_idle = self.value.parse()?;
+            }
+
+            "idle_ping_interval" => {
+                config.config.general.idle_ping_interval = self.value.parse()?;
+            }
+
             "ban_timeout" => {
                 config.config.general.ban_timeout = self.value.parse()?;
             }
 
+            "ban_timeout_max" => {
+                config.config.general.ban_timeout_max = self.value.parse()?;
+            }
+
+            "ban_backoff_window" => {
+                config.config.general.ban_backoff_window = self.value.parse()?;
+            }
+
             "tls_client_required" => {
                 config.config.general.tls_client_required = Self::from_json(&self.value)?;
             }
@@ -191,6 +228,18 @@ impl Command for Set {
                 config.config.general.client_idle_in_transaction_timeout = self.value.parse()?;
             }
 
+            "max_transaction_duration" => {
+                config.config.general.max_transaction_duration = self.value.parse()?;
+            }
+
+            "client_quarantine_errors" => {
+                config.config.general.client_quarantine_errors = self.value.parse()?;
+            }
+
+            "client_quarantine_duration" => {
+                config.config.general.client_quarantine_duration = self.value.parse()?;
+            }
+
             "reload_schema_on_ddl" => {
                 config.config.general.reload_schema_on_ddl = Self::from_json(&self.value)?;
             }
@@ -203,8 +252,22 @@ impl Command for Set {
                 config.config.general.client_connection_recovery = Self::from_json(&self.value)?;
             }
 
+            "temporary_tables" => {
+                config.config.general.temporary_tables = Self::from_json(&self.value)?;
+            }
+
             "default_pool_size" => {
                 config.config.general.default_pool_size = self.value.parse()?;
+                config::set(config)?;
+                databases::resize();
+                return Ok(vec![]);
+            }
+
+            "min_pool_size" => {
+                config.config.general.min_pool_size = self.value.parse()?;
+                config::set(config)?;
+                databases::resize();
+                return Ok(vec![]);
             }
 
             "connect_timeout" => {
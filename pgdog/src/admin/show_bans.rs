@@ -59,7 +59,7 @@ impl Command for ShowBans {
                         .add(pool.addr().port as i64)
                         .add(shard_num as i64)
                         .add(role.to_string())
-                        .add(ban.error().map(|err| err.to_string()))
+                        .add(ban.reason())
                         .add(time_left);
 
                     messages.push(row.message()?);
@@ -44,6 +44,7 @@ impl Command for ShowClients {
             Field::numeric("bytes_received"),
             Field::numeric("bytes_sent"),
             Field::numeric("errors"),
+            Field::numeric("slow_queries"),
             Field::text("application_name"),
             Field::bool("locked"),
             Field::numeric("prepared_statements"),
@@ -114,6 +115,7 @@ impl Command for ShowClients {
                 .add("bytes_received", client.stats.bytes_received)
                 .add("bytes_sent", client.stats.bytes_sent)
                 .add("errors", client.stats.errors)
+                .add("slow_queries", client.stats.slow_queries)
                 .add(
                     "application_name",
                     client.paramters.get_default("application_name", ""),
@@ -0,0 +1,52 @@
+//! SHOW ERRORS command.
+
+use crate::{
+    net::messages::{DataRow, Field, Protocol, RowDescription},
+    stats::errors::errors,
+};
+
+use super::prelude::*;
+
+/// Show errors returned to clients in the last 5 minutes,
+/// counted by category for each user and database.
+pub struct ShowErrors;
+
+#[async_trait]
+impl Command for ShowErrors {
+    fn name(&self) -> String {
+        "SHOW ERRORS".into()
+    }
+
+    fn parse(_sql: &str) -> Result<Self, Error> {
+        Ok(ShowErrors {})
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let rd = RowDescription::new(&[
+            Field::text("database"),
+            Field::text("user"),
+            Field::numeric("connection"),
+            Field::numeric("timeout"),
+            Field::numeric("query"),
+            Field::numeric("protocol"),
+            Field::numeric("total"),
+        ]);
+
+        let mut messages = vec![rd.message()?];
+
+        for counts in errors().counts() {
+            let mut row = DataRow::new();
+            row.add(counts.database.as_str())
+                .add(counts.user.as_str())
+                .add(counts.connection as i64)
+                .add(counts.timeout as i64)
+                .add(counts.query as i64)
+                .add(counts.protocol as i64)
+                .add(counts.total() as i64);
+
+            messages.push(row.message()?);
+        }
+
+        Ok(messages)
+    }
+}
@@ -26,6 +26,7 @@ impl Command for ShowMirrors {
             Field::numeric("dropped_count"),
             Field::numeric("error_count"),
             Field::numeric("queue_length"),
+            Field::numeric("mismatch_count"),
         ];
 
         let mut messages = vec![RowDescription::new(&fields).message()?];
@@ -46,7 +47,8 @@ impl Command for ShowMirrors {
                 .add(counts.mirrored_count as i64)
                 .add(counts.dropped_count as i64)
                 .add(counts.error_count as i64)
-                .add(counts.queue_length as i64);
+                .add(counts.queue_length as i64)
+                .add(counts.mismatch_count as i64);
 
             messages.push(dr.message()?);
         }
@@ -88,11 +90,11 @@ mod tests {
         let row_desc = RowDescription::from_bytes(messages[0].to_bytes()).unwrap();
         let fields = &row_desc.fields;
 
-        // Should have 7 columns for per-cluster stats
+        // Should have 8 columns for per-cluster stats
         assert_eq!(
             fields.len(),
-            7,
-            "Should have 7 columns for per-cluster stats"
+            8,
+            "Should have 8 columns for per-cluster stats"
         );
 
         // Check column names
@@ -104,6 +106,7 @@ mod tests {
             "dropped_count",
             "error_count",
             "queue_length",
+            "mismatch_count",
         ];
         for (i, expected) in expected_columns.iter().enumerate() {
             assert_eq!(
@@ -123,7 +126,7 @@ mod tests {
 
             // Skip validating database and user strings for now since DataRow doesn't have get_string
             // Just validate the counter values are integers (>= 0)
-            for i in 2..7 {
+            for i in 2..8 {
                 let value = data_row.get_int(i, true).unwrap_or(0);
                 assert!(value >= 0, "Column {} should have non-negative value", i);
             }
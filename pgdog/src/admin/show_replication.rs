@@ -11,6 +11,8 @@ use crate::{
 
 use super::prelude::*;
 
+/// Replication lag and LSN of each server,
+/// as seen by the LSN monitor.
 pub struct ShowReplication;
 
 #[async_trait]
@@ -36,6 +38,7 @@ impl Command for ShowReplication {
             Field::text("pg_lsn"),
             Field::text("lsn_age"),
             Field::text("pg_is_in_recovery"),
+            Field::text("replica_lag_bytes"),
         ]);
         let mut messages = vec![rd.message()?];
         let now = SystemTime::now();
@@ -81,6 +84,11 @@ impl Command for ShowReplication {
                             state.lsn_stats.replica.to_data_row_column()
                         } else {
                             Data::null()
+                        })
+                        .add(if valid {
+                            state.replica_lag.bytes.to_string().to_data_row_column()
+                        } else {
+                            Data::null()
                         });
 
                     messages.push(row.message()?);
@@ -0,0 +1,65 @@
+//! SHOW SHARDS - sharded tables and how they are sharded.
+
+use std::collections::BTreeMap;
+
+use crate::backend::databases::databases;
+
+use super::prelude::*;
+
+pub struct ShowShards;
+
+#[async_trait]
+impl Command for ShowShards {
+    fn name(&self) -> String {
+        "SHOW SHARDS".into()
+    }
+
+    fn parse(_: &str) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let fields = vec![
+            Field::text("database"),
+            Field::text("schema"),
+            Field::text("table"),
+            Field::text("column"),
+            Field::text("data_type"),
+            Field::text("hasher"),
+            Field::bool("mapping"),
+            Field::numeric("shards"),
+            Field::numeric("centroids"),
+            Field::bool("centroids_loaded"),
+        ];
+
+        let mut messages = vec![RowDescription::new(&fields).message()?];
+
+        // Sharding is configured per database, not per user.
+        let databases = databases();
+        let clusters = databases
+            .all()
+            .iter()
+            .map(|(user, cluster)| (user.database.as_str(), cluster))
+            .collect::<BTreeMap<_, _>>();
+
+        for (database, cluster) in clusters {
+            for table in cluster.sharded_tables() {
+                let mut dr = DataRow::new();
+                dr.add(database)
+                    .add(table.schema.as_deref().unwrap_or_default())
+                    .add(table.name.as_deref().unwrap_or_default())
+                    .add(table.column.as_str())
+                    .add(table.data_type.to_string())
+                    .add(table.hasher.to_string())
+                    .add(table.mapping.is_some())
+                    .add(cluster.shards().len() as i64)
+                    .add(table.centroids.len() as i64)
+                    .add(!table.centroids.is_empty());
+
+                messages.push(dr.message()?);
+            }
+        }
+
+        Ok(messages)
+    }
+}
@@ -0,0 +1,46 @@
+//! SHOW TAGS command.
+
+use crate::{
+    net::messages::{DataRow, Field, Protocol, RowDescription},
+    stats::tags::tags,
+};
+
+use super::prelude::*;
+
+/// Show queries counted by the tag set in their comment,
+/// for each user and database.
+pub struct ShowTags;
+
+#[async_trait]
+impl Command for ShowTags {
+    fn name(&self) -> String {
+        "SHOW TAGS".into()
+    }
+
+    fn parse(_sql: &str) -> Result<Self, Error> {
+        Ok(ShowTags {})
+    }
+
+    async fn execute(&self) -> Result<Vec<Message>, Error> {
+        let rd = RowDescription::new(&[
+            Field::text("tag"),
+            Field::text("database"),
+            Field::text("user"),
+            Field::numeric("queries"),
+        ]);
+
+        let mut messages = vec![rd.message()?];
+
+        for counts in tags().counts() {
+            let mut row = DataRow::new();
+            row.add(counts.tag.as_str())
+                .add(counts.database.as_str())
+                .add(counts.user.as_str())
+                .add(counts.queries as i64);
+
+            messages.push(row.message()?);
+        }
+
+        Ok(messages)
+    }
+}
@@ -1,16 +1,24 @@
 use crate::admin::Command;
 use crate::backend::databases::{Databases, databases, from_config, replace_databases};
 use crate::backend::pool::mirror_stats::Counts;
-use crate::config::{self, ConfigAndUsers, Database, Role, User as ConfigUser};
+use crate::config::{self, ConfigAndUsers, Database, Hasher, Role, User as ConfigUser};
 use crate::net::messages::{DataRow, DataType, FromBytes, Protocol, RowDescription};
 
+use super::ban::Ban;
+use super::route::RouteQuery;
 use super::show_bans::ShowBans;
 use super::show_client_memory::ShowClientMemory;
 use super::show_config::ShowConfig;
+use super::show_errors::ShowErrors;
 use super::show_lists::ShowLists;
 use super::show_mirrors::ShowMirrors;
 use super::show_pools::ShowPools;
+use super::show_replication::ShowReplication;
 use super::show_server_memory::ShowServerMemory;
+use super::show_shards::ShowShards;
+use super::show_tags::ShowTags;
+use pgdog_config::ShardedTableConfig;
+use pgdog_vector::Vector;
 
 #[derive(Clone)]
 struct SavedState {
@@ -121,6 +129,7 @@ async fn show_pools_reports_schema_admin_flag() {
         "force_closed",
         "online",
         "schema_admin",
+        "ban_reason",
     ];
     assert_eq!(actual_names, expected_names);
 
@@ -140,6 +149,141 @@ async fn show_pools_reports_schema_admin_flag() {
     assert_eq!(schema_admin_value.as_str(), "t");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn show_pools_reports_ban_reason() {
+    use std::time::Duration;
+
+    use crate::backend::pool::Error as PoolError;
+
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    config.config.databases.push(Database {
+        name: "app".into(),
+        host: "127.0.0.1".into(),
+        role: Role::Primary,
+        shard: 0,
+        ..Default::default()
+    });
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+    context.set_config(config);
+
+    let ban_reasons = || async {
+        let messages = ShowPools
+            .execute()
+            .await
+            .expect("show pools execution failed");
+        let row_description = RowDescription::from_bytes(messages[0].payload())
+            .expect("row description message should parse");
+        let reason_index = row_description
+            .field_index("ban_reason")
+            .expect("ban_reason column index");
+
+        messages[1..]
+            .iter()
+            .map(|message| {
+                DataRow::from_bytes(message.payload())
+                    .expect("data row should parse")
+                    .get_text(reason_index)
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Not banned, so there is no reason.
+    let reasons = ban_reasons().await;
+    assert!(!reasons.is_empty());
+    assert!(reasons.iter().all(|reason| reason.is_empty()));
+
+    for (user, cluster) in databases().all() {
+        if user.database != "app" {
+            continue;
+        }
+        for shard in cluster.shards() {
+            for (_role, ban, _pool) in shard.pools_with_roles_and_bans() {
+                ban.ban(PoolError::CheckoutTimeout, Duration::from_secs(60));
+            }
+        }
+    }
+
+    let reasons = ban_reasons().await;
+    assert!(reasons.iter().all(|reason| reason == "checkout timeout"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn unban_reinstates_one_pool() {
+    use std::time::Duration;
+
+    use crate::backend::pool::Error as PoolError;
+
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    for shard in 0..2 {
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Primary,
+            shard,
+            ..Default::default()
+        });
+    }
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+    context.set_config(config);
+
+    let bans = || {
+        databases()
+            .all()
+            .iter()
+            .filter(|(user, _)| user.database == "app")
+            .flat_map(|(_, cluster)| {
+                cluster
+                    .shards()
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(shard, pools)| {
+                        pools
+                            .pools_with_roles_and_bans()
+                            .into_iter()
+                            .map(move |(_, ban, _)| (shard, ban))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (_, ban) in bans() {
+        ban.ban(PoolError::ServerError, Duration::from_secs(60));
+    }
+
+    Ban::parse("unban app 1 primary")
+        .expect("unban should parse")
+        .execute()
+        .await
+        .expect("unban execution failed");
+
+    let bans = bans();
+    assert_eq!(bans.len(), 2);
+    for (shard, ban) in bans {
+        if shard == 1 {
+            // Eligible for checkout again.
+            assert!(!ban.banned());
+        } else {
+            assert!(ban.banned());
+        }
+    }
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn show_bans_lists_banned_pools_with_reason_and_time_left() {
     use std::time::Duration;
@@ -336,6 +480,7 @@ async fn show_mirrors_reports_counts() {
             dropped_count: 1,
             error_count: 2,
             queue_length: 3,
+            mismatch_count: 6,
         };
     }
 
@@ -356,6 +501,7 @@ async fn show_mirrors_reports_counts() {
         "dropped_count",
         "error_count",
         "queue_length",
+        "mismatch_count",
     ];
     let actual_columns: Vec<&str> = row_description
         .fields
@@ -373,6 +519,7 @@ async fn show_mirrors_reports_counts() {
     assert_eq!(data_row.get_int(4, true), Some(1));
     assert_eq!(data_row.get_int(5, true), Some(2));
     assert_eq!(data_row.get_int(6, true), Some(3));
+    assert_eq!(data_row.get_int(7, true), Some(6));
 }
 
 #[tokio::test(flavor = "current_thread")]
@@ -569,3 +716,481 @@ async fn show_client_memory_reports_memory_stats() {
         assert_eq!(field.data_type(), *expected_type);
     }
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn show_shards_reports_sharded_tables() {
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    for shard in 0..2 {
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Primary,
+            database_name: Some(format!("shard_{}", shard)),
+            shard,
+            ..Default::default()
+        });
+    }
+    config.config.sharded_tables = vec![
+        ShardedTableConfig {
+            database: "app".into(),
+            name: Some("users".into()),
+            column: "id".into(),
+            hasher: Hasher::Modulo,
+            ..Default::default()
+        },
+        ShardedTableConfig {
+            database: "app".into(),
+            schema: Some("public".into()),
+            name: Some("embeddings".into()),
+            column: "embedding".into(),
+            data_type: config::DataType::Vector,
+            centroids: vec![Vector::from(&[1.0, 2.0][..]), Vector::from(&[3.0, 4.0][..])],
+            ..Default::default()
+        },
+    ];
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+
+    context.set_config(config);
+
+    let messages = ShowShards
+        .execute()
+        .await
+        .expect("show shards execution failed");
+
+    let row_description =
+        RowDescription::from_bytes(messages[0].payload()).expect("row description should parse");
+    let columns: Vec<&str> = row_description
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            "database",
+            "schema",
+            "table",
+            "column",
+            "data_type",
+            "hasher",
+            "mapping",
+            "shards",
+            "centroids",
+            "centroids_loaded",
+        ]
+    );
+
+    let rows: Vec<Vec<String>> = messages
+        .iter()
+        .skip(1)
+        .map(|message| {
+            let row = DataRow::from_bytes(message.payload()).expect("data row should parse");
+            (0..columns.len())
+                .map(|index| row.get_text(index).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(
+        rows,
+        vec![
+            vec![
+                "app", "", "users", "id", "bigint", "modulo", "f", "2", "0", "f"
+            ],
+            vec![
+                "app",
+                "public",
+                "embeddings",
+                "embedding",
+                "vector",
+                "postgres",
+                "f",
+                "2",
+                "2",
+                "t"
+            ],
+        ]
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn show_errors_counts_errors_by_category() {
+    use crate::backend::{Error as BackendError, pool::Error as PoolError};
+    use crate::net::messages::ErrorResponse;
+    use crate::stats::errors::errors;
+
+    let error = |code: &str| {
+        Box::new(ErrorResponse {
+            code: code.into(),
+            ..Default::default()
+        })
+    };
+
+    let injected = vec![
+        BackendError::Pool(PoolError::CheckoutTimeout),
+        BackendError::ReadTimeout,
+        BackendError::ConnectionError(error("28P01")),
+        BackendError::ExecutionError(error("42P01")),
+        BackendError::ExecutionError(error("23505")),
+        BackendError::ExecutionError(error("57014")),
+        BackendError::UnexpectedMessage('X'),
+    ];
+
+    for err in &injected {
+        errors().record("show_errors_user", "show_errors_db", err.category());
+    }
+
+    let messages = ShowErrors
+        .execute()
+        .await
+        .expect("show errors execution failed");
+    let row_description = RowDescription::from_bytes(messages[0].payload())
+        .expect("row description message should parse");
+    let columns: Vec<&str> = row_description
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            "database",
+            "user",
+            "connection",
+            "timeout",
+            "query",
+            "protocol",
+            "total"
+        ]
+    );
+
+    let row = messages
+        .iter()
+        .skip(1)
+        .map(|message| DataRow::from_bytes(message.payload()).expect("data row should parse"))
+        .find(|row| row.get_text(0).as_deref() == Some("show_errors_db"))
+        .expect("errors should be reported for the database");
+
+    let counts: Vec<String> = (1..columns.len())
+        .map(|index| row.get_text(index).unwrap_or_default())
+        .collect();
+    assert_eq!(
+        counts,
+        vec!["show_errors_user", "1", "3", "2", "1", "7"],
+        "expected 1 connection, 3 timeout, 2 query and 1 protocol error"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn route_reports_shard_and_role() {
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    for shard in 0..2 {
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Primary,
+            database_name: Some(format!("shard_{}", shard)),
+            shard,
+            ..Default::default()
+        });
+    }
+    config.config.sharded_tables = vec![ShardedTableConfig {
+        database: "app".into(),
+        name: Some("users".into()),
+        column: "id".into(),
+        hasher: Hasher::Modulo,
+        ..Default::default()
+    }];
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+
+    context.set_config(config);
+
+    let route = |sql: &str| {
+        let sql = sql.to_owned();
+        async move {
+            let messages = RouteQuery::parse(&sql)
+                .expect("route should parse")
+                .execute()
+                .await
+                .expect("route execution failed");
+            assert_eq!(messages.len(), 2, "expected one row for app/alice");
+
+            let row = DataRow::from_bytes(messages[1].payload()).expect("data row should parse");
+            (0..6)
+                .map(|index| row.get_text(index).unwrap_or_default())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        route("ROUTE SELECT * FROM users WHERE id = 1").await,
+        vec!["app", "alice", "query", "1", "read", ""]
+    );
+    assert_eq!(
+        route("ROUTE SELECT * FROM users").await,
+        vec!["app", "alice", "query", "all", "read", ""]
+    );
+    assert_eq!(
+        route("ROUTE INSERT INTO users (id, email) VALUES (2, 'alice@example.com');").await,
+        vec!["app", "alice", "query", "0", "write", ""]
+    );
+    assert_eq!(
+        route("route UPDATE users SET email = 'Bob' WHERE id = 3").await,
+        vec!["app", "alice", "query", "1", "write", ""]
+    );
+
+    let error = route("ROUTE SELEKT 1").await;
+    assert_eq!(&error[..5], &["app", "alice", "", "", ""]);
+    assert!(!error[5].is_empty(), "syntax error should be reported");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn route_ignores_dry_run() {
+    use crate::frontend::router::parser::Cache;
+
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    config.config.general.dry_run = true;
+    for shard in 0..2 {
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Primary,
+            database_name: Some(format!("shard_{}", shard)),
+            shard,
+            ..Default::default()
+        });
+    }
+    config.config.sharded_tables = vec![ShardedTableConfig {
+        database: "app".into(),
+        name: Some("users".into()),
+        column: "id".into(),
+        hasher: Hasher::Modulo,
+        ..Default::default()
+    }];
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+
+    context.set_config(config);
+
+    let messages = RouteQuery::parse("ROUTE SELECT * FROM users WHERE id = 1 AND email = 'route'")
+        .expect("route should parse")
+        .execute()
+        .await
+        .expect("route execution failed");
+    let row = DataRow::from_bytes(messages[1].payload()).expect("data row should parse");
+    assert_eq!(
+        row.get_text(3).unwrap_or_default(),
+        "1",
+        "dry run shouldn't override the shard"
+    );
+
+    assert!(
+        !Cache::queries()
+            .keys()
+            .any(|query| query.contains("email = $2")),
+        "ROUTE shouldn't be recorded in the query cache"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn show_replication_reports_replica_lag() {
+    use std::time::{Duration, SystemTime};
+
+    use crate::backend::pool::LsnStats;
+    use crate::backend::replication::publisher::Lsn;
+    use pgdog_stats::{LsnStats as StatsLsnStats, ReplicaLag};
+
+    let context = TestAdminContext::new();
+
+    let mut config = ConfigAndUsers::default();
+    for (role, port) in [(Role::Primary, 5432), (Role::Replica, 5433)] {
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: "127.0.0.1".into(),
+            port,
+            role,
+            ..Default::default()
+        });
+    }
+    config.users.users.push(ConfigUser {
+        name: "alice".into(),
+        database: "app".into(),
+        password: Some("secret".into()),
+        ..Default::default()
+    });
+    context.set_config(config);
+
+    let lsn_stats = |replica: bool, lsn: i64| -> LsnStats {
+        StatsLsnStats {
+            replica,
+            lsn: Lsn::from_i64(lsn),
+            offset_bytes: lsn,
+            fetched: SystemTime::now(),
+            ..Default::default()
+        }
+        .into()
+    };
+
+    for (user, cluster) in databases().all() {
+        if user.database != "app" {
+            continue;
+        }
+        for (role, _ban, pool) in cluster.shards()[0].pools_with_roles_and_bans() {
+            match role {
+                Role::Replica => pool.set_lsn_stats(
+                    lsn_stats(true, 1000),
+                    ReplicaLag {
+                        duration: Duration::from_millis(1500),
+                        bytes: 1000,
+                    },
+                ),
+                _ => pool.set_lsn_stats(lsn_stats(false, 2000), ReplicaLag::default()),
+            }
+        }
+    }
+
+    let messages = ShowReplication
+        .execute()
+        .await
+        .expect("show replication execution failed");
+
+    let row_description =
+        RowDescription::from_bytes(messages[0].payload()).expect("row description should parse");
+    let columns: Vec<&str> = row_description
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            "id",
+            "database",
+            "user",
+            "addr",
+            "port",
+            "shard",
+            "role",
+            "replica_lag",
+            "pg_lsn",
+            "lsn_age",
+            "pg_is_in_recovery",
+            "replica_lag_bytes",
+        ]
+    );
+
+    let mut rows: Vec<Vec<String>> = messages
+        .iter()
+        .skip(1)
+        .map(|message| {
+            let row = DataRow::from_bytes(message.payload()).expect("data row should parse");
+            (0..columns.len())
+                .map(|index| row.get_text(index).unwrap_or_default())
+                .collect::<Vec<_>>()
+        })
+        .filter(|row| row[1] == "app")
+        .collect();
+    rows.sort_by(|a, b| a[4].cmp(&b[4]));
+
+    assert_eq!(rows.len(), 2);
+
+    // Skip id and lsn_age, they change between runs.
+    let stable = |row: &Vec<String>| {
+        row.iter()
+            .enumerate()
+            .filter(|(index, _)| *index != 0 && *index != 9)
+            .map(|(_, column)| column.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        stable(&rows[0]),
+        vec![
+            "app",
+            "alice",
+            "127.0.0.1",
+            "5432",
+            "0",
+            "primary",
+            "0",
+            "0/7D0",
+            "f",
+            "0"
+        ]
+    );
+    assert_eq!(
+        stable(&rows[1]),
+        vec![
+            "app",
+            "alice",
+            "127.0.0.1",
+            "5433",
+            "0",
+            "replica",
+            "1500",
+            "0/3E8",
+            "t",
+            "1000"
+        ]
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn show_tags_counts_tagged_queries() {
+    use crate::stats::tags::tags;
+
+    tags().record("show_tags_user", "show_tags_db", "show-tags-checkout");
+    tags().record("show_tags_user", "show_tags_db", "show-tags-checkout");
+    tags().record("show_tags_user", "show_tags_db", "show-tags-billing");
+
+    let messages = ShowTags
+        .execute()
+        .await
+        .expect("show tags execution failed");
+    let row_description = RowDescription::from_bytes(messages[0].payload())
+        .expect("row description message should parse");
+    let columns: Vec<&str> = row_description
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(columns, vec!["tag", "database", "user", "queries"]);
+
+    let rows: Vec<Vec<String>> = messages
+        .iter()
+        .skip(1)
+        .map(|message| DataRow::from_bytes(message.payload()).expect("data row should parse"))
+        .filter(|row| row.get_text(1).as_deref() == Some("show_tags_db"))
+        .map(|row| {
+            (0..columns.len())
+                .map(|index| row.get_text(index).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec!["show-tags-billing", "show_tags_db", "show_tags_user", "1"],
+            vec!["show-tags-checkout", "show_tags_db", "show_tags_user", "2"],
+        ]
+    );
+}
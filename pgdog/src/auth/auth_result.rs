@@ -19,6 +19,12 @@ pub enum AuthResult {
     NoUserOrDatabase,
     /// Client didn't provide password message.
     NoPasswordMessage,
+    /// Client didn't present a TLS certificate.
+    NoCertificate,
+    /// GSSAPI security context couldn't be established.
+    NoGssapi,
+    /// GSSAPI principal doesn't match the user.
+    NoGssapiPrincipal,
 }
 
 impl AuthResult {
@@ -46,6 +52,9 @@ impl Display for AuthResult {
             }
             Self::NoUserOrDatabase => write!(f, "no user or database in config"),
             Self::NoPasswordMessage => write!(f, "client did not send password message"),
+            Self::NoCertificate => write!(f, "client did not present a certificate"),
+            Self::NoGssapi => write!(f, "gssapi authentication failed"),
+            Self::NoGssapiPrincipal => write!(f, "gssapi principal does not match user"),
         }
     }
 }
@@ -0,0 +1,42 @@
+//! Client certificate authentication.
+
+use super::AuthResult;
+
+/// Check that the client certificate identity (SAN dNSName, falling back
+/// to Subject CN) is the `identity` configured for the user.
+///
+/// Connections without TLS don't have a certificate and are rejected.
+pub fn authenticate(identity: Option<&str>, expected: &str) -> AuthResult {
+    match identity {
+        Some(identity) if identity == expected => AuthResult::Ok,
+        Some(_) => AuthResult::NoIdentity,
+        None => AuthResult::NoCertificate,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rustls_pki_types::{CertificateDer, pem::PemObject};
+
+    use super::*;
+    use crate::net::tls::identity_from_certs;
+
+    #[test]
+    fn test_cert_maps_to_identity() {
+        let pem = include_str!("../../tests/tls/cert.pem");
+        let certs = CertificateDer::pem_slice_iter(pem.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let identity = identity_from_certs(&certs);
+
+        assert!(authenticate(identity.as_deref(), "CommonNameOrHostname").is_ok());
+        assert!(matches!(
+            authenticate(identity.as_deref(), "unmapped.example.com"),
+            AuthResult::NoIdentity
+        ));
+        assert!(matches!(
+            authenticate(None, "CommonNameOrHostname"),
+            AuthResult::NoCertificate
+        ));
+    }
+}
@@ -7,4 +7,10 @@ pub enum Error {
 
     #[error("server-side auth can only use one password")]
     ServerSideOnePassword,
+
+    #[error("gssapi: {0}")]
+    Gssapi(String),
+
+    #[error("gssapi support is not enabled in this build")]
+    GssapiNotEnabled,
 }
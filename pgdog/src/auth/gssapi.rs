@@ -0,0 +1,290 @@
+//! GSSAPI (Kerberos) authentication.
+//!
+//! The Kerberos acceptor requires PgDog to be built with the `gssapi` feature.
+//! The service keytab is found using the standard `KRB5_KTNAME` environment variable.
+
+use bytes::Bytes;
+use tracing::{debug, error};
+
+use crate::frontend::Error;
+use crate::net::Stream;
+use crate::net::messages::*;
+
+use super::{AuthResult, Error as AuthError};
+
+/// Server side of a GSSAPI security context.
+pub trait Acceptor: Send {
+    /// Process a token received from the client, returning
+    /// the token to send back, if any.
+    fn step(&mut self, token: &[u8]) -> Result<Option<Vec<u8>>, AuthError>;
+
+    /// The security context is established.
+    fn is_complete(&self) -> bool;
+
+    /// Principal the client authenticated as, e.g. `alice@EXAMPLE.COM`.
+    fn principal(&mut self) -> Result<String, AuthError>;
+}
+
+/// Kerberos acceptor using the system GSSAPI library.
+#[cfg(feature = "gssapi")]
+pub struct Kerberos {
+    ctx: libgssapi::context::ServerCtx,
+}
+
+#[cfg(feature = "gssapi")]
+impl Kerberos {
+    /// Acquire acceptor credentials from the keytab.
+    pub fn new() -> Result<Self, AuthError> {
+        use libgssapi::credential::{Cred, CredUsage};
+        use libgssapi::oid::{GSS_MECH_KRB5, OidSet};
+
+        let gssapi = |err: libgssapi::error::Error| AuthError::Gssapi(err.to_string());
+
+        let mut mechs = OidSet::new().map_err(gssapi)?;
+        mechs.add(&GSS_MECH_KRB5).map_err(gssapi)?;
+        let cred = Cred::acquire(None, None, CredUsage::Accept, Some(&mechs)).map_err(gssapi)?;
+
+        Ok(Self {
+            ctx: libgssapi::context::ServerCtx::new(Some(cred)),
+        })
+    }
+}
+
+#[cfg(feature = "gssapi")]
+impl Acceptor for Kerberos {
+    fn step(&mut self, token: &[u8]) -> Result<Option<Vec<u8>>, AuthError> {
+        self.ctx
+            .step(token)
+            .map(|token| token.map(|token| token.to_vec()))
+            .map_err(|err| AuthError::Gssapi(err.to_string()))
+    }
+
+    fn is_complete(&self) -> bool {
+        use libgssapi::context::SecurityContext;
+
+        self.ctx.is_complete()
+    }
+
+    fn principal(&mut self) -> Result<String, AuthError> {
+        use libgssapi::context::SecurityContext;
+
+        self.ctx
+            .source_name()
+            .map(|name| name.to_string())
+            .map_err(|err| AuthError::Gssapi(err.to_string()))
+    }
+}
+
+/// Create the acceptor used for client connections.
+pub fn acceptor() -> Result<Box<dyn Acceptor>, AuthError> {
+    #[cfg(feature = "gssapi")]
+    {
+        Ok(Box::new(Kerberos::new()?))
+    }
+
+    #[cfg(not(feature = "gssapi"))]
+    {
+        Err(AuthError::GssapiNotEnabled)
+    }
+}
+
+/// GSSAPI authentication server.
+pub struct Server<A: Acceptor + ?Sized> {
+    acceptor: Box<A>,
+}
+
+impl<A: Acceptor + ?Sized> Server<A> {
+    /// Create new server driving the given acceptor.
+    pub fn new(acceptor: Box<A>) -> Self {
+        Self { acceptor }
+    }
+
+    /// Establish the security context and check that the client
+    /// principal, from one of the allowed `realms`, matches the user it's connecting as.
+    pub async fn handle(
+        mut self,
+        stream: &mut Stream,
+        user: &str,
+        realms: &[String],
+    ) -> Result<AuthResult, Error> {
+        stream.send_flush(&Authentication::Gssapi).await?;
+
+        loop {
+            let message = stream.read().await?;
+            let response = match message.code() {
+                'p' => GssResponse::from_bytes(message.to_bytes())?,
+                c => return Err(Error::UnexpectedMessage(c)),
+            };
+
+            let reply = match self.acceptor.step(&response.token) {
+                Ok(reply) => reply,
+                Err(err) => {
+                    error!("{}", err);
+                    return Ok(AuthResult::NoGssapi);
+                }
+            };
+
+            if let Some(reply) = reply.filter(|reply| !reply.is_empty()) {
+                stream
+                    .send_flush(&Authentication::GssapiContinue(Bytes::from(reply)))
+                    .await?;
+            }
+
+            if self.acceptor.is_complete() {
+                break;
+            }
+        }
+
+        let principal = match self.acceptor.principal() {
+            Ok(principal) => principal,
+            Err(err) => {
+                error!("{}", err);
+                return Ok(AuthResult::NoGssapi);
+            }
+        };
+
+        debug!(
+            r#"gssapi principal "{}" connecting as "{}""#,
+            principal, user
+        );
+
+        if principal_user(&principal, realms) == Some(user) {
+            Ok(AuthResult::Ok)
+        } else {
+            Ok(AuthResult::NoGssapiPrincipal)
+        }
+    }
+}
+
+/// Principal name without the realm, if the realm is allowed.
+/// Realms are case-sensitive.
+fn principal_user<'a>(principal: &'a str, realms: &[String]) -> Option<&'a str> {
+    let (user, realm) = principal.rsplit_once('@')?;
+    realms
+        .iter()
+        .any(|allowed| allowed == realm)
+        .then_some(user)
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    /// Accepts the context after two round trips.
+    struct MockAcceptor {
+        steps: usize,
+        principal: String,
+    }
+
+    impl Acceptor for MockAcceptor {
+        fn step(&mut self, token: &[u8]) -> Result<Option<Vec<u8>>, AuthError> {
+            if token != format!("client-{}", self.steps).as_bytes() {
+                return Err(AuthError::Gssapi("unexpected token".into()));
+            }
+            self.steps += 1;
+            Ok(Some(format!("server-{}", self.steps).into_bytes()))
+        }
+
+        fn is_complete(&self) -> bool {
+            self.steps == 2
+        }
+
+        fn principal(&mut self) -> Result<String, AuthError> {
+            Ok(self.principal.clone())
+        }
+    }
+
+    async fn read_auth(conn: &mut TcpStream) -> Authentication {
+        let code = conn.read_u8().await.unwrap();
+        let len = conn.read_i32().await.unwrap();
+        let mut payload = vec![0u8; len as usize - 4];
+        conn.read_exact(&mut payload).await.unwrap();
+
+        let mut bytes = vec![code];
+        bytes.extend_from_slice(&len.to_be_bytes());
+        bytes.extend(payload);
+
+        Authentication::from_bytes(Bytes::from(bytes)).unwrap()
+    }
+
+    async fn handshake(principal: &str, tokens: &[&str]) -> AuthResult {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let tokens = tokens.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        let client = tokio::spawn(async move {
+            let mut conn = TcpStream::connect(addr).await.unwrap();
+            assert!(matches!(read_auth(&mut conn).await, Authentication::Gssapi));
+
+            for (i, token) in tokens.iter().enumerate() {
+                conn.write_all(&GssResponse::new(token.clone().into_bytes()).to_bytes())
+                    .await
+                    .unwrap();
+                match read_auth(&mut conn).await {
+                    Authentication::GssapiContinue(reply) => {
+                        assert_eq!(reply.as_ref(), format!("server-{}", i + 1).as_bytes())
+                    }
+                    auth => panic!("unexpected {:?}", auth),
+                }
+            }
+        });
+
+        let (conn, _) = listener.accept().await.unwrap();
+        let mut stream = Stream::plain(conn, 4096);
+
+        let server = Server::new(Box::new(MockAcceptor {
+            steps: 0,
+            principal: principal.to_string(),
+        }));
+        let result = server
+            .handle(&mut stream, "alice", &["EXAMPLE.COM".to_string()])
+            .await
+            .unwrap();
+        client.await.unwrap();
+
+        result
+    }
+
+    #[tokio::test]
+    async fn test_gssapi_handshake() {
+        let result = handshake("alice@EXAMPLE.COM", &["client-0", "client-1"]).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_gssapi_wrong_principal() {
+        let result = handshake("bob@EXAMPLE.COM", &["client-0", "client-1"]).await;
+        assert!(matches!(result, AuthResult::NoGssapiPrincipal));
+    }
+
+    #[tokio::test]
+    async fn test_gssapi_foreign_realm() {
+        let result = handshake("alice@ATTACKER.REALM", &["client-0", "client-1"]).await;
+        assert!(matches!(result, AuthResult::NoGssapiPrincipal));
+    }
+
+    #[cfg(feature = "gssapi")]
+    #[test]
+    #[ignore = "requires a KDC and a service keytab in KRB5_KTNAME"]
+    fn test_kerberos_acquire_credentials() {
+        let acceptor = Kerberos::new().unwrap();
+        assert!(!acceptor.is_complete());
+    }
+
+    #[test]
+    fn test_principal_user() {
+        let realms = ["EXAMPLE.COM".to_string()];
+        assert_eq!(principal_user("alice@EXAMPLE.COM", &realms), Some("alice"));
+        assert_eq!(principal_user("alice@example.com", &realms), None);
+        assert_eq!(principal_user("alice@ATTACKER.REALM", &realms), None);
+        assert_eq!(
+            principal_user("alice@EXAMPLE.COM@ATTACKER.REALM", &realms),
+            None
+        );
+        assert_eq!(principal_user("alice", &realms), None);
+        assert_eq!(principal_user("alice@EXAMPLE.COM", &[]), None);
+    }
+}
@@ -1,7 +1,9 @@
 //! PostgreSQL authentication mechanisms.
 
 pub mod auth_result;
+pub mod cert;
 pub mod error;
+pub mod gssapi;
 pub mod md5;
 pub mod scram;
 pub mod vault;
@@ -53,7 +53,7 @@ enum ScramFinal<'a> {
     Hashed(ClientFinal<'a, HashedPassword>),
 }
 
-use base64::prelude::*;
+use super::Verifier;
 
 impl AuthenticationProvider for UserPassword {
     fn get_password_for(&self, _user: &str) -> Option<PasswordInfo> {
@@ -74,25 +74,14 @@ impl AuthenticationProvider for UserPassword {
 
 impl AuthenticationProvider for HashedPassword {
     fn get_password_for(&self, _user: &str) -> Option<PasswordInfo> {
-        let mut parts = self.hash.split('$');
-
-        if parts.next()? != "SCRAM-SHA-256" {
-            return None;
-        }
-
-        let iter_salt = parts.next()?;
-        let keys_part = parts.next()?;
-
-        let mut is = iter_salt.split(':');
-        let iterations: u16 = is.next()?.parse().ok()?;
-        let salt = BASE64_STANDARD.decode(is.next()?).ok()?;
-
-        let mut ks = keys_part.split(':');
-        let stored_key = BASE64_STANDARD.decode(ks.next()?).ok()?;
-        let server_key = BASE64_STANDARD.decode(ks.next()?).ok()?;
+        let verifier = Verifier::parse(&self.hash)?;
+        let iterations = u16::try_from(verifier.iterations.get()).ok()?;
 
         Some(PasswordInfo::from_stored_keys(
-            stored_key, server_key, iterations, salt,
+            verifier.stored_key,
+            verifier.server_key,
+            iterations,
+            verifier.salt,
         ))
     }
 }
@@ -0,0 +1,122 @@
+//! SCRAM-SHA-256 verifier, e.g. `rolpassword` from `pg_authid`.
+
+use std::fmt::Display;
+use std::num::NonZeroU32;
+
+use aws_lc_rs::digest;
+use aws_lc_rs::hmac::{self, HMAC_SHA256};
+use base64::prelude::*;
+
+use crate::util::constant_time_eq;
+
+/// Parsed `SCRAM-SHA-256$iterations:salt$StoredKey:ServerKey` string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verifier {
+    pub iterations: NonZeroU32,
+    pub salt: Vec<u8>,
+    pub stored_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+impl Verifier {
+    /// Derive the verifier from a plaintext password.
+    pub fn derive(password: &str, iterations: NonZeroU32, salt: &[u8]) -> Self {
+        let (stored_key, server_key) = keys(password, iterations, salt);
+
+        Self {
+            iterations,
+            salt: salt.to_vec(),
+            stored_key,
+            server_key,
+        }
+    }
+
+    /// Parse the verifier. Returns `None` if it's not a SCRAM-SHA-256 verifier.
+    pub fn parse(verifier: &str) -> Option<Self> {
+        let mut parts = verifier.split('$');
+
+        if parts.next()? != "SCRAM-SHA-256" {
+            return None;
+        }
+
+        let (iterations, salt) = parts.next()?.split_once(':')?;
+        let (stored_key, server_key) = parts.next()?.split_once(':')?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            iterations: iterations.parse().ok()?,
+            salt: BASE64_STANDARD.decode(salt).ok()?,
+            stored_key: BASE64_STANDARD.decode(stored_key).ok()?,
+            server_key: BASE64_STANDARD.decode(server_key).ok()?,
+        })
+    }
+
+    /// Check a plaintext password, e.g. the one received
+    /// during passthrough authentication, against this verifier.
+    pub fn verify(&self, password: &str) -> bool {
+        let (stored_key, server_key) = keys(password, self.iterations, &self.salt);
+
+        constant_time_eq(&stored_key, &self.stored_key)
+            && constant_time_eq(&server_key, &self.server_key)
+    }
+}
+
+impl Display for Verifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SCRAM-SHA-256${}:{}${}:{}",
+            self.iterations,
+            BASE64_STANDARD.encode(&self.salt),
+            BASE64_STANDARD.encode(&self.stored_key),
+            BASE64_STANDARD.encode(&self.server_key),
+        )
+    }
+}
+
+/// Compute StoredKey and ServerKey (RFC 5802).
+fn keys(password: &str, iterations: NonZeroU32, salt: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let salted_password = scram::hash_password(password, iterations, salt);
+    let key = hmac::Key::new(HMAC_SHA256, &salted_password);
+    let client_key = hmac::sign(&key, b"Client Key");
+    let server_key = hmac::sign(&key, b"Server Key");
+    let stored_key = digest::digest(&digest::SHA256, client_key.as_ref());
+
+    (stored_key.as_ref().to_vec(), server_key.as_ref().to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCRAM_HASH: &str = "SCRAM-SHA-256$4096:B6lJyg12n6SawAu1kD9maA==$huWaU6t+WsvcS9ZrDvocZeYtlLJ60hdP46tjszFBbW0=:706OTwYyqH5WpfNpZdgt0gxuP5ff4DPUpHYu3F3w6TY=";
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let verifier = Verifier::parse(SCRAM_HASH).unwrap();
+        assert_eq!(verifier.iterations.get(), 4096);
+        assert_eq!(verifier.to_string(), SCRAM_HASH);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Verifier::parse("md5abcdef").is_none());
+        assert!(Verifier::parse("SCRAM-SHA-256$4096:salt").is_none());
+        assert!(Verifier::parse("SCRAM-SHA-256$0:c2FsdA==$a2V5:a2V5").is_none());
+        assert!(Verifier::parse("SCRAM-SHA-256$4096:!!!$a2V5:a2V5").is_none());
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let iterations = NonZeroU32::new(4096).unwrap();
+        let verifier = Verifier::derive("pgdog", iterations, b"pgdog_test_salt!");
+        let verifier = Verifier::parse(&verifier.to_string()).unwrap();
+
+        assert!(verifier.verify("pgdog"));
+        assert!(!verifier.verify("wrong"));
+        assert!(!verifier.verify(""));
+    }
+}
@@ -62,6 +62,7 @@ mod tests {
             server_iam_region: None,
             vault_path: Default::default(),
             vault_refresh_percent: None,
+            healthcheck_query: None,
             configured_role: Role::Auto,
         };
 
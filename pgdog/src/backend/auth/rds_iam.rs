@@ -103,6 +103,7 @@ mod tests {
             server_iam_region: Some("us-east-1".into()),
             vault_path: Default::default(),
             vault_refresh_percent: None,
+            healthcheck_query: None,
             configured_role: Role::Auto,
         }
     }
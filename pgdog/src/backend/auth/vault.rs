@@ -189,6 +189,7 @@ mod tests {
             vault_path: vault_path.map(Into::into),
             vault_refresh_percent: None,
             database_number: 0,
+            healthcheck_query: None,
             configured_role: Role::Primary,
         }
     }
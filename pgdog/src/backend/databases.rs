@@ -18,6 +18,7 @@ use pgdog_config::{
 use tracing::{debug, error, info, warn};
 
 use crate::auth::AuthResult;
+use crate::auth::scram::Verifier;
 use crate::backend::replication::ShardedSchemas;
 use crate::config::PoolerMode;
 use crate::frontend::PreparedStatements;
@@ -37,6 +38,7 @@ use super::{
     pool::{Address, ClusterConfig, Config},
     reload_notify,
     replication::ReplicationConfig,
+    service_discovery,
 };
 
 static DATABASES: Lazy<ArcSwap<Databases>> =
@@ -98,6 +100,29 @@ pub fn reload_from_existing() -> Result<(), Error> {
     Ok(())
 }
 
+/// Apply pool size changes from the current config
+/// to running pools, without closing connections in use.
+pub fn resize() {
+    let config = config();
+    let resized = from_config(&config);
+    let databases = databases();
+
+    for (user, cluster) in databases.all() {
+        let Some(target) = resized.all().get(user) else {
+            continue;
+        };
+
+        for (shard, target) in cluster.shards().iter().zip(target.shards()) {
+            let targets = target.pools();
+            for pool in shard.pools() {
+                if let Some(target) = targets.iter().find(|t| t.addr() == pool.addr()) {
+                    pool.resize(target.config().min, target.config().max);
+                }
+            }
+        }
+    }
+}
+
 /// Initialize the databases for the first time.
 pub fn init() -> Result<(), Error> {
     let config = config();
@@ -105,6 +130,7 @@ pub fn init() -> Result<(), Error> {
 
     // Resize query cache
     Cache::resize(config.config.general.query_cache_limit);
+    Cache::set_ttl(config.config.general.query_cache_ttl());
 
     // Start two-pc manager.
     let _monitor = Manager::get();
@@ -153,6 +179,7 @@ pub fn reload() -> Result<(), Error> {
 
     // Resize query cache.
     Cache::resize(new_config.config.general.query_cache_limit);
+    Cache::set_ttl(new_config.config.general.query_cache_ttl());
 
     Ok(())
 }
@@ -181,13 +208,7 @@ pub(crate) fn add(user: ConfigUser) -> Result<AuthResult, Error> {
 
     // User already exists in users.toml.
     if let Some(mut existing) = existing {
-        // Password hasn't been set yet.
-        if existing.password.is_none() {
-            existing.password = user.password.clone();
-            add_user(existing)?;
-            reload_from_existing()?;
-            Ok(AuthResult::Ok)
-        } else if existing
+        if existing
             .password
             .as_deref()
             .zip(user.password.as_deref())
@@ -197,6 +218,25 @@ pub(crate) fn add(user: ConfigUser) -> Result<AuthResult, Error> {
         {
             // Passwords match.
             Ok(AuthResult::Ok)
+        } else if let Some(verifier) = existing.password_hash.as_deref().and_then(Verifier::parse)
+            && let Some(provided) = user.password.as_deref()
+        {
+            // Password is stored as a SCRAM verifier, so we never
+            // need the plaintext in users.toml.
+            if verifier.verify(provided) {
+                existing.password = user.password.clone();
+                add_user(existing)?;
+                reload_from_existing()?;
+                Ok(AuthResult::Ok)
+            } else {
+                Ok(AuthResult::NoPasswordMatch)
+            }
+        } else if existing.password.is_none() {
+            // Password hasn't been set yet.
+            existing.password = user.password.clone();
+            add_user(existing)?;
+            reload_from_existing()?;
+            Ok(AuthResult::Ok)
         } else if config.config.general.passthrough_auth.allows_change() {
             // Passwords don't match but we can change it.
             existing.password = user.password.clone();
@@ -514,6 +554,7 @@ fn resolve_sharded_table(
         data_type: config.data_type,
         centroid_probes: config.centroid_probes,
         hasher: config.hasher.clone(),
+        hasher_seed: config.hasher_seed,
         mapping: mapping.flatten(),
     }
 }
@@ -637,6 +678,7 @@ fn new_pool(user: &crate::config::User, config: &crate::config::Config) -> Optio
 
 /// Load databases from config.
 pub fn from_config(config: &ConfigAndUsers) -> Databases {
+    let config = service_discovery::expand(config);
     let mut databases = HashMap::new();
 
     for user in &config.users.users {
@@ -765,6 +807,7 @@ pub fn from_config(config: &ConfigAndUsers) -> Databases {
                     .exposure
                     .unwrap_or(config.config.general.mirror_exposure),
                 level: mirror.level,
+                checksum: mirror.checksum,
             };
             mirror_configs.insert(
                 (mirror.source_db.clone(), mirror.destination_db.clone()),
@@ -901,6 +944,48 @@ mod tests {
         assert_eq!(found.unwrap().password, Some("new_pass".to_string()));
     }
 
+    fn make_user_with_verifier(name: &str, password: &str) -> ConfigUser {
+        let iterations = std::num::NonZeroU32::new(4096).unwrap();
+        ConfigUser {
+            password_hash: Some(crate::auth::scram::generate_hash(
+                password,
+                iterations,
+                b"pgdog_test_salt!",
+            )),
+            ..make_user(name, None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_existing_user_scram_verifier() {
+        setup_config(
+            crate::config::PassthroughAuth::EnabledPlain,
+            vec![make_user_with_verifier("erin", "secret")],
+        );
+
+        let result = add(make_user("erin", Some("secret")));
+        assert!(result.unwrap().is_ok());
+
+        let config = crate::config::config();
+        let found = config.users.find(&make_user("erin", None));
+        assert_eq!(found.unwrap().password, Some("secret".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_add_existing_user_scram_verifier_wrong_password() {
+        setup_config(
+            crate::config::PassthroughAuth::EnabledPlainAllowChange,
+            vec![make_user_with_verifier("frank", "secret")],
+        );
+
+        let result = add(make_user("frank", Some("wrong")));
+        assert_eq!(result.unwrap(), AuthResult::NoPasswordMatch);
+
+        let config = crate::config::config();
+        let found = config.users.find(&make_user("frank", None));
+        assert_eq!(found.unwrap().password, None);
+    }
+
     #[test]
     fn test_mirror_user_isolation() {
         // Test that each user gets their own mirror cluster
@@ -1874,6 +1959,99 @@ mod tests {
         assert_eq!(databases.all().len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_per_shard_credentials() {
+        let config = Config {
+            databases: vec![
+                Database {
+                    name: "db1".to_string(),
+                    host: "localhost".to_string(),
+                    port: 5432,
+                    role: Role::Primary,
+                    shard: 0,
+                    user: Some("shard_0_user".to_string()),
+                    password: Some("shard_0_pass".to_string()),
+                    ..Default::default()
+                },
+                Database {
+                    name: "db1".to_string(),
+                    host: "localhost".to_string(),
+                    port: 5433,
+                    role: Role::Primary,
+                    shard: 1,
+                    password: Some("shard_1_pass".to_string()),
+                    ..Default::default()
+                },
+                Database {
+                    name: "db1".to_string(),
+                    host: "localhost".to_string(),
+                    port: 5434,
+                    role: Role::Replica,
+                    shard: 1,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let users = crate::config::Users {
+            users: vec![crate::config::User {
+                name: "alice".to_string(),
+                database: "db1".to_string(),
+                password: Some("alice_pass".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let databases = from_config(&ConfigAndUsers {
+            config,
+            users,
+            config_path: std::path::PathBuf::new(),
+            users_path: std::path::PathBuf::new(),
+            ..Default::default()
+        });
+
+        let cluster = databases.cluster(("alice", "db1")).unwrap();
+        assert_eq!(cluster.shards().len(), 2);
+
+        let mut credentials = vec![];
+        for shard in cluster.shards() {
+            for (role, pool) in shard.pools_with_roles() {
+                let (user, passwords) = pool.addr().auth_credentials().await.unwrap();
+                let passwords = passwords.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                credentials.push((pool.addr().port, role, user, passwords));
+            }
+        }
+        credentials.sort_by_key(|(port, ..)| *port);
+
+        assert_eq!(
+            credentials,
+            vec![
+                (
+                    5432,
+                    Role::Primary,
+                    "shard_0_user".to_string(),
+                    vec!["shard_0_pass".to_string()]
+                ),
+                // User falls back to users.toml, password is overridden.
+                (
+                    5433,
+                    Role::Primary,
+                    "alice".to_string(),
+                    vec!["shard_1_pass".to_string()]
+                ),
+                // Nothing overridden for this database.
+                (
+                    5434,
+                    Role::Replica,
+                    "alice".to_string(),
+                    vec!["alice_pass".to_string()]
+                ),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_cutover_persists_to_disk() {
         use tempfile::TempDir;
@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 use crate::net::messages::ErrorResponse;
+use crate::stats::errors::ErrorCategory;
 
 use super::databases::User;
 
@@ -135,6 +136,12 @@ pub enum Error {
     #[error("2pc commit supported with multi-shard binding only")]
     TwoPcMultiShardOnly,
 
+    #[error("pg_export_snapshot() returned no snapshot")]
+    SnapshotExport,
+
+    #[error("cross_shard_snapshot doesn't support serializable transactions")]
+    SnapshotSerializable,
+
     #[error("unsupported aggregation {function}: {reason}")]
     UnsupportedAggregation { function: String, reason: String },
 
@@ -160,6 +167,7 @@ impl Error {
             Error::Pool(PoolError::CheckoutTimeout) => true,
             Error::Pool(PoolError::AllReplicasDown) => true,
             Error::Pool(PoolError::Banned) => true,
+            Error::Pool(PoolError::ReplicaNotFound) => true,
             _ => false,
         }
     }
@@ -194,4 +202,50 @@ impl Error {
             _ => false,
         }
     }
+
+    /// Postgres refused the connection because it has no connection slots left.
+    pub fn is_out_of_connections(&self) -> bool {
+        matches!(self, Self::ConnectionError(err) if err.is_too_many_connections())
+    }
+
+    /// Category reported by `SHOW ERRORS`.
+    pub fn category(&self) -> ErrorCategory {
+        use crate::backend::pool::Error as PoolError;
+
+        match self {
+            Self::Pool(
+                PoolError::CheckoutTimeout
+                | PoolError::ConnectTimeout
+                | PoolError::ReplicaCheckoutTimeout
+                | PoolError::HealthcheckTimeout,
+            )
+            | Self::ReadTimeout => ErrorCategory::Timeout,
+            Self::ExecutionError(err) | Self::PreparedStatementError(err) => {
+                ErrorCategory::from(err.as_ref())
+            }
+            // Postgres refused the connection, e.g. bad password.
+            Self::ConnectionError(err) => match ErrorCategory::from(err.as_ref()) {
+                ErrorCategory::Query => ErrorCategory::Connection,
+                category => category,
+            },
+            Self::Net(err) if !err.is_retryable() => ErrorCategory::Protocol,
+            Self::UnexpectedMessage(_)
+            | Self::UnexpectedTransactionStatus(_)
+            | Self::NotInSync
+            | Self::ProtocolOutOfSync
+            | Self::ExpectedParseComplete(_)
+            | Self::ExpectedCloseComplete(_)
+            | Self::DecoderRowError
+            | Self::UnsupportedHandleIgnore(_) => ErrorCategory::Protocol,
+            Self::FrontendError(err) => err.category().unwrap_or(ErrorCategory::Query),
+            Self::Router(_)
+            | Self::Type(_)
+            | Self::UnsupportedAggregation { .. }
+            | Self::PreparedStatementMissing(_)
+            | Self::MultiShard(_)
+            | Self::TwoPcMultiShardOnly
+            | Self::SnapshotExport => ErrorCategory::Query,
+            _ => ErrorCategory::Connection,
+        }
+    }
 }
@@ -15,6 +15,7 @@ pub mod replication;
 pub mod schema;
 pub mod server;
 pub mod server_options;
+pub mod service_discovery;
 pub mod stats;
 pub mod validation;
 
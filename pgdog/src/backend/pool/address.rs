@@ -43,6 +43,9 @@ pub struct Address {
     /// Role given to the database at configuration time.
     /// For automatic roles, this can change at runtime.
     pub configured_role: Role,
+    /// Query used for health checks, if not the default.
+    #[serde(default)]
+    pub healthcheck_query: Option<String>,
 }
 
 impl From<Address> for pgdog_stats::Address {
@@ -99,6 +102,7 @@ impl Address {
             vault_refresh_percent: user.vault_refresh_percent,
             database_number,
             configured_role: database.role,
+            healthcheck_query: database.healthcheck_query.clone(),
         }
     }
 
@@ -213,6 +217,7 @@ impl Address {
             vault_refresh_percent: None,
             database_number: 0,
             configured_role: Role::Primary,
+            healthcheck_query: None,
         }
     }
 }
@@ -14,11 +14,15 @@ static PREPARED: Lazy<Vec<Query>> = Lazy::new(|| vec![Query::new("DEALLOCATE ALL
 static DIRTY: Lazy<Vec<Query>> = Lazy::new(|| {
     vec![
         Query::new("RESET ALL"),                       // Reset all parameters.
+        Query::new("RESET ROLE"),                      // Not reset by RESET ALL.
         Query::new("SELECT pg_advisory_unlock_all()"), // Remove all advisory locks.
         Query::new("DISCARD TEMP"),                    // Drop all temporary tables.
     ]
 });
 
+/// Role set with `SET ROLE` must not leak to the next client.
+static ROLE: Lazy<Vec<Query>> = Lazy::new(|| vec![Query::new("RESET ROLE")]);
+
 static ALL: Lazy<Vec<Query>> =
     Lazy::new(|| vec!["DISCARD ALL"].into_iter().map(Query::new).collect());
 static NONE: Lazy<Vec<Query>> = Lazy::new(Vec::new);
@@ -67,6 +71,8 @@ impl Cleanup {
             Self::all()
         } else if server.dirty() {
             Self::parameters()
+        } else if server.role_changed() {
+            Self::role()
         } else if server.schema_changed() {
             Self::prepared_statements()
         } else {
@@ -101,6 +107,16 @@ impl Cleanup {
         }
     }
 
+    /// Reset the role. Tracked parameters need to be synchronized
+    /// again, since they could include the role as well.
+    pub fn role() -> Self {
+        Self {
+            queries: &*ROLE,
+            dirty: true,
+            ..Default::default()
+        }
+    }
+
     /// Cleanup everything.
     pub fn all() -> Self {
         Self {
@@ -1,10 +1,11 @@
 //! A collection of replicas and a primary.
 
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
 use parking_lot::Mutex;
 use pgdog_config::{
-    LoadSchema, PreparedStatements, QueryParser, QueryParserEngine, QueryParserLevel, Rewrite,
-    RewriteMode, users::PasswordKind,
+    LoadSchema, MultiStatementQueries, ParseFailures, PreparedStatements, QueryParser,
+    QueryParserEngine, QueryParserLevel, Rewrite, RewriteMode, SearchPathConflict,
+    UnknownStatements, users::PasswordKind,
 };
 use std::{sync::Arc, time::Duration};
 
@@ -16,14 +17,15 @@ use crate::{
         replication::{ReplicationConfig, ShardedSchemas},
     },
     config::{
-        ConnectionRecovery, MultiTenant, PoolerMode, ReadWriteSplit, ReadWriteStrategy, User,
+        ConnectionRecovery, MultiTenant, PoolerMode, ReadWriteSplit, ReadWriteStrategy,
+        TemporaryTables, User,
     },
     frontend::{ClientRequest, RegexParser},
     net::{Query, messages::FrontendPid},
 };
 
 use super::{
-    Address, Config, Error, Guard, MirrorStats, Request, Shard, ShardConfig,
+    Address, Config, Error, Guard, MirrorStats, RateLimiter, Request, Shard, ShardConfig,
     cluster_launch::Readiness,
 };
 use crate::config::LoadBalancingStrategy;
@@ -49,11 +51,18 @@ pub struct Cluster {
     sharded_schemas: ShardedSchemas,
     replication_sharding: Option<String>,
     multi_tenant: Option<MultiTenant>,
+    multi_tenant_filter: bool,
     rw_strategy: ReadWriteStrategy,
     rw_split: ReadWriteSplit,
     schema_admin: bool,
     stats: Arc<Mutex<MirrorStats>>,
     cross_shard_disabled: bool,
+    cross_shard_transactions_disabled: bool,
+    atomic_truncate: bool,
+    require_sharding_key: bool,
+    multi_statement_queries: MultiStatementQueries,
+    unknown_statements: UnknownStatements,
+    parse_failures: ParseFailures,
     two_phase_commit: bool,
     two_phase_commit_auto: bool,
     pub(super) readiness: Arc<Readiness>,
@@ -61,6 +70,7 @@ pub struct Cluster {
     prepared_statements: PreparedStatements,
     dry_run: bool,
     expanded_explain: bool,
+    analytics_query_cost: usize,
     pub_sub_channel_size: usize,
     query_parser: QueryParserLevel,
     connection_recovery: ConnectionRecovery,
@@ -70,13 +80,20 @@ pub struct Cluster {
     log_query_sample_length: usize,
     reload_schema_on_ddl: bool,
     load_schema: LoadSchema,
+    search_path_conflict: SearchPathConflict,
+    temporary_tables: TemporaryTables,
     resharding_parallel_copies: usize,
+    resharding_max_parallel_copies: usize,
+    resharding_copy_batch_size: usize,
+    resharding_copy_checkpoint_rows: usize,
     resharding_copy_retry_max_attempts: usize,
     resharding_copy_retry_min_delay: Duration,
     resharding_replication_retry_max_attempts: usize,
     resharding_replication_retry_min_delay: Duration,
     regex_parser: RegexParser,
     identity: Option<String>,
+    sharding_key_parameter: Option<String>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 /// Sharding configuration from the cluster.
@@ -94,6 +111,8 @@ pub struct ShardingSchema {
     pub query_parser_engine: QueryParserEngine,
     pub log_min_duration_parse: Option<Duration>,
     pub log_query_sample_length: usize,
+    /// Resolution of search_path schemas mapped to different shards.
+    pub search_path_conflict: SearchPathConflict,
 }
 
 impl ShardingSchema {
@@ -135,10 +154,17 @@ pub struct ClusterConfig<'a> {
     pub sharded_tables: ShardedTables,
     pub replication_sharding: Option<String>,
     pub multi_tenant: &'a Option<MultiTenant>,
+    pub multi_tenant_filter: bool,
     pub rw_strategy: ReadWriteStrategy,
     pub rw_split: ReadWriteSplit,
     pub schema_admin: bool,
     pub cross_shard_disabled: bool,
+    pub cross_shard_transactions_disabled: bool,
+    pub atomic_truncate: bool,
+    pub require_sharding_key: bool,
+    pub multi_statement_queries: MultiStatementQueries,
+    pub unknown_statements: UnknownStatements,
+    pub parse_failures: ParseFailures,
     pub two_pc: bool,
     pub two_pc_auto: bool,
     pub sharded_schemas: ShardedSchemas,
@@ -146,6 +172,7 @@ pub struct ClusterConfig<'a> {
     pub prepared_statements: &'a PreparedStatements,
     pub dry_run: bool,
     pub expanded_explain: bool,
+    pub analytics_query_cost: usize,
     pub pub_sub_channel_size: usize,
     pub query_parser: QueryParserLevel,
     pub query_parser_engine: QueryParserEngine,
@@ -156,14 +183,23 @@ pub struct ClusterConfig<'a> {
     pub lsn_check_interval: Duration,
     pub reload_schema_on_ddl: bool,
     pub load_schema: LoadSchema,
+    pub search_path_conflict: SearchPathConflict,
+    pub temporary_tables: TemporaryTables,
     pub resharding_parallel_copies: usize,
+    pub resharding_max_parallel_copies: usize,
+    pub resharding_copy_batch_size: usize,
+    pub resharding_copy_checkpoint_rows: usize,
     pub resharding_copy_retry_max_attempts: usize,
     pub resharding_copy_retry_min_delay: u64,
     pub resharding_replication_retry_max_attempts: usize,
     pub resharding_replication_retry_min_delay: u64,
     pub regex_parser_limit: usize,
+    pub restricted_parameters: bool,
     pub pub_sub_enabled: bool,
     pub identity: &'a Option<String>,
+    pub sharding_key_parameter: &'a Option<String>,
+    pub query_rate_limit: Option<u32>,
+    pub transaction_rate_limit: Option<u32>,
 }
 
 impl<'a> ClusterConfig<'a> {
@@ -184,6 +220,24 @@ impl<'a> ClusterConfig<'a> {
             .map(|shard| shard.pooler_mode())
             .unwrap_or(user.pooler_mode.unwrap_or(general.pooler_mode));
 
+        // User settings take priority over database settings.
+        let databases = || {
+            config
+                .databases
+                .iter()
+                .filter(|database| database.name == user.database)
+        };
+        let query_rate_limit = user
+            .query_rate_limit
+            .or_else(|| databases().find_map(|database| database.query_rate_limit));
+        let transaction_rate_limit = user
+            .transaction_rate_limit
+            .or_else(|| databases().find_map(|database| database.transaction_rate_limit));
+        let multi_tenant_filter = user
+            .multi_tenant_filter
+            .or_else(|| databases().find_map(|database| database.multi_tenant_filter))
+            .unwrap_or(multi_tenant.as_ref().is_some_and(|config| config.filter));
+
         Self {
             name: &user.database,
             passwords: user.passwords(),
@@ -194,12 +248,19 @@ impl<'a> ClusterConfig<'a> {
             shards,
             sharded_tables,
             multi_tenant,
+            multi_tenant_filter,
             rw_strategy: general.read_write_strategy,
             rw_split: general.read_write_split,
             schema_admin: user.schema_admin,
             cross_shard_disabled: user
                 .cross_shard_disabled
                 .unwrap_or(general.cross_shard_disabled),
+            cross_shard_transactions_disabled: general.cross_shard_transactions_disabled,
+            atomic_truncate: general.atomic_truncate,
+            require_sharding_key: general.require_sharding_key,
+            multi_statement_queries: general.multi_statement_queries,
+            unknown_statements: general.unknown_statements,
+            parse_failures: general.parse_failures,
             two_pc: user.two_phase_commit.unwrap_or(general.two_phase_commit),
             two_pc_auto: user
                 .two_phase_commit_auto
@@ -209,8 +270,9 @@ impl<'a> ClusterConfig<'a> {
             prepared_statements: &general.prepared_statements,
             dry_run: general.dry_run,
             expanded_explain: general.expanded_explain,
+            analytics_query_cost: general.analytics_query_cost,
             pub_sub_channel_size: general.pub_sub_channel_size,
-            query_parser: query_parser.level,
+            query_parser: user.query_parser.unwrap_or(query_parser.level),
             query_parser_engine: query_parser.engine,
             log_min_duration_parse: general.log_min_duration_parse(),
             log_query_sample_length: general.log_query_sample_length,
@@ -219,15 +281,24 @@ impl<'a> ClusterConfig<'a> {
             lsn_check_interval: Duration::from_millis(general.lsn_check_interval),
             reload_schema_on_ddl: general.reload_schema_on_ddl,
             load_schema: general.load_schema,
+            search_path_conflict: general.search_path_conflict,
+            temporary_tables: general.temporary_tables,
             resharding_parallel_copies: general.resharding_parallel_copies,
+            resharding_max_parallel_copies: general.resharding_max_parallel_copies,
+            resharding_copy_batch_size: general.resharding_copy_batch_size,
+            resharding_copy_checkpoint_rows: general.resharding_copy_checkpoint_rows,
             resharding_copy_retry_max_attempts: general.resharding_copy_retry_max_attempts,
             resharding_copy_retry_min_delay: general.resharding_copy_retry_min_delay,
             resharding_replication_retry_max_attempts: general
                 .resharding_replication_retry_max_attempts,
             resharding_replication_retry_min_delay: general.resharding_replication_retry_min_delay,
             regex_parser_limit: general.regex_parser_limit,
+            restricted_parameters: general.parameters_restricted(),
             pub_sub_enabled: general.pub_sub_enabled(),
             identity: &user.identity,
+            sharding_key_parameter: &general.sharding_key_parameter,
+            query_rate_limit,
+            transaction_rate_limit,
         }
     }
 }
@@ -245,10 +316,17 @@ impl Cluster {
             sharded_tables,
             replication_sharding,
             multi_tenant,
+            multi_tenant_filter,
             rw_strategy,
             rw_split,
             schema_admin,
             cross_shard_disabled,
+            cross_shard_transactions_disabled,
+            atomic_truncate,
+            require_sharding_key,
+            multi_statement_queries,
+            unknown_statements,
+            parse_failures,
             two_pc,
             two_pc_auto,
             sharded_schemas,
@@ -256,6 +334,7 @@ impl Cluster {
             prepared_statements,
             dry_run,
             expanded_explain,
+            analytics_query_cost,
             pub_sub_channel_size,
             query_parser,
             connection_recovery,
@@ -266,14 +345,23 @@ impl Cluster {
             log_query_sample_length,
             reload_schema_on_ddl,
             load_schema,
+            search_path_conflict,
+            temporary_tables,
             resharding_parallel_copies,
+            resharding_max_parallel_copies,
+            resharding_copy_batch_size,
+            resharding_copy_checkpoint_rows,
             resharding_copy_retry_max_attempts,
             resharding_copy_retry_min_delay,
             resharding_replication_retry_max_attempts,
             resharding_replication_retry_min_delay,
             regex_parser_limit,
+            restricted_parameters,
             pub_sub_enabled,
             identity,
+            sharding_key_parameter,
+            query_rate_limit,
+            transaction_rate_limit,
         } = config;
 
         let identifier = Arc::new(DatabaseUser {
@@ -305,11 +393,18 @@ impl Cluster {
             sharded_schemas,
             replication_sharding,
             multi_tenant: multi_tenant.clone(),
+            multi_tenant_filter,
             rw_strategy,
             rw_split,
             schema_admin,
             stats: Arc::new(Mutex::new(MirrorStats::default())),
             cross_shard_disabled,
+            cross_shard_transactions_disabled,
+            atomic_truncate,
+            require_sharding_key,
+            multi_statement_queries,
+            unknown_statements,
+            parse_failures,
             two_phase_commit: two_pc && shards.len() > 1,
             two_phase_commit_auto: two_pc_auto && shards.len() > 1,
             readiness: Arc::new(Readiness::default()),
@@ -317,6 +412,7 @@ impl Cluster {
             prepared_statements: *prepared_statements,
             dry_run,
             expanded_explain,
+            analytics_query_cost,
             pub_sub_channel_size,
             query_parser,
             connection_recovery,
@@ -326,15 +422,29 @@ impl Cluster {
             log_query_sample_length,
             reload_schema_on_ddl,
             load_schema,
+            search_path_conflict,
+            temporary_tables,
             resharding_parallel_copies,
+            resharding_max_parallel_copies,
+            resharding_copy_batch_size,
+            resharding_copy_checkpoint_rows,
             resharding_copy_retry_max_attempts,
             resharding_copy_retry_min_delay: Duration::from_millis(resharding_copy_retry_min_delay),
             resharding_replication_retry_max_attempts,
             resharding_replication_retry_min_delay: Duration::from_millis(
                 resharding_replication_retry_min_delay,
             ),
-            regex_parser: RegexParser::new(regex_parser_limit, query_parser),
+            regex_parser: RegexParser::new(
+                regex_parser_limit,
+                query_parser,
+                temporary_tables,
+                restricted_parameters,
+            ),
             identity: identity.clone(),
+            sharding_key_parameter: sharding_key_parameter
+                .as_ref()
+                .map(|name| name.to_lowercase()),
+            rate_limiter: Arc::new(RateLimiter::new(query_rate_limit, transaction_rate_limit)),
         }
     }
 
@@ -360,6 +470,17 @@ impl Cluster {
         shard.replica(request).await
     }
 
+    /// Get a connection to a specific replica of the given shard.
+    pub async fn replica_named(
+        &self,
+        shard: usize,
+        name: &str,
+        request: &Request,
+    ) -> Result<Guard, Error> {
+        let shard = self.shards.get(shard).ok_or(Error::NoShard(shard))?;
+        shard.replica_named(name, request).await
+    }
+
     /// The two clusters have the same databases.
     pub(crate) fn can_move_conns_to(&self, other: &Cluster) -> bool {
         self.shards.len() == other.shards.len()
@@ -380,12 +501,15 @@ impl Cluster {
     }
 
     /// Cancel a query executed by one of the shards.
+    ///
+    /// Cross-shard queries run on all shards at the same time, so the cancel request
+    /// is sent to all of them concurrently. Every shard is attempted,
+    /// even if sending it to some fails, and the first error is returned.
     pub async fn cancel(&self, id: FrontendPid) -> Result<(), super::super::Error> {
-        for shard in &self.shards {
-            shard.cancel(id).await?;
-        }
-
-        Ok(())
+        join_all(self.shards.iter().map(|shard| shard.cancel(id)))
+            .await
+            .into_iter()
+            .collect()
     }
 
     /// Get all shards.
@@ -453,10 +577,20 @@ impl Cluster {
         self.dry_run
     }
 
+    /// Session parameter used as the sharding key, if configured.
+    pub fn sharding_key_parameter(&self) -> Option<&str> {
+        self.sharding_key_parameter.as_deref()
+    }
+
     pub fn expanded_explain(&self) -> bool {
         self.expanded_explain
     }
 
+    /// Reads this expensive go to analytics replicas.
+    pub fn analytics_query_cost(&self) -> usize {
+        self.analytics_query_cost
+    }
+
     pub fn pub_sub_enabled(&self) -> bool {
         self.pub_sub_channel_size > 0
     }
@@ -493,6 +627,11 @@ impl Cluster {
         self.schema_admin = owner;
     }
 
+    /// Query and transaction rate limits for this cluster.
+    pub fn rate_limiter(&self) -> Arc<RateLimiter> {
+        self.rate_limiter.clone()
+    }
+
     pub fn stats(&self) -> Arc<Mutex<MirrorStats>> {
         self.stats.clone()
     }
@@ -503,6 +642,11 @@ impl Cluster {
         !(self.shards().len() == 1 && (self.read_only() || self.write_only()))
     }
 
+    /// What to do with temporary tables created in transaction mode.
+    pub fn temporary_tables(&self) -> TemporaryTables {
+        self.temporary_tables
+    }
+
     /// Use the query parser.
     pub(crate) fn use_query_parser(&self, request: &ClientRequest) -> bool {
         match self.query_parser() {
@@ -526,6 +670,11 @@ impl Cluster {
         &self.multi_tenant
     }
 
+    /// Add the tenant filter to reads that don't have one.
+    pub fn multi_tenant_filter(&self) -> bool {
+        self.multi_tenant.is_some() && self.multi_tenant_filter
+    }
+
     /// Get replication configuration for this cluster.
     pub fn replication_sharding_config(&self) -> Option<ReplicationConfig> {
         self.replication_sharding
@@ -543,6 +692,7 @@ impl Cluster {
             query_parser_engine: self.query_parser_engine,
             log_min_duration_parse: self.log_min_duration_parse,
             log_query_sample_length: self.log_query_sample_length,
+            search_path_conflict: self.search_path_conflict,
         }
     }
 
@@ -586,6 +736,36 @@ impl Cluster {
         self.cross_shard_disabled
     }
 
+    /// Transactions must stay on the first shard they use.
+    pub fn cross_shard_transactions_disabled(&self) -> bool {
+        self.cross_shard_transactions_disabled
+    }
+
+    /// Cross-shard `TRUNCATE` runs in a transaction on each shard.
+    pub fn atomic_truncate(&self) -> bool {
+        self.atomic_truncate
+    }
+
+    /// Writes to sharded tables must specify the sharding key.
+    pub fn require_sharding_key(&self) -> bool {
+        self.require_sharding_key
+    }
+
+    /// How to route queries with multiple statements.
+    pub fn multi_statement_queries(&self) -> MultiStatementQueries {
+        self.multi_statement_queries
+    }
+
+    /// How to route statements we don't recognize.
+    pub fn unknown_statements(&self) -> UnknownStatements {
+        self.unknown_statements
+    }
+
+    /// How to route queries we fail to parse.
+    pub fn parse_failures(&self) -> ParseFailures {
+        self.parse_failures
+    }
+
     /// Two-phase commit enabled.
     pub fn two_pc_enabled(&self) -> bool {
         self.two_phase_commit
@@ -603,6 +783,23 @@ impl Cluster {
         self.resharding_parallel_copies
     }
 
+    /// How many tables can be copied at the same time,
+    /// across all shards and replicas. Zero means no limit.
+    pub fn resharding_max_parallel_copies(&self) -> usize {
+        self.resharding_max_parallel_copies
+    }
+
+    /// How many rows are sharded and sent to the destination at a time.
+    pub fn resharding_copy_batch_size(&self) -> usize {
+        self.resharding_copy_batch_size
+    }
+
+    /// How many rows are copied from a table between checkpoints.
+    /// Zero means the table is copied in one go.
+    pub fn resharding_copy_checkpoint_rows(&self) -> usize {
+        self.resharding_copy_checkpoint_rows
+    }
+
     /// Maximum retries for a per-table copy during resharding.
     pub fn resharding_copy_retry_max_attempts(&self) -> usize {
         self.resharding_copy_retry_max_attempts
@@ -645,7 +842,7 @@ impl Cluster {
         query: impl Into<Query> + Clone,
     ) -> Result<(), crate::backend::Error> {
         for shard in 0..self.shards.len() {
-            let mut server = self.primary(shard, &Request::default()).await?;
+            let mut server = self.primary(shard, &Request::maintenance()).await?;
             server.execute(query.clone()).await?;
         }
 
@@ -781,15 +978,28 @@ mod test {
                 identifier,
                 prepared_statements: config.config.general.prepared_statements,
                 dry_run: config.config.general.dry_run,
+                require_sharding_key: config.config.general.require_sharding_key,
+                multi_statement_queries: config.config.general.multi_statement_queries,
+                unknown_statements: config.config.general.unknown_statements,
+                parse_failures: config.config.general.parse_failures,
+                sharding_key_parameter: config.config.general.sharding_key_parameter.clone(),
                 expanded_explain: config.config.general.expanded_explain,
+                analytics_query_cost: config.config.general.analytics_query_cost,
                 query_parser: config.config.general.query_parser,
                 regex_parser: crate::frontend::RegexParser::new(
                     config.config.general.regex_parser_limit,
                     config.config.general.query_parser,
+                    config.config.general.temporary_tables,
+                    config.config.general.parameters_restricted(),
                 ),
+                temporary_tables: config.config.general.temporary_tables,
                 rewrite: config.config.rewrite.clone(),
                 two_phase_commit: config.config.general.two_phase_commit,
                 two_phase_commit_auto: config.config.general.two_phase_commit_auto.unwrap_or(false),
+                resharding_copy_checkpoint_rows: config
+                    .config
+                    .general
+                    .resharding_copy_checkpoint_rows,
                 ..Default::default()
             }
         }
@@ -838,6 +1048,29 @@ mod test {
             cluster
         }
 
+        /// Single shard in the `pgdog1` database, e.g. a destination
+        /// kept apart from a source using the `pgdog` database.
+        pub fn new_test_other_database(config: &ConfigAndUsers) -> Cluster {
+            let mut cluster = Self::new_test_single_shard(config);
+            cluster.shards[0] = Shard::new(ShardConfig {
+                number: 0,
+                primary: &Some(PoolConfig {
+                    address: Address {
+                        database_name: "pgdog1".into(),
+                        ..Address::new_test()
+                    },
+                    config: Config::default(),
+                }),
+                replicas: &[],
+                lb_strategy: LoadBalancingStrategy::Random,
+                rw_split: ReadWriteSplit::IncludePrimary,
+                identifier: cluster.identifier.clone(),
+                lsn_check_interval: Duration::MAX,
+                pub_sub_enabled: false,
+            });
+            cluster
+        }
+
         pub fn new_test_single_primary(config: &ConfigAndUsers) -> Cluster {
             let identifier = Arc::new(DatabaseUser {
                 user: "pgdog".into(),
@@ -860,12 +1093,21 @@ mod test {
                 })],
                 prepared_statements: config.config.general.prepared_statements,
                 dry_run: config.config.general.dry_run,
+                require_sharding_key: config.config.general.require_sharding_key,
+                multi_statement_queries: config.config.general.multi_statement_queries,
+                unknown_statements: config.config.general.unknown_statements,
+                parse_failures: config.config.general.parse_failures,
+                sharding_key_parameter: config.config.general.sharding_key_parameter.clone(),
                 expanded_explain: config.config.general.expanded_explain,
+                analytics_query_cost: config.config.general.analytics_query_cost,
                 query_parser: config.config.general.query_parser,
                 regex_parser: crate::frontend::RegexParser::new(
                     config.config.general.regex_parser_limit,
                     config.config.general.query_parser,
+                    config.config.general.temporary_tables,
+                    config.config.general.parameters_restricted(),
                 ),
+                temporary_tables: config.config.general.temporary_tables,
                 rewrite: config.config.rewrite.clone(),
                 two_phase_commit: config.config.general.two_phase_commit,
                 two_phase_commit_auto: config.config.general.two_phase_commit_auto.unwrap_or(false),
@@ -903,6 +1145,35 @@ mod test {
         pub(crate) fn set_rw_split(&mut self, rw_split: ReadWriteSplit) {
             self.rw_split = rw_split;
         }
+
+        pub(crate) fn set_multi_statement_queries(
+            &mut self,
+            multi_statement_queries: MultiStatementQueries,
+        ) {
+            self.multi_statement_queries = multi_statement_queries;
+        }
+
+        pub(crate) fn set_unknown_statements(&mut self, unknown_statements: UnknownStatements) {
+            self.unknown_statements = unknown_statements;
+        }
+
+        pub(crate) fn set_parse_failures(&mut self, parse_failures: ParseFailures) {
+            self.parse_failures = parse_failures;
+        }
+
+        pub(crate) fn set_schema(&self, schema: Schema) {
+            for shard in &self.shards {
+                shard.set_schema(schema.clone());
+            }
+        }
+
+        pub(crate) fn set_multi_tenant(&mut self, multi_tenant: MultiTenant, schema: Schema) {
+            self.multi_tenant_filter = multi_tenant.filter;
+            self.multi_tenant = Some(multi_tenant);
+            for shard in &self.shards {
+                shard.set_schema(schema.clone());
+            }
+        }
     }
 
     #[test]
@@ -950,6 +1221,7 @@ mod test {
         let mut cluster = Cluster::new_test_single_shard(&config);
         cluster.multi_tenant = Some(MultiTenant {
             column: "tenant_id".into(),
+            filter: false,
         });
 
         assert!(cluster.load_schema());
@@ -962,6 +1234,7 @@ mod test {
         cluster.sharded_tables = ShardedTables::default();
         cluster.multi_tenant = Some(MultiTenant {
             column: "tenant_id".into(),
+            filter: false,
         });
 
         assert!(cluster.load_schema());
@@ -1092,6 +1365,49 @@ mod test {
         assert!(cluster.ready());
     }
 
+    #[tokio::test]
+    async fn test_cancel_cross_shard_query() {
+        use tokio::time::{sleep, timeout};
+
+        use crate::backend::{Error, pool::Request};
+        use crate::net::messages::FrontendPid;
+
+        let cluster = Cluster::new_test(&config());
+        cluster.launch();
+
+        // Same client runs the query on both shards.
+        let id = FrontendPid::new();
+        let request = Request::new(id, false);
+
+        let mut handles = vec![];
+        for shard in 0..cluster.shards().len() {
+            let mut server = cluster.primary(shard, &request).await.unwrap();
+            handles.push(tokio::spawn(async move {
+                server.execute_checked("SELECT pg_sleep(10)").await
+            }));
+        }
+        assert_eq!(handles.len(), 2);
+
+        // Let the queries start.
+        sleep(Duration::from_millis(100)).await;
+
+        cluster.cancel(id).await.unwrap();
+
+        for handle in handles {
+            let result = timeout(Duration::from_secs(5), handle)
+                .await
+                .expect("query should be cancelled on every shard")
+                .unwrap();
+
+            match result {
+                Err(Error::ExecutionError(err)) => assert_eq!(err.code, "57014"),
+                result => panic!("expected query_canceled, got {:?}", result),
+            }
+        }
+
+        cluster.shutdown();
+    }
+
     #[test]
     fn test_use_query_parser_set() {
         let mut cluster = Cluster::new_test(&config());
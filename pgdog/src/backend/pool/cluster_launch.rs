@@ -11,6 +11,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::error;
 
 use crate::backend::pool::ee::schema_changed_hook;
+use crate::backend::validation::check_partition_modulus;
 use crate::tasks;
 
 use super::Cluster;
@@ -125,10 +126,13 @@ impl Cluster {
             return;
         }
 
+        let sharding_schema = self.sharding_schema();
+
         for shard in self.shards() {
             let identifier = self.identifier();
             let shard = shard.clone();
             let shutdown = tasks::shutdown_signal();
+            let sharding_schema = sharding_schema.clone();
 
             tasks::spawn("shard schema sync", async move {
                 loop {
@@ -140,7 +144,15 @@ impl Cluster {
 
                     match result {
                         Ok(true) => {
-                            schema_changed_hook(&shard.schema(), &identifier, &shard);
+                            let schema = shard.schema();
+                            for error in check_partition_modulus(
+                                &schema,
+                                sharding_schema.tables.tables(),
+                                sharding_schema.shards,
+                            ) {
+                                error!("shard {}: {}", shard.number(), error);
+                            }
+                            schema_changed_hook(&schema, &identifier, &shard);
                             return;
                         }
                         Ok(false) => return,
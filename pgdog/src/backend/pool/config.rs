@@ -76,6 +76,16 @@ impl Config {
         self.idle_healthcheck_delay
     }
 
+    /// Healthcheck connections idle for longer than this on checkout.
+    pub fn test_on_checkout_after_idle(&self) -> Option<Duration> {
+        self.test_on_checkout_after_idle
+    }
+
+    /// Ping connections idle for longer than this.
+    pub fn idle_ping_interval(&self) -> Option<Duration> {
+        self.idle_ping_interval
+    }
+
     /// Ban timeout.
     pub fn ban_timeout(&self) -> Duration {
         self.ban_timeout
@@ -105,6 +115,7 @@ impl Config {
                 max: user
                     .pool_size
                     .unwrap_or(database.pool_size.unwrap_or(general.default_pool_size)),
+                reserve_pool_size: general.reserve_pool_size,
                 max_age: Duration::from_millis(
                     user.server_lifetime
                         .unwrap_or(database.server_lifetime.unwrap_or(general.server_lifetime)),
@@ -119,8 +130,14 @@ impl Config {
                 healthcheck_interval: Duration::from_millis(general.healthcheck_interval),
                 idle_healthcheck_interval: Duration::from_millis(general.idle_healthcheck_interval),
                 idle_healthcheck_delay: Duration::from_millis(general.idle_healthcheck_delay),
+                test_on_checkout_after_idle: (general.test_on_checkout_after_idle > 0)
+                    .then(|| Duration::from_millis(general.test_on_checkout_after_idle)),
+                idle_ping_interval: (general.idle_ping_interval > 0)
+                    .then(|| Duration::from_millis(general.idle_ping_interval)),
                 healthcheck_timeout: Duration::from_millis(general.healthcheck_timeout),
                 ban_timeout: Duration::from_millis(general.ban_timeout),
+                ban_timeout_max: Duration::from_millis(general.ban_timeout_max),
+                ban_backoff_window: Duration::from_millis(general.ban_backoff_window),
                 rollback_timeout: Duration::from_millis(general.rollback_timeout),
                 statement_timeout: user
                     .statement_timeout
@@ -137,6 +154,7 @@ impl Config {
                 connect_timeout: Duration::from_millis(general.connect_timeout),
                 connect_attempts: general.connect_attempts,
                 connect_attempt_delay: general.connect_attempt_delay(),
+                max_concurrent_connects: general.max_concurrent_connects,
                 query_timeout: Duration::from_millis(general.query_timeout),
                 checkout_timeout: Duration::from_millis(general.checkout_timeout),
                 idle_timeout: Duration::from_millis(
@@ -146,16 +164,19 @@ impl Config {
                 read_only: user
                     .read_only
                     .unwrap_or(database.read_only.unwrap_or_default()),
+                default_transaction_isolation: database.default_transaction_isolation,
                 prepared_statements_limit: general.prepared_statements_limit,
                 stats_period: Duration::from_millis(general.stats_period),
                 bannable: !is_only_replica,
                 connection_recovery: general.connection_recovery,
+                checkout_fairness: general.checkout_fairness,
                 lsn_check_interval: Duration::from_millis(general.lsn_check_interval),
                 lsn_check_timeout: Duration::from_millis(general.lsn_check_timeout),
                 lsn_check_delay: Duration::from_millis(general.lsn_check_delay),
                 role_detection: database.role == Role::Auto,
                 resharding_only: database.resharding_only,
                 lb_weight: database.lb_weight,
+                workload: database.workload,
                 prepared_statements_level: general.prepared_statements,
                 ..Default::default()
             },
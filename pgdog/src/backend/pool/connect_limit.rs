@@ -0,0 +1,39 @@
+//! Limit on how many server connections can be opened
+//! at the same time, shared by all pools.
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Semaphore for the currently configured limit.
+static CONNECTS: Lazy<Mutex<Option<(usize, Arc<Semaphore>)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Wait until a new connection can be opened. The returned permit
+/// must be held until the connection is established or fails.
+///
+/// Returns `None` if `limit` is zero, i.e. there is no limit.
+pub(super) async fn connect_permit(limit: usize) -> Option<OwnedSemaphorePermit> {
+    if limit == 0 {
+        return None;
+    }
+
+    let semaphore = {
+        let mut guard = CONNECTS.lock();
+        match guard.as_ref() {
+            Some((current, semaphore)) if *current == limit => semaphore.clone(),
+            // Limit changed after a config reload. Connections being opened
+            // already hold permits from the old semaphore, so this is temporarily
+            // exceeded until they are done.
+            _ => {
+                let semaphore = Arc::new(Semaphore::new(limit));
+                *guard = Some((limit, semaphore.clone()));
+                semaphore
+            }
+        }
+    };
+
+    // We never close the semaphore.
+    semaphore.acquire_owned().await.ok()
+}
@@ -1,8 +1,10 @@
 //! Aggregate buffer.
 
-use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use std::collections::{HashMap, VecDeque};
 use std::mem;
 
+use indexmap::{IndexMap, map::Entry};
+
 use crate::{
     frontend::router::parser::{
         Aggregate, AggregateFunction, AggregateTarget,
@@ -19,6 +21,7 @@ use super::Error;
 
 mod avg;
 mod cmp;
+mod count_distinct;
 mod sum;
 mod variance;
 #[path = "aggregate/count.rs"]
@@ -91,6 +94,7 @@ enum State {
     Cmp(cmp::Cmp),
     /// Ah ah aaaaaaah
     Count(von::Count),
+    CountDistinct(count_distinct::CountDistinct),
     Sum(sum::Sum),
     Variance(variance::Variance),
 }
@@ -110,6 +114,16 @@ impl State {
                 })?,
             ))),
             (AggregateFunction::Count, false) => Ok(Self::Count(von::Count::new(target.column()))),
+            (AggregateFunction::Count, true) => Ok(Self::CountDistinct(
+                count_distinct::CountDistinct::new(helper.distinct.ok_or_else(|| {
+                    Error::UnsupportedAggregation {
+                        function: String::from("count"),
+                        reason: String::from(
+                            "internal distinct values helper was missing (this is a bug in pgdog)",
+                        ),
+                    }
+                })?),
+            )),
             (AggregateFunction::Max, _) => Ok(Self::Cmp(cmp::Cmp::max(target.column()))),
             (AggregateFunction::Min, _) => Ok(Self::Cmp(cmp::Cmp::min(target.column()))),
             (AggregateFunction::Sum, false) => Ok(Self::Sum(sum::Sum::new(target.column()))),
@@ -156,7 +170,6 @@ impl State {
             }
             (
                 f @ (AggregateFunction::Avg
-                | AggregateFunction::Count
                 | AggregateFunction::StddevPop
                 | AggregateFunction::StddevSamp
                 | AggregateFunction::VarPop
@@ -191,6 +204,12 @@ impl State {
             State::Count(state) => state
                 .accumulate(row.get_column_checked(state.column, decoder)?.value)
                 .map_err(Into::into),
+            State::CountDistinct(state) => state
+                .accumulate(
+                    row.get_column_checked(state.distinct_helper, decoder)?
+                        .value,
+                )
+                .map_err(Into::into),
             State::Sum(state) => state
                 .accumulate(row.get_column_checked(state.column, decoder)?.value)
                 .map_err(Into::into),
@@ -209,6 +228,7 @@ impl State {
             State::Avg(state) => Ok(state.finalize()?),
             State::Cmp(state) => Ok(state.finalize()),
             State::Count(state) => Ok(state.finalize()),
+            State::CountDistinct(state) => Ok(state.finalize()),
             State::Sum(state) => Ok(state.finalize()),
             State::Variance(state) => Ok(state.finalize()?),
         }
@@ -220,12 +240,15 @@ struct HelperColumns {
     count: Option<usize>,
     sum: Option<usize>,
     sumsq: Option<usize>,
+    distinct: Option<usize>,
 }
 
 #[derive(Debug)]
 pub(super) struct Aggregates<'a> {
     rows: &'a VecDeque<DataRow>,
-    mappings: HashMap<Grouping, Vec<Accumulator<'a>>>,
+    /// First row seen for each group and its accumulators,
+    /// in the order groups were received.
+    mappings: IndexMap<Grouping, (DataRow, Vec<Accumulator<'a>>)>,
     decoder: &'a Decoder,
     aggregate: &'a Aggregate,
     helper_columns: HashMap<usize, HelperColumns>,
@@ -263,12 +286,17 @@ impl<'a> Aggregates<'a> {
                 HelperKind::Count => entry.count = Some(index),
                 HelperKind::Sum => entry.sum = Some(index),
                 HelperKind::SumSquares => entry.sumsq = Some(index),
+                HelperKind::DistinctValues => entry.distinct = Some(index),
             }
         }
 
         let helpers_present = aggregate.targets().iter().all(|target| {
             let key = target.column();
             match target.function() {
+                AggregateFunction::Count if target.is_distinct() => helper_columns
+                    .get(&key)
+                    .and_then(|columns| columns.distinct)
+                    .is_some(),
                 AggregateFunction::Avg => helper_columns
                     .get(&key)
                     .and_then(|columns| columns.count)
@@ -294,7 +322,7 @@ impl<'a> Aggregates<'a> {
             Some(Self {
                 rows,
                 decoder,
-                mappings: HashMap::new(),
+                mappings: IndexMap::new(),
                 aggregate,
                 helper_columns,
             })
@@ -306,39 +334,32 @@ impl<'a> Aggregates<'a> {
     pub(super) fn aggregate(mut self) -> Result<VecDeque<DataRow>, Error> {
         for row in self.rows {
             let grouping = Grouping::new(row, self.aggregate.group_by(), self.decoder)?;
-            let entry = match self.mappings.entry(grouping) {
+            let (_, accumulators) = match self.mappings.entry(grouping) {
                 Entry::Occupied(o) => o.into_mut(),
-                Entry::Vacant(v) => v.insert(Accumulator::from_aggregate(
-                    self.aggregate,
-                    &self.helper_columns,
-                )?),
+                Entry::Vacant(v) => v.insert((
+                    row.clone(),
+                    Accumulator::from_aggregate(self.aggregate, &self.helper_columns)?,
+                )),
             };
 
-            for aggregate in entry {
+            for aggregate in accumulators {
                 aggregate.accumulate(row, self.decoder)?;
             }
         }
 
         let mut rows = VecDeque::new();
-        for (grouping, accumulator) in self.mappings {
+        for (_, (mut row, accumulators)) in self.mappings {
             //
             // Aggregate rules in Postgres dictate that the only
             // columns present in the row are either:
             //
-            // 1. part of the GROUP BY, which means they are
-            //    stored in the grouping
+            // 1. part of the GROUP BY (or functionally dependent on it),
+            //    which are the same for all rows in the group,
+            //    so we take them from the first row
             // 2. are aggregate functions, which means they
             //    are stored in the accumulator
             //
-            let mut row = DataRow::new();
-            for (idx, datum) in grouping.columns {
-                row.insert(
-                    idx,
-                    datum.encode(self.decoder.format(idx))?,
-                    datum.is_null(),
-                );
-            }
-            for acc in accumulator {
+            for acc in accumulators {
                 let target_column = acc.target.column();
                 let datum = acc.finalize()?;
                 row.insert(
@@ -665,6 +686,109 @@ mod test {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn aggregate_group_by_overlapping_keys() {
+        let aggregate = parse("SELECT region, SUM(amount), AVG(amount) FROM sales GROUP BY region");
+
+        let rd = RowDescription::new(&[
+            Field::text("region"),
+            Field::bigint("sum"),
+            Field::double("avg"),
+            Field::bigint("__pgdog_count_col2"),
+        ]);
+        let decoder = Decoder::from(&rd);
+
+        let mut rows = VecDeque::new();
+        for (region, sum, avg, count) in [
+            ("us", 10_i64, 5.0_f64, 2_i64),
+            ("eu", 4, 4.0, 1),
+            ("us", 20, 10.0, 2),
+        ] {
+            let mut row = DataRow::new();
+            row.add(region).add(sum).add(avg).add(count);
+            rows.push_back(row);
+        }
+
+        let mut plan = AggregateRewritePlan::default();
+        plan.add_helper(HelperMapping {
+            target_column: 2,
+            helper_column: 3,
+            distinct: false,
+            kind: HelperKind::Count,
+            alias: "__pgdog_count_col2".into(),
+        });
+
+        let result = Aggregates::new(&rows, &decoder, &aggregate, &plan)
+            .unwrap()
+            .aggregate()
+            .unwrap();
+
+        // Groups are returned in the order they were first seen.
+        let groups: Vec<(String, i64, f64)> = result
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<String>(0, Format::Text).unwrap(),
+                    row.get::<i64>(1, Format::Text).unwrap(),
+                    row.get::<Double>(2, Format::Text).unwrap().0,
+                )
+            })
+            .collect();
+        assert_eq!(groups, vec![("us".into(), 30, 7.5), ("eu".into(), 4, 4.0)]);
+    }
+
+    #[test]
+    fn aggregate_count_distinct_across_shards() {
+        let aggregate = parse("SELECT category, COUNT(DISTINCT user_id) FROM menu GROUP BY 1");
+
+        let rd = RowDescription::new(&[
+            Field::text("category"),
+            Field::bigint("count"),
+            integer_array_field("__pgdog_distinct_col1"),
+        ]);
+        let decoder = Decoder::from(&rd);
+
+        let mut rows = VecDeque::new();
+        for (category, count, values) in [
+            ("a", "3", "{1,2,3}"),
+            ("a", "2", "{3,4}"),
+            ("b", "1", "{5}"),
+            ("b", "1", "{5}"),
+        ] {
+            let mut row = DataRow::new();
+            row.add(category).add(count).add(values);
+            rows.push_back(row);
+        }
+
+        let mut plan = AggregateRewritePlan::default();
+        plan.add_helper(HelperMapping {
+            target_column: 1,
+            helper_column: 2,
+            distinct: true,
+            kind: HelperKind::DistinctValues,
+            alias: "__pgdog_distinct_col1".into(),
+        });
+
+        let mut result = Aggregates::new(&rows, &decoder, &aggregate, &plan)
+            .unwrap()
+            .aggregate()
+            .unwrap();
+
+        let mut groups: Vec<(String, i64)> = result
+            .drain(..)
+            .map(|row| {
+                (
+                    row.get::<String>(0, Format::Text).unwrap(),
+                    row.get::<i64>(1, Format::Text).unwrap(),
+                )
+            })
+            .collect();
+        groups.sort();
+
+        // Values present on both shards are counted once.
+        assert_eq!(groups, vec![("a".into(), 4), ("b".into(), 1)]);
+    }
+
     #[test]
     fn test_adding_types_which_cannot_be_added() {
         let mut datum = Datum::Text("hello".to_owned());
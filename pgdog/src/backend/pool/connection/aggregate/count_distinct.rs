@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use super::TypeError;
+use crate::net::messages::Datum;
+
+/// COUNT(DISTINCT column) across shards.
+///
+/// Shards return their distinct values using a helper
+/// ARRAY_AGG(DISTINCT column), which we merge here, so values
+/// present on more than one shard are only counted once.
+#[derive(Debug)]
+pub(super) struct CountDistinct {
+    pub(super) distinct_helper: usize,
+    values: HashSet<Datum>,
+}
+
+impl CountDistinct {
+    pub(super) fn new(distinct_helper: usize) -> Self {
+        Self {
+            distinct_helper,
+            values: HashSet::new(),
+        }
+    }
+
+    pub(super) fn accumulate(&mut self, values: Datum) -> Result<(), TypeError> {
+        match values {
+            // No rows on this shard.
+            Datum::Null => Ok(()),
+            Datum::Array(array) => {
+                self.values.extend(
+                    array
+                        .elements()
+                        .iter()
+                        .filter(|value| !value.is_null())
+                        .cloned(),
+                );
+                Ok(())
+            }
+            values => Err(TypeError::InvalidOperation {
+                op: "count(DISTINCT ...)",
+                ty: values.data_type(),
+            }),
+        }
+    }
+
+    pub(super) fn finalize(self) -> Datum {
+        Datum::Bigint(self.values.len() as i64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::net::messages::data_types::Array;
+
+    fn array(values: &[i64]) -> Datum {
+        let text = format!(
+            "{{{}}}",
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        Datum::Array(Array::decode_typed(text.as_bytes(), crate::net::Format::Text, 20).unwrap())
+    }
+
+    #[test]
+    fn count_distinct_across_shards() {
+        let mut state = CountDistinct::new(1);
+        state.accumulate(array(&[1, 2, 3])).unwrap();
+        state.accumulate(array(&[3, 4])).unwrap();
+        state.accumulate(Datum::Null).unwrap();
+        assert_eq!(state.finalize(), 4i64.into());
+    }
+
+    #[test]
+    fn count_distinct_no_rows() {
+        let state = CountDistinct::new(1);
+        assert_eq!(state.finalize(), 0i64.into());
+    }
+
+    #[test]
+    fn count_distinct_rejects_scalars() {
+        let mut state = CountDistinct::new(1);
+        assert!(state.accumulate(1i64.into()).is_err());
+    }
+}
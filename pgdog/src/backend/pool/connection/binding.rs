@@ -57,6 +57,22 @@ impl Binding {
         self.disconnect();
     }
 
+    /// Close connections that failed, counting the error against their pools.
+    /// Pools that keep failing are banned by the load balancer.
+    pub fn close_failed(&mut self) {
+        match self {
+            Binding::Direct(guard, _) => guard.stats_mut().state(State::Error),
+            Binding::MultiShard(guards, _) => {
+                for guard in guards {
+                    guard.stats_mut().state(State::Error);
+                }
+            }
+            _ => (),
+        }
+
+        self.disconnect();
+    }
+
     /// Are we connected to a backend?
     pub fn connected(&self) -> bool {
         match self {
@@ -109,7 +125,7 @@ impl Binding {
                             return Ok(message);
                         }
                         let mut read = false;
-                        for server in shards.iter_mut() {
+                        for (position, server) in shards.iter_mut().enumerate() {
                             if !server.has_more_messages() {
                                 continue;
                             }
@@ -117,7 +133,7 @@ impl Binding {
                             let message = server.read().await?;
 
                             read = true;
-                            if let Some(message) = state.forward(message)? {
+                            if let Some(message) = state.forward_from(position, message)? {
                                 return Ok(message);
                             }
                         }
@@ -411,6 +427,55 @@ impl Binding {
         }
     }
 
+    /// Make all shards in a multi-shard transaction use the same snapshot.
+    ///
+    /// The snapshot is exported from the first shard and imported on the others
+    /// with `SET TRANSACTION SNAPSHOT`, so this must run before any query in the
+    /// transaction. Postgres can only import snapshots exported by the same database.
+    ///
+    /// Shards run the transaction with `REPEATABLE READ`, so `SERIALIZABLE`
+    /// transactions are rejected instead of silently downgraded.
+    pub(crate) async fn synchronize_snapshot(&mut self) -> Result<(), Error> {
+        const ISOLATION: &str = "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ";
+
+        let Binding::MultiShard(servers, _) = self else {
+            return Ok(());
+        };
+
+        let Some((first, rest)) = servers.split_first_mut() else {
+            return Ok(());
+        };
+
+        // SHOW doesn't take a snapshot, so isolation can still be changed after it.
+        let isolation = first
+            .fetch_all::<String>("SHOW transaction_isolation")
+            .await?
+            .pop()
+            .unwrap_or_default();
+        if isolation == "serializable" {
+            return Err(Error::SnapshotSerializable);
+        }
+
+        first.execute(ISOLATION).await?;
+        let snapshot = first
+            .fetch_all::<String>("SELECT pg_export_snapshot()")
+            .await?
+            .pop()
+            .ok_or(Error::SnapshotExport)?;
+
+        let queries = [
+            Query::new(ISOLATION),
+            Query::new(format!("SET TRANSACTION SNAPSHOT '{}'", snapshot)),
+        ];
+        let futures = rest.iter_mut().map(|server| server.execute_batch(&queries));
+
+        for result in join_all(futures).await {
+            result?;
+        }
+
+        Ok(())
+    }
+
     /// Link client to server.
     pub async fn link_client(
         &mut self,
@@ -479,6 +544,17 @@ impl Binding {
         }
     }
 
+    /// Client changed the role on the connected servers.
+    pub fn mark_role_changed(&mut self, changed: bool) {
+        match self {
+            Binding::Direct(server, ..) => server.mark_role_changed(changed),
+            Binding::MultiShard(servers, _) => servers
+                .iter_mut()
+                .for_each(|server| server.mark_role_changed(changed)),
+            _ => (),
+        }
+    }
+
     pub(super) fn dirty(&mut self) {
         match self {
             Binding::Direct(server, ..) => server.mark_dirty(true),
@@ -1,4 +1,4 @@
-//! Tests for the two_pc function in binding.rs
+//! Tests for the two_pc and synchronize_snapshot functions in binding.rs
 
 #[cfg(test)]
 mod tests {
@@ -14,6 +14,7 @@ mod tests {
                 parser::{Shard, ShardWithPriority},
             },
         },
+        net::{DataRow, FromBytes, Protocol, ToBytes},
     };
 
     use super::super::multi_shard::MultiShard;
@@ -276,4 +277,66 @@ mod tests {
             _ => panic!("not an error"),
         };
     }
+
+    #[tokio::test]
+    async fn test_synchronize_snapshot() {
+        // Test servers share a database, so they can share a snapshot.
+        let mut server = test_server().await;
+        server
+            .execute("DROP TABLE IF EXISTS test_synchronize_snapshot")
+            .await
+            .unwrap();
+        server
+            .execute("CREATE TABLE test_synchronize_snapshot (id BIGINT)")
+            .await
+            .unwrap();
+        server
+            .execute("INSERT INTO test_synchronize_snapshot VALUES (1)")
+            .await
+            .unwrap();
+
+        let mut binding = create_multishard_binding().await;
+        binding
+            .synchronize_snapshot()
+            .await
+            .expect("snapshot should synchronize");
+
+        // A write lands after the transaction started but before any shard reads.
+        server
+            .execute("INSERT INTO test_synchronize_snapshot VALUES (2)")
+            .await
+            .unwrap();
+
+        let messages = binding
+            .execute("SELECT COUNT(*) FROM test_synchronize_snapshot")
+            .await
+            .unwrap();
+        let counts = messages
+            .iter()
+            .filter(|message| message.code() == 'D')
+            .map(|message| DataRow::from_bytes(message.to_bytes()).unwrap())
+            .map(|row| row.get_int(0, true).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 1, 1]);
+
+        binding.execute("COMMIT").await.unwrap();
+        server
+            .execute("DROP TABLE test_synchronize_snapshot")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_synchronize_snapshot_serializable() {
+        let mut binding = create_multishard_binding().await;
+        binding
+            .execute("BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY")
+            .await
+            .unwrap();
+
+        let err = binding.synchronize_snapshot().await.unwrap_err();
+        assert!(matches!(err, crate::backend::Error::SnapshotSerializable));
+
+        binding.execute("ROLLBACK").await.unwrap();
+    }
 }
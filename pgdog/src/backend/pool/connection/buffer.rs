@@ -12,7 +12,7 @@ use crate::{
     },
     net::{
         Decoder,
-        messages::{DataRow, FromBytes, Message, Protocol, ToBytes, Vector},
+        messages::{DataRow, Format, FromBytes, Message, Protocol, ToBytes, Vector},
     },
 };
 
@@ -145,8 +145,19 @@ impl Buffer {
         decoder: &Decoder,
         plan: &AggregateRewritePlan,
     ) -> Result<(), super::Error> {
+        if !aggregate.is_empty() && aggregate.has_unresolved_group_by() {
+            return Err(super::Error::UnsupportedAggregation {
+                function: String::from("group by"),
+                reason: String::from(
+                    "GROUP BY expressions must be present in the select list for cross-shard queries",
+                ),
+            });
+        }
+
         let buffer: VecDeque<DataRow> = std::mem::take(&mut self.buffer);
-        let mut rows = if aggregate.is_empty() {
+        // Rows with the same GROUP BY key are merged
+        // even if there are no aggregate functions.
+        let mut rows = if aggregate.is_empty() && aggregate.group_by().is_empty() {
             buffer
         } else if let Some(aggregates) = Aggregates::new(&buffer, decoder, aggregate, plan) {
             aggregates.aggregate()?
@@ -154,12 +165,51 @@ impl Buffer {
             buffer
         };
 
+        Self::having(&mut rows, decoder, plan)?;
         Self::drop_helper_columns(&mut rows, plan);
         self.buffer = rows;
 
         Ok(())
     }
 
+    /// Remove groups that don't pass the HAVING clause.
+    /// This has to happen after they are merged.
+    fn having(
+        rows: &mut VecDeque<DataRow>,
+        decoder: &Decoder,
+        plan: &AggregateRewritePlan,
+    ) -> Result<(), super::Error> {
+        let Some(having) = plan.having() else {
+            return Ok(());
+        };
+
+        if !having.supported() {
+            return Err(super::Error::UnsupportedAggregation {
+                function: String::from("having"),
+                reason: String::from(
+                    "HAVING must compare aggregates from the select list with constants for cross-shard queries, and can't be used in cross-shard prepared statements",
+                ),
+            });
+        }
+
+        rows.retain(|row| {
+            let values = (0..row.len())
+                .map(|index| {
+                    let column = row.get_column(index, decoder).ok().flatten()?;
+                    if column.value.is_null() {
+                        return None;
+                    }
+                    let value = column.value.encode(Format::Text).ok()?;
+                    Some(String::from_utf8_lossy(&value).into_owned())
+                })
+                .collect::<Vec<_>>();
+
+            having.matches(|index| values.get(index)?.as_deref())
+        });
+
+        Ok(())
+    }
+
     fn drop_helper_columns(rows: &mut VecDeque<DataRow>, plan: &AggregateRewritePlan) {
         if plan.is_noop() {
             return;
@@ -324,6 +374,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_aggregate_buffer_group_by_without_aggregates() {
+        let mut buf = Buffer::default();
+        let rd = RowDescription::new(&[Field::text("region")]);
+        let agg = Aggregate::new_group_by(&[0]);
+
+        for region in ["us", "eu", "us", "eu", "apac"] {
+            let mut dr = DataRow::new();
+            dr.add(region);
+            buf.add(dr.message().unwrap()).unwrap();
+        }
+
+        buf.aggregate(&agg, &Decoder::from(&rd), &AggregateRewritePlan::default())
+            .unwrap();
+        buf.full();
+
+        let mut regions = vec![];
+        while let Some(row) = buf.take() {
+            let dr = DataRow::from_bytes(row.to_bytes()).unwrap();
+            regions.push(dr.get::<String>(0, Format::Text).unwrap());
+        }
+        assert_eq!(regions, ["us", "eu", "apac"]);
+    }
+
     #[test]
     fn test_sort_buffer_with_timestamps() {
         let mut buf = Buffer::default();
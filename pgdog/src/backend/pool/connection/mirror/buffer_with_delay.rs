@@ -10,4 +10,6 @@ use crate::frontend::ClientRequest;
 pub struct BufferWithDelay {
     pub(super) delay: Duration,
     pub(super) buffer: ClientRequest,
+    /// Checksum of the rows returned by the source database.
+    pub(super) checksum: Option<u32>,
 }
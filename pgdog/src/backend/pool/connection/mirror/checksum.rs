@@ -0,0 +1,70 @@
+//! Checksum of rows returned by a query,
+//! used to compare mirrored results with the source database.
+
+use crate::net::{Message, Protocol};
+
+/// Running checksum over DataRow and CommandComplete messages.
+///
+/// Each message is hashed separately and the hashes are summed,
+/// so rows returned in a different order produce the same checksum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResultChecksum {
+    value: u32,
+}
+
+impl ResultChecksum {
+    /// Add server message to the checksum. Messages other than
+    /// DataRow and CommandComplete are ignored.
+    pub fn update(&mut self, message: &Message) {
+        if matches!(message.code(), 'D' | 'C') {
+            self.value = self.value.wrapping_add(crc32c::crc32c(&message.payload()));
+        }
+    }
+
+    /// Checksum value.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::net::{CommandComplete, DataRow};
+
+    fn rows(values: &[&str]) -> Vec<Message> {
+        let mut messages = values
+            .iter()
+            .map(|value| {
+                let mut dr = DataRow::new();
+                dr.add(*value);
+                dr.message().unwrap()
+            })
+            .collect::<Vec<_>>();
+        messages.push(
+            CommandComplete::new(format!("SELECT {}", values.len()))
+                .message()
+                .unwrap(),
+        );
+        messages
+    }
+
+    fn checksum(messages: &[Message]) -> u32 {
+        let mut checksum = ResultChecksum::default();
+        for message in messages {
+            checksum.update(message);
+        }
+        checksum.value()
+    }
+
+    #[test]
+    fn test_checksum_ignores_row_order() {
+        assert_eq!(checksum(&rows(&["1", "2"])), checksum(&rows(&["2", "1"])));
+    }
+
+    #[test]
+    fn test_checksum_detects_divergence() {
+        assert_ne!(checksum(&rows(&["1", "2"])), checksum(&rows(&["1", "3"])));
+        assert_ne!(checksum(&rows(&["1"])), checksum(&rows(&["1", "1"])));
+    }
+}
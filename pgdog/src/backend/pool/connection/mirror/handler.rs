@@ -38,6 +38,8 @@ pub struct MirrorHandler {
     timer: Instant,
     /// Reference to cluster stats for tracking mirror metrics.
     stats: Arc<Mutex<MirrorStats>>,
+    /// Last buffered request is waiting for its result checksum.
+    checksum_pending: bool,
 }
 
 impl MirrorHandler {
@@ -59,6 +61,7 @@ impl MirrorHandler {
             buffer: vec![],
             timer: Instant::now(),
             stats,
+            checksum_pending: false,
         }
     }
 
@@ -98,8 +101,10 @@ impl MirrorHandler {
                     self.buffer.push(BufferWithDelay {
                         buffer: buffer.clone(),
                         delay: Duration::ZERO,
+                        checksum: None,
                     });
                     self.timer = Instant::now();
+                    self.checksum_pending = self.config.checksum;
                     true
                 } else {
                     self.state = MirrorHandlerState::Dropping;
@@ -115,15 +120,36 @@ impl MirrorHandler {
                 self.buffer.push(BufferWithDelay {
                     delay: now.duration_since(self.timer),
                     buffer: buffer.clone(),
+                    checksum: None,
                 });
                 self.timer = now;
+                self.checksum_pending = self.config.checksum;
                 true
             }
         }
     }
 
+    /// The last buffered request needs the checksum
+    /// of the rows returned by the source database.
+    pub fn checksum_pending(&self) -> bool {
+        self.checksum_pending
+    }
+
+    /// Attach the source database's result checksum
+    /// to the last buffered request.
+    pub fn checksum(&mut self, checksum: u32) {
+        if self.checksum_pending
+            && let Some(last) = self.buffer.last_mut()
+        {
+            last.checksum = Some(checksum);
+        }
+        self.checksum_pending = false;
+    }
+
     /// Flush buffered requests to mirror.
     pub fn flush(&mut self) -> bool {
+        self.checksum_pending = false;
+
         self.increment_total_count();
 
         if self.state == MirrorHandlerState::Dropping {
@@ -155,6 +181,7 @@ impl MirrorHandler {
     /// Remove all messages from mirror buffer;
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.checksum_pending = false;
         self.state = MirrorHandlerState::Idle;
         debug!("mirror transaction cancelled");
     }
@@ -21,10 +21,12 @@ use crate::tasks;
 use super::Error;
 
 pub mod buffer_with_delay;
+pub mod checksum;
 pub mod handler;
 pub mod request;
 
 pub use buffer_with_delay::*;
+pub use checksum::*;
 pub use handler::*;
 pub use request::*;
 
@@ -108,6 +110,7 @@ impl Mirror {
                 queue_length: config.config.general.mirror_queue,
                 exposure: config.config.general.mirror_exposure,
                 level: MirroringLevel::default(),
+                checksum: false,
             });
 
         // Mirror queue.
@@ -126,11 +129,19 @@ impl Mirror {
                                 stats.counts.queue_length = stats.counts.queue_length.saturating_sub(1);
                             }
                             // TODO: timeout these.
-                            if let Err(err) = mirror.handle(&mut req, &mut query_engine).await {
-                                error!("mirror error: {}", err);
-                                // Increment error count on mirror handling error
-                                let mut stats = stats_for_errors.lock();
-                                stats.counts.error_count += 1;
+                            match mirror.handle(&mut req, &mut query_engine).await {
+                                Ok(mismatches) => {
+                                    if mismatches > 0 {
+                                        let mut stats = stats_for_errors.lock();
+                                        stats.counts.mismatch_count += mismatches;
+                                    }
+                                }
+                                Err(err) => {
+                                    error!("mirror error: {}", err);
+                                    // Increment error count on mirror handling error
+                                    let mut stats = stats_for_errors.lock();
+                                    stats.counts.error_count += 1;
+                                }
                             }
                         } else {
                             debug!("mirror client shutting down");
@@ -145,24 +156,44 @@ impl Mirror {
     }
 
     /// Handle a single mirror request.
+    ///
+    /// Returns the number of requests that returned different
+    /// rows than the source database.
     pub async fn handle(
         &mut self,
         request: &mut MirrorRequest,
         query_engine: &mut QueryEngine,
-    ) -> Result<(), Error> {
+    ) -> Result<usize, Error> {
         debug!("mirroring {} client requests", request.buffer.len());
 
+        let mut mismatches = 0;
+
         for req in &mut request.buffer {
             if req.delay > Duration::ZERO {
                 sleep(req.delay).await;
             }
 
+            if req.checksum.is_some() {
+                query_engine.start_checksum();
+            }
+
             let mut context = QueryEngineContext::new_mirror(self, &mut req.buffer);
             query_engine.handle(&mut context).await?;
             self.transaction = context.transaction();
+
+            if let Some(expected) = req.checksum {
+                let actual = query_engine.take_checksum();
+                if actual != Some(expected) {
+                    warn!(
+                        "mirror result mismatch [expected: {}, actual: {:?}]",
+                        expected, actual
+                    );
+                    mismatches += 1;
+                }
+            }
         }
 
-        Ok(())
+        Ok(mismatches)
     }
 }
 
@@ -173,7 +204,7 @@ mod test {
     use crate::{
         backend::pool::Request,
         config::{self, PoolerMode, PreparedStatements as PreparedStatementsLevel},
-        net::{Parameter, Parameters, Query},
+        net::{CommandComplete, DataRow, Parameter, Parameters, Protocol, Query},
     };
 
     use super::*;
@@ -337,6 +368,61 @@ mod test {
         cluster.shutdown();
     }
 
+    async fn mirror_checksum(checksum: u32) -> usize {
+        config::load_test();
+        let cluster = Cluster::new_test(&config());
+        cluster.launch();
+
+        let initial = cluster.stats().lock().counts.mismatch_count;
+
+        let mut mirror = Mirror::spawn(
+            "pgdog",
+            &cluster,
+            Some(&MirrorConfig {
+                queue_length: 10,
+                exposure: 1.0,
+                checksum: true,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        assert!(mirror.send(&vec![Query::new("SELECT 1").into()].into()));
+        assert!(mirror.checksum_pending());
+        mirror.checksum(checksum);
+        assert!(!mirror.checksum_pending());
+        assert!(mirror.flush());
+
+        sleep(Duration::from_millis(100)).await;
+
+        let mismatches = cluster.stats().lock().counts.mismatch_count - initial;
+        cluster.shutdown();
+
+        mismatches
+    }
+
+    #[tokio::test]
+    async fn test_mirror_checksum_match() {
+        let mut expected = ResultChecksum::default();
+        let mut row = DataRow::new();
+        row.add("1");
+        expected.update(&row.message().unwrap());
+        expected.update(&CommandComplete::new("SELECT 1").message().unwrap());
+
+        assert_eq!(mirror_checksum(expected.value()).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mirror_checksum_mismatch() {
+        let mut expected = ResultChecksum::default();
+        let mut row = DataRow::new();
+        row.add("2");
+        expected.update(&row.message().unwrap());
+        expected.update(&CommandComplete::new("SELECT 1").message().unwrap());
+
+        assert_eq!(mirror_checksum(expected.value()).await, 1);
+    }
+
     #[tokio::test]
     async fn test_mirror_uses_effective_prepared_statements_level() {
         config::load_test();
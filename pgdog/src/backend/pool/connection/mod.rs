@@ -118,6 +118,18 @@ impl Connection {
         }
     }
 
+    /// Mirrors are waiting for the result checksum of the current request.
+    pub fn mirror_checksum_pending(&self) -> bool {
+        self.mirrors.iter().any(|mirror| mirror.checksum_pending())
+    }
+
+    /// Send result checksum of the current request to mirrors.
+    pub fn mirror_checksum(&mut self, checksum: u32) {
+        for mirror in &mut self.mirrors {
+            mirror.checksum(checksum);
+        }
+    }
+
     /// Tell mirrors to flush buffered transaction.
     pub fn mirror_flush(&mut self) {
         for mirror in &mut self.mirrors {
@@ -135,7 +147,9 @@ impl Connection {
     /// Try to get a connection for the given route.
     async fn try_conn(&mut self, request: &Request, route: &Route) -> Result<(), Error> {
         if let Shard::Direct(shard) = route.shard() {
-            let mut server = if route.is_read() {
+            let mut server = if let Some(name) = route.replica() {
+                self.cluster()?.replica_named(*shard, name, request).await?
+            } else if route.use_replica() {
                 self.cluster()?.replica(*shard, request).await?
             } else {
                 self.cluster()?.primary(*shard, request).await?
@@ -157,7 +171,7 @@ impl Connection {
                 {
                     continue;
                 };
-                let mut server = if route.is_read() {
+                let mut server = if route.use_replica() {
                     shard.replica(request).await?
                 } else {
                     shard.primary(request).await?
@@ -0,0 +1,115 @@
+//! Combine `EXPLAIN ANALYZE` output from multiple shards.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::net::messages::{DataRow, Message, Protocol, RowDescription};
+
+use super::Error;
+
+/// Plans returned by each shard, labeled and
+/// followed by a summary row once all shards finished.
+#[derive(Debug, Default)]
+pub(super) struct ExplainAnalyze {
+    /// Plan lines, grouped by shard number.
+    plans: BTreeMap<usize, Vec<String>>,
+    /// Rows ready to be sent to the client.
+    rows: VecDeque<Message>,
+}
+
+impl ExplainAnalyze {
+    /// The result set is a text plan we know how to combine.
+    pub(super) fn supported(rd: &RowDescription) -> bool {
+        rd.fields.len() == 1 && rd.field(0).map(|field| field.type_oid) == Some(25)
+    }
+
+    /// Record a plan line received from a shard.
+    pub(super) fn add(&mut self, shard: usize, row: &DataRow) {
+        self.plans
+            .entry(shard)
+            .or_default()
+            .push(row.get_text(0).unwrap_or_default());
+    }
+
+    /// We received plan lines that haven't been combined yet.
+    pub(super) fn is_pending(&self) -> bool {
+        !self.plans.is_empty()
+    }
+
+    /// All shards finished, build the combined plan.
+    pub(super) fn finish(&mut self) -> Result<(), Error> {
+        for line in self.render() {
+            let mut row = DataRow::new();
+            row.add(line);
+            self.rows.push_back(row.message()?);
+        }
+        self.plans.clear();
+
+        Ok(())
+    }
+
+    /// Get the next combined plan row.
+    pub(super) fn take(&mut self) -> Option<Message> {
+        self.rows.pop_front()
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.plans.clear();
+        self.rows.clear();
+    }
+
+    fn render(&self) -> Vec<String> {
+        let mut lines = vec![];
+        let mut total = 0.0;
+        let mut slowest: Option<(usize, f64)> = None;
+
+        for (shard, plan) in &self.plans {
+            lines.push(format!("Shard {}:", shard));
+
+            for line in plan {
+                if let Some(time) = execution_time(line) {
+                    total += time;
+                    if slowest.is_none_or(|(_, max)| time > max) {
+                        slowest = Some((*shard, time));
+                    }
+                }
+                lines.push(format!("  {}", line));
+            }
+        }
+
+        let mut summary = format!(
+            "Total Execution Time: {:.3} ms (shards: {}",
+            total,
+            self.plans.len()
+        );
+        if let Some((shard, time)) = slowest {
+            summary.push_str(&format!(", slowest: shard {} at {:.3} ms", shard, time));
+        }
+        summary.push(')');
+        lines.push(summary);
+
+        lines
+    }
+}
+
+/// Parse "Execution Time: 0.123 ms" from a plan line.
+fn execution_time(line: &str) -> Option<f64> {
+    line.trim()
+        .strip_prefix("Execution Time:")?
+        .trim()
+        .strip_suffix("ms")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_execution_time() {
+        assert_eq!(execution_time("Execution Time: 0.123 ms"), Some(0.123));
+        assert_eq!(execution_time("Planning Time: 0.123 ms"), None);
+        assert_eq!(execution_time("Seq Scan on sharded"), None);
+    }
+}
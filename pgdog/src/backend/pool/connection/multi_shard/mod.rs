@@ -5,7 +5,7 @@ use context::Context;
 use crate::{
     frontend::{PreparedStatements, router::Route},
     net::{
-        BackendPid, Decoder, ReadyForQuery,
+        BackendPid, Decoder, ErrorResponse, ReadyForQuery,
         messages::{
             DataRow, FromBytes, Message, Protocol, RowDescription, ToBytes,
             command_complete::CommandComplete,
@@ -17,11 +17,13 @@ use super::buffer::Buffer;
 
 mod context;
 mod error;
+mod explain;
 #[cfg(test)]
 mod test;
 mod validator;
 
 pub use error::Error;
+use explain::ExplainAnalyze;
 use validator::Validator;
 
 #[derive(Default, Debug)]
@@ -65,6 +67,8 @@ pub struct MultiShard {
     decoder: Decoder,
     /// Row consistency validator.
     validator: Validator,
+    /// Combined `EXPLAIN ANALYZE` output.
+    explain: ExplainAnalyze,
 }
 
 impl MultiShard {
@@ -106,12 +110,41 @@ impl MultiShard {
         self.counters = Counters::default();
         self.buffer.reset();
         self.validator.reset();
+        self.explain.reset();
         // Don't reset:
         //  1. Route to keep routing decision
         //  2. Number of shards
         //  3. Decoder
     }
 
+    /// Same as [`Self::forward`], but knows which server, by its position
+    /// in the servers vec, sent the message.
+    pub(super) fn forward_from(
+        &mut self,
+        position: usize,
+        message: Message,
+    ) -> Result<Option<Message>, Error> {
+        if message.code() == 'D' && self.explain_analyze() {
+            let data_row = DataRow::from_bytes(message.to_bytes())?;
+            self.explain.add(self.shard_index(position), &data_row);
+            return Ok(None);
+        }
+
+        // Tell the client which shard failed, since
+        // the table was truncated on the others.
+        if message.code() == 'E' && self.shards > 1 && self.route.is_truncate() {
+            let mut error = ErrorResponse::from_bytes(message.to_bytes())?;
+            let shard = self.shard_index(position);
+            error.detail = Some(match error.detail.take() {
+                Some(detail) => format!("TRUNCATE failed on shard {}: {}", shard, detail),
+                None => format!("TRUNCATE failed on shard {}", shard),
+            });
+            return self.forward(error.message()?);
+        }
+
+        self.forward(message)
+    }
+
     /// Check if the message should be sent to the client, skipped,
     /// or modified.
     pub(super) fn forward(&mut self, message: Message) -> Result<Option<Message>, Error> {
@@ -177,7 +210,11 @@ impl MultiShard {
                         self.buffer.limit(self.route.limit());
                     }
 
-                    if has_rows {
+                    if self.explain.is_pending() {
+                        // Send the combined plan first.
+                        self.explain.finish()?;
+                        self.counters.command_complete = Some(cc.message()?);
+                    } else if has_rows {
                         let rows = if self.should_buffer() {
                             self.buffer.len()
                         } else {
@@ -203,9 +240,12 @@ impl MultiShard {
                     self.decoder.row_description(&rd);
                 }
 
-                if self.counters.row_description == self.shards {
+                if self.counters.row_description.is_multiple_of(self.shards) {
                     // Only send it to the client once all shards sent it,
                     // so we don't get early requests from clients.
+                    //
+                    // The same request can describe both the statement and the portal,
+                    // so each shard sends one for each Describe.
                     let plan = self.route.aggregate_rewrite_plan();
                     if plan.is_noop() {
                         forward = Some(message);
@@ -338,8 +378,20 @@ impl MultiShard {
         self.shards > 1 && self.route.should_buffer() && !self.route.is_omnisharded()
     }
 
+    /// Combine `EXPLAIN ANALYZE` plans from all shards
+    /// instead of sending them to the client as-is.
+    fn explain_analyze(&self) -> bool {
+        self.shards > 1
+            && self.route.is_explain_analyze()
+            && ExplainAnalyze::supported(self.decoder.rd())
+    }
+
     /// Multi-shard state is ready to send messages.
     pub(super) fn message(&mut self) -> Option<Message> {
+        if let Some(row) = self.explain.take() {
+            return Some(row);
+        }
+
         match self.buffer.take() {
             Some(data_row) => Some(data_row),
             _ => self.counters.command_complete.take(),
@@ -1,6 +1,6 @@
 use crate::{
     frontend::router::parser::{Shard, ShardWithPriority},
-    net::{DataRow, Field},
+    net::{DataRow, Field, NoData},
 };
 
 use super::*;
@@ -124,6 +124,34 @@ fn test_rd_before_dr() {
     assert!(multi_shard.message().is_none());
 }
 
+#[test]
+fn test_describe_statement_and_portal() {
+    let mut multi_shard = MultiShard::new(
+        vec![0, 1],
+        &Route::read(ShardWithPriority::new_default_unset(Shard::All)),
+    );
+    let rd = RowDescription::new(&[Field::bigint("id")]);
+
+    // Describe 'S' and Describe 'P' in the same request:
+    // each shard sends a RowDescription for both.
+    for _ in 0..2 {
+        let result = multi_shard.forward(rd.message().unwrap()).unwrap();
+        assert!(result.is_none());
+        let result = multi_shard.forward(rd.message().unwrap()).unwrap();
+        assert_eq!(result, Some(rd.message().unwrap()));
+    }
+
+    // Describe 'P' of a portal that doesn't return rows.
+    let mut multi_shard = MultiShard::new(
+        vec![0, 1],
+        &Route::write(ShardWithPriority::new_default_unset(Shard::All)),
+    );
+    let result = multi_shard.forward(NoData.message().unwrap()).unwrap();
+    assert!(result.is_none());
+    let result = multi_shard.forward(NoData.message().unwrap()).unwrap();
+    assert_eq!(result, Some(NoData.message().unwrap()));
+}
+
 #[test]
 fn test_ready_for_query_error_preservation() {
     let route = Route::default();
@@ -267,3 +295,92 @@ fn test_omni_data_rows_only_from_first_server() {
         .unwrap();
     assert!(result.is_some()); // Should be forwarded
 }
+
+#[test]
+fn test_explain_analyze_combined() {
+    let mut route = Route::read(ShardWithPriority::new_table(Shard::All));
+    route.set_explain_analyze(true);
+    let mut multi_shard = MultiShard::new(vec![0, 1], &route);
+
+    let rd = RowDescription::new(&[Field::text("QUERY PLAN")]);
+    assert!(
+        multi_shard
+            .forward(rd.message().unwrap())
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        multi_shard
+            .forward(rd.message().unwrap())
+            .unwrap()
+            .is_some()
+    );
+
+    for (position, time) in [(0, "0.100"), (1, "0.250")] {
+        for line in [
+            "Seq Scan on sharded  (cost=0.00..1.01 rows=1 width=8)".to_string(),
+            "Planning Time: 0.050 ms".to_string(),
+            format!("Execution Time: {} ms", time),
+        ] {
+            let mut dr = DataRow::new();
+            dr.add(line);
+            let result = multi_shard
+                .forward_from(position, dr.message().unwrap())
+                .unwrap();
+            assert!(result.is_none());
+        }
+    }
+
+    for _ in 0..2 {
+        let result = multi_shard
+            .forward(CommandComplete::from_str("EXPLAIN").message().unwrap())
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    let mut lines = vec![];
+    while let Some(message) = multi_shard.message() {
+        if message.code() == 'D' {
+            let dr = DataRow::from_bytes(message.to_bytes()).unwrap();
+            lines.push(dr.get_text(0).unwrap());
+        } else {
+            let cc = CommandComplete::from_bytes(message.to_bytes()).unwrap();
+            assert_eq!(cc.tag(), "EXPLAIN");
+        }
+    }
+
+    assert_eq!(
+        lines,
+        vec![
+            "Shard 0:",
+            "  Seq Scan on sharded  (cost=0.00..1.01 rows=1 width=8)",
+            "  Planning Time: 0.050 ms",
+            "  Execution Time: 0.100 ms",
+            "Shard 1:",
+            "  Seq Scan on sharded  (cost=0.00..1.01 rows=1 width=8)",
+            "  Planning Time: 0.050 ms",
+            "  Execution Time: 0.250 ms",
+            "Total Execution Time: 0.350 ms (shards: 2, slowest: shard 1 at 0.250 ms)",
+        ]
+    );
+}
+
+#[test]
+fn test_truncate_error_names_shard() {
+    let route = Route::write(ShardWithPriority::new_table(Shard::All)).with_truncate(true);
+    let mut multi_shard = MultiShard::new(vec![0, 1], &route);
+
+    let error = ErrorResponse {
+        code: "42P01".into(),
+        message: "relation \"sharded\" does not exist".into(),
+        ..Default::default()
+    };
+
+    let result = multi_shard
+        .forward_from(1, error.message().unwrap())
+        .unwrap()
+        .unwrap();
+    let error = ErrorResponse::from_bytes(result.to_bytes()).unwrap();
+    assert_eq!(error.message, "relation \"sharded\" does not exist");
+    assert_eq!(error.detail.as_deref(), Some("TRUNCATE failed on shard 1"));
+}
@@ -76,6 +76,12 @@ pub enum Error {
 
     #[error("replica lag")]
     ReplicaLag,
+
+    #[error("no such replica")]
+    ReplicaNotFound,
+
+    #[error("backend out of connections")]
+    OutOfConnections,
 }
 
 impl Error {
@@ -92,6 +98,7 @@ impl Error {
                 | Self::NoDatabases
                 | Self::PubSubDisabled
                 | Self::PoolNoHealthTarget(_)
+                | Self::ReplicaNotFound
                 // Admin decisions — respect them.
                 | Self::ManualBan
                 // Programming errors.
@@ -123,6 +130,7 @@ mod tests {
         assert!(Error::Offline.is_retryable());
         assert!(Error::ReplicaLag.is_retryable());
         assert!(Error::PoolUnhealthy.is_retryable());
+        assert!(Error::OutOfConnections.is_retryable());
     }
 
     #[test]
@@ -133,5 +141,6 @@ mod tests {
         assert!(!Error::PubSubDisabled.is_retryable());
         assert!(!Error::FastShutdown.is_retryable());
         assert!(!Error::NoShard(0).is_retryable());
+        assert!(!Error::ReplicaNotFound.is_retryable());
     }
 }
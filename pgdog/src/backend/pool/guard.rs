@@ -58,7 +58,8 @@ impl Guard {
             let reset = cleanup.needed();
             let sync_prepared = server.sync_prepared();
             let needs_drain = server.needs_drain();
-            let force_close = server.force_close();
+            // Broken connections can't be recovered and are closed right away.
+            let force_close = server.force_close() || server.error();
             let needs_cleanup = rollback || reset || sync_prepared || needs_drain;
 
             server.reset_changed_params();
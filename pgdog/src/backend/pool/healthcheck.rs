@@ -8,6 +8,11 @@ use tracing::error;
 
 use super::{Error, Pool};
 use crate::backend::Server;
+use crate::net::{DataRow, FromBytes, Message, Protocol, ToBytes};
+use pgdog_postgres_types::Format;
+
+/// Detects the database role when a custom healthcheck query is configured.
+const ROLE_QUERY: &str = "SELECT pg_is_in_recovery()";
 
 /// Perform a healtcheck on a connection.
 pub struct Healtcheck<'a> {
@@ -55,8 +60,28 @@ impl<'a> Healtcheck<'a> {
             return Ok(());
         }
 
-        match timeout(self.healthcheck_timeout, self.conn.healthcheck(";")).await {
-            Ok(Ok(())) => Ok(()),
+        let query = self.pool.addr().healthcheck_query.as_deref();
+        let conn = &mut *self.conn;
+
+        match timeout(self.healthcheck_timeout, async move {
+            conn.healthcheck(query.unwrap_or(";")).await?;
+
+            // Detect the role with its own query, so whatever
+            // the custom healthcheck returns can't change it.
+            if query.is_some() {
+                conn.execute(ROLE_QUERY).await.map(Some)
+            } else {
+                Ok(None)
+            }
+        })
+        .await
+        {
+            Ok(Ok(role)) => {
+                if let Some(replica) = role.as_deref().and_then(Self::replica) {
+                    self.pool.set_replica(replica);
+                }
+                Ok(())
+            }
             Ok(Err(err)) => {
                 // Check if this is an administrator command termination
                 if Self::is_admin_termination(&err) {
@@ -73,6 +98,13 @@ impl<'a> Healtcheck<'a> {
         }
     }
 
+    /// Get the role from the result of `pg_is_in_recovery()`.
+    fn replica(messages: &[Message]) -> Option<bool> {
+        let message = messages.iter().find(|message| message.code() == 'D')?;
+        let row = DataRow::from_bytes(message.to_bytes()).ok()?;
+        row.get::<bool>(0, Format::Text)
+    }
+
     /// Check if the error is caused by administrator termination.
     fn is_admin_termination(err: &crate::backend::Error) -> bool {
         use crate::backend::Error;
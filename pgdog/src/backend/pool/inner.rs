@@ -3,9 +3,11 @@
 use std::cmp::max;
 use std::collections::VecDeque;
 use std::fmt::Display;
+use std::time::Duration;
 
 use crate::backend::{ConnectReason, DisconnectReason};
 use crate::backend::{Server, stats::Counts as BackendCounts};
+use crate::config::CheckoutFairness;
 use crate::net::messages::{BackendKeyData, FrontendPid};
 
 use tokio::time::Instant;
@@ -151,14 +153,36 @@ impl Inner {
         self.config.max
     }
 
+    /// Maximum number of connections in the pool, including
+    /// the reserve available only to reserved requests.
+    #[inline]
+    pub(super) fn max_reserved(&self) -> usize {
+        self.config.max + self.config.reserve_pool_size
+    }
+
+    /// The request can be given a connection. Once the pool
+    /// is saturated, only reserved requests can get one.
+    #[inline]
+    pub(super) fn can_serve(&self, request: &Request) -> bool {
+        request.reserved || self.checked_out() < self.max()
+    }
+
+    /// Position of the first waiter that can be given a connection.
+    #[inline]
+    fn next_waiter(&self) -> Option<usize> {
+        self.waiting.iter().position(|w| self.can_serve(&w.request))
+    }
+
     /// The pool should create more connections now.
     #[inline]
     pub(super) fn should_create(&self) -> ShouldCreate {
         let below_min = self.total() < self.min();
         let below_max = self.total() < self.max();
+        let below_max_reserved = self.total() < self.max_reserved();
         let maintain_min = below_min && below_max;
-        let client_needs =
-            below_max && !self.waiting.is_empty() && self.idle_connections.is_empty();
+        let reserved_waiting = self.waiting.iter().any(|w| w.request.reserved);
+        let client_needs = self.idle_connections.is_empty()
+            && (below_max && !self.waiting.is_empty() || below_max_reserved && reserved_waiting);
         let maintenance_on = self.online && !self.paused;
 
         // Clients from banned pools won't be able to request connections
@@ -230,15 +254,72 @@ impl Inner {
         removed
     }
 
+    /// Change the pool size limits. If the pool is now too large,
+    /// idle connections are closed; checked out connections
+    /// will be closed when they are returned.
+    pub(super) fn resize(&mut self, min: usize, max: usize) -> usize {
+        self.config.max = max;
+        self.config.min = min.min(max);
+
+        let mut remove = self.total().saturating_sub(max);
+        let mut removed = 0;
+
+        self.idle_connections.retain_mut(|c| {
+            if remove > 0 {
+                remove -= 1;
+                removed += 1;
+                c.disconnect_reason(DisconnectReason::Resized);
+                false
+            } else {
+                true
+            }
+        });
+
+        removed
+    }
+
     /// Pool configuration options.
     #[inline]
     pub(super) fn config(&self) -> &Config {
         &self.config
     }
 
+    /// Idle connections can be taken without waiting in line.
+    #[inline]
+    pub(super) fn can_take(&self) -> bool {
+        self.config.checkout_fairness == CheckoutFairness::Barging || self.waiting.is_empty()
+    }
+
+    /// Get in line for a connection. With FIFO fairness, the waiter
+    /// is placed according to when it first asked for a connection.
+    pub(super) fn add_waiter(&mut self, waiter: Waiter, now: Instant) -> Result<(), Error> {
+        if self.config.checkout_fairness == CheckoutFairness::Fifo {
+            let position = self
+                .waiting
+                .partition_point(|w| w.request.created_at <= waiter.request.created_at);
+            self.waiting.insert(position, waiter);
+        } else {
+            self.waiting.push_back(waiter);
+        }
+
+        // Idle connections go to whoever is first in line.
+        while self.next_waiter().is_some() {
+            let Some(conn) = self.idle_connections.pop() else {
+                break;
+            };
+            self.put(conn, now)?;
+        }
+
+        Ok(())
+    }
+
     /// Take connection from the idle pool.
     #[inline(always)]
     pub(super) fn take(&mut self, request: &Request) -> Result<Option<Box<Server>>, Error> {
+        if !self.can_serve(request) {
+            return Ok(None);
+        }
+
         match self.idle_connections.pop() {
             Some(conn) => {
                 let cancel_key = conn.key().clone();
@@ -250,6 +331,32 @@ impl Inner {
         }
     }
 
+    /// Take an idle connection that hasn't been used or pinged
+    /// for at least `interval`, so it can be pinged.
+    pub(super) fn take_ping(
+        &mut self,
+        request: &Request,
+        interval: Duration,
+        now: Instant,
+    ) -> Option<Box<Server>> {
+        let position = self
+            .idle_connections
+            .iter()
+            .position(|conn| conn.ping_age(now) >= interval)?;
+        let conn = self.idle_connections.remove(position);
+        self.taken.take(request.id, conn.id(), conn.key().clone());
+
+        Some(conn)
+    }
+
+    /// Forget a taken connection that's being closed instead of checked in,
+    /// without counting it as a server error.
+    pub(super) fn forget(&mut self, server: &Server) -> Result<(), Error> {
+        self.taken.check_in(server.id())?;
+
+        Ok(())
+    }
+
     /// Place connection back into the pool
     /// or give it to a waiting client.
     #[inline]
@@ -257,7 +364,8 @@ impl Inner {
         // Try to give it to a client that's been waiting, if any.
         let cancel_key = conn.key().clone();
         let server_id = conn.id();
-        while let Some(waiter) = self.waiting.pop_front() {
+        while let Some(position) = self.next_waiter() {
+            let waiter = self.waiting.remove(position).expect("waiter position");
             match waiter.tx.send(Ok(conn)) {
                 Err(conn_ret) => {
                     conn = conn_ret.unwrap(); // SAFETY: We sent Ok(conn), we'll get back Ok(conn) if channel is closed.
@@ -395,6 +503,18 @@ impl Inner {
             return Ok(result);
         }
 
+        // Pool was resized and has too many connections,
+        // or the connection came from the reserve pool.
+        if self.total() >= self.max() {
+            result.replenish = false;
+            server.disconnect_reason(if self.total() < self.max_reserved() {
+                DisconnectReason::Reserve
+            } else {
+                DisconnectReason::Resized
+            });
+            return Ok(result);
+        }
+
         if server.re_synced() {
             self.re_synced += 1;
             server.reset_re_synced();
@@ -442,6 +562,18 @@ impl Inner {
             let _ = waiter.tx.send(Err(err));
         }
     }
+
+    /// Fail waiters that can be sent to another database.
+    /// The others keep waiting for a connection.
+    pub(super) fn close_fail_fast_waiters(&mut self, err: Error) {
+        for waiter in std::mem::take(&mut self.waiting) {
+            if waiter.request.fail_fast {
+                let _ = waiter.tx.send(Err(err));
+            } else {
+                self.waiting.push_back(waiter);
+            }
+        }
+    }
 }
 
 /// Result of connection check into the pool.
@@ -820,6 +952,47 @@ mod test {
         assert_eq!(taken.cancel_key(other_client), None);
     }
 
+    #[test]
+    fn test_resize_closes_idle_only() {
+        let mut inner = Inner {
+            online: true,
+            ..Default::default()
+        };
+        inner.config.max = 4;
+
+        for _ in 0..3 {
+            inner.idle_connections.push(Box::new(Server::default()));
+        }
+        let server = Box::new(Server::default());
+        inner
+            .taken
+            .take(FrontendPid::new(), server.id(), server.key().clone());
+
+        // Shrink below the number of checked out connections.
+        assert_eq!(inner.resize(2, 1), 3);
+        assert_eq!(inner.idle(), 0);
+        assert_eq!(inner.checked_out(), 1);
+        assert_eq!(inner.min(), 1);
+
+        // Not over the limit once checked in.
+        inner
+            .maybe_check_in(server, Instant::now(), BackendCounts::default(), false)
+            .unwrap();
+        assert_eq!(inner.idle(), 1);
+
+        // Over the limit, closed on check in.
+        inner.resize(0, 0);
+        let server = Box::new(Server::default());
+        inner
+            .taken
+            .take(FrontendPid::new(), server.id(), server.key().clone());
+        let result = inner
+            .maybe_check_in(server, Instant::now(), BackendCounts::default(), false)
+            .unwrap();
+        assert!(!result.replenish);
+        assert_eq!(inner.total(), 0);
+    }
+
     #[test]
     fn test_can_remove() {
         let mut inner = Inner::default();
@@ -1,4 +1,5 @@
 use super::*;
+use crate::backend::pool::Config;
 use parking_lot::RwLock;
 use std::{fmt::Display, time::Instant};
 
@@ -32,7 +33,7 @@ impl Ban {
     /// Create new ban handler.
     pub(super) fn new(pool: &Pool) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(BanInner { ban: None })),
+            inner: Arc::new(RwLock::new(BanInner::default())),
             pool: pool.clone(),
         }
     }
@@ -47,6 +48,17 @@ impl Ban {
         self.inner.read().ban.as_ref().map(|b| b.error)
     }
 
+    /// Why the database is banned, if it is.
+    pub fn reason(&self) -> Option<String> {
+        self.error().map(|err| err.to_string())
+    }
+
+    /// Duration of the current ban.
+    #[cfg(test)]
+    pub fn ban_timeout(&self) -> Option<Duration> {
+        self.inner.read().ban.as_ref().map(|ban| ban.ban_timeout)
+    }
+
     /// Time remaining before the ban expires.
     ///
     /// Returns `None` when the pool isn't banned or the ban is manual, since
@@ -77,6 +89,7 @@ impl Ban {
             if ban.error != Error::ManualBan || !manual_check {
                 guard.with_upgraded(|guard| {
                     guard.ban = None;
+                    guard.unbanned_at = Some(Instant::now());
                 });
                 unbanned = true;
             }
@@ -93,12 +106,20 @@ impl Ban {
     }
 
     /// Ban the database for the ban_timeout duration.
+    ///
+    /// If the database was banned recently, the duration is doubled
+    /// every time, up to `ban_timeout_max`.
     pub fn ban(&self, error: Error, ban_timeout: Duration) -> bool {
         let created_at = Instant::now();
         let mut guard = self.inner.upgradable_read();
 
         if guard.ban.is_none() {
             guard.with_upgraded(|guard| {
+                let ban_timeout = if error == Error::ManualBan {
+                    ban_timeout
+                } else {
+                    guard.backoff(self.pool.config(), ban_timeout, created_at)
+                };
                 guard.ban = Some(BanEntry {
                     created_at,
                     error,
@@ -133,6 +154,7 @@ impl Ban {
             }
             guard.with_upgraded(|guard| {
                 guard.ban = None;
+                guard.unbanned_at = Some(now);
             });
 
             true
@@ -158,9 +180,40 @@ struct BanEntry {
     ban_timeout: Duration,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub(super) struct BanInner {
     ban: Option<BanEntry>,
+    /// Number of bans since the database was last stable.
+    bans: u32,
+    unbanned_at: Option<Instant>,
+}
+
+impl BanInner {
+    /// Get the duration of the next ban, backing off
+    /// if the database keeps getting banned.
+    fn backoff(&mut self, config: &Config, ban_timeout: Duration, now: Instant) -> Duration {
+        if config.ban_timeout_max <= ban_timeout {
+            return ban_timeout;
+        }
+
+        let stable = self
+            .unbanned_at
+            .map(|unbanned_at| {
+                now.saturating_duration_since(unbanned_at) >= config.ban_backoff_window
+            })
+            .unwrap_or(true);
+
+        if stable {
+            self.bans = 0;
+        }
+
+        let ban_timeout = ban_timeout
+            .saturating_mul(2u32.saturating_pow(self.bans))
+            .min(config.ban_timeout_max);
+        self.bans = self.bans.saturating_add(1);
+
+        ban_timeout
+    }
 }
 
 impl BanEntry {
@@ -198,6 +251,7 @@ mod tests {
         let ban = Ban::new(&pool);
         ban.ban(Error::ConnectTimeout, Duration::from_secs(1));
         assert_eq!(ban.error(), Some(Error::ConnectTimeout));
+        assert_eq!(ban.reason().as_deref(), Some("connect timeout"));
     }
 
     #[test]
@@ -426,6 +480,62 @@ mod tests {
         assert!(ban.banned());
     }
 
+    fn backoff_pool() -> Pool {
+        Pool::new(&PoolConfig {
+            address: crate::backend::pool::Address::new_test(),
+            config: Config {
+                inner: pgdog_stats::Config {
+                    ban_timeout_max: Duration::from_millis(350),
+                    ban_backoff_window: Duration::from_millis(50),
+                    ..Default::default()
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn test_ban_backoff() {
+        let pool = backoff_pool();
+        let ban = Ban::new(&pool);
+        let ban_timeout = Duration::from_millis(100);
+
+        // Flapping pool, ban duration keeps growing up to the max.
+        for expected in [100, 200, 350, 350] {
+            assert!(ban.ban(Error::ServerError, ban_timeout));
+            assert_eq!(ban.ban_timeout(), Some(Duration::from_millis(expected)));
+            ban.unban(false, UnbanReason::Manual);
+        }
+
+        // Pool was stable for a while, back to the original ban duration.
+        thread::sleep(Duration::from_millis(60));
+        assert!(ban.ban(Error::ServerError, ban_timeout));
+        assert_eq!(ban.ban_timeout(), Some(ban_timeout));
+    }
+
+    #[test]
+    fn test_ban_backoff_expired() {
+        let pool = backoff_pool();
+        let ban = Ban::new(&pool);
+        let ban_timeout = Duration::from_millis(10);
+
+        ban.ban(Error::ServerError, ban_timeout);
+        assert!(ban.unban_if_expired(Instant::now() + ban_timeout));
+        ban.ban(Error::ServerError, ban_timeout);
+        assert_eq!(ban.ban_timeout(), Some(ban_timeout * 2));
+    }
+
+    #[test]
+    fn test_ban_backoff_disabled() {
+        let pool = Pool::new_test();
+        let ban = Ban::new(&pool);
+
+        for _ in 0..5 {
+            ban.ban(Error::ServerError, Duration::from_millis(100));
+            assert_eq!(ban.ban_timeout(), Some(Duration::from_millis(100)));
+            ban.unban(false, UnbanReason::Manual);
+        }
+    }
+
     #[test]
     fn test_unban_if_expired_does_not_unban_manual_ban() {
         let pool = Pool::new_test();
@@ -8,13 +8,13 @@ use std::{
     time::{Duration, SystemTime},
 };
 
-use rand::seq::SliceRandom;
+use rand::{Rng, seq::SliceRandom};
 use tokio::{sync::Notify, time::timeout};
 use tracing::warn;
 
 use crate::{config::config, net::messages::FrontendPid};
 use crate::{
-    config::{LoadBalancingStrategy, ReadWriteSplit, Role},
+    config::{LoadBalancingStrategy, ReadWriteSplit, Role, Workload},
     net::Parameters,
 };
 
@@ -32,6 +32,10 @@ pub use target_health::*;
 #[cfg(test)]
 mod test;
 
+/// Replication lag, in bytes, that halves the share of reads
+/// a replica gets with the freshness strategy. One WAL segment.
+const FRESHNESS_LAG_UNIT: f64 = 16.0 * 1024.0 * 1024.0;
+
 /// Read query load balancer target.
 #[derive(Clone, Debug)]
 pub struct Target {
@@ -55,6 +59,15 @@ impl Target {
         }
     }
 
+    /// Target is addressed by this name, either `host` or `host:port`.
+    fn is_named(&self, name: &str) -> bool {
+        let addr = self.pool.addr();
+        match name.rsplit_once(':') {
+            Some((host, port)) => host == addr.host && port.parse() == Ok(addr.port),
+            None => name == addr.host,
+        }
+    }
+
     /// Get role.
     pub(super) fn role(&self) -> Role {
         let role = self.role.load(Ordering::Relaxed);
@@ -220,6 +233,24 @@ impl LoadBalancer {
         self.get_internal(request).await
     }
 
+    /// Get a live connection from the target with the given name,
+    /// either `host` or `host:port`. We don't fall back to other targets
+    /// if it's banned or unavailable.
+    pub async fn get_named(&self, name: &str, request: &Request) -> Result<Guard, Error> {
+        let target = self
+            .targets
+            .iter()
+            .filter(|target| matches!(target.role(), Role::Replica | Role::Auto))
+            .find(|target| target.is_named(name))
+            .ok_or(Error::ReplicaNotFound)?;
+
+        if target.ban.banned() {
+            return Err(Error::Banned);
+        }
+
+        target.pool.get(request).await
+    }
+
     /// Get parameters from first non-banned connection pool.
     pub async fn params(&self, request: &Request) -> Result<&Parameters, Error> {
         if let Some(target) = self.targets.iter().find(|target| !target.ban.banned()) {
@@ -279,6 +310,16 @@ impl LoadBalancer {
             .any(|target| matches!(target.role(), Role::Replica | Role::Auto))
     }
 
+    /// Returns true if there are replicas and all of them are banned.
+    pub fn replicas_banned(&self) -> bool {
+        self.has_replicas()
+            && self
+                .targets
+                .iter()
+                .filter(|target| matches!(target.role(), Role::Replica | Role::Auto))
+                .all(|target| target.ban.banned())
+    }
+
     /// Cancel a query if one is running.
     pub async fn cancel(&self, id: FrontendPid) -> Result<(), super::super::Error> {
         for target in &self.targets {
@@ -346,7 +387,6 @@ impl LoadBalancer {
     }
 
     async fn get_internal(&self, request: &Request) -> Result<Guard, Error> {
-        use LoadBalancingStrategy::*;
         use ReadWriteSplit::*;
 
         let mut candidates: Vec<&Target> = self
@@ -380,14 +420,74 @@ impl LoadBalancer {
             return Err(Error::AllReplicasDown);
         }
 
+        // Send expensive reads to analytics replicas and keep other reads
+        // away from them. The others are only used if they are all down.
+        let (preferred, others): (Vec<&Target>, Vec<&Target>) =
+            candidates.into_iter().partition(|target| {
+                (target.pool.config().workload == Workload::Analytics) == request.analytics
+            });
+        let (mut candidates, others) = if preferred.is_empty() {
+            (others, vec![])
+        } else {
+            (preferred, others)
+        };
+
+        self.order(&mut candidates);
+        candidates.extend(others);
+
+        // Databases that just returned a broken connection are tried last,
+        // until the health check passes again or the monitor bans them.
+        candidates.sort_by_key(|target| !target.health.healthy());
+
+        // Only ban a candidate pool if there are more than one
+        // and we have alternates.
+        let bannable = candidates.len() > 1;
+        let request = Request {
+            fail_fast: bannable,
+            ..*request
+        };
+
+        for target in &candidates {
+            if target.ban.banned() {
+                continue;
+            }
+            match target.pool.get(&request).await {
+                Ok(conn) => return Ok(conn),
+                Err(Error::Offline) => {
+                    continue;
+                }
+                Err(err) => {
+                    if bannable {
+                        let config = target.pool.config();
+                        // Postgres frees up connection slots on its own,
+                        // so it's only skipped while they are in use.
+                        let ban_timeout = if err == Error::OutOfConnections {
+                            config.checkout_timeout.min(config.ban_timeout)
+                        } else {
+                            config.ban_timeout
+                        };
+                        target.ban.ban(err, ban_timeout);
+                    }
+                }
+            }
+        }
+
+        candidates
+            .iter()
+            .for_each(|target| target.ban.unban(true, UnbanReason::AllTargetsBanned));
+
+        Err(Error::AllReplicasDown)
+    }
+
+    /// Order candidates according to the load balancing strategy.
+    fn order(&self, candidates: &mut [&Target]) {
+        use LoadBalancingStrategy::*;
+
         match self.lb_strategy {
             Random => candidates.shuffle(&mut rand::rng()),
             RoundRobin => {
                 let first = self.round_robin.fetch_add(1, Ordering::Relaxed) % candidates.len();
-                let mut reshuffled = vec![];
-                reshuffled.extend_from_slice(&candidates[first..]);
-                reshuffled.extend_from_slice(&candidates[..first]);
-                candidates = reshuffled;
+                candidates.rotate_left(first);
             }
             LeastActiveConnections => {
                 candidates.sort_by_cached_key(|target| target.pool.lock().checked_out());
@@ -399,7 +499,7 @@ impl LoadBalancer {
                     .sum();
 
                 if total_weight > 0 {
-                    for target in &candidates {
+                    for target in candidates.iter() {
                         target
                             .current_weight
                             .fetch_add(target.pool.config().lb_weight as i64, Ordering::Relaxed);
@@ -419,34 +519,47 @@ impl LoadBalancer {
                     candidates.swap(0, max_idx);
                 }
             }
+            Freshness => Self::sort_by_freshness(candidates),
         }
+    }
 
-        // Only ban a candidate pool if there are more than one
-        // and we have alternates.
-        let bannable = candidates.len() > 1;
-
-        for target in &candidates {
-            if target.ban.banned() {
-                continue;
-            }
-            match target.pool.get(request).await {
-                Ok(conn) => return Ok(conn),
-                Err(Error::Offline) => {
-                    continue;
-                }
-                Err(err) => {
-                    if bannable {
-                        target.ban.ban(err, target.pool.config().ban_timeout);
-                    }
-                }
-            }
-        }
+    /// Order candidates at random, weighted by how close they are
+    /// to the most up-to-date candidate. Replicas that are behind still get
+    /// some traffic, just less of it the further behind they are.
+    fn sort_by_freshness(candidates: &mut [&Target]) {
+        let stats = candidates
+            .iter()
+            .map(|target| target.pool.lsn_stats())
+            .collect::<Vec<_>>();
+        let freshest = stats
+            .iter()
+            .filter(|stats| stats.valid())
+            .map(|stats| stats.lsn.lsn)
+            .max()
+            .unwrap_or_default();
 
-        candidates
+        let mut rng = rand::rng();
+        let mut weighted = candidates
             .iter()
-            .for_each(|target| target.ban.unban(true, UnbanReason::AllTargetsBanned));
+            .zip(stats)
+            .map(|(target, stats)| {
+                // Without stats, we don't know how far behind it is.
+                let lag = if stats.valid() {
+                    (freshest - stats.lsn.lsn).max(0)
+                } else {
+                    0
+                };
+                let weight = 1.0 / (1.0 + lag as f64 / FRESHNESS_LAG_UNIT);
+                // Weighted random sampling without replacement (Efraimidis-Spirakis).
+                (rng.random::<f64>().powf(1.0 / weight), *target)
+            })
+            .collect::<Vec<_>>();
 
-        Err(Error::AllReplicasDown)
+        weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        for (candidate, (_, target)) in candidates.iter_mut().zip(weighted) {
+            *candidate = target;
+        }
     }
 
     /// Shutdown replica pools.
@@ -789,6 +789,23 @@ async fn test_monitor_shuts_down_on_notify() {
     );
 }
 
+#[tokio::test]
+async fn test_unhealthy_target_tried_last() {
+    let replicas = setup_test_replicas();
+    let request = Request::default();
+
+    // Returned a broken connection, but isn't banned yet.
+    replicas.targets[0].health.toggle(false);
+    assert!(!replicas.targets[0].ban.banned());
+
+    for _ in 0..10 {
+        let conn = replicas.get(&request).await.unwrap();
+        assert_eq!(conn.pool.id(), replicas.targets[1].pool.id());
+    }
+
+    replicas.shutdown();
+}
+
 #[tokio::test]
 async fn test_monitor_bans_unhealthy_target() {
     let replicas = setup_test_replicas();
@@ -2181,3 +2198,310 @@ async fn test_params_returns_all_replicas_down_when_empty() {
         "params() should return AllReplicasDown when no targets exist"
     );
 }
+
+#[tokio::test]
+async fn test_get_named_replica() {
+    let replicas = setup_test_replicas();
+    let request = Request::default();
+
+    for _ in 0..5 {
+        let conn = replicas.get_named("localhost", &request).await.unwrap();
+        assert_eq!(conn.addr().host, "localhost");
+    }
+
+    let conn = replicas
+        .get_named("127.0.0.1:5432", &request)
+        .await
+        .unwrap();
+    assert_eq!(conn.addr().host, "127.0.0.1");
+    drop(conn);
+
+    let result = replicas.get_named("127.0.0.1:5433", &request).await;
+    assert!(matches!(result, Err(Error::ReplicaNotFound)));
+
+    let result = replicas.get_named("unknown", &request).await;
+    assert!(matches!(result, Err(Error::ReplicaNotFound)));
+
+    replicas.shutdown();
+}
+
+#[tokio::test]
+async fn test_get_named_replica_banned() {
+    let replicas = setup_test_replicas();
+    let request = Request::default();
+
+    replicas.targets[1]
+        .ban
+        .ban(Error::ServerError, Duration::from_secs(60));
+
+    // No fallback to the healthy replica.
+    let result = replicas.get_named("localhost", &request).await;
+    assert!(matches!(result, Err(Error::Banned)));
+
+    let conn = replicas.get_named("127.0.0.1", &request).await.unwrap();
+    assert_eq!(conn.addr().host, "127.0.0.1");
+    drop(conn);
+
+    replicas.shutdown();
+}
+
+/// Server that refuses every connection because it reached `max_connections`.
+async fn out_of_connections_server() -> u16 {
+    use crate::net::{ErrorResponse, Startup, ToBytes};
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut startup = Startup::from_stream(&mut socket).await.unwrap();
+                if matches!(startup, Startup::Ssl) {
+                    socket.write_all(b"N").await.unwrap();
+                    startup = Startup::from_stream(&mut socket).await.unwrap();
+                }
+                assert!(matches!(startup, Startup::Startup { .. }));
+
+                let error = ErrorResponse {
+                    severity: "FATAL".into(),
+                    code: "53300".into(),
+                    message: "sorry, too many clients already".into(),
+                    ..Default::default()
+                };
+                let _ = socket.write_all(&error.to_bytes()).await;
+            });
+        }
+    });
+
+    port
+}
+
+#[tokio::test]
+async fn test_replica_out_of_connections_banned() {
+    let port = out_of_connections_server().await;
+
+    let mut full = create_test_pool_config("127.0.0.1", port);
+    full.config.inner.ban_timeout = Duration::from_secs(300);
+
+    let replicas = LoadBalancer::new(
+        &None,
+        &[full, create_test_pool_config("127.0.0.1", 5432)],
+        LoadBalancingStrategy::RoundRobin,
+        ReadWriteSplit::IncludePrimary,
+    );
+    replicas.launch();
+
+    // Clients are sent to the other replica without waiting for the checkout timeout.
+    for _ in 0..2 {
+        let conn = replicas.get(&Request::default()).await.unwrap();
+        assert_eq!(conn.addr().port, 5432);
+    }
+
+    let full = &replicas.targets[0];
+    assert!(full.ban.banned());
+    assert_eq!(full.ban.error(), Some(Error::OutOfConnections));
+    // Banned only for as long as a client would wait, not the full ban_timeout.
+    assert_eq!(full.ban.ban_timeout(), Some(Duration::from_millis(1000)));
+    assert!(full.pool.state().stats.counts.out_of_connections >= 1);
+    assert_eq!(
+        replicas.targets[1]
+            .pool
+            .state()
+            .stats
+            .counts
+            .out_of_connections,
+        0
+    );
+
+    replicas.shutdown();
+}
+
+#[tokio::test]
+async fn test_analytics_workload_routing() {
+    let mut analytics = create_test_pool_config("127.0.0.1", 5432);
+    analytics.config.workload = Workload::Analytics;
+
+    let replicas = LoadBalancer::new(
+        &None,
+        &[
+            create_test_pool_config("localhost", 5432),
+            analytics,
+            create_test_pool_config("127.0.0.1", 5432),
+        ],
+        LoadBalancingStrategy::Random,
+        ReadWriteSplit::IncludePrimary,
+    );
+    replicas.launch();
+
+    let analytics_id = replicas.targets[1].pool.id();
+    let expensive = Request {
+        analytics: true,
+        ..Request::default()
+    };
+
+    // Expensive reads only go to the analytics replica.
+    for _ in 0..20 {
+        let conn = replicas.get(&expensive).await.unwrap();
+        assert_eq!(conn.pool.id(), analytics_id);
+    }
+
+    // Cheap reads stay on the other replicas.
+    let mut ids = HashSet::new();
+    for _ in 0..50 {
+        let conn = replicas.get(&Request::default()).await.unwrap();
+        ids.insert(conn.pool.id());
+    }
+    assert_eq!(ids.len(), 2);
+    assert!(!ids.contains(&analytics_id));
+
+    // Expensive reads use other replicas if the analytics one is down.
+    replicas.targets[1]
+        .ban
+        .ban(Error::ServerError, Duration::from_millis(1000));
+    let conn = replicas.get(&expensive).await.unwrap();
+    assert_ne!(conn.pool.id(), analytics_id);
+
+    replicas.shutdown();
+}
+
+#[tokio::test]
+async fn test_freshness_prefers_replicas_with_less_lag() {
+    let pool_config1 = create_test_pool_config("127.0.0.1", 5432);
+    let pool_config2 = create_test_pool_config("localhost", 5432);
+    let pool_config3 = create_test_pool_config("127.0.0.1", 5433);
+
+    let lb = LoadBalancer::new(
+        &None,
+        &[pool_config1, pool_config2, pool_config3],
+        LoadBalancingStrategy::Freshness,
+        ReadWriteSplit::IncludePrimary,
+    );
+
+    // Up to date, one and three WAL segments behind.
+    let unit = FRESHNESS_LAG_UNIT as i64;
+    set_lsn_stats(&lb.targets[0], true, 10 * unit);
+    set_lsn_stats(&lb.targets[1], true, 9 * unit);
+    set_lsn_stats(&lb.targets[2], true, 7 * unit);
+
+    // Weights are 1, 1/2 and 1/4.
+    let mut first = [0; 3];
+    for _ in 0..7000 {
+        let mut candidates: Vec<&Target> = lb.targets.iter().collect();
+        LoadBalancer::sort_by_freshness(&mut candidates);
+        assert_eq!(candidates.len(), 3);
+
+        let index = lb
+            .targets
+            .iter()
+            .position(|target| target.pool.id() == candidates[0].pool.id())
+            .unwrap();
+        first[index] += 1;
+    }
+
+    assert!(first[0] > first[1], "{:?}", first);
+    assert!(first[1] > first[2], "{:?}", first);
+    // Laggards are not excluded.
+    assert!(first[2] > 0, "{:?}", first);
+    // Expected 4000, 2000 and 1000.
+    assert!((3600..4400).contains(&first[0]), "{:?}", first);
+    assert!((850..1150).contains(&first[2]), "{:?}", first);
+}
+
+#[tokio::test]
+async fn test_only_target_out_of_connections_waits() {
+    let port = out_of_connections_server().await;
+
+    let replicas = LoadBalancer::new(
+        &None,
+        &[create_test_pool_config("127.0.0.1", port)],
+        LoadBalancingStrategy::RoundRobin,
+        ReadWriteSplit::IncludePrimary,
+    );
+    replicas.launch();
+
+    // There is nowhere else to go, so the client waits
+    // for a connection slot until the checkout timeout.
+    let start = tokio::time::Instant::now();
+    assert!(replicas.get(&Request::default()).await.is_err());
+    assert!(start.elapsed() >= Duration::from_millis(1000));
+    assert!(!replicas.targets[0].ban.banned());
+
+    replicas.shutdown();
+}
+
+#[tokio::test]
+async fn test_analytics_workload_round_robin() {
+    let mut analytics = create_test_pool_config("localhost", 5432);
+    analytics.config.workload = Workload::Analytics;
+
+    let replicas = LoadBalancer::new(
+        &None,
+        &[
+            create_test_pool_config("127.0.0.1", 5432),
+            analytics,
+            create_test_pool_config("localhost", 5432),
+        ],
+        LoadBalancingStrategy::RoundRobin,
+        ReadWriteSplit::IncludePrimary,
+    );
+    replicas.launch();
+
+    let pool_a = replicas.targets[0].pool.id();
+    let pool_b = replicas.targets[2].pool.id();
+
+    // The analytics replica doesn't take a turn.
+    let mut sequence = vec![];
+    for _ in 0..4 {
+        let conn = replicas.get(&Request::default()).await.unwrap();
+        sequence.push(conn.pool.id());
+    }
+    assert_eq!(sequence, vec![pool_a, pool_b, pool_a, pool_b]);
+
+    replicas.shutdown();
+}
+
+#[tokio::test]
+async fn test_analytics_workload_weighted_round_robin() {
+    let mut analytics = create_test_pool_config_weighted("localhost", 5432, 5);
+    analytics.config.workload = Workload::Analytics;
+
+    let replicas = LoadBalancer::new(
+        &None,
+        &[
+            create_test_pool_config_weighted("127.0.0.1", 5432, 2),
+            analytics,
+            create_test_pool_config_weighted("localhost", 5432, 1),
+        ],
+        LoadBalancingStrategy::WeightedRoundRobin,
+        ReadWriteSplit::IncludePrimary,
+    );
+    replicas.launch();
+
+    let pool_a = replicas.targets[0].pool.id();
+    let analytics_id = replicas.targets[1].pool.id();
+    let pool_b = replicas.targets[2].pool.id();
+
+    // Weights are only compared within the same workload.
+    let mut sequence = vec![];
+    for _ in 0..6 {
+        let conn = replicas.get(&Request::default()).await.unwrap();
+        sequence.push(conn.pool.id());
+    }
+    assert_eq!(
+        sequence,
+        vec![pool_a, pool_b, pool_a, pool_a, pool_b, pool_a]
+    );
+
+    let expensive = Request {
+        analytics: true,
+        ..Request::default()
+    };
+    for _ in 0..5 {
+        let conn = replicas.get(&expensive).await.unwrap();
+        assert_eq!(conn.pool.id(), analytics_id);
+    }
+
+    replicas.shutdown();
+}
@@ -251,7 +251,7 @@ impl LsnMonitor {
     }
 
     async fn get_connection(&self) -> Result<LsnConnection, Error> {
-        match self.pool.get(&Request::default()).await {
+        match self.pool.get(&Request::maintenance()).await {
             Ok(conn) => Ok(LsnConnection::Guard(conn)),
             Err(Error::Offline) => Err(Error::Offline),
             Err(Error::CheckoutTimeout) => Ok(LsnConnection::Conn(Box::new(
@@ -12,6 +12,7 @@ pub struct Counts {
     pub dropped_count: usize,
     pub error_count: usize,
     pub queue_length: usize,
+    pub mismatch_count: usize,
 }
 
 impl Sub for Counts {
@@ -24,6 +25,7 @@ impl Sub for Counts {
             dropped_count: self.dropped_count.saturating_sub(rhs.dropped_count),
             error_count: self.error_count.saturating_sub(rhs.error_count),
             queue_length: self.queue_length.saturating_sub(rhs.queue_length),
+            mismatch_count: self.mismatch_count.saturating_sub(rhs.mismatch_count),
         }
     }
 }
@@ -38,6 +40,7 @@ impl Div<usize> for Counts {
             dropped_count: self.dropped_count.saturating_div(rhs),
             error_count: self.error_count.saturating_div(rhs),
             queue_length: self.queue_length.saturating_div(rhs),
+            mismatch_count: self.mismatch_count.saturating_div(rhs),
         }
     }
 }
@@ -52,6 +55,7 @@ impl Add for Counts {
             dropped_count: self.dropped_count + rhs.dropped_count,
             error_count: self.error_count + rhs.error_count,
             queue_length: self.queue_length + rhs.queue_length,
+            mismatch_count: self.mismatch_count + rhs.mismatch_count,
         }
     }
 }
@@ -93,6 +97,7 @@ mod tests {
             dropped_count: 3,
             error_count: 2,
             queue_length: 7,
+            mismatch_count: 0,
         };
 
         let counts2 = Counts {
@@ -101,6 +106,7 @@ mod tests {
             dropped_count: 1,
             error_count: 1,
             queue_length: 3,
+            mismatch_count: 0,
         };
 
         // Test Add
@@ -133,6 +139,7 @@ mod tests {
             dropped_count: 3,
             error_count: 2,
             queue_length: 3,
+            mismatch_count: 0,
         };
 
         let counts2 = Counts {
@@ -141,6 +148,7 @@ mod tests {
             dropped_count: 1,
             error_count: 1,
             queue_length: 5,
+            mismatch_count: 0,
         };
 
         // Test that subtraction doesn't go negative (saturating_sub)
@@ -6,6 +6,7 @@ pub mod cluster;
 pub mod cluster_launch;
 pub mod comms;
 pub mod config;
+pub mod connect_limit;
 pub mod connection;
 pub mod dns_cache;
 pub mod ee;
@@ -20,6 +21,7 @@ pub mod monitor;
 pub mod oids;
 pub mod password;
 pub mod pool_impl;
+pub mod rate_limit;
 pub mod request;
 pub mod shard;
 pub mod state;
@@ -42,6 +44,7 @@ pub use monitor::Monitor;
 pub use oids::Oids;
 pub use password::Password;
 pub use pool_impl::Pool;
+pub use rate_limit::RateLimiter;
 pub use request::Request;
 pub use shard::Shard;
 pub use state::State;
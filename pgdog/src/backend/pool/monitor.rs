@@ -2,10 +2,11 @@
 //!
 //! # Summary
 //!
-//! The monitor has four (4) loops running in different Tokio tasks:
+//! The monitor has five (5) loops running in different Tokio tasks:
 //!
 //! * the maintenance loop which runs ~3 times per second,
 //! * the healthcheck loop which runs every `idle_healthcheck_interval`,
+//! * the idle ping loop which runs if `idle_ping_interval` is set,
 //! * the new connection loop which runs every time a client asks
 //!   for a new connection to be created,
 //! * the token refresh loop which runs for pools backed by an external
@@ -17,16 +18,19 @@
 //! have been idle for longer than `idle_timeout` and are older than `max_age`.
 //!
 //! Additionally, the maintenance loop checks the number of clients waiting and
-//! triggers the new connection loop to run if there are. This mechanism makes sure
-//! that only one connection is created at a time (due to [`tokio::sync::Notify`] storing
-//! only a single permit) and prevents the thundering herd problem when many clients request
-//! a connection from the pool.
+//! triggers the new connection loop to run if there are, or if the pool has fewer than
+//! `min_pool_size` connections, e.g. because some were closed for exceeding `max_age`.
+//! This mechanism makes sure that only one connection is created at a time (due to
+//! [`tokio::sync::Notify`] storing only a single permit) and prevents the thundering herd
+//! problem when many clients request a connection from the pool.
 //!
 //! ## New connection loop
 //!
 //! The new connection loop runs every time a client or the maintenance loop request
 //! a new connection to be created. This happens when there are no more idle connections
-//! in the pool & there are clients waiting for a connection.
+//! in the pool & there are clients waiting for a connection, or when the pool is below
+//! `min_pool_size`. In the latter case, the loop keeps running until the pool has
+//! `min_pool_size` connections again.
 //!
 //! Only one iteration of this loop can run at a time, so the pool will create one connection
 //! at a time and re-evaluate the need for more when it's done creating the connection. Since opening
@@ -34,6 +38,13 @@
 //! connections back to the idle pool in that amount of time, and new connections are no longer needed even
 //! if clients requested ones to be created ~100ms ago.
 //!
+//! ## Idle ping loop
+//!
+//! Sends an empty query to idle connections that haven't been used or pinged for
+//! `idle_ping_interval`, so NAT gateways and load balancers between PgDog and the database
+//! don't drop them. Unlike health checks, pings don't change the pool's health and
+//! don't count as using the connection, so `idle_timeout` still applies.
+//!
 //! ## Token refresh loop
 //!
 //! Spawned once per pool for addresses that use an external identity provider.
@@ -45,6 +56,7 @@
 
 use std::time::Duration;
 
+use super::connect_limit::connect_permit;
 use super::{Error, Guard, Healtcheck, Oids, Pool, Request};
 use crate::backend::auth::{azure_workload_identity, rds_iam, vault};
 use crate::backend::pool::inner::ShouldCreate;
@@ -115,6 +127,13 @@ impl Monitor {
             });
         }
 
+        if let Some(interval) = self.pool.config().idle_ping_interval() {
+            let pool = self.pool.clone();
+            tasks::spawn("pool idle pings", async move {
+                Self::idle_pings(pool, interval).await
+            });
+        }
+
         // Token refresh loop — one task per pool, tied to pool lifetime.
         // Only spawned for pools that use an external identity provider.
         if self.pool.addr().server_auth.is_external_identity() {
@@ -163,6 +182,10 @@ impl Monitor {
                         };
                         if !ok {
                             self.pool.inner().health.toggle(false);
+                        } else if self.pool.lock().should_create().yes() {
+                            // Keep going until the pool has `min` connections
+                            // instead of waiting for the next maintenance run.
+                            comms.request.notify_one();
                         }
                     }
                 }
@@ -288,6 +311,85 @@ impl Monitor {
         debug!("health checks stopped [{}]", pool.addr());
     }
 
+    /// The idle ping loop.
+    ///
+    /// Checks twice per interval, so connections aren't left
+    /// without traffic for much longer than `interval`.
+    async fn idle_pings(pool: Pool, ping_interval: Duration) {
+        let mut tick = interval(ping_interval / 2);
+        let comms = pool.comms();
+
+        debug!("idle pings running [{}]", pool.addr());
+
+        loop {
+            select! {
+                _ = tick.tick() => {
+                    {
+                        let guard = pool.lock();
+
+                        if !guard.online {
+                            break;
+                        }
+
+                        if guard.paused {
+                            continue;
+                        }
+                    }
+
+                    Self::ping_idle(&pool, ping_interval).await;
+                }
+
+                _ = comms.shutdown.cancelled() => break,
+            }
+        }
+
+        debug!("idle pings stopped [{}]", pool.addr());
+    }
+
+    /// Ping all connections that have been idle for at least `interval`.
+    async fn ping_idle(pool: &Pool, interval: Duration) {
+        let ping_timeout = pool.config().healthcheck_timeout();
+        let request = Request::maintenance();
+        let now = Instant::now();
+
+        // Each connection is pinged once, since pinging it
+        // resets how long it's been idle for.
+        loop {
+            let conn = pool.lock().take_ping(&request, interval, now);
+            let Some(mut conn) = conn else {
+                break;
+            };
+
+            let error = match timeout(ping_timeout, conn.ping()).await {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err.to_string()),
+                Err(_) => Some("timed out".into()),
+            };
+
+            // Close the connection instead of returning it to the pool.
+            // A dropped idle connection doesn't say anything about the database,
+            // so the pool's health is left alone.
+            if let Some(error) = error {
+                warn!("idle ping error: {} [{}]", error, pool.addr());
+
+                if let Err(err) = pool.lock().forget(&conn) {
+                    error!("idle ping checkin error: {} [{}]", err, pool.addr());
+                }
+
+                conn.disconnect_reason(DisconnectReason::Unhealthy);
+                drop(conn);
+
+                // Replace it if the pool is below the minimum.
+                pool.comms().request.notify_one();
+                continue;
+            }
+
+            if let Err(err) = pool.checkin(conn) {
+                error!("idle ping checkin error: {} [{}]", err, pool.addr());
+            }
+        }
+    }
+
     /// Perform maintenance on the pool periodically.
     async fn maintenance(pool: Pool) {
         let mut tick = interval(MAINTENANCE);
@@ -307,19 +409,18 @@ impl Monitor {
                         break;
                     }
 
-                    // If a client is waiting already,
-                    // create it a connection.
-                    if guard.should_create().yes() {
-                        comms.request.notify_one();
+                    // Don't close connections while paused.
+                    if !guard.paused {
+                        guard.close_idle(now);
+                        guard.close_old(now);
                     }
 
-                    // Don't perform any additional maintenance tasks.
-                    if guard.paused {
-                        continue;
+                    // If a client is waiting already, create it a connection.
+                    // Connections closed above are replaced right away if that
+                    // puts the pool below the minimum.
+                    if guard.should_create().yes() {
+                        comms.request.notify_one();
                     }
-
-                    guard.close_idle(now);
-                    guard.close_old(now);
                 }
 
                 _ = comms.shutdown.cancelled() => break,
@@ -341,6 +442,14 @@ impl Monitor {
                 }
                 Ok(true)
             }
+            Err(Error::OutOfConnections) => {
+                // Don't make clients wait for the checkout timeout if
+                // they can be sent to another database right away.
+                self.pool
+                    .lock()
+                    .close_fail_fast_waiters(Error::OutOfConnections);
+                Ok(false)
+            }
             _ => Ok(false),
         }
     }
@@ -348,7 +457,7 @@ impl Monitor {
     #[allow(dead_code)]
     async fn fetch_oids(pool: &Pool) -> Result<(), Error> {
         if pool.lock().oids.is_none() {
-            let oids = Oids::load(&mut pool.get(&Request::default()).await?)
+            let oids = Oids::load(&mut pool.get(&Request::maintenance()).await?)
                 .await
                 .ok();
             if let Some(oids) = oids {
@@ -380,7 +489,7 @@ impl Monitor {
             if !guard.online {
                 return Ok(false);
             }
-            guard.take(&Request::default())?
+            guard.take(&Request::maintenance())?
         };
 
         let healthcheck_timeout = pool.config().healthcheck_timeout;
@@ -446,6 +555,7 @@ impl Monitor {
         let connect_timeout = pool.config().connect_timeout;
         let connect_attempts = pool.config().connect_attempts;
         let connect_attempt_delay = pool.config().connect_attempt_delay;
+        let max_concurrent_connects = pool.config().max_concurrent_connects;
         let options = pool.server_options();
 
         let mut error = Error::ServerError;
@@ -455,12 +565,15 @@ impl Monitor {
         let max_age_jitter = pool.config().max_age_jitter;
 
         for attempt in 0..connect_attempts {
-            match timeout(
+            let permit = connect_permit(max_concurrent_connects).await;
+            let result = timeout(
                 connect_timeout,
                 Server::connect(pool.addr(), options.clone(), reason),
             )
-            .await
-            {
+            .await;
+            drop(permit);
+
+            match result {
                 Ok(Ok(mut conn)) => {
                     let elapsed = now.elapsed();
                     {
@@ -479,6 +592,12 @@ impl Monitor {
                     if err.is_auth() {
                         pool.lock().stats.counts.auth_attempts += pool.addr().passwords.len();
                     }
+                    // Retrying right away won't help, the server needs to free up connections first.
+                    if err.is_out_of_connections() {
+                        pool.lock().stats.counts.out_of_connections += 1;
+                        error!("backend out of connections: {} [{}]", err, pool.addr());
+                        return Err(Error::OutOfConnections);
+                    }
                     error!(
                         "{}error connecting to server: {} [{}]",
                         if attempt > 0 {
@@ -593,6 +712,35 @@ mod test {
         assert!(!pool.inner().health.healthy());
     }
 
+    #[tokio::test]
+    async fn test_custom_healthcheck_query() {
+        crate::logger();
+
+        let pool = Pool::new(&PoolConfig {
+            address: Address {
+                host: "127.0.0.1".into(),
+                port: 5432,
+                database_name: "pgdog".into(),
+                user: "pgdog".into(),
+                passwords: vec!["pgdog".into()],
+                // Looks like pg_is_in_recovery() on a replica.
+                healthcheck_query: Some("SELECT true".into()),
+                ..Default::default()
+            },
+            config: Config::default(),
+        });
+        pool.launch();
+
+        // Replica unless proven otherwise.
+        assert!(pool.lsn_stats().replica);
+
+        // The role comes from pg_is_in_recovery(),
+        // not the healthcheck query.
+        let ok = Monitor::healthcheck(&pool).await.unwrap();
+        assert!(ok);
+        assert!(!pool.lsn_stats().replica);
+    }
+
     #[tokio::test]
     async fn test_replenish_only_when_pool_is_online() {
         crate::logger();
@@ -630,4 +778,122 @@ mod test {
         assert_eq!(pool.lock().total(), initial_total);
         assert!(!pool.lock().online);
     }
+
+    #[tokio::test]
+    async fn test_min_pool_size_backfill() {
+        crate::logger();
+
+        let config = Config {
+            inner: pgdog_stats::Config {
+                max: 5,
+                min: 2,
+                ..Config::default().inner
+            },
+        };
+
+        let pool = Pool::new(&PoolConfig {
+            address: Address {
+                host: "127.0.0.1".into(),
+                port: 5432,
+                database_name: "pgdog".into(),
+                user: "pgdog".into(),
+                passwords: vec!["pgdog".into()],
+                ..Default::default()
+            },
+            config,
+        });
+        pool.launch();
+
+        let wait_for_min = |pool: Pool| async move {
+            timeout(Duration::from_secs(1), async {
+                while pool.lock().idle() < 2 {
+                    sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+        };
+
+        // Pool fills up to the minimum without any clients.
+        wait_for_min(pool.clone()).await.unwrap();
+
+        // Connections are closed, e.g. by the server.
+        pool.lock().dump_idle();
+        assert_eq!(pool.lock().total(), 0);
+
+        // Monitor replaces them within a couple maintenance runs.
+        wait_for_min(pool.clone()).await.unwrap();
+        assert_eq!(pool.lock().idle(), 2);
+        assert_eq!(pool.lock().total(), 2);
+
+        pool.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_connects() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::net::TcpListener;
+
+        crate::logger();
+
+        // Server that takes a while to answer and then hangs up.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+
+        {
+            let active = active.clone();
+            let max_active = max_active.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    let active = active.clone();
+                    let max_active = max_active.clone();
+                    tokio::spawn(async move {
+                        let current = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_active.fetch_max(current, Ordering::SeqCst);
+                        sleep(Duration::from_millis(50)).await;
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        drop(stream);
+                    });
+                }
+            });
+        }
+
+        let config = Config {
+            inner: pgdog_stats::Config {
+                max_concurrent_connects: 2,
+                connect_attempts: 1,
+                ..Config::default().inner
+            },
+        };
+
+        let pool = Pool::new(&PoolConfig {
+            address: Address {
+                host: "127.0.0.1".into(),
+                port,
+                database_name: "pgdog".into(),
+                user: "pgdog".into(),
+                passwords: vec!["pgdog".into()],
+                ..Default::default()
+            },
+            config,
+        });
+
+        let handles = (0..10)
+            .map(|_| {
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    Monitor::create_connection(&pool, ConnectReason::Other).await
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_err());
+        }
+
+        assert_eq!(max_active.load(Ordering::SeqCst), 2);
+    }
 }
@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use futures::future::try_join_all;
 use once_cell::sync::{Lazy, OnceCell};
@@ -43,7 +43,7 @@ pub(crate) struct InnerSync {
     pub(super) inner: Mutex<Inner>,
     pub(super) id: u64,
     pub(super) config: Config,
-    pub(super) health: TargetHealth,
+    pub(crate) health: TargetHealth,
     pub(super) params: OnceCell<Parameters>,
     pub(super) lsn_stats: RwLock<LsnStats>,
     pub(super) lsn_role_change: Notify,
@@ -136,7 +136,11 @@ impl Pool {
                     return Err(Error::Offline);
                 }
 
-                let conn = guard.take(request)?;
+                let conn = if guard.can_take() {
+                    guard.take(request)?
+                } else {
+                    None
+                };
 
                 if conn.is_some() {
                     guard.stats.counts.wait_time += elapsed;
@@ -208,6 +212,13 @@ impl Pool {
         healthcheck_interval: Duration,
         now: Instant,
     ) -> Result<Guard, Error> {
+        // Connections idle for too long could have been closed
+        // by the server or the network, so check them regardless of the interval.
+        let healthcheck_interval = match self.inner.config.test_on_checkout_after_idle {
+            Some(idle) if conn.idle_for(now) >= idle => Duration::ZERO,
+            _ => healthcheck_interval,
+        };
+
         let mut healthcheck = Healtcheck::conditional(
             &mut conn,
             self,
@@ -455,6 +466,13 @@ impl Pool {
             });
         }
 
+        if let Some(isolation) = config.default_transaction_isolation {
+            params.push(Parameter {
+                name: "default_transaction_isolation".into(),
+                value: isolation.to_string().into(),
+            });
+        }
+
         ServerOptions {
             params,
             pool_id: self.id(),
@@ -476,12 +494,53 @@ impl Pool {
         *self.inner().lsn_stats.read()
     }
 
+    /// Record pg_is_in_recovery() returned by a healthcheck.
+    pub(super) fn set_replica(&self, replica: bool) {
+        let mut guard = self.inner().lsn_stats.write();
+        // Notify that the role changed and the shard monitor
+        // should immediately resynchronize.
+        if guard.replica != replica {
+            self.inner().lsn_role_change.notify_one();
+        }
+        guard.replica = replica;
+        guard.fetched = SystemTime::now();
+    }
+
+    /// Change the pool size without closing connections
+    /// that are in use. Shrinking the pool closes idle connections
+    /// only; growing it creates new connections as needed.
+    pub fn resize(&self, min: usize, max: usize) {
+        let (removed, create) = {
+            let mut guard = self.lock();
+            let removed = guard.resize(min, max);
+            (removed, guard.should_create().yes())
+        };
+
+        if removed > 0 {
+            debug!(
+                "closed {} idle connections after resize [{}]",
+                removed,
+                self.addr()
+            );
+        }
+
+        if create {
+            self.comms().request.notify_one();
+        }
+    }
+
     /// Update pool configuration used in internals.
     #[cfg(test)]
     pub(crate) fn update_config(&self, config: Config) {
         self.lock().config = config;
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_lsn_stats(&self, lsn_stats: LsnStats, replica_lag: ReplicaLag) {
+        *self.inner().lsn_stats.write() = lsn_stats;
+        self.lock().replica_lag = replica_lag;
+    }
+
     /// Fetch OIDs for user-defined data types.
     pub fn oids(&self) -> Option<Oids> {
         self.lock().oids
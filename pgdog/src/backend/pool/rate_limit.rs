@@ -0,0 +1,150 @@
+//! Query and transaction rate limits, shared by all clients
+//! of the same cluster (user and database).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lock-free token bucket.
+///
+/// Implemented as a generic cell rate algorithm: instead of counting tokens,
+/// we track the time at which the bucket will be full again. The bucket holds
+/// up to one second worth of tokens.
+#[derive(Debug)]
+pub struct TokenBucket {
+    /// Reference point for `tat`.
+    started_at: Instant,
+    /// Time it takes to refill one token, in nanoseconds.
+    interval: u64,
+    /// How far ahead of now `tat` can be, in nanoseconds.
+    tolerance: u64,
+    /// Theoretical arrival time of the next request,
+    /// in nanoseconds since `started_at`.
+    tat: AtomicU64,
+}
+
+impl TokenBucket {
+    /// Create token bucket allowing `rate` requests per second.
+    pub fn new(rate: u32) -> Self {
+        let rate = rate.max(1) as u64;
+        let interval = Duration::from_secs(1).as_nanos() as u64 / rate;
+
+        Self {
+            started_at: Instant::now(),
+            interval,
+            tolerance: interval * (rate - 1),
+            tat: AtomicU64::new(0),
+        }
+    }
+
+    /// Take a token. If none are available, return
+    /// how long until the next one is.
+    pub fn acquire(&self) -> Result<(), Duration> {
+        self.acquire_at(Instant::now())
+    }
+
+    fn acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let now = now.saturating_duration_since(self.started_at).as_nanos() as u64;
+        let mut tat = self.tat.load(Ordering::Acquire);
+
+        loop {
+            let next = tat.max(now);
+            let ahead = next - now;
+
+            if ahead > self.tolerance {
+                return Err(Duration::from_nanos(ahead - self.tolerance));
+            }
+
+            match self.tat.compare_exchange_weak(
+                tat,
+                next + self.interval,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(actual) => tat = actual,
+            }
+        }
+    }
+}
+
+/// Rate limits for a cluster.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    queries: Option<TokenBucket>,
+    transactions: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    /// Create rate limiter. Limits that are not set or are zero are disabled.
+    pub fn new(queries: Option<u32>, transactions: Option<u32>) -> Self {
+        Self {
+            queries: queries.filter(|rate| *rate > 0).map(TokenBucket::new),
+            transactions: transactions.filter(|rate| *rate > 0).map(TokenBucket::new),
+        }
+    }
+
+    /// At least one limit is configured.
+    pub fn enabled(&self) -> bool {
+        self.queries.is_some() || self.transactions.is_some()
+    }
+
+    /// Account for one query. If the limit is exceeded,
+    /// return how long to wait before trying again.
+    pub fn acquire_query(&self) -> Result<(), Duration> {
+        match self.queries {
+            Some(ref queries) => queries.acquire(),
+            None => Ok(()),
+        }
+    }
+
+    /// Account for one transaction. If the limit is exceeded,
+    /// return how long to wait before trying again.
+    pub fn acquire_transaction(&self) -> Result<(), Duration> {
+        match self.transactions {
+            Some(ref transactions) => transactions.acquire(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_burst_and_refill() {
+        let bucket = TokenBucket::new(10);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert!(bucket.acquire_at(now).is_ok());
+        }
+
+        let wait = bucket.acquire_at(now).unwrap_err();
+        assert!(wait <= Duration::from_millis(100));
+
+        assert!(bucket.acquire_at(now + wait).is_ok());
+        assert!(bucket.acquire_at(now + wait).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_disabled() {
+        let limiter = RateLimiter::new(None, Some(0));
+        assert!(!limiter.enabled());
+
+        for _ in 0..1_000 {
+            assert!(limiter.acquire_query().is_ok());
+            assert!(limiter.acquire_transaction().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_transactions() {
+        let limiter = RateLimiter::new(None, Some(1));
+        assert!(limiter.enabled());
+
+        assert!(limiter.acquire_transaction().is_ok());
+        assert!(limiter.acquire_query().is_ok());
+        assert!(limiter.acquire_transaction().is_err());
+    }
+}
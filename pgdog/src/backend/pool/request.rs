@@ -8,6 +8,13 @@ pub struct Request {
     pub id: FrontendPid,
     pub created_at: Instant,
     pub read: bool,
+    /// Allowed to use the reserve pool.
+    pub reserved: bool,
+    /// Expensive read, prefers analytics replicas.
+    pub analytics: bool,
+    /// Another database can serve the request, so it shouldn't
+    /// wait for a pool that can't create connections.
+    pub fail_fast: bool,
 }
 
 impl Request {
@@ -16,6 +23,9 @@ impl Request {
             id,
             created_at: Instant::now(),
             read,
+            reserved: false,
+            analytics: false,
+            fail_fast: false,
         }
     }
 
@@ -24,6 +34,18 @@ impl Request {
             id,
             created_at: Instant::now(),
             read: false,
+            reserved: false,
+            analytics: false,
+            fail_fast: false,
+        }
+    }
+
+    /// Request made by an internal maintenance task or an admin command.
+    /// It can get a connection from the reserve pool if the pool is saturated.
+    pub fn maintenance() -> Self {
+        Self {
+            reserved: true,
+            ..Self::default()
         }
     }
 }
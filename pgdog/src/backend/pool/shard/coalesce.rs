@@ -0,0 +1,215 @@
+//! Coalesce identical reads running concurrently on the same shard.
+//!
+//! The first client to run a read executes it and records the messages
+//! it received. Clients that send the same read while it's still running
+//! wait for it to finish and receive the same messages, instead of
+//! executing it again. Results larger than [`MAX_RESPONSE_SIZE`] aren't shared,
+//! so large reads don't have to be buffered in memory.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+use crate::net::Message;
+
+/// Messages returned by a read, shared by all clients that requested it.
+pub type Response = Arc<Vec<Message>>;
+
+/// Largest result, in bytes, shared with other clients.
+pub const MAX_RESPONSE_SIZE: usize = 8 * 1024 * 1024;
+
+type Inflight = Arc<Mutex<HashMap<Vec<u8>, watch::Receiver<Option<Response>>>>>;
+
+/// Reads currently executing on a shard.
+#[derive(Debug, Default)]
+pub struct Coalesce {
+    inflight: Inflight,
+}
+
+/// Result of joining an in-flight read.
+#[derive(Debug)]
+pub enum Coalesced {
+    /// No identical read is running, so this client needs to execute it.
+    Leader(Leader),
+    /// Identical read is running already, wait for its result.
+    Follower(Follower),
+}
+
+impl Coalesce {
+    /// Join an identical read, if one is running already, or start a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: Uniquely identifies the read, e.g. its query, parameters
+    ///   and client settings.
+    ///
+    pub fn join(&self, key: Vec<u8>) -> Coalesced {
+        let mut inflight = self.inflight.lock();
+
+        if let Some(receiver) = inflight.get(&key) {
+            return Coalesced::Follower(Follower {
+                receiver: receiver.clone(),
+            });
+        }
+
+        let (sender, receiver) = watch::channel(None);
+        inflight.insert(key.clone(), receiver);
+
+        Coalesced::Leader(Leader {
+            key,
+            sender,
+            messages: vec![],
+            size: 0,
+            inflight: self.inflight.clone(),
+        })
+    }
+
+    /// Number of reads currently executing.
+    pub fn len(&self) -> usize {
+        self.inflight.lock().len()
+    }
+
+    /// No reads are executing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Client executing the read on behalf of everyone else.
+///
+/// If it's dropped before the read finished, waiting clients
+/// will execute it themselves.
+#[derive(Debug)]
+pub struct Leader {
+    key: Vec<u8>,
+    sender: watch::Sender<Option<Response>>,
+    messages: Vec<Message>,
+    size: usize,
+    inflight: Inflight,
+}
+
+impl Leader {
+    /// Record a message sent to the client.
+    ///
+    /// Returns false if the result is too large to share. The leader should be
+    /// dropped, so waiting clients execute the read themselves.
+    pub fn push(&mut self, message: &Message) -> bool {
+        self.size += message.len();
+        if self.size > MAX_RESPONSE_SIZE {
+            self.messages.clear();
+            return false;
+        }
+
+        self.messages.push(message.clone());
+        true
+    }
+
+    /// Read finished, return the result to everyone waiting for it.
+    pub fn finish(mut self) {
+        let messages = std::mem::take(&mut self.messages);
+        let _ = self.sender.send(Some(Arc::new(messages)));
+    }
+}
+
+impl Drop for Leader {
+    fn drop(&mut self) {
+        self.inflight.lock().remove(&self.key);
+    }
+}
+
+/// Client waiting for an identical read to finish.
+#[derive(Debug)]
+pub struct Follower {
+    receiver: watch::Receiver<Option<Response>>,
+}
+
+impl Follower {
+    /// Wait for the read to finish. Returns `None` if the client
+    /// executing it didn't get a result we can share.
+    pub async fn wait(mut self) -> Option<Response> {
+        self.receiver
+            .wait_for(|response| response.is_some())
+            .await
+            .ok()
+            .and_then(|response| response.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::net::{CommandComplete, DataRow, Protocol, ReadyForQuery};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_coalesce_followers_get_result() {
+        let coalesce = Coalesce::default();
+
+        let Coalesced::Leader(mut leader) = coalesce.join(b"SELECT 1".to_vec()) else {
+            panic!("first read should execute");
+        };
+        let followers = (0..5)
+            .map(|_| match coalesce.join(b"SELECT 1".to_vec()) {
+                Coalesced::Follower(follower) => tokio::spawn(follower.wait()),
+                Coalesced::Leader(_) => panic!("identical read should wait"),
+            })
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            coalesce.join(b"SELECT 2".to_vec()),
+            Coalesced::Leader(_)
+        ));
+        assert_eq!(coalesce.len(), 1);
+
+        leader.push(&CommandComplete::new("SELECT 1").message().unwrap());
+        leader.push(&ReadyForQuery::idle().message().unwrap());
+        leader.finish();
+        assert!(coalesce.is_empty());
+
+        for follower in followers {
+            let response = follower.await.unwrap().unwrap();
+            assert_eq!(response.len(), 2);
+            assert_eq!(response[1].code(), 'Z');
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_leader_dropped() {
+        let coalesce = Coalesce::default();
+
+        let leader = coalesce.join(b"SELECT 1".to_vec());
+        let Coalesced::Follower(follower) = coalesce.join(b"SELECT 1".to_vec()) else {
+            panic!("identical read should wait");
+        };
+
+        drop(leader);
+
+        assert!(follower.wait().await.is_none());
+        assert!(matches!(
+            coalesce.join(b"SELECT 1".to_vec()),
+            Coalesced::Leader(_)
+        ));
+    }
+
+    #[test]
+    fn test_coalesce_large_result() {
+        let coalesce = Coalesce::default();
+
+        let Coalesced::Leader(mut leader) = coalesce.join(b"SELECT 1".to_vec()) else {
+            panic!("first read should execute");
+        };
+
+        let row = DataRow::from_columns(vec!["a".repeat(1024 * 1024)])
+            .message()
+            .unwrap();
+        let mut pushed = 0;
+        while leader.push(&row) {
+            pushed += 1;
+        }
+
+        assert!(pushed < MAX_RESPONSE_SIZE / row.len());
+        assert!(leader.messages.is_empty());
+    }
+}
@@ -18,11 +18,13 @@ use crate::config::{LoadBalancingStrategy, ReadWriteSplit, Role};
 use crate::net::Parameters;
 use crate::net::messages::FrontendPid;
 
-use super::{Error, Guard, LoadBalancer, Pool, PoolConfig, Request};
+use super::{Address, Error, Guard, LoadBalancer, Pool, PoolConfig, Request};
 
+pub mod coalesce;
 pub mod monitor;
 pub mod role_detector;
 
+use coalesce::Coalesce;
 use monitor::*;
 use role_detector::*;
 
@@ -80,6 +82,11 @@ impl Shard {
         self.lb.get(request).await
     }
 
+    /// Get connection to the replica with the given name, bypassing load balancing.
+    pub async fn replica_named(&self, name: &str, request: &Request) -> Result<Guard, Error> {
+        self.lb.get_named(name, request).await
+    }
+
     /// Get connection to primary if configured, otherwise replica.
     pub async fn primary_or_replica(&self, request: &Request) -> Result<Guard, Error> {
         match self.primary(request).await {
@@ -126,7 +133,7 @@ impl Shard {
             return Ok(false);
         }
 
-        let mut server = self.primary_or_replica(&Request::default()).await?;
+        let mut server = self.primary_or_replica(&Request::maintenance()).await?;
         let schema = Schema::load(&mut server).await?;
         info!(
             "loaded schema for {} tables on shard {} [{}]",
@@ -146,6 +153,13 @@ impl Shard {
         self.schema_waiter.notify_one();
     }
 
+    /// Use this schema instead of loading it from the database.
+    #[cfg(test)]
+    pub(crate) fn set_schema(&self, schema: Schema) {
+        let _ = self.schema.set(schema);
+        self.schema_waiter.notify_one();
+    }
+
     /// Wait for the shard to load the schema.
     /// If the schema is loaded already, this returns immediately.
     pub(super) async fn wait_schema_loaded(&self) {
@@ -157,6 +171,11 @@ impl Shard {
         self.schema_waiter.notify_one();
     }
 
+    /// Identical reads currently executing on this shard.
+    pub fn coalesce(&self) -> &Coalesce {
+        &self.coalesce
+    }
+
     /// Check that the shard LB targets are all launched.
     pub fn online(&self) -> bool {
         self.lb.online()
@@ -182,6 +201,16 @@ impl Shard {
         self.lb.has_replicas()
     }
 
+    /// Returns true if the shard has replicas and all of them are banned.
+    pub fn replicas_banned(&self) -> bool {
+        self.lb.replicas_banned()
+    }
+
+    /// Address of the primary database, if any.
+    pub fn primary_addr(&self) -> Option<&Address> {
+        self.lb.primary().map(|pool| pool.addr())
+    }
+
     /// Request a query to be cancelled on any of the servers in the connection pools
     /// in this shard.
     ///
@@ -313,6 +342,7 @@ pub struct ShardInner {
     schema: Arc<OnceCell<Schema>>,
     schema_waiter: Notify,
     pub_sub_enabled: bool,
+    coalesce: Coalesce,
 }
 
 impl ShardInner {
@@ -343,6 +373,7 @@ impl ShardInner {
             schema: Arc::new(OnceCell::new()),
             schema_waiter: Notify::new(),
             pub_sub_enabled,
+            coalesce: Coalesce::default(),
         }
     }
 }
@@ -44,7 +44,7 @@ mod test {
 
     use crate::backend::databases::User;
     use crate::backend::pool::lsn_monitor::LsnStats;
-    use crate::backend::pool::{Address, Config, PoolConfig};
+    use crate::backend::pool::{Address, Config, Monitor, PoolConfig};
     use crate::backend::replication::publisher::Lsn;
     use crate::config::{LoadBalancingStrategy, ReadWriteSplit, Role};
     use pgdog_stats::LsnStats as StatsLsnStats;
@@ -173,6 +173,42 @@ mod test {
         assert!(!detector.changed());
     }
 
+    #[tokio::test]
+    async fn test_changed_by_healthcheck_query() {
+        let primary = create_test_pool_config("127.0.0.1", 5432, true);
+        let mut replica = create_test_pool_config("localhost", 5432, true);
+        replica.address.healthcheck_query = Some("SELECT 1".into());
+        let shard = create_test_shard(&Some(primary), &[replica]);
+
+        // Both databases are in recovery, so there is no primary yet.
+        let pools = shard.pools();
+        for i in 0..pools.len() {
+            set_lsn_stats(&shard, i, true, 100 + i as i64);
+        }
+
+        let mut detector = RoleDetector::new(&shard);
+        assert!(!detector.changed());
+
+        // The replica's healthcheck finds out it's been promoted.
+        let promoted = pools
+            .iter()
+            .find(|pool| pool.addr().host == "localhost")
+            .unwrap();
+        promoted.lock().online = true;
+        assert!(Monitor::healthcheck(promoted).await.unwrap());
+        assert!(!promoted.lsn_stats().replica);
+
+        assert!(detector.changed());
+        assert_eq!(
+            shard
+                .pools_with_roles()
+                .into_iter()
+                .find(|(role, _)| *role == Role::Primary)
+                .map(|(_, pool)| pool.addr().host.clone()),
+            Some("localhost".into())
+        );
+    }
+
     #[test]
     fn test_disabled_when_not_all_roles_auto() {
         let primary = Some(create_test_pool_config("127.0.0.1", 5432, false));
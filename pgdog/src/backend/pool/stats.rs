@@ -180,6 +180,7 @@ mod tests {
             reads: 25,
             writes: 50,
             auth_attempts: 30,
+            out_of_connections: 3,
         }
         .into();
 
@@ -207,6 +208,7 @@ mod tests {
             reads: 10,
             writes: 20,
             auth_attempts: 20,
+            out_of_connections: 2,
         }
         .into();
 
@@ -235,6 +237,7 @@ mod tests {
         assert_eq!(result.reads, 35);
         assert_eq!(result.writes, 70);
         assert_eq!(result.auth_attempts, 50);
+        assert_eq!(result.out_of_connections, 5);
     }
 
     #[test]
@@ -263,6 +266,7 @@ mod tests {
             reads: 25,
             writes: 50,
             auth_attempts: 50,
+            out_of_connections: 5,
         }
         .into();
 
@@ -290,6 +294,7 @@ mod tests {
             reads: 10,
             writes: 20,
             auth_attempts: 30,
+            out_of_connections: 3,
         }
         .into();
 
@@ -318,6 +323,7 @@ mod tests {
         assert_eq!(result.reads, 15);
         assert_eq!(result.writes, 30);
         assert_eq!(result.auth_attempts, 20);
+        assert_eq!(result.out_of_connections, 2);
     }
 
     #[test]
@@ -368,6 +374,7 @@ mod tests {
             reads: 10,
             writes: 20,
             auth_attempts: 10,
+            out_of_connections: 4,
         }
         .into();
 
@@ -396,6 +403,7 @@ mod tests {
         assert_eq!(result.reads, 5);
         assert_eq!(result.writes, 10);
         assert_eq!(result.auth_attempts, 5);
+        assert_eq!(result.out_of_connections, 2);
     }
 
     #[test]
@@ -439,6 +447,7 @@ mod tests {
             reads: 10,
             writes: 25,
             auth_attempts: 100,
+            out_of_connections: 1,
         }
         .into();
 
@@ -487,6 +496,7 @@ mod tests {
         assert_eq!(result.reads, 10);
         assert_eq!(result.writes, 25);
         assert_eq!(result.auth_attempts, 100);
+        assert_eq!(result.out_of_connections, 1);
     }
 
     #[test]
@@ -1,10 +1,11 @@
 //! Pool tests.
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{Duration, SystemTime};
 
-use pgdog_config::ServerAuth;
+use parking_lot::Mutex;
+use pgdog_config::{IsolationLevel, ServerAuth};
 use rand::Rng;
 use tokio::spawn;
 use tokio::task::yield_now;
@@ -13,6 +14,7 @@ use tokio_util::task::TaskTracker;
 
 use crate::backend::ConnectReason;
 use crate::backend::pool::token_cache::TokenCache;
+use crate::config::CheckoutFairness;
 use crate::net::ProtocolMessage;
 use crate::net::{Parse, Protocol, Query, Sync};
 use crate::state::State;
@@ -155,6 +157,198 @@ async fn test_concurrency_with_gas() {
     assert_eq!(pool.lock().total(), 10);
 }
 
+#[tokio::test]
+async fn test_resize_under_load() {
+    let pool = pool();
+    let tracker = TaskTracker::new();
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..500 {
+        let pool = pool.clone();
+        let peak = peak.clone();
+        tracker.spawn(async move {
+            let _conn = pool.get(&Request::default()).await.unwrap();
+            peak.fetch_max(pool.lock().total(), Ordering::Relaxed);
+            assert!(pool.lock().total() <= 5);
+            sleep(Duration::from_millis(5)).await;
+        });
+    }
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.lock().total(), 1);
+
+    pool.resize(1, 5);
+    assert_eq!(pool.lock().max(), 5);
+
+    tracker.close();
+    tracker.wait().await;
+
+    assert!(peak.load(Ordering::Relaxed) > 1);
+    assert_eq!(pool.lock().errors, 0);
+
+    // Shrinking closes idle connections only.
+    let conn = pool.get(&Request::default()).await.unwrap();
+    pool.resize(1, 1);
+    assert_eq!(pool.lock().total(), 1);
+    assert_eq!(pool.lock().checked_out(), 1);
+
+    drop(conn);
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.lock().total(), 1);
+    assert_eq!(pool.lock().idle(), 1);
+}
+
+#[tokio::test]
+async fn test_fifo_checkout_fairness() {
+    let pool = pool();
+    pool.update_config(Config {
+        inner: pgdog_stats::Config {
+            max: 1,
+            min: 1,
+            checkout_fairness: CheckoutFairness::Fifo,
+            ..Config::default().inner
+        },
+    });
+
+    let hold = pool.get(&Request::default()).await.unwrap();
+    let granted = Arc::new(Mutex::new(vec![]));
+    let tracker = TaskTracker::new();
+
+    for i in 0..50 {
+        let pool = pool.clone();
+        let granted = granted.clone();
+        tracker.spawn(async move {
+            let _conn = pool.get(&Request::default()).await.unwrap();
+            granted.lock().push(i);
+            sleep(Duration::from_millis(1)).await;
+        });
+        // Make sure requests are created in order.
+        sleep(Duration::from_millis(1)).await;
+    }
+
+    assert_eq!(pool.lock().waiting.len(), 50);
+
+    // Late request, can't skip the line even
+    // though a connection is about to be idle.
+    let late = {
+        let pool = pool.clone();
+        let granted = granted.clone();
+        spawn(async move {
+            let _conn = pool.get(&Request::default()).await.unwrap();
+            granted.lock().push(50);
+        })
+    };
+
+    drop(hold);
+    tracker.close();
+    tracker.wait().await;
+    late.await.unwrap();
+
+    let granted = granted.lock().clone();
+    assert_eq!(granted, (0..=50).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_reserve_pool() {
+    let pool = pool();
+    pool.update_config(Config {
+        inner: pgdog_stats::Config {
+            max: 1,
+            min: 1,
+            reserve_pool_size: 1,
+            checkout_timeout: Duration::from_millis(100),
+            ..Config::default().inner
+        },
+    });
+
+    // Saturate the pool.
+    let hold = pool.get(&Request::default()).await.unwrap();
+    let err = pool.get(&Request::default()).await.unwrap_err();
+    assert_eq!(err, Error::CheckoutTimeout);
+
+    // Maintenance requests still get a connection.
+    let reserved = pool.get(&Request::maintenance()).await.unwrap();
+    assert_eq!(pool.lock().total(), 2);
+
+    // The reserve is limited too.
+    let err = pool.get(&Request::maintenance()).await.unwrap_err();
+    assert_eq!(err, Error::CheckoutTimeout);
+
+    // Reserve connection isn't kept above the pool size.
+    drop(reserved);
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.lock().total(), 1);
+
+    drop(hold);
+    let _conn = pool.get(&Request::default()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_default_transaction_isolation() {
+    let pool = Pool::new(&PoolConfig {
+        address: Address {
+            host: "127.0.0.1".into(),
+            port: 5432,
+            database_name: "pgdog".into(),
+            user: "pgdog".into(),
+            passwords: vec!["pgdog".into()],
+            ..Default::default()
+        },
+        config: Config {
+            inner: pgdog_stats::Config {
+                max: 1,
+                min: 1,
+                default_transaction_isolation: Some(IsolationLevel::RepeatableRead),
+                ..Config::default().inner
+            },
+        },
+    });
+    pool.launch();
+
+    let mut conn = pool.get(&Request::default()).await.unwrap();
+
+    // Transactions use the configured level by default.
+    conn.execute("BEGIN").await.unwrap();
+    let level = conn
+        .fetch_all::<String>("SHOW transaction_isolation")
+        .await
+        .unwrap();
+    assert_eq!(level, vec!["repeatable read".to_string()]);
+    conn.execute("COMMIT").await.unwrap();
+
+    // Explicit level set by the client wins.
+    conn.execute("BEGIN ISOLATION LEVEL SERIALIZABLE")
+        .await
+        .unwrap();
+    let level = conn
+        .fetch_all::<String>("SHOW transaction_isolation")
+        .await
+        .unwrap();
+    assert_eq!(level, vec!["serializable".to_string()]);
+    conn.execute("COMMIT").await.unwrap();
+
+    conn.execute("SET default_transaction_isolation TO 'read committed'")
+        .await
+        .unwrap();
+    conn.execute("BEGIN").await.unwrap();
+    let level = conn
+        .fetch_all::<String>("SHOW transaction_isolation")
+        .await
+        .unwrap();
+    assert_eq!(level, vec!["read committed".to_string()]);
+    conn.execute("COMMIT").await.unwrap();
+
+    // Resetting the session brings back the configured default.
+    conn.execute("RESET default_transaction_isolation")
+        .await
+        .unwrap();
+    let level = conn
+        .fetch_all::<String>("SHOW default_transaction_isolation")
+        .await
+        .unwrap();
+    assert_eq!(level, vec!["repeatable read".to_string()]);
+}
+
 #[tokio::test]
 async fn test_offline() {
     let pool = pool();
@@ -568,6 +762,163 @@ async fn test_idle_healthcheck_loop_disabled_with_zero_interval() {
     assert!(pool.healthy());
 }
 
+#[tokio::test]
+async fn test_idle_ping_loop() {
+    crate::logger();
+
+    let config = Config {
+        inner: pgdog_stats::Config {
+            max: 1,
+            min: 1,
+            idle_healthcheck_interval: Duration::ZERO,
+            idle_ping_interval: Some(Duration::from_millis(50)),
+            ..Config::default().inner
+        },
+    };
+
+    let pool = Pool::new(&PoolConfig {
+        address: Address::new_test(),
+        config,
+    });
+    pool.launch();
+
+    let conn = pool.get(&Request::default()).await.unwrap();
+    let id = conn.id();
+    drop(conn);
+
+    let last_used = pool.lock().idle_conns()[0].stats().last_used();
+
+    sleep(Duration::from_millis(300)).await;
+
+    {
+        let guard = pool.lock();
+        let conn = &guard.idle_conns()[0];
+        let last_ping = conn
+            .stats()
+            .last_ping()
+            .expect("idle connection should be pinged");
+
+        // Pinged recently, but not counted as used.
+        assert!(last_ping.elapsed() < Duration::from_millis(150));
+        assert_eq!(conn.stats().last_used(), last_used);
+    }
+
+    // Same connection is still usable.
+    let mut conn = pool.get(&Request::default()).await.unwrap();
+    assert_eq!(conn.id(), id);
+    conn.execute("SELECT 1").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_idle_ping_failure_keeps_pool_healthy() {
+    crate::logger();
+
+    let config = Config {
+        inner: pgdog_stats::Config {
+            max: 2,
+            min: 1,
+            idle_healthcheck_interval: Duration::ZERO,
+            idle_ping_interval: Some(Duration::from_millis(50)),
+            ..Config::default().inner
+        },
+    };
+
+    let pool = Pool::new(&PoolConfig {
+        address: Address::new_test(),
+        config,
+    });
+    pool.launch();
+
+    let dead = pool.get(&Request::default()).await.unwrap();
+    let dead_id = dead.id();
+    let mut conn = pool.get(&Request::default()).await.unwrap();
+    drop(dead);
+
+    conn.execute(format!("SELECT pg_terminate_backend({})", dead_id.pid).as_str())
+        .await
+        .unwrap();
+    drop(conn);
+
+    sleep(Duration::from_millis(300)).await;
+
+    // The dead connection is closed without banning the pool.
+    assert!(pool.healthy());
+    assert_eq!(pool.state().errors, 0);
+    assert!(
+        pool.lock()
+            .idle_conns()
+            .iter()
+            .all(|conn| conn.id() != dead_id)
+    );
+
+    let mut conn = pool.get(&Request::default()).await.unwrap();
+    conn.execute("SELECT 1").await.unwrap();
+}
+
+fn pool_test_on_checkout(max: usize) -> Pool {
+    let config = Config {
+        inner: pgdog_stats::Config {
+            max,
+            min: 1,
+            healthcheck_interval: Duration::from_secs(3600),
+            idle_healthcheck_interval: Duration::ZERO,
+            test_on_checkout_after_idle: Some(Duration::from_millis(50)),
+            ..Config::default().inner
+        },
+    };
+
+    let pool = Pool::new(&PoolConfig {
+        address: Address::new_test(),
+        config,
+    });
+    pool.launch();
+    pool
+}
+
+#[tokio::test]
+async fn test_on_checkout_after_idle() {
+    crate::logger();
+
+    let pool = pool_test_on_checkout(1);
+
+    // New connections are always checked.
+    drop(pool.get(&Request::default()).await.unwrap());
+    let healthchecks = pool.state().stats.counts.healthchecks;
+
+    // Recently used connection isn't checked.
+    drop(pool.get(&Request::default()).await.unwrap());
+    assert_eq!(pool.state().stats.counts.healthchecks, healthchecks);
+
+    // Idle for longer than the threshold, checked
+    // despite the healthcheck interval.
+    sleep(Duration::from_millis(100)).await;
+    drop(pool.get(&Request::default()).await.unwrap());
+    assert_eq!(pool.state().stats.counts.healthchecks, healthchecks + 1);
+}
+
+#[tokio::test]
+async fn test_on_checkout_after_idle_replaces_dead_connection() {
+    crate::logger();
+
+    let pool = pool_test_on_checkout(2);
+
+    let dead = pool.get(&Request::default()).await.unwrap();
+    let dead_id = dead.id();
+    let mut conn = pool.get(&Request::default()).await.unwrap();
+    drop(dead);
+
+    conn.execute(format!("SELECT pg_terminate_backend({})", dead_id.pid).as_str())
+        .await
+        .unwrap();
+
+    sleep(Duration::from_millis(100)).await;
+
+    // The only idle connection is dead, so the client gets a new one.
+    let mut replacement = pool.get(&Request::default()).await.unwrap();
+    assert_ne!(replacement.id(), dead_id);
+    replacement.execute("SELECT 1").await.unwrap();
+}
+
 #[tokio::test]
 async fn test_checkout_timeout() {
     crate::logger();
@@ -37,12 +37,13 @@ impl Waiting {
             } else {
                 guard.stats.counts.writes += 1;
             }
-            guard.waiting.push_back(Waiter { request, tx });
+            guard.add_waiter(Waiter { request, tx }, Instant::now())?;
             guard.full()
         };
 
         // Tell maintenance we are in line waiting for a connection.
-        if !full {
+        // Reserved requests can get one even if the pool is full.
+        if !full || request.reserved {
             pool.comms().request.notify_one();
         }
 
@@ -4,7 +4,7 @@
 
 use pgdog_config::CopyFormat;
 
-use super::publisher::PublicationTable;
+use super::publisher::{PublicationTable, queries::quote_literal};
 
 /// COPY statement generator.
 #[derive(Debug, Clone)]
@@ -61,17 +61,89 @@ impl CopyStatement {
         }
     }
 
+    /// Generate COPY ... TO STDOUT statement for rows with `key`
+    /// after `after` and up to and including `until`.
+    pub fn copy_out_range(
+        &self,
+        key: &[String],
+        after: Option<&[String]>,
+        until: Option<&[String]>,
+    ) -> String {
+        let column_list = Self::column_list(key);
+        let mut filters = vec![];
+        if let Some(after) = after {
+            filters.push(format!(
+                "({}) > ({})",
+                column_list,
+                Self::literal_list(after)
+            ));
+        }
+        if let Some(until) = until {
+            filters.push(format!(
+                "({}) <= ({})",
+                column_list,
+                Self::literal_list(until)
+            ));
+        }
+
+        if filters.is_empty() {
+            return self.copy_out();
+        }
+
+        format!(
+            r#"COPY (SELECT {} FROM ONLY "{}"."{}" WHERE {}) TO STDOUT WITH (FORMAT {})"#,
+            Self::column_list(&self.columns),
+            self.schema_name(true),
+            self.table_name(true),
+            filters.join(" AND "),
+            self.copy_format
+        )
+    }
+
+    /// Generate a query returning `key` of the last row in the next `rows` rows
+    /// after `after`, in text format.
+    pub fn range_end(&self, key: &[String], after: Option<&[String]>, rows: usize) -> String {
+        let column_list = Self::column_list(key);
+
+        format!(
+            r#"SELECT {} FROM ONLY "{}"."{}"{} ORDER BY {} OFFSET {} LIMIT 1"#,
+            key.iter()
+                .map(|c| format!(r#""{}"::text"#, c))
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.schema_name(true),
+            self.table_name(true),
+            after
+                .map(|after| format!(" WHERE ({}) > ({})", column_list, Self::literal_list(after)))
+                .unwrap_or_default(),
+            column_list,
+            rows.saturating_sub(1),
+        )
+    }
+
+    fn column_list(columns: &[String]) -> String {
+        columns
+            .iter()
+            .map(|c| format!(r#""{}""#, c))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn literal_list(values: &[String]) -> String {
+        values
+            .iter()
+            .map(|value| quote_literal(value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     // Generate the statement.
     fn copy(&self, out: bool) -> String {
         format!(
             r#"COPY "{}"."{}" ({}) {} WITH (FORMAT {})"#,
             self.schema_name(out),
             self.table_name(out),
-            self.columns
-                .iter()
-                .map(|c| format!(r#""{}""#, c))
-                .collect::<Vec<_>>()
-                .join(", "),
+            Self::column_list(&self.columns),
             if out { "TO STDOUT" } else { "FROM STDIN" },
             self.copy_format
         )
@@ -122,4 +194,41 @@ mod test {
             r#"COPY "public"."test_0" ("id", "email") TO STDOUT WITH (FORMAT binary)"#
         );
     }
+
+    #[test]
+    fn test_copy_stmt_range() {
+        let table = PublicationTable {
+            schema: "public".into(),
+            name: "test".into(),
+            ..Default::default()
+        };
+
+        let copy = CopyStatement::new(&table, &["id".into(), "email".into()], CopyFormat::Text);
+        let key = ["id".to_string()];
+
+        assert_eq!(copy.copy_out_range(&key, None, None), copy.copy_out());
+        assert_eq!(
+            copy.copy_out_range(&key, None, Some(&["100".into()])),
+            r#"COPY (SELECT "id", "email" FROM ONLY "public"."test" WHERE ("id") <= ('100')) TO STDOUT WITH (FORMAT text)"#
+        );
+        assert_eq!(
+            copy.copy_out_range(&key, Some(&["100".into()]), Some(&["200".into()])),
+            r#"COPY (SELECT "id", "email" FROM ONLY "public"."test" WHERE ("id") > ('100') AND ("id") <= ('200')) TO STDOUT WITH (FORMAT text)"#
+        );
+
+        assert_eq!(
+            copy.range_end(&key, None, 100),
+            r#"SELECT "id"::text FROM ONLY "public"."test" ORDER BY "id" OFFSET 99 LIMIT 1"#
+        );
+
+        let key = ["tenant".to_string(), "id".to_string()];
+        assert_eq!(
+            copy.copy_out_range(&key, Some(&["o'neil".into(), "1".into()]), None),
+            r#"COPY (SELECT "id", "email" FROM ONLY "public"."test" WHERE ("tenant", "id") > ('o''neil', '1')) TO STDOUT WITH (FORMAT text)"#
+        );
+        assert_eq!(
+            copy.range_end(&key, Some(&["o'neil".into(), "1".into()]), 100),
+            r#"SELECT "tenant"::text, "id"::text FROM ONLY "public"."test" WHERE ("tenant", "id") > ('o''neil', '1') ORDER BY "tenant", "id" OFFSET 99 LIMIT 1"#
+        );
+    }
 }
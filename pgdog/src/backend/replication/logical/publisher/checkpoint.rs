@@ -0,0 +1,576 @@
+//! Data copied during data sync, recorded durably so an interrupted
+//! copy can resume without copying it again.
+//!
+//! Each table is copied at its own LSN and changes made to it after that LSN
+//! are replayed from the permanent replication slot. As long as the slot survives
+//! the interruption, data copied before it doesn't need to be copied again.
+//!
+//! Tables are copied in ranges of their replica identity, and the last key of each
+//! copied range is recorded. A table interrupted mid-copy resumes after that key,
+//! at the LSN of the first attempt. Changes replayed from that LSN are idempotent
+//! for rows copied later, so the table stays consistent.
+//!
+//! Checkpoints are stored in the first shard of the destination database and
+//! written in the transaction that copies the range into it, so a range is either
+//! copied and recorded, or neither. That shard commits last, so a recorded range
+//! was committed on every shard. They are dropped once every table was copied.
+
+use std::collections::HashMap;
+
+use tracing::debug;
+
+use super::super::Error;
+use super::{Lsn, Table, queries::quote_literal};
+use crate::backend::{Cluster, pool::Request};
+use crate::net::{DataRow, Format};
+
+/// Tables copied from a source shard, completely or in part.
+pub type Copied = HashMap<(String, String), TableCheckpoint>;
+
+static SETUP: &str = "CREATE SCHEMA IF NOT EXISTS pgdog;
+CREATE TABLE IF NOT EXISTS pgdog.copy_checkpoints (
+    slot_name TEXT NOT NULL,
+    shard INTEGER NOT NULL,
+    schema_name TEXT NOT NULL,
+    table_name TEXT NOT NULL,
+    lsn BIGINT NOT NULL,
+    last_key TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (slot_name, shard, schema_name, table_name)
+)";
+
+/// How much of a table was copied.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableCheckpoint {
+    /// Table data as of this LSN.
+    pub lsn: Lsn,
+    /// Replica identity of the last row copied, if the table
+    /// was only partially copied.
+    pub last_key: Option<Vec<String>>,
+}
+
+impl TableCheckpoint {
+    /// The whole table was copied.
+    pub fn done(&self) -> bool {
+        self.last_key.is_none()
+    }
+}
+
+/// Data sync checkpoints for a replication slot on a source shard.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    dest: Cluster,
+    slot: String,
+    shard: usize,
+}
+
+impl Checkpoint {
+    /// Create checkpoints for the replication slot used by data sync on the source shard.
+    pub fn new(dest: &Cluster, slot: &str, shard: usize) -> Self {
+        Self {
+            dest: dest.clone(),
+            slot: slot.to_owned(),
+            shard,
+        }
+    }
+
+    /// Create the checkpoints table, if it doesn't exist.
+    pub async fn setup(&self) -> Result<(), Error> {
+        let mut server = self.dest.primary(0, &Request::default()).await?;
+        server.execute_checked(SETUP).await?;
+
+        Ok(())
+    }
+
+    /// Get tables copied before the interruption.
+    pub async fn load(&self) -> Result<Copied, Error> {
+        let mut server = self.dest.primary(0, &Request::default()).await?;
+        let rows: Vec<DataRow> = server
+            .fetch_all(format!(
+                "SELECT schema_name, table_name, lsn, last_key FROM pgdog.copy_checkpoints WHERE slot_name = {} AND shard = {}",
+                quote_literal(&self.slot),
+                self.shard,
+            ))
+            .await?;
+
+        let mut copied = Copied::new();
+
+        for row in rows {
+            let (Some(schema), Some(name), Some(lsn)) = (
+                row.get::<String>(0, Format::Text),
+                row.get::<String>(1, Format::Text),
+                row.get::<i64>(2, Format::Text),
+            ) else {
+                return Err(Error::MissingData);
+            };
+
+            let last_key = match row.get::<String>(3, Format::Text) {
+                Some(last_key) => {
+                    Some(serde_json::from_str(&last_key).map_err(|_| Error::MissingData)?)
+                }
+                None => None,
+            };
+
+            copied.insert(
+                (schema, name),
+                TableCheckpoint {
+                    lsn: Lsn::from_i64(lsn),
+                    last_key,
+                },
+            );
+        }
+
+        Ok(copied)
+    }
+
+    /// Query recording how much of the table was copied. It's executed on the
+    /// first shard of the destination, in the transaction copying the range.
+    pub fn record(&self, table: &Table, checkpoint: &TableCheckpoint) -> Result<String, Error> {
+        let last_key = match &checkpoint.last_key {
+            Some(last_key) => {
+                quote_literal(&serde_json::to_string(last_key).map_err(|_| Error::MissingData)?)
+            }
+            None => "NULL".into(),
+        };
+
+        debug!(
+            "recording checkpoint for \"{}\".\"{}\" at lsn {} [shard: {}, last key: {:?}]",
+            table.table.schema, table.table.name, checkpoint.lsn, self.shard, checkpoint.last_key,
+        );
+
+        Ok(format!(
+            "INSERT INTO pgdog.copy_checkpoints (slot_name, shard, schema_name, table_name, lsn, last_key)
+            VALUES ({}, {}, {}, {}, {}, {})
+            ON CONFLICT (slot_name, shard, schema_name, table_name) DO UPDATE SET lsn = EXCLUDED.lsn, last_key = EXCLUDED.last_key",
+            quote_literal(&self.slot),
+            self.shard,
+            quote_literal(&table.table.schema),
+            quote_literal(&table.table.name),
+            checkpoint.lsn.lsn,
+            last_key,
+        ))
+    }
+
+    /// Remove all checkpoints for the source shard, e.g. because the replication
+    /// slot was created again and changes made before that are lost.
+    pub async fn clear(&self) -> Result<(), Error> {
+        let mut server = self.dest.primary(0, &Request::default()).await?;
+        server
+            .execute_checked(format!(
+                "DELETE FROM pgdog.copy_checkpoints WHERE slot_name = {} AND shard = {}",
+                quote_literal(&self.slot),
+                self.shard,
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Drop the checkpoints table once every source shard was copied.
+    pub async fn teardown(dest: &Cluster) -> Result<(), Error> {
+        let mut server = dest.primary(0, &Request::default()).await?;
+        server
+            .execute_checked("DROP TABLE IF EXISTS pgdog.copy_checkpoints")
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Split tables into those that still need to be copied, completely or in part,
+/// and those that were copied already, setting the LSN they were copied at.
+pub(super) fn resume_tables(tables: Vec<Table>, copied: &Copied) -> (Vec<Table>, Vec<Table>) {
+    let (mut done, pending): (Vec<_>, Vec<_>) = tables.into_iter().partition(|table| {
+        copied
+            .get(&table.key())
+            .is_some_and(|checkpoint| checkpoint.done())
+    });
+
+    for table in &mut done {
+        if let Some(checkpoint) = copied.get(&table.key()) {
+            table.lsn = checkpoint.lsn;
+        }
+    }
+
+    (pending, done)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::pool::Address;
+    use crate::backend::replication::publisher::{PublicationTable, ReplicaIdentity};
+    use crate::backend::replication::status::TableCopy;
+    use crate::backend::server::test::test_server;
+    use crate::config::config;
+    use pgdog_config::QueryParserEngine;
+    use pgdog_postgres_types::Oid;
+    use tokio_util::sync::CancellationToken;
+
+    fn table(name: &str) -> Table {
+        Table {
+            publication: "test".into(),
+            table: PublicationTable {
+                schema: "public".into(),
+                name: name.into(),
+                ..Default::default()
+            },
+            identity: ReplicaIdentity {
+                oid: Oid(1),
+                identity: String::new(),
+                kind: String::new(),
+            },
+            columns: vec![],
+            lsn: Lsn::default(),
+            query_parser_engine: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_resume_tables() {
+        let copied = Copied::from([
+            (
+                ("public".to_string(), "users".to_string()),
+                TableCheckpoint {
+                    lsn: Lsn::from_i64(123),
+                    last_key: None,
+                },
+            ),
+            (
+                ("public".to_string(), "orders".to_string()),
+                TableCheckpoint {
+                    lsn: Lsn::from_i64(456),
+                    last_key: Some(vec!["1000".into()]),
+                },
+            ),
+        ]);
+
+        // Partially copied tables still need to be copied.
+        let (pending, done) = resume_tables(
+            vec![table("users"), table("orders"), table("items")],
+            &copied,
+        );
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].table.name, "orders");
+        assert_eq!(pending[1].table.name, "items");
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].table.name, "users");
+        assert_eq!(done[0].lsn, Lsn::from_i64(123));
+
+        // Nothing was copied, so everything is copied from the start.
+        let (pending, done) = resume_tables(vec![table("users"), table("orders")], &Copied::new());
+        assert_eq!(pending.len(), 2);
+        assert!(done.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_resume() {
+        crate::logger();
+
+        let dest = Cluster::new_test(&config());
+        dest.launch();
+
+        let checkpoint = Checkpoint::new(&dest, "test_checkpoint_resume", 0);
+        let other_shard = Checkpoint::new(&dest, "test_checkpoint_resume", 1);
+        checkpoint.setup().await.unwrap();
+        checkpoint.clear().await.unwrap();
+        other_shard.clear().await.unwrap();
+
+        // Sync is interrupted after copying one table and part of another.
+        let users = TableCheckpoint {
+            lsn: Lsn::from_i64(456),
+            last_key: None,
+        };
+        let orders = TableCheckpoint {
+            lsn: Lsn::from_i64(789),
+            last_key: Some(vec!["o'neil".into(), "5".into()]),
+        };
+        let mut server = dest.primary(0, &Request::default()).await.unwrap();
+        for (name, copied) in [("users", &users), ("orders", &orders)] {
+            server
+                .execute_checked(checkpoint.record(&table(name), copied).unwrap())
+                .await
+                .unwrap();
+        }
+        drop(server);
+
+        let copied = checkpoint.load().await.unwrap();
+        assert_eq!(
+            copied.get(&("public".to_string(), "users".to_string())),
+            Some(&users)
+        );
+        assert_eq!(
+            copied.get(&("public".to_string(), "orders".to_string())),
+            Some(&orders)
+        );
+
+        // Tables from the other shard still need to be copied.
+        assert!(other_shard.load().await.unwrap().is_empty());
+
+        // Slot was created again, so the sync restarts.
+        checkpoint.clear().await.unwrap();
+        assert!(checkpoint.load().await.unwrap().is_empty());
+
+        dest.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_copy_resumes() {
+        crate::logger();
+
+        let mut source = test_server().await;
+        for query in [
+            "DROP PUBLICATION IF EXISTS checkpoint_resume_test",
+            "DROP TABLE IF EXISTS public.checkpoint_resume_test",
+            "CREATE TABLE public.checkpoint_resume_test (id BIGINT PRIMARY KEY, value TEXT)",
+            "INSERT INTO public.checkpoint_resume_test SELECT i, 'value_' || i FROM generate_series(1, 10) i",
+            "CREATE PUBLICATION checkpoint_resume_test FOR TABLE public.checkpoint_resume_test",
+        ] {
+            source.execute(query).await.unwrap();
+        }
+
+        // Copy 3 rows at a time into another database.
+        let mut cfg = (*config()).clone();
+        cfg.config.general.resharding_copy_checkpoint_rows = 3;
+        let dest = Cluster::new_test_other_database(&cfg);
+        dest.launch();
+
+        let mut destination = dest.primary(0, &Request::default()).await.unwrap();
+        for query in [
+            "DROP TABLE IF EXISTS public.checkpoint_resume_test",
+            "CREATE TABLE public.checkpoint_resume_test (id BIGINT PRIMARY KEY, value TEXT)",
+            // Conflicts with the third range, interrupting the copy.
+            "INSERT INTO public.checkpoint_resume_test VALUES (8, 'conflict')",
+        ] {
+            destination.execute(query).await.unwrap();
+        }
+
+        let checkpoint = Checkpoint::new(&dest, "test_interrupted_copy_resumes", 0);
+        checkpoint.setup().await.unwrap();
+        checkpoint.clear().await.unwrap();
+
+        let mut table = Table::load(
+            "checkpoint_resume_test",
+            &mut source,
+            QueryParserEngine::default(),
+        )
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+        let tracker = TableCopy::new(&table.table.schema, &table.table.name);
+        let cancel = CancellationToken::new();
+        let mut resume = None;
+
+        table
+            .data_sync(
+                &Address::new_test(),
+                &dest,
+                &cancel,
+                &tracker,
+                Some(&checkpoint),
+                &mut resume,
+            )
+            .await
+            .expect_err("copy should be interrupted by the conflicting row");
+
+        // The first two ranges were copied.
+        let copied = checkpoint.load().await.unwrap();
+        let interrupted = copied.get(&table.key()).cloned().unwrap();
+        assert_eq!(interrupted.last_key, Some(vec!["6".to_string()]));
+        assert_eq!(resume, Some(interrupted.clone()));
+
+        destination
+            .execute("DELETE FROM public.checkpoint_resume_test WHERE value = 'conflict'")
+            .await
+            .unwrap();
+
+        // Copying the first two ranges again would conflict with the rows already there.
+        let mut resume = copied.get(&table.key()).cloned();
+        table
+            .data_sync(
+                &Address::new_test(),
+                &dest,
+                &cancel,
+                &tracker,
+                Some(&checkpoint),
+                &mut resume,
+            )
+            .await
+            .unwrap();
+
+        // Changes are replayed from where the copy started.
+        assert_eq!(table.lsn, interrupted.lsn);
+
+        let copied = checkpoint.load().await.unwrap();
+        let finished = copied.get(&table.key()).unwrap();
+        assert!(finished.done());
+        assert_eq!(finished.lsn, interrupted.lsn);
+
+        let rows: Vec<DataRow> = destination
+            .fetch_all("SELECT id, value FROM public.checkpoint_resume_test ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 10);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(row.get::<i64>(0, Format::Text), Some(i as i64 + 1));
+            assert_eq!(
+                row.get::<String>(1, Format::Text),
+                Some(format!("value_{}", i + 1))
+            );
+        }
+
+        // Copied tables are not copied again.
+        let mut resume = Some(finished.clone());
+        table
+            .data_sync(
+                &Address::new_test(),
+                &dest,
+                &cancel,
+                &tracker,
+                Some(&checkpoint),
+                &mut resume,
+            )
+            .await
+            .unwrap();
+
+        checkpoint.clear().await.unwrap();
+        destination
+            .execute("DROP TABLE public.checkpoint_resume_test")
+            .await
+            .unwrap();
+        drop(destination);
+        dest.shutdown();
+
+        for query in [
+            "DROP PUBLICATION checkpoint_resume_test",
+            "DROP TABLE public.checkpoint_resume_test",
+        ] {
+            source.execute(query).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_range_rolled_back_without_checkpoint() {
+        crate::logger();
+
+        let mut source = test_server().await;
+        for query in [
+            "DROP PUBLICATION IF EXISTS checkpoint_crash_test",
+            "DROP TABLE IF EXISTS public.checkpoint_crash_test",
+            "CREATE TABLE public.checkpoint_crash_test (id BIGINT PRIMARY KEY, value TEXT)",
+            "INSERT INTO public.checkpoint_crash_test SELECT i, 'value_' || i FROM generate_series(1, 10) i",
+            "CREATE PUBLICATION checkpoint_crash_test FOR TABLE public.checkpoint_crash_test",
+        ] {
+            source.execute(query).await.unwrap();
+        }
+
+        let mut cfg = (*config()).clone();
+        cfg.config.general.resharding_copy_checkpoint_rows = 3;
+        let dest = Cluster::new_test_other_database(&cfg);
+        dest.launch();
+
+        let checkpoint = Checkpoint::new(&dest, "test_range_rolled_back_without_checkpoint", 0);
+        checkpoint.setup().await.unwrap();
+        checkpoint.clear().await.unwrap();
+
+        let mut destination = dest.primary(0, &Request::default()).await.unwrap();
+        for query in [
+            "DROP TABLE IF EXISTS public.checkpoint_crash_test",
+            "CREATE TABLE public.checkpoint_crash_test (id BIGINT PRIMARY KEY, value TEXT)",
+            // Crash before the second range is recorded.
+            "CREATE OR REPLACE FUNCTION pgdog.checkpoint_crash_test() RETURNS TRIGGER AS $$
+            BEGIN
+                IF NEW.slot_name = 'test_range_rolled_back_without_checkpoint' AND NEW.last_key = '[\"6\"]' THEN
+                    RAISE EXCEPTION 'crash';
+                END IF;
+                RETURN NEW;
+            END
+            $$ LANGUAGE plpgsql",
+            "CREATE TRIGGER checkpoint_crash_test BEFORE INSERT OR UPDATE ON pgdog.copy_checkpoints
+            FOR EACH ROW EXECUTE FUNCTION pgdog.checkpoint_crash_test()",
+        ] {
+            destination.execute(query).await.unwrap();
+        }
+
+        let mut table = Table::load(
+            "checkpoint_crash_test",
+            &mut source,
+            QueryParserEngine::default(),
+        )
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+        let tracker = TableCopy::new(&table.table.schema, &table.table.name);
+        let cancel = CancellationToken::new();
+        let mut resume = None;
+
+        table
+            .data_sync(
+                &Address::new_test(),
+                &dest,
+                &cancel,
+                &tracker,
+                Some(&checkpoint),
+                &mut resume,
+            )
+            .await
+            .expect_err("checkpoint should fail");
+
+        // The second range was rolled back with its checkpoint.
+        let copied = checkpoint.load().await.unwrap();
+        let interrupted = copied.get(&table.key()).cloned().unwrap();
+        assert_eq!(interrupted.last_key, Some(vec!["3".to_string()]));
+        let rows: Vec<DataRow> = destination
+            .fetch_all("SELECT id FROM public.checkpoint_crash_test")
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+
+        destination
+            .execute("DROP TRIGGER checkpoint_crash_test ON pgdog.copy_checkpoints")
+            .await
+            .unwrap();
+
+        // Resuming doesn't copy any row twice.
+        let mut resume = Some(interrupted);
+        table
+            .data_sync(
+                &Address::new_test(),
+                &dest,
+                &cancel,
+                &tracker,
+                Some(&checkpoint),
+                &mut resume,
+            )
+            .await
+            .unwrap();
+
+        let rows: Vec<DataRow> = destination
+            .fetch_all("SELECT id FROM public.checkpoint_crash_test ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 10);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(row.get::<i64>(0, Format::Text), Some(i as i64 + 1));
+        }
+
+        checkpoint.clear().await.unwrap();
+        for query in [
+            "DROP TABLE public.checkpoint_crash_test",
+            "DROP FUNCTION pgdog.checkpoint_crash_test()",
+        ] {
+            destination.execute(query).await.unwrap();
+        }
+        drop(destination);
+        dest.shutdown();
+
+        for query in [
+            "DROP PUBLICATION checkpoint_crash_test",
+            "DROP TABLE public.checkpoint_crash_test",
+        ] {
+            source.execute(query).await.unwrap();
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     backend::Server,
-    net::{CopyData, ErrorResponse, FromBytes, Protocol, Query, ToBytes},
+    net::{CopyData, DataRow, ErrorResponse, Format, FromBytes, Protocol, Query, ToBytes},
 };
 use pgdog_config::CopyFormat;
 use tracing::{debug, trace};
@@ -31,11 +31,22 @@ impl Copy {
     }
 
     pub async fn start(&self, server: &mut Server) -> Result<(), Error> {
+        self.start_range(server, &[], None, None).await
+    }
+
+    /// Start copying rows with `key` after `after` and up to and including `until`.
+    pub async fn start_range(
+        &self,
+        server: &mut Server,
+        key: &[String],
+        after: Option<&[String]>,
+        until: Option<&[String]>,
+    ) -> Result<(), Error> {
         if !server.in_transaction() {
             return Err(Error::TransactionNotStarted);
         }
 
-        let query = Query::new(self.stmt.copy_out());
+        let query = Query::new(self.stmt.copy_out_range(key, after, until));
         debug!("{} [{}]", query.query(), server.addr());
 
         server.send(&vec![query.into()].into()).await?;
@@ -67,6 +78,32 @@ impl Copy {
         }
     }
 
+    /// Get `key` of the last row in the next `rows` rows after `after`.
+    /// Returns `None` if there are fewer rows left.
+    pub async fn range_end(
+        &self,
+        server: &mut Server,
+        key: &[String],
+        after: Option<&[String]>,
+        rows: usize,
+    ) -> Result<Option<Vec<String>>, Error> {
+        let rows: Vec<DataRow> = server
+            .fetch_all(self.stmt.range_end(key, after, rows))
+            .await?;
+
+        let Some(row) = rows.first() else {
+            return Ok(None);
+        };
+
+        (0..key.len())
+            .map(|index| {
+                row.get::<String>(index, Format::Text)
+                    .ok_or(Error::MissingData)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
     pub fn statement(&self) -> &CopyStatement {
         &self.stmt
     }
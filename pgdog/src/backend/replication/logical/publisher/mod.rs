@@ -3,12 +3,14 @@ pub use non_identity_columns_presence::*;
 
 pub mod slot;
 pub use slot::*;
+pub mod checkpoint;
 pub mod copy;
 pub mod parallel_sync;
 pub mod progress;
 pub mod publisher_impl;
 pub mod queries;
 pub mod table;
+pub use checkpoint::Checkpoint;
 pub use copy::*;
 pub use parallel_sync::ParallelSyncManager;
 pub use queries::*;
@@ -5,14 +5,25 @@
 //!
 use std::sync::Arc;
 
-use tokio::{sync::Semaphore, task::JoinHandle, time::sleep};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    task::JoinHandle,
+    time::sleep,
+};
 use tracing::{info, warn};
 
 use super::super::Error;
 use crate::backend::{
     Cluster, Pool,
     pool::{Address, Request},
-    replication::{publisher::Table, status::TableCopy},
+    replication::{
+        publisher::{
+            Checkpoint, Table,
+            checkpoint::{Copied, TableCheckpoint},
+            queries::quote_literal,
+        },
+        status::TableCopy,
+    },
 };
 use crate::frontend::client::query_engine::two_pc::Manager;
 use crate::net::messages::Protocol;
@@ -26,7 +37,37 @@ struct ParallelSync {
     addr: Address,
     dest: Cluster,
     permit: Arc<Semaphore>,
+    limit: Option<Arc<Semaphore>>,
     cancel: CancellationToken,
+    checkpoint: Option<Checkpoint>,
+    resume: Option<TableCheckpoint>,
+}
+
+/// Wait for a permit from the source shard and, if configured,
+/// from the limit shared by all shards.
+///
+/// acquire_owned() consumes a cloned Arc, returning an OwnedSemaphorePermit with
+/// no lifetime tied to the semaphore, so the permits can be held across the copy.
+async fn acquire(
+    permit: &Arc<Semaphore>,
+    limit: Option<&Arc<Semaphore>>,
+) -> Result<(OwnedSemaphorePermit, Option<OwnedSemaphorePermit>), Error> {
+    let permit = Arc::clone(permit)
+        .acquire_owned()
+        .await
+        .map_err(|_| Error::ParallelConnection)?;
+
+    let limit = match limit {
+        Some(limit) => Some(
+            Arc::clone(limit)
+                .acquire_owned()
+                .await
+                .map_err(|_| Error::ParallelConnection)?,
+        ),
+        None => None,
+    };
+
+    Ok((permit, limit))
 }
 
 impl ParallelSync {
@@ -37,13 +78,8 @@ impl ParallelSync {
             let tracker = TableCopy::new(&self.table.table.schema, &self.table.table.name);
 
             // This won't acquire until we have at least 1 available permit.
-            // Permit will be given back when this task completes.
-            // acquire_owned() consumes a cloned Arc, returning an OwnedSemaphorePermit with
-            // no lifetime tied to `self`, which allows the subsequent `&mut self` borrow.
-            let _permit = Arc::clone(&self.permit)
-                .acquire_owned()
-                .await
-                .map_err(|_| Error::ParallelConnection)?;
+            // Permits will be given back when this task completes.
+            let _permits = acquire(&self.permit, self.limit.as_ref()).await?;
 
             if self.cancel.is_cancelled() {
                 return Err(Error::DataSyncAborted);
@@ -63,7 +99,14 @@ impl ParallelSync {
         loop {
             match self
                 .table
-                .data_sync(&self.addr, &self.dest, &self.cancel, tracker)
+                .data_sync(
+                    &self.addr,
+                    &self.dest,
+                    &self.cancel,
+                    tracker,
+                    self.checkpoint.as_ref(),
+                    &mut self.resume,
+                )
                 .await
             {
                 Ok(_) => return Ok(self.table),
@@ -104,6 +147,8 @@ impl ParallelSync {
                     // pool can recover first.
                     // FUTURE: truncate before retry to handle the COPY-committed-but-dropped
                     // race (rows remain → PK violations). Safe once source-guard checks exist.
+                    // Rows copied before the last checkpoint are expected and
+                    // the copy resumes after them, so only rows after it count.
                     if self.destination_has_rows().await {
                         return Err(err);
                     }
@@ -113,17 +158,40 @@ impl ParallelSync {
     }
 
     /// Returns `true` if any reachable destination shard holds rows from a prior COPY
-    /// attempt. Every shard is probed; a shard whose probe errors (e.g. its pool was
-    /// shut down by a RELOAD) is logged at WARN and not counted, since we cannot prove
-    /// it dirty. Emits a single WARN listing the shards that do hold rows.
+    /// attempt, after the last checkpoint if the copy resumes. Every shard is probed;
+    /// a shard whose probe errors (e.g. its pool was shut down by a RELOAD) is logged
+    /// at WARN and not counted, since we cannot prove it dirty. Emits a single WARN listing the shards that do hold rows.
     async fn destination_has_rows(&self) -> bool {
         let schema = self.table.table.destination_schema();
         let name = self.table.table.destination_name();
-        let sql = format!(
-            "SELECT 1 FROM \"{}\".\"{}\" LIMIT 1",
+        let mut sql = format!(
+            "SELECT 1 FROM \"{}\".\"{}\"",
             escape_identifier(schema),
             escape_identifier(name),
         );
+        if let Some(after) = self
+            .resume
+            .as_ref()
+            .and_then(|resume| resume.last_key.as_ref())
+        {
+            let key = self
+                .table
+                .columns
+                .iter()
+                .filter(|column| column.identity)
+                .map(|column| format!("\"{}\"", escape_identifier(&column.name)))
+                .collect::<Vec<_>>();
+            let values = after
+                .iter()
+                .map(|value| quote_literal(value))
+                .collect::<Vec<_>>();
+            sql.push_str(&format!(
+                " WHERE ({}) > ({})",
+                key.join(", "),
+                values.join(", ")
+            ));
+        }
+        sql.push_str(" LIMIT 1");
 
         let mut shards_with_rows = vec![];
         for (shard, _) in self.dest.shards().iter().enumerate() {
@@ -161,9 +229,12 @@ impl ParallelSync {
 /// Sync tables in parallel up to maximum concurrency.
 pub struct ParallelSyncManager {
     permit: Arc<Semaphore>,
+    limit: Option<Arc<Semaphore>>,
     tables: Vec<Table>,
     replicas: Vec<Pool>,
     dest: Cluster,
+    checkpoint: Option<Checkpoint>,
+    copied: Copied,
 }
 
 impl ParallelSyncManager {
@@ -182,12 +253,30 @@ impl ParallelSyncManager {
             permit: Arc::new(Semaphore::new(
                 replicas.len() * dest.resharding_parallel_copies(),
             )),
+            limit: None,
             tables,
             replicas,
             dest,
+            checkpoint: None,
+            copied: Copied::new(),
         })
     }
 
+    /// Record the progress of each table copied from the source shard, so the sync
+    /// can be resumed if it's interrupted. Tables in `copied` resume where they left off.
+    pub fn checkpoint(mut self, checkpoint: Checkpoint, copied: Copied) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self.copied = copied;
+        self
+    }
+
+    /// Limit the number of tables copied at the same time,
+    /// shared with the other source shards.
+    pub fn limit(mut self, limit: Option<Arc<Semaphore>>) -> Self {
+        self.limit = limit;
+        self
+    }
+
     /// Run parallel table sync and return table LSNs when everything is done.
     pub async fn run(self, cancel: CancellationToken) -> Result<Vec<Table>, Error> {
         info!(
@@ -213,13 +302,17 @@ impl ParallelSyncManager {
             let replica = replicas_iter
                 .next()
                 .expect("replicas is non-empty; checked in new()");
+            let resume = self.copied.get(&table.key()).cloned();
             handles.push(
                 ParallelSync {
                     table,
                     addr: replica.addr().clone(),
                     dest: self.dest.clone(),
                     permit: self.permit.clone(),
+                    limit: self.limit.clone(),
                     cancel: cancel.clone(),
+                    checkpoint: self.checkpoint.clone(),
+                    resume,
                 }
                 .run(),
             );
@@ -236,3 +329,37 @@ impl ParallelSyncManager {
         Ok(tables)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parallel_copies_limit() {
+        // Each source shard allows 4 copies, but only 2 can run in total.
+        let shard_a = Arc::new(Semaphore::new(4));
+        let shard_b = Arc::new(Semaphore::new(4));
+        let limit = Arc::new(Semaphore::new(2));
+
+        let first = acquire(&shard_a, Some(&limit)).await.unwrap();
+        let _second = acquire(&shard_b, Some(&limit)).await.unwrap();
+
+        let third = timeout(Duration::from_millis(50), acquire(&shard_a, Some(&limit))).await;
+        assert!(third.is_err(), "limit should be reached");
+
+        drop(first);
+        let third = timeout(Duration::from_millis(50), acquire(&shard_a, Some(&limit))).await;
+        assert!(third.unwrap().is_ok());
+
+        // Without the limit, only the shard permits apply.
+        let mut permits = vec![];
+        for _ in 0..3 {
+            permits.push(acquire(&shard_b, None).await.unwrap());
+        }
+        assert_eq!(shard_b.available_permits(), 0);
+    }
+}
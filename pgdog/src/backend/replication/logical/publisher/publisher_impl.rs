@@ -5,6 +5,7 @@ use std::time::Duration;
 use futures::stream::{FuturesUnordered, StreamExt};
 use parking_lot::Mutex;
 use pgdog_config::QueryParserEngine;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 use tokio::time::{Instant, sleep};
 use tokio::try_join;
@@ -12,12 +13,16 @@ use tokio::{select, time::interval};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
-use super::super::{Error, ensure_validation, publisher::Table};
+use super::super::{
+    Error, TableValidationError, TableValidationErrorKind, ensure_validation,
+    publisher::{Table, tables_missing_unique_index},
+};
 use super::ReplicationSlot;
 
 use crate::backend::replication::logical::subscriber::omni_ownership::OmniOwnership;
 use crate::backend::replication::logical::subscriber::stream::StreamSubscriber;
 use crate::backend::replication::publisher::Lsn;
+use crate::backend::replication::publisher::checkpoint::{Checkpoint, Copied, resume_tables};
 use crate::backend::replication::publisher::progress::Progress;
 use crate::backend::replication::{
     logical::publisher::ReplicationData, publisher::ParallelSyncManager,
@@ -92,7 +97,12 @@ impl Publisher {
         for number in 0..shard_count {
             self.tables.entry(number).or_default();
         }
-        for (shard_index, table) in omnisharded.into_values().enumerate() {
+        // Sorted, so tables are assigned to the same shards
+        // if the sync is resumed.
+        let mut omnisharded = omnisharded.into_values().collect::<Vec<_>>();
+        omnisharded.sort_by_key(|table| table.key());
+
+        for (shard_index, table) in omnisharded.into_iter().enumerate() {
             let shard = shard_index % shard_count;
             if let Some(tables) = self.tables.get_mut(&shard) {
                 tables.push(table);
@@ -156,6 +166,45 @@ impl Publisher {
         Ok(())
     }
 
+    /// Check that the replica identity of each table works with the way
+    /// changes are applied to the destination.
+    ///
+    /// Inserts into omnisharded tables with `REPLICA IDENTITY FULL` are rewritten
+    /// to `INSERT ... ON CONFLICT DO NOTHING`, which needs a unique index on the destination.
+    /// Without one, replication would fail only after it started, leaving an inactive slot behind,
+    /// so this is checked before any slots are created.
+    async fn check_replica_identity(&self, dest: &Cluster) -> Result<(), Error> {
+        let sharded_tables = dest.sharding_schema().tables;
+        let omni_full = self
+            .tables
+            .values()
+            .flatten()
+            .filter(|table| table.is_identity_full() && !table.is_sharded(&sharded_tables))
+            .map(|table| &table.table)
+            .collect::<Vec<_>>();
+
+        if omni_full.is_empty() {
+            return Ok(());
+        }
+
+        let mut errors = vec![];
+
+        for shard in 0..dest.shards().len() {
+            let mut server = dest.primary(shard, &Request::default()).await?;
+            let missing =
+                tables_missing_unique_index(omni_full.iter().copied(), &mut server).await?;
+
+            errors.extend(missing.into_iter().map(|table_name| TableValidationError {
+                table_name,
+                kind: TableValidationErrorKind::FullIdentityOmniNoUniqueIndex,
+            }));
+        }
+
+        ensure_validation!(errors);
+
+        Ok(())
+    }
+
     /// Create permanent slots for each shard.
     /// This uses a dedicated connection.
     ///
@@ -204,6 +253,7 @@ impl Publisher {
 
         // Synchronize tables from publication.
         self.sync_tables(false, source, dest).await?;
+        self.check_replica_identity(dest).await?;
 
         // Create replication slots if we haven't already.
         if self.slots.is_empty() {
@@ -396,6 +446,7 @@ impl Publisher {
             .collect();
 
         ensure_validation!(validation_errors);
+        self.check_replica_identity(dest).await?;
 
         // Create replication slots only after validation passes — a slot
         // created before valid() would be orphaned on validation errors.
@@ -407,6 +458,12 @@ impl Publisher {
         let cancel = cancel.child_token();
         let _guard = cancel.drop_guard_ref();
 
+        // Limit the number of tables copied at the same time across all shards.
+        let limit = match dest.resharding_max_parallel_copies() {
+            0 => None,
+            limit => Some(Arc::new(Semaphore::new(limit))),
+        };
+
         let mut handles = FuturesUnordered::new();
 
         for (number, shard) in source.shards().iter().enumerate() {
@@ -416,6 +473,26 @@ impl Publisher {
                 .ok_or(Error::NoReplicationTables(number))?
                 .clone();
 
+            // Data copied before the sync was interrupted doesn't need to be copied again,
+            // as long as the shard's replication slot kept the changes made to it since.
+            let checkpoint = Checkpoint::new(dest, &self.slot_name, number);
+            checkpoint.setup().await?;
+            let copied = if self.slots.get(&number).is_some_and(|slot| slot.existing()) {
+                checkpoint.load().await?
+            } else {
+                checkpoint.clear().await?;
+                Copied::new()
+            };
+            let (tables, done) = resume_tables(tables, &copied);
+
+            if !done.is_empty() {
+                info!(
+                    "resuming table sync, {} tables copied already, shard={}",
+                    done.len(),
+                    number
+                );
+            }
+
             info!(
                 "table sync starting for {} tables, shard={}",
                 tables.len(),
@@ -445,9 +522,13 @@ impl Publisher {
 
             let dest = dest.clone();
             let cancel = cancel.clone();
+            let limit = limit.clone();
             handles.push(tasks::spawn("parallel sync manager", async move {
-                let manager = ParallelSyncManager::new(tables, replicas, dest)?;
-                let tables = manager.run(cancel).await?;
+                let manager = ParallelSyncManager::new(tables, replicas, dest)?
+                    .checkpoint(checkpoint, copied)
+                    .limit(limit);
+                let mut tables = manager.run(cancel).await?;
+                tables.extend(done);
 
                 Ok::<(usize, Vec<Table>), Error>((number, tables))
             }));
@@ -468,6 +549,9 @@ impl Publisher {
             self.tables.insert(number, tables);
         }
 
+        // Every table was copied, so there is nothing left to resume.
+        Checkpoint::teardown(dest).await?;
+
         Ok(())
     }
 
@@ -724,6 +808,70 @@ mod test {
         }
     }
 
+    /// Inserts into an omnisharded `REPLICA IDENTITY FULL` table are applied as upserts,
+    /// which need a unique index on the destination. A table without one must be rejected
+    /// before the replication slot is created, not after replication starts.
+    #[tokio::test]
+    async fn data_sync_rejects_full_identity_omni_without_unique_index() {
+        crate::logger();
+
+        let mut server = test_replication_server().await;
+        for ddl in &[
+            "DROP TABLE IF EXISTS pub_test_full_omni",
+            "CREATE TABLE pub_test_full_omni (data TEXT NOT NULL, value BIGINT)",
+            "ALTER TABLE pub_test_full_omni REPLICA IDENTITY FULL",
+            "DROP PUBLICATION IF EXISTS pub_full_identity_omni_test",
+            "CREATE PUBLICATION pub_full_identity_omni_test FOR TABLE pub_test_full_omni",
+        ] {
+            server.execute(*ddl).await.unwrap();
+        }
+
+        // Source and destination are the same database, so
+        // the destination table has no unique index either.
+        let source = Cluster::new_test(&config());
+        source.launch();
+        let dest = Cluster::new_test(&config());
+        dest.launch();
+
+        let mut publisher = Publisher::new(
+            "pub_full_identity_omni_test",
+            QueryParserEngine::default(),
+            "pub_full_identity_omni_slot".into(),
+        );
+
+        let err = publisher
+            .data_sync(&source, &dest, &CancellationToken::new())
+            .await
+            .expect_err("data_sync must fail for FULL identity omni table without unique index");
+
+        assert_eq!(
+            err.to_string(),
+            "Table validation failed:\n\
+            \ttable \"pgdog\".\"pub_test_full_omni\": REPLICA IDENTITY FULL on a non-sharded table requires a unique index on the destination; \
+            add a unique index on the source or destination, use REPLICA IDENTITY USING INDEX on the source, or shard the table",
+        );
+        assert!(
+            publisher.slots.is_empty(),
+            "no replication slot must be created when the pre-check fails"
+        );
+
+        // With a unique index, the table can be replicated.
+        server
+            .execute("CREATE UNIQUE INDEX pub_test_full_omni_data_idx ON pub_test_full_omni (data)")
+            .await
+            .unwrap();
+        publisher.check_replica_identity(&dest).await.unwrap();
+
+        source.shutdown();
+        dest.shutdown();
+        for ddl in &[
+            "DROP PUBLICATION IF EXISTS pub_full_identity_omni_test",
+            "DROP TABLE IF EXISTS pub_test_full_omni",
+        ] {
+            server.execute(*ddl).await.unwrap();
+        }
+    }
+
     // ── Helpers ─────────────────────────────────────────────────────────────
 
     use crate::net::{
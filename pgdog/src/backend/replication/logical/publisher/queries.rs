@@ -14,7 +14,7 @@ use crate::{
 
 use super::super::Error;
 
-fn quote_literal(s: &str) -> String {
+pub(crate) fn quote_literal(s: &str) -> String {
     format!("'{}'", s.replace('\'', "''"))
 }
 
@@ -56,6 +56,8 @@ pub struct ReplicationSlot {
     kind: SlotKind,
     server_meta: Option<Server>,
     tracker: Option<ReplicationSlotTracker>,
+    /// Slot existed already when we tried to create it.
+    existing: bool,
 }
 
 impl ReplicationSlot {
@@ -81,6 +83,7 @@ impl ReplicationSlot {
             kind: SlotKind::Replication,
             server_meta: None,
             tracker: None,
+            existing: false,
         }
     }
 
@@ -100,6 +103,7 @@ impl ReplicationSlot {
             kind: SlotKind::DataSync,
             server_meta: None,
             tracker: None,
+            existing: false,
         }
     }
 
@@ -248,6 +252,7 @@ impl ReplicationSlot {
                         {
                             let lsn = Lsn::from_str(&lsn)?;
                             self.lsn = lsn;
+                            self.existing = true;
                             self.tracker = Some(ReplicationSlotTracker::new(
                                 &self.name,
                                 &self.lsn,
@@ -411,6 +416,11 @@ impl ReplicationSlot {
         Ok(())
     }
 
+    /// The slot was created before, e.g. by a data sync that was interrupted.
+    pub fn existing(&self) -> bool {
+        self.existing
+    }
+
     /// Current slot LSN.
     pub fn lsn(&self) -> Lsn {
         self.lsn
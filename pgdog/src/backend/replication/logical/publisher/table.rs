@@ -21,7 +21,10 @@ use super::super::{
     Error, TableValidationError, TableValidationErrorKind, subscriber::CopySubscriber,
 };
 use super::non_identity_columns_presence::NonIdentityColumnsPresence;
-use super::{Copy, PublicationTable, PublicationTableColumn, ReplicaIdentity, ReplicationSlot};
+use super::{
+    Checkpoint, Copy, PublicationTable, PublicationTableColumn, ReplicaIdentity, ReplicationSlot,
+    checkpoint::TableCheckpoint,
+};
 use tokio_util::sync::CancellationToken;
 
 use tracing::info;
@@ -436,13 +439,25 @@ impl Table {
         false
     }
 
+    /// Copy the table to the destination.
+    ///
+    /// The table is copied in ranges of its replica identity, each recorded in `checkpoint`
+    /// in the transaction copying it and in `resume` once committed. If `resume` is set,
+    /// the copy continues after the last range copied, at the LSN of the first attempt.
     pub async fn data_sync(
         &mut self,
         source: &Address,
         dest: &Cluster,
         cancel: &CancellationToken,
         tracker: &TableCopy,
+        checkpoint: Option<&Checkpoint>,
+        resume: &mut Option<TableCheckpoint>,
     ) -> Result<Lsn, Error> {
+        if let Some(resume) = resume.as_ref().filter(|resume| resume.done()) {
+            self.lsn = resume.lsn;
+            return Ok(self.lsn);
+        }
+
         info!(
             "data sync for \"{}\".\"{}\" started [{}]",
             self.table.schema, self.table.name, source
@@ -455,16 +470,6 @@ impl Table {
 
         tracker.update_sql(&copy.statement().copy_out());
 
-        // Create new standalone connection for the copy.
-        // let mut server = Server::connect(source, ServerOptions::new_replication()).await?;
-        let mut copy_sub = CopySubscriber::new(
-            copy.statement(),
-            dest,
-            #[cfg(not(feature = "new_parser"))]
-            self.query_parser_engine,
-        )?;
-        copy_sub.connect().await?;
-
         // Create sync slot.
         let mut slot = ReplicationSlot::data_sync(&self.publication, source);
         slot.connect().await?;
@@ -473,29 +478,93 @@ impl Table {
         // Reload table info just to be sure it's consistent.
         self.reload(slot.server()?).await?;
 
-        // Copy rows over.
-        copy.start(slot.server()?).await?;
-        copy_sub.start_copy().await?;
+        // Rows copied before the interruption were copied at an earlier LSN.
+        // Changes since then are replayed for all rows, which is idempotent
+        // for rows copied now.
+        let mut after = None;
+        if let Some(resume) = resume.as_ref() {
+            info!(
+                "resuming data sync for \"{}\".\"{}\" after {:?} at lsn {}",
+                self.table.schema, self.table.name, resume.last_key, resume.lsn
+            );
+            self.lsn = resume.lsn;
+            after = resume.last_key.clone();
+        }
+
+        // Tables without a usable key are copied in one go.
+        let rows = dest.resharding_copy_checkpoint_rows();
+        let key = if rows > 0 && !self.is_identity_full() {
+            self.columns
+                .iter()
+                .filter(|column| column.identity)
+                .map(|column| column.name.clone())
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
         let progress = Progress::new_data_sync(&self.table);
+        let mut bytes_sharded = 0;
 
-        while let Some(data_row) = copy.data(slot.server()?).await? {
-            select! {
-                _ = cancel.cancelled() =>  {
-                    error!("aborting data sync for table {}", self.table);
-
-                    return Err(Error::CopyAborted(self.table.clone()))
-                },
-                result = copy_sub.copy_data(data_row) => {
-                    let (rows, bytes) = result?;
-                    progress.update(copy_sub.bytes_sharded(), slot.lsn().lsn);
-                    tracker.update_progress(bytes, rows);
+        loop {
+            let until = if key.is_empty() {
+                None
+            } else {
+                copy.range_end(slot.server()?, &key, after.as_deref(), rows)
+                    .await?
+            };
+
+            // Each range is copied in its own transaction on the destination.
+            let mut copy_sub = CopySubscriber::new(
+                copy.statement(),
+                dest,
+                #[cfg(not(feature = "new_parser"))]
+                self.query_parser_engine,
+            )?;
+            copy_sub.connect().await?;
+
+            let copied = TableCheckpoint {
+                lsn: self.lsn,
+                last_key: until.clone(),
+            };
+
+            // The range and its checkpoint are committed together.
+            if let Some(checkpoint) = checkpoint {
+                copy_sub.checkpoint(checkpoint.record(self, &copied)?);
+            }
+
+            // Copy rows over.
+            copy.start_range(slot.server()?, &key, after.as_deref(), until.as_deref())
+                .await?;
+            copy_sub.start_copy().await?;
+
+            while let Some(data_row) = copy.data(slot.server()?).await? {
+                select! {
+                    _ = cancel.cancelled() =>  {
+                        error!("aborting data sync for table {}", self.table);
+
+                        return Err(Error::CopyAborted(self.table.clone()))
+                    },
+                    result = copy_sub.copy_data(data_row) => {
+                        let (rows, bytes) = result?;
+                        progress.update(bytes_sharded + copy_sub.bytes_sharded(), slot.lsn().lsn);
+                        tracker.update_progress(bytes, rows);
+                    }
                 }
             }
-        }
 
-        copy_sub.copy_done().await?;
+            copy_sub.copy_done().await?;
+            copy_sub.disconnect().await?;
+            bytes_sharded += copy_sub.bytes_sharded();
+
+            *resume = Some(copied);
+
+            match until {
+                Some(until) => after = Some(until),
+                None => break,
+            }
+        }
 
-        copy_sub.disconnect().await?;
         progress.done();
 
         slot.server()?.execute("COMMIT").await?;
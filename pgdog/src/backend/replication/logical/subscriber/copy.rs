@@ -29,11 +29,6 @@ use crate::{
 
 use super::super::{CopyStatement, Error};
 
-// Not really needed, but we're currently
-// sharding 3 CopyData messages at a time.
-// This reduces memory allocations.
-static BUFFER_SIZE: usize = 3;
-
 #[derive(Debug)]
 pub struct CopySubscriber {
     copy: CopyParser,
@@ -42,6 +37,7 @@ pub struct CopySubscriber {
     connections: Vec<ParallelConnection>,
     stmt: CopyStatement,
     bytes_sharded: usize,
+    checkpoint: Option<String>,
 }
 
 impl CopySubscriber {
@@ -55,7 +51,7 @@ impl CopySubscriber {
         let ast = pg_raw_parse::parse(&copy_stmt.copy_in()).map_err(ParseError::from)?;
         let stmt = ast.stmts().next().ok_or(ParseError::EmptyQuery)?;
         let copy = if let Node::CopyStmt(stmt) = stmt {
-            CopyParser::new(stmt, cluster).map_err(|_| Error::MissingData)?
+            CopyParser::new(stmt, cluster, None).map_err(|_| Error::MissingData)?
         } else {
             return Err(Error::MissingData);
         };
@@ -67,6 +63,7 @@ impl CopySubscriber {
             connections: vec![],
             stmt: copy_stmt.clone(),
             bytes_sharded: 0,
+            checkpoint: None,
         })
     }
 
@@ -95,7 +92,7 @@ impl CopySubscriber {
                     .as_ref()
                     .ok_or(Error::MissingData)?;
                 let copy = if let NodeEnum::CopyStmt(stmt) = stmt {
-                    CopyParser::new(stmt, cluster).map_err(|_| Error::MissingData)?
+                    CopyParser::new(stmt, cluster, None).map_err(|_| Error::MissingData)?
                 } else {
                     return Err(Error::MissingData);
                 };
@@ -107,12 +104,19 @@ impl CopySubscriber {
                     connections: vec![],
                     stmt: copy_stmt.clone(),
                     bytes_sharded: 0,
+                    checkpoint: None,
                 })
             }
         }
         _ => {}
     }
 
+    /// Record the copy with `query`, executed on the first shard
+    /// in the same transaction as the COPY.
+    pub fn checkpoint(&mut self, query: String) {
+        self.checkpoint = Some(query);
+    }
+
     /// Connect to all shards. One connection per primary.
     pub async fn connect(&mut self) -> Result<(), Error> {
         let mut servers = vec![];
@@ -261,18 +265,24 @@ impl CopySubscriber {
                 })?;
         }
 
+        if let Some(query) = self.checkpoint.take() {
+            let server = self.connections.first_mut().ok_or(Error::NoPrimary)?;
+            Self::send_and_confirm(server, Query::new(query).into()).await?;
+        }
+
         // Commit pass: every shard has staged its rows and is sitting in an open transaction.
         // Commit them. The data is already written, so COMMIT is cheap and very likely to
         // succeed. Sequential and NOT atomic across shards: if a COMMIT fails after one or more
         // earlier shards have already committed, those shards stay committed — the only residual
         // partial-commit window (full cross-shard atomicity via 2PC is intentionally out of
-        // scope). Shards not yet committed roll back on connection close. The
-        // destination_has_rows() guard in parallel_sync.rs prevents a doomed retry if this
+        // scope). Shards not yet committed roll back on connection close. The first shard,
+        // holding the checkpoint, commits last, so a recorded range is committed everywhere.
+        // The destination_has_rows() guard in parallel_sync.rs prevents a doomed retry if this
         // window is ever hit.
         if self.cluster.two_pc_enabled() {
             self.commit_two_pc().await?;
         } else {
-            for (shard, server) in self.connections.iter_mut().enumerate() {
+            for (shard, server) in self.connections.iter_mut().enumerate().rev() {
                 if let Err(error) =
                     Self::send_and_confirm(server, Query::new("COMMIT").into()).await
                 {
@@ -342,9 +352,12 @@ impl CopySubscriber {
     }
 
     /// Send data to subscriber, buffered.
+    ///
+    /// Rows are sharded in batches of `resharding_copy_batch_size`,
+    /// which reduces memory allocations.
     pub async fn copy_data(&mut self, data: CopyData) -> Result<(usize, usize), Error> {
         self.buffer.push(data);
-        if self.buffer.len() == BUFFER_SIZE {
+        if self.buffer.len() >= self.cluster.resharding_copy_batch_size().max(1) {
             return self.flush().await;
         }
 
@@ -389,6 +402,8 @@ impl CopySubscriber {
 
 #[cfg(test)]
 mod test {
+    use std::ops::Deref;
+
     use bytes::Bytes;
 
     use crate::{
@@ -401,13 +416,12 @@ mod test {
 
     use super::*;
 
-    #[tokio::test]
-    async fn test_subscriber() {
+    async fn copy_rows(name: &str, batch_size: usize) {
         crate::logger();
 
         let table = PublicationTable {
             schema: "pgdog".into(),
-            name: "sharded".into(),
+            name: name.into(),
             ..Default::default()
         };
 
@@ -416,16 +430,21 @@ mod test {
             &["id".into(), "value".into()],
             pgdog_config::CopyFormat::Binary,
         );
-        let cluster = Cluster::new_test(&config());
+        let mut config = config().deref().clone();
+        config.config.general.resharding_copy_batch_size = batch_size;
+        let cluster = Cluster::new_test(&config);
         cluster.launch();
 
         cluster
-            .execute("CREATE TABLE IF NOT EXISTS pgdog.sharded (id BIGINT, value TEXT)")
+            .execute(format!(
+                "CREATE TABLE IF NOT EXISTS pgdog.{} (id BIGINT, value TEXT)",
+                name
+            ))
             .await
             .unwrap();
 
         cluster
-            .execute("TRUNCATE TABLE pgdog.sharded")
+            .execute(format!("TRUNCATE TABLE pgdog.{}", name))
             .await
             .unwrap();
 
@@ -433,7 +452,7 @@ mod test {
             &copy,
             &cluster,
             #[cfg(not(feature = "new_parser"))]
-            config().config.general.query_parser_engine,
+            config.config.general.query_parser_engine,
         )
         .unwrap();
         subscriber.start_copy().await.unwrap();
@@ -459,7 +478,7 @@ mod test {
         subscriber.copy_done().await.unwrap();
         let mut server = cluster.primary(0, &Request::default()).await.unwrap();
         let count = server
-            .fetch_all::<i64>("SELECT COUNT(*)::BIGINT FROM pgdog.sharded")
+            .fetch_all::<i64>(format!("SELECT COUNT(*)::BIGINT FROM pgdog.{}", name))
             .await
             .unwrap();
         // Test shards point to the same database.
@@ -467,13 +486,24 @@ mod test {
         assert_eq!(count.first().unwrap().clone(), 25);
 
         cluster
-            .execute("TRUNCATE TABLE pgdog.sharded")
+            .execute(format!("TRUNCATE TABLE pgdog.{}", name))
             .await
             .unwrap();
 
         cluster.shutdown();
     }
 
+    #[tokio::test]
+    async fn test_subscriber() {
+        copy_rows("sharded", 3).await;
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_small_batch_size() {
+        // Every row is sharded and sent on its own.
+        copy_rows("sharded_small_batch", 1).await;
+    }
+
     #[tokio::test]
     async fn send_and_confirm_skips_async_messages() {
         crate::logger();
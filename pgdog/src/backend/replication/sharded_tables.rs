@@ -2,7 +2,7 @@
 use pgdog_config::{OmnishardedTable, SystemCatalogsBehavior};
 
 use crate::{
-    config::DataType,
+    config::{DataType, Hasher},
     frontend::router::{
         parser::Column,
         sharding::{Mapping, ShardedTable},
@@ -30,6 +30,9 @@ pub struct CommonMapping {
     /// The list/range mapping, if any.
     /// If none, the column is using hash sharding.
     pub mapping: Option<Mapping>,
+    /// The hash function and its seed.
+    pub hasher: Hasher,
+    pub hasher_seed: u64,
 }
 
 /// Sharded tables.
@@ -69,17 +72,22 @@ impl ShardedTables {
         // It is only valid when every table agrees on the same sharding scheme,
         // so that any table's function produces the same shard for the same key.
         //
-        // Only data_type and mapping are compared because those are the only fields
-        // CommonMapping stores and infer_from_from_and_config reads.
+        // Only data_type, mapping and the hasher are compared because those are
+        // the only fields CommonMapping stores and infer_from_from_and_config reads.
         let common_mapping = match tables.split_first() {
             Some((first, rest))
-                if rest
-                    .iter()
-                    .all(|t| t.data_type == first.data_type && t.mapping == first.mapping) =>
+                if rest.iter().all(|t| {
+                    t.data_type == first.data_type
+                        && t.mapping == first.mapping
+                        && t.hasher == first.hasher
+                        && t.hasher_seed == first.hasher_seed
+                }) =>
             {
                 Some(CommonMapping {
                     data_type: first.data_type,
                     mapping: first.mapping.clone(),
+                    hasher: first.hasher.clone(),
+                    hasher_seed: first.hasher_seed,
                 })
             }
             _ => None,
@@ -120,6 +128,11 @@ impl ShardedTables {
         self.inner.system_catalogs == SystemCatalogsBehavior::Sharded
     }
 
+    /// Each client reads system catalogs from the same shard.
+    pub fn is_system_catalog_sticky(&self) -> bool {
+        self.inner.system_catalogs == SystemCatalogsBehavior::OmnishardedSticky
+    }
+
     /// The deployment has only one sharded table.
     pub fn common_mapping(&self) -> &Option<CommonMapping> {
         &self.inner.common_mapping
@@ -11,15 +11,35 @@ use std::ops::DerefMut;
 use std::{collections::HashMap, ops::Deref};
 use tracing::info;
 
+use columns::StatsColumn;
 pub use relation::Relation;
 
 use super::{Cluster, Error, Server, pool::Request};
-use crate::frontend::router::parser::Table;
+use crate::config::{DataType, Hasher};
+use crate::frontend::router::{parser::Table, sharding::ShardedTable};
 use crate::net::parameter::ParameterValue;
 use sync::ShardConfig;
 
 static SETUP: &str = include_str!("setup.sql");
 
+/// The column is sharded with the modulo hasher, so its sequence
+/// is split between shards instead of being replaced.
+fn modulo_sequence(sharded_tables: &[ShardedTable], column: &StatsColumn) -> bool {
+    sharded_tables.iter().any(|table| {
+        table.hasher == Hasher::Modulo
+            && table.data_type == DataType::Bigint
+            && table.column == column.column_name
+            && table
+                .name
+                .as_ref()
+                .is_none_or(|name| *name == column.table_name)
+            && table
+                .schema
+                .as_ref()
+                .is_none_or(|schema| *schema == column.table_schema)
+    })
+}
+
 /// Load schema from database.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Hash)]
 pub struct Schema {
@@ -120,7 +140,12 @@ impl Schema {
         Ok(())
     }
 
-    async fn install_server(server: &mut Server) -> Result<(), Error> {
+    async fn install_server(
+        server: &mut Server,
+        shard: usize,
+        shards: usize,
+        sharded_tables: &[ShardedTable],
+    ) -> Result<(), Error> {
         Self::setup(server).await?;
         let schema = Self::load(server).await?;
 
@@ -146,20 +171,34 @@ impl Schema {
         );
 
         for table in tables {
-            for column in table.columns().iter().filter(|column| {
-                column.1.is_primary_key // Only primary keys.
-                    && matches!(column.1.data_type.as_str(), "bigint" | "int8") // Only BIGINT.
-                // Only the ones that rely on a sequence.
-            }) {
-                info!(
-                    "[schema] creating sharded sequence for \"{}\".\"{}\".\"{}\"",
-                    column.1.table_schema, column.1.table_name, column.1.column_name,
-                );
-
-                let query = format!(
-                    "SELECT pgdog.install_sharded_sequence('{}', '{}', '{}')",
-                    column.1.table_schema, column.1.table_name, column.1.column_name,
-                );
+            for column in table.columns().values() {
+                // Columns sharded with the modulo hasher keep their sequence,
+                // other BIGINT primary keys get a sharded one.
+                let query = if modulo_sequence(sharded_tables, column) {
+                    info!(
+                        "[schema] splitting sequence between shards for \"{}\".\"{}\".\"{}\"",
+                        column.table_schema, column.table_name, column.column_name,
+                    );
+
+                    format!(
+                        "SELECT pgdog.install_modulo_sequence('{}', '{}', '{}', {}, {})",
+                        column.table_schema, column.table_name, column.column_name, shards, shard,
+                    )
+                } else if column.is_primary_key
+                    && matches!(column.data_type.as_str(), "bigint" | "int8")
+                {
+                    info!(
+                        "[schema] creating sharded sequence for \"{}\".\"{}\".\"{}\"",
+                        column.table_schema, column.table_name, column.column_name,
+                    );
+
+                    format!(
+                        "SELECT pgdog.install_sharded_sequence('{}', '{}', '{}')",
+                        column.table_schema, column.table_name, column.column_name,
+                    )
+                } else {
+                    continue;
+                };
 
                 server.execute_checked(&query).await?;
             }
@@ -182,9 +221,9 @@ impl Schema {
         // Sync configuration.
         ShardConfig::sync_all(cluster).await?;
 
-        for shard in shards {
-            let mut server = shard.primary(&Request::default()).await?;
-            Self::install_server(&mut server).await?;
+        for (number, shard) in shards.iter().enumerate() {
+            let mut server = shard.primary(&Request::maintenance()).await?;
+            Self::install_server(&mut server, number, shards.len(), sharded_tables).await?;
         }
 
         Ok(())
@@ -379,6 +418,89 @@ mod test {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_install_modulo_sequence() {
+        use crate::config::{DataType, Hasher};
+        use crate::frontend::router::parser::Shard;
+        use crate::frontend::router::sharding::{ContextBuilder, ShardedTable};
+
+        let mut conn = test_server().await;
+
+        conn.execute_checked("DROP SCHEMA IF EXISTS pgdog_modulo_test CASCADE")
+            .await
+            .unwrap();
+        conn.execute_checked("CREATE SCHEMA pgdog_modulo_test")
+            .await
+            .unwrap();
+        conn.execute_checked(
+            "CREATE TABLE pgdog_modulo_test.ids (id BIGSERIAL PRIMARY KEY, value TEXT)",
+        )
+        .await
+        .unwrap();
+        // Rows written before the sequence was split.
+        conn.execute_checked("INSERT INTO pgdog_modulo_test.ids (value) VALUES ('a'), ('b')")
+            .await
+            .unwrap();
+
+        let table = ShardedTable {
+            schema: Some("pgdog_modulo_test".into()),
+            name: Some("ids".into()),
+            column: "id".into(),
+            data_type: DataType::Bigint,
+            hasher: Hasher::Modulo,
+            ..Default::default()
+        };
+
+        // Second shard out of three.
+        Schema::install_server(&mut conn, 1, 3, std::slice::from_ref(&table))
+            .await
+            .unwrap();
+
+        // The sequence is kept as the column default.
+        let default = conn
+            .fetch_all::<String>(
+                "SELECT column_default FROM information_schema.columns \
+                 WHERE table_schema = 'pgdog_modulo_test' \
+                 AND table_name = 'ids' AND column_name = 'id'",
+            )
+            .await
+            .unwrap();
+        assert!(
+            default.first().is_some_and(|d| d.contains("nextval")),
+            "{:?}",
+            default
+        );
+
+        for _ in 0..10 {
+            conn.execute_checked("INSERT INTO pgdog_modulo_test.ids (value) VALUES ('c')")
+                .await
+                .unwrap();
+        }
+
+        let ids = conn
+            .fetch_all::<i64>("SELECT id FROM pgdog_modulo_test.ids WHERE value = 'c' ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 10);
+        assert!(ids[0] > 2, "{:?}", ids);
+
+        // Generated IDs route back to the shard they were generated on.
+        for id in ids {
+            let shard = ContextBuilder::new(&table)
+                .data(id)
+                .shards(3)
+                .build()
+                .unwrap()
+                .apply()
+                .unwrap();
+            assert_eq!(shard, Shard::Direct(1), "id {} routed to {:?}", id, shard);
+        }
+
+        conn.execute_checked("DROP SCHEMA pgdog_modulo_test CASCADE")
+            .await
+            .unwrap();
+    }
+
     #[test]
     fn test_resolve_search_path_default() {
         let schema = Schema::from_parts(vec!["$user".into(), "public".into()], HashMap::new());
@@ -537,7 +659,9 @@ mod test {
             .execute_checked(include_str!("test_schema.sql"))
             .await
             .unwrap();
-        Schema::install_server(&mut server).await.unwrap();
+        Schema::install_server(&mut server, 0, 1, &[])
+            .await
+            .unwrap();
 
         // Verify the partitioned parents had identity dropped and a sharded
         // sequence default installed. Children inherit the default from the
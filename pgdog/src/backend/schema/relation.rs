@@ -65,6 +65,8 @@ impl From<DataRow> for Relation {
                 oid: value.get::<i32>(7, Format::Text).unwrap_or_default(),
                 parent_table_schema: non_empty(value.get_text(8)),
                 parent_table_name: non_empty(value.get_text(9)),
+                partition_bound: non_empty(value.get_text(10)),
+                partition_key: non_empty(value.get_text(11)),
                 columns: IndexMap::new(),
             },
         }
@@ -114,6 +116,8 @@ impl Relation {
             oid: 0,
             parent_table_schema: None,
             parent_table_name: None,
+            partition_bound: None,
+            partition_key: None,
             columns: columns.into_iter().map(|(k, v)| (k, v.into())).collect(),
         }
         .into()
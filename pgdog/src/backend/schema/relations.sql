@@ -21,7 +21,9 @@
         pg_catalog.obj_description(c.oid, 'pg_class')              AS "description",
        c.oid::integer                                             AS "oid",
        parent_ns.nspname                                          AS "parent_table_schema",
-       parent_c.relname                                           AS "parent_table_name"
+       parent_c.relname                                           AS "parent_table_name",
+       pg_catalog.pg_get_expr(c.relpartbound, c.oid)             AS "partition_bound",
+       pg_catalog.pg_get_partkeydef(c.oid)                        AS "partition_key"
 FROM   pg_catalog.pg_class c
        LEFT JOIN pg_catalog.pg_namespace n
               ON n.oid = c.relnamespace
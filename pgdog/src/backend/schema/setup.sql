@@ -384,6 +384,81 @@ BEGIN
 END;
 $body$ LANGUAGE plpgsql;
 
+-- Split the column's sequence between shards: shard N only generates
+-- values where value % shards = N, so IDs never collide between shards
+-- and route back to the shard that generated them with the modulo hasher.
+CREATE OR REPLACE FUNCTION pgdog.install_modulo_sequence(
+    schema_name TEXT,
+    table_name TEXT,
+    column_name TEXT,
+    shards INTEGER,
+    shard INTEGER,
+    lock_timeout TEXT DEFAULT '1s'
+) RETURNS TEXT AS $body$
+DECLARE max_id BIGINT;
+DECLARE current_id BIGINT;
+DECLARE next_id BIGINT;
+DECLARE seq_name TEXT;
+BEGIN
+    IF shards <= shard OR shards < 1 OR shard < 0 THEN
+        RAISE EXCEPTION 'shards=%, shard=% is an invalid sharding configuration', shards, shard;
+    END IF;
+
+    SELECT pg_get_serial_sequence(format('"%s"."%s"', schema_name, table_name), column_name)
+    INTO seq_name;
+
+    IF seq_name IS NULL THEN
+        RAISE EXCEPTION 'column "%" on table "%"."%" is not backed by a sequence', column_name, schema_name, table_name;
+    END IF;
+
+    PERFORM pgdog.check_table(schema_name, table_name, lock_timeout);
+
+    -- Lock table to prevent more writes.
+    EXECUTE format('LOCK TABLE "%s"."%s" IN ACCESS EXCLUSIVE MODE', schema_name, table_name);
+
+    -- Get the max column value.
+    EXECUTE format('SELECT MAX("%s") FROM "%s"."%s"', column_name, schema_name, table_name) INTO max_id;
+
+    -- Get current sequence value.
+    EXECUTE format('SELECT last_value FROM %s', seq_name) INTO current_id;
+
+    -- First value belonging to this shard that hasn't been used yet.
+    SELECT GREATEST(COALESCE(max_id, 0), current_id) + 1 INTO next_id;
+    SELECT next_id + (shard - next_id % shards + shards) % shards INTO next_id;
+
+    IF EXISTS (
+        SELECT 1
+        FROM information_schema.columns c
+        WHERE c.table_schema = install_modulo_sequence.schema_name
+        AND c.table_name = install_modulo_sequence.table_name
+        AND c.column_name = install_modulo_sequence.column_name
+        AND c.is_identity = 'YES'
+    ) THEN
+        EXECUTE format('ALTER TABLE "%s"."%s" ALTER COLUMN "%s" SET INCREMENT BY %s RESTART WITH %s',
+            schema_name,
+            table_name,
+            column_name,
+            shards::text,
+            next_id::text
+        );
+    ELSE
+        EXECUTE format('ALTER SEQUENCE %s INCREMENT BY %s RESTART WITH %s',
+            seq_name,
+            shards::text,
+            next_id::text
+        );
+    END IF;
+
+    RETURN format('%s split between %s shards on table "%s"."%s", next value is %s',
+        seq_name,
+        shards::text,
+        schema_name,
+        table_name,
+        next_id::text
+    );
+END;
+$body$ LANGUAGE plpgsql;
+
 -- Install trigger protecting the sharded column from bad inserts/updates.
 CREATE OR REPLACE FUNCTION pgdog.install_trigger(
     schema_name text,
@@ -24,7 +24,7 @@ use tracing::{info, trace, warn};
 use super::{Error, progress::Progress};
 use crate::{
     backend::{
-        self, Cluster,
+        self, Cluster, ShardedTables,
         pool::Request,
         replication::{publisher::PublicationTable, status::SchemaStatement},
     },
@@ -362,6 +362,45 @@ impl<'a> Deref for Statement<'a> {
     }
 }
 
+/// Foreign key constraint added by `ALTER TABLE ... ADD CONSTRAINT ... FOREIGN KEY`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ForeignKey<'a> {
+    /// Table with the constraint.
+    pub(crate) table: Table<'a>,
+    /// Constrained columns.
+    pub(crate) columns: Vec<&'a str>,
+    /// Referenced table.
+    pub(crate) references: Table<'a>,
+    /// Referenced columns, in the same order as `columns`.
+    pub(crate) referenced_columns: Vec<&'a str>,
+    /// Statement adding the constraint.
+    pub(crate) sql: &'a str,
+}
+
+impl ForeignKey<'_> {
+    /// The referenced rows are always on the same shard as the rows
+    /// referencing them, so the constraint can be enforced by each shard.
+    ///
+    /// That's the case if the referenced table isn't sharded, i.e. it's
+    /// copied to all shards, or both tables are sharded and the foreign key
+    /// maps the sharding key of one to the sharding key of the other.
+    pub(crate) fn is_colocated(&self, tables: &ShardedTables) -> bool {
+        let referenced = tables.sharded_column(self.references.name, &self.referenced_columns);
+
+        if referenced.is_none() && tables.table(self.references.name).is_none() {
+            return true;
+        }
+
+        match (
+            tables.sharded_column(self.table.name, &self.columns),
+            referenced,
+        ) {
+            (Some(column), Some(referenced)) => column.position == referenced.position,
+            _ => false,
+        }
+    }
+}
+
 impl<'a> From<&'a str> for Statement<'a> {
     fn from(value: &'a str) -> Self {
         Self::Other {
@@ -669,6 +708,146 @@ impl PgDumpOutput {
         _ => {}
     }
 
+    /// Get all foreign key constraints.
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn foreign_keys(&self) -> Result<Vec<ForeignKey<'_>>, Error> {
+        let mut result = vec![];
+
+        for stmt in self.stmts.into_iter() {
+            let Node::AlterTableStmt(alter_stmt) = stmt.stmt() else {
+                continue;
+            };
+
+            let Some(fk_table) = alter_stmt.relation() else {
+                continue;
+            };
+
+            let (_, original_start) = self
+                .original
+                .split_at_checked(stmt.stmt_location as usize)
+                .ok_or(Error::StmtOutOfBounds)?;
+            let (original, _) = original_start
+                .split_at_checked(stmt.stmt_len as usize)
+                .ok_or(Error::StmtOutOfBounds)?;
+
+            for cmd in alter_stmt.cmds() {
+                let Node::AlterTableCmd(cmd) = cmd else {
+                    continue;
+                };
+
+                if cmd.subtype != nodes::AlterTableType::AT_AddConstraint {
+                    continue;
+                }
+
+                let Node::Constraint(cons) = cmd.def() else {
+                    continue;
+                };
+
+                if cons.contype != nodes::ConstrType::CONSTR_FOREIGN {
+                    continue;
+                }
+
+                let Some(pk_table) = cons.pktable() else {
+                    continue;
+                };
+
+                result.push(ForeignKey {
+                    table: Table::from(fk_table),
+                    columns: cons
+                        .fk_attrs()
+                        .iter()
+                        .filter_map(|attr| attr.as_str())
+                        .collect(),
+                    references: Table::from(pk_table),
+                    referenced_columns: cons
+                        .pk_attrs()
+                        .iter()
+                        .filter_map(|attr| attr.as_str())
+                        .collect(),
+                    sql: original,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            pub(crate) fn foreign_keys(&self) -> Result<Vec<ForeignKey<'_>>, Error> {
+                let mut result = vec![];
+
+                fn names(attrs: &[Node]) -> Vec<&str> {
+                    attrs
+                        .iter()
+                        .filter_map(|attr| match attr.node {
+                            Some(NodeEnum::String(PgString { ref sval })) => Some(sval.as_str()),
+                            _ => None,
+                        })
+                        .collect()
+                }
+
+                for stmt in &self.stmts.stmts {
+                    let Some(ref node) = stmt.stmt else {
+                        continue;
+                    };
+                    let Some(NodeEnum::AlterTableStmt(ref alter_stmt)) = node.node else {
+                        continue;
+                    };
+
+                    let Some(ref fk_table) = alter_stmt.relation else {
+                        continue;
+                    };
+
+                    let (_, original_start) = self
+                        .original
+                        .split_at_checked(stmt.stmt_location as usize)
+                        .ok_or(Error::StmtOutOfBounds)?;
+                    let (original, _) = original_start
+                        .split_at_checked(stmt.stmt_len as usize)
+                        .ok_or(Error::StmtOutOfBounds)?;
+
+                    for cmd in &alter_stmt.cmds {
+                        let Some(NodeEnum::AlterTableCmd(ref cmd)) = cmd.node else {
+                            continue;
+                        };
+
+                        if cmd.subtype() != AlterTableType::AtAddConstraint {
+                            continue;
+                        }
+
+                        let Some(ref def) = cmd.def else {
+                            continue;
+                        };
+
+                        let Some(NodeEnum::Constraint(ref cons)) = def.node else {
+                            continue;
+                        };
+
+                        if cons.contype() != ConstrType::ConstrForeign {
+                            continue;
+                        }
+
+                        let Some(ref pk_table) = cons.pktable else {
+                            continue;
+                        };
+
+                        result.push(ForeignKey {
+                            table: Table::from(fk_table),
+                            columns: names(&cons.fk_attrs),
+                            references: Table::from(pk_table),
+                            referenced_columns: names(&cons.pk_attrs),
+                            sql: original,
+                        });
+                    }
+                }
+
+                Ok(result)
+            }
+        }
+        _ => {}
+    }
+
     /// Get partitioned parent tables (tables with PARTITION BY).
     #[cfg(feature = "new_parser")]
     fn partitioned_tables(&self) -> HashSet<Table<'_>> {
@@ -1766,6 +1945,36 @@ impl PgDumpOutput {
         _ => {}
     }
 
+    /// Foreign keys that can't be enforced by each shard on its own,
+    /// because the referenced rows could be on a different shard.
+    /// They are skipped when restoring the schema.
+    fn cross_shard_foreign_keys(
+        &self,
+        dest: &Cluster,
+        state: SyncState,
+    ) -> Result<HashSet<String>, Error> {
+        if state != SyncState::PostData || dest.shards().len() < 2 {
+            return Ok(HashSet::new());
+        }
+
+        let tables = dest.sharding_schema().tables;
+
+        Ok(self
+            .foreign_keys()?
+            .into_iter()
+            .filter(|fk| !fk.is_colocated(&tables))
+            .map(|fk| {
+                warn!(
+                    "skipping foreign key from {} to {}, referenced rows could be on another shard [{}]",
+                    fk.table,
+                    fk.references,
+                    dest.name()
+                );
+                fk.sql.to_owned()
+            })
+            .collect())
+    }
+
     /// Create objects in destination cluster.
     pub async fn restore(
         &self,
@@ -1773,7 +1982,12 @@ impl PgDumpOutput {
         ignore_errors: bool,
         state: SyncState,
     ) -> Result<(), Error> {
+        let skip = Arc::new(self.cross_shard_foreign_keys(dest, state)?);
         let stmts = self.statements(state)?;
+        let stmts = stmts
+            .iter()
+            .filter(|stmt| !skip.contains(stmt.sql()))
+            .collect::<Vec<_>>();
         let trackers = Arc::new(Mutex::new(
             (0..dest.shards().len())
                 .map(|shard| {
@@ -1807,9 +2021,14 @@ impl PgDumpOutput {
 
             let trackers = trackers.clone();
             let output = self.clone();
+            let skip = skip.clone();
 
             set.spawn(async move {
-                let stmts = output.statements(state)?;
+                let stmts = output
+                    .statements(state)?
+                    .into_iter()
+                    .filter(|stmt| !skip.contains(stmt.sql()))
+                    .collect::<Vec<_>>();
 
                 let mut progress = Progress::new(stmts.len());
 
@@ -2228,6 +2447,130 @@ ALTER TABLE ONLY parent ATTACH PARTITION parent_2024 FOR VALUES FROM ('2024-01-0
         assert!(post_data.is_empty());
     }
 
+    #[test]
+    fn test_partial_and_expression_indexes() {
+        let output = parse(
+            r#"
+CREATE TABLE public.users (id BIGINT, email TEXT, deleted_at TIMESTAMPTZ);
+CREATE INDEX users_active_email_idx ON public.users USING btree (email) WHERE (deleted_at IS NULL);
+CREATE UNIQUE INDEX users_lower_email_idx ON public.users USING btree (lower(email));"#,
+        );
+
+        let statements = output.statements(SyncState::PostData).unwrap();
+        assert_eq!(statements.len(), 4);
+
+        assert_eq!(
+            statements[0].deref(),
+            "DROP INDEX IF EXISTS \"public\".\"users_active_email_idx\""
+        );
+        let partial = statements[1].deref();
+        assert!(partial.contains("IF NOT EXISTS users_active_email_idx"));
+        assert!(
+            partial.contains("WHERE deleted_at IS NULL"),
+            "predicate missing: {}",
+            partial
+        );
+
+        let expression = statements[3].deref();
+        assert!(expression.contains("CREATE UNIQUE INDEX IF NOT EXISTS users_lower_email_idx"));
+        assert!(
+            expression.contains("(lower(email))"),
+            "expression missing: {}",
+            expression
+        );
+    }
+
+    #[test]
+    fn test_check_constraints_and_defaults() {
+        let output = parse(
+            r#"
+CREATE TABLE public.orders (
+    id uuid DEFAULT gen_random_uuid() NOT NULL,
+    price numeric CONSTRAINT orders_price_check CHECK ((price > (0)::numeric)),
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    shipped_at timestamp with time zone,
+    CONSTRAINT orders_shipped_check CHECK ((shipped_at >= created_at))
+);
+ALTER TABLE public.orders ADD CONSTRAINT orders_id_check CHECK ((id IS NOT NULL)) NOT VALID;"#,
+        );
+
+        let statements = output.statements(SyncState::PreData).unwrap();
+        assert_eq!(statements.len(), 1);
+
+        let create = statements[0].deref();
+        for expected in [
+            "DEFAULT gen_random_uuid()",
+            "DEFAULT now()",
+            "CONSTRAINT orders_price_check CHECK",
+            "CONSTRAINT orders_shipped_check CHECK (shipped_at >= created_at)",
+        ] {
+            assert!(
+                create.contains(expected),
+                "{} missing: {}",
+                expected,
+                create
+            );
+        }
+
+        // Constraints added separately are created after the data is copied.
+        let statements = output.statements(SyncState::PostData).unwrap();
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].contains("CHECK ((id IS NOT NULL)) NOT VALID"));
+    }
+
+    #[test]
+    fn test_foreign_keys_colocated() {
+        use crate::frontend::router::sharding::ShardedTable;
+
+        let output = parse(
+            r#"
+CREATE TABLE public.comments (tenant_id BIGINT, id BIGINT, parent_id BIGINT, author_id BIGINT);
+CREATE TABLE public.authors (id BIGINT, country_id BIGINT);
+CREATE TABLE public.countries (id BIGINT);
+ALTER TABLE ONLY public.comments ADD CONSTRAINT comments_parent_fkey FOREIGN KEY (tenant_id, parent_id) REFERENCES public.comments(tenant_id, id);
+ALTER TABLE ONLY public.comments ADD CONSTRAINT comments_author_fkey FOREIGN KEY (author_id) REFERENCES public.authors(id);
+ALTER TABLE ONLY public.authors ADD CONSTRAINT authors_country_fkey FOREIGN KEY (country_id) REFERENCES public.countries(id);"#,
+        );
+
+        let tables = ShardedTables::from(
+            &[
+                ShardedTable {
+                    name: Some("comments".into()),
+                    column: "tenant_id".into(),
+                    ..Default::default()
+                },
+                ShardedTable {
+                    name: Some("authors".into()),
+                    column: "id".into(),
+                    ..Default::default()
+                },
+            ][..],
+        );
+
+        let fks = output.foreign_keys().unwrap();
+        assert_eq!(fks.len(), 3);
+
+        // Self-referential, both sides use the sharding key.
+        assert_eq!(fks[0].table.name, "comments");
+        assert_eq!(fks[0].columns, vec!["tenant_id", "parent_id"]);
+        assert_eq!(fks[0].referenced_columns, vec!["tenant_id", "id"]);
+        assert!(fks[0].is_colocated(&tables));
+
+        // Comments are sharded by tenant, authors by id.
+        assert_eq!(fks[1].references.name, "authors");
+        assert!(!fks[1].is_colocated(&tables));
+
+        // Countries aren't sharded, so they are on every shard.
+        assert_eq!(fks[2].references.name, "countries");
+        assert!(fks[2].is_colocated(&tables));
+
+        // Statements are restored as-is.
+        let statements = output.statements(SyncState::PostData).unwrap();
+        for fk in &fks {
+            assert!(statements.iter().any(|stmt| stmt.sql() == fk.sql));
+        }
+    }
+
     #[test]
     fn test_create_publication_restored() {
         let output = parse("CREATE PUBLICATION my_pub FOR TABLE users, orders;");
@@ -3,7 +3,6 @@
 use std::{ops::Deref, time::Duration};
 
 use bytes::{BufMut, BytesMut};
-use rustls_pki_types::ServerName;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpStream,
@@ -37,7 +36,7 @@ use crate::{
         CommandComplete, Stream,
         messages::{DataRow, NoticeResponse},
         parameter::Parameters,
-        tls::connector_with_verify_mode,
+        tls,
     },
 };
 use crate::{net::tweak, state::State};
@@ -55,6 +54,7 @@ pub struct Server {
     stats: Stats,
     prepared_statements: PreparedStatements,
     dirty: bool,
+    role_changed: bool,
     streaming: bool,
     schema_changed: bool,
     sync_prepared: bool,
@@ -185,16 +185,15 @@ impl Server {
             if ssl == SslReply::Yes {
                 debug!("server supports TLS, initiating TLS handshake [{}]", addr);
 
-                let connector = connector_with_verify_mode(
+                let plain = stream.take()?;
+                let handshake = tls::connect(
+                    plain,
+                    &addr.host,
                     tls_mode,
                     config.config.general.tls_server_ca_certificate.as_ref(),
                 )?;
-                let plain = stream.take()?;
 
-                let server_name = ServerName::try_from(addr.host.clone())?;
-                debug!("connecting with TLS to server name: {:?}", server_name);
-
-                match connector.connect(server_name.clone(), plain).await {
+                match handshake.await {
                     Ok(tls_stream) => {
                         debug!("TLS handshake successful with {}", addr.host);
                         let cipher = tokio_rustls::TlsStream::Client(tls_stream);
@@ -271,6 +270,9 @@ impl Server {
                                 md5::Client::new_salt(user, &[auth_secret.to_string()], &salt)?;
                             stream.send_flush(&client.response()?).await?;
                         }
+                        Authentication::Gssapi
+                        | Authentication::GssapiContinue(_)
+                        | Authentication::Sspi => return Err(Error::UnsupportedAuth),
                     }
                 }
 
@@ -341,6 +343,7 @@ impl Server {
             client_params: Parameters::default(),
             prepared_statements: PreparedStatements::new(),
             dirty: false,
+            role_changed: false,
             streaming: false,
             schema_changed: false,
             sync_prepared: false,
@@ -537,7 +540,7 @@ impl Server {
             }
             'E' => {
                 let error = ErrorResponse::from_bytes(message.to_bytes())?;
-                self.schema_changed = error.code == "0A000";
+                self.schema_changed = error.code == "0A000" || error.is_schema_changed();
                 self.stats.error();
 
                 // Non-recoverable, Postgres is about to close the connection,
@@ -566,6 +569,7 @@ impl Server {
                     "DISCARD ALL" => {
                         self.prepared_statements.clear();
                         self.client_params.clear();
+                        self.dirty = false; // Nothing left to clean up.
                     }
                     "RESET" => self.client_params.clear(), // Someone reset params, we're gonna need to re-sync.
                     _ => (),
@@ -864,6 +868,17 @@ impl Server {
         Ok(())
     }
 
+    /// Send an empty query to keep an idle connection from being dropped
+    /// by the network. This doesn't count as using the connection.
+    pub async fn ping(&mut self) -> Result<(), Error> {
+        let last_used = self.stats.last_used();
+
+        self.execute_checked(";").await?;
+        self.stats.ping(last_used);
+
+        Ok(())
+    }
+
     /// Attempt to rollback the transaction on this server, if any has been started.
     pub(super) async fn rollback(&mut self) -> Result<(), Error> {
         if self.in_transaction() {
@@ -1087,6 +1102,18 @@ impl Server {
         }
     }
 
+    /// How long since anything was sent to the server: a query, a healthcheck or a ping.
+    #[inline]
+    pub fn ping_age(&self, instant: Instant) -> Duration {
+        let stats = self.stats();
+        let last_active = [stats.last_healthcheck(), stats.last_ping()]
+            .into_iter()
+            .flatten()
+            .fold(stats.last_used(), Instant::max);
+
+        instant.saturating_duration_since(last_active)
+    }
+
     /// Get server address.
     #[inline]
     pub fn addr(&self) -> &Address {
@@ -1110,10 +1137,23 @@ impl Server {
         self.dirty = dirty;
     }
 
+    /// Client changed the role with `SET ROLE` directly on this server.
+    /// `RESET ALL` doesn't reset it, so it needs its own cleanup.
+    #[inline]
+    pub fn role_changed(&self) -> bool {
+        self.role_changed
+    }
+
+    #[inline]
+    pub fn mark_role_changed(&mut self, changed: bool) {
+        self.role_changed = changed;
+    }
+
     /// Server has been cleaned.
     #[inline]
     pub(super) fn cleaned(&mut self) {
         self.dirty = false;
+        self.role_changed = false;
         self.stats.cleaned();
     }
 
@@ -1248,6 +1288,7 @@ pub mod test {
                 prepared_statements: super::PreparedStatements::new(),
                 addr,
                 dirty: false,
+                role_changed: false,
                 streaming: false,
                 schema_changed: false,
                 sync_prepared: false,
@@ -3089,6 +3130,62 @@ pub mod test {
         server.execute("ROLLBACK").await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_copy_out_memory_bounded() {
+        let mut server = test_server().await;
+
+        // ~10MB export, one row per CopyData message.
+        server
+            .send(
+                &vec![
+                    Query::from(
+                        "COPY (SELECT repeat('x', 100) FROM generate_series(1, 100000)) TO STDOUT",
+                    )
+                    .into(),
+                ]
+                .into(),
+            )
+            .await
+            .unwrap();
+
+        let msg = server.read().await.unwrap();
+        assert_eq!(msg.code(), 'H', "Expected CopyOutResponse");
+
+        let baseline = server.memory_usage();
+        let mut max_memory = baseline;
+        let mut rows = 0;
+        let mut bytes = 0;
+
+        loop {
+            let msg = server.read().await.unwrap();
+            match msg.code() {
+                'd' => {
+                    rows += 1;
+                    bytes += msg.len();
+                    max_memory = max_memory.max(server.memory_usage());
+                }
+                'c' => break,
+                code => panic!("Unexpected message code: {}", code),
+            }
+        }
+
+        assert_eq!(rows, 100_000);
+        assert!(bytes > 10_000_000);
+
+        // Messages are forwarded as they arrive, so the read buffer
+        // is reused instead of growing with the export.
+        assert!(
+            max_memory < baseline + 64 * 1024,
+            "memory grew during COPY: baseline={}, max={}",
+            baseline,
+            max_memory
+        );
+
+        assert_eq!(server.read().await.unwrap().code(), 'C');
+        assert_eq!(server.read().await.unwrap().code(), 'Z');
+        assert!(server.done());
+    }
+
     #[tokio::test]
     async fn test_extended_execute_flush_not_done_without_sync() {
         let mut server = test_server().await;
@@ -0,0 +1,585 @@
+//! Shards discovered with DNS SRV records.
+//!
+//! Databases with `srv` enabled use `host` as the name of an SRV record, e.g.
+//! `_postgres._tcp.shards.svc`, and get one shard for each of its targets. The shard
+//! number is the ordinal at the end of the target's first label, e.g. `postgres-2.shards.svc`
+//! is shard 2, like pods of a Kubernetes StatefulSet. It doesn't depend on what other
+//! targets are in the record, so a missing target doesn't renumber the others.
+//!
+//! Shards are created once the record resolves to a target for each of the `srv_shards`
+//! shards of the database. Until then, the record is resolved again every second and the
+//! database doesn't exist. Records are then resolved again every `srv_refresh_interval`,
+//! but only update the host and port of existing shards: shards are never added, removed
+//! or renumbered, since that would move data between them. If the hosts change, databases
+//! are reloaded, keeping existing connections.
+//!
+//! Records of databases added with a config reload are resolved by the same task.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hickory_resolver::{Resolver, name_server::TokioConnectionProvider};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use tokio::time::{Instant, sleep};
+use tracing::{error, info, warn};
+
+use super::{Error, databases::reload_from_existing};
+use crate::config::{Config, ConfigAndUsers, Database, config};
+use crate::tasks;
+
+/// Resolved targets for each SRV record, by shard number.
+static TARGETS: Lazy<RwLock<HashMap<String, BTreeMap<usize, SrvTarget>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Target of an SRV record.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SrvTarget {
+    pub priority: u16,
+    pub weight: u16,
+    pub host: String,
+    pub port: u16,
+}
+
+impl SrvTarget {
+    /// Shard number from the ordinal at the end of the first label
+    /// of the hostname, e.g. `2` for `postgres-2.shards.svc`.
+    pub fn shard(&self) -> Option<usize> {
+        let label = self.host.split('.').next()?;
+        let digits = label.len() - label.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+
+        label[label.len() - digits..].parse().ok()
+    }
+}
+
+/// Looks up SRV records.
+#[async_trait]
+pub trait SrvResolver: Sync {
+    async fn lookup(&self, name: &str) -> Result<Vec<SrvTarget>, Error>;
+}
+
+/// SRV resolver using the system DNS configuration.
+pub struct DnsSrvResolver {
+    resolver: Resolver<TokioConnectionProvider>,
+}
+
+impl DnsSrvResolver {
+    /// Create resolver with the system config (e.g., /etc/resolv.conf on Unix).
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            resolver: Resolver::builder(TokioConnectionProvider::default())?.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl SrvResolver for DnsSrvResolver {
+    async fn lookup(&self, name: &str) -> Result<Vec<SrvTarget>, Error> {
+        let response = self.resolver.srv_lookup(name).await?;
+
+        Ok(response
+            .iter()
+            .map(|srv| SrvTarget {
+                priority: srv.priority(),
+                weight: srv.weight(),
+                host: srv.target().to_utf8().trim_end_matches('.').to_string(),
+                port: srv.port(),
+            })
+            .collect())
+    }
+}
+
+/// SRV records used by databases in the config and the number of shards
+/// each one resolves to.
+fn records(config: &Config) -> Vec<(&str, usize)> {
+    let mut records = config
+        .databases
+        .iter()
+        .filter(|database| database.srv)
+        .filter_map(|database| match database.srv_shards {
+            Some(shards) => Some((database.host.as_str(), shards)),
+            None => {
+                warn!(
+                    "database \"{}\" uses srv record \"{}\" without srv_shards, skipping",
+                    database.name, database.host
+                );
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    records.sort();
+    records.dedup();
+
+    records
+}
+
+/// Check if any SRV record used by databases in the config wasn't resolved yet.
+fn unresolved(config: &Config) -> bool {
+    let targets = TARGETS.read();
+
+    config.databases.iter().any(|database| {
+        database.srv && database.srv_shards.is_some() && !targets.contains_key(&database.host)
+    })
+}
+
+/// Resolve SRV records used by databases in the config.
+///
+/// Returns true if targets of any record changed. If a lookup fails,
+/// the targets resolved previously are kept.
+pub async fn refresh(config: &Config, resolver: &impl SrvResolver) -> bool {
+    let mut changed = false;
+
+    for (name, expected) in records(config) {
+        let mut targets = match resolver.lookup(name).await {
+            Ok(targets) => targets,
+            Err(err) => {
+                warn!("srv lookup for \"{}\" failed: {}", name, err);
+                continue;
+            }
+        };
+
+        // Prefer targets with higher priority if more than one has the same ordinal.
+        targets.sort();
+        targets.dedup_by(|a, b| a.host == b.host && a.port == b.port);
+
+        let mut shards = BTreeMap::new();
+        for target in targets {
+            let Some(shard) = target.shard() else {
+                warn!(
+                    "srv record \"{}\" target \"{}\" has no ordinal to use as the shard number, skipping",
+                    name, target.host
+                );
+                continue;
+            };
+
+            if shard >= expected {
+                warn!(
+                    "srv record \"{}\" target \"{}\" is for shard {}, but there are only {} shards, skipping",
+                    name, target.host, shard, expected
+                );
+                continue;
+            }
+
+            if let Some(existing) = shards.get(&shard) {
+                warn!(
+                    "srv record \"{}\" targets \"{}\" and \"{}\" are both shard {}, using \"{}\"",
+                    name, existing.host, target.host, shard, existing.host
+                );
+                continue;
+            }
+
+            shards.insert(shard, target);
+        }
+
+        let mut guard = TARGETS.write();
+
+        match guard.get_mut(name) {
+            Some(existing) => changed |= update(name, existing, shards),
+
+            None => {
+                let missing = (0..expected)
+                    .filter(|shard| !shards.contains_key(shard))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    warn!(
+                        "srv record \"{}\" has no targets for shards {:?}, waiting for all {} shards",
+                        name, missing, expected
+                    );
+                    continue;
+                }
+
+                info!(
+                    "srv record \"{}\" resolved to {} shards: {}",
+                    name,
+                    shards.len(),
+                    shards
+                        .iter()
+                        .map(|(shard, target)| format!("{}={}:{}", shard, target.host, target.port))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                guard.insert(name.to_owned(), shards);
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+/// Update hosts and ports of shards resolved before. Shards
+/// are never added or removed, so the data in them stays put.
+///
+/// Returns true if any shard changed.
+fn update(
+    name: &str,
+    existing: &mut BTreeMap<usize, SrvTarget>,
+    mut resolved: BTreeMap<usize, SrvTarget>,
+) -> bool {
+    let mut changed = false;
+
+    for (shard, current) in existing.iter_mut() {
+        match resolved.remove(shard) {
+            Some(target) => {
+                if target.host != current.host || target.port != current.port {
+                    info!(
+                        "srv record \"{}\" moved shard {} from {}:{} to {}:{}",
+                        name, shard, current.host, current.port, target.host, target.port
+                    );
+                    *current = target;
+                    changed = true;
+                }
+            }
+
+            None => warn!(
+                "srv record \"{}\" has no target for shard {}, keeping {}:{}",
+                name, shard, current.host, current.port
+            ),
+        }
+    }
+
+    for (shard, target) in resolved {
+        warn!(
+            "srv record \"{}\" target \"{}\" is for shard {}, which didn't exist when it was first resolved, skipping",
+            name, target.host, shard
+        );
+    }
+
+    changed
+}
+
+/// Replace databases using SRV records with one database
+/// for each shard, using the targets resolved last.
+pub fn expand(config: &ConfigAndUsers) -> Cow<'_, ConfigAndUsers> {
+    if !config.config.databases.iter().any(|database| database.srv) {
+        return Cow::Borrowed(config);
+    }
+
+    let targets = TARGETS.read();
+    let mut expanded = config.clone();
+
+    expanded.config.databases = config
+        .config
+        .databases
+        .iter()
+        .flat_map(|database| {
+            if !database.srv {
+                return vec![database.clone()];
+            }
+
+            let Some(expected) = database.srv_shards else {
+                return vec![];
+            };
+
+            let Some(targets) = targets.get(&database.host) else {
+                warn!(
+                    "srv record \"{}\" for database \"{}\" isn't resolved, skipping",
+                    database.host, database.name
+                );
+                return vec![];
+            };
+
+            if (0..expected).any(|shard| !targets.contains_key(&shard)) {
+                warn!(
+                    "srv record \"{}\" for database \"{}\" doesn't have all {} shards, skipping",
+                    database.host, database.name, expected
+                );
+                return vec![];
+            }
+
+            targets
+                .range(..expected)
+                .map(|(shard, target)| Database {
+                    host: target.host.clone(),
+                    port: target.port,
+                    shard: *shard,
+                    srv: false,
+                    ..database.clone()
+                })
+                .collect()
+        })
+        .collect();
+
+    Cow::Owned(expanded)
+}
+
+/// Resolve SRV records for the first time and keep them up to date.
+///
+/// Records used by databases added with a config reload, or not
+/// resolved to all their shards yet, are resolved every second.
+pub async fn init() -> Result<(), Error> {
+    let resolver = match DnsSrvResolver::new() {
+        Ok(resolver) => resolver,
+        // Only needed if databases use SRV records.
+        Err(err)
+            if config()
+                .config
+                .databases
+                .iter()
+                .all(|database| !database.srv) =>
+        {
+            warn!("srv discovery is disabled: {}", err);
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    refresh(&config().config, &resolver).await;
+
+    tasks::spawn("srv discovery", async move {
+        let mut refreshed = Instant::now();
+
+        loop {
+            sleep(Duration::from_secs(1)).await;
+
+            let config = config();
+            let interval = config.config.general.srv_refresh_interval();
+
+            // Refresh can be disabled and enabled again with a config reload.
+            let due = !interval.is_zero() && refreshed.elapsed() >= interval;
+            if !due && !unresolved(&config.config) {
+                continue;
+            }
+
+            if due {
+                refreshed = Instant::now();
+            }
+
+            if refresh(&config.config, &resolver).await {
+                info!("srv targets changed, reloading databases");
+
+                if let Err(err) = reload_from_existing() {
+                    error!("reloading databases after srv change failed: {}", err);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::databases::from_config;
+    use crate::config::{Role, User};
+
+    struct MockResolver {
+        targets: Vec<SrvTarget>,
+    }
+
+    #[async_trait]
+    impl SrvResolver for MockResolver {
+        async fn lookup(&self, name: &str) -> Result<Vec<SrvTarget>, Error> {
+            if name == "_postgres._tcp.missing.svc" {
+                Err(Error::DnsResolutionFailed(name.into()))
+            } else {
+                Ok(self.targets.clone())
+            }
+        }
+    }
+
+    fn target(priority: u16, host: &str, port: u16) -> SrvTarget {
+        SrvTarget {
+            priority,
+            weight: 0,
+            host: host.into(),
+            port,
+        }
+    }
+
+    fn config(srv: &str) -> ConfigAndUsers {
+        let mut config = ConfigAndUsers::default();
+        config.config.databases.push(Database {
+            name: "app".into(),
+            host: srv.into(),
+            role: Role::Primary,
+            srv: true,
+            srv_shards: Some(3),
+            ..Default::default()
+        });
+        config.users.users.push(User {
+            name: "alice".into(),
+            database: "app".into(),
+            password: Some("secret".into()),
+            ..Default::default()
+        });
+        config
+    }
+
+    #[test]
+    fn test_srv_target_shard() {
+        assert_eq!(target(0, "postgres-2.shards.svc", 5432).shard(), Some(2));
+        assert_eq!(target(0, "shard10", 5432).shard(), Some(10));
+        assert_eq!(target(0, "postgres.shard-1.svc", 5432).shard(), None);
+        assert_eq!(target(0, "", 5432).shard(), None);
+    }
+
+    #[tokio::test]
+    async fn test_srv_shards() {
+        let config = config("_postgres._tcp.test_srv_shards.svc");
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-2.shards.svc", 5432),
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-1.shards.svc", 5433),
+                target(0, "shard-0.shards.svc", 5432),
+                target(10, "shard-1-backup.shards.svc", 5432),
+                target(0, "primary.shards.svc", 5432),
+            ],
+        };
+
+        assert!(refresh(&config.config, &resolver).await);
+        // Nothing changed.
+        assert!(!refresh(&config.config, &resolver).await);
+
+        let expanded = expand(&config);
+        let hosts = expanded
+            .config
+            .databases
+            .iter()
+            .map(|database| (database.shard, database.host.as_str(), database.port))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hosts,
+            vec![
+                (0, "shard-0.shards.svc", 5432),
+                (1, "shard-1.shards.svc", 5433),
+                (2, "shard-2.shards.svc", 5432),
+            ]
+        );
+
+        let databases = from_config(&config);
+        let cluster = databases.cluster(("alice", "app")).unwrap();
+        assert_eq!(cluster.shards().len(), 3);
+        assert_eq!(
+            cluster.shards()[1].pools()[0].addr().host,
+            "shard-1.shards.svc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_srv_refresh_keeps_shards() {
+        let config = config("_postgres._tcp.test_srv_refresh_keeps_shards.svc");
+        let hosts = || {
+            expand(&config)
+                .config
+                .databases
+                .iter()
+                .map(|database| (database.shard, database.host.clone(), database.port))
+                .collect::<Vec<_>>()
+        };
+
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-1.shards.svc", 5432),
+                target(0, "shard-2.shards.svc", 5432),
+            ],
+        };
+        assert!(refresh(&config.config, &resolver).await);
+        let before = hosts();
+
+        // Shard 1 is restarting: the other shards keep their numbers
+        // and shard 1 keeps its last known host.
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-2.shards.svc", 5432),
+            ],
+        };
+        assert!(!refresh(&config.config, &resolver).await);
+        assert_eq!(hosts(), before);
+
+        // Shards are never added.
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-1.shards.svc", 5432),
+                target(0, "shard-2.shards.svc", 5432),
+                target(0, "shard-3.shards.svc", 5432),
+            ],
+        };
+        assert!(!refresh(&config.config, &resolver).await);
+        assert_eq!(hosts(), before);
+
+        // Existing shards can move.
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-1.shards.svc", 5433),
+                target(0, "shard-2.shards.svc", 5432),
+            ],
+        };
+        assert!(refresh(&config.config, &resolver).await);
+        assert_eq!(
+            hosts(),
+            vec![
+                (0, "shard-0.shards.svc".to_string(), 5432),
+                (1, "shard-1.shards.svc".to_string(), 5433),
+                (2, "shard-2.shards.svc".to_string(), 5432),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_srv_lookup_failure() {
+        let config = config("_postgres._tcp.missing.svc");
+        let resolver = MockResolver { targets: vec![] };
+
+        assert!(!refresh(&config.config, &resolver).await);
+
+        // No shards until the record is resolved.
+        assert!(expand(&config).config.databases.is_empty());
+        assert!(from_config(&config).cluster(("alice", "app")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_srv_waits_for_all_shards() {
+        let config = config("_postgres._tcp.test_srv_waits_for_all_shards.svc");
+        assert!(unresolved(&config.config));
+
+        // Shard 1 isn't up yet.
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-2.shards.svc", 5432),
+                target(0, "shard-3.shards.svc", 5432),
+            ],
+        };
+        assert!(!refresh(&config.config, &resolver).await);
+        assert!(unresolved(&config.config));
+        assert!(expand(&config).config.databases.is_empty());
+        assert!(from_config(&config).cluster(("alice", "app")).is_err());
+
+        // All shards are up. Targets past the last shard are ignored.
+        let resolver = MockResolver {
+            targets: vec![
+                target(0, "shard-0.shards.svc", 5432),
+                target(0, "shard-1.shards.svc", 5432),
+                target(0, "shard-2.shards.svc", 5432),
+                target(0, "shard-3.shards.svc", 5432),
+            ],
+        };
+        assert!(refresh(&config.config, &resolver).await);
+        assert!(!unresolved(&config.config));
+        let databases = from_config(&config);
+        let cluster = databases.cluster(("alice", "app")).unwrap();
+        assert_eq!(cluster.shards().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_srv_shards_required() {
+        let mut config = config("_postgres._tcp.test_srv_shards_required.svc");
+        config.config.databases[0].srv_shards = None;
+        let resolver = MockResolver {
+            targets: vec![target(0, "shard-0.shards.svc", 5432)],
+        };
+
+        assert!(!refresh(&config.config, &resolver).await);
+        assert!(!unresolved(&config.config));
+        assert!(expand(&config).config.databases.is_empty());
+    }
+}
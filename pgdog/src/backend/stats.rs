@@ -48,6 +48,7 @@ pub struct ServerStats {
     pub id: BackendPid,
     pub last_used: Instant,
     pub last_healthcheck: Option<Instant>,
+    pub last_ping: Option<Instant>,
     pub created_at: Instant,
     pub client_id: Option<FrontendPid>,
     query_timer: Option<Instant>,
@@ -69,6 +70,7 @@ impl ServerStats {
             id,
             last_used: now,
             last_healthcheck: None,
+            last_ping: None,
             created_at: now,
             client_id: None,
             query_timer: None,
@@ -284,6 +286,14 @@ impl Stats {
         self.sync_to_shared();
     }
 
+    /// Track idle pings. Pings don't count as using
+    /// the connection, so `last_used` is restored.
+    pub fn ping(&mut self, last_used: Instant) {
+        self.local.last_used = last_used;
+        self.local.last_ping = Some(Instant::now());
+        self.sync_to_shared();
+    }
+
     #[inline]
     pub fn memory_used(&mut self, stats: MemoryStats) {
         self.local.memory = *stats;
@@ -340,6 +350,12 @@ impl Stats {
         self.local.last_healthcheck
     }
 
+    /// Get last_ping timestamp (local, no lock).
+    #[inline]
+    pub fn last_ping(&self) -> Option<Instant> {
+        self.local.last_ping
+    }
+
     /// Get pool_id (local, no lock).
     #[inline]
     pub fn pool_id(&self) -> u64 {
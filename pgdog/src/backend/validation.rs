@@ -1,13 +1,14 @@
-//! Config-time validation for sharded table mappings.
+//! Validation for sharded table mappings and partitions.
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BTreeMap};
 
 use derive_more::Display;
 use pgdog_config::{
     DataType, FlexibleType, FlexibleTypeRef, ShardedMappingConfig, ShardedMappingRange,
 };
 
-use crate::frontend::router::sharding::mapping::compare_flexible_type;
+use crate::backend::Schema;
+use crate::frontend::router::sharding::{ShardedTable, mapping::compare_flexible_type};
 
 /// A single validation problem detected in a sharded table mapping configuration.
 #[derive(Debug, Display)]
@@ -43,6 +44,16 @@ pub enum ValidationError {
         value: FlexibleType,
         data_type: DataType,
     },
+
+    /// Hash partitions of a sharded table have a modulus different from the shard count.
+    #[display(
+        "sharded table \"{table}\" is hash partitioned with modulus {modulus}, but there are {num_shards} shards"
+    )]
+    PartitionModulus {
+        table: String,
+        modulus: usize,
+        num_shards: usize,
+    },
 }
 
 /// Collect all validation errors for a mapping configuration.
@@ -65,6 +76,70 @@ pub fn validate(
     errors
 }
 
+/// Check that hash partitions of sharded tables have one partition per shard.
+///
+/// Tables sharded with native partitioning route a row to the partition
+/// `hash % modulus`, so a modulus different from the shard count
+/// puts rows on the wrong shard. Only tables hash partitioned by
+/// the sharding column are checked, and each is reported once.
+///
+/// Partitions are read from the schema, so this is only checked
+/// if `load_schema` isn't `off`.
+pub fn check_partition_modulus(
+    schema: &Schema,
+    tables: &[ShardedTable],
+    num_shards: usize,
+) -> Vec<ValidationError> {
+    // Largest modulus that doesn't match, by table.
+    let mut mismatched: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+
+    for partition in schema.tables().values().flat_map(|tables| tables.values()) {
+        let (Some(parent_schema), Some(parent_name), Some(modulus)) = (
+            partition.parent_table_schema.as_deref(),
+            partition.parent_table_name.as_deref(),
+            partition.hash_partition_modulus(),
+        ) else {
+            continue;
+        };
+
+        if modulus == num_shards {
+            continue;
+        }
+
+        let Some(partition_column) = schema
+            .get(parent_schema, parent_name)
+            .and_then(|parent| parent.hash_partition_column())
+        else {
+            continue;
+        };
+
+        let sharded = tables.iter().any(|table| {
+            table.name.as_deref().is_none_or(|name| name == parent_name)
+                && table
+                    .schema
+                    .as_deref()
+                    .is_none_or(|schema| schema == parent_schema)
+                && table.column == partition_column
+        });
+
+        if sharded {
+            let max_modulus = mismatched.entry((parent_schema, parent_name)).or_default();
+            *max_modulus = modulus.max(*max_modulus);
+        }
+    }
+
+    mismatched
+        .into_iter()
+        .map(
+            |((schema, name), modulus)| ValidationError::PartitionModulus {
+                table: format!("{}.{}", schema, name),
+                modulus,
+                num_shards,
+            },
+        )
+        .collect()
+}
+
 /// Check that the shard number in `config` is within `[0, num_shards)`.
 pub fn check_shard_range(
     config: &ShardedMappingConfig,
@@ -432,4 +507,116 @@ mod tests {
             );
         }
     }
+
+    mod check_partition_modulus {
+        use std::collections::HashMap;
+
+        use indexmap::IndexMap;
+
+        use super::*;
+        use crate::backend::schema::{Relation, columns::StatsColumn as Column};
+
+        fn orders(modulus: usize, partition_key: &str) -> Schema {
+            let mut columns = IndexMap::new();
+            columns.insert(
+                "customer_id".to_string(),
+                Column {
+                    table_catalog: "catalog".into(),
+                    table_schema: "public".into(),
+                    table_name: "orders".into(),
+                    column_name: "customer_id".into(),
+                    column_default: String::new(),
+                    is_nullable: false,
+                    data_type: "bigint".into(),
+                    ordinal_position: 1,
+                    is_primary_key: false,
+                    foreign_keys: Vec::new(),
+                }
+                .into(),
+            );
+
+            let mut parent = Relation::test_table("public", "orders", columns.clone());
+            parent.partition_key = Some(partition_key.into());
+            let mut relations =
+                HashMap::from([(("public".to_string(), "orders".to_string()), parent)]);
+
+            for remainder in 0..modulus {
+                let name = format!("orders_{}", remainder);
+                let mut partition = Relation::test_table("public", &name, columns.clone());
+                partition.parent_table_schema = Some("public".into());
+                partition.parent_table_name = Some("orders".into());
+                partition.partition_bound = Some(format!(
+                    "FOR VALUES WITH (modulus {}, remainder {})",
+                    modulus, remainder
+                ));
+                relations.insert(("public".into(), name), partition);
+            }
+
+            Schema::from_parts(vec!["public".into()], relations)
+        }
+
+        fn sharded(name: Option<&str>, column: &str) -> Vec<ShardedTable> {
+            vec![ShardedTable {
+                name: name.map(String::from),
+                column: column.into(),
+                ..Default::default()
+            }]
+        }
+
+        #[test]
+        fn modulus_matches_shards() {
+            let tables = sharded(Some("orders"), "customer_id");
+            assert!(
+                check_partition_modulus(&orders(3, "HASH (customer_id)"), &tables, 3).is_empty()
+            );
+        }
+
+        #[test]
+        fn modulus_disagrees_with_shards() {
+            let tables = sharded(Some("orders"), "customer_id");
+            let errors = check_partition_modulus(&orders(4, "HASH (customer_id)"), &tables, 3);
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0].to_string(),
+                "sharded table \"public.orders\" is hash partitioned with modulus 4, but there are 3 shards"
+            );
+
+            // Sharded by column only.
+            let tables = sharded(None, "customer_id");
+            assert_eq!(
+                check_partition_modulus(&orders(4, "HASH (customer_id)"), &tables, 3).len(),
+                1
+            );
+        }
+
+        #[test]
+        fn not_sharded() {
+            for tables in [
+                sharded(Some("users"), "customer_id"),
+                sharded(None, "id"),
+                vec![],
+            ] {
+                assert!(
+                    check_partition_modulus(&orders(4, "HASH (customer_id)"), &tables, 3)
+                        .is_empty()
+                );
+            }
+        }
+
+        #[test]
+        fn partitioned_by_other_column() {
+            let tables = sharded(Some("orders"), "customer_id");
+            for partition_key in ["HASH (created_at)", "HASH (customer_id, created_at)"] {
+                assert!(check_partition_modulus(&orders(4, partition_key), &tables, 3).is_empty());
+            }
+
+            // Operator class and quoting don't change the column.
+            for partition_key in ["HASH (customer_id int8_ops)", "HASH (\"customer_id\")"] {
+                assert_eq!(
+                    check_partition_modulus(&orders(4, partition_key), &tables, 3).len(),
+                    1
+                );
+            }
+        }
+    }
 }
@@ -24,8 +24,11 @@ pub use networking::{MultiTenant, Tcp, TlsVerifyMode};
 pub use overrides::Overrides;
 use pgdog_config::ShardedTableConfig;
 pub use pgdog_config::auth::{AuthType, PassthroughAuth};
-pub use pgdog_config::{LoadBalancingStrategy, ReadWriteSplit, ReadWriteStrategy};
-pub use pooling::{ConnectionRecovery, PoolerMode, PreparedStatements};
+pub use pgdog_config::{LoadBalancingStrategy, ReadWriteSplit, ReadWriteStrategy, Workload};
+pub use pooling::{
+    CheckoutFairness, ConnectionRecovery, PoolerMode, PreparedStatements,
+    PreparedStatementsSchemaChange, TemporaryTables,
+};
 pub use rewrite::{Rewrite, RewriteMode};
 use std::path::Path;
 pub use users::{Admin, Plugin, ServerAuth, User, Users};
@@ -1 +1,6 @@
-pub use pgdog_config::{PoolerMode, PreparedStatements, pooling::ConnectionRecovery};
+pub use pgdog_config::{
+    PoolerMode, PreparedStatements,
+    pooling::{
+        CheckoutFairness, ConnectionRecovery, PreparedStatementsSchemaChange, TemporaryTables,
+    },
+};
@@ -14,7 +14,7 @@ use tracing::{Level as LogLevel, debug, enabled, error, info, trace, warn};
 
 use super::{ClientRequest, Error, PreparedStatements};
 use crate::auth::AuthResult;
-use crate::auth::{md5, scram::Server};
+use crate::auth::{cert, gssapi, md5, scram::Server};
 use crate::backend::maintenance_mode;
 use crate::backend::pool::stats::MemoryStats;
 use crate::backend::{
@@ -23,11 +23,11 @@ use crate::backend::{
 };
 use crate::config::convert::user_from_params;
 use crate::config::{self, AuthType, ConfigAndUsers, config};
-use crate::frontend::ClientComms;
 use crate::frontend::client::query_engine::{QueryEngine, QueryEngineContext};
+use crate::frontend::{ClientComms, comms::comms};
 use crate::net::messages::{
-    Authentication, BackendKeyData, ErrorResponse, FromBytes, FrontendPid, Message, Password,
-    Protocol, ProtocolVersion, ReadyForQuery, ToBytes,
+    Authentication, BackendKeyData, ErrorResponse, FromBytes, FrontendPid, Message,
+    ParameterStatus, Password, Protocol, ProtocolVersion, ReadyForQuery, ToBytes,
 };
 use crate::net::{MessageBuffer, ProtocolMessage, Stream, parameter::Parameters};
 use crate::state::State;
@@ -73,6 +73,8 @@ pub struct Client {
     prepared_statements: PreparedStatements,
     // Client transaction state.
     transaction: Option<TransactionType>,
+    // When the current transaction started.
+    transaction_start: Option<Instant>,
     // Current timeouts to use for client/server communication.
     // These change based on client state, e.g. if client is running query,
     // the `query_timeout` is active, and if the client is idle, the `client_idle_timeout` is.
@@ -110,12 +112,18 @@ impl Client {
     ///   for more modern clients.
     ///
     pub async fn spawn(
-        stream: Stream,
+        mut stream: Stream,
         params: Parameters,
         addr: SocketAddr,
         config: Arc<ConfigAndUsers>,
         protocol_version: ProtocolVersion,
     ) -> Result<(), Error> {
+        if comms().quarantined(addr.ip()) {
+            warn!("client quarantined, disconnecting [{}]", addr);
+            stream.fatal(ErrorResponse::client_quarantined()).await?;
+            return Ok(());
+        }
+
         let login_timeout = Duration::from_millis(config.config.general.client_login_timeout);
 
         match safe_timeout(
@@ -156,11 +164,22 @@ impl Client {
         auth_type: &AuthType,
         passwords: &[PasswordKind],
     ) -> Result<AuthResult, Error> {
-        if passwords.is_empty() {
-            return Ok(AuthResult::NoPasswordConfig);
-        }
-
         let result = match auth_type {
+            // Kerberos vouches for the client, no passwords needed.
+            AuthType::Gssapi => match gssapi::acceptor() {
+                Ok(acceptor) => {
+                    gssapi::Server::new(acceptor)
+                        .handle(stream, user, &config().config.general.gssapi_realms)
+                        .await?
+                }
+                Err(err) => {
+                    error!("{}", err);
+                    AuthResult::NoGssapi
+                }
+            },
+
+            _ if passwords.is_empty() => AuthResult::NoPasswordConfig,
+
             AuthType::Md5 => {
                 let md5 = md5::Client::new(
                     user,
@@ -219,7 +238,7 @@ impl Client {
     /// Create new frontend client from the given TCP stream.
     async fn login(
         mut stream: Stream,
-        params: Parameters,
+        mut params: Parameters,
         addr: SocketAddr,
         config: Arc<ConfigAndUsers>,
         protocol_version: ProtocolVersion,
@@ -230,6 +249,19 @@ impl Client {
             return Ok(None);
         }
 
+        // Settings passed with `-c`, e.g. `search_path`, are used for routing
+        // and synchronized with servers like any other parameter.
+        params.apply_options();
+
+        // Don't forward parameters the server isn't allowed to see.
+        let general = &config.config.general;
+        for name in params.remove_tracked(|name| general.parameter_allowed(name)) {
+            warn!(
+                r#"removed startup parameter "{}", it's not allowed [{}]"#,
+                name, addr
+            );
+        }
+
         let (user, database) = user_database_from_params(&params);
         let admin = database == config.config.admin.name && config.config.admin.user == user;
         let admin_password = &config.config.admin.password;
@@ -274,11 +306,7 @@ impl Client {
                     if let Some(identity) = cluster.identity() {
                         // mTLS authentication: the client certificate identity
                         // must match the configured user identity.
-                        if stream.tls_identity() == Some(identity) {
-                            AuthResult::Ok
-                        } else {
-                            AuthResult::NoIdentity
-                        }
+                        cert::authenticate(stream.tls_identity(), identity)
                     } else {
                         // Resolve Vault static role
                         // entries to plaintext before the auth exchange
@@ -327,7 +355,7 @@ impl Client {
 
         // Get connection parameters. These will be most likely cached,
         // unless the pool was just created.
-        let server_params = match conn.parameters(&Request::unrouted(id)).await {
+        let mut server_params = match conn.parameters(&Request::unrouted(id)).await {
             Ok(params) => params,
             Err(err) => {
                 if err.no_server() {
@@ -345,6 +373,10 @@ impl Client {
             }
         };
 
+        if let Some(ref version) = config.config.general.server_version {
+            ParameterStatus::override_server_version(&mut server_params, version);
+        }
+
         for param in server_params {
             stream.send(&param).await?;
         }
@@ -354,17 +386,18 @@ impl Client {
         comms.connect(key.clone(), addr, &params);
 
         if config.config.general.log_connections {
+            let auth = if passthrough {
+                "passthrough".into()
+            } else {
+                auth_type.to_string()
+            };
             info!(
-                r#"client "{}" connected to database "{}" [{}, auth: {}] {}"#,
-                user,
-                database,
-                addr,
-                if passthrough {
-                    "passthrough".into()
-                } else {
-                    auth_type.to_string()
-                },
-                if stream.is_tls() { "🔒" } else { "" }
+                client_addr = %addr,
+                database = %database,
+                user = %user,
+                auth = %auth,
+                tls = stream.is_tls(),
+                "client connected"
             );
         }
 
@@ -383,6 +416,7 @@ impl Client {
             params: params.clone(),
             prepared_statements: PreparedStatements::new(),
             transaction: None,
+            transaction_start: None,
             timeouts: Timeouts::from_config(&config.config.general),
             client_request: ClientRequest::default(),
             stream_buffer: MessageBuffer::new(
@@ -409,6 +443,13 @@ impl Client {
         let key = BackendKeyData::new_frontend(ProtocolVersion::V3_0, id);
         let mut prepared_statements = PreparedStatements::new();
         prepared_statements.level = config().config.general.prepared_statements;
+        prepared_statements.set_limit(
+            config().config.general.prepared_statements_client_limit,
+            config()
+                .config
+                .general
+                .prepared_statements_client_limit_action,
+        );
 
         Self {
             stream,
@@ -419,6 +460,7 @@ impl Client {
             prepared_statements,
             admin: false,
             transaction: None,
+            transaction_start: None,
             timeouts: Timeouts::from_config(&config().config.general),
             client_request: ClientRequest::default(),
             stream_buffer: MessageBuffer::new(
@@ -440,8 +482,10 @@ impl Client {
                 if config().config.general.log_disconnections {
                     let (user, database) = user_database_from_params(&self.params);
                     info!(
-                        r#"client "{}" disconnected from database "{}" [{}]"#,
-                        user, database, self.addr
+                        client_addr = %self.addr,
+                        database = %database,
+                        user = %user,
+                        "client disconnected"
                     )
                 }
             }
@@ -450,17 +494,38 @@ impl Client {
                     .stream
                     .fatal(ErrorResponse::from_client_err(&err))
                     .await;
+                if err.protocol_violation() {
+                    self.protocol_violation(&err);
+                }
                 if config().config.general.log_disconnections {
                     let (user, database) = user_database_from_params(&self.params);
                     error!(
-                        r#"client "{}" disconnected from database "{}" with error [{}]: {}"#,
-                        user, database, self.addr, err
+                        client_addr = %self.addr,
+                        database = %database,
+                        user = %user,
+                        error = %err,
+                        "client disconnected with error"
                     )
                 }
             }
         }
     }
 
+    /// Count the error towards quarantining the client's address.
+    fn protocol_violation(&self, err: &impl std::fmt::Display) {
+        let general = &config().config.general;
+        if self.comms.client_error(
+            self.addr.ip(),
+            general.client_quarantine_errors,
+            Duration::from_millis(general.client_quarantine_duration),
+        ) {
+            warn!(
+                "client quarantined for {}ms: {} [{}]",
+                general.client_quarantine_duration, err, self.addr
+            );
+        }
+    }
+
     /// Run the client.
     async fn run(&mut self) -> Result<(), Error> {
         let shutdown = self.comms.shutting_down();
@@ -524,7 +589,7 @@ impl Client {
         query_engine
             .process_server_message(&mut context, message)
             .await?;
-        self.transaction = context.transaction();
+        self.set_transaction(context.transaction());
 
         Ok(())
     }
@@ -547,16 +612,16 @@ impl Client {
         if spliced.is_empty() {
             let mut context = QueryEngineContext::new(self);
             query_engine.handle(&mut context).await?;
-            self.transaction = context.transaction();
+            self.set_transaction(context.transaction());
         } else {
             let total = spliced.len();
             let mut reqs = spliced.into_iter().enumerate();
-            self.transaction.get_or_insert(TransactionType::Implicit);
+            self.set_transaction(Some(self.transaction.unwrap_or(TransactionType::Implicit)));
             while let Some((num, mut req)) = reqs.next() {
                 debug!("processing spliced request {}/{}", num + 1, total);
                 let mut context = QueryEngineContext::new(self).spliced(&mut req, reqs.len());
                 query_engine.handle(&mut context).await?;
-                self.transaction = context.transaction();
+                self.set_transaction(context.transaction());
 
                 // If pipeline is aborted due to error, skip to Sync to complete the pipeline.
                 // Postgres ignores all commands after an error until it receives Sync.
@@ -567,7 +632,7 @@ impl Client {
                             debug!("processing Sync to complete aborted pipeline");
                             let mut ctx = QueryEngineContext::new(self).spliced(&mut next_req, 0);
                             query_engine.handle(&mut ctx).await?;
-                            self.transaction = ctx.transaction();
+                            self.set_transaction(ctx.transaction());
                             break;
                         }
                     }
@@ -596,6 +661,13 @@ impl Client {
         let config = config::config();
         // Configure prepared statements cache.
         self.prepared_statements.level = config.prepared_statements();
+        self.prepared_statements.set_limit(
+            config.config.general.prepared_statements_client_limit,
+            config
+                .config
+                .general
+                .prepared_statements_client_limit_action,
+        );
         self.timeouts = Timeouts::from_config(&config.config.general);
         self.query_log_stdout = config.config.general.query_log_stdout;
         self.query_size_limit = config.config.general.query_size_limit;
@@ -606,24 +678,33 @@ impl Client {
             let idle_timeout = self
                 .timeouts
                 .client_idle_timeout(&state, &self.client_request);
+            let transaction_timeout = self.timeouts.transaction_timeout(self.transaction_start);
+
+            let message = match safe_timeout(
+                idle_timeout.min(transaction_timeout),
+                self.stream_buffer.read(&mut self.stream),
+            )
+            .await
+            {
+                Err(_) => {
+                    let error = if transaction_timeout < idle_timeout {
+                        ErrorResponse::transaction_timeout(self.timeouts.max_transaction_duration)
+                    } else {
+                        ErrorResponse::client_idle_timeout(idle_timeout, &state)
+                    };
+                    self.stream.fatal(error).await?;
+                    return Ok(BufferEvent::DisconnectAbrupt);
+                }
 
-            let message =
-                match safe_timeout(idle_timeout, self.stream_buffer.read(&mut self.stream)).await {
-                    Err(_) => {
-                        self.stream
-                            .fatal(ErrorResponse::client_idle_timeout(idle_timeout, &state))
-                            .await?;
-                        return Ok(BufferEvent::DisconnectAbrupt);
-                    }
-
-                    Ok(Ok(message)) => message.stream(self.streaming).frontend(),
-                    Ok(Err(err)) => {
-                        if let Some(response) = err.as_fatal_error_response() {
-                            self.stream.fatal(response).await?;
-                        }
-                        return Ok(BufferEvent::DisconnectAbrupt);
+                Ok(Ok(message)) => message.stream(self.streaming).frontend(),
+                Ok(Err(err)) => {
+                    if let Some(response) = err.as_fatal_error_response() {
+                        self.stream.fatal(response).await?;
+                        self.protocol_violation(&err);
                     }
-                };
+                    return Ok(BufferEvent::DisconnectAbrupt);
+                }
+            };
 
             if timer.is_none() {
                 timer = Some(Instant::now());
@@ -663,6 +744,13 @@ impl Client {
         self.transaction.is_some()
     }
 
+    /// Update transaction state, keeping track of when the transaction started.
+    fn set_transaction(&mut self, transaction: Option<TransactionType>) {
+        self.transaction_start =
+            transaction.map(|_| self.transaction_start.unwrap_or_else(Instant::now));
+        self.transaction = transaction;
+    }
+
     /// Get client memory stats.
     pub fn memory_stats(&self) -> MemoryStats {
         MemoryStats {
@@ -0,0 +1,217 @@
+//! Audit log of routing decisions.
+//!
+//! Each routed query is recorded as one JSON object per line,
+//! with the user, database, normalized query, shard(s) and whether
+//! it was sent to a primary or a replica. Records are written by a separate
+//! thread, so slow disks don't hold up clients. If the thread falls too far behind,
+//! new records are dropped and counted instead of queued.
+
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::thread;
+
+use chrono::{SecondsFormat, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tracing::{error, warn};
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::config::config;
+use crate::frontend::router::parser::Cache;
+use crate::util::user_database_from_params;
+
+/// Send records to syslog instead of a file.
+const SYSLOG: &str = "syslog";
+
+/// Socket of the local syslog daemon.
+#[cfg(unix)]
+const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// Facility `local0`, severity `info`.
+#[cfg(unix)]
+const SYSLOG_PRIORITY: u8 = 16 * 8 + 6;
+
+/// Maximum number of records waiting to be written.
+const QUEUE_SIZE: usize = 16_384;
+
+/// Records waiting to be written, with their destination.
+static WRITER: Lazy<Queue> = Lazy::new(|| {
+    let (queue, rx) = Queue::new(QUEUE_SIZE);
+    thread::Builder::new()
+        .name("pgdog-audit-log".into())
+        .spawn(move || writer(rx))
+        .expect("audit log thread");
+    queue
+});
+
+/// Number of records dropped because the writer fell behind.
+pub fn dropped() -> u64 {
+    WRITER.dropped()
+}
+
+/// Bounded queue of records for the writer thread.
+pub(super) struct Queue {
+    tx: SyncSender<(PathBuf, String)>,
+    dropped: AtomicU64,
+}
+
+impl Queue {
+    pub(super) fn new(size: usize) -> (Self, Receiver<(PathBuf, String)>) {
+        let (tx, rx) = sync_channel(size);
+        (
+            Self {
+                tx,
+                dropped: AtomicU64::new(0),
+            },
+            rx,
+        )
+    }
+
+    /// Queue a record without waiting for the writer. The record is dropped
+    /// if the queue is full.
+    pub(super) fn send(&self, path: PathBuf, line: String) {
+        match self.tx.try_send((path, line)) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+
+                // Don't flood the log while the writer is behind.
+                if dropped.is_power_of_two() {
+                    warn!("audit log is falling behind, {} records dropped", dropped);
+                }
+            }
+            // The writer thread never exits.
+            Err(TrySendError::Disconnected(_)) => (),
+        }
+    }
+
+    pub(super) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Routing decision for one query.
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    timestamp: String,
+    user: &'a str,
+    database: &'a str,
+    query: &'a str,
+    shard: String,
+    route: &'static str,
+}
+
+impl QueryEngine {
+    /// Record where the request was routed, if `audit_log` is configured.
+    pub(super) fn audit_log(&self, context: &QueryEngineContext<'_>) {
+        let Some(path) = config().config.general.audit_log.clone() else {
+            return;
+        };
+
+        let Some(route) = context.client_request.route.as_ref() else {
+            return;
+        };
+
+        let Ok(Some(query)) = context.client_request.query() else {
+            return;
+        };
+
+        // Normalized once for each cached query, so it's not parsed again.
+        let normalized = match context
+            .client_request
+            .ast
+            .as_ref()
+            .and_then(|ast| ast.normalized())
+        {
+            Some(normalized) => Cow::Borrowed(normalized),
+            None => Cow::Owned(
+                Cache::normalize(query.query()).unwrap_or_else(|_| "<unparsable>".into()),
+            ),
+        };
+        let (user, database) = user_database_from_params(context.params);
+
+        let record = AuditRecord {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            user,
+            database,
+            query: normalized.trim(),
+            shard: route.shard().to_string(),
+            route: if route.is_read() { "read" } else { "write" },
+        };
+
+        match serde_json::to_string(&record) {
+            Ok(line) => WRITER.send(path, line),
+            Err(err) => error!("audit log record: {}", err),
+        }
+    }
+}
+
+/// Where records are written.
+enum Output {
+    File(File),
+    #[cfg(unix)]
+    Syslog(UnixDatagram),
+}
+
+impl Output {
+    fn open(path: &Path) -> io::Result<Self> {
+        if path == Path::new(SYSLOG) {
+            #[cfg(unix)]
+            {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(SYSLOG_SOCKET)?;
+                return Ok(Self::Syslog(socket));
+            }
+
+            #[cfg(not(unix))]
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "syslog is only supported on unix",
+            ));
+        }
+
+        Ok(Self::File(
+            OpenOptions::new().append(true).create(true).open(path)?,
+        ))
+    }
+
+    fn write(&mut self, line: &str) -> io::Result<()> {
+        match self {
+            Self::File(file) => file.write_all(format!("{}\n", line).as_bytes()),
+            #[cfg(unix)]
+            Self::Syslog(socket) => socket
+                .send(format!("<{}>pgdog: {}", SYSLOG_PRIORITY, line).as_bytes())
+                .map(|_| ()),
+        }
+    }
+}
+
+fn writer(rx: Receiver<(PathBuf, String)>) {
+    let mut output: Option<(PathBuf, Output)> = None;
+
+    while let Ok((path, line)) = rx.recv() {
+        // Destination changed after a config reload, or the last write failed.
+        if output.as_ref().is_none_or(|(current, _)| current != &path) {
+            output = match Output::open(&path) {
+                Ok(opened) => Some((path.clone(), opened)),
+                Err(err) => {
+                    error!("audit log \"{}\": {}", path.display(), err);
+                    continue;
+                }
+            };
+        }
+
+        if let Some((_, out)) = output.as_mut()
+            && let Err(err) = out.write(&line)
+        {
+            error!("audit log \"{}\": {}", path.display(), err);
+            output = None;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Share results of identical reads executed
+//! concurrently by different clients.
+
+use crate::{
+    backend::pool::shard::coalesce::Coalesced,
+    net::{Message, ToBytes},
+};
+
+use super::*;
+
+impl QueryEngine {
+    /// The request is a complete read that can be answered
+    /// with the result of an identical one.
+    fn can_coalesce(&self, context: &QueryEngineContext<'_>) -> bool {
+        config().config.general.coalesce_reads
+            && self.can_retry_read(context)
+            && self.pending_explain.is_none()
+            && self.checksum.is_none()
+            && context.client_request.is_executable()
+            && matches!(
+                context.client_request.messages.last().map(|m| m.code()),
+                Some('Q' | 'S')
+            )
+    }
+
+    /// Identifies the read: client settings, the query and its parameters.
+    fn coalesce_key(context: &QueryEngineContext<'_>) -> Vec<u8> {
+        let mut key = context.params.tracked().to_string().into_bytes();
+        key.push(0);
+
+        for message in &context.client_request.messages {
+            key.extend_from_slice(&message.to_bytes());
+        }
+
+        key
+    }
+
+    /// Wait for an identical read running on the same shard and return
+    /// its result to the client. If there isn't one, this client
+    /// executes the read for everyone else.
+    ///
+    /// Returns true if the client received the result of another read.
+    pub(super) async fn coalesce(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+    ) -> Result<bool, Error> {
+        if !self.can_coalesce(context) {
+            return Ok(false);
+        }
+
+        let Shard::Direct(shard) = context.client_request.route().shard() else {
+            return Ok(false);
+        };
+
+        let cluster = self.backend.cluster().ok();
+        let coalesced = match cluster.and_then(|cluster| cluster.shards().get(*shard)) {
+            Some(shard) => shard.coalesce().join(Self::coalesce_key(context)),
+            None => return Ok(false),
+        };
+
+        let follower = match coalesced {
+            Coalesced::Leader(leader) => {
+                self.coalesce = Some(leader);
+                return Ok(false);
+            }
+            Coalesced::Follower(follower) => follower,
+        };
+
+        // The other client didn't finish the read,
+        // so we need to execute it ourselves.
+        let Some(response) = follower.wait().await else {
+            return Ok(false);
+        };
+
+        debug!("read coalesced [{:?}]", context.stream.peer_addr());
+
+        self.stats.query();
+        self.stats.idle(false);
+        self.stats.transaction(false);
+
+        let sent = context.stream.send_many(response.as_slice()).await?;
+        self.stats.sent(sent);
+
+        for message in response.iter() {
+            self.hooks.on_server_message(context, message)?;
+        }
+
+        self.router.reset();
+
+        Ok(true)
+    }
+
+    /// Record a message sent to the client, if other clients
+    /// are waiting for the result of the same read.
+    pub(super) fn coalesce_message(&mut self, context: &QueryEngineContext<'_>, message: &Message) {
+        if let Some(leader) = self.coalesce.as_mut() {
+            // Errors could be specific to this client, e.g. the query
+            // was cancelled, and streams and large results are not buffered.
+            if message.code() == 'E' || message.streaming() || !leader.push(message) {
+                self.coalesce = None;
+            }
+        }
+
+        if message.code() == 'Z'
+            && let Some(leader) = self.coalesce.take()
+            && !context.in_transaction()
+        {
+            leader.finish();
+        }
+    }
+}
@@ -29,7 +29,8 @@ impl QueryEngine {
 
         let connect_route = connect_route.unwrap_or(context.client_request.route());
 
-        let request = Request::new(context.id, connect_route.is_read());
+        let mut request = Request::new(context.id, connect_route.is_read());
+        request.analytics = connect_route.is_analytics();
 
         self.stats.waiting(request.created_at);
         self.comms.update_stats(self.stats);
@@ -53,6 +54,21 @@ impl QueryEngine {
                 )
                 .await??;
 
+                // Read-only transactions across shards see the same snapshot.
+                if begin_stmt.is_some()
+                    && config().config.general.cross_shard_snapshot
+                    && context
+                        .transaction()
+                        .map(|t| t.read_only())
+                        .unwrap_or_default()
+                {
+                    safe_timeout(query_timeout, self.backend.synchronize_snapshot()).await??;
+                }
+
+                if let Some(notice) = self.explain_primary_fallback(connect_route) {
+                    self.routing_notice(context, notice).await?;
+                }
+
                 true
             }
 
@@ -66,6 +82,7 @@ impl QueryEngine {
 
                 if err.no_server() && can_recover {
                     error!("{} [{:?}]", err, context.stream.peer_addr());
+                    self.record_error(err.category());
 
                     let error = ErrorResponse::from_err(&err);
 
@@ -79,6 +96,7 @@ impl QueryEngine {
                     self.stats.sent(bytes_sent);
                     self.backend.disconnect();
                     self.router.reset();
+                    self.transaction_shard = None;
                 } else {
                     return Err(err.into());
                 }
@@ -128,7 +146,8 @@ impl QueryEngine {
                 Route::write(ShardWithPriority::new_override_transaction(Shard::Direct(
                     0,
                 )))
-                .with_read(route.is_read()),
+                .with_read(route.is_read())
+                .with_primary(route.is_primary()),
             )
         } else if route.is_search_path_driven()
             || route.shard_with_priority().source() == &ShardSource::Set
@@ -139,7 +158,8 @@ impl QueryEngine {
         } else {
             Ok(
                 Route::write(ShardWithPriority::new_override_transaction(Shard::All))
-                    .with_read(route.is_read()),
+                    .with_read(route.is_read())
+                    .with_primary(route.is_primary()),
             )
         }
     }
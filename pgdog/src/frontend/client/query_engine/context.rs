@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use crate::{
     backend::pool::{connection::mirror::Mirror, stats::MemoryStats},
     frontend::{
@@ -25,6 +27,8 @@ pub struct QueryEngineContext<'a> {
     pub(super) stream: &'a mut Stream,
     /// Client in transaction?
     pub(super) transaction: Option<TransactionType>,
+    /// When the transaction started.
+    pub(super) transaction_start: Option<Instant>,
     /// Timeouts
     pub(super) timeouts: Timeouts,
     /// Cross shard  queries are disabled.
@@ -56,6 +60,7 @@ impl<'a> QueryEngineContext<'a> {
             client_request: &mut client.client_request,
             stream: &mut client.stream,
             transaction: client.transaction,
+            transaction_start: client.transaction_start,
             timeouts: client.timeouts,
             cross_shard_disabled: None,
             memory_stats,
@@ -84,6 +89,7 @@ impl<'a> QueryEngineContext<'a> {
             client_request: buffer,
             stream: &mut mirror.stream,
             transaction: mirror.transaction,
+            transaction_start: None,
             timeouts: mirror.timeouts,
             cross_shard_disabled: None,
             memory_stats: MemoryStats::default(),
@@ -0,0 +1,73 @@
+//! Keep transactions on one shard, if `cross_shard_transactions_disabled` is set.
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::frontend::{
+    Error,
+    router::parser::{Shard, route::ShardSource},
+};
+use crate::net::ErrorResponse;
+
+impl QueryEngine {
+    /// Check the query goes to the same shard as the rest of the transaction.
+    /// The first query that picks a shard using a table or a hint
+    /// decides which shard the transaction is using.
+    ///
+    /// Returns false if the query was rejected.
+    pub(super) async fn cross_shard_transaction_check(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+    ) -> Result<bool, Error> {
+        if context.admin || !context.in_transaction() || !context.client_request.is_executable() {
+            return Ok(true);
+        }
+
+        let disabled = self
+            .backend
+            .cluster()
+            .map(|cluster| {
+                cluster.cross_shard_transactions_disabled() && cluster.shards().len() > 1
+            })
+            .unwrap_or_default();
+
+        if !disabled {
+            return Ok(true);
+        }
+
+        let route = context.client_request.route();
+
+        // Queries that don't touch tables, e.g. SELECT 1, and reads
+        // from omnisharded tables can run on any shard.
+        if !matches!(
+            route.shard_with_priority().source(),
+            ShardSource::Table(_) | ShardSource::Comment | ShardSource::Plugin
+        ) {
+            return Ok(true);
+        }
+
+        let shard = route.shard().clone();
+
+        match (&shard, self.transaction_shard) {
+            (Shard::Direct(shard), None) => {
+                self.transaction_shard = Some(*shard);
+                Ok(true)
+            }
+
+            (Shard::Direct(shard), Some(transaction_shard)) if *shard == transaction_shard => {
+                Ok(true)
+            }
+
+            _ => {
+                let query = context.client_request.query()?;
+                let error = ErrorResponse::cross_shard_transaction(
+                    self.transaction_shard,
+                    &shard,
+                    query.as_ref().map(|query| query.query()),
+                );
+
+                self.error_response(context, error).await?;
+
+                Ok(false)
+            }
+        }
+    }
+}
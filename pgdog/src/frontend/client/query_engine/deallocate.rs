@@ -1,22 +1,30 @@
-use crate::net::{CommandComplete, Protocol, ReadyForQuery};
-
 use super::*;
 
 impl QueryEngine {
+    /// Handle `DEALLOCATE` command.
+    ///
+    /// Server connections are shared, so statements are only removed
+    /// from the client's cache and the global cache usage counts.
+    /// Nothing is sent to the server.
     pub(super) async fn deallocate(
         &mut self,
         context: &mut QueryEngineContext<'_>,
+        name: Option<&str>,
     ) -> Result<(), Error> {
-        let bytes_sent = context
-            .stream
-            .send_many(&[
-                CommandComplete::from_str("DEALLOCATE").message()?,
-                ReadyForQuery::in_transaction(context.in_transaction()).message()?,
-            ])
-            .await?;
+        if context.client_request.is_executable() {
+            match name {
+                Some(name) => context.prepared_statements.close(name),
+                None => context.prepared_statements.close_all(),
+            }
+        }
 
-        self.stats.sent(bytes_sent);
+        let tag = if name.is_some() {
+            "DEALLOCATE"
+        } else {
+            "DEALLOCATE ALL"
+        };
 
-        Ok(())
+        self.fake_command_response(context, tag, None::<Option<_>>)
+            .await
     }
 }
@@ -1,19 +1,46 @@
-use crate::net::{CommandComplete, Protocol, ReadyForQuery};
+use crate::net::{CommandComplete, ErrorResponse, Protocol, ReadyForQuery};
 
 use super::*;
 
 impl QueryEngine {
-    /// Ignore DISCARD command.
+    /// Handle DISCARD command.
+    ///
+    /// `DISCARD ALL` resets the parameters and prepared statements we track
+    /// for the client. The command is forwarded to the server if we're connected
+    /// to one, otherwise there is no server state to discard.
     pub(super) async fn discard(
         &mut self,
         context: &mut QueryEngineContext<'_>,
         extended: bool,
+        all: bool,
     ) -> Result<(), Error> {
         let _extended = extended;
+
+        if all {
+            if context.in_transaction() {
+                // Postgres doesn't allow this in a transaction. If we're connected,
+                // let the server return the error and abort the transaction.
+                if !self.backend.connected() {
+                    self.error_response(context, ErrorResponse::discard_all_in_transaction())
+                        .await?;
+                    return Ok(());
+                }
+            } else {
+                context.params.reset_all();
+                context.prepared_statements.close_all();
+            }
+        }
+
+        if self.backend.connected() {
+            self.execute(context).await?;
+            return Ok(());
+        }
+
+        let tag = if all { "DISCARD ALL" } else { "DISCARD" };
         let bytes_sent = context
             .stream
             .send_many(&[
-                CommandComplete::new("DISCARD").message()?,
+                CommandComplete::new(tag).message()?,
                 ReadyForQuery::in_transaction(context.in_transaction()).message()?,
             ])
             .await?;
@@ -1,5 +1,9 @@
+use tracing::warn;
+
 use crate::net::{CommandComplete, NoticeResponse, Protocol, ReadyForQuery};
 
+use super::two_pc::TwoPcTransaction;
+
 use super::*;
 
 impl QueryEngine {
@@ -31,6 +35,7 @@ impl QueryEngine {
 
         self.stats.sent(bytes_sent);
         self.begin_stmt = None;
+        self.transaction_shard = None;
         context.transaction = None; // Clear transaction state
 
         if rollback {
@@ -75,7 +80,11 @@ impl QueryEngine {
             && context.transaction().map(|t| t.write()).unwrap_or(false);
 
         if two_pc {
-            self.end_two_pc(false).await?;
+            let prepare_error = match self.end_two_pc(false).await {
+                Ok(()) => None,
+                Err(Error::TwoPcPrepare(err)) => Some(err),
+                Err(err) => return Err(err),
+            };
 
             // Update stats.
             self.stats.query();
@@ -84,8 +93,17 @@ impl QueryEngine {
             // Disconnect from servers.
             self.cleanup_backend(context)?;
 
-            // Tell client we finished the transaction.
-            self.end_not_connected(context, false, extended).await?;
+            if let Some(err) = prepare_error {
+                // Like Postgres, a failed COMMIT ends the transaction.
+                self.begin_stmt = None;
+                self.transaction_shard = None;
+                context.transaction = None;
+                self.notify_buffer.clear();
+                self.error_response(context, *err).await?;
+            } else {
+                // Tell client we finished the transaction.
+                self.end_not_connected(context, false, extended).await?;
+            }
         } else {
             if rollback {
                 self.notify_buffer.clear();
@@ -110,7 +128,9 @@ impl QueryEngine {
 
         // If interrupted here, the transaction must be rolled back.
         let _guard_phase_1 = self.two_pc.phase_one(&identifier).await?;
-        self.backend.two_pc(transaction, TwoPcPhase::Phase1).await?;
+        if let Err(err) = self.backend.two_pc(transaction, TwoPcPhase::Phase1).await {
+            return Err(self.abort_two_pc(transaction, err).await);
+        }
 
         debug!("[2pc] phase 1 complete");
 
@@ -125,6 +145,36 @@ impl QueryEngine {
 
         Ok(())
     }
+
+    /// Phase one failed on at least one shard.
+    ///
+    /// Shards that failed to prepare have already aborted their transaction,
+    /// so roll back the ones that succeeded. If that fails too, the manager
+    /// retries the rollback in the background.
+    async fn abort_two_pc(
+        &mut self,
+        transaction: TwoPcTransaction,
+        err: crate::backend::Error,
+    ) -> Error {
+        warn!("[2pc] phase 1 failed, rolling back: {}", err);
+
+        match self.backend.two_pc(transaction, TwoPcPhase::Rollback).await {
+            Ok(()) => {
+                if let Err(err) = self.two_pc.done().await {
+                    return err;
+                }
+            }
+            Err(rollback_err) => {
+                warn!("[2pc] rollback failed: {}", rollback_err);
+                self.two_pc.reset();
+            }
+        }
+
+        match err {
+            crate::backend::Error::ExecutionError(err) => Error::TwoPcPrepare(err),
+            err => err.into(),
+        }
+    }
 }
 
 #[cfg(test)]
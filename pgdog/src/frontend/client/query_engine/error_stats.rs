@@ -0,0 +1,14 @@
+//! Count errors returned to the client for `SHOW ERRORS`.
+
+use super::QueryEngine;
+use crate::stats::errors::{ErrorCategory, errors};
+
+impl QueryEngine {
+    /// Count the error towards the user and database the client is connected to.
+    pub(super) fn record_error(&self, category: ErrorCategory) {
+        if let Ok(cluster) = self.backend.cluster() {
+            let identifier = cluster.identifier();
+            errors().record(&identifier.user, &identifier.database, category);
+        }
+    }
+}
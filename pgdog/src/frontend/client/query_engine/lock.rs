@@ -4,9 +4,9 @@ impl QueryEngine {
     /// Check if we need to lock the backend to this client, and do so
     /// if needed.
     pub(super) fn check_lock(&mut self) {
-        // The presence of advisory locks or manual pin
+        // The presence of advisory locks, temporary tables or manual pin
         // indicates we cannot release the backend.
-        let locked = self.advisory_locks.locked() || self.manual_lock;
+        let locked = self.advisory_locks.locked() || self.manual_lock || self.temporary_table;
 
         self.backend.lock(locked);
         self.stats.locked(locked);
@@ -1,5 +1,7 @@
 use crate::{
-    backend::pool::{Connection, Request},
+    backend::pool::{
+        Connection, Request, connection::mirror::ResultChecksum, shard::coalesce::Leader,
+    },
     config::config,
     frontend::{
         BufferedQuery, Client, ClientComms, Command, Error, Router, RouterContext, Stats,
@@ -8,16 +10,22 @@ use crate::{
     },
     net::{ErrorResponse, Message, Parameters},
     state::State,
+    stats::tags::ClientTags,
 };
 
+use tokio::time::Instant;
 use tracing::debug;
 
 pub mod advisory_lock;
+pub mod audit_log;
+mod coalesce;
 pub mod connect;
 pub mod context;
+mod cross_shard_transaction;
 pub mod deallocate;
 pub mod discard;
 pub mod end_transaction;
+mod error_stats;
 pub mod fake;
 pub mod hooks;
 pub mod incomplete_requests;
@@ -25,13 +33,21 @@ pub mod internal_values;
 pub mod lock;
 pub mod multi_step;
 pub mod notify_buffer;
+mod primary_reads;
 pub mod pub_sub;
 pub mod query;
+mod query_label;
 mod query_log_stdout;
+mod rate_limit;
+mod retry_read;
 pub mod rewrite;
 pub mod route_query;
+mod routing_notice;
 pub mod set;
+mod slow_query;
 pub mod start_transaction;
+mod tag_stats;
+mod temporary_tables;
 #[cfg(test)]
 mod test;
 #[cfg(test)]
@@ -41,6 +57,7 @@ pub mod unknown_command;
 
 use self::query::ExplainResponseState;
 use self::query_log_stdout::log_query_stdout;
+use self::retry_read::ReadRetry;
 pub(crate) use advisory_lock::AdvisoryLocks;
 pub use context::QueryEngineContext;
 use notify_buffer::NotifyBuffer;
@@ -66,6 +83,32 @@ pub struct QueryEngine {
     // They will remain pinned to their connection until they unpin manually
     // or disconnect.
     manual_lock: bool,
+    // Reads that failed before returning rows can be retried.
+    read_retry: ReadRetry,
+    // Checksum of rows returned for the current request,
+    // if mirrors are comparing results.
+    checksum: Option<ResultChecksum>,
+    // Other clients are waiting for the result
+    // of the read we're executing.
+    coalesce: Option<Leader>,
+    // Reads sent by the client since it connected.
+    session_reads: usize,
+    // When the client connected.
+    connected_at: Instant,
+    // Shard used by the current transaction,
+    // if cross-shard transactions are disabled.
+    transaction_shard: Option<usize>,
+    // Cross-shard TRUNCATE is running inside a transaction
+    // we started on each shard.
+    atomic_truncate: bool,
+    // The client created a temporary table and stays
+    // on the same connection until it disconnects.
+    temporary_table: bool,
+    // Route taken by the last query, shown with
+    // SHOW pgdog.last_route.
+    last_route: Option<String>,
+    // Counters for tags set in query comments.
+    tags: ClientTags,
 }
 
 impl QueryEngine {
@@ -89,6 +132,16 @@ impl QueryEngine {
             router: Router::default(),
             advisory_locks: AdvisoryLocks::default(),
             manual_lock: false,
+            read_retry: ReadRetry::default(),
+            checksum: None,
+            coalesce: None,
+            session_reads: 0,
+            connected_at: Instant::now(),
+            transaction_shard: None,
+            atomic_truncate: false,
+            temporary_table: false,
+            last_route: None,
+            tags: ClientTags::default(),
         })
     }
 
@@ -111,8 +164,19 @@ impl QueryEngine {
         self.stats.state
     }
 
+    /// Start computing the result checksum for the next request.
+    pub fn start_checksum(&mut self) {
+        self.checksum = Some(ResultChecksum::default());
+    }
+
+    /// Result checksum of the last request, if one was started.
+    pub fn take_checksum(&mut self) -> Option<u32> {
+        self.checksum.take().map(|checksum| checksum.value())
+    }
+
     /// Handle client request.
     pub async fn handle(&mut self, context: &mut QueryEngineContext<'_>) -> Result<(), Error> {
+        let started = Instant::now();
         self.stats
             .received(context.client_request.total_message_len());
         self.set_state(State::Active); // Client is active.
@@ -120,12 +184,21 @@ impl QueryEngine {
         log_query_stdout(context);
 
         // Rewrite prepared statements.
-        self.rewrite_extended(context)?;
+        if !self.rewrite_extended(context).await? {
+            self.update_stats(context);
+            return Ok(());
+        }
 
         if let ClusterCheck::Offline = self.cluster_check(context).await? {
             return Ok(());
         }
 
+        // Throttle clients exceeding their rate limits.
+        if !self.rate_limit(context).await? {
+            self.update_stats(context);
+            return Ok(());
+        }
+
         // Rewrite statement if necessary.
         if !self.parse_and_rewrite(context).await? {
             return Ok(());
@@ -144,12 +217,18 @@ impl QueryEngine {
             return Ok(());
         }
 
+        self.audit_log(context);
+        self.record_tag(context);
+
         self.hooks.before_execution(context)?;
 
         // Queue up request to mirrors, if any.
         // Do this before sending query to actual server
         // to have accurate timings between queries.
         self.backend.mirror(context.client_request);
+        if self.backend.mirror_checksum_pending() {
+            self.start_checksum();
+        }
 
         self.pending_explain = None;
 
@@ -247,13 +326,22 @@ impl QueryEngine {
                 self.reset_all(context).await?;
             }
             Command::Copy(_) => self.execute(context).await?,
-            Command::Deallocate => self.deallocate(context).await?,
-            Command::Discard { extended } => self.discard(context, *extended).await?,
+            Command::Deallocate { name } => {
+                let name = name.clone();
+                self.deallocate(context, name.as_deref()).await?
+            }
+            Command::Discard { extended, all } => self.discard(context, *extended, *all).await?,
             command => self.unknown_command(context, command.clone()).await?,
         }
 
         self.hooks.after_execution(context)?;
 
+        if self.backend.mirror_checksum_pending()
+            && let Some(checksum) = self.checksum.take()
+        {
+            self.backend.mirror_checksum(checksum.value());
+        }
+
         if context.in_error() {
             self.backend.mirror_clear();
             self.notify_buffer.clear();
@@ -262,6 +350,7 @@ impl QueryEngine {
             self.flush_notify().await?;
         }
 
+        self.log_slow_query(context, started.elapsed());
         self.update_stats(context);
 
         Ok(())
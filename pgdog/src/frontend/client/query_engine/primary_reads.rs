@@ -0,0 +1,39 @@
+//! Send the first reads after a client connects to the primary,
+//! in case the replicas are lagging behind.
+
+use std::time::Duration;
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::config::config;
+
+impl QueryEngine {
+    /// Send the read to the primary if the client connected recently,
+    /// as configured by `primary_reads_after_connect` and
+    /// `primary_reads_after_connect_window`.
+    pub(super) fn primary_reads_after_connect(&mut self, context: &mut QueryEngineContext<'_>) {
+        if !context.client_request.is_executable() || !context.client_request.route().is_read() {
+            return;
+        }
+
+        // Read-only clusters don't have a primary.
+        let read_only = self
+            .backend
+            .cluster()
+            .map(|cluster| cluster.read_only())
+            .unwrap_or(true);
+        if read_only {
+            return;
+        }
+
+        self.session_reads += 1;
+
+        let general = &config().config.general;
+        let window = Duration::from_millis(general.primary_reads_after_connect_window);
+        let primary = self.session_reads <= general.primary_reads_after_connect
+            || self.connected_at.elapsed() < window;
+
+        if primary && let Some(route) = context.client_request.route.as_mut() {
+            route.set_primary(true);
+        }
+    }
+}
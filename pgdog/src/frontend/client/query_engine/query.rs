@@ -10,6 +10,7 @@ use crate::{
         RowDescription, ToBytes, TransactionState,
     },
     state::State,
+    stats::errors::ErrorCategory,
     util::safe_timeout,
 };
 
@@ -24,6 +25,19 @@ impl QueryEngine {
         &mut self,
         context: &mut QueryEngineContext<'_>,
     ) -> Result<(), Error> {
+        let result = self.execute_query(context).await;
+        // Clients waiting for the same read will execute
+        // it themselves if we didn't finish it.
+        self.coalesce = None;
+        if let Err(err) = &result
+            && let Some(category) = err.category()
+        {
+            self.record_error(category);
+        }
+        result
+    }
+
+    async fn execute_query(&mut self, context: &mut QueryEngineContext<'_>) -> Result<(), Error> {
         // Check that we're not in a transaction error state.
         if !self.transaction_error_check(context).await? {
             return Ok(());
@@ -33,6 +47,31 @@ impl QueryEngine {
         // for single-statement writes.
         self.two_pc_check(context);
 
+        // Check if we need to run a cross-shard TRUNCATE
+        // in a transaction on each shard.
+        self.atomic_truncate_check(context);
+
+        // Reads can be retried on another replica
+        // if the server goes away before returning any rows.
+        // Prepared statements can be retried if the schema changed.
+        self.read_retry = ReadRetry::new(self.can_retry_read(context))
+            .schema_change(self.can_retry_schema_changed(context));
+
+        // Identical read is running already, use its result.
+        if self.coalesce(context).await? {
+            return Ok(());
+        }
+
+        // Check the transaction stays on one shard.
+        if !self.cross_shard_transaction_check(context).await? {
+            return Ok(());
+        }
+
+        // Check we can create temporary tables.
+        if !self.temporary_table_check(context).await? {
+            return Ok(());
+        }
+
         // We need to run a query now.
         if context.in_transaction() {
             // Connect to one shard if not sharded or to all shards
@@ -49,6 +88,51 @@ impl QueryEngine {
             return Ok(());
         }
 
+        self.after_connected(context)?;
+
+        loop {
+            let query_timeout = context.timeouts.query_timeout(&State::Active);
+            let transaction_timeout = context
+                .timeouts
+                .transaction_timeout(context.transaction_start);
+
+            match safe_timeout(
+                query_timeout.min(transaction_timeout),
+                self.client_server_exchange(context),
+            )
+            .await
+            {
+                Ok(Err(err)) if self.read_failed(&err) => {
+                    if !self.reconnect_read(context, &err).await? {
+                        return Ok(());
+                    }
+                }
+                Ok(Err(Error::SchemaChanged(err))) => {
+                    if !self.retry_schema_changed(context, &err).await? {
+                        return Ok(());
+                    }
+                }
+                Ok(response) => return response,
+                Err(err) => {
+                    // Close the conn, it could be stuck executing a query
+                    // or dead.
+                    self.backend.force_close();
+                    if transaction_timeout < query_timeout {
+                        return Err(Error::TransactionTimeout(
+                            context.timeouts.max_transaction_duration,
+                        ));
+                    }
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    /// Prepare the servers we just connected to for the request.
+    pub(super) fn after_connected(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+    ) -> Result<(), Error> {
         self.hooks.after_connected(context, &self.backend)?;
 
         // Set response format.
@@ -58,21 +142,6 @@ impl QueryEngine {
             }
         }
 
-        match safe_timeout(
-            context.timeouts.query_timeout(&State::Active),
-            self.client_server_exchange(context),
-        )
-        .await
-        {
-            Ok(response) => response?,
-            Err(err) => {
-                // Close the conn, it could be stuck executing a query
-                // or dead.
-                self.backend.force_close();
-                return Err(err.into());
-            }
-        }
-
         Ok(())
     }
 
@@ -88,8 +157,21 @@ impl QueryEngine {
             }
 
             Some(RewriteResult::InPlace { .. }) | None => {
+                // Read rewritten by the router to filter by the session's tenant.
+                let tenant_filter = context
+                    .client_request
+                    .route()
+                    .tenant_filter()
+                    .map(Query::new);
+                if let Some(query) = tenant_filter {
+                    context.client_request.rewrite(&[query.into()])?;
+                }
+
+                let labeled = Self::labeled_request(context);
+                let client_request = labeled.as_ref().unwrap_or(&*context.client_request);
+
                 self.backend
-                    .handle_client_request(context.client_request, &mut self.router, self.streaming)
+                    .handle_client_request(client_request, &mut self.router, self.streaming)
                     .await?;
 
                 while self.backend.has_more_messages()
@@ -122,6 +204,23 @@ impl QueryEngine {
     ) -> Result<(), Error> {
         self.streaming = message.streaming();
 
+        if message.code() == 'E' {
+            self.schema_changed_check(context, &message)?;
+        }
+
+        if !self.read_retry.forward(message.code()) {
+            return Ok(());
+        }
+
+        if message.code() == 'E' {
+            let error = ErrorResponse::from_bytes(message.to_bytes())?;
+            self.record_error(ErrorCategory::from(&error));
+        }
+
+        if let Some(checksum) = self.checksum.as_mut() {
+            checksum.update(&message);
+        }
+
         let code = message.code();
         let payload = if code == 'T' {
             Some(message.payload())
@@ -166,6 +265,7 @@ impl QueryEngine {
             self.stats.query();
 
             let mut two_pc_auto = false;
+            let mut atomic_truncate = false;
             let state = ReadyForQuery::from_bytes(message.to_bytes())?.state()?;
 
             match state {
@@ -183,6 +283,11 @@ impl QueryEngine {
                         // TODO: this records a 2pc transaction in client
                         // stats anyway but not on the servers. Is this what we want?
                         two_pc_auto = true;
+                    } else if self.atomic_truncate {
+                        // TRUNCATE failed on at least one shard,
+                        // so undo it on the others.
+                        self.backend.execute("ROLLBACK").await?;
+                        atomic_truncate = true;
                     }
                 }
 
@@ -194,6 +299,9 @@ impl QueryEngine {
                     if self.two_pc.auto() {
                         self.end_two_pc(false).await?;
                         two_pc_auto = true;
+                    } else if self.atomic_truncate {
+                        self.backend.execute("COMMIT").await?;
+                        atomic_truncate = true;
                     }
                     match context.transaction {
                         // Query parser is disabled, so the server is responsible for telling us
@@ -216,12 +324,13 @@ impl QueryEngine {
                 }
             }
 
-            if two_pc_auto {
-                // In auto mode, 2pc transaction was started automatically
-                // without the client's knowledge. We need to return a regular RFQ
-                // message and close the transaction.
+            if two_pc_auto || atomic_truncate {
+                // In auto mode, 2pc transaction (or the TRUNCATE transaction)
+                // was started automatically without the client's knowledge.
+                // We need to return a regular RFQ message and close the transaction.
                 context.transaction = None;
                 message = ReadyForQuery::in_transaction(false).message()?;
+                self.atomic_truncate = false;
             }
 
             self.stats.idle(context.in_transaction());
@@ -229,6 +338,9 @@ impl QueryEngine {
             // the router and the command state.
             self.advisory_locks
                 .merge(self.router.command().route().advisory_locks());
+            if state != TransactionState::Error {
+                self.pin_temporary_table();
+            }
             self.check_lock();
 
             if !context.in_transaction() {
@@ -236,6 +348,7 @@ impl QueryEngine {
             }
         }
 
+        self.coalesce_message(context, &message);
         self.stats.sent(message.len());
 
         // Do this before flushing, because flushing can take time.
@@ -314,6 +427,7 @@ impl QueryEngine {
             }
 
             self.router.reset();
+            self.transaction_shard = None;
 
             debug!(
                 "transaction finished [{:.3}ms]",
@@ -421,6 +535,31 @@ impl QueryEngine {
         }
     }
 
+    fn atomic_truncate_check(&mut self, context: &mut QueryEngineContext<'_>) {
+        let enabled = self
+            .backend
+            .cluster()
+            .map(|c| c.atomic_truncate())
+            .unwrap_or_default();
+        let route = context.client_request.route();
+
+        if !context.client_request.is_executable() {
+            return;
+        }
+
+        // Two-phase commit takes care of it already, if it's enabled.
+        self.atomic_truncate = enabled
+            && route.is_truncate()
+            && route.is_cross_shard()
+            && self.begin_stmt.is_none()
+            && !context.in_transaction();
+
+        if self.atomic_truncate {
+            debug!("running cross-shard TRUNCATE in a transaction");
+            self.begin_stmt = Some(BufferedQuery::Query(Query::new("BEGIN")));
+        }
+    }
+
     async fn transaction_error_check(
         &mut self,
         context: &mut QueryEngineContext<'_>,
@@ -0,0 +1,70 @@
+//! Label queries sent to the database with the client
+//! and the shard, so they can be traced in `pg_stat_activity`.
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::config::config;
+use crate::frontend::{ClientRequest, router::parser::Shard};
+use crate::net::{ProtocolMessage, Query};
+
+impl QueryEngine {
+    /// Copy of the client request with its simple queries labeled,
+    /// if `query_labels` is enabled.
+    ///
+    /// The request is routed already, so the label doesn't change
+    /// where it's going.
+    pub(super) fn labeled_request(context: &QueryEngineContext<'_>) -> Option<ClientRequest> {
+        if !config().config.general.query_labels || context.admin {
+            return None;
+        }
+
+        let simple = context
+            .client_request
+            .messages
+            .iter()
+            .any(|message| matches!(message, ProtocolMessage::Query(_)));
+
+        // Extended protocol statements are cached by their text,
+        // so we don't change them.
+        if !simple {
+            return None;
+        }
+
+        let client = context
+            .stream
+            .peer_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default();
+        let label = query_label(&client, context.client_request.route().shard());
+
+        let mut request = context.client_request.clone();
+        for message in request.messages.iter_mut() {
+            if let ProtocolMessage::Query(query) = message {
+                *query = Query::new(format!("{} {}", label, query.query()));
+            }
+        }
+
+        Some(request)
+    }
+}
+
+/// Comment identifying the client and the shard.
+fn query_label(client: &str, shard: &Shard) -> String {
+    format!("/* pgdog: client={} shard={} */", client, shard)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_query_label() {
+        assert_eq!(
+            query_label("127.0.0.1:5432", &Shard::Direct(1)),
+            "/* pgdog: client=127.0.0.1:5432 shard=1 */"
+        );
+        assert_eq!(
+            query_label("127.0.0.1:5432", &Shard::All),
+            "/* pgdog: client=127.0.0.1:5432 shard=all */"
+        );
+    }
+}
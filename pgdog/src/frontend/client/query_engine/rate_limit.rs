@@ -0,0 +1,72 @@
+use tokio::time::{Duration, Instant, sleep};
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::{config::config, frontend::Error, net::ErrorResponse, state::State};
+
+impl QueryEngine {
+    /// Enforce the cluster's query and transaction rate limits.
+    ///
+    /// Requests over the limit wait up to `rate_limit_timeout`
+    /// and are rejected afterwards.
+    ///
+    /// Returns true if the request can proceed.
+    pub(super) async fn rate_limit(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+    ) -> Result<bool, Error> {
+        let Ok(cluster) = self.backend.cluster() else {
+            return Ok(true);
+        };
+
+        let limiter = cluster.rate_limiter();
+        if !limiter.enabled() || !context.client_request.is_executable() {
+            return Ok(true);
+        }
+
+        let identifier = cluster.identifier();
+        let timeout = Duration::from_millis(config().config.general.rate_limit_timeout);
+        let deadline = Instant::now() + timeout;
+
+        // Requests received outside a transaction start a new one.
+        let mut transaction = !context.in_transaction();
+        let mut waited = false;
+
+        let allowed = loop {
+            let result = if transaction {
+                limiter
+                    .acquire_transaction()
+                    .inspect(|_| transaction = false)
+            } else {
+                Ok(())
+            }
+            .and_then(|_| limiter.acquire_query());
+
+            match result {
+                Ok(()) => break true,
+                Err(wait) => {
+                    if Instant::now() + wait > deadline {
+                        break false;
+                    }
+
+                    self.set_state(State::Waiting);
+                    waited = true;
+                    sleep(wait).await;
+                }
+            }
+        };
+
+        if waited {
+            self.set_state(State::Active);
+        }
+
+        if !allowed {
+            self.error_response(
+                context,
+                ErrorResponse::rate_limited(&identifier.user, &identifier.database),
+            )
+            .await?;
+        }
+
+        Ok(allowed)
+    }
+}
@@ -0,0 +1,277 @@
+//! Retry reads on another replica if the one
+//! serving them went away, and prepared statements
+//! that failed because the schema changed.
+
+use std::collections::VecDeque;
+
+use tracing::warn;
+
+use crate::{
+    frontend::{PreparedStatements, router::parser::rewrite::statement::plan::RewriteResult},
+    net::{FromBytes, ProtocolMessage, ToBytes},
+};
+
+use super::*;
+
+/// Messages sent to the client by a read we may retry.
+#[derive(Debug, Default)]
+pub(super) struct ReadRetry {
+    /// The read can be retried.
+    enabled: bool,
+    /// The request can be retried if the schema changed.
+    schema_change: bool,
+    /// The read has been retried already.
+    retried: bool,
+    /// Messages forwarded to the client.
+    sent: Vec<char>,
+    /// Messages the client already received before the retry.
+    skip: VecDeque<char>,
+}
+
+impl ReadRetry {
+    pub(super) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Allow retrying the request once if a prepared statement
+    /// failed because the schema changed.
+    pub(super) fn schema_change(mut self, schema_change: bool) -> Self {
+        self.schema_change = schema_change;
+        self
+    }
+
+    /// Record a message sent by the server. Returns false if
+    /// the client already received it and it shouldn't be forwarded again.
+    pub(super) fn forward(&mut self, code: char) -> bool {
+        if self.skip.front() == Some(&code) {
+            self.skip.pop_front();
+            return false;
+        }
+        self.skip.clear();
+
+        if self.enabled || self.schema_change {
+            // Only statement metadata can be sent to the client
+            // before we can't retry anymore.
+            if matches!(code, '1' | '2' | 't' | 'T' | 'n') {
+                self.sent.push(code);
+            } else {
+                self.enabled = false;
+                self.schema_change = false;
+                self.sent.clear();
+            }
+        }
+
+        true
+    }
+
+    /// The read can be retried.
+    pub(super) fn possible(&self) -> bool {
+        self.enabled && !self.retried
+    }
+
+    /// The request can be retried after a schema change.
+    pub(super) fn schema_change_possible(&self) -> bool {
+        self.schema_change && !self.retried
+    }
+
+    /// Retry the read, skipping messages the client already received.
+    fn retry(&mut self) {
+        self.retried = true;
+        self.skip = self.sent.drain(..).collect();
+    }
+}
+
+impl QueryEngine {
+    /// The request is a single-shard read outside of a transaction,
+    /// so it can be safely re-executed on another database.
+    pub(super) fn can_retry_read(&self, context: &QueryEngineContext<'_>) -> bool {
+        let route = context.client_request.route();
+
+        matches!(self.router.command(), Command::Query(_))
+            && route.is_read()
+            && route.shard().is_direct()
+            && !context.in_transaction()
+            && !self.backend.connected()
+            && !self.backend.session_mode()
+            && !self.manual_lock
+            && matches!(
+                context.rewrite_result,
+                None | Some(RewriteResult::InPlace { .. })
+            )
+    }
+
+    /// The read failed with an error we can recover from.
+    pub(super) fn read_failed(&self, err: &Error) -> bool {
+        self.read_retry.possible() && matches!(err, Error::Backend(err) if err.is_retryable())
+    }
+
+    /// Close the broken connection, counting the error against the database it
+    /// was connected to, and connect to another one. Connecting syncs session
+    /// parameters with the new server, and prepared statements used by the request
+    /// are prepared on it when it's sent again.
+    pub(super) async fn reconnect_read(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+        err: &Error,
+    ) -> Result<bool, Error> {
+        let addr = self
+            .backend
+            .addr()?
+            .first()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default();
+        warn!(
+            "retrying read on another replica, {} failed: {} [{:?}]",
+            addr,
+            err,
+            context.stream.peer_addr()
+        );
+
+        self.read_retry.retry();
+        self.backend.close_failed();
+
+        self.reconnect(context).await
+    }
+
+    /// The request uses prepared statements and runs outside of a transaction,
+    /// so it can be safely re-executed if the schema changed.
+    pub(super) fn can_retry_schema_changed(&self, context: &QueryEngineContext<'_>) -> bool {
+        config()
+            .config
+            .general
+            .prepared_statements_schema_change
+            .retry()
+            && context
+                .client_request
+                .messages
+                .iter()
+                .any(|message| matches!(message, ProtocolMessage::Bind(bind) if !bind.anonymous()))
+            && context.client_request.route().shard().is_direct()
+            && !context.in_transaction()
+            && !self.backend.connected()
+            && !self.backend.session_mode()
+            && !self.manual_lock
+            && matches!(
+                context.rewrite_result,
+                None | Some(RewriteResult::InPlace { .. })
+            )
+    }
+
+    /// Server returned an error. If it's because the schema changed, remove
+    /// stale RowDescriptions of the statements used by the request from the global cache,
+    /// and retry the request if we can.
+    pub(super) fn schema_changed_check(
+        &mut self,
+        context: &QueryEngineContext<'_>,
+        message: &Message,
+    ) -> Result<(), Error> {
+        let error = ErrorResponse::from_bytes(message.to_bytes())?;
+        if !error.is_schema_changed() {
+            return Ok(());
+        }
+
+        {
+            let global = PreparedStatements::global();
+            let mut global = global.write();
+            for message in context.client_request.messages.iter() {
+                match message {
+                    ProtocolMessage::Bind(bind) => global.invalidate(bind.statement()),
+                    ProtocolMessage::Describe(describe) if describe.is_statement() => {
+                        global.invalidate(describe.statement())
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if self.read_retry.schema_change_possible() {
+            Err(Error::SchemaChanged(Box::new(error)))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wait for the server to finish the failed request and return it to the pool,
+    /// which closes its stale prepared statements. Connect again and retry the request.
+    pub(super) async fn retry_schema_changed(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+        err: &ErrorResponse,
+    ) -> Result<bool, Error> {
+        warn!(
+            "retrying prepared statement after schema change: {} [{:?}]",
+            err,
+            context.stream.peer_addr()
+        );
+
+        self.read_retry.retry();
+
+        while self.backend.has_more_messages() {
+            self.read_server_message().await?;
+        }
+        self.backend.disconnect();
+
+        self.reconnect(context).await
+    }
+
+    /// Connect again to retry the request.
+    async fn reconnect(&mut self, context: &mut QueryEngineContext<'_>) -> Result<bool, Error> {
+        if !self.connect(context, None).await? {
+            return Ok(false);
+        }
+        self.after_connected(context)?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_retry_skips_sent_messages() {
+        let mut retry = ReadRetry::new(true);
+        assert!(retry.forward('1'));
+        assert!(retry.forward('T'));
+        assert!(retry.possible());
+
+        retry.retry();
+        assert!(!retry.possible());
+        assert!(!retry.forward('1'));
+        assert!(!retry.forward('T'));
+        assert!(retry.forward('D'));
+        assert!(retry.forward('C'));
+    }
+
+    #[test]
+    fn test_read_retry_not_after_rows() {
+        let mut retry = ReadRetry::new(true);
+        assert!(retry.forward('T'));
+        assert!(retry.forward('D'));
+        assert!(!retry.possible());
+
+        let retry = ReadRetry::new(false);
+        assert!(!retry.possible());
+    }
+
+    #[test]
+    fn test_schema_change_retry() {
+        let mut retry = ReadRetry::new(false).schema_change(true);
+        assert!(retry.forward('1'));
+        assert!(!retry.possible());
+        assert!(retry.schema_change_possible());
+
+        retry.retry();
+        assert!(!retry.schema_change_possible());
+        assert!(!retry.forward('1'));
+        assert!(retry.forward('2'));
+
+        let mut retry = ReadRetry::new(false).schema_change(true);
+        assert!(retry.forward('C'));
+        assert!(!retry.schema_change_possible());
+    }
+}
@@ -1,22 +1,51 @@
-use crate::frontend::router::parser::{AstContext, Cache};
+use pgdog_config::ParseFailures;
+
+use crate::frontend::{
+    prepared_statements,
+    router::parser::{AstContext, Cache},
+};
+use crate::net::ProtocolMessage;
 
 use super::*;
 
 impl QueryEngine {
     /// Rewrite extended protocol messages.
-    pub(super) fn rewrite_extended(
+    pub(super) async fn rewrite_extended(
         &mut self,
         context: &mut QueryEngineContext<'_>,
-    ) -> Result<(), Error> {
+    ) -> Result<bool, Error> {
+        let mut limit = None;
+        let binary_results = config().config.general.backend_binary_results;
+
         for message in context.client_request.iter_mut() {
+            if binary_results && let ProtocolMessage::Bind(bind) = message {
+                bind.binary_results();
+            }
+
             if message.is_extended() {
                 let level = context.prepared_statements.level;
                 if level.handles_extended() && (level.rewrite_anonymous() || !message.anonymous()) {
-                    context.prepared_statements.maybe_rewrite(message)?;
+                    match context.prepared_statements.maybe_rewrite(message) {
+                        Err(prepared_statements::Error::ClientLimit(max)) => {
+                            limit = Some(max);
+                            break;
+                        }
+                        result => result?,
+                    }
                 }
             }
         }
-        Ok(())
+
+        if let Some(limit) = limit {
+            self.error_response(
+                context,
+                ErrorResponse::prepared_statements_client_limit(limit),
+            )
+            .await?;
+            return Ok(false);
+        }
+
+        Ok(true)
     }
 
     /// Parse client request and rewrite it, if necessary.
@@ -40,6 +69,12 @@ impl QueryEngine {
             let ast_ctx = AstContext::from_cluster(cluster, context.params);
             let ast = match Cache::get().query(&query, &ast_ctx, context.prepared_statements) {
                 Ok(ast) => ast,
+                // Let the router send it somewhere without knowing what it is.
+                Err(err) if err.is_parse() && cluster.parse_failures() != ParseFailures::Reject => {
+                    debug!("failed to parse query, using fallback route: {}", err);
+                    context.client_request.parse_failed = true;
+                    return Ok(true);
+                }
                 Err(err) => {
                     self.error_response(context, ErrorResponse::syntax(err.to_string().as_str()))
                         .await?;
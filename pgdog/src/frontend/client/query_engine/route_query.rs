@@ -1,7 +1,9 @@
-use pgdog_config::PoolerMode;
+use pgdog_config::{DeniedParameterAction, PoolerMode};
 use tracing::trace;
 
 use crate::backend::Cluster;
+use crate::frontend::router::{Error as RouterError, parser::Error as ParserError};
+use crate::net::NoticeResponse;
 use crate::util::safe_timeout;
 
 use super::*;
@@ -84,13 +86,15 @@ impl QueryEngine {
             }
         };
 
-        let router_context = RouterContext::new(
+        let mut router_context = RouterContext::new(
             context.client_request,
             cluster,
             context.params,
             context.transaction,
             context.sticky,
         )?;
+        router_context.last_route = self.last_route.as_deref();
+        router_context.connected_shard = self.backend.direct_shard_number();
         match self.router.query(router_context) {
             Ok(command) => {
                 context.client_request.route = Some(command.route().clone());
@@ -120,7 +124,52 @@ impl QueryEngine {
                             .await?;
                         return Ok(false);
                     }
+
+                    if let Some((lock_shard, shard)) =
+                        Self::advisory_lock_shard_mismatch(command, &self.backend, cluster)
+                    {
+                        self.error_response(
+                            context,
+                            ErrorResponse::advisory_lock_shard_mismatch(lock_shard, shard),
+                        )
+                        .await?;
+                        return Ok(false);
+                    }
+
+                    if let Some(notice) = Self::explain_route(command, cluster) {
+                        self.routing_notice(context, notice).await?;
+                    }
+
+                    if let Command::Query(route) = command {
+                        self.last_route = Some(route.to_string());
+                    }
+                }
+            }
+            Err(RouterError::Parser(ParserError::ParameterNotAllowed(name))) => {
+                let mut error = ErrorResponse::parameter_not_allowed(&name);
+
+                if config().config.general.denied_parameter_action == DeniedParameterAction::Notice
+                {
+                    error.severity = "WARNING".into();
+                    let sent = context.stream.send(&NoticeResponse::from(error)).await?;
+                    self.stats.sent(sent);
+                    self.fake_command_response(context, "SET", None::<Option<_>>)
+                        .await?;
+                } else {
+                    self.error_response(context, error).await?;
                 }
+
+                return Ok(false);
+            }
+            Err(RouterError::Parser(ParserError::ShardingKeyRequired(command))) => {
+                let query = context.client_request.query()?;
+                let error = ErrorResponse::sharding_key_required(
+                    command,
+                    query.as_ref().map(|q| q.query()),
+                );
+                self.error_response(context, error).await?;
+
+                return Ok(false);
             }
             Err(err) => {
                 self.error_response(context, ErrorResponse::syntax(err.to_string().as_str()))
@@ -130,6 +179,8 @@ impl QueryEngine {
             }
         }
 
+        self.primary_reads_after_connect(context);
+
         Ok(true)
     }
 
@@ -168,11 +219,45 @@ impl QueryEngine {
             }
         } else if let Command::Query(route) = command {
             // Tried to run a cross-shard query while connected to one shard only.
-            if route.is_cross_shard() && backend.direct_shard_number().is_some() {
+            // Advisory locks are only held on the shard we're pinned to,
+            // so releasing them there is enough.
+            if route.is_cross_shard()
+                && backend.direct_shard_number().is_some()
+                && !route.advisory_locks().has_unlock_any()
+            {
                 return true;
             }
         }
 
         false
     }
+
+    // Advisory locks must be taken on the shard picked by their key, otherwise
+    // clients using the same key won't see each other's locks. Returns the lock's
+    // shard and the shard the query would run on if they're different.
+    fn advisory_lock_shard_mismatch(
+        command: &Command,
+        backend: &Connection,
+        cluster: &Cluster,
+    ) -> Option<(usize, usize)> {
+        let Command::Query(route) = command else {
+            return None;
+        };
+        let Some(Shard::Direct(lock_shard)) = route.advisory_locks().shard(cluster.shards().len())
+        else {
+            return None;
+        };
+
+        // Pinned to a shard by the session or the transaction.
+        let shard = match (backend.direct_shard_number(), route.shard()) {
+            (Some(shard), _) => shard,
+            (None, Shard::Direct(_)) if route.shard_with_priority().source().is_round_robin() => {
+                return None;
+            }
+            (None, Shard::Direct(shard)) => *shard,
+            (None, _) => return None,
+        };
+
+        (shard != lock_shard).then_some((lock_shard, shard))
+    }
 }
@@ -0,0 +1,84 @@
+use super::{QueryEngine, QueryEngineContext};
+use crate::backend::Cluster;
+use crate::config::config;
+use crate::frontend::{
+    Command, Error,
+    router::{
+        Route,
+        parser::{Shard, route::ShardSource},
+    },
+};
+use crate::net::{ErrorResponse, NoticeResponse};
+
+impl QueryEngine {
+    /// Explain a notable routing decision, if `routing_notices` is enabled.
+    pub(super) fn explain_route(command: &Command, cluster: &Cluster) -> Option<String> {
+        if !config().config.general.routing_notices {
+            return None;
+        }
+
+        let Command::Query(route) = command else {
+            return None;
+        };
+
+        if route.shard_with_priority().source() == &ShardSource::Plugin {
+            return Some(format!("shard {} selected by plugin", route.shard()));
+        }
+
+        if cluster.shards().len() > 1 {
+            match route.shard() {
+                Shard::All => {
+                    return Some(format!(
+                        "query sent to all {} shards",
+                        cluster.shards().len()
+                    ));
+                }
+                Shard::Multi(shards) => {
+                    return Some(format!("query sent to shards {:?}", shards));
+                }
+                Shard::Direct(_) => (),
+            }
+        }
+
+        None
+    }
+
+    /// Explain why a read went to the primary, if `routing_notices` is enabled
+    /// and all replicas are banned.
+    pub(super) fn explain_primary_fallback(&self, route: &Route) -> Option<String> {
+        if !config().config.general.routing_notices || !route.is_read() {
+            return None;
+        }
+
+        let Shard::Direct(number) = route.shard() else {
+            return None;
+        };
+
+        let cluster = self.backend.cluster().ok()?;
+        let addr = self.backend.addr().ok()?;
+        let shard = cluster.shards().get(*number)?;
+        let primary = shard.primary_addr()?;
+
+        if shard.replicas_banned() && addr.contains(&primary) {
+            Some(format!(
+                "all replicas on shard {} are banned, read sent to the primary",
+                number
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Send the routing decision explanation to the client.
+    pub(super) async fn routing_notice(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+        message: String,
+    ) -> Result<(), Error> {
+        let notice = NoticeResponse::from(ErrorResponse::routing_notice(message));
+        let sent = context.stream.send(&notice).await?;
+        self.stats.sent(sent);
+
+        Ok(())
+    }
+}
@@ -56,6 +56,14 @@ impl QueryEngine {
         }
 
         if self.backend.connected() {
+            // The role is changed directly on the server, so make sure
+            // it's reset before the server is given to another client.
+            if let Some(role) = params
+                .iter()
+                .rfind(|param| param.name.eq_ignore_ascii_case("role"))
+            {
+                self.backend.mark_role_changed(role.value.is_some());
+            }
             self.execute(context).await?;
         } else {
             let values_to_return =
@@ -68,7 +76,8 @@ impl QueryEngine {
     }
 
     /// Make sure the client isn't changing the route mid-transaction
-    /// by issuing a `SET pgdog.shard` or `SET pgdog.sharding_key` command.
+    /// by issuing a `SET pgdog.shard` or `SET pgdog.sharding_key` command,
+    /// or by changing the configured sharding key parameter.
     async fn route_change_check(
         &mut self,
         context: &mut QueryEngineContext<'_>,
@@ -78,9 +87,17 @@ impl QueryEngine {
             return Ok(false);
         }
 
+        let sharding_key_parameter = self
+            .backend
+            .cluster()
+            .ok()
+            .and_then(|cluster| cluster.sharding_key_parameter());
+
         let Some(param) = params.iter().find(|param| {
             SHARD_TARGETING_PARAMS
                 .iter()
+                .copied()
+                .chain(sharding_key_parameter)
                 .any(|name| param.name.eq_ignore_ascii_case(name))
         }) else {
             return Ok(false);
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::config::config;
+use crate::frontend::router::parser::Cache;
+use crate::util::user_database_from_params;
+
+impl QueryEngine {
+    /// Log the request if it took longer than `log_slow_queries_ms`.
+    ///
+    /// The query is normalized first, so literal values
+    /// don't end up in the logs.
+    pub(super) fn log_slow_query(&mut self, context: &QueryEngineContext<'_>, elapsed: Duration) {
+        let Some(threshold) = config().config.general.log_slow_queries() else {
+            return;
+        };
+
+        if elapsed < threshold {
+            return;
+        }
+
+        let Ok(Some(query)) = context.client_request.query() else {
+            return;
+        };
+
+        self.stats.slow_query();
+
+        let normalized = Cache::normalize(query.query()).unwrap_or_else(|_| "<unparsable>".into());
+        let (user, database) = user_database_from_params(context.params);
+
+        warn!(
+            duration_ms = elapsed.as_secs_f64() * 1000.0,
+            threshold_ms = threshold.as_millis() as u64,
+            shard = %context.client_request.route().shard(),
+            database = %database,
+            user = %user,
+            query = normalized.trim(),
+            "slow query"
+        );
+    }
+}
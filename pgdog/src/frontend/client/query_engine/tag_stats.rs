@@ -0,0 +1,23 @@
+//! Count queries tagged by the client for `SHOW TAGS`.
+
+use super::{QueryEngine, QueryEngineContext};
+
+impl QueryEngine {
+    /// Count the query towards the tag set in its comment, if any.
+    pub(super) fn record_tag(&mut self, context: &QueryEngineContext<'_>) {
+        let Some(tag) = context
+            .client_request
+            .ast
+            .as_ref()
+            .and_then(|ast| ast.comment_tag.as_deref())
+        else {
+            return;
+        };
+
+        if let Ok(cluster) = self.backend.cluster() {
+            let identifier = cluster.identifier();
+            self.tags
+                .record(&identifier.user, &identifier.database, tag);
+        }
+    }
+}
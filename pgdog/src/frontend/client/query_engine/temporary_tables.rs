@@ -0,0 +1,52 @@
+//! Temporary tables in transaction mode, see the `temporary_tables` setting.
+
+use tracing::debug;
+
+use super::{QueryEngine, QueryEngineContext};
+use crate::config::TemporaryTables;
+use crate::frontend::Error;
+use crate::net::ErrorResponse;
+
+impl QueryEngine {
+    /// Reject the query if it creates a temporary table and
+    /// `temporary_tables` is set to `reject`.
+    ///
+    /// Returns false if the query was rejected.
+    pub(super) async fn temporary_table_check(
+        &mut self,
+        context: &mut QueryEngineContext<'_>,
+    ) -> Result<bool, Error> {
+        if context.admin
+            || self.backend.session_mode()
+            || !context.client_request.route().is_temporary_table()
+            || self.temporary_tables() != TemporaryTables::Reject
+        {
+            return Ok(true);
+        }
+
+        self.error_response(context, ErrorResponse::temporary_table())
+            .await?;
+
+        Ok(false)
+    }
+
+    /// Keep the client on the connection that has its temporary table,
+    /// if `temporary_tables` is set to `pin`.
+    pub(super) fn pin_temporary_table(&mut self) {
+        if self.router.command().route().is_temporary_table()
+            && self.temporary_tables() == TemporaryTables::Pin
+            && !self.temporary_table
+        {
+            debug!("temporary table created, pinning connection");
+            self.temporary_table = true;
+        }
+    }
+
+    /// The `temporary_tables` setting of the cluster we're connected to.
+    fn temporary_tables(&self) -> TemporaryTables {
+        self.backend
+            .cluster()
+            .map(|cluster| cluster.temporary_tables())
+            .unwrap_or_default()
+    }
+}
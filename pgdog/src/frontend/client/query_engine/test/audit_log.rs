@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::time::{sleep, timeout};
+
+use crate::config::{config, set};
+use crate::frontend::client::query_engine::audit_log::Queue;
+
+use super::prelude::*;
+
+#[tokio::test]
+async fn test_audit_log() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.log");
+
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.audit_log = Some(path.clone());
+    set(cfg).unwrap();
+
+    let id = client.random_id_for_shard(1);
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(
+            "INSERT INTO sharded (id, value) VALUES (1, 'secret')",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Records are written in the background.
+    let lines = timeout(Duration::from_secs(5), async {
+        loop {
+            let contents = std::fs::read_to_string(&path).unwrap_or_default();
+            let lines = contents
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect::<Vec<_>>();
+            if lines.len() == 2 {
+                break lines;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let select = &lines[0];
+    assert_eq!(select["user"], "pgdog");
+    assert_eq!(select["database"], "pgdog");
+    assert_eq!(select["query"], "SELECT * FROM sharded WHERE id = $1");
+    assert_eq!(select["shard"], "1");
+    assert_eq!(select["route"], "read");
+    assert!(select["timestamp"].is_string());
+
+    let insert = &lines[1];
+    assert_eq!(
+        insert["query"],
+        "INSERT INTO sharded (id, value) VALUES ($1, $2)"
+    );
+    assert_eq!(insert["route"], "write");
+    assert!(!insert.to_string().contains("secret"));
+}
+
+#[test]
+fn test_audit_log_queue_full() {
+    let (queue, rx) = Queue::new(2);
+
+    // Writer isn't keeping up.
+    for i in 0..5 {
+        queue.send("audit.log".into(), i.to_string());
+    }
+    assert_eq!(queue.dropped(), 3);
+
+    // Queued records are written in order.
+    let lines = rx.try_iter().map(|(_, line)| line).collect::<Vec<_>>();
+    assert_eq!(lines, vec!["0", "1"]);
+
+    queue.send("audit.log".into(), "5".into());
+    assert_eq!(queue.dropped(), 3);
+    assert_eq!(rx.try_recv().unwrap().1, "5");
+}
@@ -0,0 +1,195 @@
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{config, load_test_sharded, set},
+    net::{DataRow, Format, FromBytes, Numeric, ToBytes},
+};
+
+use super::prelude::*;
+
+/// Execute query using the extended protocol, requesting binary results.
+async fn execute_binary(client: &mut TestClient, query: &str) -> Vec<DataRow> {
+    execute(client, query, &[1]).await
+}
+
+/// Execute query using the extended protocol, with the given result format codes.
+async fn execute(client: &mut TestClient, query: &str, results: &[i16]) -> Vec<DataRow> {
+    client.send(Parse::new_anonymous(query)).await;
+    client
+        .send(Bind::new_params_codes_results("", &[], &[], results))
+        .await;
+    client.send(Describe::new_portal("")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    client
+        .read_until('Z')
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|message| message.code() == 'D')
+        .map(|message| DataRow::from_bytes(message.to_bytes()).unwrap())
+        .collect()
+}
+
+async fn setup(client: &mut TestClient) -> Vec<i64> {
+    let mut ids = vec![
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(1),
+        client.random_id_for_shard(1),
+    ];
+    ids.sort();
+    ids.dedup();
+
+    cleanup(client, &ids).await;
+
+    let values = ids
+        .iter()
+        .map(|id| format!("({id}, 'binary')"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES {values}"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    ids
+}
+
+async fn cleanup(client: &mut TestClient, ids: &[i64]) {
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({})",
+            id_list(ids)
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+fn id_list(ids: &[i64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[tokio::test]
+async fn test_binary_cross_shard_order_by() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+    let ids = setup(&mut client).await;
+
+    let rows = execute_binary(
+        &mut client,
+        &format!(
+            "SELECT id FROM sharded WHERE id IN ({}) ORDER BY id DESC",
+            id_list(&ids)
+        ),
+    )
+    .await;
+
+    let received = rows
+        .iter()
+        .map(|row| row.get::<i64>(0, Format::Binary).unwrap())
+        .collect::<Vec<_>>();
+    let expected = ids.iter().rev().copied().collect::<Vec<_>>();
+    assert_eq!(received, expected);
+
+    cleanup(&mut client, &ids).await;
+}
+
+#[tokio::test]
+async fn test_binary_cross_shard_sum() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+    let ids = setup(&mut client).await;
+
+    let rows = execute_binary(
+        &mut client,
+        &format!(
+            "SELECT SUM(id), COUNT(*) FROM sharded WHERE id IN ({})",
+            id_list(&ids)
+        ),
+    )
+    .await;
+
+    assert_eq!(rows.len(), 1);
+    let sum = rows[0].get::<Numeric>(0, Format::Binary).unwrap();
+    let count = rows[0].get::<i64>(1, Format::Binary).unwrap();
+
+    assert_eq!(sum, Numeric::from(ids.iter().sum::<i64>()));
+    assert_eq!(count, ids.len() as i64);
+
+    cleanup(&mut client, &ids).await;
+}
+
+async fn client_with_backend_binary_results() -> TestClient {
+    load_test_sharded();
+    let mut cfg = (*config()).clone();
+    cfg.config.general.backend_binary_results = true;
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+
+    TestClient::new(Parameters::default()).await
+}
+
+#[tokio::test]
+async fn test_backend_binary_results_pass_through() {
+    let mut client = client_with_backend_binary_results().await;
+    let ids = setup(&mut client).await;
+
+    // One format code per column.
+    let rows = execute(
+        &mut client,
+        &format!(
+            "SELECT id, value, id::numeric FROM sharded WHERE id IN ({}) ORDER BY id",
+            id_list(&ids)
+        ),
+        &[1, 1, 1],
+    )
+    .await;
+
+    assert_eq!(rows.len(), ids.len());
+    for (row, id) in rows.iter().zip(ids.iter()) {
+        assert_eq!(row.get::<i64>(0, Format::Binary).unwrap(), *id);
+        assert_eq!(row.get::<String>(1, Format::Binary).unwrap(), "binary");
+        assert_eq!(
+            row.get::<Numeric>(2, Format::Binary).unwrap(),
+            Numeric::from(*id)
+        );
+    }
+
+    cleanup(&mut client, &ids).await;
+}
+
+#[tokio::test]
+async fn test_backend_binary_results_text_client() {
+    let mut client = client_with_backend_binary_results().await;
+    let ids = setup(&mut client).await;
+
+    for results in [&[][..], &[0], &[1, 0]] {
+        let rows = execute(
+            &mut client,
+            &format!(
+                "SELECT id, value FROM sharded WHERE id IN ({}) ORDER BY id",
+                id_list(&ids)
+            ),
+            results,
+        )
+        .await;
+
+        assert_eq!(rows.len(), ids.len());
+        for (row, id) in rows.iter().zip(ids.iter()) {
+            if results.first() == Some(&1) {
+                assert_eq!(row.get::<i64>(0, Format::Binary).unwrap(), *id);
+            } else {
+                assert_eq!(row.get::<i64>(0, Format::Text).unwrap(), *id);
+            }
+            assert_eq!(row.get::<String>(1, Format::Text).unwrap(), "binary");
+        }
+    }
+
+    cleanup(&mut client, &ids).await;
+}
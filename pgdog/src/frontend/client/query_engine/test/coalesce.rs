@@ -0,0 +1,86 @@
+use futures::future::join_all;
+
+use crate::{
+    backend::databases::{databases, reload_from_existing},
+    config::{config, load_test, set},
+    expect_message,
+    net::{CommandComplete, DataRow, ReadyForQuery, RowDescription},
+};
+
+use super::prelude::*;
+
+const QUERY: &str = "SELECT 42 FROM pg_sleep(0.5) /* coalesce */";
+
+async fn clients(coalesce_reads: bool, count: usize) -> Vec<TestClient> {
+    load_test();
+
+    let mut config = (*config()).clone();
+    config.config.general.coalesce_reads = coalesce_reads;
+    set(config).unwrap();
+    reload_from_existing().unwrap();
+
+    let mut clients = vec![];
+    for _ in 0..count {
+        clients.push(TestClient::new(Parameters::default()).await);
+    }
+
+    clients
+}
+
+/// Number of times a server connection was given to a client.
+fn server_assignments() -> usize {
+    databases().cluster(("pgdog", "pgdog")).unwrap().shards()[0].pools()[0]
+        .state()
+        .stats
+        .counts
+        .server_assignment_count
+}
+
+/// Send the same read from all clients at the same time.
+async fn read_concurrently(clients: &mut [TestClient]) {
+    join_all(
+        clients
+            .iter_mut()
+            .map(|client| client.send_simple(Query::new(QUERY))),
+    )
+    .await;
+
+    for client in clients.iter_mut() {
+        expect_message!(client.read().await, RowDescription);
+        let row = expect_message!(client.read().await, DataRow);
+        assert_eq!(row.get_int(0, true), Some(42));
+        assert_eq!(
+            expect_message!(client.read().await, CommandComplete).command(),
+            "SELECT 1"
+        );
+        assert_eq!(
+            expect_message!(client.read().await, ReadyForQuery).status,
+            'I'
+        );
+        assert!(!client.backend_connected());
+    }
+}
+
+#[tokio::test]
+async fn test_coalesce_identical_reads() {
+    let mut clients = clients(true, 50).await;
+    let assignments = server_assignments();
+
+    read_concurrently(&mut clients).await;
+
+    // Only one client executed the read.
+    assert_eq!(server_assignments() - assignments, 1);
+
+    let cluster = databases().cluster(("pgdog", "pgdog")).unwrap();
+    assert!(cluster.shards()[0].coalesce().is_empty());
+}
+
+#[tokio::test]
+async fn test_coalesce_reads_disabled() {
+    let mut clients = clients(false, 5).await;
+    let assignments = server_assignments();
+
+    read_concurrently(&mut clients).await;
+
+    assert_eq!(server_assignments() - assignments, 5);
+}
@@ -1,3 +1,9 @@
+use crate::{
+    config::{TemporaryTables, config, set},
+    expect_message,
+    net::ReadyForQuery,
+};
+
 use super::prelude::*;
 
 #[tokio::test]
@@ -15,3 +21,116 @@ async fn test_cross_shard_ddl() {
 
     client.send_simple(Query::new("ROLLBACK")).await;
 }
+
+#[tokio::test]
+async fn test_cross_shard_transaction_rejected() {
+    let mut client = TestClient::new_cross_shard_transactions_disabled(Parameters::default()).await;
+
+    let id_shard0 = client.random_id_for_shard(0);
+    let id_shard1 = client.random_id_for_shard(1);
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    // Transaction is using shard 0 now.
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard0
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard1
+        )))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.message, "cross-shard transactions are disabled");
+    assert!(
+        error
+            .detail
+            .unwrap()
+            .starts_with("transaction is using shard 0 but the query requires shard 1"),
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // Queries without a sharding key go to all shards.
+    client
+        .send_simple(Query::new("SELECT * FROM sharded"))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.message, "cross-shard transactions are disabled");
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // Shard 0 can still be used.
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard0
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+
+    // The next transaction can use another shard.
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cross_shard_transaction_reset_without_server() {
+    let mut client = TestClient::new_cross_shard_transactions_disabled(Parameters::default()).await;
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.temporary_tables = TemporaryTables::Reject;
+    set(cfg).unwrap();
+
+    let id_shard1 = client.random_id_for_shard(1);
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    // Picks shard 0 for the transaction but is rejected
+    // before connecting to it.
+    client
+        .send_simple(Query::new(
+            "/* pgdog_shard: 0 */ CREATE TEMP TABLE test_cross_shard_transaction_reset (id BIGINT)",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap_err();
+    expect_message!(client.read().await, ReadyForQuery);
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+
+    // The next transaction can use another shard.
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
@@ -0,0 +1,104 @@
+use crate::{
+    expect_message,
+    net::{BindComplete, CommandComplete, ParseComplete, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+async fn prepare(client: &mut TestClient, names: &[&str]) {
+    for (i, name) in names.iter().enumerate() {
+        client
+            .send(Parse::named(name, &format!("SELECT {}", i + 1000)))
+            .await;
+    }
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    for _ in names {
+        expect_message!(client.read().await, ParseComplete);
+    }
+    expect_message!(client.read().await, ReadyForQuery);
+}
+
+fn global_used(client: &TestClient) -> usize {
+    client
+        .client()
+        .prepared_statements
+        .global
+        .read()
+        .statements()
+        .values()
+        .map(|stmt| stmt.used)
+        .sum()
+}
+
+#[tokio::test]
+async fn test_deallocate_all() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare(&mut client, &["dealloc_1", "dealloc_2", "dealloc_3"]).await;
+
+    assert_eq!(client.client().prepared_statements.len_local(), 3);
+    assert_eq!(global_used(&client), 3);
+
+    client.send_simple(Query::new("DEALLOCATE ALL")).await;
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "DEALLOCATE ALL"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert_eq!(client.client().prepared_statements.len_local(), 0);
+    assert_eq!(global_used(&client), 0);
+
+    // The connection is still usable.
+    client.send_simple(Query::new("SELECT 1")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_deallocate_all_extended() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare(&mut client, &["dealloc_ext_1", "dealloc_ext_2"]).await;
+    assert_eq!(global_used(&client), 2);
+
+    client.send(Parse::new_anonymous("DEALLOCATE ALL")).await;
+    client.send(Bind::default()).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, BindComplete);
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "DEALLOCATE ALL"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert_eq!(client.client().prepared_statements.len_local(), 0);
+    assert_eq!(global_used(&client), 0);
+}
+
+#[tokio::test]
+async fn test_deallocate_named() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare(&mut client, &["dealloc_keep", "dealloc_drop"]).await;
+
+    client
+        .send_simple(Query::new("DEALLOCATE dealloc_drop"))
+        .await;
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "DEALLOCATE"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    let prepared = &client.client().prepared_statements;
+    assert_eq!(prepared.len_local(), 1);
+    assert!(prepared.name("dealloc_keep").is_some());
+    assert!(prepared.name("dealloc_drop").is_none());
+    assert_eq!(global_used(&client), 1);
+}
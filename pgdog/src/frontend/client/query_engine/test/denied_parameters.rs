@@ -0,0 +1,124 @@
+use pgdog_config::DeniedParameterAction;
+
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{config, load_test_sharded, load_test_with_user, set},
+    expect_message,
+    net::{CommandComplete, ErrorResponse, NoticeResponse, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+fn deny(action: DeniedParameterAction) {
+    let mut cfg = (*config()).clone();
+    cfg.config.general.denied_parameters = vec!["session_preload_libraries".into()];
+    cfg.config.general.denied_parameter_action = action;
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+}
+
+async fn test_client(action: DeniedParameterAction) -> TestClient {
+    load_test_sharded();
+    deny(action);
+
+    TestClient::new(Parameters::default()).await
+}
+
+#[tokio::test]
+async fn test_denied_parameter_error() {
+    let mut client = test_client(DeniedParameterAction::Error).await;
+
+    client
+        .send_simple(Query::new(
+            "SET session_preload_libraries TO 'auto_explain'",
+        ))
+        .await;
+
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "42501");
+    assert_eq!(
+        error.message,
+        r#"parameter "session_preload_libraries" is not allowed"#
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert!(
+        client
+            .client()
+            .params
+            .get("session_preload_libraries")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_denied_parameter_notice() {
+    let mut client = test_client(DeniedParameterAction::Notice).await;
+
+    client
+        .send_simple(Query::new(
+            "SET session_preload_libraries TO 'auto_explain'",
+        ))
+        .await;
+
+    let notice = expect_message!(client.read().await, NoticeResponse);
+    assert_eq!(notice.message.severity, "WARNING");
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "SET"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert!(
+        client
+            .client()
+            .params
+            .get("session_preload_libraries")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_allowed_parameter() {
+    let mut client = test_client(DeniedParameterAction::Error).await;
+
+    client
+        .send_simple(Query::new("SET statement_timeout TO '10s'"))
+        .await;
+
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "SET"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert!(client.client().params.get("statement_timeout").is_some());
+}
+
+#[tokio::test]
+async fn test_denied_parameter_set_config() {
+    // Single primary: the query parser only runs for some statements.
+    load_test_with_user("pgdog");
+    deny(DeniedParameterAction::Error);
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    for query in [
+        "SELECT set_config('session_preload_libraries', 'auto_explain', false)",
+        "SELECT pg_catalog.set_config('session_preload_libraries', 'auto_explain', true)",
+    ] {
+        client.send_simple(Query::new(query)).await;
+
+        let error = expect_message!(client.read().await, ErrorResponse);
+        assert_eq!(error.code, "42501", "{}", query);
+        expect_message!(client.read().await, ReadyForQuery);
+    }
+
+    client
+        .send_simple(Query::new(
+            "SET session_preload_libraries TO 'auto_explain'",
+        ))
+        .await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "42501");
+    expect_message!(client.read().await, ReadyForQuery);
+}
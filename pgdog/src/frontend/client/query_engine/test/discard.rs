@@ -0,0 +1,68 @@
+use crate::{
+    expect_message,
+    net::{CommandComplete, ErrorResponse, ParseComplete, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+#[tokio::test]
+async fn test_discard_all_resets_client_state() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    client.send(Parse::named("test_discard", "SELECT 1")).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+
+    client
+        .send_simple(Query::new("SET application_name TO 'test_discard'"))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    assert_eq!(client.client().prepared_statements.len_local(), 1);
+    assert!(client.client().params.get("application_name").is_some());
+
+    client.send_simple(Query::new("DISCARD ALL")).await;
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "DISCARD ALL"
+    );
+    assert_eq!(
+        expect_message!(client.read().await, ReadyForQuery).status,
+        'I'
+    );
+
+    assert_eq!(client.client().prepared_statements.len_local(), 0);
+    assert!(
+        client
+            .client()
+            .prepared_statements
+            .name("test_discard")
+            .is_none()
+    );
+    assert!(client.client().params.get("application_name").is_none());
+
+    // The connection is still usable.
+    client.send_simple(Query::new("SELECT 1")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_discard_all_in_transaction() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("DISCARD ALL")).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "25001");
+    assert_eq!(
+        expect_message!(client.read().await, ReadyForQuery).status,
+        'E'
+    );
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
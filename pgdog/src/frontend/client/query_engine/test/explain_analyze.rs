@@ -0,0 +1,35 @@
+use crate::net::{DataRow, FromBytes, ToBytes};
+
+use super::prelude::*;
+
+#[tokio::test]
+async fn test_explain_analyze_labels_shards() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    client
+        .send_simple(Query::new("EXPLAIN ANALYZE SELECT * FROM sharded"))
+        .await;
+    let messages = client.read_until('Z').await.unwrap();
+
+    let lines = messages
+        .iter()
+        .filter(|message| message.code() == 'D')
+        .map(|message| {
+            DataRow::from_bytes(message.to_bytes())
+                .unwrap()
+                .get_text(0)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines.first().map(|line| line.as_str()), Some("Shard 0:"));
+    assert!(lines.iter().any(|line| line == "Shard 1:"));
+    assert!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("  "))
+            .any(|line| line.contains("Execution Time:"))
+    );
+    assert!(lines.last().unwrap().starts_with("Total Execution Time: "));
+    assert!(lines.last().unwrap().contains("shards: 2"));
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use pgdog_postgres_types::Format;
+use tokio::io::AsyncReadExt;
+use tokio::time::timeout;
+
+use crate::{
+    config::{config, set},
+    expect_message,
+    net::{DataRow, ErrorResponse, RowDescription},
+};
+
+use super::prelude::*;
+
+/// Read a setting from the server connection.
+async fn server_setting(client: &mut TestClient, name: &str) -> String {
+    client
+        .send_simple(Query::new(format!("SELECT current_setting('{}')", name)))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+
+    row.get::<String>(0, Format::Text).unwrap()
+}
+
+/// Expect exactly one error, followed by the connection closing.
+async fn expect_fatal(client: &mut SpawnedClient) -> ErrorResponse {
+    let messages = client.read_until('E').await;
+    let error = ErrorResponse::try_from(messages.last().unwrap().clone()).unwrap();
+    assert_eq!(error.severity, "FATAL");
+
+    let mut buf = [0u8; 1];
+    let n = timeout(Duration::from_secs(1), client.conn.read(&mut buf))
+        .await
+        .expect("stream was not closed after FATAL error")
+        .expect("read EOF");
+    assert_eq!(n, 0, "expected socket to be closed after FATAL error");
+
+    error
+}
+
+#[tokio::test]
+async fn test_timeouts_passthrough() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    for query in [
+        "SET lock_timeout TO '1234ms'",
+        "SET idle_in_transaction_session_timeout TO '4321ms'",
+    ] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    assert_eq!(server_setting(&mut client, "lock_timeout").await, "1234ms");
+    assert_eq!(
+        server_setting(&mut client, "idle_in_transaction_session_timeout").await,
+        "4321ms"
+    );
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_server_idle_in_transaction_timeout() {
+    let mut client = SpawnedClient::new_default(Parameters::default()).await;
+
+    client
+        .send(Query::new(
+            "SET idle_in_transaction_session_timeout TO '100ms'",
+        ))
+        .await;
+    client.read_until('Z').await;
+    client.send(Query::new("BEGIN")).await;
+    client.read_until('Z').await;
+
+    // Postgres closes the connection, the client gets its error only.
+    let error = expect_fatal(&mut client).await;
+    assert_eq!(error.code, "25P03");
+}
+
+#[tokio::test]
+async fn test_client_idle_in_transaction_timeout() {
+    let mut client = SpawnedClient::new_default(Parameters::default()).await;
+
+    // Checked by the client before every request.
+    let mut config = (*config()).clone();
+    config.config.general.client_idle_in_transaction_timeout = 100;
+    set(config).unwrap();
+
+    client.send(Query::new("BEGIN")).await;
+    client.read_until('Z').await;
+    client.send(Query::new("SELECT 1")).await;
+    client.read_until('Z').await;
+
+    let error = expect_fatal(&mut client).await;
+    assert_eq!(error.code, "25P03");
+    assert_eq!(
+        error.detail.as_deref(),
+        Some("client_idle_in_transaction_timeout of 100ms expired")
+    );
+}
+
+/// Check that the transaction holding the advisory lock was rolled back.
+async fn expect_lock_released(key: i64) {
+    let mut client = SpawnedClient::new_default(Parameters::default()).await;
+
+    for _ in 0..50 {
+        client
+            .send(Query::new(format!(
+                "SELECT pg_try_advisory_xact_lock({})",
+                key
+            )))
+            .await;
+        let messages = client.read_until('Z').await;
+        let row = DataRow::try_from(messages[1].clone()).unwrap();
+        if row.get::<bool>(0, Format::Text).unwrap() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    panic!("transaction was not rolled back");
+}
+
+async fn max_transaction_duration_client(key: i64) -> SpawnedClient {
+    let mut client = SpawnedClient::new_default(Parameters::default()).await;
+
+    let mut config = (*config()).clone();
+    config.config.general.max_transaction_duration = 200;
+    set(config).unwrap();
+
+    client.send(Query::new("BEGIN")).await;
+    client.read_until('Z').await;
+    client
+        .send(Query::new(format!("SELECT pg_advisory_xact_lock({})", key)))
+        .await;
+    client.read_until('Z').await;
+
+    client
+}
+
+#[tokio::test]
+async fn test_max_transaction_duration_idle() {
+    let key = 1_645_001;
+    let mut client = max_transaction_duration_client(key).await;
+
+    let error = expect_fatal(&mut client).await;
+    assert_eq!(error.code, "25P04");
+    assert_eq!(
+        error.detail.as_deref(),
+        Some("max_transaction_duration of 200ms expired, the transaction was rolled back")
+    );
+
+    expect_lock_released(key).await;
+}
+
+#[tokio::test]
+async fn test_max_transaction_duration_active() {
+    let key = 1_645_002;
+    let mut client = max_transaction_duration_client(key).await;
+
+    // Query is still running when the transaction runs out of time.
+    client.send(Query::new("SELECT pg_sleep(5)")).await;
+
+    let error = expect_fatal(&mut client).await;
+    assert_eq!(error.code, "25P04");
+
+    expect_lock_released(key).await;
+}
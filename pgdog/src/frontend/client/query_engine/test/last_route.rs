@@ -0,0 +1,49 @@
+use crate::{
+    expect_message,
+    net::{DataRow, RowDescription},
+};
+
+use super::prelude::*;
+
+async fn last_route(client: &mut TestClient) -> String {
+    client
+        .send_simple(Query::new("SHOW pgdog.last_route"))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+    row.get_text(0).unwrap()
+}
+
+#[tokio::test]
+async fn test_show_last_route() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    // Nothing was routed yet.
+    assert_eq!(last_route(&mut client).await, "");
+
+    for shard in [0, 1] {
+        let id = client.random_id_for_shard(shard);
+        client
+            .send_simple(Query::new(format!(
+                "SELECT * FROM sharded WHERE id = {}",
+                id
+            )))
+            .await;
+        client.read_until('Z').await.unwrap();
+
+        assert_eq!(
+            last_route(&mut client).await,
+            format!("shard={}, role=replica", shard)
+        );
+    }
+
+    client
+        .send_simple(Query::new("SELECT * FROM sharded"))
+        .await;
+    client.read_until('Z').await.unwrap();
+    assert_eq!(last_route(&mut client).await, "shard=all, role=replica");
+
+    // Showing the route doesn't replace it.
+    assert_eq!(last_route(&mut client).await, "shard=all, role=replica");
+}
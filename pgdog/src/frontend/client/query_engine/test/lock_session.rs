@@ -1,5 +1,30 @@
+use crate::{
+    expect_message,
+    frontend::router::sharding::bigint,
+    net::{DataRow, ReadyForQuery, RowDescription},
+};
+
 use super::prelude::*;
 
+/// First lock key from `start` that hashes to the shard in a two-shard cluster.
+fn lock_key(start: i64, shard: usize) -> i64 {
+    (start..)
+        .find(|key| bigint(*key) as usize % 2 == shard)
+        .unwrap()
+}
+
+async fn try_lock(client: &mut TestClient, key: i64) -> bool {
+    client
+        .send_simple(Query::new(
+            format!("SELECT pg_try_advisory_xact_lock({})", key).as_str(),
+        ))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+    row.get_text(0).unwrap() == "t"
+}
+
 #[tokio::test]
 async fn test_lock_session_advisory_lock() {
     let mut test_client = TestClient::new_sharded(Parameters::default()).await;
@@ -19,6 +44,34 @@ async fn test_lock_session_advisory_lock() {
     assert!(test_client.backend_locked());
 }
 
+#[tokio::test]
+async fn test_advisory_lock_same_shard_for_all_clients() {
+    let mut holder = TestClient::new_sharded(Parameters::default()).await;
+    let mut other = TestClient::new_sharded(Parameters::default()).await;
+
+    holder
+        .send_simple(Query::new("SELECT pg_advisory_lock(54321)"))
+        .await;
+    holder.read_until('Z').await.unwrap();
+
+    // The lock is taken on the same shard every time,
+    // so other clients can't get it.
+    for _ in 0..4 {
+        other
+            .send_simple(Query::new("SELECT pg_try_advisory_lock(54321)"))
+            .await;
+        expect_message!(other.read().await, RowDescription);
+        let row = expect_message!(other.read().await, DataRow);
+        other.read_until('Z').await.unwrap();
+        assert_eq!(row.get_text(0).unwrap(), "f");
+    }
+
+    holder
+        .send_simple(Query::new("SELECT pg_advisory_unlock(54321)"))
+        .await;
+    holder.read_until('Z').await.unwrap();
+}
+
 #[tokio::test]
 async fn test_regular_query_not_locked() {
     let mut test_client = TestClient::new_sharded(Parameters::default()).await;
@@ -48,3 +101,93 @@ async fn test_transaction_not_locked() {
 
     assert!(!test_client.backend_locked());
 }
+
+#[tokio::test]
+async fn test_advisory_lock_other_shard_in_pinned_transaction() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    for query in ["BEGIN", "SET pgdog.shard TO 0"] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+
+    // The key belongs to shard 1, but the transaction is on shard 0.
+    client
+        .send_simple(Query::new(
+            format!("SELECT pg_advisory_xact_lock({})", lock_key(1_000, 1)).as_str(),
+        ))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.code, "58000");
+    assert_eq!(
+        error.message,
+        "advisory lock key belongs to a different shard"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+
+    // Keys on the pinned shard are fine.
+    for query in ["BEGIN", "SET pgdog.shard TO 0"] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+    assert!(try_lock(&mut client, lock_key(1_000, 0)).await);
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_advisory_unlock_all_on_all_shards() {
+    let mut holder = TestClient::new_sharded(Parameters::default()).await;
+    let mut other = TestClient::new_sharded(Parameters::default()).await;
+    let keys = [lock_key(2_000, 0), lock_key(2_000, 1)];
+
+    // A cross-shard transaction can hold locks on every shard.
+    holder.send_simple(Query::new("BEGIN")).await;
+    holder.read_until('Z').await.unwrap();
+    for key in keys {
+        holder
+            .send_simple(Query::new(
+                format!("SELECT pg_advisory_lock({})", key).as_str(),
+            ))
+            .await;
+        holder.read_until('Z').await.unwrap();
+        assert!(!try_lock(&mut other, key).await);
+    }
+
+    holder
+        .send_simple(Query::new("SELECT pg_advisory_unlock_all()"))
+        .await;
+    holder.read_until('Z').await.unwrap();
+    holder.send_simple(Query::new("COMMIT")).await;
+    holder.read_until('Z').await.unwrap();
+
+    for key in keys {
+        assert!(try_lock(&mut other, key).await);
+    }
+}
+
+#[tokio::test]
+async fn test_advisory_unlock_all_pinned_to_one_shard() {
+    let mut holder = TestClient::new_sharded(Parameters::default()).await;
+    let mut other = TestClient::new_sharded(Parameters::default()).await;
+    let key = lock_key(3_000, 1);
+
+    holder
+        .send_simple(Query::new(
+            format!("SELECT pg_advisory_lock({})", key).as_str(),
+        ))
+        .await;
+    holder.read_until('Z').await.unwrap();
+    assert!(holder.backend_locked());
+    assert!(!try_lock(&mut other, key).await);
+
+    // The locks are released on the shard the client is pinned to.
+    holder
+        .send_simple(Query::new("SELECT pg_advisory_unlock_all()"))
+        .await;
+    holder.read_until('Z').await.unwrap();
+    assert!(try_lock(&mut other, key).await);
+}
@@ -8,9 +8,16 @@ use crate::{
 };
 
 mod advisory_lock;
+mod audit_log;
+mod binary_format;
 mod close_parse;
 mod close_parse_global_cache;
+mod coalesce;
 mod cross_shard_disabled;
+mod deallocate;
+mod denied_parameters;
+mod discard;
+mod explain_analyze;
 mod extended;
 mod extended_anonymous;
 mod extended_transaction;
@@ -18,24 +25,41 @@ mod fatal_error;
 mod graceful_disconnect;
 mod graceful_shutdown;
 mod idle_in_transaction_recovery;
+mod idle_in_transaction_timeout;
+mod last_route;
 mod lock_session;
 mod manual_lock;
 mod multi_binding;
 mod omni;
 pub mod prelude;
+mod prepared_client_limit;
+mod prepared_import;
+mod prepared_schema_change;
 mod prepared_syntax_error;
+mod primary_reads;
+mod query_label;
+mod query_tags;
+mod rate_limit;
 mod replicas;
+mod retry_read;
 mod rewrite_extended;
 mod rewrite_insert_split;
 mod rewrite_offset;
 mod rewrite_simple_prepared;
+mod routing_notice;
+mod savepoint;
 mod schema_changed;
 mod set;
+mod set_role;
 mod set_schema_sharding;
 mod sharded;
+mod slow_query;
 mod spliced;
+mod temporary_tables;
 mod test_omnisharded;
 mod transaction_state;
+mod truncate;
+mod two_pc;
 
 pub(super) fn test_client() -> Client {
     load_test();
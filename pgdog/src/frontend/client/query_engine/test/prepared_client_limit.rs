@@ -0,0 +1,75 @@
+use pgdog_config::PreparedStatementsClientLimitAction;
+
+use crate::{
+    config::{config, load_test, set},
+    expect_message,
+    net::{BindComplete, ErrorResponse, ParseComplete, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+async fn test_client(action: PreparedStatementsClientLimitAction) -> TestClient {
+    load_test();
+    let mut cfg = (*config()).clone();
+    cfg.config.general.prepared_statements_client_limit = Some(1);
+    cfg.config.general.prepared_statements_client_limit_action = action;
+    set(cfg).unwrap();
+
+    TestClient::new(Parameters::default()).await
+}
+
+async fn prepare(client: &mut TestClient, name: &str, query: &str) {
+    client.send(Parse::named(name, query)).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prepared_client_limit_error() {
+    let mut client = test_client(PreparedStatementsClientLimitAction::Error).await;
+
+    prepare(&mut client, "a", "SELECT 1").await;
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+
+    prepare(&mut client, "b", "SELECT 2").await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "53400");
+    assert_eq!(error.message, "too many prepared statements");
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'I');
+
+    assert_eq!(client.client().prepared_statements.len_local(), 1);
+    assert!(client.client().prepared_statements.name("b").is_none());
+
+    // The first statement still works.
+    client.send(Bind::new_statement("a")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+    expect_message!(client.read().await, BindComplete);
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prepared_client_limit_evict() {
+    let mut client = test_client(PreparedStatementsClientLimitAction::Evict).await;
+
+    prepare(&mut client, "a", "SELECT 1").await;
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+
+    prepare(&mut client, "b", "SELECT 2").await;
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+
+    assert_eq!(client.client().prepared_statements.len_local(), 1);
+    assert!(client.client().prepared_statements.name("a").is_none());
+
+    client.send(Bind::new_statement("b")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+    expect_message!(client.read().await, BindComplete);
+    client.read_until('Z').await.unwrap();
+}
@@ -0,0 +1,50 @@
+use pgdog_postgres_types::Format;
+
+use crate::{
+    expect_message,
+    frontend::{PreparedStatements, prepared_statements::ExportedStatement},
+    net::{BindComplete, CommandComplete, DataRow, ParseComplete, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+const QUERY: &str = "SELECT 42::bigint /* prepared_import */";
+
+#[tokio::test]
+async fn test_imported_statement_is_usable() {
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    let global = PreparedStatements::global();
+    let imported = global.write().import(&[ExportedStatement {
+        query: QUERY.into(),
+        data_types: vec![],
+    }]);
+    assert_eq!(imported, 1);
+    let exported = global.read().export();
+    assert!(exported.iter().any(|stmt| stmt.query == QUERY));
+
+    // Not prepared on any server connection yet,
+    // so it's prepared when the client executes it.
+    client.send(Parse::named("imported", QUERY)).await;
+    client.send(Bind::new_statement("imported")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, BindComplete);
+    let row = expect_message!(client.read().await, DataRow);
+    assert_eq!(row.get::<i64>(0, Format::Text), Some(42));
+    expect_message!(client.read().await, CommandComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // The client is using the imported statement.
+    let global = global.read();
+    let used = global
+        .statements()
+        .iter()
+        .filter(|(key, _)| key.query().unwrap() == QUERY)
+        .map(|(_, stmt)| stmt.used)
+        .collect::<Vec<_>>();
+    assert_eq!(used, vec![1]);
+}
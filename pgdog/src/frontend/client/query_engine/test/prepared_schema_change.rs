@@ -0,0 +1,166 @@
+use crate::{
+    backend::{databases::reload_from_existing, server::test::test_server},
+    config::{PreparedStatementsSchemaChange, config, load_test, set},
+    expect_message,
+    frontend::PreparedStatements,
+    net::{
+        BindComplete, CommandComplete, DataRow, ErrorResponse, ParameterDescription, ParseComplete,
+        ReadyForQuery, RowDescription,
+    },
+};
+
+use super::prelude::*;
+
+/// Single connection pool, so the statement is always
+/// executed on the connection it was prepared on.
+fn load_single_connection_test_pool(schema_change: PreparedStatementsSchemaChange) {
+    load_test();
+
+    let mut config = (*config()).clone();
+    config.config.general.default_pool_size = 1;
+    config.config.general.min_pool_size = 0;
+    config.config.general.prepared_statements_schema_change = schema_change;
+    set(config).unwrap();
+    reload_from_existing().unwrap();
+}
+
+/// Prepare and execute a statement selecting all columns from the table,
+/// then add a column to it, changing the statement's result type.
+async fn prepare_and_alter(client: &mut TestClient, table: &str) {
+    let mut server = test_server().await;
+    server
+        .execute(format!("DROP TABLE IF EXISTS {}", table))
+        .await
+        .unwrap();
+    server
+        .execute(format!("CREATE TABLE {} AS SELECT 1::BIGINT AS id", table))
+        .await
+        .unwrap();
+
+    client
+        .send(Parse::named("test", &format!("SELECT * FROM {}", table)))
+        .await;
+    client.send(Bind::new_statement("test")).await;
+    client.send(Describe::new_statement("test")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, BindComplete);
+    expect_message!(client.read().await, ParameterDescription);
+    expect_message!(client.read().await, RowDescription);
+    assert_eq!(expect_message!(client.read().await, DataRow).len(), 1);
+    expect_message!(client.read().await, CommandComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+    assert!(row_description(table).is_some());
+
+    server
+        .execute(format!("ALTER TABLE {} ADD COLUMN value TEXT", table))
+        .await
+        .unwrap();
+}
+
+async fn execute(client: &mut TestClient) {
+    client.send(Bind::new_statement("test")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+}
+
+/// RowDescription of the statement stored in the global cache.
+fn row_description(table: &str) -> Option<RowDescription> {
+    let query = format!("SELECT * FROM {}", table);
+    let global = PreparedStatements::global();
+    let global = global.read();
+    let name = global
+        .names()
+        .iter()
+        .find(|(_, stmt)| stmt.query() == query)
+        .map(|(name, _)| name.clone())
+        .unwrap();
+    global.row_description(&name)
+}
+
+/// The schema change error is returned to the client
+/// and the statement works again on the next try.
+#[tokio::test]
+async fn test_prepared_schema_change_error() {
+    load_single_connection_test_pool(PreparedStatementsSchemaChange::Error);
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare_and_alter(&mut client, "prepared_schema_change_error").await;
+
+    execute(&mut client).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "0A000");
+    assert!(error.is_schema_changed());
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // Stale RowDescription is removed from the global cache.
+    assert!(row_description("prepared_schema_change_error").is_none());
+
+    execute(&mut client).await;
+    expect_message!(client.read().await, BindComplete);
+    assert_eq!(expect_message!(client.read().await, DataRow).len(), 2);
+    expect_message!(client.read().await, CommandComplete);
+    expect_message!(client.read().await, ReadyForQuery);
+}
+
+/// The statement is prepared again and retried transparently.
+#[tokio::test]
+async fn test_prepared_schema_change_retry() {
+    load_single_connection_test_pool(PreparedStatementsSchemaChange::Retry);
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare_and_alter(&mut client, "prepared_schema_change_retry").await;
+
+    execute(&mut client).await;
+    expect_message!(client.read().await, BindComplete);
+    assert_eq!(expect_message!(client.read().await, DataRow).len(), 2);
+    expect_message!(client.read().await, CommandComplete);
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'I');
+
+    assert!(row_description("prepared_schema_change_retry").is_none());
+}
+
+/// Retry is not possible inside a transaction.
+#[tokio::test]
+async fn test_prepared_schema_change_no_retry_in_transaction() {
+    load_single_connection_test_pool(PreparedStatementsSchemaChange::Retry);
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    prepare_and_alter(&mut client, "prepared_schema_change_transaction").await;
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    execute(&mut client).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "0A000");
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'E');
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[test]
+fn test_schema_changed_translated_message() {
+    let error = ErrorResponse {
+        code: "0A000".into(),
+        message: "el plan almacenado no debe cambiar el tipo de resultado".into(),
+        routine: Some("RevalidateCachedQuery".into()),
+        ..Default::default()
+    };
+    assert!(error.is_schema_changed());
+
+    let error = ErrorResponse {
+        code: "0A000".into(),
+        message: "FOR UPDATE is not allowed with DISTINCT clause".into(),
+        routine: Some("CheckSelectLocking".into()),
+        ..Default::default()
+    };
+    assert!(!error.is_schema_changed());
+}
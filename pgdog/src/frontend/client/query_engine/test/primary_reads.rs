@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use pgdog_config::ReadWriteSplit;
+
+use crate::{
+    config::load_test_replicas,
+    expect_message,
+    net::{DataRow, RowDescription},
+};
+
+use super::{change_config, prelude::*};
+
+/// Replicas are configured with `read_only`, so their
+/// connections have `default_transaction_read_only` enabled.
+async fn read_only(client: &mut TestClient) -> String {
+    client
+        .send_simple(Query::new(
+            "SELECT current_setting('default_transaction_read_only')",
+        ))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+    row.get_text(0).unwrap()
+}
+
+#[tokio::test]
+async fn test_primary_reads_after_connect() {
+    load_test_replicas();
+    change_config(|general| {
+        general.read_write_split = ReadWriteSplit::ExcludePrimary;
+        general.primary_reads_after_connect = 2;
+    });
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    for _ in 0..2 {
+        assert_eq!(read_only(&mut client).await, "off");
+    }
+
+    for _ in 0..3 {
+        assert_eq!(read_only(&mut client).await, "on");
+    }
+}
+
+#[tokio::test]
+async fn test_primary_reads_after_connect_window() {
+    load_test_replicas();
+    change_config(|general| {
+        general.read_write_split = ReadWriteSplit::ExcludePrimary;
+        general.primary_reads_after_connect_window = 250;
+    });
+
+    let mut client = TestClient::new(Parameters::default()).await;
+    assert_eq!(read_only(&mut client).await, "off");
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(read_only(&mut client).await, "on");
+}
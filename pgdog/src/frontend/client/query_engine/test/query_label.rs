@@ -0,0 +1,48 @@
+use crate::{
+    config::{config, set},
+    expect_message,
+    net::{DataRow, RowDescription},
+};
+
+use super::prelude::*;
+
+async fn sharded_client(query_labels: bool) -> TestClient {
+    let client = TestClient::new_sharded(Parameters::default()).await;
+    let mut cfg = (*config()).clone();
+    cfg.config.general.query_labels = query_labels;
+    set(cfg).unwrap();
+    client
+}
+
+async fn current_query(client: &mut TestClient, query: &str) -> String {
+    client.send_simple(Query::new(query)).await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+    row.get_text(0).unwrap()
+}
+
+#[tokio::test]
+async fn test_query_label_sent_to_server() {
+    let mut client = sharded_client(true).await;
+    let addr = client.conn.local_addr().unwrap();
+
+    for shard in [0, 1] {
+        let query = format!("/* pgdog_shard: {} */ SELECT current_query()", shard);
+        let current = current_query(&mut client, &query).await;
+
+        // Routing used the client's comment, not the label.
+        assert_eq!(
+            current,
+            format!("/* pgdog: client={} shard={} */ {}", addr, shard, query)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_query_label_disabled() {
+    let mut client = sharded_client(false).await;
+
+    let query = "/* pgdog_shard: 1 */ SELECT current_query()";
+    assert_eq!(current_query(&mut client, query).await, query);
+}
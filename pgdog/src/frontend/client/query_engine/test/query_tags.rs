@@ -0,0 +1,28 @@
+use crate::stats::tags::tags;
+
+use super::prelude::*;
+
+fn tagged_queries(tag: &str) -> usize {
+    tags()
+        .counts()
+        .iter()
+        .filter(|counts| counts.tag == tag)
+        .map(|counts| counts.queries)
+        .sum()
+}
+
+#[tokio::test]
+async fn test_query_tags_counted() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    for query in [
+        "/* tag: test_query_tags_counted */ SELECT 1",
+        "SELECT 1 /* pgdog_shard: 1 tag: test_query_tags_counted */",
+        "SELECT 1",
+    ] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+
+    assert_eq!(tagged_queries("test_query_tags_counted"), 2);
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{User, config, load_test, set},
+    expect_message,
+    net::{ErrorResponse, ReadyForQuery},
+};
+
+use super::prelude::*;
+
+fn load_rate_limited(timeout: u64, f: impl FnOnce(&mut User)) {
+    load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.rate_limit_timeout = timeout;
+    f(&mut cfg.users.users[0]);
+    cfg.users.users.push(User {
+        name: "pgdog_unlimited".into(),
+        database: "pgdog".into(),
+        password: Some("pgdog".into()),
+        server_user: Some("pgdog".into()),
+        server_password: Some("pgdog".into()),
+        ..Default::default()
+    });
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+}
+
+async fn run(client: &mut TestClient, query: &str) {
+    client.send_simple(Query::new(query)).await;
+    client.read_until('Z').await.unwrap();
+}
+
+async fn expect_rate_limited(client: &mut TestClient, query: &str) {
+    client.send_simple(Query::new(query)).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "53400");
+    expect_message!(client.read().await, ReadyForQuery);
+}
+
+#[tokio::test]
+async fn test_query_rate_limit_rejects() {
+    load_rate_limited(0, |user| user.query_rate_limit = Some(2));
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    run(&mut client, "SELECT 1").await;
+    run(&mut client, "SELECT 1").await;
+    expect_rate_limited(&mut client, "SELECT 1").await;
+
+    // Other users have their own limits.
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog_unlimited");
+    let mut other = TestClient::new(params).await;
+
+    for _ in 0..10 {
+        run(&mut other, "SELECT 1").await;
+    }
+}
+
+#[tokio::test]
+async fn test_query_rate_limit_waits() {
+    load_rate_limited(1_000, |user| user.query_rate_limit = Some(2));
+
+    let mut client = TestClient::new(Parameters::default()).await;
+    let started = Instant::now();
+
+    for _ in 0..3 {
+        run(&mut client, "SELECT 1").await;
+    }
+
+    assert!(
+        started.elapsed() >= Duration::from_millis(400),
+        "third query should have waited for a token"
+    );
+}
+
+#[tokio::test]
+async fn test_transaction_rate_limit() {
+    load_rate_limited(0, |user| user.transaction_rate_limit = Some(1));
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    // Queries inside the transaction don't start new ones.
+    run(&mut client, "BEGIN").await;
+    run(&mut client, "SELECT 1").await;
+    run(&mut client, "COMMIT").await;
+
+    expect_rate_limited(&mut client, "SELECT 1").await;
+}
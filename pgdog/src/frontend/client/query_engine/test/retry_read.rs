@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use tokio::{
+    spawn,
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
+
+use crate::{
+    backend::{databases::databases, server::test::test_server},
+    expect_message,
+    net::{BindComplete, CommandComplete, DataRow, ParseComplete, ReadyForQuery, RowDescription},
+};
+
+use super::prelude::*;
+
+/// Terminate the backend running a query that starts with `prefix`
+/// as soon as it shows up in `pg_stat_activity`.
+fn terminate(prefix: &'static str) -> JoinHandle<()> {
+    spawn(async move {
+        let mut server = test_server().await;
+        let query = format!(
+            "SELECT count(pg_terminate_backend(pid)) FROM pg_stat_activity \
+             WHERE state = 'active' AND query LIKE '{}%'",
+            prefix
+        );
+        timeout(Duration::from_secs(5), async {
+            while server.fetch_all::<i64>(query.as_str()).await.unwrap() == [0] {
+                sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+    })
+}
+
+/// The replica serving a read is terminated before it returns any rows.
+/// The read is retried on the other database and the client
+/// gets the result as if nothing happened.
+#[tokio::test]
+async fn test_retry_read_on_another_replica() {
+    let mut client = TestClient::new_replicas(Parameters::default()).await;
+
+    let terminate = terminate("SELECT 42 FROM pg_sleep(1) /* retry_read */");
+
+    client
+        .send_simple(Query::new("SELECT 42 FROM pg_sleep(1) /* retry_read */"))
+        .await;
+    terminate.await.unwrap();
+
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    assert_eq!(row.get_int(0, true), Some(42));
+    assert_eq!(
+        expect_message!(client.read().await, CommandComplete).command(),
+        "SELECT 1"
+    );
+    assert_eq!(
+        expect_message!(client.read().await, ReadyForQuery).status,
+        'I'
+    );
+
+    // The error is counted against the database that failed.
+    let failed = databases().cluster(("pgdog", "pgdog")).unwrap().shards()[0]
+        .pools()
+        .into_iter()
+        .filter(|pool| pool.state().errors > 0)
+        .count();
+    assert_eq!(failed, 1);
+}
+
+/// The read is retried with the client's session parameters
+/// and prepared statement.
+#[tokio::test]
+async fn test_retry_read_syncs_session() {
+    let mut client = TestClient::new_replicas(Parameters::default()).await;
+
+    client
+        .send_simple(Query::new("SET lock_timeout TO '4321ms'"))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    let terminate = terminate("SELECT current_setting('lock_timeout') FROM pg_sleep(1)");
+
+    client
+        .send(Parse::named(
+            "retry_read",
+            "SELECT current_setting('lock_timeout') FROM pg_sleep(1) /* retry_read */",
+        ))
+        .await;
+    client.send(Bind::new_statement("retry_read")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+    terminate.await.unwrap();
+
+    expect_message!(client.read().await, ParseComplete);
+    expect_message!(client.read().await, BindComplete);
+    let row = expect_message!(client.read().await, DataRow);
+    assert_eq!(row.get_text(0), Some("4321ms".into()));
+    expect_message!(client.read().await, CommandComplete);
+    assert_eq!(
+        expect_message!(client.read().await, ReadyForQuery).status,
+        'I'
+    );
+}
+
+/// Writes are never retried.
+#[tokio::test]
+async fn test_no_retry_write() {
+    let mut client = TestClient::new_replicas(Parameters::default()).await;
+
+    let terminate = terminate("CREATE TEMPORARY TABLE retry_write");
+
+    let result = client
+        .try_send_simple(Query::new(
+            "CREATE TEMPORARY TABLE retry_write AS SELECT 1 FROM pg_sleep(1)",
+        ))
+        .await;
+    terminate.await.unwrap();
+
+    assert!(result.is_err());
+}
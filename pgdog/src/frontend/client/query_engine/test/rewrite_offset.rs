@@ -18,7 +18,7 @@ async fn run_test(messages: Vec<ProtocolMessage>) -> Option<OffsetPlan> {
     engine.parse_and_rewrite(&mut context).await.unwrap();
 
     match context.rewrite_result {
-        Some(RewriteResult::InPlace { offset }) => offset,
+        Some(RewriteResult::InPlace { offset, .. }) => offset,
         other => panic!("expected InPlace, got {:?}", other),
     }
 }
@@ -83,13 +83,20 @@ async fn test_offset_limit_no_offset_no_plan() {
 }
 
 #[tokio::test]
-async fn test_offset_limit_no_limit_no_plan() {
+async fn test_offset_no_limit() {
     let offset = run_test(vec![ProtocolMessage::Query(Query::new(
         "SELECT * FROM test OFFSET 5",
     ))])
     .await;
 
-    assert!(offset.is_none());
+    let offset = offset.expect("expected OffsetPlan");
+    assert_eq!(
+        offset.limit,
+        Limit {
+            limit: None,
+            offset: Some(5)
+        }
+    );
 }
 
 #[tokio::test]
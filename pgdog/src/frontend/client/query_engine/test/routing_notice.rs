@@ -0,0 +1,54 @@
+use crate::{
+    config::{config, set},
+    expect_message,
+    net::{NoticeResponse, RowDescription},
+};
+
+use super::prelude::*;
+
+async fn sharded_client(routing_notices: bool) -> TestClient {
+    let client = TestClient::new_sharded(Parameters::default()).await;
+    let mut cfg = (*config()).clone();
+    cfg.config.general.routing_notices = routing_notices;
+    set(cfg).unwrap();
+    client
+}
+
+#[tokio::test]
+async fn test_broadcast_read_notice() {
+    let mut client = sharded_client(true).await;
+
+    client
+        .send_simple(Query::new("SELECT id FROM sharded"))
+        .await;
+
+    let notice = expect_message!(client.read().await, NoticeResponse);
+    assert_eq!(notice.message.severity, "NOTICE");
+    assert_eq!(notice.message.message, "query sent to all 2 shards");
+    expect_message!(client.read().await, RowDescription);
+    client.read_until('Z').await.unwrap();
+
+    // Direct-to-shard queries are not notable.
+    let id = client.random_id_for_shard(0);
+    client
+        .send_simple(Query::new(format!(
+            "SELECT id FROM sharded WHERE id = {}",
+            id
+        )))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_broadcast_read_no_notice() {
+    let mut client = sharded_client(false).await;
+
+    client
+        .send_simple(Query::new("SELECT id FROM sharded"))
+        .await;
+
+    expect_message!(client.read().await, RowDescription);
+    let messages = client.read_until('Z').await.unwrap();
+    assert!(messages.iter().all(|message| message.code() != 'N'));
+}
@@ -0,0 +1,95 @@
+use crate::{expect_message, frontend::client::TransactionType, net::ReadyForQuery};
+
+use super::prelude::*;
+
+async fn rollback_to_savepoint(mut client: TestClient) {
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("SAVEPOINT sp")).await;
+    client.read_until('Z').await.unwrap();
+    assert!(client.backend_locked());
+
+    client.send_simple(Query::new("SELECT 1/0")).await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.code, "22012");
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'E');
+    assert_eq!(
+        client.client().transaction,
+        Some(TransactionType::ErrorReadWrite)
+    );
+
+    client
+        .send_simple(Query::new("ROLLBACK TO SAVEPOINT sp"))
+        .await;
+    client.read_until('Z').await.unwrap();
+    assert_eq!(
+        client.client().transaction,
+        Some(TransactionType::ReadWrite)
+    );
+    assert!(client.backend_locked());
+
+    // Transaction can be used again.
+    client.send_simple(Query::new("SELECT 1")).await;
+    let messages = client.read_until('Z').await.unwrap();
+    assert!(messages.iter().any(|message| message.code() == 'D'));
+    let rfq = ReadyForQuery::try_from(messages.last().unwrap().clone()).unwrap();
+    assert_eq!(rfq.status, 'T');
+
+    client.send_simple(Query::new("RELEASE SAVEPOINT sp")).await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+    assert!(client.client().transaction.is_none());
+    assert!(!client.backend_locked());
+}
+
+#[tokio::test]
+async fn test_rollback_to_savepoint() {
+    rollback_to_savepoint(TestClient::new(Parameters::default()).await).await;
+}
+
+#[tokio::test]
+async fn test_rollback_to_savepoint_sharded() {
+    rollback_to_savepoint(TestClient::new_sharded(Parameters::default()).await).await;
+}
+
+#[tokio::test]
+async fn test_savepoint_cross_shard_transactions_disabled() {
+    let mut client = TestClient::new_cross_shard_transactions_disabled(Parameters::default()).await;
+    let id = client.random_id_for_shard(1);
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Savepoints follow the transaction, so they're not rejected.
+    for query in [
+        "SAVEPOINT sp",
+        "ROLLBACK TO SAVEPOINT sp",
+        "RELEASE SAVEPOINT sp",
+    ] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+}
@@ -485,6 +485,68 @@ async fn test_set_sharding_key_pins_transaction_to_one_shard() {
     test_client.read_until('Z').await.unwrap();
 }
 
+/// The configured `sharding_key_parameter` is used as the sharding key,
+/// including when it's set with `SET LOCAL`, until it's reset.
+#[tokio::test]
+async fn test_sharding_key_parameter() {
+    load_test_sharded();
+    let mut cfg = (*config()).clone();
+    cfg.config.sharded_schemas.clear();
+    cfg.config
+        .sharded_tables
+        .retain(|t| t.name.as_deref() == Some("sharded"));
+    cfg.config.general.sharding_key_parameter = Some("app.current_tenant".into());
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+
+    let mut test_client = TestClient::new(Parameters::default()).await;
+    let shards = shard_count(&mut test_client);
+    assert!(shards > 1, "test requires a multi-shard cluster");
+
+    for query in ["BEGIN", "SET LOCAL app.current_tenant TO '1'"] {
+        test_client.send_simple(Query::new(query)).await;
+        test_client.read_until('Z').await.unwrap();
+    }
+
+    test_client
+        .send_simple(Query::new("SELECT * FROM sharded"))
+        .await;
+    test_client.read_until('Z').await.unwrap();
+    assert_eq!(connected_servers(&mut test_client), 1);
+
+    test_client.send_simple(Query::new("ROLLBACK")).await;
+    test_client.read_until('Z').await.unwrap();
+    assert!(
+        test_client
+            .client()
+            .params
+            .get("app.current_tenant")
+            .is_none()
+    );
+
+    for query in [
+        "SET app.current_tenant TO '1'",
+        "BEGIN",
+        "SELECT * FROM sharded",
+    ] {
+        test_client.send_simple(Query::new(query)).await;
+        test_client.read_until('Z').await.unwrap();
+    }
+    assert_eq!(connected_servers(&mut test_client), 1);
+
+    test_client.send_simple(Query::new("ROLLBACK")).await;
+    test_client.read_until('Z').await.unwrap();
+
+    for query in ["RESET app.current_tenant", "BEGIN", "SELECT * FROM sharded"] {
+        test_client.send_simple(Query::new(query)).await;
+        test_client.read_until('Z').await.unwrap();
+    }
+    assert_eq!(connected_servers(&mut test_client), shards);
+
+    test_client.send_simple(Query::new("ROLLBACK")).await;
+    test_client.read_until('Z').await.unwrap();
+}
+
 /// Once a query has connected the transaction to a server, `SET pgdog.shard` is
 /// rejected: the connection is already pinned.
 #[tokio::test]
@@ -0,0 +1,73 @@
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{config, load_test, set},
+    net::{DataRow, FromBytes, ToBytes},
+};
+
+use super::prelude::*;
+
+/// Single server connection, so clients share it.
+fn load_single_connection_test_pool() {
+    load_test();
+
+    let mut config = (*config()).clone();
+    config.config.general.default_pool_size = 1;
+    config.config.general.min_pool_size = 0;
+    set(config).unwrap();
+    reload_from_existing().unwrap();
+}
+
+async fn current_user(client: &mut TestClient) -> String {
+    client.send_simple(Query::new("SELECT current_user")).await;
+    let messages = client.read_until('Z').await.unwrap();
+
+    messages
+        .iter()
+        .find(|message| message.code() == 'D')
+        .map(|message| {
+            DataRow::from_bytes(message.to_bytes())
+                .unwrap()
+                .get_text(0)
+                .unwrap()
+        })
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_set_role_in_transaction_does_not_leak() {
+    load_single_connection_test_pool();
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    for query in ["BEGIN", "SELECT 1", "SET ROLE pgdog1"] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+    assert_eq!(current_user(&mut client).await, "pgdog1");
+
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+    assert!(!client.backend_connected());
+
+    let mut other = TestClient::new(Parameters::default()).await;
+    assert_eq!(current_user(&mut other).await, "pgdog");
+}
+
+#[tokio::test]
+async fn test_reset_role_in_transaction() {
+    load_single_connection_test_pool();
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    for query in ["BEGIN", "SELECT 1", "SET ROLE pgdog1", "RESET ROLE"] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+    assert_eq!(current_user(&mut client).await, "pgdog");
+
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+
+    let mut other = TestClient::new(Parameters::default()).await;
+    assert_eq!(current_user(&mut other).await, "pgdog");
+}
@@ -1,6 +1,11 @@
 use crate::{
+    backend::databases::reload_from_existing,
+    config::{config, load_test_sharded, set},
     expect_message,
-    net::{CommandComplete, Parameters, Query, ReadyForQuery},
+    net::{
+        CommandComplete, DataRow, ErrorResponse, Fastpath, Format, Message, Parameters, Query,
+        ReadyForQuery,
+    },
 };
 
 use super::prelude::*;
@@ -96,6 +101,77 @@ async fn test_sharded_insert_returning_from_all_shards() {
     client.read_until('Z').await.unwrap();
 }
 
+#[tokio::test]
+async fn test_sharded_insert_returning_extended_binary() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let id_shard0 = client.random_id_for_shard(0);
+    let id_shard1 = client.random_id_for_shard(1);
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({}, {})",
+            id_shard0, id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Split INSERT with RETURNING, with ids requested in binary.
+    client
+        .send(Parse::new_anonymous(
+            "INSERT INTO sharded (id, value) VALUES ($1, 'ret1'), ($2, 'ret2') RETURNING id",
+        ))
+        .await;
+    client
+        .send(Bind::new_params_codes_results(
+            "",
+            &[
+                Parameter::new(id_shard0.to_string().as_bytes()),
+                Parameter::new(id_shard1.to_string().as_bytes()),
+            ],
+            &[],
+            &[1],
+        ))
+        .await;
+    client.send(Describe::new_portal("")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+
+    let mut ids = messages
+        .iter()
+        .filter(|m| m.code() == 'D')
+        .map(|m| {
+            DataRow::try_from(m.clone())
+                .unwrap()
+                .get::<i64>(0, Format::Binary)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    ids.sort();
+
+    let mut expected = vec![id_shard0, id_shard1];
+    expected.sort();
+    assert_eq!(
+        ids, expected,
+        "split INSERT RETURNING should return ids from all shards"
+    );
+
+    let cc_msg = messages.iter().find(|m| m.code() == 'C').unwrap();
+    let cc = CommandComplete::try_from(cc_msg.clone()).unwrap();
+    assert_eq!(cc.command(), "INSERT 0 2");
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({}, {})",
+            id_shard0, id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
 #[tokio::test]
 async fn test_sharded_update_sums_row_counts() {
     let mut client = TestClient::new_sharded(Parameters::default()).await;
@@ -255,3 +331,446 @@ async fn test_sharded_delete_returning_from_all_shards() {
     let cc = CommandComplete::try_from(cc_msg.clone()).unwrap();
     assert_eq!(cc.command(), "DELETE 2");
 }
+
+#[tokio::test]
+async fn test_sharded_insert_requires_sharding_key() {
+    load_test_sharded();
+    let mut cfg = (*config()).clone();
+    cfg.config.general.require_sharding_key = true;
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    client
+        .send_simple(Query::new("INSERT INTO sharded (value) VALUES ('test')"))
+        .await;
+
+    let err = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(err.code, "58000");
+    assert_eq!(
+        err.message,
+        "INSERT on a sharded table must specify the sharding key"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+}
+
+#[tokio::test]
+async fn test_sharded_count_distinct() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let id_shard0 = client.random_id_for_shard(0);
+    let id_shard1 = client.random_id_for_shard(1);
+    let other_shard1 = client.random_id_for_shard(1);
+    let ids = format!("{}, {}, {}", id_shard0, id_shard1, other_shard1);
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({ids})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // The same value is present on both shards.
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES ({}, 'same'), ({}, 'same'), ({}, 'other')",
+            id_shard0, id_shard1, other_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT COUNT(DISTINCT value), COUNT(value) FROM sharded WHERE id IN ({ids})"
+        )))
+        .await;
+    let messages = client.read_until('Z').await.unwrap();
+
+    let rows: Vec<_> = messages
+        .into_iter()
+        .filter(|message| message.code() == 'D')
+        .map(|message| DataRow::try_from(message).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i64>(0, Format::Text), Some(2));
+    assert_eq!(rows[0].get::<i64>(1, Format::Text), Some(3));
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({ids})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sharded_group_by_merges_groups() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let ids = [
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(1),
+        client.random_id_for_shard(1),
+    ];
+    let list = ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Group "a" is present on both shards.
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES ({}, 'a'), ({}, 'b'), ({}, 'a'), ({}, 'a')",
+            ids[0], ids[1], ids[2], ids[3]
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT value, COUNT(*), SUM(id) FROM sharded WHERE id IN ({list}) GROUP BY value ORDER BY value"
+        )))
+        .await;
+    let messages = client.read_until('Z').await.unwrap();
+
+    let groups: Vec<_> = messages
+        .into_iter()
+        .filter(|message| message.code() == 'D')
+        .map(|message| {
+            let row = DataRow::try_from(message).unwrap();
+            (
+                row.get::<String>(0, Format::Text).unwrap(),
+                row.get::<i64>(1, Format::Text).unwrap(),
+                row.get::<i64>(2, Format::Text).unwrap(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            ("a".to_string(), 3, ids[0] + ids[2] + ids[3]),
+            ("b".to_string(), 1, ids[1]),
+        ]
+    );
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+async fn count_groups(client: &mut TestClient, query: String) -> Vec<(String, i64)> {
+    client.send_simple(Query::new(query)).await;
+    client
+        .read_until('Z')
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|message| message.code() == 'D')
+        .map(|message| {
+            let row = DataRow::try_from(message).unwrap();
+            (
+                row.get::<String>(0, Format::Text).unwrap(),
+                row.get::<i64>(1, Format::Text).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sharded_having_after_merge() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let ids = [
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(0),
+        client.random_id_for_shard(1),
+        client.random_id_for_shard(1),
+    ];
+    let list = ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Group "a" has one row on each shard, "b" and "c" have one row in total.
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES ({}, 'a'), ({}, 'b'), ({}, 'a'), ({}, 'c')",
+            ids[0], ids[1], ids[2], ids[3]
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // "a" only passes once both shards are merged.
+    let passed = count_groups(
+        &mut client,
+        format!(
+            "SELECT value, COUNT(*) FROM sharded WHERE id IN ({list}) GROUP BY value HAVING COUNT(*) > 1"
+        ),
+    )
+    .await;
+    assert_eq!(passed, vec![("a".to_string(), 2)]);
+
+    // No group passes, even after merging.
+    let failed = count_groups(
+        &mut client,
+        format!(
+            "SELECT value, COUNT(*) FROM sharded WHERE id IN ({list}) GROUP BY value HAVING COUNT(*) > 2"
+        ),
+    )
+    .await;
+    assert!(failed.is_empty());
+
+    // Direct-to-shard queries are filtered by Postgres.
+    let direct = count_groups(
+        &mut client,
+        format!(
+            "SELECT value, COUNT(*) FROM sharded WHERE id = {} GROUP BY value HAVING COUNT(*) > 1",
+            ids[0]
+        ),
+    )
+    .await;
+    assert!(direct.is_empty());
+
+    // SUM(id) isn't in the select list, so it can't be applied after merging.
+    // The error closes the connection, so use another client.
+    let mut unsupported = TestClient::new_sharded(Parameters::default()).await;
+    unsupported
+        .send_simple(Query::new(format!(
+            "SELECT value, COUNT(*) FROM sharded WHERE id IN ({list}) GROUP BY value HAVING SUM(id) > 1"
+        )))
+        .await;
+    let error = unsupported.read_until('Z').await.unwrap_err();
+    assert!(error.message.contains("HAVING"), "{}", error.message);
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sharded_order_by_limit_offset() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    let mut ids = (0..6)
+        .flat_map(|_| [client.random_id_for_shard(0), client.random_id_for_shard(1)])
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    let list = ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    let values = ids
+        .iter()
+        .map(|id| format!("({id}, 'offset')"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES {values}"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    let ids_of = |messages: Vec<Message>| {
+        messages
+            .into_iter()
+            .filter(|message| message.code() == 'D')
+            .map(|message| {
+                DataRow::try_from(message)
+                    .unwrap()
+                    .get::<i64>(0, Format::Text)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT id FROM sharded WHERE id IN ({list}) ORDER BY id LIMIT 5 OFFSET 5"
+        )))
+        .await;
+    let received = ids_of(client.read_until('Z').await.unwrap());
+    assert_eq!(received, ids[5..10]);
+
+    client
+        .send_simple(Query::new(format!(
+            "SELECT id FROM sharded WHERE id IN ({list}) ORDER BY id DESC OFFSET 5"
+        )))
+        .await;
+    let received = ids_of(client.read_until('Z').await.unwrap());
+    let expected = ids.iter().rev().skip(5).copied().collect::<Vec<_>>();
+    assert_eq!(received, expected);
+
+    client
+        .send(Parse::new_anonymous(&format!(
+            "SELECT id FROM sharded WHERE id IN ({list}) ORDER BY id LIMIT $1 OFFSET $2"
+        )))
+        .await;
+    client
+        .send(Bind::new_params(
+            "",
+            &[Parameter::new(b"5"), Parameter::new(b"5")],
+        ))
+        .await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+    let received = ids_of(client.read_until('Z').await.unwrap());
+    assert_eq!(received, ids[5..10]);
+
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({list})"
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sharded_describe_portal() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    client
+        .send(Parse::new_anonymous("SELECT id, value FROM sharded"))
+        .await;
+    client.send(Bind::new_statement("")).await;
+    client.send(Describe::new_portal("")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+    let row_descriptions = messages.iter().filter(|m| m.code() == 'T').count();
+    assert_eq!(
+        row_descriptions, 1,
+        "cross-shard portal describe should return one RowDescription"
+    );
+    assert!(messages.iter().any(|m| m.code() == 'C'));
+}
+
+#[tokio::test]
+async fn test_sharded_describe_portal_no_data() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    client
+        .send(Parse::new_anonymous(
+            "UPDATE sharded SET value = value WHERE value = 'describe_portal_no_data'",
+        ))
+        .await;
+    client.send(Bind::new_statement("")).await;
+    client.send(Describe::new_portal("")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+    let no_data = messages.iter().filter(|m| m.code() == 'n').count();
+    assert_eq!(
+        no_data, 1,
+        "cross-shard portal describe should return one NoData"
+    );
+    assert!(!messages.iter().any(|m| m.code() == 'T'));
+}
+
+#[tokio::test]
+async fn test_sharded_describe_statement_and_portal() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    client
+        .send(Parse::new_anonymous("SELECT id, value FROM sharded"))
+        .await;
+    client.send(Describe::new_statement("")).await;
+    client.send(Bind::new_statement("")).await;
+    client.send(Describe::new_portal("")).await;
+    client.send(Execute::new()).await;
+    client.send(Sync).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+    let codes = messages.iter().map(|m| m.code()).collect::<Vec<_>>();
+
+    // One RowDescription for each Describe, not one for each shard.
+    assert_eq!(codes.iter().filter(|c| **c == 't').count(), 1);
+    assert_eq!(codes.iter().filter(|c| **c == 'T').count(), 2);
+    assert_eq!(&codes[..4], &['1', 't', 'T', '2']);
+    assert_eq!(codes[4], 'T');
+}
+
+#[tokio::test]
+async fn test_sharded_fastpath_function_call() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    // version()
+    client.send(Fastpath::new(89)).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+    let codes = messages.iter().map(|m| m.code()).collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec!['V', 'Z'],
+        "function call should run on one shard only"
+    );
+
+    // Connection is still usable.
+    client.send_simple(Query::new("SELECT 1")).await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sharded_fastpath_function_call_connected_shard() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+
+    for query in ["BEGIN", "SET pgdog.shard TO 1", "SELECT 1"] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+    assert_eq!(client.engine.backend().direct_shard_number(), Some(1));
+
+    // version(), on the shard the transaction is pinned to.
+    client.send(Fastpath::new(89)).await;
+    client.try_process().await.unwrap();
+
+    let messages = client.read_until('Z').await.unwrap();
+    let codes = messages.iter().map(|m| m.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec!['V', 'Z']);
+    assert_eq!(client.engine.backend().direct_shard_number(), Some(1));
+
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
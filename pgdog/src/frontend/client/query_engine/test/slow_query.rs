@@ -0,0 +1,37 @@
+use crate::config::{config, load_test, set};
+
+use super::prelude::*;
+
+#[tokio::test]
+async fn test_slow_query_logged_once() {
+    load_test();
+    let mut cfg = (*config()).clone();
+    cfg.config.general.log_slow_queries_ms = Some(100);
+    set(cfg).unwrap();
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    client
+        .send_simple(Query::new("SELECT pg_sleep(0.25), 'secret'"))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("SELECT 1")).await;
+    client.read_until('Z').await.unwrap();
+
+    assert_eq!(client.engine.stats.slow_queries, 1);
+}
+
+#[tokio::test]
+async fn test_slow_query_disabled() {
+    load_test();
+
+    let mut client = TestClient::new(Parameters::default()).await;
+
+    client
+        .send_simple(Query::new("SELECT pg_sleep(0.25)"))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    assert_eq!(client.engine.stats.slow_queries, 0);
+}
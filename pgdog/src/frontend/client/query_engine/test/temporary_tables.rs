@@ -0,0 +1,130 @@
+use pgdog_config::QueryParserLevel;
+
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{TemporaryTables, config, load_test_with_user, set},
+    expect_message,
+    net::ReadyForQuery,
+};
+
+use super::prelude::*;
+
+/// Single shard with `role = "primary"` and the default `query_parser = "auto"`,
+/// so the query router isn't otherwise needed.
+async fn client(temporary_tables: TemporaryTables) -> TestClient {
+    load_test_with_user("pgdog");
+    let mut cfg = (*config()).clone();
+    assert_eq!(cfg.config.general.query_parser, QueryParserLevel::Auto);
+    cfg.config.general.temporary_tables = temporary_tables;
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+    TestClient::new(Parameters::default()).await
+}
+
+#[tokio::test]
+async fn test_temporary_table_pinned() {
+    let mut client = client(TemporaryTables::Pin).await;
+
+    client.send_simple(Query::new("SELECT 1")).await;
+    client.read_until('Z').await.unwrap();
+    assert!(!client.backend_locked());
+
+    client
+        .send_simple(Query::new(
+            "CREATE TEMP TABLE test_temporary_table_pinned (id BIGINT)",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+    assert!(client.backend_locked());
+
+    let pid = client.backend_pid().await;
+
+    // Subsequent queries reach the connection that has the table.
+    for _ in 0..3 {
+        client
+            .send_simple(Query::new(
+                "INSERT INTO test_temporary_table_pinned VALUES (1)",
+            ))
+            .await;
+        client.read_until('Z').await.unwrap();
+        assert!(client.backend_locked());
+        assert_eq!(client.backend_pid().await, pid);
+    }
+}
+
+#[tokio::test]
+async fn test_temporary_table_on_commit_drop_not_pinned() {
+    let mut client = client(TemporaryTables::Pin).await;
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+    client
+        .send_simple(Query::new(
+            "CREATE TEMP TABLE test_temporary_table_on_commit_drop (id BIGINT) ON COMMIT DROP",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+    client.send_simple(Query::new("COMMIT")).await;
+    client.read_until('Z').await.unwrap();
+
+    assert!(!client.backend_locked());
+}
+
+#[tokio::test]
+async fn test_temporary_table_rejected() {
+    let mut client = client(TemporaryTables::Reject).await;
+
+    client
+        .send_simple(Query::new(
+            "CREATE TEMP TABLE test_temporary_table_rejected (id BIGINT)",
+        ))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.code, "0A000");
+    assert_eq!(
+        error.message,
+        "temporary tables are not supported in transaction mode"
+    );
+    expect_message!(client.read().await, ReadyForQuery);
+    assert!(!client.backend_locked());
+
+    // Regular tables are fine.
+    client
+        .send_simple(Query::new(
+            "CREATE TABLE IF NOT EXISTS test_temporary_table_rejected (id BIGINT)",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+}
+
+#[tokio::test]
+async fn test_temporary_objects_pinned() {
+    for query in [
+        "SELECT 1 AS id INTO TEMP test_temporary_objects_pinned_table",
+        "CREATE TEMP VIEW test_temporary_objects_pinned_view AS SELECT 1 AS id",
+        "CREATE TEMP SEQUENCE test_temporary_objects_pinned_seq",
+    ] {
+        let mut client = client(TemporaryTables::Pin).await;
+
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+        assert!(client.backend_locked(), "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn test_temporary_objects_rejected() {
+    for query in [
+        "SELECT 1 AS id INTO TEMP test_temporary_objects_rejected_table",
+        "CREATE TEMP VIEW test_temporary_objects_rejected_view AS SELECT 1 AS id",
+        "CREATE TEMP SEQUENCE test_temporary_objects_rejected_seq",
+    ] {
+        let mut client = client(TemporaryTables::Reject).await;
+
+        client.send_simple(Query::new(query)).await;
+        let error = client.read_until('Z').await.unwrap_err();
+        assert_eq!(error.code, "0A000", "{}", query);
+        expect_message!(client.read().await, ReadyForQuery);
+        assert!(!client.backend_locked(), "{}", query);
+    }
+}
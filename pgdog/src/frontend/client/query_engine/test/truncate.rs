@@ -0,0 +1,100 @@
+use crate::{
+    expect_message,
+    net::{DataRow, ReadyForQuery, RowDescription},
+};
+
+use super::prelude::*;
+
+/// Create the tables on both shards, with one row in each shard,
+/// and `{prefix}_partial` on shard 0 only.
+async fn setup(client: &mut TestClient, prefix: &str) {
+    for query in [
+        format!("DROP TABLE IF EXISTS {}_all, {}_partial", prefix, prefix),
+        format!("CREATE TABLE {}_all (id BIGINT)", prefix),
+        format!(
+            "/* pgdog_shard: 0 */ CREATE TABLE {}_partial (id BIGINT)",
+            prefix
+        ),
+        format!("/* pgdog_shard: 0 */ INSERT INTO {}_all VALUES (1)", prefix),
+        format!("/* pgdog_shard: 1 */ INSERT INTO {}_all VALUES (2)", prefix),
+    ] {
+        client.send_simple(Query::new(query)).await;
+        client.read_until('Z').await.unwrap();
+    }
+}
+
+async fn count(client: &mut TestClient, table: &str, shard: usize) -> i64 {
+    client
+        .send_simple(Query::new(format!(
+            "/* pgdog_shard: {} */ SELECT COUNT(*) FROM {}",
+            shard, table
+        )))
+        .await;
+    expect_message!(client.read().await, RowDescription);
+    let row = expect_message!(client.read().await, DataRow);
+    client.read_until('Z').await.unwrap();
+    row.get_int(0, true).unwrap()
+}
+
+#[tokio::test]
+async fn test_truncate_all_shards() {
+    let mut client = TestClient::new_atomic_truncate(Parameters::default()).await;
+    setup(&mut client, "truncate_all_shards").await;
+
+    client
+        .send_simple(Query::new("TRUNCATE truncate_all_shards_all"))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    for shard in [0, 1] {
+        assert_eq!(
+            count(&mut client, "truncate_all_shards_all", shard).await,
+            0
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_truncate_failed_shard_reported() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+    setup(&mut client, "truncate_failed_shard").await;
+
+    client
+        .send_simple(Query::new(
+            "TRUNCATE truncate_failed_shard_all, truncate_failed_shard_partial",
+        ))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.code, "42P01");
+    assert_eq!(error.detail.as_deref(), Some("TRUNCATE failed on shard 1"));
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // Shard 0 was truncated anyway.
+    assert_eq!(count(&mut client, "truncate_failed_shard_all", 0).await, 0);
+    assert_eq!(count(&mut client, "truncate_failed_shard_all", 1).await, 1);
+}
+
+#[tokio::test]
+async fn test_atomic_truncate_rolled_back() {
+    let mut client = TestClient::new_atomic_truncate(Parameters::default()).await;
+    setup(&mut client, "truncate_rolled_back").await;
+
+    client
+        .send_simple(Query::new(
+            "TRUNCATE truncate_rolled_back_all, truncate_rolled_back_partial",
+        ))
+        .await;
+    let error = client.read_until('Z').await.unwrap_err();
+    assert_eq!(error.code, "42P01");
+    assert_eq!(error.detail.as_deref(), Some("TRUNCATE failed on shard 1"));
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'I');
+
+    // Nothing was truncated.
+    for shard in [0, 1] {
+        assert_eq!(
+            count(&mut client, "truncate_rolled_back_all", shard).await,
+            1
+        );
+    }
+}
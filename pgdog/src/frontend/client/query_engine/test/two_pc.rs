@@ -0,0 +1,114 @@
+use crate::{
+    expect_message,
+    net::{ErrorResponse, Parameters, Query, ReadyForQuery},
+};
+
+use super::{change_config, prelude::*};
+
+#[tokio::test]
+async fn test_two_pc_prepare_failure_rolls_back_all_shards() {
+    let mut client = TestClient::new_sharded(Parameters::default()).await;
+    change_config(|general| {
+        general.two_phase_commit = true;
+    });
+
+    let id_shard0 = client.random_id_for_shard(0);
+    let id_shard1 = client.random_id_for_shard(1);
+
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES ({}, 'two_pc'), ({}, 'two_pc')",
+            id_shard0, id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    // Postgres refuses to PREPARE transactions that touched temporary objects,
+    // so only shard 1 fails phase one.
+    client
+        .send_simple(Query::new(
+            "DO $$ BEGIN IF current_database() = 'shard_1' THEN CREATE TEMP TABLE test_two_pc_prepare_failure (id BIGINT); END IF; END $$",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("COMMIT")).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "0A000");
+    let rfq = expect_message!(client.read().await, ReadyForQuery);
+    assert_eq!(rfq.status, 'I');
+    assert!(!client.backend_connected());
+
+    // Neither shard committed the insert.
+    client
+        .send_simple(Query::new(format!(
+            "SELECT id FROM sharded WHERE id IN ({}, {})",
+            id_shard0, id_shard1
+        )))
+        .await;
+    let messages = client.read_until('Z').await.unwrap();
+    assert!(messages.iter().all(|m| m.code() != 'D'));
+
+    // The client can keep using 2pc.
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+    client
+        .send_simple(Query::new(format!(
+            "DELETE FROM sharded WHERE id IN ({}, {})",
+            id_shard0, id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+    client.send_simple(Query::new("COMMIT")).await;
+    let messages = client.read_until('Z').await.unwrap();
+    assert!(messages.iter().all(|m| m.code() != 'E'));
+}
+
+#[tokio::test]
+async fn test_two_pc_prepare_failure_releases_transaction_shard() {
+    let mut client = TestClient::new_cross_shard_transactions_disabled(Parameters::default()).await;
+    change_config(|general| {
+        general.two_phase_commit = true;
+    });
+
+    let id_shard0 = client.random_id_for_shard(0);
+    let id_shard1 = client.random_id_for_shard(1);
+
+    // Transaction is using shard 1.
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+    client
+        .send_simple(Query::new(format!(
+            "INSERT INTO sharded (id, value) VALUES ({}, 'two_pc')",
+            id_shard1
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+    client
+        .send_simple(Query::new(
+            "DO $$ BEGIN IF current_database() = 'shard_1' THEN CREATE TEMP TABLE test_two_pc_prepare_failure_shard (id BIGINT); END IF; END $$",
+        ))
+        .await;
+    client.read_until('Z').await.unwrap();
+
+    client.send_simple(Query::new("COMMIT")).await;
+    let error = expect_message!(client.read().await, ErrorResponse);
+    assert_eq!(error.code, "0A000");
+    expect_message!(client.read().await, ReadyForQuery);
+
+    // The next transaction can use another shard.
+    client.send_simple(Query::new("BEGIN")).await;
+    client.read_until('Z').await.unwrap();
+    client
+        .send_simple(Query::new(format!(
+            "SELECT * FROM sharded WHERE id = {}",
+            id_shard0
+        )))
+        .await;
+    client.read_until('Z').await.unwrap();
+    client.send_simple(Query::new("ROLLBACK")).await;
+    client.read_until('Z').await.unwrap();
+}
@@ -84,16 +84,27 @@ impl Manager {
 
     /// Open the WAL at the configured directory, replay any in-flight
     /// transactions back into this manager, and start the writer +
-    /// checkpoint tasks. If WAL initialization fails (lock contention,
-    /// disk error, corrupt segment that can't be quarantined), the
-    /// manager keeps running without durability and a warning is logged
-    /// so operators can investigate.
+    /// checkpoint tasks. Prepared transactions left on the shards that
+    /// the WAL doesn't know about are rolled back before this returns,
+    /// so it must be called before accepting clients. If WAL initialization
+    /// fails (lock contention, disk error, corrupt segment that can't be
+    /// quarantined), the manager keeps running without durability and a
+    /// warning is logged so operators can investigate.
     pub async fn enable_wal(&self, wal_dir: &PathBuf) {
         match Wal::open(self, wal_dir).await {
             Ok(wal) => {
+                let corruption = wal.corruption();
                 self.wal.store(Some(Arc::new(wal)));
                 info!("[2pc] wal enabled");
                 tasks::spawn("2pc wal", Self::checkpoint_loop());
+
+                // Without a complete log we can't tell orphans apart from
+                // transactions that were committing.
+                if corruption {
+                    warn!("[2pc] wal is incomplete, skipping orphaned transaction recovery");
+                } else {
+                    self.recover_orphans().await;
+                }
             }
             Err(err) => {
                 warn!(
@@ -276,6 +287,34 @@ impl Manager {
         self.notify.notify.notify_one();
     }
 
+    /// Queue a prepared transaction found on a shard for rollback,
+    /// unless the manager is already tracking it.
+    pub(super) fn restore_orphan(
+        &self,
+        transaction: TwoPcTransaction,
+        user: String,
+        database: String,
+    ) -> bool {
+        let identifier = Arc::new(User { user, database });
+        {
+            let mut guard = self.inner.lock();
+            if guard.transactions.contains_key(&transaction) {
+                return false;
+            }
+            guard.transactions.insert(
+                transaction,
+                TransactionInfo {
+                    phase: TwoPcPhase::Phase1,
+                    identifier,
+                },
+            );
+            guard.queue.push_back(transaction);
+        }
+        self.stats.incr_recovered();
+        self.notify.notify.notify_one();
+        true
+    }
+
     pub(super) fn return_guard(&self, guard: &TwoPcGuard) {
         let exists = self
             .inner
@@ -375,7 +414,7 @@ impl Manager {
 
         connection
             .connect(
-                &Request::default(),
+                &Request::maintenance(),
                 &Route::write(ShardWithPriority::new_override_transaction(Shard::All)),
             )
             .await?;
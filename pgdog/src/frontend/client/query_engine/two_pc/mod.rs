@@ -7,6 +7,7 @@ use super::Error;
 
 pub mod guard;
 pub mod manager;
+pub mod orphans;
 pub mod phase;
 pub mod server_transactions;
 pub mod statement;
@@ -17,7 +18,7 @@ pub mod wal;
 pub use guard::TwoPcGuard;
 pub use manager::Manager;
 pub use phase::TwoPcPhase;
-pub(crate) use server_transactions::TwoPcTransactions;
+pub(crate) use server_transactions::{TwoPcServerTransaction, TwoPcTransactions};
 pub(crate) use statement::TwoPcTransactionOnShard;
 pub use stats::TwoPcStats;
 pub use transaction::TwoPcTransaction;
@@ -84,6 +85,16 @@ impl TwoPc {
         Ok(())
     }
 
+    /// Forget the current two-pc transaction without removing it
+    /// from the manager.
+    ///
+    /// The next transaction gets a new identifier. If the manager still tracks
+    /// this one, it will be cleaned up in the background once its guards are dropped.
+    pub(super) fn reset(&mut self) {
+        self.transaction = None;
+        self.auto = false;
+    }
+
     pub fn set_auto(&mut self) {
         self.auto = true;
     }
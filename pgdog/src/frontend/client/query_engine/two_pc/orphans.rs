@@ -0,0 +1,99 @@
+//! Resolve two-phase transactions left prepared on shards by a previous run.
+use fnv::FnvHashSet as HashSet;
+use tracing::{info, warn};
+
+use crate::backend::{databases::databases, pool::Request};
+
+use super::{Manager, TwoPcServerTransaction, TwoPcTransactions};
+
+impl Manager {
+    /// Find prepared transactions created by this PgDog instance that
+    /// the manager doesn't know about and queue them for rollback.
+    ///
+    /// Transactions restored from the WAL are already being driven to
+    /// a terminal state. Anything else never durably logged a commit
+    /// decision, so rolling it back is safe. Shards that can't be reached
+    /// are skipped.
+    ///
+    /// Each transaction is checked again under the manager lock before
+    /// it's queued, so transactions started while this runs are left alone.
+    ///
+    /// Returns the number of transactions queued for rollback.
+    pub async fn recover_orphans(&self) -> usize {
+        let known = self.transactions();
+        let mut orphans = HashSet::default();
+        let databases = databases();
+
+        for cluster in databases.all().values() {
+            if !cluster.two_pc_enabled() {
+                continue;
+            }
+
+            for (number, shard) in cluster.shards().iter().enumerate() {
+                let mut server = match shard.primary(&Request::maintenance()).await {
+                    Ok(server) => server,
+                    Err(err) => {
+                        warn!(
+                            "[2pc] skipping orphan recovery on shard {} [{}]: {}",
+                            number,
+                            cluster.identifier(),
+                            err
+                        );
+                        continue;
+                    }
+                };
+
+                let transactions = match TwoPcTransactions::load(&mut server).await {
+                    Ok(transactions) => transactions,
+                    Err(err) => {
+                        warn!(
+                            "[2pc] failed to load prepared transactions on shard {} [{}]: {}",
+                            number,
+                            cluster.identifier(),
+                            err
+                        );
+                        continue;
+                    }
+                };
+
+                // pg_prepared_xacts is server-wide, but a prepared transaction
+                // can only be finished from the database that prepared it.
+                let shard_database = &server.addr().database_name;
+
+                for transaction in transactions.iter() {
+                    if let TwoPcServerTransaction::Ours {
+                        txn,
+                        database,
+                        local: true,
+                        ..
+                    } = transaction
+                        && database == shard_database
+                        && !known.contains_key(txn)
+                        && !orphans.contains(txn)
+                        && self.restore_orphan(
+                            *txn,
+                            cluster.user().to_owned(),
+                            cluster.name().to_owned(),
+                        )
+                    {
+                        orphans.insert(*txn);
+                        warn!(
+                            r#"[2pc] found orphaned transaction "{}", rolling back [{}]"#,
+                            txn,
+                            cluster.identifier()
+                        );
+                    }
+                }
+            }
+        }
+
+        if !orphans.is_empty() {
+            info!(
+                "[2pc] queued {} orphaned transactions for rollback",
+                orphans.len()
+            );
+        }
+
+        orphans.len()
+    }
+}
@@ -13,6 +13,8 @@ pub enum TwoPcServerTransaction {
         txn: TwoPcTransaction,
         user: String,
         database: String,
+        /// Created by this PgDog instance.
+        local: bool,
     },
     Other {
         name: String,
@@ -52,6 +54,7 @@ impl TwoPcTransactions {
                         txn: txn.transaction(),
                         user,
                         database,
+                        local: TwoPcTransaction::is_local(&gid),
                     }
                 } else {
                     TwoPcServerTransaction::Other {
@@ -1,6 +1,6 @@
 use crate::{
     backend::{
-        databases::databases,
+        databases::{databases, reload_from_existing},
         pool::{Connection, Request},
     },
     config,
@@ -154,3 +154,64 @@ async fn test_cleanup_transaction_phase_two() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn test_recover_orphans() {
+    config::load_test_sharded();
+    let mut updated = (*config::config()).clone();
+    updated.config.general.two_phase_commit = true;
+    config::set(updated).unwrap();
+    reload_from_existing().unwrap();
+
+    let cluster = databases().all().iter().next().unwrap().1.clone();
+    assert!(cluster.two_pc_enabled());
+
+    // Simulate a crash after phase one: the transaction is prepared
+    // on the shard, but the manager has never heard of it.
+    let transaction = TwoPcTransaction::new();
+    let gid = TwoPcTransactionOnShard::new(transaction, 0).to_string();
+
+    let mut server = cluster.primary(0, &Request::default()).await.unwrap();
+    server.execute("BEGIN").await.unwrap();
+    server
+        .execute("CREATE TABLE test_recover_orphans(id BIGINT)")
+        .await
+        .unwrap();
+    server
+        .execute(format!("PREPARE TRANSACTION '{}'", gid))
+        .await
+        .unwrap();
+    drop(server);
+
+    assert!(Manager::get().transaction(&transaction).is_none());
+    assert!(Manager::get().recover_orphans().await >= 1);
+    assert_eq!(
+        Manager::get()
+            .transaction(&transaction)
+            .map(|info| info.phase),
+        Some(TwoPcPhase::Phase1)
+    );
+
+    Manager::get().wait_until_cleaned_up(transaction).await;
+
+    let mut server = cluster.primary(0, &Request::default()).await.unwrap();
+    let prepared = server
+        .execute(format!(
+            "SELECT gid FROM pg_prepared_xacts WHERE gid = '{}'",
+            gid
+        ))
+        .await
+        .unwrap();
+    // Rolled back.
+    assert!(prepared.iter().all(|m| m.code() != 'D'));
+    // Table wasn't committed.
+    assert!(
+        server
+            .execute("SELECT * FROM test_recover_orphans")
+            .await
+            .is_err()
+    );
+
+    // Nothing left to recover.
+    assert_eq!(Manager::get().recover_orphans().await, 0);
+}
@@ -29,6 +29,14 @@ impl TwoPcTransaction {
             instance_id(),
         )
     }
+
+    /// Check that a transaction name was generated by this PgDog instance.
+    ///
+    /// Instances are identified by `NODE_ID` (and `DEPLOYMENT_ID`, if set),
+    /// so this only recognizes names from previous runs if those are stable.
+    pub(crate) fn is_local(name: &str) -> bool {
+        name.starts_with(&Self::global_prefix())
+    }
 }
 
 impl Display for TwoPcTransaction {
@@ -65,6 +73,15 @@ mod test {
         assert_eq!(reverse.0, transaction.0);
     }
 
+    #[test]
+    fn test_is_local() {
+        let transaction = TwoPcTransaction::new();
+        assert!(TwoPcTransaction::is_local(&transaction.to_string()));
+        assert!(TwoPcTransaction::is_local(&format!("{transaction}_1")));
+        assert!(!TwoPcTransaction::is_local("__pgdog_2pc_other_1234_0"));
+        assert!(!TwoPcTransaction::is_local("my_transaction"));
+    }
+
     #[test]
     fn test_instance_id() {
         for id in [1024, 11111111, usize::MAX, usize::MIN] {
@@ -35,6 +35,8 @@ pub(super) struct Recovered {
     /// Initial active-2PC snapshot derived from replay so the writer
     /// can serve checkpoints without rebuilding it.
     pub snapshot: HashMap<TwoPcTransaction, CheckpointEntry>,
+    /// At least one segment was corrupt and got quarantined.
+    pub corruption: bool,
 }
 
 /// Scan every segment in `dir` in LSN order, hand each in-flight
@@ -60,6 +62,7 @@ pub(super) async fn recover_transactions(
         return Ok(Recovered {
             segment: Segment::create(dir, 0).await?,
             snapshot: HashMap::default(),
+            corruption: false,
         });
     };
 
@@ -144,7 +147,11 @@ pub(super) async fn recover_transactions(
         Some(segment) => segment,
         None => Segment::create(dir, next_lsn).await?,
     };
-    Ok(Recovered { segment, snapshot })
+    Ok(Recovered {
+        segment,
+        snapshot,
+        corruption,
+    })
 }
 
 /// Drain every record in `reader` into `working`. Errors propagate as
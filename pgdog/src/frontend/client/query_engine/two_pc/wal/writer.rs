@@ -132,6 +132,7 @@ struct WalShutdown {
 pub struct Wal {
     tx: mpsc::Sender<WriteRequest>,
     shutdown: Arc<WalShutdown>,
+    corruption: bool,
 }
 
 impl Wal {
@@ -155,6 +156,7 @@ impl Wal {
             })?;
         let lock = lock_dir(dir)?;
         let recovered = recovery::recover_transactions(manager, dir).await?;
+        let corruption = recovered.corruption;
 
         let (tx, rx) = mpsc::channel::<WriteRequest>(CHANNEL_CAPACITY);
         let shutdown = Arc::new(WalShutdown::default());
@@ -177,7 +179,17 @@ impl Wal {
             }
         });
 
-        Ok(Self { tx, shutdown })
+        Ok(Self {
+            tx,
+            shutdown,
+            corruption,
+        })
+    }
+
+    /// Recovery found and quarantined corrupt segments, so the log
+    /// may be missing commit decisions.
+    pub fn corruption(&self) -> bool {
+        self.corruption
     }
 
     /// Log that `txn` is about to issue PREPARE TRANSACTION on its
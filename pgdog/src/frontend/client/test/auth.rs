@@ -1,11 +1,14 @@
 //! Client authentication tests.
 
+use std::net::SocketAddr;
+
 use pgdog_config::{AuthType, PassthroughAuth};
+use tokio::io::AsyncWriteExt;
 
 use crate::{
     config::{config, set},
     expect_message,
-    net::{Authentication, ErrorResponse, Parameters, Password},
+    net::{Authentication, ErrorResponse, Parameters, Password, Protocol, Query},
 };
 
 use super::SpawnedClient;
@@ -56,3 +59,76 @@ async fn test_admin_password_checked_with_passthrough_auth() {
     client.read_until('Z').await;
     client.join().await;
 }
+
+/// Users with an mTLS identity can't log in without a client certificate.
+#[tokio::test]
+async fn test_identity_rejects_plain_connection() {
+    crate::logger();
+    crate::config::load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.users.users[0].identity = Some("CommonNameOrHostname".into());
+    set(cfg).unwrap();
+    crate::backend::databases::init().unwrap();
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+
+    let mut client = SpawnedClient::new_with_login(params).await;
+    let error = ErrorResponse::try_from(client.read().await).unwrap();
+    assert_eq!(error.code, "28000");
+    client.join().await;
+}
+
+/// Log in from the given address and send a message with a malformed length.
+async fn protocol_violation(addr: SocketAddr) {
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+
+    let mut client = SpawnedClient::new_with_login_from(params, addr).await;
+    client.read_until('Z').await;
+
+    client.conn.write_all(&[b'Q', 0, 0, 0, 2]).await.unwrap();
+    let error = ErrorResponse::try_from(client.read().await).unwrap();
+    assert_eq!(error.code, "08P01");
+    client.join().await;
+}
+
+/// Clients that keep violating the protocol are quarantined by address,
+/// other clients can still connect.
+#[tokio::test]
+async fn test_client_quarantine() {
+    crate::logger();
+    crate::config::load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.auth_type = AuthType::Trust;
+    cfg.config.general.client_quarantine_errors = 2;
+    cfg.config.general.client_quarantine_duration = 60_000;
+    set(cfg).unwrap();
+
+    let bad: SocketAddr = "10.1.2.3:5432".parse().unwrap();
+    let good: SocketAddr = "10.1.2.4:5432".parse().unwrap();
+
+    protocol_violation(bad).await;
+    protocol_violation(bad).await;
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+
+    // Rejected before authentication.
+    let mut client = SpawnedClient::new_with_login_from(params.clone(), bad).await;
+    let error = ErrorResponse::try_from(client.read().await).unwrap();
+    assert_eq!(error.code, "08004");
+    client.join().await;
+
+    let mut client = SpawnedClient::new_with_login_from(params, good).await;
+    client.read_until('Z').await;
+    client.send(Query::new("SELECT 1")).await;
+    let messages = client.read_until('Z').await;
+    assert_eq!(messages.last().unwrap().code(), 'Z');
+    assert!(messages.iter().all(|message| message.code() != 'E'));
+}
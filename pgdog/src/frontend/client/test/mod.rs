@@ -31,6 +31,7 @@ use crate::{
 use super::Stream;
 
 pub mod auth;
+pub mod parameters;
 pub mod target_session_attrs;
 pub mod test_client;
 pub use test_client::{SpawnedClient, TestClient};
@@ -568,6 +569,46 @@ async fn test_query_size_limit_block() {
     handle.await.unwrap();
 }
 
+#[tokio::test]
+async fn test_query_size_limit_block_boundary() {
+    crate::logger();
+    load_test();
+    let (mut conn, mut client) = parallel_test_client_with_params(Parameters::default()).await;
+
+    let query = "SELECT 1 AS limit_check";
+    let limit = Query::new(query).to_bytes().len();
+
+    let mut c = (*config()).clone();
+    c.config.general.query_size_limit = Some(limit);
+    c.config.general.query_size_limit_action = QuerySizeLimitAction::Block;
+    set(c).unwrap();
+
+    let handle = tokio::spawn(async move { client.run().await.unwrap() });
+
+    // Exactly at the limit: executed.
+    conn.write_all(&Query::new(query).to_bytes()).await.unwrap();
+    read_messages(&mut conn, &['T', 'D', 'C', 'Z']).await;
+
+    // One byte over the limit: rejected before it's parsed.
+    let over = format!("{query} ");
+    conn.write_all(&Query::new(&over).to_bytes()).await.unwrap();
+
+    let err = read_one!(conn);
+    let err = ErrorResponse::from_bytes(err.freeze()).unwrap();
+    assert_eq!(err.severity, "FATAL");
+    assert_eq!(err.code, "54000");
+    assert_eq!(
+        err.detail,
+        Some(format!(
+            "message is {} bytes, query_size_limit is {} bytes",
+            limit + 1,
+            limit
+        ))
+    );
+
+    handle.await.unwrap();
+}
+
 #[tokio::test]
 async fn test_query_size_limit_warn() {
     let (mut conn, mut client, _) = new_client!(false);
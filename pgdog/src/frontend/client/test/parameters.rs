@@ -0,0 +1,147 @@
+//! Startup parameters forwarded to the server.
+
+use pgdog_config::AuthType;
+
+use pgdog_config::ShardedSchema;
+
+use crate::{
+    backend::databases::reload_from_existing,
+    config::{config, load_test, load_test_sharded_3, set},
+    net::{DataRow, ParameterStatus, Parameters, Protocol, Query},
+};
+
+use super::SpawnedClient;
+
+async fn show(client: &mut SpawnedClient, name: &str) -> String {
+    client.send(Query::new(format!("SHOW {}", name))).await;
+    let messages = client.read_until('Z').await;
+    let row = messages
+        .into_iter()
+        .find(|message| message.code() == 'D')
+        .unwrap();
+
+    DataRow::try_from(row).unwrap().get_text(0).unwrap()
+}
+
+#[tokio::test]
+async fn test_denied_startup_parameter_removed() {
+    crate::logger();
+    load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.auth_type = AuthType::Trust;
+    cfg.config.general.denied_parameters = vec!["statement_timeout".into()];
+    set(cfg).unwrap();
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+    params.insert("statement_timeout", "1234");
+    params.insert("application_name", "test_denied_startup_parameter");
+
+    let mut client = SpawnedClient::new_with_login(params).await;
+    client.read_until('Z').await;
+
+    assert_ne!(show(&mut client, "statement_timeout").await, "1234ms");
+    assert_eq!(
+        show(&mut client, "application_name").await,
+        "test_denied_startup_parameter"
+    );
+}
+
+#[tokio::test]
+async fn test_denied_options_parameter_removed() {
+    crate::logger();
+    load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.auth_type = AuthType::Trust;
+    cfg.config.general.denied_parameters = vec!["statement_timeout".into()];
+    set(cfg).unwrap();
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+    params.insert(
+        "options",
+        "-c statement_timeout=1234 -c application_name=test_denied_options_parameter",
+    );
+
+    let mut client = SpawnedClient::new_with_login(params).await;
+    client.read_until('Z').await;
+
+    assert_ne!(show(&mut client, "statement_timeout").await, "1234ms");
+    assert_eq!(
+        show(&mut client, "application_name").await,
+        "test_denied_options_parameter"
+    );
+}
+
+#[tokio::test]
+async fn test_server_version_override() {
+    crate::logger();
+    load_test();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.auth_type = AuthType::Trust;
+    cfg.config.general.server_version = Some("9.6.24".into());
+    set(cfg).unwrap();
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+
+    let mut client = SpawnedClient::new_with_login(params).await;
+    let versions = client
+        .read_until('Z')
+        .await
+        .into_iter()
+        .filter(|message| message.code() == 'S')
+        .map(|message| ParameterStatus::try_from(message).unwrap())
+        .filter(|param| param.name == "server_version")
+        .map(|param| param.value.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(versions, vec!["9.6.24".to_string()]);
+}
+
+#[tokio::test]
+async fn test_options_search_path_routing() {
+    crate::logger();
+    load_test_sharded_3();
+
+    let mut cfg = (*config()).clone();
+    cfg.config.general.auth_type = AuthType::Trust;
+    cfg.config.sharded_schemas.push(ShardedSchema {
+        database: "pgdog".into(),
+        name: Some("shard_2".into()),
+        shard: 2,
+        ..Default::default()
+    });
+    set(cfg).unwrap();
+    reload_from_existing().unwrap();
+
+    let mut params = Parameters::default();
+    params.insert("user", "pgdog");
+    params.insert("database", "pgdog");
+    params.insert("options", "-csearch_path=shard_2");
+
+    let mut client = SpawnedClient::new_with_login(params).await;
+    client.read_until('Z').await;
+
+    assert_eq!(show(&mut client, "search_path").await, "shard_2");
+
+    for _ in 0..3 {
+        client.send(Query::new("SELECT current_database()")).await;
+        let row = client
+            .read_until('Z')
+            .await
+            .into_iter()
+            .find(|message| message.code() == 'D')
+            .unwrap();
+        assert_eq!(
+            DataRow::try_from(row).unwrap().get_text(0).unwrap(),
+            "shard_2"
+        );
+    }
+}
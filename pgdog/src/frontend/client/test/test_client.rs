@@ -1,4 +1,4 @@
-use std::{fmt::Debug, ops::Deref};
+use std::{fmt::Debug, net::SocketAddr, ops::Deref};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use pgdog_config::RewriteMode;
@@ -180,6 +180,30 @@ impl TestClient {
         Self::new(params).await
     }
 
+    /// New client with cross-shard transactions disabled.
+    pub(crate) async fn new_cross_shard_transactions_disabled(params: Parameters) -> Self {
+        load_test_sharded();
+
+        let mut config = config().deref().clone();
+        config.config.general.cross_shard_transactions_disabled = true;
+        set(config).unwrap();
+        reload_from_existing().unwrap();
+
+        Self::new(params).await
+    }
+
+    /// New client that truncates tables on all shards or none.
+    pub(crate) async fn new_atomic_truncate(params: Parameters) -> Self {
+        load_test_sharded();
+
+        let mut config = config().deref().clone();
+        config.config.general.atomic_truncate = true;
+        set(config).unwrap();
+        reload_from_existing().unwrap();
+
+        Self::new(params).await
+    }
+
     /// Create client that will rewrite all queries.
     pub(crate) async fn new_rewrites(params: Parameters) -> Self {
         load_test_sharded();
@@ -320,11 +344,22 @@ impl SpawnedClient {
     ///
     /// Config needs to be loaded.
     pub async fn new_with_login(params: Parameters) -> Self {
+        Self::login(params, None).await
+    }
+
+    /// Same as [`SpawnedClient::new_with_login`], but the client
+    /// appears to be connecting from the given address.
+    pub async fn new_with_login_from(params: Parameters, addr: SocketAddr) -> Self {
+        Self::login(params, Some(addr)).await
+    }
+
+    async fn login(params: Parameters, client_addr: Option<SocketAddr>) -> Self {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let port = listener.local_addr().unwrap().port();
 
         let handle = tokio::spawn(async move {
             let (stream, addr) = listener.accept().await.unwrap();
+            let addr = client_addr.unwrap_or(addr);
             let stream = Stream::plain(stream, 4096);
             Client::spawn(stream, params, addr, config(), ProtocolVersion::V3_0)
                 .await
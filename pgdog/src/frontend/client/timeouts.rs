@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::{config::General, frontend::ClientRequest, state::State};
 
@@ -7,6 +7,7 @@ pub struct Timeouts {
     pub(super) query_timeout: Duration,
     pub(super) client_idle_timeout: Duration,
     pub(super) idle_in_transaction_timeout: Duration,
+    pub(super) max_transaction_duration: Duration,
 }
 
 impl Default for Timeouts {
@@ -15,6 +16,7 @@ impl Default for Timeouts {
             query_timeout: Duration::MAX,
             client_idle_timeout: Duration::MAX,
             idle_in_transaction_timeout: Duration::MAX,
+            max_transaction_duration: Duration::MAX,
         }
     }
 }
@@ -25,6 +27,7 @@ impl Timeouts {
             query_timeout: general.query_timeout(),
             client_idle_timeout: general.client_idle_timeout(),
             idle_in_transaction_timeout: general.client_idle_in_transaction_timeout(),
+            max_transaction_duration: general.max_transaction_duration(),
         }
     }
 
@@ -63,6 +66,18 @@ impl Timeouts {
             _ => Duration::MAX,
         }
     }
+
+    /// Time left until the transaction started at `started`
+    /// exceeds `max_transaction_duration`.
+    #[inline]
+    pub(crate) fn transaction_timeout(&self, started: Option<Instant>) -> Duration {
+        match started {
+            Some(started) => self
+                .max_transaction_duration
+                .saturating_sub(started.elapsed()),
+            None => Duration::MAX,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +102,20 @@ mod test {
         );
         assert_eq!(actual, Duration::MAX);
     }
+
+    #[test]
+    fn test_transaction_timeout() {
+        let timeout = Timeouts {
+            max_transaction_duration: Duration::from_secs(5),
+            ..Default::default()
+        };
+
+        assert_eq!(timeout.transaction_timeout(None), Duration::MAX);
+
+        let started = Instant::now() - Duration::from_secs(2);
+        assert!(timeout.transaction_timeout(Some(started)) <= Duration::from_secs(3));
+
+        let started = Instant::now() - Duration::from_secs(10);
+        assert_eq!(timeout.transaction_timeout(Some(started)), Duration::ZERO);
+    }
 }
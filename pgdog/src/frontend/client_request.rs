@@ -33,6 +33,9 @@ pub struct ClientRequest {
     pub ast: Option<Ast>,
     /// Last Parse we received.
     pub last_parse: Option<Parse>,
+    /// The query parser failed to parse the request,
+    /// so it's routed according to `parse_failures`.
+    pub parse_failed: bool,
 }
 
 impl MemoryUsage for ClientRequest {
@@ -58,6 +61,7 @@ impl ClientRequest {
             route: None,
             ast: None,
             last_parse: None,
+            parse_failed: false,
         }
     }
 
@@ -92,6 +96,7 @@ impl ClientRequest {
         self.messages.clear();
         self.route = None;
         self.ast = None;
+        self.parse_failed = false;
     }
 
     /// We received a complete request and we are ready to
@@ -242,7 +247,14 @@ impl ClientRequest {
     pub(crate) fn is_executable(&self) -> bool {
         self.messages
             .iter()
-            .any(|m| ['E', 'Q', 'B'].contains(&m.code()))
+            .any(|m| ['E', 'Q', 'B', 'F'].contains(&m.code()))
+    }
+
+    /// The request is a fastpath function call.
+    pub fn is_fastpath(&self) -> bool {
+        self.messages
+            .iter()
+            .any(|m| matches!(m, ProtocolMessage::Fastpath(_)))
     }
 
     /// We split up the extended protocol exhange as soon as we see
@@ -384,6 +396,7 @@ impl From<Vec<ProtocolMessage>> for ClientRequest {
             route: None,
             ast: None,
             last_parse: None,
+            parse_failed: false,
         }
     }
 }
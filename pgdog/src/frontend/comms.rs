@@ -1,11 +1,12 @@
 //! Communication to/from connected clients.
 
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::ops::Deref;
 use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
 };
+use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
 use fnv::FnvHashMap as HashMap;
@@ -35,6 +36,24 @@ struct Global {
     // not derived from untrusted client input.
     clients: Arc<DashMap<FrontendPid, ConnectedClient>>,
     tracker: TaskTracker,
+    // Addresses come from clients, so this uses the default hasher.
+    quarantine: DashMap<IpAddr, Quarantine>,
+}
+
+/// Protocol errors sent by clients from the same address.
+#[derive(Debug, Clone, Copy)]
+struct Quarantine {
+    errors: usize,
+    last_error: Instant,
+    until: Option<Instant>,
+}
+
+impl Quarantine {
+    /// Address is quarantined or had errors recently.
+    fn active(&self, now: Instant, duration: Duration) -> bool {
+        self.until.is_some_and(|until| until > now)
+            || now.saturating_duration_since(self.last_error) < duration
+    }
 }
 
 /// Bi-directional communications between client and internals.
@@ -58,6 +77,7 @@ impl Comms {
                 offline: AtomicBool::new(false),
                 clients: Arc::new(DashMap::default()),
                 tracker: TaskTracker::new(),
+                quarantine: DashMap::default(),
             }),
         }
     }
@@ -122,6 +142,51 @@ impl Comms {
             .unwrap_or(false)
     }
 
+    /// Client was disconnected for sending a malformed message or violating the protocol.
+    /// Returns true if its address is quarantined as a result.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip`: Client address.
+    /// * `errors`: Number of errors that quarantine the address, 0 disables quarantine.
+    /// * `duration`: How long the address is quarantined for. Errors older than this are forgotten.
+    ///
+    pub fn client_error(&self, ip: IpAddr, errors: usize, duration: Duration) -> bool {
+        if errors == 0 {
+            return false;
+        }
+
+        let now = Instant::now();
+        self.global
+            .quarantine
+            .retain(|_, quarantine| quarantine.active(now, duration));
+
+        let mut entry = self.global.quarantine.entry(ip).or_insert(Quarantine {
+            errors: 0,
+            last_error: now,
+            until: None,
+        });
+        entry.errors += 1;
+        entry.last_error = now;
+
+        if entry.errors >= errors {
+            entry.errors = 0;
+            entry.until = Some(now + duration);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Clients from this address aren't allowed to connect.
+    pub fn quarantined(&self, ip: IpAddr) -> bool {
+        self.global
+            .quarantine
+            .get(&ip)
+            .and_then(|quarantine| quarantine.until)
+            .is_some_and(|until| until > Instant::now())
+    }
+
     /// Notify clients pgDog is shutting down.
     pub fn shutdown(&self) {
         self.global.offline.store(true, Ordering::Relaxed);
@@ -219,6 +284,32 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_client_quarantine() {
+        let comms = Comms::default();
+        let bad: IpAddr = "10.0.0.1".parse().unwrap();
+        let good: IpAddr = "10.0.0.2".parse().unwrap();
+        let duration = Duration::from_millis(50);
+
+        assert!(!comms.client_error(bad, 3, duration));
+        assert!(!comms.client_error(bad, 3, duration));
+        assert!(!comms.client_error(good, 3, duration));
+        assert!(!comms.quarantined(bad));
+
+        assert!(comms.client_error(bad, 3, duration));
+        assert!(comms.quarantined(bad));
+        assert!(!comms.quarantined(good));
+
+        std::thread::sleep(duration);
+        assert!(!comms.quarantined(bad));
+
+        // Disabled.
+        for _ in 0..5 {
+            assert!(!comms.client_error(good, 0, duration));
+        }
+        assert!(!comms.quarantined(good));
+    }
+
     #[test]
     fn test_verify_cancel_after_disconnect() {
         let comms = Comms::default();
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
+use crate::stats::errors::ErrorCategory;
 use crate::unique_id;
 
 /// Frontend error.
@@ -46,6 +47,9 @@ pub enum Error {
     #[error("query timeout")]
     Timeout(#[from] tokio::time::error::Elapsed),
 
+    #[error("transaction timeout")]
+    TransactionTimeout(std::time::Duration),
+
     #[error("cluster start timeout")]
     ClusterStart,
 
@@ -80,6 +84,16 @@ pub enum Error {
     /// without a durable record of it.
     #[error("2pc wal: {0}")]
     TwoPcWal(Arc<crate::frontend::client::query_engine::two_pc::wal::Error>),
+
+    /// `PREPARE TRANSACTION` failed on at least one shard. The
+    /// transaction was rolled back on all participants.
+    #[error("2pc prepare failed: {0}")]
+    TwoPcPrepare(Box<crate::net::ErrorResponse>),
+
+    /// Prepared statement failed because the schema changed
+    /// and the request can be retried.
+    #[error("schema changed: {0}")]
+    SchemaChanged(Box<crate::net::ErrorResponse>),
 }
 
 impl From<crate::frontend::client::query_engine::multi_step::error::Error> for Error {
@@ -100,6 +114,27 @@ impl Error {
         )
     }
 
+    /// Category reported by `SHOW ERRORS`, if this is an error
+    /// we got from the database or while waiting for it.
+    pub(crate) fn category(&self) -> Option<ErrorCategory> {
+        match self {
+            Error::Backend(err) => Some(err.category()),
+            Error::Timeout(_) | Error::TransactionTimeout(_) => Some(ErrorCategory::Timeout),
+            _ => None,
+        }
+    }
+
+    /// Client sent a malformed message or violated the protocol.
+    ///
+    /// Bad rows in COPY data are data errors, not protocol violations.
+    pub(crate) fn protocol_violation(&self) -> bool {
+        match self {
+            Error::UnexpectedMessage(_) => true,
+            Error::Net(err) => !err.is_retryable(),
+            _ => false,
+        }
+    }
+
     pub(crate) fn disconnect(&self) -> bool {
         if let Error::Net(crate::net::Error::Io(err)) = self
             && err.kind() == ErrorKind::UnexpectedEof
@@ -114,3 +149,21 @@ impl Error {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_protocol_violation() {
+        assert!(Error::UnexpectedMessage('d').protocol_violation());
+        assert!(Error::Net(crate::net::Error::MalformedMessageLength(2)).protocol_violation());
+        assert!(!Error::Net(crate::net::Error::UnexpectedEof).protocol_violation());
+
+        // Malformed COPY rows.
+        let copy = Error::Backend(crate::backend::Error::Router(
+            "invalid input syntax for type bigint".into(),
+        ));
+        assert!(!copy.protocol_violation());
+    }
+}
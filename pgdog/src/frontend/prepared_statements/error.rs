@@ -10,4 +10,7 @@ pub enum Error {
 
     #[error("wrong message")]
     WrongMessage,
+
+    #[error("client has too many prepared statements, limit is {0}")]
+    ClientLimit(usize),
 }
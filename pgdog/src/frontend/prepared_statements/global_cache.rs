@@ -1,4 +1,5 @@
 use bytes::Bytes;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     net::messages::{Parse, RowDescription},
@@ -78,6 +79,13 @@ impl CacheKey {
     }
 }
 
+/// Prepared statement exported from the global cache.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedStatement {
+    pub query: String,
+    pub data_types: Vec<i32>,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct CachedStmt {
     pub counter: usize,
@@ -232,6 +240,14 @@ impl GlobalCache {
         }
     }
 
+    /// The statement failed because the schema changed,
+    /// so its RowDescription is no longer accurate.
+    pub fn invalidate(&mut self, name: &str) {
+        if let Some(entry) = self.names.get_mut(name) {
+            entry.row_description = None;
+        }
+    }
+
     /// Clear the global cache.
     pub fn reset(&mut self) {
         self.statements.clear();
@@ -356,6 +372,52 @@ impl GlobalCache {
     pub fn statements(&self) -> &HashMap<CacheKey, CachedStmt> {
         &self.statements
     }
+
+    /// Export prepared statements, so another PgDog instance
+    /// can import them, e.g. during an upgrade.
+    pub fn export(&self) -> Vec<ExportedStatement> {
+        let mut statements = self
+            .statements
+            .iter()
+            // Duplicates created by insert_anyway.
+            .filter(|(key, _)| key.version == 0)
+            .filter_map(|(_, cached)| {
+                self.names
+                    .get(&cached.name())
+                    .map(|stmt| (cached.counter, stmt))
+            })
+            .collect::<Vec<_>>();
+        statements.sort_by_key(|(counter, _)| *counter);
+
+        statements
+            .into_iter()
+            .map(|(_, stmt)| ExportedStatement {
+                query: stmt.query().to_owned(),
+                data_types: stmt.parse.data_types().collect(),
+            })
+            .collect()
+    }
+
+    /// Import prepared statements exported by another PgDog instance.
+    ///
+    /// They are not used by any client yet and will be prepared
+    /// on server connections the first time a client executes them.
+    /// Returns the number of statements added to the cache.
+    pub fn import(&mut self, statements: &[ExportedStatement]) -> usize {
+        let mut imported = 0;
+
+        for stmt in statements {
+            let parse = Parse::new_anonymous(&stmt.query).with_data_types(&stmt.data_types);
+            let (new, name) = self.insert(&parse);
+            self.decrement(&name);
+
+            if new {
+                imported += 1;
+            }
+        }
+
+        imported
+    }
 }
 
 #[cfg(test)]
@@ -494,6 +556,28 @@ mod test {
         assert_eq!(cache.len(), 10);
     }
 
+    #[test]
+    fn test_invalidate_row_description() {
+        use crate::net::messages::Field;
+
+        let mut cache = GlobalCache::default();
+        let (_, name) = cache.insert(&Parse::named("test", "SELECT * FROM users"));
+
+        cache.insert_row_description(&name, &RowDescription::new(&[Field::bigint("id")]));
+        assert_eq!(cache.row_description(&name).unwrap().fields.len(), 1);
+
+        // Schema changed, a new column was added to the table.
+        cache.invalidate(&name);
+        assert!(cache.row_description(&name).is_none());
+        assert_eq!(cache.query(&name), Some("SELECT * FROM users"));
+
+        cache.insert_row_description(
+            &name,
+            &RowDescription::new(&[Field::bigint("id"), Field::text("email")]),
+        );
+        assert_eq!(cache.row_description(&name).unwrap().fields.len(), 2);
+    }
+
     #[test]
     fn test_decrement_marks_as_unused() {
         let mut cache = GlobalCache::default();
@@ -612,4 +696,52 @@ mod test {
         assert!(cache.names.is_empty());
         assert!(cache.unused.is_empty());
     }
+
+    #[test]
+    fn test_export_import() {
+        let mut cache = GlobalCache::default();
+        cache.insert(&Parse::named("test_1", "SELECT $1").with_data_types(&[23]));
+        cache.insert(&Parse::named("test_2", "SELECT 2"));
+        cache.insert_anyway(&Parse::named("test_3", "SELECT 2"));
+
+        let exported = cache.export();
+        assert_eq!(
+            exported,
+            vec![
+                ExportedStatement {
+                    query: "SELECT $1".into(),
+                    data_types: vec![23],
+                },
+                ExportedStatement {
+                    query: "SELECT 2".into(),
+                    data_types: vec![],
+                },
+            ]
+        );
+
+        let json = serde_json::to_string(&exported).unwrap();
+        let exported: Vec<ExportedStatement> = serde_json::from_str(&json).unwrap();
+
+        let mut imported = GlobalCache::default();
+        assert_eq!(imported.import(&exported), 2);
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported.unused.len(), 2);
+        assert!(imported.statements().values().all(|stmt| stmt.used == 0));
+        // Importing the same statements again doesn't create duplicates.
+        assert_eq!(imported.import(&exported), 0);
+        assert_eq!(imported.len(), 2);
+
+        // Clients preparing the same statement use the imported one.
+        let (new, name) =
+            imported.insert(&Parse::named("client", "SELECT $1").with_data_types(&[23]));
+        assert!(!new);
+        assert_eq!(imported.query(&name), Some("SELECT $1"));
+        assert_eq!(imported.parse(&name).unwrap().name(), name);
+        assert!(imported.row_description(&name).is_none());
+        assert!(!imported.unused.contains(&1));
+
+        // Different data types, different statement.
+        let (new, _) = imported.insert(&Parse::named("client", "SELECT $1"));
+        assert!(new);
+    }
 }
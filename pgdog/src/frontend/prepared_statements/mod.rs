@@ -1,12 +1,15 @@
 //! Prepared statements cache.
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{sync::Arc, time::Duration};
 
+use lru::LruCache;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use tokio::time::sleep;
 use tracing::debug;
 
+use pgdog_config::PreparedStatementsClientLimitAction;
+
 use crate::{
     config::{PreparedStatements as PreparedStatementsLevel, config},
     net::{Parse, ProtocolMessage},
@@ -17,7 +20,7 @@ pub mod global_cache;
 pub mod rewrite;
 
 pub use error::Error;
-pub use global_cache::GlobalCache;
+pub use global_cache::{ExportedStatement, GlobalCache};
 pub use rewrite::Rewrite;
 
 static CACHE: Lazy<PreparedStatements> = Lazy::new(PreparedStatements::default);
@@ -31,18 +34,22 @@ fn str_mem(s: &str) -> usize {
 #[derive(Clone, Debug)]
 pub struct PreparedStatements {
     pub(super) global: Arc<RwLock<GlobalCache>>,
-    pub(super) local: HashMap<String, String>,
+    pub(super) local: LruCache<String, String>,
     pub(super) level: PreparedStatementsLevel,
     pub(super) memory_used: usize,
+    pub(super) limit: Option<usize>,
+    pub(super) limit_action: PreparedStatementsClientLimitAction,
 }
 
 impl Default for PreparedStatements {
     fn default() -> Self {
         Self {
             global: Arc::new(RwLock::new(GlobalCache::default())),
-            local: HashMap::default(),
+            local: LruCache::unbounded(),
             level: PreparedStatementsLevel::Extended,
             memory_used: 0,
+            limit: None,
+            limit_action: PreparedStatementsClientLimitAction::default(),
         }
     }
 }
@@ -69,7 +76,7 @@ impl PreparedStatements {
     pub fn insert(&mut self, parse: &mut Parse) {
         let (_new, name) = { self.global.write().insert(parse) };
         let key = parse.name();
-        let existed = self.local.insert(key.to_owned(), name.clone());
+        let existed = self.local.put(key.to_owned(), name.clone());
 
         // Client prepared it again because it got an error the first time.
         // We can check if this is a new statement first, but this is an error
@@ -91,7 +98,7 @@ impl PreparedStatements {
     pub fn insert_anyway(&mut self, parse: &mut Parse) {
         let name = { self.global.write().insert_anyway(parse) };
         let key = parse.name();
-        let existed = self.local.insert(key.to_owned(), name.clone());
+        let existed = self.local.put(key.to_owned(), name.clone());
 
         if let Some(old_value) = existed {
             // Key already existed, only value changed.
@@ -105,15 +112,15 @@ impl PreparedStatements {
         parse.rename_fast(&name)
     }
 
-    /// Get global statement counter.
-    pub fn name(&self, name: &str) -> Option<&String> {
+    /// Get global statement name, marking the statement as recently used.
+    pub fn name(&mut self, name: &str) -> Option<&String> {
         self.local.get(name)
     }
 
     /// Get globally-prepared statement by local name.
     pub fn parse(&self, name: &str) -> Option<Parse> {
         self.local
-            .get(name)
+            .peek(name)
             .and_then(|name| self.global.read().parse(name))
     }
 
@@ -135,7 +142,7 @@ impl PreparedStatements {
 
     /// Remove prepared statement from local cache.
     pub fn close(&mut self, name: &str) {
-        if let Some(global_name) = self.local.remove(name) {
+        if let Some(global_name) = self.local.pop(name) {
             self.global.write().close(&global_name);
             self.memory_used = self
                 .memory_used
@@ -148,7 +155,7 @@ impl PreparedStatements {
         if !self.local.is_empty() {
             let mut global = self.global.write();
 
-            for global_name in self.local.values() {
+            for (_, global_name) in self.local.iter() {
                 global.close(global_name);
             }
         }
@@ -157,6 +164,45 @@ impl PreparedStatements {
         self.memory_used = 0;
     }
 
+    /// Make room for a new prepared statement if the client
+    /// reached `prepared_statements_client_limit`.
+    pub fn check_limit(&mut self, name: &str) -> Result<(), Error> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+
+        // Replacing a statement doesn't add a new one.
+        if self.local.contains(name) {
+            return Ok(());
+        }
+
+        match self.limit_action {
+            PreparedStatementsClientLimitAction::Error => {
+                if self.local.len() >= limit {
+                    return Err(Error::ClientLimit(limit));
+                }
+            }
+
+            PreparedStatementsClientLimitAction::Evict => {
+                while self.local.len() >= limit {
+                    let Some((name, global_name)) = self.local.pop_lru() else {
+                        break;
+                    };
+                    debug!(
+                        "evicting prepared statement \"{}\", client limit of {} reached",
+                        name, limit
+                    );
+                    self.global.write().close(&global_name);
+                    self.memory_used = self
+                        .memory_used
+                        .saturating_sub(str_mem(&name) + str_mem(&global_name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// How much memory is used, approx.
     pub fn memory_used(&self) -> usize {
         self.memory_used
@@ -166,6 +212,12 @@ impl PreparedStatements {
     pub fn set_level(&mut self, level: PreparedStatementsLevel) {
         self.level = level;
     }
+
+    /// Set the maximum number of prepared statements this client can have.
+    pub fn set_limit(&mut self, limit: Option<usize>, action: PreparedStatementsClientLimitAction) {
+        self.limit = limit;
+        self.limit_action = action;
+    }
 }
 
 /// Run prepared statements maintenance task
@@ -278,6 +330,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_client_limit_evict() {
+        let mut statements = PreparedStatements::default();
+        statements.set_limit(Some(2), PreparedStatementsClientLimitAction::Evict);
+
+        for (name, query) in [("a", "SELECT 1"), ("b", "SELECT 2")] {
+            let mut parse = ProtocolMessage::from(Parse::named(name, query));
+            statements.maybe_rewrite(&mut parse).unwrap();
+        }
+
+        // Use "a", so "b" is the least recently used.
+        let mut bind = ProtocolMessage::from(Bind::new_statement("a"));
+        statements.maybe_rewrite(&mut bind).unwrap();
+
+        let mut parse = ProtocolMessage::from(Parse::named("c", "SELECT 3"));
+        statements.maybe_rewrite(&mut parse).unwrap();
+
+        assert_eq!(statements.len_local(), 2);
+        assert!(statements.name("a").is_some());
+        assert!(statements.name("b").is_none());
+        assert!(statements.name("c").is_some());
+
+        // Evicted statement is closed in the global cache.
+        let global = statements.global.read();
+        let unused = global.statements().values().filter(|s| s.used == 0).count();
+        assert_eq!(unused, 1);
+        drop(global);
+
+        // Preparing an existing statement again doesn't evict anything.
+        let mut parse = ProtocolMessage::from(Parse::named("c", "SELECT 3"));
+        statements.maybe_rewrite(&mut parse).unwrap();
+        assert!(statements.name("a").is_some());
+        assert_eq!(statements.len_local(), 2);
+    }
+
+    #[test]
+    fn test_client_limit_error() {
+        let mut statements = PreparedStatements::default();
+        statements.set_limit(Some(2), PreparedStatementsClientLimitAction::Error);
+
+        for (name, query) in [("a", "SELECT 1"), ("b", "SELECT 2")] {
+            let mut parse = ProtocolMessage::from(Parse::named(name, query));
+            statements.maybe_rewrite(&mut parse).unwrap();
+        }
+
+        let mut parse = ProtocolMessage::from(Parse::named("c", "SELECT 3"));
+        let err = statements.maybe_rewrite(&mut parse).unwrap_err();
+        assert!(matches!(err, Error::ClientLimit(2)));
+        assert_eq!(statements.len_local(), 2);
+        assert!(statements.name("c").is_none());
+        assert_eq!(statements.global.read().len(), 2);
+
+        // Closing a statement makes room for a new one.
+        statements.close("a");
+        statements.maybe_rewrite(&mut parse).unwrap();
+        assert!(statements.name("c").is_some());
+    }
+
     /// Regression test: anonymous statements with different query texts
     /// must decrement the OLD global entry, not the new one.
     /// Previously, the new entry was immediately set to used=0 (evictable)
@@ -31,6 +31,7 @@ impl<'a> Rewrite<'a> {
 
     /// Rewrite Parse message.
     fn parse(&mut self, parse: &mut Parse) -> Result<(), Error> {
+        self.statements.check_limit(parse.name())?;
         self.statements.insert(parse);
         Ok(())
     }
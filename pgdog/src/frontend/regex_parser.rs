@@ -2,6 +2,7 @@ use once_cell::sync::Lazy;
 use pgdog_config::{General, QueryParserLevel};
 use regex::RegexSet;
 
+use crate::config::TemporaryTables;
 use crate::frontend::ClientRequest;
 use crate::util::truncate_utf8;
 
@@ -27,6 +28,15 @@ static CMD_ADVISORY: &[&str] = &[
     r"(?i)\bpg_try_advisory_xact_lock_shared\b",
 ];
 
+/// Temporary tables, views and sequences.
+static CMD_TEMPORARY: &str = r"CREATE\s+(OR\s+REPLACE\s+)?((GLOBAL|LOCAL)\s+)?TEMP(ORARY)?\b";
+
+/// `SELECT ... INTO TEMP`, which can appear anywhere in the query.
+static CMD_INTO_TEMPORARY: &str = r"(?i)\bINTO\s+((GLOBAL|LOCAL)\s+)?TEMP(ORARY)?\b";
+
+/// `SELECT set_config(...)` changes parameters just like `SET`.
+static CMD_SET_CONFIG: &str = r"(?i)\bset_config\b";
+
 fn cmd_base_patterns() -> impl Iterator<Item = String> {
     CMD_BASE
         .iter()
@@ -37,11 +47,24 @@ static CMD_RE: Lazy<RegexSet> = Lazy::new(|| RegexSet::new(cmd_base_patterns()).
 static CMD_RE_ADVISORY: Lazy<RegexSet> = Lazy::new(|| {
     RegexSet::new(cmd_base_patterns().chain(CMD_ADVISORY.iter().map(|s| s.to_string()))).unwrap()
 });
+static CMD_RE_TEMPORARY: Lazy<RegexSet> = Lazy::new(|| {
+    RegexSet::new([
+        format!("{}{}", COMMENT_PREFIX, CMD_TEMPORARY),
+        CMD_INTO_TEMPORARY.to_string(),
+    ])
+    .unwrap()
+});
+static CMD_RE_SET_CONFIG: Lazy<RegexSet> = Lazy::new(|| RegexSet::new([CMD_SET_CONFIG]).unwrap());
 
 #[derive(Debug, Clone)]
 pub(crate) struct RegexParser {
     limit: usize,
     level: QueryParserLevel,
+    /// Parse statements creating temporary objects, so they can be pinned or rejected.
+    temporary_tables: bool,
+    /// Parse `set_config()` calls, so parameters can be checked against
+    /// `allowed_parameters` and `denied_parameters`.
+    restricted_parameters: bool,
 }
 
 impl Default for RegexParser {
@@ -49,13 +72,25 @@ impl Default for RegexParser {
         Self {
             limit: General::regex_parser_limit(),
             level: QueryParserLevel::default(),
+            temporary_tables: false,
+            restricted_parameters: false,
         }
     }
 }
 
 impl RegexParser {
-    pub(crate) fn new(limit: usize, level: QueryParserLevel) -> Self {
-        Self { level, limit }
+    pub(crate) fn new(
+        limit: usize,
+        level: QueryParserLevel,
+        temporary_tables: TemporaryTables,
+        restricted_parameters: bool,
+    ) -> Self {
+        Self {
+            level,
+            limit,
+            temporary_tables: temporary_tables != TemporaryTables::Allow,
+            restricted_parameters,
+        }
     }
 
     /// Check if we should enable the parser just for this request.
@@ -68,11 +103,15 @@ impl RegexParser {
             && let Ok(Some(query)) = request.query()
         {
             let prefix = truncate_utf8(query.query(), self.limit);
-            if with_locks {
-                return CMD_RE_ADVISORY.is_match(prefix);
+            let matched = if with_locks {
+                CMD_RE_ADVISORY.is_match(prefix)
             } else {
-                return CMD_RE.is_match(prefix);
-            }
+                CMD_RE.is_match(prefix)
+            };
+
+            return matched
+                || (self.temporary_tables && CMD_RE_TEMPORARY.is_match(prefix))
+                || (self.restricted_parameters && CMD_RE_SET_CONFIG.is_match(prefix));
         }
 
         false
@@ -96,7 +135,13 @@ mod test {
             Parse::new_anonymous(query).into(),
         ] {
             let req = ClientRequest::from(vec![req]);
-            yes = RegexParser::new(General::regex_parser_limit(), level).use_parser(&req);
+            yes = RegexParser::new(
+                General::regex_parser_limit(),
+                level,
+                TemporaryTables::Allow,
+                false,
+            )
+            .use_parser(&req);
         }
 
         yes
@@ -214,14 +259,24 @@ mod test {
 
         for limit in [query.len(), query.len() + 1, 10_000, usize::MAX] {
             let req = ClientRequest::from(vec![ProtocolMessage::from(Query::new(query))]);
-            let parser = RegexParser::new(limit, QueryParserLevel::SessionControl);
+            let parser = RegexParser::new(
+                limit,
+                QueryParserLevel::SessionControl,
+                TemporaryTables::Allow,
+                false,
+            );
             assert!(parser.use_parser(&req), "limit {} should match", limit);
         }
 
         // Multi-byte characters at the end don't trip the boundary scan.
         let multibyte = "SET application_name TO 'héllo'";
         let req = ClientRequest::from(vec![ProtocolMessage::from(Query::new(multibyte))]);
-        let parser = RegexParser::new(usize::MAX, QueryParserLevel::SessionControl);
+        let parser = RegexParser::new(
+            usize::MAX,
+            QueryParserLevel::SessionControl,
+            TemporaryTables::Allow,
+            false,
+        );
         assert!(parser.use_parser(&req));
     }
 
@@ -289,6 +344,80 @@ mod test {
         assert!(matches_at("SELECT /* inline */ pg_try_advisory_lock(1)", l));
     }
 
+    #[test]
+    fn test_temporary() {
+        let matches_temporary = |query: &str, temporary_tables: TemporaryTables| {
+            let req = ClientRequest::from(vec![ProtocolMessage::from(Query::new(query))]);
+            RegexParser::new(
+                General::regex_parser_limit(),
+                QueryParserLevel::Auto,
+                temporary_tables,
+                false,
+            )
+            .use_parser(&req)
+        };
+
+        for query in [
+            "CREATE TEMP TABLE test (id BIGINT)",
+            "create temporary table test (id bigint)",
+            "CREATE LOCAL TEMPORARY TABLE test (id BIGINT)",
+            "CREATE TEMP TABLE test AS SELECT 1",
+            "/* comment */ CREATE TEMP SEQUENCE test",
+            "CREATE OR REPLACE TEMP VIEW test AS SELECT 1",
+            "CREATE TEMPORARY RECURSIVE VIEW test (n) AS SELECT 1",
+            "SELECT * INTO TEMP test FROM users",
+            "SELECT 1 AS id INTO TEMPORARY TABLE test",
+        ] {
+            assert!(matches_temporary(query, TemporaryTables::Pin), "{}", query);
+            assert!(
+                matches_temporary(query, TemporaryTables::Reject),
+                "{}",
+                query
+            );
+            assert!(
+                !matches_temporary(query, TemporaryTables::Allow),
+                "{}",
+                query
+            );
+        }
+
+        for query in [
+            "CREATE TABLE test (id BIGINT)",
+            "CREATE TABLE temporary_data (id BIGINT)",
+            "SELECT * INTO test FROM users",
+            "INSERT INTO temporary_data VALUES (1)",
+        ] {
+            assert!(!matches_temporary(query, TemporaryTables::Pin), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_set_config() {
+        let matches_set_config = |query: &str, restricted_parameters: bool| {
+            let req = ClientRequest::from(vec![ProtocolMessage::from(Query::new(query))]);
+            RegexParser::new(
+                General::regex_parser_limit(),
+                QueryParserLevel::Auto,
+                TemporaryTables::Allow,
+                restricted_parameters,
+            )
+            .use_parser(&req)
+        };
+
+        for query in [
+            "SELECT set_config('statement_timeout', '1s', false)",
+            "select pg_catalog.set_config('statement_timeout', '1s', true)",
+        ] {
+            assert!(matches_set_config(query, true), "{}", query);
+            assert!(!matches_set_config(query, false), "{}", query);
+        }
+
+        assert!(!matches_set_config(
+            "SELECT current_setting('statement_timeout')",
+            true
+        ));
+    }
+
     #[test]
     fn test_no_match() {
         assert!(!matches("SELECT 1"));
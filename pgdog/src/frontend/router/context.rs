@@ -33,10 +33,18 @@ pub struct RouterContext<'a> {
     pub extended: bool,
     /// AST.
     pub ast: Option<Ast>,
+    /// The query parser failed to parse the query.
+    pub parse_failed: bool,
     /// Schema.
     pub schema: Schema,
     /// Original client request.
     pub client_request: &'a ClientRequest,
+    /// Route taken by the client's last query.
+    pub last_route: Option<&'a str>,
+    /// Shard the client is connected to, if it's just one.
+    pub connected_shard: Option<usize>,
+    /// Route to shard 0 and record the query in the cache, see `dry_run`.
+    pub dry_run: bool,
 }
 
 impl<'a> RouterContext<'a> {
@@ -53,7 +61,7 @@ impl<'a> RouterContext<'a> {
 
         Ok(Self {
             bind,
-            parameter_hints: ParameterHints::from(params),
+            parameter_hints: ParameterHints::new(params, cluster.sharding_key_parameter()),
             cluster,
             transaction,
             copy_mode,
@@ -63,8 +71,12 @@ impl<'a> RouterContext<'a> {
             extended: matches!(query, Some(BufferedQuery::Prepared(_))) || bind.is_some(),
             query,
             ast: buffer.ast.clone(),
+            parse_failed: buffer.parse_failed,
             schema: cluster.schema(),
             client_request: buffer,
+            last_route: None,
+            connected_shard: None,
+            dry_run: cluster.dry_run(),
         })
     }
 
@@ -74,7 +74,14 @@ impl Router {
     /// Parse CopyData messages and shard them.
     pub fn copy_data(&mut self, buffer: &ClientRequest) -> Result<Vec<CopyRow>, Error> {
         match self.latest_command {
-            Command::Copy(ref mut copy) => Ok(copy.shard(&buffer.copy_data()?)?),
+            Command::Copy(ref mut copy) => {
+                let mut rows = copy.shard(&buffer.copy_data()?)?;
+                // CopyDone
+                if buffer.iter().any(|message| message.code() == 'c') {
+                    rows.extend(copy.finish()?);
+                }
+                Ok(rows)
+            }
             _ => Ok(buffer
                 .copy_data()?
                 .into_iter()
@@ -1,4 +1,4 @@
-use pgdog_config::Role;
+use pgdog_config::{Role, SearchPathConflict};
 
 use super::parser::Error;
 use crate::{
@@ -30,11 +30,23 @@ pub struct ParameterHints<'a> {
 
 impl<'a> From<&'a Parameters> for ParameterHints<'a> {
     fn from(value: &'a Parameters) -> Self {
+        Self::new(value, None)
+    }
+}
+
+impl<'a> ParameterHints<'a> {
+    /// Extract routing hints from client parameters.
+    ///
+    /// If `sharding_key_parameter` is set, its value is used as the sharding key,
+    /// unless the client set `pgdog.sharding_key` explicitly.
+    pub fn new(params: &'a Parameters, sharding_key_parameter: Option<&str>) -> Self {
         Self {
-            search_path: value.search_path(),
-            pgdog_shard: value.get(PGDOG_SHARD),
-            pgdog_role: value.get(PGDOG_ROLE),
-            pgdog_sharding_key: value.get(PGDOG_SHARDING_KEY),
+            search_path: params.search_path(),
+            pgdog_shard: params.get(PGDOG_SHARD),
+            pgdog_role: params.get(PGDOG_ROLE),
+            pgdog_sharding_key: params
+                .get(PGDOG_SHARDING_KEY)
+                .or_else(|| sharding_key_parameter.and_then(|name| params.get(name))),
             hooks: ParserHooks::default(),
         }
     }
@@ -42,12 +54,17 @@ impl<'a> From<&'a Parameters> for ParameterHints<'a> {
 
 impl ParameterHints<'_> {
     /// Compute shard from parameters.
+    ///
+    /// Returns the conflicting schemas in `search_path` if the cluster is configured
+    /// to return an error for them. It's up to the caller to decide if the query
+    /// actually depends on `search_path`.
     pub(crate) fn compute_shard(
         &self,
         shards: &mut ShardsWithPriority,
         sharding_schema: &ShardingSchema,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<String>, Error> {
         let mut schema_sharder = SchemaSharder::default();
+        let mut conflict = None;
 
         if let Some(ParameterValue::Integer(val)) = self.pgdog_shard {
             let shard = Shard::Direct(*val as usize);
@@ -95,12 +112,28 @@ impl ParameterHints<'_> {
                 _ => (),
             }
 
+            // Schemas in search_path mapped to different shards.
+            let mut search_path_sharder = SchemaSharder::default();
+            search_path_sharder.resolve_parameter(search_path, &sharding_schema.schemas);
+
+            if let Some((shard, schemas)) = search_path_sharder.conflict() {
+                match sharding_schema.search_path_conflict {
+                    SearchPathConflict::First => (),
+                    SearchPathConflict::Error => conflict = Some(schemas),
+                    SearchPathConflict::Broadcast => {
+                        self.hooks.record_sharded_schema(&shard, &schemas);
+                        shards.push(ShardWithPriority::new_search_path(shard, &schemas));
+                        return Ok(None);
+                    }
+                }
+            }
+
             if let Some((shard, schema)) = schema_sharder.get() {
                 self.hooks.record_sharded_schema(&shard, schema);
                 shards.push(ShardWithPriority::new_search_path(shard, schema));
             }
         }
-        Ok(())
+        Ok(conflict)
     }
 
     /// Compute role from parameter value.
@@ -182,4 +215,76 @@ mod tests {
         let result = shards.shard();
         assert_eq!(*result, Shard::Direct(0));
     }
+
+    #[test]
+    fn test_search_path_conflict() {
+        let mut sharding_schema = make_sharding_schema(&[("sales", 0), ("inventory", 1)]);
+        let search_path = ParameterValue::Tuple(vec!["inventory".into(), "sales".into()]);
+        let hints = ParameterHints {
+            search_path: Some(&search_path),
+            ..Default::default()
+        };
+        let shard = |sharding_schema: &ShardingSchema| {
+            let mut shards = ShardsWithPriority::default();
+            let conflict = hints.compute_shard(&mut shards, sharding_schema).unwrap();
+            ((*shards.shard()).clone(), conflict)
+        };
+
+        sharding_schema.search_path_conflict = SearchPathConflict::First;
+        assert_eq!(shard(&sharding_schema), (Shard::Direct(1), None));
+
+        // The conflict is reported, but the parser decides if the query needs it.
+        sharding_schema.search_path_conflict = SearchPathConflict::Error;
+        assert_eq!(
+            shard(&sharding_schema),
+            (Shard::Direct(1), Some("inventory,sales".into()))
+        );
+
+        sharding_schema.search_path_conflict = SearchPathConflict::Broadcast;
+        assert_eq!(shard(&sharding_schema), (Shard::Multi(vec![0, 1]), None));
+
+        // Schemas on the same shard don't conflict.
+        let search_path = ParameterValue::Tuple(vec!["sales".into(), "sales".into()]);
+        let hints = ParameterHints {
+            search_path: Some(&search_path),
+            ..Default::default()
+        };
+        sharding_schema.search_path_conflict = SearchPathConflict::Error;
+        let mut shards = ShardsWithPriority::default();
+        let conflict = hints.compute_shard(&mut shards, &sharding_schema).unwrap();
+        assert_eq!(*shards.shard(), Shard::Direct(0));
+        assert!(conflict.is_none());
+    }
+
+    #[test]
+    fn test_sharding_key_parameter() {
+        let sharding_schema = make_sharding_schema(&[("sales", 0), ("inventory", 1)]);
+        let shard = |params: &Parameters| {
+            let hints = ParameterHints::new(params, Some("app.current_tenant"));
+            let mut shards = ShardsWithPriority::default();
+            hints.compute_shard(&mut shards, &sharding_schema).unwrap();
+            let result = shards.shard();
+            (*result).clone()
+        };
+
+        let mut params = Parameters::default();
+        assert_eq!(shard(&params), Shard::All);
+
+        params.insert("app.current_tenant", "inventory");
+        assert_eq!(shard(&params), Shard::Direct(1));
+
+        // SET LOCAL overrides the session value until the transaction ends.
+        params.insert_transaction("app.current_tenant", "sales", true);
+        assert_eq!(shard(&params), Shard::Direct(0));
+        params.rollback();
+        assert_eq!(shard(&params), Shard::Direct(1));
+
+        // pgdog.sharding_key takes priority.
+        params.insert(PGDOG_SHARDING_KEY, "sales");
+        assert_eq!(shard(&params), Shard::Direct(0));
+        params.reset(PGDOG_SHARDING_KEY);
+
+        params.reset("app.current_tenant");
+        assert_eq!(shard(&params), Shard::All);
+    }
 }
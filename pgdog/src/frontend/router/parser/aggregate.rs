@@ -66,21 +66,33 @@ impl fmt::Display for AggregateFunction {
 pub struct Aggregate {
     targets: Vec<AggregateTarget>,
     group_by: Vec<usize>,
+    /// GROUP BY contains expressions that aren't in the select list.
+    unresolved_group_by: bool,
 }
 
 #[cfg(feature = "new_parser")]
 fn index_of_column(stmt: &nodes::SelectStmt, qualified_column_name: &[&str]) -> Option<usize> {
-    stmt.target_list().iter().position(|node| {
-        let Node::ColumnRef(c) = node.val() else {
-            return false;
-        };
-        let selected_column = c
-            .fields()
-            .iter()
-            .filter_map(Node::as_str)
-            .collect::<Vec<_>>();
-        columns_match(&selected_column, qualified_column_name)
-    })
+    stmt.target_list()
+        .iter()
+        .position(|node| {
+            let Node::ColumnRef(c) = node.val() else {
+                return false;
+            };
+            let selected_column = c
+                .fields()
+                .iter()
+                .filter_map(Node::as_str)
+                .collect::<Vec<_>>();
+            columns_match(&selected_column, qualified_column_name)
+        })
+        // GROUP BY can also reference an output column by its alias.
+        .or_else(|| match qualified_column_name {
+            [name] => stmt
+                .target_list()
+                .iter()
+                .position(|node| node.name() == Some(*name)),
+            _ => None,
+        })
 }
 
 #[cfg(not(feature = "new_parser"))]
@@ -119,7 +131,17 @@ fn target_list_to_index(stmt: &SelectStmt, column_names: &[&str]) -> Option<usiz
             }
         }
     }
-    None
+
+    // GROUP BY can also reference an output column by its alias.
+    match column_names {
+        [name] => stmt.target_list.iter().position(|node| {
+            matches!(
+                node.node.as_ref(),
+                Some(NodeEnum::ResTarget(res_target)) if res_target.name == *name
+            )
+        }),
+        _ => None,
+    }
 }
 
 fn columns_match(group_by_names: &[&str], select_names: &[&str]) -> bool {
@@ -142,22 +164,27 @@ impl Aggregate {
     /// Figure out what aggregates are present and which ones PgDog supports.
     #[cfg(feature = "new_parser")]
     pub(crate) fn parse(stmt: &nodes::SelectStmt, schema: &Schema) -> Self {
+        let mut unresolved_group_by = false;
         let group_by = stmt
             .group_clause()
             .iter()
-            .filter_map(|node| match node {
-                // We use 0-indexed arrays, Postgres uses 1-indexed.
-                Node::A_Const(c) => c
-                    .val()
-                    .and_then(|v| v.numeric_value::<i32>().map(|x| x as usize - 1)),
-                Node::ColumnRef(c) => index_of_column(
-                    stmt,
-                    &c.fields()
-                        .iter()
-                        .filter_map(Node::as_str)
-                        .collect::<Vec<_>>(),
-                ),
-                _ => None, // FIXME: We should error instead of silently skipping
+            .filter_map(|node| {
+                let index = match node {
+                    // We use 0-indexed arrays, Postgres uses 1-indexed.
+                    Node::A_Const(c) => c
+                        .val()
+                        .and_then(|v| v.numeric_value::<i32>().map(|x| x as usize - 1)),
+                    Node::ColumnRef(c) => index_of_column(
+                        stmt,
+                        &c.fields()
+                            .iter()
+                            .filter_map(Node::as_str)
+                            .collect::<Vec<_>>(),
+                    ),
+                    _ => None,
+                };
+                unresolved_group_by |= index.is_none();
+                index
             })
             .collect();
 
@@ -201,18 +228,23 @@ impl Aggregate {
                 })
             })
             .collect();
-        Self { group_by, targets }
+        Self {
+            group_by,
+            targets,
+            unresolved_group_by,
+        }
     }
 
     #[cfg(not(feature = "new_parser"))]
     pub fn parse(stmt: &SelectStmt, schema: &Schema) -> Self {
         let mut targets = vec![];
+        let mut unresolved_group_by = false;
         let group_by = stmt
             .group_clause
             .iter()
-            .filter_map(|node| {
-                node.node.as_ref().map(|node| match node {
-                    NodeEnum::AConst(aconst) => aconst.val.as_ref().map(|val| match val {
+            .map(|node| {
+                node.node.as_ref().and_then(|node| match node {
+                    NodeEnum::AConst(aconst) => aconst.val.as_ref().and_then(|val| match val {
                         Val::Ival(Integer { ival }) => Some(*ival as usize - 1), // We use 0-indexed arrays, Postgres uses 1-indexed.
                         _ => None,
                     }),
@@ -228,13 +260,15 @@ impl Aggregate {
                                 _ => None,
                             })
                             .collect();
-                        Some(target_list_to_index(stmt, &column_names))
+                        target_list_to_index(stmt, &column_names)
                     }
                     _ => None,
                 })
             })
-            .flatten()
-            .flatten()
+            .filter_map(|index| {
+                unresolved_group_by |= index.is_none();
+                index
+            })
             .collect::<Vec<_>>();
 
         for (idx, node) in stmt.target_list.iter().enumerate() {
@@ -276,7 +310,11 @@ impl Aggregate {
             }
         }
 
-        Self { targets, group_by }
+        Self {
+            targets,
+            group_by,
+            unresolved_group_by,
+        }
     }
 
     pub fn targets(&self) -> &[AggregateTarget] {
@@ -287,6 +325,12 @@ impl Aggregate {
         &self.group_by
     }
 
+    /// GROUP BY references expressions that aren't in the select list,
+    /// so rows from different shards can't be merged.
+    pub fn has_unresolved_group_by(&self) -> bool {
+        self.unresolved_group_by
+    }
+
     pub fn new_count(column: usize) -> Self {
         Self {
             targets: vec![AggregateTarget {
@@ -295,6 +339,7 @@ impl Aggregate {
                 distinct: false,
             }],
             group_by: vec![],
+            unresolved_group_by: false,
         }
     }
 
@@ -306,6 +351,15 @@ impl Aggregate {
                 distinct: false,
             }],
             group_by: group_by.to_vec(),
+            unresolved_group_by: false,
+        }
+    }
+
+    pub fn new_group_by(group_by: &[usize]) -> Self {
+        Self {
+            targets: vec![],
+            group_by: group_by.to_vec(),
+            unresolved_group_by: false,
         }
     }
 
@@ -431,6 +485,7 @@ mod test {
     fn test_parse_group_by_column_name_single() {
         let aggr = parse("SELECT user_id, COUNT(1) FROM example GROUP BY user_id");
         assert_eq!(aggr.group_by(), &[0]);
+        assert!(!aggr.has_unresolved_group_by());
         assert_eq!(aggr.targets().len(), 1);
         let target = &aggr.targets()[0];
         assert!(matches!(target.function(), AggregateFunction::Count));
@@ -475,6 +530,21 @@ mod test {
         let aggr = parse("SELECT COUNT(*) FROM example GROUP BY user_id");
         assert!(aggr.group_by().is_empty());
         assert_eq!(aggr.targets().len(), 1);
+        assert!(aggr.has_unresolved_group_by());
+    }
+
+    #[test]
+    fn test_parse_group_by_expression_not_in_select() {
+        let aggr = parse("SELECT lower(region), SUM(amount) FROM sales GROUP BY lower(region)");
+        assert!(aggr.group_by().is_empty());
+        assert!(aggr.has_unresolved_group_by());
+    }
+
+    #[test]
+    fn test_parse_group_by_alias() {
+        let aggr = parse("SELECT lower(region) AS r, SUM(amount) FROM sales GROUP BY r");
+        assert_eq!(aggr.group_by(), &[0]);
+        assert!(!aggr.has_unresolved_group_by());
     }
 
     #[test]
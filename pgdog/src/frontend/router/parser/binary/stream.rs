@@ -23,7 +23,7 @@ impl BinaryStream {
     pub fn tuple(&mut self) -> Result<Option<Tuple>, Error> {
         loop {
             if let Some(header) = &self.header {
-                match Tuple::read(header, &mut self.buffer.as_slice()) {
+                match Tuple::read(header, &mut self.buffer.as_slice())? {
                     Some(tuple) => {
                         self.buffer = Vec::from(&self.buffer[tuple.bytes_read(header)..]);
                         return Ok(Some(tuple));
@@ -41,6 +41,11 @@ impl BinaryStream {
         }
     }
 
+    /// Received data that doesn't form a complete header or tuple.
+    pub fn pending(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
     pub fn tuples(&mut self) -> Iter<'_> {
         Iter::new(self)
     }
@@ -4,7 +4,7 @@ use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use crate::net::messages::ToBytes;
 
-use super::header::Header;
+use super::{super::Error, header::Header};
 
 #[derive(Debug, Clone)]
 pub enum Data {
@@ -58,33 +58,37 @@ impl Tuple {
 
     /// Calculate the total bytes needed to read a complete tuple.
     /// Returns None if there isn't enough data to determine the size.
-    fn calculate_needed_bytes(header: &Header, data: &[u8]) -> Option<usize> {
+    fn calculate_needed_bytes(header: &Header, data: &[u8]) -> Result<Option<usize>, Error> {
         let mut offset = 0;
 
         // Need at least 2 bytes for num_cols
         if data.len() < 2 {
-            return None;
+            return Ok(None);
         }
         let num_cols = i16::from_be_bytes([data[0], data[1]]);
         offset += 2;
 
         // Terminator (-1) only needs the 2 bytes we already checked
         if num_cols == -1 {
-            return Some(2);
+            return Ok(Some(2));
+        }
+
+        if num_cols < 0 {
+            return Err(Error::BinaryColumnCount(num_cols));
         }
 
         // OID if header has it
         if header.has_oid {
             offset += 4;
             if data.len() < offset {
-                return None;
+                return Ok(None);
             }
         }
 
         // Each column has a 4-byte length, plus the data
         for _ in 0..num_cols {
             if data.len() < offset + 4 {
-                return None;
+                return Ok(None);
             }
             let len = i32::from_be_bytes([
                 data[offset],
@@ -94,32 +98,41 @@ impl Tuple {
             ]);
             offset += 4;
 
-            if len >= 0 {
-                offset += len as usize;
-                if data.len() < offset {
-                    return None;
-                }
+            // NULL
+            if len == -1 {
+                continue;
+            }
+
+            if len < 0 {
+                return Err(Error::BinaryColumnLength(len));
+            }
+
+            offset += len as usize;
+            if data.len() < offset {
+                return Ok(None);
             }
         }
 
-        Some(offset)
+        Ok(Some(offset))
     }
 
-    pub(super) fn read(header: &Header, buf: &mut impl Buf) -> Option<Self> {
+    pub(super) fn read(header: &Header, buf: &mut impl Buf) -> Result<Option<Self>, Error> {
         // Get a view of the buffer data to calculate needed bytes
         let data = buf.chunk();
 
         // Check if we have enough data for a complete tuple
-        let needed = Self::calculate_needed_bytes(header, data)?;
+        let Some(needed) = Self::calculate_needed_bytes(header, data)? else {
+            return Ok(None);
+        };
 
         // We have enough data - now actually parse it
         let num_cols = buf.get_i16();
         if num_cols == -1 {
-            return Some(Tuple {
+            return Ok(Some(Tuple {
                 row: vec![],
                 oid: None,
                 end: true,
-            });
+            }));
         }
 
         let oid = if header.has_oid {
@@ -146,11 +159,11 @@ impl Tuple {
                 + if header.has_oid { 4 } else { 0 }
         );
 
-        Some(Self {
+        Ok(Some(Self {
             row,
             oid,
             end: false,
-        })
+        }))
     }
 
     pub(super) fn bytes_read(&self, header: &Header) -> usize {
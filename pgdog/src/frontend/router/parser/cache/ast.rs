@@ -7,12 +7,13 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::time::Instant;
 
+use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use tracing::warn;
 
 use super::super::{Error, Route, Shard, StatementRewrite, StatementRewriteContext};
-use super::Stats;
+use super::{Cache, Stats};
 use crate::backend::schema::Schema;
 use crate::frontend::PreparedStatements;
 use crate::frontend::router::parser::cache::AstQuery;
@@ -30,6 +31,10 @@ pub struct Ast {
     pub comment_shard: Option<Shard>,
     /// Role.
     pub comment_role: Option<Role>,
+    /// Replica pinned by a query comment.
+    pub comment_replica: Option<String>,
+    /// Tag set by a query comment, used as a label in stats.
+    pub comment_tag: Option<String>,
     /// Parser query engine used.
     pub query_parser_engine: QueryParserEngine,
     /// Inner sync.
@@ -50,6 +55,9 @@ pub struct AstInner {
     pub rewrite_plan: RewritePlan,
     /// Original query.
     pub query_without_comment: Arc<str>,
+    /// Original query with constants replaced by placeholders,
+    /// computed the first time it's needed.
+    normalized: OnceCell<Option<Arc<str>>>,
 }
 
 impl AstInner {
@@ -61,6 +69,7 @@ impl AstInner {
             stats: Mutex::new(Stats::new()),
             rewrite_plan: RewritePlan::default(),
             query_without_comment: "".into(),
+            normalized: OnceCell::new(),
         }
     }
 
@@ -71,8 +80,27 @@ impl AstInner {
             stats: Mutex::new(Stats::new()),
             rewrite_plan: RewritePlan::default(),
             query_without_comment: "".into(),
+            normalized: OnceCell::new(),
         }
     }
+
+    /// Original query with constants replaced by placeholders. It's normalized once
+    /// and shared by all requests using this entry.
+    ///
+    /// Returns `None` if the query isn't known or can't be normalized.
+    pub fn normalized(&self) -> Option<&str> {
+        if self.query_without_comment.is_empty() {
+            return None;
+        }
+
+        self.normalized
+            .get_or_init(|| {
+                Cache::normalize(&self.query_without_comment)
+                    .ok()
+                    .map(Arc::from)
+            })
+            .as_deref()
+    }
 }
 
 impl Deref for Ast {
@@ -152,6 +180,8 @@ impl Ast {
         Ok(Self {
             cached: true,
             comment_shard: None,
+            comment_replica: None,
+            comment_tag: None,
             comment_role: None,
             query_parser_engine: schema.query_parser_engine,
             inner: Arc::new(AstInner {
@@ -162,6 +192,7 @@ impl Ast {
                 ast,
                 rewrite_plan,
                 query_without_comment: query.query_without_comment.into(),
+                normalized: OnceCell::new(),
             }),
         })
     }
@@ -194,6 +225,8 @@ impl Ast {
             cached: true,
             comment_role: None,
             comment_shard: None,
+            comment_replica: None,
+            comment_tag: None,
             query_parser_engine,
             inner: Arc::new(AstInner::new(ast.into_inner())),
         })
@@ -212,6 +245,8 @@ impl Ast {
                     cached: true,
                     comment_role: None,
                     comment_shard: None,
+                    comment_replica: None,
+                    comment_tag: None,
                     query_parser_engine,
                     inner: Arc::new(AstInner::old(ast)),
                 })
@@ -227,6 +262,8 @@ impl Ast {
             cached: true,
             comment_role: None,
             comment_shard: None,
+            comment_replica: None,
+            comment_tag: None,
             query_parser_engine: QueryParserEngine::default(),
             inner: Arc::new(AstInner::new(stmts)),
         }
@@ -239,6 +276,8 @@ impl Ast {
             cached: true,
             comment_role: None,
             comment_shard: None,
+            comment_replica: None,
+            comment_tag: None,
             query_parser_engine: QueryParserEngine::default(),
             inner: Arc::new(AstInner::old(parse_result)),
         }
@@ -250,6 +289,62 @@ impl Ast {
         &self.ast
     }
 
+    /// Number of statements in the query.
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn len(&self) -> usize {
+        self.ast.len()
+    }
+
+    /// Number of statements in the query.
+    #[cfg(not(feature = "new_parser"))]
+    pub(crate) fn len(&self) -> usize {
+        self.ast.protobuf.stmts.len()
+    }
+
+    /// Split a multi-statement query into one AST per statement.
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn split(&self) -> Vec<Ast> {
+        self.ast
+            .iter()
+            .map(|stmt| Self {
+                cached: false,
+                comment_shard: self.comment_shard.clone(),
+                comment_role: self.comment_role,
+                comment_replica: self.comment_replica.clone(),
+                comment_tag: self.comment_tag.clone(),
+                query_parser_engine: self.query_parser_engine,
+                inner: Arc::new(AstInner::new(make::owned(|mem| {
+                    mem.make_list(&[mem.make_unique(stmt)])
+                }))),
+            })
+            .collect()
+    }
+
+    /// Split a multi-statement query into one AST per statement.
+    #[cfg(not(feature = "new_parser"))]
+    pub(crate) fn split(&self) -> Vec<Ast> {
+        self.ast
+            .protobuf
+            .stmts
+            .iter()
+            .map(|stmt| Self {
+                cached: false,
+                comment_shard: self.comment_shard.clone(),
+                comment_role: self.comment_role,
+                comment_replica: self.comment_replica.clone(),
+                comment_tag: self.comment_tag.clone(),
+                query_parser_engine: self.query_parser_engine,
+                inner: Arc::new(AstInner::old(ParseResult::new(
+                    pg_query::protobuf::ParseResult {
+                        version: self.ast.protobuf.version,
+                        stmts: vec![stmt.clone()],
+                    },
+                    "".into(),
+                ))),
+            })
+            .collect()
+    }
+
     /// Update stats for this statement, given the route
     /// calculated by the query parser.
     pub fn update_stats(&self, route: &Route) {
@@ -6,7 +6,7 @@ use pg_query::normalize;
 use pg_raw_parse::normalize::normalize;
 use pgdog_config::QueryParserEngine;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -33,6 +33,14 @@ pub struct Stats {
     pub parse_time: Duration,
     /// Fingerprints calculated.
     pub fingerprints: usize,
+    /// Queries evicted to make room for new ones.
+    pub evictions: usize,
+    /// Queries removed because they outlived the TTL.
+    pub expired: usize,
+    /// Statements of a type the query router doesn't recognize.
+    pub unknown: usize,
+    /// Queries the parser failed to parse.
+    pub parse_failures: usize,
 }
 
 impl Stats {
@@ -45,15 +53,56 @@ impl Stats {
     }
 }
 
+/// Cached AST and when it was parsed.
+#[derive(Debug)]
+struct Entry {
+    ast: Ast,
+    created_at: Instant,
+}
+
 /// Mutex-protected query cache.
 #[derive(Debug)]
 pub(super) struct Inner {
     /// Least-recently-used cache.
-    queries: LruCache<Arc<str>, Ast>,
+    queries: LruCache<Arc<str>, Entry>,
+    /// How long entries stay in the cache.
+    ttl: Option<Duration>,
     /// Cache global stats.
     pub(super) stats: Stats,
 }
 
+impl Inner {
+    /// Get a cached query, removing it if it's expired.
+    fn get(&mut self, query: &str) -> Option<&Ast> {
+        let expired = match (self.ttl, self.queries.peek(query)) {
+            (Some(ttl), Some(entry)) => entry.created_at.elapsed() >= ttl,
+            _ => false,
+        };
+
+        if expired {
+            self.queries.pop(query);
+            self.stats.expired += 1;
+            return None;
+        }
+
+        self.queries.get(query).map(|entry| &entry.ast)
+    }
+
+    /// Add a query to the cache, evicting the least recently used one if full.
+    fn put(&mut self, query: Arc<str>, ast: Ast) {
+        let entry = Entry {
+            ast,
+            created_at: Instant::now(),
+        };
+
+        if let Some((evicted, _)) = self.queries.push(query.clone(), entry)
+            && evicted != query
+        {
+            self.stats.evictions += 1;
+        }
+    }
+}
+
 /// AST cache.
 #[derive(Clone, Debug)]
 pub struct Cache {
@@ -66,6 +115,7 @@ impl Cache {
         Self {
             inner: Arc::new(Mutex::new(Inner {
                 queries: LruCache::unbounded(),
+                ttl: None,
                 stats: Stats::default(),
             })),
         }
@@ -86,6 +136,22 @@ impl Cache {
         debug!("ast cache size set to {}", capacity);
     }
 
+    /// Current cache capacity and TTL.
+    #[cfg(test)]
+    pub(crate) fn limits() -> (usize, Option<Duration>) {
+        let inner = CACHE.inner.lock();
+        (inner.queries.cap().get(), inner.ttl)
+    }
+
+    /// Set how long queries stay in the cache before they are parsed again.
+    ///
+    /// `None` keeps them until evicted.
+    pub fn set_ttl(ttl: Option<Duration>) {
+        CACHE.inner.lock().ttl = ttl;
+
+        debug!("ast cache ttl set to {:?}", ttl);
+    }
+
     /// Handle parsing a query.
     pub fn query(
         &self,
@@ -93,10 +159,18 @@ impl Cache {
         ctx: &AstContext<'_>,
         prepared_statements: &mut PreparedStatements,
     ) -> Result<Ast, Error> {
-        match query {
+        let result = match query {
             BufferedQuery::Prepared(_) => self.parse(query, ctx, prepared_statements),
             BufferedQuery::Query(_) => self.simple(query, ctx, prepared_statements),
+        };
+
+        if let Err(ref err) = result
+            && err.is_parse()
+        {
+            self.inner.lock().stats.parse_failures += 1;
         }
+
+        result
     }
 
     /// Parse a statement by either getting it from cache
@@ -116,7 +190,7 @@ impl Cache {
 
         {
             let mut guard = self.inner.lock();
-            let ast = guard.queries.get_mut(query_and_comment.query).map(|entry| {
+            let ast = guard.get(query_and_comment.query).map(|entry| {
                 entry.stats.lock().hits += 1; // No contention on this.
                 entry.clone()
             });
@@ -124,6 +198,8 @@ impl Cache {
                 guard.stats.hits += 1;
                 ast.comment_role = query_and_comment.role;
                 ast.comment_shard = query_and_comment.shard.clone();
+                ast.comment_replica = query_and_comment.replica.clone();
+                ast.comment_tag = query_and_comment.tag.clone();
 
                 return Ok(ast);
             }
@@ -140,6 +216,8 @@ impl Cache {
         )?;
         entry.comment_role = query_and_comment.role;
         entry.comment_shard = query_and_comment.shard.clone();
+        entry.comment_replica = query_and_comment.replica.clone();
+        entry.comment_tag = query_and_comment.tag.clone();
         let parse_time = entry.stats.lock().parse_time;
 
         let mut guard = self.inner.lock();
@@ -150,9 +228,7 @@ impl Cache {
         // (direct-shard) variant.
         let cacheable = entry.comment_shard.is_none() || entry.rewrite_plan.is_empty();
         if cacheable {
-            guard
-                .queries
-                .put(entry.query_without_comment.clone(), entry.clone());
+            guard.put(entry.query_without_comment.clone(), entry.clone());
         }
         guard.stats.misses += 1;
         guard.stats.parse_time += parse_time;
@@ -181,6 +257,8 @@ impl Cache {
         entry.cached = false;
         entry.comment_role = query_and_comment.role;
         entry.comment_shard = query_and_comment.shard.clone();
+        entry.comment_replica = query_and_comment.replica.clone();
+        entry.comment_tag = query_and_comment.tag.clone();
 
         let parse_time = entry.stats.lock().parse_time;
 
@@ -201,11 +279,11 @@ impl Cache {
         route: &Route,
         query_parser_engine: QueryParserEngine,
     ) -> Result<(), Error> {
-        let normalized = normalize(query)?;
+        let normalized = Self::normalize(query)?;
 
         {
             let mut guard = self.inner.lock();
-            if let Some(entry) = guard.queries.get(normalized.as_str()) {
+            if let Some(entry) = guard.get(normalized.as_str()) {
                 entry.update_stats(route);
                 guard.stats.hits += 1;
                 return Ok(());
@@ -216,12 +294,18 @@ impl Cache {
         entry.update_stats(route);
 
         let mut guard = self.inner.lock();
-        guard.queries.put(normalized.into(), entry);
+        guard.put(normalized.into(), entry);
         guard.stats.misses += 1;
 
         Ok(())
     }
 
+    /// Replace constants in the query with placeholders,
+    /// so it can be logged without leaking sensitive values.
+    pub fn normalize(query: &str) -> Result<String, Error> {
+        Ok(normalize(query)?)
+    }
+
     /// Get global cache instance.
     pub fn get() -> Self {
         CACHE.clone()
@@ -237,7 +321,7 @@ impl Cache {
                 guard
                     .queries
                     .iter()
-                    .map(|c| *c.1.stats.lock())
+                    .map(|c| *c.1.ast.stats.lock())
                     .collect::<Vec<_>>(),
                 guard.stats,
             )
@@ -249,6 +333,11 @@ impl Cache {
         (stats, len)
     }
 
+    /// Record a statement of a type the query router doesn't recognize.
+    pub fn record_unknown() {
+        CACHE.inner.lock().stats.unknown += 1;
+    }
+
     /// Get a copy of all queries stored in the cache.
     pub fn queries() -> HashMap<Arc<str>, Ast> {
         Self::get()
@@ -256,7 +345,7 @@ impl Cache {
             .lock()
             .queries
             .iter()
-            .map(|i| (i.0.clone(), i.1.clone()))
+            .map(|i| (i.0.clone(), i.1.ast.clone()))
             .collect()
     }
 
@@ -268,5 +357,7 @@ impl Cache {
         guard.queries.clear();
         guard.stats.hits = 0;
         guard.stats.misses = 0;
+        guard.stats.evictions = 0;
+        guard.stats.expired = 0;
     }
 }
@@ -358,3 +358,43 @@ fn test_truncated_query_non_ascii_char_boundary() {
     let ast_query = AstQuery::from_query(&buffered);
     assert_eq!(ast_query.truncated_query(9), "SELECT '€");
 }
+
+#[test]
+fn test_cache_evictions() {
+    let _guard = CACHE_STATS_LOCK.lock();
+    Cache::reset();
+    let (capacity, _) = Cache::limits();
+    Cache::resize(2);
+
+    run_prepared("SELECT 1 FROM cache_evictions_1");
+    run_prepared("SELECT 1 FROM cache_evictions_2");
+    run_prepared("SELECT 1 FROM cache_evictions_3");
+
+    let (stats, len) = Cache::stats();
+    Cache::resize(capacity);
+
+    assert_eq!(len, 2);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.misses, 3);
+}
+
+#[test]
+fn test_cache_ttl() {
+    let _guard = CACHE_STATS_LOCK.lock();
+    Cache::reset();
+    let (_, ttl) = Cache::limits();
+    Cache::set_ttl(Some(Duration::from_millis(10)));
+
+    let q = "SELECT 1 FROM cache_ttl";
+    run_prepared(q);
+    std::thread::sleep(Duration::from_millis(20));
+    run_prepared(q);
+
+    let (stats, len) = Cache::stats();
+    Cache::set_ttl(ttl);
+
+    assert_eq!(len, 1);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.hits, 0);
+    assert_eq!(stats.expired, 1);
+}
@@ -39,9 +39,14 @@ pub enum Command {
         name: String,
         value: String,
     },
-    Deallocate,
+    Deallocate {
+        /// Statement name, `None` for `DEALLOCATE ALL`.
+        name: Option<String>,
+    },
     Discard {
         extended: bool,
+        /// `DISCARD ALL`, as opposed to e.g. `DISCARD TEMP`.
+        all: bool,
     },
     Listen {
         channel: String,
@@ -15,6 +15,12 @@ pub(super) static SHARDING_KEY: Lazy<Regex> = Lazy::new(|| {
 });
 pub(super) static ROLE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"pgdog_role: *(primary|replica)"#).unwrap());
+pub(super) static REPLICA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"pgdog_replica: *(?:"([^"]*)"|'([^']*)'|([0-9a-zA-Z._:-]+))"#).unwrap()
+});
+
+pub(super) static TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\btag: *(?:"([^"]*)"|'([^']*)'|([0-9a-zA-Z._:-]+))"#).unwrap());
 
 pub(super) fn get_matched_value<'a>(caps: &'a regex::Captures<'a>) -> Option<&'a str> {
     caps.get(1)
@@ -23,6 +29,21 @@ pub(super) fn get_matched_value<'a>(caps: &'a regex::Captures<'a>) -> Option<&'a
         .map(|m| m.as_str())
 }
 
+/// Replica pinned by the comment, e.g. `pgdog_replica: replica-1`.
+pub(super) fn replica_from_comment(comment: &str) -> Option<String> {
+    REPLICA
+        .captures(comment)
+        .and_then(|cap| get_matched_value(&cap).map(|name| name.to_string()))
+        .filter(|name| !name.is_empty())
+}
+
+/// Tag used as a label in query stats, e.g. `tag: checkout-flow`.
+pub(super) fn tag_from_comment(comment: &str) -> Option<String> {
+    TAG.captures(comment)
+        .and_then(|cap| get_matched_value(&cap).map(|tag| tag.to_string()))
+        .filter(|tag| !tag.is_empty())
+}
+
 pub(super) fn shard_role_from_comment(
     comment: &str,
     schema: &ShardingSchema,
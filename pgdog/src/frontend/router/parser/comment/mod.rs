@@ -18,12 +18,14 @@ pub struct QueryAndComment<'a> {
     pub comment: String,
     pub role: Option<Role>,
     pub shard: Option<Shard>,
+    pub replica: Option<String>,
+    pub tag: Option<String>,
 }
 
 /// Extract SQL C-style block comments from both the beginning and the end
 /// of the query, returning the stripped query string and directives found
 /// in either side. Leading takes precedence when both sides carry the same
-/// directive (e.g. shard or role).
+/// directive (e.g. shard, role, replica or tag).
 ///
 /// This algorithm uses a heuristic, and not the real Postgres parser, because the heuristic
 /// is 2x faster and will work most of the time.
@@ -56,6 +58,8 @@ pub fn parse_edge_comment<'a>(
         Some(c) => directive::shard_role_from_comment(c, schema)?,
         None => (None, None),
     };
+    let mut replica = leading.and_then(directive::replica_from_comment);
+    let mut tag = leading.and_then(directive::tag_from_comment);
     if let Some(c) = trailing {
         let (t_shard, t_role) = directive::shard_role_from_comment(c, schema)?;
         if shard.is_none() {
@@ -64,6 +68,12 @@ pub fn parse_edge_comment<'a>(
         if role.is_none() {
             role = t_role;
         }
+        if replica.is_none() {
+            replica = directive::replica_from_comment(c);
+        }
+        if tag.is_none() {
+            tag = directive::tag_from_comment(c);
+        }
     }
 
     Ok(QueryAndComment {
@@ -77,5 +87,7 @@ pub fn parse_edge_comment<'a>(
         },
         shard,
         role,
+        replica,
+        tag,
     })
 }
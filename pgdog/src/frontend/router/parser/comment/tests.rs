@@ -117,6 +117,42 @@ fn test_invalid_role_detection() {
     assert_eq!(result.role, None);
 }
 
+#[test]
+fn test_replica_detection() {
+    let schema = test_schema();
+    let query = "SELECT * FROM users /* pgdog_replica: replica-1.internal */";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.replica.as_deref(), Some("replica-1.internal"));
+
+    let query = "/* pgdog_replica: '10.0.0.2:5433' */ SELECT 1 /* pgdog_replica: other */";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.replica.as_deref(), Some("10.0.0.2:5433"));
+    assert_eq!(result.role, None);
+
+    let query = "SELECT * FROM users /* pgdog_replica: '' */";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.replica, None);
+}
+
+#[test]
+fn test_tag_detection() {
+    let schema = test_schema();
+    let query = "/* tag: checkout-flow */ SELECT * FROM users";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.tag.as_deref(), Some("checkout-flow"));
+    assert_eq!(result.query, "SELECT * FROM users");
+
+    let query = "SELECT 1 /* pgdog_shard: 1 tag: 'billing report' */";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.tag.as_deref(), Some("billing report"));
+    assert_eq!(result.shard, Some(Shard::Direct(1)));
+
+    // Not a tag.
+    let query = "/* pgdog_tag: checkout-flow */ SELECT 1";
+    let result = parse_edge_comment(query, &schema).unwrap();
+    assert_eq!(result.tag, None);
+}
+
 #[test]
 fn test_no_role_comment() {
     let schema = test_schema();
@@ -421,6 +457,20 @@ fn test_remove_comment_pgdog_directive() {
     assert_eq!(qac.role, Some(Role::Primary));
 }
 
+#[test]
+fn test_query_label_is_not_a_directive() {
+    // Queries copied from pg_stat_activity carry the label added by `query_labels`.
+    let schema = test_schema();
+    let qac = parse_edge_comment(
+        "/* pgdog: client=127.0.0.1:5432 shard=1 */ SELECT * FROM users",
+        &schema,
+    )
+    .unwrap();
+    assert_eq!(qac.query, "SELECT * FROM users");
+    assert_eq!(qac.shard, None);
+    assert_eq!(qac.role, None);
+}
+
 #[test]
 fn test_sharding_key_with_schema_name() {
     use crate::backend::replication::ShardedSchemas;
@@ -1,6 +1,6 @@
 //! Shortcut the parser given the cluster config.
 
-use pgdog_config::Role;
+use pgdog_config::{MultiStatementQueries, ParseFailures, Role, UnknownStatements};
 
 use crate::frontend::client::TransactionType;
 use crate::frontend::router::parser::ShardsWithPriority;
@@ -8,6 +8,7 @@ use crate::{
     backend::ShardingSchema,
     config::{MultiTenant, ReadWriteStrategy},
     frontend::{BufferedQuery, RouterContext},
+    net::parameter::ParameterValue,
 };
 
 use super::Error;
@@ -39,12 +40,26 @@ pub struct QueryParserContext<'a> {
     pub(super) router_needed: bool,
     /// Are we running multi-tenant checks?
     pub(super) multi_tenant: &'a Option<MultiTenant>,
+    /// Add the tenant filter to reads that don't have one.
+    pub(super) multi_tenant_filter: bool,
     /// Dry run enabled?
     pub(super) dry_run: bool,
     /// Expanded EXPLAIN annotations enabled?
     pub(super) expanded_explain: bool,
+    /// Writes to sharded tables must specify the sharding key.
+    pub(super) require_sharding_key: bool,
+    /// How to route queries with multiple statements.
+    pub(super) multi_statement_queries: MultiStatementQueries,
+    /// How to route statements we don't recognize.
+    pub(super) unknown_statements: UnknownStatements,
+    /// How to route queries we failed to parse.
+    pub(super) parse_failures: ParseFailures,
+    /// Reads at or above this cost go to analytics replicas.
+    pub(super) analytics_query_cost: usize,
     /// Shards calculator.
     pub(super) shards_calculator: ShardsWithPriority,
+    /// Schemas in `search_path` mapped to different shards, if that's an error.
+    pub(super) search_path_conflict: Option<String>,
 }
 
 impl<'a> QueryParserContext<'a> {
@@ -53,7 +68,7 @@ impl<'a> QueryParserContext<'a> {
         let mut shards_calculator = ShardsWithPriority::default();
         let sharding_schema = router_context.cluster.sharding_schema();
 
-        router_context
+        let search_path_conflict = router_context
             .parameter_hints
             .compute_shard(&mut shards_calculator, &sharding_schema)?;
 
@@ -67,10 +82,17 @@ impl<'a> QueryParserContext<'a> {
             prefer_replica: router_context.cluster.prefer_replica(),
             router_needed: router_context.cluster.router_needed(),
             multi_tenant: router_context.cluster.multi_tenant(),
-            dry_run: router_context.cluster.dry_run(),
+            multi_tenant_filter: router_context.cluster.multi_tenant_filter(),
+            dry_run: router_context.dry_run,
             expanded_explain: router_context.cluster.expanded_explain(),
+            require_sharding_key: router_context.cluster.require_sharding_key(),
+            multi_statement_queries: router_context.cluster.multi_statement_queries(),
+            unknown_statements: router_context.cluster.unknown_statements(),
+            parse_failures: router_context.cluster.parse_failures(),
+            analytics_query_cost: router_context.cluster.analytics_query_cost(),
             router_context,
             shards_calculator,
+            search_path_conflict,
         })
     }
 
@@ -102,6 +124,18 @@ impl<'a> QueryParserContext<'a> {
         self.multi_tenant
     }
 
+    /// Tenant the session is bound to, if reads should be filtered by it.
+    pub(super) fn tenant(&self) -> Option<&'a str> {
+        if !self.multi_tenant_filter {
+            return None;
+        }
+
+        match self.router_context.parameter_hints.pgdog_sharding_key {
+            Some(ParameterValue::String(tenant)) => Some(tenant.as_str()),
+            _ => None,
+        }
+    }
+
     pub(super) fn expanded_explain(&self) -> bool {
         self.expanded_explain
     }
@@ -12,10 +12,17 @@ use crate::{
         parser::Shard,
         sharding::{ContextBuilder, ShardedTable, Tables},
     },
-    net::messages::{CopyData, ToBytes},
+    net::{
+        messages::{CopyData, ToBytes},
+        parameter::ParameterValue,
+    },
 };
 
-use super::{BinaryStream, Column, CsvStream, Error, Table, binary::Data};
+use super::{
+    BinaryStream, Column, CsvStream, Error, Table,
+    binary::Data,
+    copy_filter::{ColumnType, CopyFilter},
+};
 
 /// Copy information parsed from a COPY statement.
 #[derive(Debug, Clone)]
@@ -74,8 +81,8 @@ pub struct CopyParser {
     sharded_column: usize,
     /// Schema shard.
     schema_shard: Option<Shard>,
-    /// String representing NULL values in text/CSV format.
-    null_string: String,
+    /// Rows must match this filter, from `COPY ... WHERE`.
+    filter: Option<CopyFilter>,
 }
 
 impl Default for CopyParser {
@@ -90,31 +97,41 @@ impl Default for CopyParser {
             sharded_table: None,
             sharded_column: 0,
             schema_shard: None,
-            null_string: "\\N".to_owned(),
+            filter: None,
         }
     }
 }
 
 impl CopyParser {
     /// Create new copy parser from a COPY statement.
+    ///
+    /// `search_path` is the client's, used to find the types of
+    /// the columns filtered by `WHERE`.
     #[cfg(feature = "new_parser")]
-    pub fn new(stmt: &nodes::CopyStmt, cluster: &Cluster) -> Result<Self, Error> {
+    pub fn new(
+        stmt: &nodes::CopyStmt,
+        cluster: &Cluster,
+        search_path: Option<&ParameterValue>,
+    ) -> Result<Self, Error> {
         let mut parser = Self {
             is_from: stmt.is_from,
             ..Default::default()
         };
 
         let mut format = CopyFormat::Text;
-        let mut null_string = "\\N".to_owned();
+        let mut null_string = None;
 
         if let Some(rel) = stmt.relation() {
             let columns = stmt
                 .attlist()
                 .iter()
                 .map(|column| {
-                    Column::from(column.as_str().expect("attlist is empty or all strings"))
+                    column
+                        .as_str()
+                        .map(Column::from)
+                        .ok_or(Error::CopyColumnList)
                 })
-                .collect::<Vec<_>>();
+                .collect::<Result<Vec<_>, _>>()?;
 
             let table = Table::from(rel);
 
@@ -130,7 +147,14 @@ impl CopyParser {
                 parser.sharded_column = key.position;
             }
 
+            let types = cluster
+                .schema()
+                .table(table, cluster.user(), search_path)
+                .map(|relation| ColumnType::columns(relation, &columns))
+                .unwrap_or_default();
+
             parser.columns = columns.len();
+            parser.filter = CopyFilter::new(stmt.where_clause(), &columns, &types);
 
             for elem in stmt.options() {
                 match elem.defname().unwrap_or_default().to_lowercase().as_str() {
@@ -160,7 +184,7 @@ impl CopyParser {
 
                     "null" => {
                         if let Some(string) = elem.arg().as_str() {
-                            null_string = string.to_owned();
+                            null_string = Some(string.to_owned());
                         }
                     }
 
@@ -169,6 +193,12 @@ impl CopyParser {
             }
         }
 
+        // `\N` in text format, an unquoted empty string in CSV.
+        let null_string = null_string.unwrap_or_else(|| match format {
+            CopyFormat::Csv => String::new(),
+            _ => "\\N".to_owned(),
+        });
+
         parser.stream = if format == CopyFormat::Binary {
             CopyStream::Binary(BinaryStream::default())
         } else {
@@ -180,21 +210,24 @@ impl CopyParser {
             )))
         };
         parser.sharding_schema = cluster.sharding_schema();
-        parser.null_string = null_string;
 
         Ok(parser)
     }
 
     cfg_select! {
         not(feature = "new_parser") => {
-            pub fn new(stmt: &CopyStmt, cluster: &Cluster) -> Result<Self, Error> {
+            pub fn new(
+                stmt: &CopyStmt,
+                cluster: &Cluster,
+                search_path: Option<&ParameterValue>,
+            ) -> Result<Self, Error> {
                 let mut parser = Self {
                     is_from: stmt.is_from,
                     ..Default::default()
                 };
 
                 let mut format = CopyFormat::Text;
-                let mut null_string = "\\N".to_owned();
+                let mut null_string = None;
 
                 if let Some(ref rel) = stmt.relation {
                     let mut columns = vec![];
@@ -219,7 +252,14 @@ impl CopyParser {
                         parser.sharded_column = key.position;
                     }
 
+                    let types = cluster
+                        .schema()
+                        .table(table, cluster.user(), search_path)
+                        .map(|relation| ColumnType::columns(relation, &columns))
+                        .unwrap_or_default();
+
                     parser.columns = columns.len();
+                    parser.filter = CopyFilter::new(&stmt.where_clause, &columns, &types);
 
                     for option in &stmt.options {
                         if let Some(NodeEnum::DefElem(ref elem)) = option.node {
@@ -260,7 +300,7 @@ impl CopyParser {
                                     if let Some(ref arg) = elem.arg
                                         && let Some(NodeEnum::String(ref string)) = arg.node
                                     {
-                                        null_string = string.sval.clone();
+                                        null_string = Some(string.sval.clone());
                                     }
                                 }
 
@@ -270,6 +310,12 @@ impl CopyParser {
                     }
                 }
 
+                // `\N` in text format, an unquoted empty string in CSV.
+                let null_string = null_string.unwrap_or_else(|| match format {
+                    CopyFormat::Csv => String::new(),
+                    _ => "\\N".to_owned(),
+                });
+
                 parser.stream = if format == CopyFormat::Binary {
                     CopyStream::Binary(BinaryStream::default())
                 } else {
@@ -281,7 +327,6 @@ impl CopyParser {
                     )))
                 };
                 parser.sharding_schema = cluster.sharding_schema();
-                parser.null_string = null_string;
 
                 Ok(parser)
             }
@@ -325,6 +370,20 @@ impl CopyParser {
                         // pg_dump text format uses `\.` as end-of-copy marker.
                         let is_end_marker = record.len() == 1 && record.get(0) == Some("\\.");
 
+                        // Filtered out by the WHERE clause.
+                        if !is_end_marker
+                            && let Some(filter) = &self.filter
+                            && !filter.matches(|column| {
+                                if record.is_null(column) {
+                                    None
+                                } else {
+                                    record.get(column)
+                                }
+                            })
+                        {
+                            continue;
+                        }
+
                         let shard = if is_end_marker {
                             Shard::All
                         } else if let Some(table) = &self.sharded_table {
@@ -332,7 +391,7 @@ impl CopyParser {
                                 .get(self.sharded_column)
                                 .ok_or(Error::NoShardingColumn)?;
 
-                            if key == self.null_string {
+                            if record.is_null(self.sharded_column) {
                                 Shard::All
                             } else {
                                 let ctx = ContextBuilder::new(table)
@@ -373,6 +432,16 @@ impl CopyParser {
                             ));
                             break;
                         }
+
+                        // Columns are positional, so a tuple with a different
+                        // number of them would be sharded using the wrong value.
+                        if self.columns > 0 && tuple.len() != self.columns {
+                            return Err(Error::BinaryColumnMismatch {
+                                expected: self.columns,
+                                actual: tuple.len(),
+                            });
+                        }
+
                         let shard = if let Some(table) = &self.sharded_table {
                             let key = tuple
                                 .get(self.sharded_column)
@@ -401,11 +470,42 @@ impl CopyParser {
 
         Ok(rows)
     }
+
+    /// The client sent CopyDone. Returns the last row if it wasn't
+    /// terminated with a newline and makes sure no incomplete row is left behind.
+    pub fn finish(&mut self) -> Result<Vec<CopyRow>, Error> {
+        let mut rows = vec![];
+
+        if matches!(self.stream, CopyStream::Text(_)) && self.pending() {
+            rows = self.shard(&[CopyData::new(b"\n")])?;
+        }
+
+        if self.pending() {
+            Err(Error::CopyIncomplete)
+        } else {
+            Ok(rows)
+        }
+    }
+
+    /// Received data that doesn't form a complete row yet.
+    fn pending(&self) -> bool {
+        match &self.stream {
+            CopyStream::Text(stream) => stream.pending(),
+            CopyStream::Binary(stream) => stream.pending(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
+    use indexmap::IndexMap;
+
+    use crate::backend::Schema;
+    use crate::backend::schema::{Relation, columns::StatsColumn as SchemaColumn};
     use crate::config::config;
+    use crate::frontend::router::parser::binary::header::binary_signature;
     #[cfg(feature = "new_parser")]
     use pg_raw_parse::{Node, Owned, make};
 
@@ -414,7 +514,7 @@ mod test {
     #[test]
     fn test_copy_text() {
         let copy = parse("COPY sharded (id, value) FROM STDIN");
-        let mut copy = CopyParser::new(&copy, &Cluster::default()).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::default(), None).unwrap();
 
         assert_eq!(copy.delimiter(), '\t');
         assert!(!copy.headers);
@@ -429,7 +529,7 @@ mod test {
     #[test]
     fn test_copy_csv() {
         let copy = parse("COPY sharded (id, value) FROM STDIN CSV HEADER");
-        let mut copy = CopyParser::new(&copy, &Cluster::default()).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::default(), None).unwrap();
         assert!(copy.is_from);
 
         assert_eq!(copy.delimiter(), ',');
@@ -461,7 +561,7 @@ mod test {
         let copy_data = CopyData::new(b"id,value\n1,test\n6,test6\n");
 
         let copy = parse("COPY sharded (id, value) FROM STDIN CSV HEADER");
-        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config())).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
 
         let rows = copy.shard(&[copy_data]).unwrap();
         assert_eq!(rows.len(), 3);
@@ -476,7 +576,7 @@ mod test {
     #[test]
     fn test_copy_csv_custom_null() {
         let copy = parse("COPY sharded (id, value) FROM STDIN CSV NULL 'NULL'");
-        let mut copy = CopyParser::new(&copy, &Cluster::default()).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::default(), None).unwrap();
 
         assert_eq!(copy.delimiter(), ',');
         assert!(!copy.headers);
@@ -495,7 +595,7 @@ mod test {
         // pg_dump generates text format COPY with `\.` as end-of-copy marker.
         // This marker should be sent to all shards without extracting a sharding key.
         let copy = parse("COPY sharded (id, value) FROM STDIN");
-        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config())).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
 
         let one = CopyData::new("1\tAlice\n".as_bytes());
         let two = CopyData::new("6\tBob\n".as_bytes());
@@ -517,7 +617,7 @@ mod test {
         // When the sharding key is NULL, route to all shards.
         // When a non-sharding column is NULL, route normally based on the key.
         let copy = parse("COPY sharded (id, value) FROM STDIN");
-        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config())).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
 
         let one = CopyData::new("1\tAlice\n".as_bytes());
         let two = CopyData::new("\\N\tBob\n".as_bytes());
@@ -540,7 +640,7 @@ mod test {
     fn test_copy_text_composite_type_sharded() {
         // Test the same composite type but with sharding enabled (using the sharded table from config)
         let copy = parse("COPY sharded (id, value) FROM STDIN");
-        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config())).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
 
         // Row where the value contains a composite type with commas and quotes
         let row = CopyData::new(b"1\t(,Annapolis,Maryland,\"United States\",)\n");
@@ -561,7 +661,7 @@ mod test {
         // Test with explicit FORMAT text (like during resharding)
         let sql = r#"COPY "public"."entity_values" ("id", "value_location") FROM STDIN WITH (FORMAT text)"#;
         let copy_stmt = parse(sql);
-        let mut copy = CopyParser::new(&copy_stmt, &Cluster::default()).unwrap();
+        let mut copy = CopyParser::new(&copy_stmt, &Cluster::default(), None).unwrap();
 
         // Verify it's using tab delimiter (text format default)
         assert_eq!(
@@ -582,10 +682,103 @@ mod test {
         );
     }
 
+    /// Cluster with the schema of the `sharded` table loaded.
+    fn cluster_with_schema() -> Cluster {
+        let mut columns = IndexMap::new();
+        for (position, (name, data_type)) in [("id", "bigint"), ("value", "text")]
+            .into_iter()
+            .enumerate()
+        {
+            columns.insert(
+                name.to_string(),
+                SchemaColumn {
+                    table_schema: "public".into(),
+                    table_name: "sharded".into(),
+                    column_name: name.into(),
+                    data_type: data_type.into(),
+                    ordinal_position: position as i32 + 1,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        let relation = Relation::test_table("public", "sharded", columns);
+        let relations = HashMap::from([(("public".into(), "sharded".into()), relation)]);
+
+        let cluster = Cluster::new_test(&config());
+        cluster.set_schema(Schema::from_parts(vec!["public".into()], relations));
+        cluster
+    }
+
+    #[test]
+    fn test_copy_where() {
+        let copy = parse("COPY sharded (id, value) FROM STDIN WHERE id > 5");
+        let mut copy = CopyParser::new(&copy, &cluster_with_schema(), None).unwrap();
+
+        let data = CopyData::new(b"1\tAlice\n6\tBob\n11\tCharlie\n5\tDave\n\\.\n");
+        let sharded = copy.shard(&[data]).unwrap();
+
+        assert_eq!(sharded.len(), 3);
+        assert_eq!(sharded[0].message().data(), b"6\tBob\n");
+        assert_eq!(sharded[0].shard(), &Shard::Direct(1));
+        assert_eq!(sharded[1].message().data(), b"11\tCharlie\n");
+        assert_eq!(sharded[1].shard(), &Shard::Direct(1));
+        assert_eq!(sharded[2].message().data(), b"\\.\n");
+        assert_eq!(sharded[2].shard(), &Shard::All);
+
+        // Column types unknown, Postgres will filter the rows.
+        let copy = parse("COPY sharded (id, value) FROM STDIN WHERE id > 5");
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
+        let data = CopyData::new(b"1\tAlice\n6\tBob\n");
+        assert_eq!(copy.shard(&[data]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_copy_where_expressions() {
+        // Unquoted empty field is NULL in CSV, quoted one is an empty string.
+        let rows = b"1,Alice\n6,Bob\n11,\n3,Dave\n10,\"\"\n";
+        let all = vec!["1", "6", "11", "3", "10"];
+        let cases = [
+            // Compared as integers, not text.
+            ("id > '5'", vec!["6", "11", "10"]),
+            ("id IN (6, 11) AND value IS NOT NULL", vec!["6"]),
+            ("value IS NULL", vec!["11"]),
+            ("value IS NULL OR id = 1", vec!["1", "11"]),
+            ("NOT (id < 6)", vec!["6", "11", "10"]),
+            ("id NOT IN (1, 6)", vec!["11", "3", "10"]),
+            ("3 >= id::bigint", vec!["1", "3"]),
+            ("id = '3'::int8", vec!["3"]),
+            // Not something we can evaluate, Postgres will do it.
+            ("id::text > '5'", all.clone()),
+            ("value = 'Bob'", all.clone()),
+            ("value = 'Bob' COLLATE \"C\"", all.clone()),
+            ("id > 5.5", all.clone()),
+            ("length(value) > 3", all.clone()),
+        ];
+
+        for (filter, expected) in cases {
+            let copy = parse(&format!(
+                "COPY sharded (id, value) FROM STDIN CSV WHERE {}",
+                filter
+            ));
+            let mut copy = CopyParser::new(&copy, &cluster_with_schema(), None).unwrap();
+            let sharded = copy.shard(&[CopyData::new(rows)]).unwrap();
+
+            let ids = sharded
+                .iter()
+                .map(|row| {
+                    let data = String::from_utf8(row.message().data().to_vec()).unwrap();
+                    data.split(',').next().unwrap().trim_matches('"').to_owned()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(ids, expected, "{}", filter);
+        }
+    }
+
     #[test]
     fn test_copy_binary() {
         let copy = parse("COPY sharded (id, value) FROM STDIN (FORMAT 'binary')");
-        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config())).unwrap();
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
         assert!(copy.is_from);
         assert!(copy.headers);
         let mut data = b"PGCOPY".to_vec();
@@ -613,6 +806,147 @@ mod test {
         assert_eq!(sharded[2].shard(), &Shard::All)
     }
 
+    #[test]
+    fn test_copy_binary_nulls() {
+        let copy = parse("COPY sharded (id, value) FROM STDIN (FORMAT 'binary')");
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
+        let mut data = binary_signature().clone();
+        data.extend(0_i32.to_be_bytes());
+        data.extend(0_i32.to_be_bytes());
+        // NULL value.
+        data.extend(2_i16.to_be_bytes());
+        data.extend(8_i32.to_be_bytes());
+        data.extend(1234_i64.to_be_bytes());
+        data.extend((-1_i32).to_be_bytes());
+        // NULL sharding key.
+        data.extend(2_i16.to_be_bytes());
+        data.extend((-1_i32).to_be_bytes());
+        data.extend(3_i32.to_be_bytes());
+        data.extend(b"yes");
+        // All NULLs.
+        data.extend(2_i16.to_be_bytes());
+        data.extend((-1_i32).to_be_bytes());
+        data.extend((-1_i32).to_be_bytes());
+        data.extend((-1_i16).to_be_bytes());
+
+        let sharded = copy.shard(&[CopyData::new(&data)]).unwrap();
+        assert_eq!(sharded.len(), 5);
+        assert_eq!(sharded[1].message().data(), &data[19..19 + 2 + 4 + 8 + 4]);
+        assert!(matches!(sharded[1].shard(), &Shard::Direct(_)));
+        assert_eq!(sharded[2].message().data().len(), 2 + 4 + 4 + 3);
+        assert_eq!(sharded[2].shard(), &Shard::All);
+        assert_eq!(sharded[3].message().data().len(), 2 + 4 + 4);
+        assert_eq!(sharded[3].shard(), &Shard::All);
+        assert_eq!(sharded[4].message().data(), (-1_i16).to_be_bytes());
+        assert!(copy.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_copy_malformed() {
+        let cluster = Cluster::new_test(&config());
+        let header = {
+            let mut header = binary_signature().clone();
+            header.extend(0_i32.to_be_bytes());
+            header.extend(0_i32.to_be_bytes());
+            header
+        };
+        let binary = || {
+            let copy = parse("COPY sharded (id, value) FROM STDIN (FORMAT 'binary')");
+            CopyParser::new(&copy, &cluster, None).unwrap()
+        };
+
+        // Truncated binary header.
+        let mut copy = binary();
+        assert!(
+            copy.shard(&[CopyData::new(&header[..5])])
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(copy.finish(), Err(Error::CopyIncomplete)));
+
+        // Wrong binary signature.
+        let mut copy = binary();
+        let mut data = header.clone();
+        data[0] = b'X';
+        assert!(matches!(
+            copy.shard(&[CopyData::new(&data)]),
+            Err(Error::BinaryMissingHeader)
+        ));
+
+        // Negative number of columns.
+        let mut copy = binary();
+        let mut data = header.clone();
+        data.extend((-5_i16).to_be_bytes());
+        assert!(matches!(
+            copy.shard(&[CopyData::new(&data)]),
+            Err(Error::BinaryColumnCount(-5))
+        ));
+
+        // Negative column length.
+        let mut copy = binary();
+        let mut data = header.clone();
+        data.extend(1_i16.to_be_bytes());
+        data.extend((-3_i32).to_be_bytes());
+        assert!(matches!(
+            copy.shard(&[CopyData::new(&data)]),
+            Err(Error::BinaryColumnLength(-3))
+        ));
+
+        // Tuple doesn't match the column list.
+        let mut copy = binary();
+        let mut data = header.clone();
+        data.extend(1_i16.to_be_bytes());
+        data.extend(8_i32.to_be_bytes());
+        data.extend(1234_i64.to_be_bytes());
+        assert!(matches!(
+            copy.shard(&[CopyData::new(&data)]),
+            Err(Error::BinaryColumnMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+
+        // Row is missing the sharding column.
+        let copy = parse("COPY sharded (value, id) FROM STDIN");
+        let mut copy = CopyParser::new(&copy, &cluster, None).unwrap();
+        assert!(matches!(
+            copy.shard(&[CopyData::new(b"hello\n")]),
+            Err(Error::NoShardingColumn)
+        ));
+
+        // Invalid UTF-8.
+        let copy = parse("COPY sharded (id, value) FROM STDIN");
+        let mut copy = CopyParser::new(&copy, &cluster, None).unwrap();
+        assert!(matches!(
+            copy.shard(&[CopyData::new(b"5\t\xff\xfe\n")]),
+            Err(Error::CopyUtf8)
+        ));
+
+        // Quote is never closed.
+        let copy = parse("COPY sharded (id, value) FROM STDIN CSV");
+        let mut copy = CopyParser::new(&copy, &cluster, None).unwrap();
+        let rows = copy
+            .shard(&[CopyData::new(b"5,\"hello\n6,world\n")])
+            .unwrap();
+        assert!(rows.is_empty());
+        assert!(matches!(copy.finish(), Err(Error::CopyIncomplete)));
+    }
+
+    #[test]
+    fn test_copy_finish_unterminated_row() {
+        let copy = parse("COPY sharded (id, value) FROM STDIN");
+        let mut copy = CopyParser::new(&copy, &Cluster::new_test(&config()), None).unwrap();
+
+        let rows = copy.shard(&[CopyData::new(b"5\thello\n6\tworld")]).unwrap();
+        assert_eq!(rows.len(), 1);
+
+        let rows = copy.finish().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].message().data(), b"6\tworld\n");
+        assert!(matches!(rows[0].shard(), &Shard::Direct(_)));
+        assert!(copy.finish().unwrap().is_empty());
+    }
+
     #[cfg(feature = "new_parser")]
     fn parse(sql: &str) -> Owned<nodes::CopyStmt> {
         let stmt = pg_raw_parse::parse(sql).unwrap();
@@ -0,0 +1,638 @@
+//! Evaluate the `WHERE` clause of `COPY ... FROM STDIN`.
+//!
+//! Rows that don't match aren't sent to any shard, so they are never written.
+//! Values are compared using the column's declared type, from the loaded schema.
+//! Anything we can't evaluate exactly like Postgres lets the row through
+//! and Postgres applies the filter on the shard instead: unknown column types,
+//! casts to another type, collations, functions and binary COPY.
+
+use std::cmp::Ordering;
+
+#[cfg(not(feature = "new_parser"))]
+use pg_query::{
+    Node, NodeEnum,
+    protobuf::{AExprKind, BoolExprType, NullTestType},
+};
+#[cfg(feature = "new_parser")]
+use pg_raw_parse::{Node, nodes};
+
+use super::{Column, Value};
+use crate::backend::schema::StatsRelation;
+
+/// Constant the column is compared with.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Constant {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+}
+
+impl Constant {
+    pub(super) fn new(value: Value<'_>) -> Option<Self> {
+        match value {
+            Value::Integer(value) => Some(Self::Integer(value)),
+            Value::Float(value) => Some(Self::Float(value)),
+            Value::Boolean(value) => Some(Self::Boolean(value)),
+            Value::String(value) => Some(Self::String(value.to_owned())),
+            _ => None,
+        }
+    }
+}
+
+/// Column types we compare the same way Postgres does.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ColumnType {
+    Integer,
+    Real,
+    Double,
+    Boolean,
+}
+
+impl ColumnType {
+    /// Type from `information_schema.columns` or a cast.
+    pub(super) fn new(name: &str) -> Option<Self> {
+        Some(match name {
+            "smallint" | "integer" | "bigint" | "int2" | "int4" | "int8" => Self::Integer,
+            "real" | "float4" => Self::Real,
+            "double precision" | "float8" => Self::Double,
+            "boolean" | "bool" => Self::Boolean,
+            _ => return None,
+        })
+    }
+
+    /// Types of the columns in the COPY statement.
+    pub(super) fn columns(relation: &StatsRelation, columns: &[Column<'_>]) -> Vec<Option<Self>> {
+        columns
+            .iter()
+            .map(|column| {
+                relation
+                    .columns
+                    .get(column.name)
+                    .and_then(|column| Self::new(&column.data_type))
+            })
+            .collect()
+    }
+
+    /// Parse the column value, as sent by the client.
+    fn parse(self, value: &str) -> Option<Typed> {
+        let value = value.trim();
+        match self {
+            Self::Integer => value.parse().ok().map(Typed::Integer),
+            // Stored with single precision.
+            Self::Real => value
+                .parse::<f32>()
+                .ok()
+                .map(|value| Typed::Float(value as f64)),
+            Self::Double => value.parse().ok().map(Typed::Float),
+            Self::Boolean => parse_bool(value).map(Typed::Boolean),
+        }
+    }
+
+    /// Convert the constant to this type, like Postgres does when comparing
+    /// it with the column, or when it's cast to this type.
+    fn constant(self, constant: &Constant, cast: bool) -> Option<Typed> {
+        match (self, constant) {
+            (Self::Integer, Constant::Integer(value)) => Some(Typed::Integer(*value)),
+            (Self::Real | Self::Double, Constant::Integer(value)) => {
+                Some(Typed::Float(*value as f64))
+            }
+            // Without a cast, `real` columns are compared as `double precision`.
+            (Self::Real, Constant::Float(value)) if cast => {
+                Some(Typed::Float(*value as f32 as f64))
+            }
+            (Self::Real | Self::Double, Constant::Float(value)) => Some(Typed::Float(*value)),
+            (Self::Boolean, Constant::Boolean(value)) => Some(Typed::Boolean(*value)),
+            // Quoted constants are parsed as the column type.
+            (_, Constant::String(value)) => self.parse(value),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a column, or a constant converted to its type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Typed {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Typed {
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => Some(left.cmp(right)),
+            // NaN is equal to itself and greater than everything else.
+            (Self::Float(left), Self::Float(right)) => {
+                Some(match (left.is_nan(), right.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => left.partial_cmp(right)?,
+                })
+            }
+            (Self::Boolean(left), Self::Boolean(right)) => Some(left.cmp(right)),
+            _ => None,
+        }
+    }
+}
+
+/// Text representation of a boolean.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" | "on" | "1" => Some(true),
+        "f" | "false" | "n" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Result of evaluating the filter on a row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Truth {
+    True,
+    False,
+    Null,
+    /// We can't tell, e.g. the value doesn't parse. Postgres will decide.
+    Unknown,
+}
+
+impl Truth {
+    fn from_bool(value: bool) -> Self {
+        if value { Self::True } else { Self::False }
+    }
+
+    fn negate(self) -> Self {
+        match self {
+            Self::True => Self::False,
+            Self::False => Self::True,
+            truth => truth,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Op {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl Op {
+    pub(super) fn new(op: &str) -> Option<Self> {
+        Some(match op {
+            "=" => Self::Eq,
+            "<>" | "!=" => Self::NotEq,
+            "<" => Self::Lt,
+            "<=" => Self::LtEq,
+            ">" => Self::Gt,
+            ">=" => Self::GtEq,
+            _ => return None,
+        })
+    }
+
+    /// Same operator with operands swapped, e.g. `5 < id` is `id > 5`.
+    fn flip(self) -> Self {
+        match self {
+            Self::Lt => Self::Gt,
+            Self::LtEq => Self::GtEq,
+            Self::Gt => Self::Lt,
+            Self::GtEq => Self::LtEq,
+            op => op,
+        }
+    }
+
+    fn apply(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::NotEq => ordering.is_ne(),
+            Self::Lt => ordering.is_lt(),
+            Self::LtEq => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::GtEq => ordering.is_ge(),
+        }
+    }
+}
+
+/// Side of a comparison, with the type it's cast to, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Operand {
+    Column(usize, Option<ColumnType>),
+    Constant(Constant, Option<ColumnType>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Expr {
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    Compare {
+        column: usize,
+        column_type: ColumnType,
+        op: Op,
+        value: Typed,
+    },
+    In {
+        column: usize,
+        column_type: ColumnType,
+        values: Vec<Typed>,
+        negated: bool,
+    },
+    IsNull {
+        column: usize,
+        negated: bool,
+    },
+}
+
+impl Expr {
+    /// Evaluate the expression using SQL three-valued logic.
+    pub(super) fn eval<'a>(&self, row: &impl Fn(usize) -> Option<&'a str>) -> Truth {
+        match self {
+            // False wins over anything we can't evaluate.
+            Self::And(exprs) => {
+                let mut result = Truth::True;
+                for expr in exprs {
+                    match expr.eval(row) {
+                        Truth::False => return Truth::False,
+                        Truth::Unknown => result = Truth::Unknown,
+                        Truth::Null if result == Truth::True => result = Truth::Null,
+                        _ => (),
+                    }
+                }
+                result
+            }
+
+            Self::Or(exprs) => {
+                let mut result = Truth::False;
+                for expr in exprs {
+                    match expr.eval(row) {
+                        Truth::True => return Truth::True,
+                        Truth::Unknown => result = Truth::Unknown,
+                        Truth::Null if result == Truth::False => result = Truth::Null,
+                        _ => (),
+                    }
+                }
+                result
+            }
+
+            Self::Not(expr) => expr.eval(row).negate(),
+
+            Self::Compare {
+                column,
+                column_type,
+                op,
+                value,
+            } => {
+                let Some(column) = row(*column) else {
+                    return Truth::Null;
+                };
+                match column_type
+                    .parse(column)
+                    .and_then(|column| column.compare(value))
+                {
+                    Some(ordering) => Truth::from_bool(op.apply(ordering)),
+                    None => Truth::Unknown,
+                }
+            }
+
+            Self::In {
+                column,
+                column_type,
+                values,
+                negated,
+            } => {
+                let Some(column) = row(*column) else {
+                    return Truth::Null;
+                };
+                let Some(column) = column_type.parse(column) else {
+                    return Truth::Unknown;
+                };
+                let found = values
+                    .iter()
+                    .any(|value| column.compare(value) == Some(Ordering::Equal));
+                Truth::from_bool(found != *negated)
+            }
+
+            Self::IsNull { column, negated } => {
+                Truth::from_bool(row(*column).is_none() != *negated)
+            }
+        }
+    }
+
+    /// Comparison between a column and a constant, on either side.
+    pub(super) fn compare(
+        left: Operand,
+        op: Op,
+        right: Operand,
+        types: &[Option<ColumnType>],
+    ) -> Option<Self> {
+        let (column, cast, constant, op) = match (left, right) {
+            (Operand::Column(column, cast), constant @ Operand::Constant(..)) => {
+                (column, cast, constant, op)
+            }
+            (constant @ Operand::Constant(..), Operand::Column(column, cast)) => {
+                (column, cast, constant, op.flip())
+            }
+            _ => return None,
+        };
+        let column_type = Self::column_type(column, cast, types)?;
+
+        Some(Self::Compare {
+            column,
+            column_type,
+            op,
+            value: Self::constant(constant, column_type)?,
+        })
+    }
+
+    /// Column, or list of constants, in `IN (...)`.
+    pub(super) fn in_list(
+        column: Operand,
+        values: Vec<Operand>,
+        negated: bool,
+        types: &[Option<ColumnType>],
+    ) -> Option<Self> {
+        let Operand::Column(column, cast) = column else {
+            return None;
+        };
+        let column_type = Self::column_type(column, cast, types)?;
+        let values = values
+            .into_iter()
+            .map(|value| Self::constant(value, column_type))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self::In {
+            column,
+            column_type,
+            values,
+            negated,
+        })
+    }
+
+    /// Declared type of the column. Casts to another type
+    /// change how values are compared, so we don't handle them.
+    fn column_type(
+        column: usize,
+        cast: Option<ColumnType>,
+        types: &[Option<ColumnType>],
+    ) -> Option<ColumnType> {
+        let column_type = (*types.get(column)?)?;
+        cast.is_none_or(|cast| cast == column_type)
+            .then_some(column_type)
+    }
+
+    fn constant(operand: Operand, column_type: ColumnType) -> Option<Typed> {
+        match operand {
+            Operand::Constant(constant, cast) if cast.is_none_or(|cast| cast == column_type) => {
+                column_type.constant(&constant, cast.is_some())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `WHERE` clause of a `COPY` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct CopyFilter {
+    expr: Expr,
+}
+
+impl CopyFilter {
+    /// Row should be sent to the shards. The closure returns
+    /// the value in each column, `None` if it's NULL.
+    pub(super) fn matches<'a>(&self, row: impl Fn(usize) -> Option<&'a str>) -> bool {
+        matches!(self.expr.eval(&row), Truth::True | Truth::Unknown)
+    }
+
+    fn column(columns: &[Column<'_>], name: &str) -> Option<usize> {
+        columns.iter().position(|column| column.name == name)
+    }
+
+    /// Parse the `WHERE` clause. Returns `None` if it contains
+    /// anything we can't evaluate.
+    #[cfg(feature = "new_parser")]
+    pub(super) fn new(
+        where_clause: Node<'_>,
+        columns: &[Column<'_>],
+        types: &[Option<ColumnType>],
+    ) -> Option<Self> {
+        if let Node::None = where_clause {
+            return None;
+        }
+
+        Some(Self {
+            expr: Self::expr(where_clause, columns, types)?,
+        })
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn expr(node: Node<'_>, columns: &[Column<'_>], types: &[Option<ColumnType>]) -> Option<Expr> {
+        match node {
+            Node::BoolExpr(expr) => {
+                let args = expr
+                    .args()
+                    .iter()
+                    .map(|arg| Self::expr(arg, columns, types))
+                    .collect::<Option<Vec<_>>>()?;
+
+                match expr.boolop {
+                    nodes::BoolExprType::AND_EXPR => Some(Expr::And(args)),
+                    nodes::BoolExprType::OR_EXPR => Some(Expr::Or(args)),
+                    nodes::BoolExprType::NOT_EXPR => {
+                        Some(Expr::Not(Box::new(args.into_iter().next()?)))
+                    }
+                    _ => None,
+                }
+            }
+
+            Node::NullTest(test) => {
+                let Operand::Column(column, None) = Self::operand(test.arg(), columns)? else {
+                    return None;
+                };
+                Some(Expr::IsNull {
+                    column,
+                    negated: test.nulltesttype == nodes::NullTestType::IS_NOT_NULL,
+                })
+            }
+
+            Node::A_Expr(expr) => {
+                use nodes::A_Expr_Kind;
+                let op = expr.name().first().and_then(Node::as_str)?;
+
+                match expr.kind {
+                    A_Expr_Kind::AEXPR_OP => Expr::compare(
+                        Self::operand(expr.lexpr(), columns)?,
+                        Op::new(op)?,
+                        Self::operand(expr.rexpr(), columns)?,
+                        types,
+                    ),
+
+                    A_Expr_Kind::AEXPR_IN => {
+                        let Node::NodeList(list) = expr.rexpr() else {
+                            return None;
+                        };
+                        let values = list
+                            .iter()
+                            .map(|node| Self::operand(node, columns))
+                            .collect::<Option<Vec<_>>>()?;
+
+                        Expr::in_list(
+                            Self::operand(expr.lexpr(), columns)?,
+                            values,
+                            Op::new(op)? == Op::NotEq,
+                            types,
+                        )
+                    }
+
+                    _ => None,
+                }
+            }
+
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn operand(node: Node<'_>, columns: &[Column<'_>]) -> Option<Operand> {
+        match node {
+            Node::ColumnRef(column) => {
+                let name = column.fields().into_iter().last().and_then(Node::as_str)?;
+                Some(Operand::Column(Self::column(columns, name)?, None))
+            }
+            Node::A_Const(value) => {
+                Some(Operand::Constant(Constant::new(Value::from(value))?, None))
+            }
+            Node::TypeCast(cast) => {
+                let cast_type = cast
+                    .type_name()?
+                    .names()
+                    .into_iter()
+                    .last()
+                    .and_then(Node::as_str)
+                    .and_then(ColumnType::new)?;
+                // Only one cast, so we know what's being compared.
+                match Self::operand(cast.arg(), columns)? {
+                    Operand::Column(column, None) => Some(Operand::Column(column, Some(cast_type))),
+                    Operand::Constant(constant, None) => {
+                        Some(Operand::Constant(constant, Some(cast_type)))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            /// Parse the `WHERE` clause. Returns `None` if it contains
+            /// anything we can't evaluate.
+            pub(super) fn new(
+                where_clause: &Option<Box<Node>>,
+                columns: &[Column<'_>],
+                types: &[Option<ColumnType>],
+            ) -> Option<Self> {
+                let where_clause = where_clause.as_ref()?;
+
+                Some(Self {
+                    expr: Self::expr(where_clause, columns, types)?,
+                })
+            }
+
+            fn string(node: Option<&Node>) -> Option<&str> {
+                match node?.node {
+                    Some(NodeEnum::String(ref string)) => Some(string.sval.as_str()),
+                    _ => None,
+                }
+            }
+
+            fn expr(node: &Node, columns: &[Column<'_>], types: &[Option<ColumnType>]) -> Option<Expr> {
+                match node.node.as_ref()? {
+                    NodeEnum::BoolExpr(expr) => {
+                        let args = expr
+                            .args
+                            .iter()
+                            .map(|arg| Self::expr(arg, columns, types))
+                            .collect::<Option<Vec<_>>>()?;
+
+                        match expr.boolop() {
+                            BoolExprType::AndExpr => Some(Expr::And(args)),
+                            BoolExprType::OrExpr => Some(Expr::Or(args)),
+                            BoolExprType::NotExpr => Some(Expr::Not(Box::new(args.into_iter().next()?))),
+                            _ => None,
+                        }
+                    }
+
+                    NodeEnum::NullTest(test) => {
+                        let Operand::Column(column, None) = Self::operand(test.arg.as_deref()?, columns)? else {
+                            return None;
+                        };
+                        Some(Expr::IsNull {
+                            column,
+                            negated: NullTestType::try_from(test.nulltesttype) == Ok(NullTestType::IsNotNull),
+                        })
+                    }
+
+                    NodeEnum::AExpr(expr) => {
+                        let op = Self::string(expr.name.first())?;
+
+                        match expr.kind() {
+                            AExprKind::AexprOp => Expr::compare(
+                                Self::operand(expr.lexpr.as_deref()?, columns)?,
+                                Op::new(op)?,
+                                Self::operand(expr.rexpr.as_deref()?, columns)?,
+                                types,
+                            ),
+
+                            AExprKind::AexprIn => {
+                                let Some(NodeEnum::List(ref list)) = expr.rexpr.as_deref()?.node else {
+                                    return None;
+                                };
+                                let values = list
+                                    .items
+                                    .iter()
+                                    .map(|node| Self::operand(node, columns))
+                                    .collect::<Option<Vec<_>>>()?;
+
+                                Expr::in_list(
+                                    Self::operand(expr.lexpr.as_deref()?, columns)?,
+                                    values,
+                                    Op::new(op)? == Op::NotEq,
+                                    types,
+                                )
+                            }
+
+                            _ => None,
+                        }
+                    }
+
+                    _ => None,
+                }
+            }
+
+            fn operand(node: &Node, columns: &[Column<'_>]) -> Option<Operand> {
+                match node.node.as_ref()? {
+                    NodeEnum::ColumnRef(column) => {
+                        let name = Self::string(column.fields.last())?;
+                        Some(Operand::Column(Self::column(columns, name)?, None))
+                    }
+                    NodeEnum::AConst(value) => Some(Operand::Constant(Constant::new(Value::from(value))?, None)),
+                    NodeEnum::TypeCast(cast) => {
+                        let cast_type = Self::string(cast.type_name.as_ref()?.names.last())
+                            .and_then(ColumnType::new)?;
+                        // Only one cast, so we know what's being compared.
+                        match Self::operand(cast.arg.as_deref()?, columns)? {
+                            Operand::Column(column, None) => Some(Operand::Column(column, Some(cast_type))),
+                            Operand::Constant(constant, None) => Some(Operand::Constant(constant, Some(cast_type))),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,223 @@
+//! Estimate how expensive a SELECT is to execute.
+//!
+//! The estimate is a heuristic based on the shape of the query
+//! and is used to send expensive reads to analytics replicas.
+
+#[cfg(not(feature = "new_parser"))]
+use pg_query::{Node, NodeEnum, protobuf::SelectStmt};
+#[cfg(feature = "new_parser")]
+use pg_raw_parse::{Node, nodes, walk};
+
+use super::Aggregate;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Cost<'a> {
+    #[cfg(not(feature = "new_parser"))]
+    stmt: &'a SelectStmt,
+    #[cfg(feature = "new_parser")]
+    stmt: &'a nodes::SelectStmt,
+    aggregate: &'a Aggregate,
+}
+
+impl<'a> Cost<'a> {
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn new(stmt: &'a nodes::SelectStmt, aggregate: &'a Aggregate) -> Self {
+        Self { stmt, aggregate }
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            pub(crate) fn new(stmt: &'a SelectStmt, aggregate: &'a Aggregate) -> Self {
+                Self { stmt, aggregate }
+            }
+        }
+        _ => {}
+    }
+
+    /// Estimated cost of the query. Each join, subquery and CTE adds one,
+    /// and so do aggregates or GROUP BY, DISTINCT and a missing WHERE clause.
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn estimate(&self) -> usize {
+        let mut cost = self.stmt.from_clause().iter().count().saturating_sub(1);
+
+        walk::walk(self.stmt.into(), |node| match node {
+            Node::JoinExpr(_)
+            | Node::RangeSubselect(_)
+            | Node::SubLink(_)
+            | Node::CommonTableExpr(_) => cost += 1,
+            _ => (),
+        });
+
+        cost += usize::from(!self.aggregate.is_empty() || !self.stmt.group_clause().is_empty());
+        cost += usize::from(!self.stmt.distinct_clause().is_empty());
+        cost += usize::from(matches!(self.stmt.where_clause(), Node::None));
+
+        cost
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            pub(crate) fn estimate(&self) -> usize {
+                let mut cost =
+                    self.stmt.from_clause.len().saturating_sub(1) + Self::nodes(self.stmt);
+
+                cost += usize::from(
+                    !self.aggregate.is_empty() || !self.stmt.group_clause.is_empty(),
+                );
+                cost += usize::from(!self.stmt.distinct_clause.is_empty());
+                cost += usize::from(self.stmt.where_clause.is_none());
+
+                cost
+            }
+
+            /// Count joins, subqueries and CTEs in the statement.
+            fn nodes(stmt: &SelectStmt) -> usize {
+                let ctes = stmt
+                    .with_clause
+                    .iter()
+                    .flat_map(|with_clause| with_clause.ctes.iter());
+
+                ctes.chain(stmt.from_clause.iter())
+                    .chain(stmt.target_list.iter())
+                    .chain(stmt.where_clause.as_deref())
+                    .chain(stmt.group_clause.iter())
+                    .chain(stmt.having_clause.as_deref())
+                    .chain(stmt.sort_clause.iter())
+                    .map(Self::node)
+                    .sum::<usize>()
+                    + [&stmt.larg, &stmt.rarg]
+                        .into_iter()
+                        .flatten()
+                        .map(|stmt| Self::nodes(stmt))
+                        .sum::<usize>()
+            }
+
+            fn node(node: &Node) -> usize {
+                let boxed = |node: &Option<Box<Node>>| {
+                    node.as_deref().map(Self::node).unwrap_or_default()
+                };
+                let list = |nodes: &Vec<Node>| nodes.iter().map(Self::node).sum::<usize>();
+
+                match node.node.as_ref() {
+                    Some(NodeEnum::SelectStmt(stmt)) => Self::nodes(stmt),
+                    Some(NodeEnum::JoinExpr(join)) => {
+                        1 + boxed(&join.larg) + boxed(&join.rarg) + boxed(&join.quals)
+                    }
+                    Some(NodeEnum::RangeSubselect(subselect)) => 1 + boxed(&subselect.subquery),
+                    Some(NodeEnum::SubLink(sublink)) => {
+                        1 + boxed(&sublink.testexpr) + boxed(&sublink.subselect)
+                    }
+                    Some(NodeEnum::CommonTableExpr(cte)) => 1 + boxed(&cte.ctequery),
+                    Some(NodeEnum::ResTarget(target)) => boxed(&target.val),
+                    Some(NodeEnum::SortBy(sort)) => boxed(&sort.node),
+                    Some(NodeEnum::BoolExpr(expr)) => list(&expr.args),
+                    Some(NodeEnum::AExpr(expr)) => boxed(&expr.lexpr) + boxed(&expr.rexpr),
+                    Some(NodeEnum::FuncCall(func)) => list(&func.args) + boxed(&func.agg_filter),
+                    Some(NodeEnum::TypeCast(cast)) => boxed(&cast.arg),
+                    Some(NodeEnum::NullTest(test)) => boxed(&test.arg),
+                    Some(NodeEnum::BooleanTest(test)) => boxed(&test.arg),
+                    Some(NodeEnum::CaseExpr(case)) => {
+                        boxed(&case.arg) + list(&case.args) + boxed(&case.defresult)
+                    }
+                    Some(NodeEnum::CaseWhen(when)) => boxed(&when.expr) + boxed(&when.result),
+                    Some(NodeEnum::CoalesceExpr(expr)) => list(&expr.args),
+                    Some(NodeEnum::MinMaxExpr(expr)) => list(&expr.args),
+                    Some(NodeEnum::RowExpr(expr)) => list(&expr.args),
+                    Some(NodeEnum::AArrayExpr(expr)) => list(&expr.elements),
+                    Some(NodeEnum::List(items)) => list(&items.items),
+                    _ => 0,
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "new_parser")]
+    use pg_raw_parse::{Owned, make};
+
+    #[cfg(feature = "new_parser")]
+    fn select(stmt: &str) -> Owned<nodes::SelectStmt> {
+        match pg_raw_parse::parse(stmt).unwrap().stmts().next().unwrap() {
+            Node::SelectStmt(stmt) => make::owned(|mem| mem.make_unique(stmt)),
+            _ => panic!("not a select"),
+        }
+    }
+
+    #[cfg(not(feature = "new_parser"))]
+    fn select(stmt: &str) -> SelectStmt {
+        let stmt = pg_query::parse(stmt)
+            .unwrap()
+            .protobuf
+            .stmts
+            .remove(0)
+            .stmt
+            .unwrap();
+        match stmt.node.unwrap() {
+            NodeEnum::SelectStmt(stmt) => *stmt,
+            _ => panic!("not a select"),
+        }
+    }
+
+    fn estimate(query: &str) -> usize {
+        let stmt = select(query);
+        let aggregate = Aggregate::parse(&stmt, &Default::default());
+        Cost::new(&stmt, &aggregate).estimate()
+    }
+
+    #[test]
+    fn test_point_lookup_is_cheap() {
+        assert_eq!(estimate("SELECT * FROM users WHERE id = $1"), 0);
+        assert_eq!(estimate("SELECT email FROM users WHERE id = 1 LIMIT 1"), 0);
+    }
+
+    #[test]
+    fn test_join_aggregate_is_expensive() {
+        assert_eq!(
+            estimate(
+                "SELECT u.country, count(*) FROM users u
+                JOIN orders o ON o.user_id = u.id
+                JOIN items i ON i.order_id = o.id
+                GROUP BY u.country"
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn test_subqueries_and_ctes() {
+        assert_eq!(
+            estimate(
+                "WITH recent AS (SELECT * FROM orders WHERE created_at > now() - interval '1 day')
+                SELECT DISTINCT user_id FROM recent
+                WHERE user_id IN (SELECT id FROM users WHERE active)"
+            ),
+            3
+        );
+        assert_eq!(
+            estimate("SELECT * FROM users, orders WHERE users.id = orders.user_id"),
+            1
+        );
+    }
+
+    #[test]
+    fn test_nested_subqueries() {
+        assert_eq!(
+            estimate(
+                "SELECT lower((SELECT name FROM users WHERE id = 1)) FROM orders WHERE id = 2"
+            ),
+            1
+        );
+        assert_eq!(
+            estimate(
+                "SELECT * FROM orders WHERE id = 1
+                AND coalesce((SELECT max(id) FROM items WHERE items.order_id IN (SELECT id FROM orders)), 0) > 0
+                ORDER BY (SELECT count(*) FROM users)"
+            ),
+            3
+        );
+    }
+}
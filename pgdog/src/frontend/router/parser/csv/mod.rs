@@ -111,7 +111,12 @@ impl CsvStream {
                 }
 
                 ReadRecordResult::Record => {
+                    if std::str::from_utf8(&self.record[..written]).is_err() {
+                        return Err(super::Error::CopyUtf8);
+                    }
+
                     let record = Record::new(
+                        &self.buffer[self.read..self.read + read],
                         &self.record[..written],
                         &self.ends[..ends],
                         self.delimiter,
@@ -136,6 +141,11 @@ impl CsvStream {
         }
     }
 
+    /// Received data that doesn't form a complete record yet.
+    pub(crate) fn pending(&self) -> bool {
+        self.read < self.buffer.len()
+    }
+
     /// Get an iterator over all records available in the buffer.
     pub(crate) fn records(&mut self) -> impl Iterator<Item = Result<Record, super::Error>> {
         std::iter::from_fn(|| self.record().transpose())
@@ -221,6 +231,25 @@ mod test {
         assert_eq!(output, "\"four\",\\N,\\N\n");
     }
 
+    #[test]
+    fn test_csv_empty_null_string() {
+        // Postgres default in CSV format: only an unquoted empty field is NULL.
+        let csv = "1,,\"\",\"a,\"\"b\"\"\",\n";
+        let mut reader = CsvStream::new(',', false, CopyFormat::Csv, "");
+        reader.write(csv.as_bytes());
+
+        let record = reader.record().unwrap().unwrap();
+        assert_eq!(record.get(2), Some(""));
+        assert_eq!(record.get(3), Some("a,\"b\""));
+        assert_eq!(
+            (0..5)
+                .map(|field| record.is_null(field))
+                .collect::<Vec<_>>(),
+            vec![false, true, false, false, true]
+        );
+        assert_eq!(record.to_string(), "\"1\",,\"\",\"a,\"\"b\"\"\",\n");
+    }
+
     #[test]
     fn test_json_field_quote_escaping() {
         // Test that JSON/JSONB fields with quotes are handled properly in CSV format
@@ -15,6 +15,8 @@ pub(crate) struct Record {
     format: CopyFormat,
     /// Null string.
     null_string: String,
+    /// Fields that were quoted, in CSV format.
+    quoted: Vec<bool>,
 }
 
 impl std::fmt::Display for Record {
@@ -25,14 +27,14 @@ impl std::fmt::Display for Record {
             (0..self.len())
                 .map(|field| match self.format {
                     CopyFormat::Csv => {
-                        let text = self.get(field).unwrap();
-                        if text == self.null_string {
+                        let text = self.get(field).unwrap_or_default();
+                        if self.is_null(field) {
                             text.to_owned()
                         } else {
-                            format!("\"{}\"", self.get(field).unwrap().replace("\"", "\"\""))
+                            format!("\"{}\"", text.replace("\"", "\"\""))
                         }
                     }
-                    _ => self.get(field).unwrap().to_string(),
+                    _ => self.get(field).unwrap_or_default().to_string(),
                 })
                 .collect::<Vec<String>>()
                 .join(&format!("{}", self.delimiter))
@@ -42,6 +44,7 @@ impl std::fmt::Display for Record {
 
 impl Record {
     pub(super) fn new(
+        raw: &[u8],
         data: &[u8],
         ends: &[usize],
         delimiter: char,
@@ -60,6 +63,11 @@ impl Record {
             delimiter,
             format,
             null_string: null_string.to_owned(),
+            quoted: if format == CopyFormat::Csv {
+                quoted_fields(raw, delimiter as u8)
+            } else {
+                vec![]
+            },
         }
     }
 
@@ -74,4 +82,44 @@ impl Record {
             .cloned()
             .and_then(|range| from_utf8(&self.data[range]).ok())
     }
+
+    /// Field is NULL. In CSV format, quoted fields never are.
+    pub(crate) fn is_null(&self, index: usize) -> bool {
+        !self.quoted.get(index).copied().unwrap_or_default()
+            && self.get(index) == Some(self.null_string.as_str())
+    }
+}
+
+/// Which fields of a raw CSV record are quoted.
+fn quoted_fields(raw: &[u8], delimiter: u8) -> Vec<bool> {
+    let mut quoted: Vec<bool> = vec![];
+    let mut field_start = true;
+    let mut in_quotes = false;
+
+    for &byte in raw {
+        if field_start {
+            field_start = false;
+            quoted.push(byte == b'"');
+            if byte == b'"' {
+                in_quotes = true;
+                continue;
+            }
+        }
+
+        // Quotes are only special in quoted fields. An escaped
+        // quote closes and reopens them.
+        match byte {
+            b'"' if quoted.last() == Some(&true) => in_quotes = !in_quotes,
+            _ if in_quotes => (),
+            b'\n' | b'\r' => return quoted,
+            byte if byte == delimiter => field_start = true,
+            _ => (),
+        }
+    }
+
+    if field_start {
+        quoted.push(false);
+    }
+
+    quoted
 }
@@ -48,6 +48,24 @@ pub enum Error {
     #[error("unexpected header extension")]
     BinaryHeaderExtension,
 
+    #[error("invalid number of columns in binary copy tuple: {0}")]
+    BinaryColumnCount(i16),
+
+    #[error("invalid column length in binary copy tuple: {0}")]
+    BinaryColumnLength(i32),
+
+    #[error("binary copy tuple has {actual} columns, expected {expected}")]
+    BinaryColumnMismatch { expected: usize, actual: usize },
+
+    #[error("copy data is not valid UTF-8")]
+    CopyUtf8,
+
+    #[error("copy ended with an incomplete row")]
+    CopyIncomplete,
+
+    #[error("copy column list can only contain column names")]
+    CopyColumnList,
+
     #[error("set shard syntax error")]
     SetShard,
 
@@ -81,6 +99,9 @@ pub enum Error {
     #[error("cross-shard truncate not supported when schema-sharding is used")]
     CrossShardTruncateSchemaSharding,
 
+    #[error("search_path schemas \"{0}\" are mapped to different shards")]
+    SearchPathConflict(String),
+
     #[error("prepared statement \"{0}\" doesn't exist")]
     PreparedStatementDoesntExist(String),
 
@@ -104,4 +125,36 @@ pub enum Error {
 
     #[error("multi-statement queries cannot mix SET with other commands")]
     MultiStatementMixedSet,
+
+    #[error("multi-statement queries are not allowed")]
+    MultiStatementRejected,
+
+    #[error("statements in a multi-statement query must target the same shard")]
+    MultiStatementCrossShard,
+
+    #[error("statement type is not supported")]
+    UnknownStatementRejected,
+
+    #[error("query could not be parsed")]
+    ParseFailureRejected,
+
+    #[error("parameter \"{0}\" is not allowed")]
+    ParameterNotAllowed(String),
+
+    #[error("{0} on a sharded table must specify the sharding key")]
+    ShardingKeyRequired(&'static str),
+}
+
+impl Error {
+    /// The parser couldn't parse the query.
+    #[cfg(not(feature = "new_parser"))]
+    pub fn is_parse(&self) -> bool {
+        matches!(self, Self::PgQuery(_))
+    }
+
+    /// The parser couldn't parse the query.
+    #[cfg(feature = "new_parser")]
+    pub fn is_parse(&self) -> bool {
+        matches!(self, Self::Parse(_))
+    }
 }
@@ -5,7 +5,12 @@ use pg_raw_parse::{Node, nodes};
 
 const WRITE_ONLY: &[&str] = &["nextval", "setval"];
 
-const CROSS_SHARD: &[(Option<&str>, &str)] = &[(Some("pgdog"), "install_sharded_sequence")];
+const CROSS_SHARD: &[(Option<&str>, &str)] = &[
+    (Some("pgdog"), "install_sharded_sequence"),
+    // Advisory locks are spread across shards by their key.
+    (None, "pg_advisory_unlock_all"),
+    (Some("pg_catalog"), "pg_advisory_unlock_all"),
+];
 
 #[derive(Default, Debug, Copy, Clone)]
 pub(crate) struct FunctionBehavior {
@@ -192,5 +197,15 @@ mod test {
                 assert!(!func.behavior().cross_shard);
             },
         );
+
+        for query in [
+            "SELECT pg_advisory_unlock_all()",
+            "SELECT pg_catalog.pg_advisory_unlock_all()",
+        ] {
+            first_func(query, |func| {
+                assert_eq!(func.name, "pg_advisory_unlock_all");
+                assert!(func.behavior().cross_shard);
+            });
+        }
     }
 }
@@ -0,0 +1,338 @@
+//! `HAVING` clause of a cross-shard aggregate.
+//!
+//! Each shard only sees part of every group, so Postgres can't evaluate
+//! `HAVING` correctly. Instead, we remove it from the query and apply it
+//! to the groups after merging them. We support comparisons between aggregates
+//! and constants, combined with `AND`, `OR` and `NOT`. Each aggregate must also
+//! be in the select list, since that's where we read its merged value from.
+//! Cross-shard queries with any other `HAVING` clause are rejected.
+
+#[cfg(not(feature = "new_parser"))]
+use pg_query::{
+    Node, NodeEnum,
+    protobuf::{AExprKind, BoolExprType, FuncCall, SelectStmt},
+};
+#[cfg(feature = "new_parser")]
+use pg_raw_parse::{Node, nodes};
+
+use super::Function;
+use super::Value;
+use super::copy_filter::{Constant, Expr, Op, Operand};
+
+/// Aggregate function call, compared without source locations.
+#[derive(Debug, PartialEq)]
+struct Call {
+    name: String,
+    star: bool,
+    distinct: bool,
+    args: Vec<Vec<String>>,
+}
+
+/// `HAVING` clause evaluated on merged groups.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Having {
+    /// `None` if we can't evaluate it.
+    expr: Option<Expr>,
+}
+
+impl Having {
+    /// `HAVING` clause that can't be evaluated after merging groups.
+    pub(crate) fn unsupported() -> Self {
+        Self::default()
+    }
+
+    /// We can evaluate the clause after merging groups.
+    pub(crate) fn supported(&self) -> bool {
+        self.expr.is_some()
+    }
+
+    /// Group passes the filter. The closure returns
+    /// the value in each column, `None` if it's NULL.
+    pub(crate) fn matches<'a>(&self, row: impl Fn(usize) -> Option<&'a str>) -> bool {
+        self.expr
+            .as_ref()
+            .is_some_and(|expr| expr.eval(&row) == Some(true))
+    }
+
+    /// Parse the `HAVING` clause. Returns `None` if there isn't one.
+    #[cfg(feature = "new_parser")]
+    pub(crate) fn parse(stmt: &nodes::SelectStmt) -> Option<Self> {
+        if let Node::None = stmt.having_clause() {
+            return None;
+        }
+
+        Some(Self {
+            expr: Self::expr(stmt.having_clause(), stmt),
+        })
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn expr(node: Node<'_>, stmt: &nodes::SelectStmt) -> Option<Expr> {
+        match node {
+            Node::BoolExpr(expr) => {
+                let args = expr
+                    .args()
+                    .iter()
+                    .map(|arg| Self::expr(arg, stmt))
+                    .collect::<Option<Vec<_>>>()?;
+
+                match expr.boolop {
+                    nodes::BoolExprType::AND_EXPR => Some(Expr::And(args)),
+                    nodes::BoolExprType::OR_EXPR => Some(Expr::Or(args)),
+                    nodes::BoolExprType::NOT_EXPR => {
+                        Some(Expr::Not(Box::new(args.into_iter().next()?)))
+                    }
+                    _ => None,
+                }
+            }
+
+            Node::A_Expr(expr) if expr.kind == nodes::A_Expr_Kind::AEXPR_OP => {
+                let op = expr.name().first().and_then(Node::as_str)?;
+                Expr::compare(
+                    Self::operand(expr.lexpr(), stmt)?,
+                    Op::new(op)?,
+                    Self::operand(expr.rexpr(), stmt)?,
+                )
+            }
+
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn operand(node: Node<'_>, stmt: &nodes::SelectStmt) -> Option<Operand> {
+        match node {
+            Node::A_Const(value) => Some(Operand::Constant(Constant::new(Value::from(value))?)),
+            Node::TypeCast(cast) if matches!(cast.arg(), Node::A_Const(_)) => {
+                Self::operand(cast.arg(), stmt)
+            }
+            node => {
+                let call = Self::call(node)?;
+                stmt.target_list()
+                    .iter()
+                    .position(|target| Self::call(target.val()).as_ref() == Some(&call))
+                    .map(Operand::Column)
+            }
+        }
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn call(node: Node<'_>) -> Option<Call> {
+        let func = match node {
+            Node::FuncCall(func) => func,
+            Node::TypeCast(cast) => return Self::call(cast.arg()),
+            _ => return None,
+        };
+
+        let args = func
+            .args()
+            .iter()
+            .map(|arg| match arg {
+                Node::ColumnRef(column) => Some(
+                    column
+                        .fields()
+                        .iter()
+                        .filter_map(Node::as_str)
+                        .map(str::to_owned)
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Call {
+            name: Function::from_strings(func.funcname().iter().filter_map(Node::as_str))?
+                .name
+                .to_owned(),
+            star: func.agg_star,
+            distinct: func.agg_distinct,
+            args,
+        })
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            /// Parse the `HAVING` clause. Returns `None` if there isn't one.
+            pub(crate) fn parse(stmt: &SelectStmt) -> Option<Self> {
+                let having = stmt.having_clause.as_ref()?;
+
+                Some(Self {
+                    expr: Self::expr(having, stmt),
+                })
+            }
+
+            fn string(node: &Node) -> Option<&str> {
+                match node.node {
+                    Some(NodeEnum::String(ref string)) => Some(string.sval.as_str()),
+                    _ => None,
+                }
+            }
+
+            fn expr(node: &Node, stmt: &SelectStmt) -> Option<Expr> {
+                match node.node.as_ref()? {
+                    NodeEnum::BoolExpr(expr) => {
+                        let args = expr
+                            .args
+                            .iter()
+                            .map(|arg| Self::expr(arg, stmt))
+                            .collect::<Option<Vec<_>>>()?;
+
+                        match expr.boolop() {
+                            BoolExprType::AndExpr => Some(Expr::And(args)),
+                            BoolExprType::OrExpr => Some(Expr::Or(args)),
+                            BoolExprType::NotExpr => Some(Expr::Not(Box::new(args.into_iter().next()?))),
+                            _ => None,
+                        }
+                    }
+
+                    NodeEnum::AExpr(expr) if expr.kind() == AExprKind::AexprOp => {
+                        let op = Self::string(expr.name.first()?)?;
+                        Expr::compare(
+                            Self::operand(expr.lexpr.as_deref()?, stmt)?,
+                            Op::new(op)?,
+                            Self::operand(expr.rexpr.as_deref()?, stmt)?,
+                        )
+                    }
+
+                    _ => None,
+                }
+            }
+
+            fn operand(node: &Node, stmt: &SelectStmt) -> Option<Operand> {
+                match node.node.as_ref()? {
+                    NodeEnum::AConst(value) => Some(Operand::Constant(Constant::new(Value::from(value))?)),
+                    NodeEnum::TypeCast(cast)
+                        if matches!(
+                            cast.arg.as_deref().and_then(|arg| arg.node.as_ref()),
+                            Some(NodeEnum::AConst(_))
+                        ) =>
+                    {
+                        Self::operand(cast.arg.as_deref()?, stmt)
+                    }
+                    _ => {
+                        let call = Self::call(node)?;
+                        stmt.target_list
+                            .iter()
+                            .position(|target| match target.node.as_ref() {
+                                Some(NodeEnum::ResTarget(res)) => res
+                                    .val
+                                    .as_deref()
+                                    .and_then(Self::call)
+                                    .is_some_and(|target| target == call),
+                                _ => false,
+                            })
+                            .map(Operand::Column)
+                    }
+                }
+            }
+
+            fn call(node: &Node) -> Option<Call> {
+                let func: &FuncCall = match node.node.as_ref()? {
+                    NodeEnum::FuncCall(func) => func,
+                    NodeEnum::TypeCast(cast) => return Self::call(cast.arg.as_deref()?),
+                    _ => return None,
+                };
+
+                let args = func
+                    .args
+                    .iter()
+                    .map(|arg| match arg.node.as_ref()? {
+                        NodeEnum::ColumnRef(column) => Some(
+                            column
+                                .fields
+                                .iter()
+                                .filter_map(Self::string)
+                                .map(str::to_owned)
+                                .collect(),
+                        ),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(Call {
+                    name: Function::from_strings(func.funcname.iter().filter_map(Self::string))?
+                        .name
+                        .to_owned(),
+                    star: func.agg_star,
+                    distinct: func.agg_distinct,
+                    args,
+                })
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "new_parser")]
+    use pg_raw_parse::{Owned, make};
+
+    #[cfg(feature = "new_parser")]
+    fn parse(stmt: &str) -> Option<Having> {
+        let stmt: Owned<nodes::SelectStmt> =
+            match pg_raw_parse::parse(stmt).unwrap().stmts().next().unwrap() {
+                Node::SelectStmt(stmt) => make::owned(|mem| mem.make_unique(stmt)),
+                _ => panic!("not a select"),
+            };
+        Having::parse(&stmt)
+    }
+
+    #[cfg(not(feature = "new_parser"))]
+    fn parse(stmt: &str) -> Option<Having> {
+        let stmt = pg_query::parse(stmt)
+            .unwrap()
+            .protobuf
+            .stmts
+            .remove(0)
+            .stmt
+            .unwrap();
+        match stmt.node.unwrap() {
+            NodeEnum::SelectStmt(stmt) => Having::parse(&stmt),
+            _ => panic!("not a select"),
+        }
+    }
+
+    #[test]
+    fn test_having_compare() {
+        let having =
+            parse("SELECT user_id, SUM(price) FROM orders GROUP BY 1 HAVING SUM(price) > 100")
+                .unwrap();
+
+        assert!(having.matches(|column| [Some("1"), Some("150")][column]));
+        assert!(!having.matches(|column| [Some("1"), Some("100")][column]));
+        assert!(!having.matches(|column| [Some("1"), None][column]));
+    }
+
+    #[test]
+    fn test_having_boolean() {
+        let having = parse(
+            "SELECT user_id, COUNT(*), MAX(price) FROM orders GROUP BY 1 \
+             HAVING 2 <= COUNT(*) AND NOT (MAX(price) >= 10.5 OR COUNT(*) = 5)",
+        )
+        .unwrap();
+
+        assert!(having.matches(|column| [Some("1"), Some("3"), Some("7")][column]));
+        assert!(!having.matches(|column| [Some("1"), Some("1"), Some("7")][column]));
+        assert!(!having.matches(|column| [Some("1"), Some("3"), Some("11")][column]));
+        assert!(!having.matches(|column| [Some("1"), Some("5"), Some("7")][column]));
+    }
+
+    #[test]
+    fn test_having_unsupported() {
+        assert!(parse("SELECT user_id, SUM(price) FROM orders GROUP BY 1").is_none());
+
+        for stmt in [
+            // Aggregate is not in the select list.
+            "SELECT user_id, SUM(price) FROM orders GROUP BY 1 HAVING COUNT(*) > 1",
+            "SELECT user_id, SUM(price) FROM orders GROUP BY 1 HAVING SUM(quantity) > 1",
+            // Comparing aggregates with each other.
+            "SELECT user_id, SUM(price), COUNT(*) FROM orders GROUP BY 1 HAVING SUM(price) > COUNT(*)",
+        ] {
+            let having = parse(stmt).unwrap();
+            assert!(!having.supported(), "{}", stmt);
+            assert!(!having.matches(|_| Some("1")));
+        }
+    }
+}
@@ -8,6 +8,8 @@ pub mod command;
 pub mod comment;
 pub mod context;
 pub mod copy;
+mod copy_filter;
+mod cost;
 mod csv;
 mod distinct;
 pub mod ee;
@@ -15,6 +17,7 @@ pub mod error;
 pub mod explain_trace;
 mod from_clause;
 pub mod function;
+mod having;
 pub mod key;
 mod limit;
 pub mod multi_tenant;
@@ -38,11 +41,13 @@ pub use command::{Command, SetParam};
 pub(crate) use comment::parse_edge_comment;
 pub use context::QueryParserContext;
 pub use copy::{CopyFormat, CopyParser};
+pub(crate) use cost::Cost;
 pub(crate) use csv::CsvStream;
 pub(crate) use distinct::{Distinct, DistinctBy, DistinctColumn};
 pub use error::Error;
 pub(crate) use from_clause::FromClause;
 use function::Function;
+pub(crate) use having::Having;
 pub use key::Key;
 pub(crate) use limit::{Limit, LimitClause};
 pub use order_by::OrderBy;
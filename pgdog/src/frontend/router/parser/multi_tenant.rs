@@ -1,7 +1,13 @@
 #[cfg(not(feature = "new_parser"))]
-use pg_query::{NodeEnum, ParseResult};
+use pg_query::{
+    Node as PgNode, NodeEnum, ParseResult,
+    protobuf::{
+        AConst, AExpr, AExprKind, BoolExpr, BoolExprType, ColumnRef, String as PgString,
+        a_const::Val,
+    },
+};
 #[cfg(feature = "new_parser")]
-use pg_raw_parse::Node;
+use pg_raw_parse::{ConstValue, Node, deparse, make::owned, nodes};
 
 use super::Error;
 use crate::{
@@ -126,6 +132,115 @@ impl<'a> MultiTenantCheck<'a> {
         _ => {}
     }
 
+    /// Same as [`Self::run`], except reads without the tenant filter
+    /// get one added, instead of being rejected.
+    ///
+    /// Returns the query with the filter added, if it needed one.
+    pub fn filter(&self, tenant: &str) -> Result<Option<String>, Error> {
+        match self.run() {
+            Err(Error::MultiTenantId) => self.add_filter(tenant),
+            result => result.map(|_| None),
+        }
+    }
+
+    #[cfg(feature = "new_parser")]
+    fn add_filter(&self, tenant: &str) -> Result<Option<String>, Error> {
+        let Node::SelectStmt(stmt) = self.ast else {
+            return Err(Error::MultiTenantId);
+        };
+
+        let select =
+            owned(|mem| {
+                let filter = mem
+                    .make_a_expr(
+                        nodes::A_Expr_Kind::AEXPR_OP,
+                        mem.make_list(&[mem.make_string(Some("=")).uncast()]),
+                        mem.make_column_ref(mem.make_list(&[
+                            mem.make_string(Some(self.config.column.as_str())).uncast(),
+                        ]))
+                        .uncast(),
+                        mem.make_a_const(ConstValue::String(tenant)).uncast(),
+                    )
+                    .uncast();
+
+                let where_clause = match stmt.where_clause() {
+                    Node::None => filter,
+                    where_clause => {
+                        let mut and = mem.make_node::<nodes::BoolExpr>();
+                        and.as_mut().set_boolop(nodes::BoolExprType::AND_EXPR);
+                        and.as_mut()
+                            .set_args(mem.make_list(&[filter, mem.make_unique(where_clause)]));
+                        and.uncast()
+                    }
+                };
+
+                let mut select = mem.make_unique(stmt);
+                select.as_mut().set_where_clause(where_clause);
+                mem.make_list(&[mem.make_raw_stmt(select.uncast())])
+            });
+
+        Ok(Some(deparse(select.first().unwrap())?.as_str().to_owned()))
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            fn add_filter(&self, tenant: &str) -> Result<Option<String>, Error> {
+                let mut ast = self.ast.protobuf.clone();
+                let Some(NodeEnum::SelectStmt(stmt)) = ast
+                    .stmts
+                    .first_mut()
+                    .and_then(|s| s.stmt.as_mut())
+                    .and_then(|n| n.node.as_mut())
+                else {
+                    return Err(Error::MultiTenantId);
+                };
+
+                let string = |sval: &str| PgNode {
+                    node: Some(NodeEnum::String(PgString {
+                        sval: sval.to_string(),
+                    })),
+                };
+
+                let filter = PgNode {
+                    node: Some(NodeEnum::AExpr(Box::new(AExpr {
+                        kind: AExprKind::AexprOp.into(),
+                        name: vec![string("=")],
+                        lexpr: Some(Box::new(PgNode {
+                            node: Some(NodeEnum::ColumnRef(ColumnRef {
+                                fields: vec![string(&self.config.column)],
+                                ..Default::default()
+                            })),
+                        })),
+                        rexpr: Some(Box::new(PgNode {
+                            node: Some(NodeEnum::AConst(AConst {
+                                val: Some(Val::Sval(PgString {
+                                    sval: tenant.to_string(),
+                                })),
+                                ..Default::default()
+                            })),
+                        })),
+                        ..Default::default()
+                    }))),
+                };
+
+                let where_clause = match stmt.where_clause.take() {
+                    Some(where_clause) => PgNode {
+                        node: Some(NodeEnum::BoolExpr(Box::new(BoolExpr {
+                            boolop: BoolExprType::AndExpr.into(),
+                            args: vec![filter, *where_clause],
+                            ..Default::default()
+                        }))),
+                    },
+                    None => filter,
+                };
+                stmt.where_clause = Some(Box::new(where_clause));
+
+                Ok(Some(ast.deparse()?))
+            }
+        }
+        _ => {}
+    }
+
     fn check(&self, table: Table, where_clause: Option<WhereClause>) -> Result<(), Error> {
         let search_path = SearchPath::new(self.user, self.search_path, &self.schema);
         let schemas = search_path.resolve();
@@ -194,6 +309,7 @@ mod tests {
         let stmt = ast.stmts().next().unwrap();
         let config = MultiTenant {
             column: "tenant_id".into(),
+            filter: false,
         };
 
         let check = MultiTenantCheck::new("alice", &config, schema, stmt, None);
@@ -209,6 +325,7 @@ mod tests {
                     .expect("parse select statement");
                 let config = MultiTenant {
                     column: "tenant_id".into(),
+                    filter: false,
                 };
 
                 let check = MultiTenantCheck::new("alice", &config, schema, &ast, None);
@@ -226,6 +343,7 @@ mod tests {
         let stmt = ast.stmts().next().unwrap();
         let config = MultiTenant {
             column: "tenant_id".into(),
+            filter: false,
         };
 
         let check = MultiTenantCheck::new("alice", &config, schema, stmt, None);
@@ -246,6 +364,7 @@ mod tests {
                     .expect("parse select statement");
                 let config = MultiTenant {
                     column: "tenant_id".into(),
+                    filter: false,
                 };
 
                 let check = MultiTenantCheck::new("alice", &config, schema, &ast, None);
@@ -14,26 +14,38 @@ impl QueryParser {
             &mut context.shards_calculator,
         )?;
 
-        Ok(command)
+        match command {
+            Some(command) => Ok(command),
+            None => Self::unknown_statement(context),
+        }
     }
 
+    /// Route DDL and other utility commands.
+    /// Returns `None` for statements we don't know how to route.
     #[cfg(feature = "new_parser")]
     pub(super) fn shard_ddl(
         node: Node<'_>,
         schema: &ShardingSchema,
         calculator: &mut ShardsWithPriority,
-    ) -> Result<Command, Error> {
+    ) -> Result<Option<Command>, Error> {
         use nodes::ObjectType;
         let mut shard = Shard::All;
         let mut schema_changed = false;
+        let mut truncate = false;
+        let mut temporary = false;
 
         match node {
             Node::CreateStmt(stmt) => {
                 schema_changed = true;
+                temporary = Self::temporary_table(
+                    stmt.relation(),
+                    stmt.oncommit == nodes::OnCommitAction::ONCOMMIT_DROP,
+                );
                 shard = Self::shard_ddl_table(stmt.relation(), schema)?.unwrap_or(Shard::All);
             }
 
             Node::CreateSeqStmt(stmt) => {
+                temporary = Self::temporary_table(stmt.sequence(), false);
                 shard = Self::shard_ddl_table(stmt.sequence(), schema)?.unwrap_or(Shard::All);
             }
 
@@ -74,16 +86,31 @@ impl QueryParser {
 
             Node::ViewStmt(stmt) => {
                 schema_changed = true;
+                temporary = Self::temporary_table(stmt.view(), false);
                 shard = Self::shard_ddl_table(stmt.view(), schema)?.unwrap_or(Shard::All);
             }
 
             Node::CreateTableAsStmt(stmt) => {
                 schema_changed = true;
                 if let Some(into) = stmt.into() {
+                    temporary = Self::temporary_table(
+                        into.rel(),
+                        into.onCommit == nodes::OnCommitAction::ONCOMMIT_DROP,
+                    );
                     shard = Self::shard_ddl_table(into.rel(), schema)?.unwrap_or(Shard::All);
                 }
             }
 
+            // SELECT ... INTO creates a table, just like CREATE TABLE ... AS.
+            Node::SelectStmt(stmt) if let Some(into) = stmt.into_clause() => {
+                schema_changed = true;
+                temporary = Self::temporary_table(
+                    into.rel(),
+                    into.onCommit == nodes::OnCommitAction::ONCOMMIT_DROP,
+                );
+                shard = Self::shard_ddl_table(into.rel(), schema)?.unwrap_or(Shard::All);
+            }
+
             Node::CreateFunctionStmt(stmt) => {
                 let table = Table::try_from(stmt.funcname()).ok();
                 if let Some(table) = table {
@@ -167,7 +194,7 @@ impl QueryParser {
                             // any DDL pattern (like BEGIN, END, etc.)
                             let mut inner_calculator = ShardsWithPriority::default();
                             let command = Self::shard_ddl(node, schema, &mut inner_calculator)?;
-                            if let Command::Query(query) = command
+                            if let Some(Command::Query(query)) = command
                                 && !query.is_cross_shard()
                             {
                                 shard = query.shard().clone();
@@ -179,6 +206,7 @@ impl QueryParser {
             }
 
             Node::TruncateStmt(stmt) => {
+                truncate = true;
                 let mut shards = HashSet::new();
                 for relation in stmt.relations() {
                     if let Node::RangeVar(relation) = relation {
@@ -197,35 +225,128 @@ impl QueryParser {
                 }
             }
 
-            // All others are not handled.
-            // They are sent to all shards concurrently.
-            _ => (),
+            // Sent to all shards concurrently.
+            Node::AlterCollationStmt(_)
+            | Node::AlterDatabaseSetStmt(_)
+            | Node::AlterDatabaseStmt(_)
+            | Node::AlterDefaultPrivilegesStmt(_)
+            | Node::AlterDomainStmt(_)
+            | Node::AlterEnumStmt(_)
+            | Node::AlterEventTrigStmt(_)
+            | Node::AlterExtensionContentsStmt(_)
+            | Node::AlterExtensionStmt(_)
+            | Node::AlterFdwStmt(_)
+            | Node::AlterForeignServerStmt(_)
+            | Node::AlterFunctionStmt(_)
+            | Node::AlterObjectDependsStmt(_)
+            | Node::AlterObjectSchemaStmt(_)
+            | Node::AlterOpFamilyStmt(_)
+            | Node::AlterOperatorStmt(_)
+            | Node::AlterPolicyStmt(_)
+            | Node::AlterPublicationStmt(_)
+            | Node::AlterRoleSetStmt(_)
+            | Node::AlterRoleStmt(_)
+            | Node::AlterStatsStmt(_)
+            | Node::AlterSubscriptionStmt(_)
+            | Node::AlterSystemStmt(_)
+            | Node::AlterTableMoveAllStmt(_)
+            | Node::AlterTableSpaceOptionsStmt(_)
+            | Node::AlterTsconfigurationStmt(_)
+            | Node::AlterTsdictionaryStmt(_)
+            | Node::AlterTypeStmt(_)
+            | Node::AlterUserMappingStmt(_)
+            | Node::CallStmt(_)
+            | Node::CheckPointStmt(_)
+            | Node::ClosePortalStmt(_)
+            | Node::ClusterStmt(_)
+            | Node::CommentStmt(_)
+            | Node::CompositeTypeStmt(_)
+            | Node::ConstraintsSetStmt(_)
+            | Node::CreateAmStmt(_)
+            | Node::CreateCastStmt(_)
+            | Node::CreateConversionStmt(_)
+            | Node::CreateDomainStmt(_)
+            | Node::CreateEventTrigStmt(_)
+            | Node::CreateExtensionStmt(_)
+            | Node::CreateFdwStmt(_)
+            | Node::CreateForeignServerStmt(_)
+            | Node::CreateForeignTableStmt(_)
+            | Node::CreateOpClassStmt(_)
+            | Node::CreateOpFamilyStmt(_)
+            | Node::CreatePlangStmt(_)
+            | Node::CreatePolicyStmt(_)
+            | Node::CreatePublicationStmt(_)
+            | Node::CreateRangeStmt(_)
+            | Node::CreateRoleStmt(_)
+            | Node::CreateStatsStmt(_)
+            | Node::CreateSubscriptionStmt(_)
+            | Node::CreateTableSpaceStmt(_)
+            | Node::CreateTransformStmt(_)
+            | Node::CreateTrigStmt(_)
+            | Node::CreateUserMappingStmt(_)
+            | Node::CreatedbStmt(_)
+            | Node::DeclareCursorStmt(_)
+            | Node::DefineStmt(_)
+            | Node::DropOwnedStmt(_)
+            | Node::DropRoleStmt(_)
+            | Node::DropSubscriptionStmt(_)
+            | Node::DropTableSpaceStmt(_)
+            | Node::DropUserMappingStmt(_)
+            | Node::DropdbStmt(_)
+            | Node::ExecuteStmt(_)
+            | Node::FetchStmt(_)
+            | Node::GrantRoleStmt(_)
+            | Node::GrantStmt(_)
+            | Node::ImportForeignSchemaStmt(_)
+            | Node::LoadStmt(_)
+            | Node::MergeStmt(_)
+            | Node::PrepareStmt(_)
+            | Node::ReassignOwnedStmt(_)
+            | Node::RefreshMatViewStmt(_)
+            | Node::ReindexStmt(_)
+            | Node::RuleStmt(_)
+            | Node::SecLabelStmt(_) => (),
+
+            // We don't know how to route this.
+            _ => return Ok(None),
         };
 
         calculator.push(ShardWithPriority::new_table(shard));
 
-        Ok(Command::Query(
-            Route::write(calculator.shard()).with_schema_changed(schema_changed),
-        ))
+        Ok(Some(Command::Query(
+            Route::write(calculator.shard())
+                .with_schema_changed(schema_changed)
+                .with_truncate(truncate)
+                .with_temporary_table(temporary),
+        )))
     }
 
     cfg_select! {
         not(feature = "new_parser") => {
+            /// Route DDL and other utility commands.
+            /// Returns `None` for statements we don't know how to route.
             pub(super) fn shard_ddl(
                 node: &Option<NodeEnum>,
                 schema: &ShardingSchema,
                 calculator: &mut ShardsWithPriority,
-            ) -> Result<Command, Error> {
+            ) -> Result<Option<Command>, Error> {
                 let mut shard = Shard::All;
                 let mut schema_changed = false;
+                let mut truncate = false;
+                let mut temporary = false;
 
                 match node {
                     Some(NodeEnum::CreateStmt(stmt)) => {
                         schema_changed = true;
+                        temporary = Self::temporary_table(
+                            &stmt.relation,
+                            stmt.oncommit() == OnCommitAction::OncommitDrop,
+                        );
                         shard = Self::shard_ddl_table(&stmt.relation, schema)?.unwrap_or(Shard::All);
                     }
 
                     Some(NodeEnum::CreateSeqStmt(stmt)) => {
+                        temporary = Self::temporary_table(&stmt.sequence, false);
                         shard = Self::shard_ddl_table(&stmt.sequence, schema)?.unwrap_or(Shard::All);
                     }
 
@@ -268,16 +389,31 @@ impl QueryParser {
 
                     Some(NodeEnum::ViewStmt(stmt)) => {
                         schema_changed = true;
+                        temporary = Self::temporary_table(&stmt.view, false);
                         shard = Self::shard_ddl_table(&stmt.view, schema)?.unwrap_or(Shard::All);
                     }
 
                     Some(NodeEnum::CreateTableAsStmt(stmt)) => {
                         schema_changed = true;
                         if let Some(into) = &stmt.into {
+                            temporary = Self::temporary_table(
+                                &into.rel,
+                                into.on_commit() == OnCommitAction::OncommitDrop,
+                            );
                             shard = Self::shard_ddl_table(&into.rel, schema)?.unwrap_or(Shard::All);
                         }
                     }
 
+                    // SELECT ... INTO creates a table, just like CREATE TABLE ... AS.
+                    Some(NodeEnum::SelectStmt(stmt)) if let Some(into) = &stmt.into_clause => {
+                        schema_changed = true;
+                        temporary = Self::temporary_table(
+                            &into.rel,
+                            into.on_commit() == OnCommitAction::OncommitDrop,
+                        );
+                        shard = Self::shard_ddl_table(&into.rel, schema)?.unwrap_or(Shard::All);
+                    }
+
                     Some(NodeEnum::CreateFunctionStmt(stmt)) => {
                         let table = Table::try_from(&stmt.funcname).ok();
                         if let Some(table) = table {
@@ -370,7 +506,7 @@ impl QueryParser {
                                     let mut inner_calculator = ShardsWithPriority::default();
                                     let command =
                                         Self::shard_ddl(&node.node, schema, &mut inner_calculator)?;
-                                    if let Command::Query(query) = command
+                                    if let Some(Command::Query(query)) = command
                                         && !query.is_cross_shard()
                                     {
                                         shard = query.shard().clone();
@@ -382,6 +518,7 @@ impl QueryParser {
                     }
 
                     Some(NodeEnum::TruncateStmt(stmt)) => {
+                        truncate = true;
                         let mut shards = HashSet::new();
                         for relation in &stmt.relations {
                             if let Some(NodeEnum::RangeVar(ref relation)) = relation.node {
@@ -401,16 +538,102 @@ impl QueryParser {
                         }
                     }
 
-                    // All others are not handled.
-                    // They are sent to all shards concurrently.
-                    _ => (),
+                    // Sent to all shards concurrently.
+                    Some(
+                        NodeEnum::AlterCollationStmt(_)
+                            | NodeEnum::AlterDatabaseSetStmt(_)
+                            | NodeEnum::AlterDatabaseStmt(_)
+                            | NodeEnum::AlterDefaultPrivilegesStmt(_)
+                            | NodeEnum::AlterDomainStmt(_)
+                            | NodeEnum::AlterEnumStmt(_)
+                            | NodeEnum::AlterEventTrigStmt(_)
+                            | NodeEnum::AlterExtensionContentsStmt(_)
+                            | NodeEnum::AlterExtensionStmt(_)
+                            | NodeEnum::AlterFdwStmt(_)
+                            | NodeEnum::AlterForeignServerStmt(_)
+                            | NodeEnum::AlterFunctionStmt(_)
+                            | NodeEnum::AlterObjectDependsStmt(_)
+                            | NodeEnum::AlterObjectSchemaStmt(_)
+                            | NodeEnum::AlterOpFamilyStmt(_)
+                            | NodeEnum::AlterOperatorStmt(_)
+                            | NodeEnum::AlterPolicyStmt(_)
+                            | NodeEnum::AlterPublicationStmt(_)
+                            | NodeEnum::AlterRoleSetStmt(_)
+                            | NodeEnum::AlterRoleStmt(_)
+                            | NodeEnum::AlterStatsStmt(_)
+                            | NodeEnum::AlterSubscriptionStmt(_)
+                            | NodeEnum::AlterSystemStmt(_)
+                            | NodeEnum::AlterTableMoveAllStmt(_)
+                            | NodeEnum::AlterTableSpaceOptionsStmt(_)
+                            | NodeEnum::AlterTsconfigurationStmt(_)
+                            | NodeEnum::AlterTsdictionaryStmt(_)
+                            | NodeEnum::AlterTypeStmt(_)
+                            | NodeEnum::AlterUserMappingStmt(_)
+                            | NodeEnum::CallStmt(_)
+                            | NodeEnum::CheckPointStmt(_)
+                            | NodeEnum::ClosePortalStmt(_)
+                            | NodeEnum::ClusterStmt(_)
+                            | NodeEnum::CommentStmt(_)
+                            | NodeEnum::CompositeTypeStmt(_)
+                            | NodeEnum::ConstraintsSetStmt(_)
+                            | NodeEnum::CreateAmStmt(_)
+                            | NodeEnum::CreateCastStmt(_)
+                            | NodeEnum::CreateConversionStmt(_)
+                            | NodeEnum::CreateDomainStmt(_)
+                            | NodeEnum::CreateEventTrigStmt(_)
+                            | NodeEnum::CreateExtensionStmt(_)
+                            | NodeEnum::CreateFdwStmt(_)
+                            | NodeEnum::CreateForeignServerStmt(_)
+                            | NodeEnum::CreateForeignTableStmt(_)
+                            | NodeEnum::CreateOpClassStmt(_)
+                            | NodeEnum::CreateOpFamilyStmt(_)
+                            | NodeEnum::CreatePlangStmt(_)
+                            | NodeEnum::CreatePolicyStmt(_)
+                            | NodeEnum::CreatePublicationStmt(_)
+                            | NodeEnum::CreateRangeStmt(_)
+                            | NodeEnum::CreateRoleStmt(_)
+                            | NodeEnum::CreateStatsStmt(_)
+                            | NodeEnum::CreateSubscriptionStmt(_)
+                            | NodeEnum::CreateTableSpaceStmt(_)
+                            | NodeEnum::CreateTransformStmt(_)
+                            | NodeEnum::CreateTrigStmt(_)
+                            | NodeEnum::CreateUserMappingStmt(_)
+                            | NodeEnum::CreatedbStmt(_)
+                            | NodeEnum::DeclareCursorStmt(_)
+                            | NodeEnum::DefineStmt(_)
+                            | NodeEnum::DropOwnedStmt(_)
+                            | NodeEnum::DropRoleStmt(_)
+                            | NodeEnum::DropSubscriptionStmt(_)
+                            | NodeEnum::DropTableSpaceStmt(_)
+                            | NodeEnum::DropUserMappingStmt(_)
+                            | NodeEnum::DropdbStmt(_)
+                            | NodeEnum::ExecuteStmt(_)
+                            | NodeEnum::FetchStmt(_)
+                            | NodeEnum::GrantRoleStmt(_)
+                            | NodeEnum::GrantStmt(_)
+                            | NodeEnum::ImportForeignSchemaStmt(_)
+                            | NodeEnum::LoadStmt(_)
+                            | NodeEnum::MergeStmt(_)
+                            | NodeEnum::PrepareStmt(_)
+                            | NodeEnum::ReassignOwnedStmt(_)
+                            | NodeEnum::RefreshMatViewStmt(_)
+                            | NodeEnum::ReindexStmt(_)
+                            | NodeEnum::RuleStmt(_)
+                            | NodeEnum::SecLabelStmt(_)
+                    ) => (),
+
+                    // We don't know how to route this.
+                    _ => return Ok(None),
                 };
 
                 calculator.push(ShardWithPriority::new_table(shard));
 
-                Ok(Command::Query(
-                    Route::write(calculator.shard()).with_schema_changed(schema_changed),
-                ))
+                Ok(Some(Command::Query(
+                    Route::write(calculator.shard())
+                        .with_schema_changed(schema_changed)
+                        .with_truncate(truncate)
+                        .with_temporary_table(temporary),
+                )))
             }
         }
         _ => {}
@@ -431,8 +654,22 @@ impl QueryParser {
         Ok(None)
     }
 
+    /// The table is temporary and outlives the transaction that created it.
+    #[cfg(feature = "new_parser")]
+    fn temporary_table(range_var: Option<&nodes::RangeVar>, on_commit_drop: bool) -> bool {
+        range_var.is_some_and(|range_var| range_var.relpersistence as u8 == b't') && !on_commit_drop
+    }
+
     cfg_select! {
         not(feature = "new_parser") => {
+            /// The table is temporary and outlives the transaction that created it.
+            fn temporary_table(range_var: &Option<RangeVar>, on_commit_drop: bool) -> bool {
+                range_var
+                    .as_ref()
+                    .is_some_and(|range_var| range_var.relpersistence == "t")
+                    && !on_commit_drop
+            }
+
             pub(super) fn shard_ddl_table(
                 range_var: &Option<RangeVar>,
                 schema: &ShardingSchema,
@@ -481,7 +718,9 @@ mod test {
         let ast = pg_raw_parse::parse(query).unwrap();
         let root = ast.stmts().next().unwrap();
         let mut calculator = ShardsWithPriority::default();
-        QueryParser::shard_ddl(root, &test_schema(), &mut calculator).unwrap()
+        QueryParser::shard_ddl(root, &test_schema(), &mut calculator)
+            .unwrap()
+            .unwrap()
     }
 
     cfg_select! {
@@ -498,7 +737,9 @@ mod test {
                     .unwrap()
                     .node;
                 let mut calculator = ShardsWithPriority::default();
-                QueryParser::shard_ddl(&root, &test_schema(), &mut calculator).unwrap()
+                QueryParser::shard_ddl(&root, &test_schema(), &mut calculator)
+                    .unwrap()
+                    .unwrap()
             }
         }
         _ => {}
@@ -777,6 +1018,7 @@ mod test {
         let command = parse_stmt("VACUUM");
         assert_eq!(command.route().shard(), &Shard::All);
         assert!(!command.route().is_schema_changed());
+        assert!(!command.route().is_truncate());
     }
 
     #[test]
@@ -791,6 +1033,7 @@ mod test {
         let command = parse_stmt("TRUNCATE public.test");
         assert_eq!(command.route().shard(), &Shard::All);
         assert!(!command.route().is_schema_changed());
+        assert!(command.route().is_truncate());
     }
 
     #[test]
@@ -806,6 +1049,34 @@ mod test {
         parse_stmt("TRUNCATE shard_0.test1, shard_1.test2");
     }
 
+    #[test]
+    fn test_create_temporary_table() {
+        for query in [
+            "CREATE TEMP TABLE test (id BIGINT)",
+            "CREATE TEMPORARY TABLE test (id BIGINT) ON COMMIT DELETE ROWS",
+            "CREATE TEMP TABLE test AS SELECT 1 AS id",
+            "SELECT 1 AS id INTO TEMP test",
+            "SELECT * INTO TEMPORARY TABLE test FROM users",
+            "CREATE TEMP VIEW test AS SELECT 1 AS id",
+            "CREATE OR REPLACE TEMPORARY VIEW test AS SELECT 1 AS id",
+            "CREATE TEMP SEQUENCE test",
+        ] {
+            assert!(parse_stmt(query).route().is_temporary_table(), "{}", query);
+        }
+
+        for query in [
+            "CREATE TABLE test (id BIGINT)",
+            "CREATE UNLOGGED TABLE test (id BIGINT)",
+            "CREATE TEMP TABLE test (id BIGINT) ON COMMIT DROP",
+            "CREATE TEMP TABLE test ON COMMIT DROP AS SELECT 1 AS id",
+            "SELECT 1 AS id INTO test",
+            "CREATE VIEW test AS SELECT 1 AS id",
+            "CREATE SEQUENCE test",
+        ] {
+            assert!(!parse_stmt(query).route().is_temporary_table(), "{}", query);
+        }
+    }
+
     #[test]
     fn test_unhandled_ddl_defaults_to_all() {
         let command = parse_stmt("COMMENT ON TABLE public.test IS 'test comment'");
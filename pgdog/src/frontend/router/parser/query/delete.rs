@@ -51,6 +51,8 @@ impl QueryParser {
             }
         }
 
+        Self::check_sharding_key(context, is_sharded, "DELETE")?;
+
         Ok(Command::Query(
             Route::write(context.shards_calculator.shard()).with_omnisharded(omnisharded),
         ))
@@ -11,6 +11,13 @@ impl QueryParser {
         context: &mut QueryParserContext,
     ) -> Result<Command, Error> {
         let query = stmt.query();
+        let analyze = stmt.options().any(|elem| {
+            elem.defname() == Some("analyze")
+                && !matches!(
+                    elem.arg().as_str().map(|s| s.to_lowercase()).as_deref(),
+                    Some("false" | "off" | "0")
+                )
+        });
 
         if context.expanded_explain() {
             if self.explain_recorder.is_none() {
@@ -38,7 +45,12 @@ impl QueryParser {
         };
 
         match result {
-            Ok(command) => Ok(command),
+            Ok(mut command) => {
+                if let Command::Query(ref mut route) = command {
+                    route.set_explain_analyze(analyze);
+                }
+                Ok(command)
+            }
             Err(err) => {
                 self.explain_recorder = None;
                 Err(err)
@@ -56,6 +68,19 @@ impl QueryParser {
             ) -> Result<Command, Error> {
                 let query = stmt.query.as_ref().ok_or(Error::EmptyQuery)?;
                 let node = query.node.as_ref().ok_or(Error::EmptyQuery)?;
+                let analyze = stmt.options.iter().any(|option| {
+                    if let Some(NodeEnum::DefElem(ref elem)) = option.node
+                        && elem.defname == "analyze"
+                    {
+                        !matches!(
+                            elem.arg.as_ref().and_then(|arg| arg.node.as_ref()),
+                            Some(NodeEnum::String(string))
+                                if matches!(string.sval.to_lowercase().as_str(), "false" | "off" | "0")
+                        )
+                    } else {
+                        false
+                    }
+                });
 
                 if context.expanded_explain() {
                     if self.explain_recorder.is_none() {
@@ -96,7 +121,12 @@ impl QueryParser {
                 };
 
                 match result {
-                    Ok(command) => Ok(command),
+                    Ok(mut command) => {
+                        if let Command::Query(ref mut route) = command {
+                            route.set_explain_analyze(analyze);
+                        }
+                        Ok(command)
+                    }
                     Err(err) => {
                         self.explain_recorder = None;
                         Err(err)
@@ -229,6 +259,22 @@ mod tests {
         assert!(lines.iter().any(|line| line.contains("broadcast")));
     }
 
+    #[test]
+    fn test_explain_analyze() {
+        let r = route("EXPLAIN ANALYZE SELECT * FROM sharded");
+        assert_eq!(r.shard(), &Shard::All);
+        assert!(r.is_explain_analyze());
+
+        let r = route("EXPLAIN (ANALYZE, BUFFERS) SELECT * FROM sharded");
+        assert!(r.is_explain_analyze());
+
+        let r = route("EXPLAIN (ANALYZE false) SELECT * FROM sharded");
+        assert!(!r.is_explain_analyze());
+
+        let r = route("EXPLAIN SELECT * FROM sharded");
+        assert!(!r.is_explain_analyze());
+    }
+
     #[test]
     fn test_explain_insert() {
         let r = route_parameterized(
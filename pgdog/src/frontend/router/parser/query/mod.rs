@@ -26,13 +26,16 @@ use super::{
 mod ddl;
 mod delete;
 mod explain;
+mod multi_statement;
 mod plugins;
+mod point_read;
 mod select;
 mod set;
 mod set_config;
 mod shared;
 mod show;
 mod transaction;
+mod unknown;
 mod update;
 
 #[cfg(feature = "new_parser")]
@@ -68,6 +71,8 @@ pub struct QueryParser {
     plugin_output: PluginOutput,
     // Record explain output.
     explain_recorder: Option<ExplainRecorder>,
+    // Query with the tenant filter added to it.
+    tenant_filter: Option<String>,
 }
 
 impl QueryParser {
@@ -121,15 +126,28 @@ impl QueryParser {
     /// Parse a query and return a command.
     pub fn parse(&mut self, context: RouterContext) -> Result<Command, Error> {
         let mut context = QueryParserContext::new(context)?;
+        self.tenant_filter = None;
 
         let mut command = if context.query().is_ok() {
             self.write_override = context.write_override();
 
-            self.query(&mut context)?
+            let command = self.query(&mut context)?;
+            self.multi_statement(command, &context)?
+        } else if context.router_context.client_request.is_fastpath() {
+            // Fastpath function calls don't have a query we can look at,
+            // so they go to the shard we're connected to, or the first one.
+            let shard = context.router_context.connected_shard.unwrap_or(0);
+            Command::Query(Route::write(ShardWithPriority::new_override_fastpath(
+                Shard::Direct(shard),
+            )))
         } else {
             Command::default()
         };
 
+        if let Command::Set { params, .. } = &command {
+            Self::check_set_params(params)?;
+        }
+
         match &mut command {
             Command::Query(route) | Command::Set { route, .. } => {
                 if route.is_cross_shard() && context.shards == 1 {
@@ -149,11 +167,25 @@ impl QueryParser {
                         _ => route.set_read(true),
                     }
                 }
+
+                if let Some(ast) = &context.router_context.ast {
+                    route.set_replica(ast.comment_replica.clone());
+                }
+
+                route.set_tenant_filter(self.tenant_filter.take());
             }
 
             _ => (),
         }
 
+        // Only queries routed by search_path can end up on the wrong shard.
+        if let Command::Query(route) = &command
+            && route.is_search_path_driven()
+            && let Some(schemas) = context.search_path_conflict.take()
+        {
+            return Err(Error::SearchPathConflict(schemas));
+        }
+
         debug!("query router decision: {:#?}", command);
 
         self.attach_explain(&mut command);
@@ -217,6 +249,10 @@ impl QueryParser {
     ///
     #[cfg(feature = "new_parser")]
     fn query(&mut self, context: &mut QueryParserContext) -> Result<Command, Error> {
+        if context.router_context.parse_failed {
+            return Self::parse_failure(context);
+        }
+
         let parser_enabled = context.router_context.ast.is_some();
 
         debug!(
@@ -280,14 +316,22 @@ impl QueryParser {
         {
             debug!("running multi-tenant check");
 
-            MultiTenantCheck::new(
+            let check = MultiTenantCheck::new(
                 context.router_context.cluster.user(),
                 multi_tenant,
                 context.router_context.cluster.schema(),
                 stmt,
                 context.router_context.parameter_hints.search_path,
-            )
-            .run()?;
+            );
+
+            // Extended protocol statements are cached by their text,
+            // so only simple queries can be rewritten.
+            match context.tenant() {
+                Some(tenant) if context.query()?.simple() && stmts.len() == 1 => {
+                    self.tenant_filter = check.filter(tenant)?;
+                }
+                _ => check.run()?,
+            }
         }
 
         // Handle multi-statement SET commands (e.g. "SET x TO 1; SET y TO 2").
@@ -328,8 +372,14 @@ impl QueryParser {
                 return self.show(stmt, context);
             }
 
-            Node::DeallocateStmt(_) => {
-                return Ok(Command::Deallocate);
+            Node::DeallocateStmt(stmt) => {
+                return Ok(Command::Deallocate {
+                    name: stmt.name().map(str::to_owned),
+                });
+            }
+
+            Node::SelectStmt(stmt) if stmt.into_clause().is_some() => {
+                self.ddl(root.stmt(), context)
             }
 
             Node::SelectStmt(stmt) => self.select(&statement, stmt, context),
@@ -384,9 +434,10 @@ impl QueryParser {
 
             Node::ExplainStmt(stmt) => self.explain(&statement, stmt, context),
 
-            Node::DiscardStmt { .. } => {
+            Node::DiscardStmt(stmt) => {
                 return Ok(Command::Discard {
                     extended: !context.query()?.simple(),
+                    all: stmt.target == nodes::DiscardMode::DISCARD_ALL,
                 });
             }
 
@@ -486,6 +537,10 @@ impl QueryParser {
     cfg_select! {
         not(feature = "new_parser") => {
             fn query(&mut self, context: &mut QueryParserContext) -> Result<Command, Error> {
+                if context.router_context.parse_failed {
+                    return Self::parse_failure(context);
+                }
+
                 let parser_enabled = context.router_context.ast.is_some();
 
                 debug!(
@@ -543,14 +598,25 @@ impl QueryParser {
                 if let Some(multi_tenant) = context.multi_tenant() {
                     debug!("running multi-tenant check");
 
-                    MultiTenantCheck::new(
+                    let check = MultiTenantCheck::new(
                         context.router_context.cluster.user(),
                         multi_tenant,
                         context.router_context.cluster.schema(),
                         statement.parse_result(),
                         context.router_context.parameter_hints.search_path,
-                    )
-                    .run()?;
+                    );
+
+                    // Extended protocol statements are cached by their text,
+                    // so only simple queries can be rewritten.
+                    match context.tenant() {
+                        Some(tenant)
+                            if context.query()?.simple()
+                                && statement.parse_result().protobuf.stmts.len() == 1 =>
+                        {
+                            self.tenant_filter = check.filter(tenant)?;
+                        }
+                        _ => check.run()?,
+                    }
                 }
 
                 let stmts = &statement.parse_result().protobuf.stmts;
@@ -600,8 +666,14 @@ impl QueryParser {
                     // SHOW statements -> return immediately.
                     Some(NodeEnum::VariableShowStmt(ref stmt)) => return self.show(stmt, context),
                     // DEALLOCATE statements -> return immediately.
-                    Some(NodeEnum::DeallocateStmt(_)) => {
-                        return Ok(Command::Deallocate);
+                    Some(NodeEnum::DeallocateStmt(ref stmt)) => {
+                        return Ok(Command::Deallocate {
+                            name: (!stmt.name.is_empty()).then(|| stmt.name.clone()),
+                        });
+                    }
+                    // SELECT ... INTO is DDL.
+                    Some(NodeEnum::SelectStmt(ref stmt)) if stmt.into_clause.is_some() => {
+                        self.ddl(&root.node, context)
                     }
                     // SELECT statements.
                     Some(NodeEnum::SelectStmt(ref stmt)) => self.select(
@@ -665,9 +737,10 @@ impl QueryParser {
 
                     Some(NodeEnum::ExplainStmt(ref stmt)) => self.explain(&statement, stmt, context),
 
-                    Some(NodeEnum::DiscardStmt { .. }) => {
+                    Some(NodeEnum::DiscardStmt(ref stmt)) => {
                         return Ok(Command::Discard {
                             extended: !context.query()?.simple(),
+                            all: stmt.target() == DiscardMode::DiscardAll,
                         });
                     }
 
@@ -799,7 +872,11 @@ impl QueryParser {
             }
         }
 
-        let parser = CopyParser::new(stmt, context.router_context.cluster)?;
+        let parser = CopyParser::new(
+            stmt,
+            context.router_context.cluster,
+            context.router_context.parameter_hints.search_path,
+        )?;
         if !stmt.is_from {
             context
                 .shards_calculator
@@ -844,7 +921,11 @@ impl QueryParser {
                     }
                 }
 
-                let parser = CopyParser::new(stmt, context.router_context.cluster)?;
+                let parser = CopyParser::new(
+                    stmt,
+                    context.router_context.cluster,
+                    context.router_context.parameter_hints.search_path,
+                )?;
                 if !stmt.is_from {
                     context
                         .shards_calculator
@@ -904,6 +985,8 @@ impl QueryParser {
             ShardWithPriority::new_table_omni(shard)
         });
 
+        Self::check_sharding_key(context, is_sharded, "INSERT")?;
+
         let shard = context.shards_calculator.shard();
 
         if let Some(recorder) = self.recorder_mut() {
@@ -922,6 +1005,24 @@ impl QueryParser {
             Route::write(shard).with_omnisharded(omnisharded),
         ))
     }
+
+    /// Reject a write to a sharded table that would be sent to all shards
+    /// because it doesn't specify the sharding key.
+    fn check_sharding_key(
+        context: &QueryParserContext,
+        is_sharded: bool,
+        command: &'static str,
+    ) -> Result<(), Error> {
+        if context.require_sharding_key
+            && is_sharded
+            && context.shards > 1
+            && context.shards_calculator.shard().is_all()
+        {
+            return Err(Error::ShardingKeyRequired(command));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "new_parser")]
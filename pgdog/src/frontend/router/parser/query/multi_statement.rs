@@ -0,0 +1,89 @@
+//! Routing of simple queries containing multiple statements.
+use pgdog_config::MultiStatementQueries;
+
+use super::*;
+
+impl QueryParser {
+    /// Apply the multi-statement query policy to the routing decision
+    /// made for the first statement.
+    pub(super) fn multi_statement(
+        &mut self,
+        mut command: Command,
+        context: &QueryParserContext,
+    ) -> Result<Command, Error> {
+        let Some(ast) = &context.router_context.ast else {
+            return Ok(command);
+        };
+
+        // Multiple SET commands are handled separately and the server
+        // owns the session in session mode.
+        if ast.len() < 2 || context.is_session_mode() || matches!(command, Command::Set { .. }) {
+            return Ok(command);
+        }
+
+        match context.multi_statement_queries {
+            MultiStatementQueries::FirstStatement => Ok(command),
+            MultiStatementQueries::Reject => Err(Error::MultiStatementRejected),
+            MultiStatementQueries::SameShard => {
+                let mut shard: Option<ShardWithPriority> = None;
+                let mut write = false;
+
+                for stmt in ast.split() {
+                    let router_context = &context.router_context;
+                    let sub = QueryParser::default().parse(RouterContext {
+                        bind: router_context.bind,
+                        query: router_context.query.clone(),
+                        cluster: router_context.cluster,
+                        parameter_hints: router_context.parameter_hints.clone(),
+                        transaction: router_context.transaction,
+                        copy_mode: router_context.copy_mode,
+                        executable: router_context.executable,
+                        two_pc: router_context.two_pc,
+                        sticky: router_context.sticky,
+                        extended: router_context.extended,
+                        ast: Some(stmt),
+                        parse_failed: false,
+                        schema: router_context.schema.clone(),
+                        client_request: router_context.client_request,
+                        last_route: router_context.last_route,
+                        connected_shard: router_context.connected_shard,
+                        dry_run: router_context.dry_run,
+                    })?;
+
+                    let Command::Query(route) = sub else {
+                        continue;
+                    };
+
+                    write |= route.is_write();
+
+                    // Statements that can go anywhere follow the others.
+                    let target = route.shard_with_priority();
+                    if target.source().is_round_robin() {
+                        continue;
+                    }
+
+                    match shard {
+                        Some(ref shard) if shard.deref() != target.deref() => {
+                            return Err(Error::MultiStatementCrossShard);
+                        }
+                        Some(_) => (),
+                        None => shard = Some(target.clone()),
+                    }
+                }
+
+                if let Command::Query(route) = &mut command {
+                    if let Some(shard) = shard {
+                        route.set_shard(shard);
+                    }
+
+                    // The whole batch goes to the same server.
+                    if write {
+                        route.set_read(false);
+                    }
+                }
+
+                Ok(command)
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Fast path for point reads on clusters with only one shard.
+
+use super::*;
+
+impl QueryParser {
+    /// Read is a point lookup, e.g. `SELECT * FROM users WHERE id = $1`,
+    /// and the cluster has only one shard.
+    ///
+    /// There is no sharding key to extract and no results to merge,
+    /// so these reads skip the rest of the SELECT machinery.
+    #[cfg(feature = "new_parser")]
+    pub(super) fn single_shard_point_read(
+        &self,
+        stmt: &nodes::SelectStmt,
+        context: &QueryParserContext,
+    ) -> bool {
+        self.single_shard_fast_path(context) && Self::primary_key_lookup(stmt, context)
+    }
+
+    /// The query reads one row from one table, by its primary key.
+    #[cfg(feature = "new_parser")]
+    fn primary_key_lookup(stmt: &nodes::SelectStmt, context: &QueryParserContext) -> bool {
+        let Ok(Node::RangeVar(range_var)) = stmt.from_clause().iter().exactly_one() else {
+            return false;
+        };
+
+        let mut columns = vec![];
+        Self::equalities(stmt.where_clause(), &mut columns);
+
+        Self::unique_key(Table::from(range_var), &columns, context)
+    }
+
+    /// Columns compared to a value with `=`, e.g. `id = $1`,
+    /// that all have to match.
+    #[cfg(feature = "new_parser")]
+    fn equalities<'a>(node: Node<'a>, columns: &mut Vec<Column<'a>>) {
+        match node {
+            Node::BoolExpr(expr) if expr.boolop == nodes::BoolExprType::AND_EXPR => {
+                for arg in expr.args().iter() {
+                    Self::equalities(arg, columns);
+                }
+            }
+
+            Node::A_Expr(expr)
+                if expr.kind == nodes::A_Expr_Kind::AEXPR_OP
+                    && expr
+                        .name()
+                        .into_iter()
+                        .exactly_one()
+                        .ok()
+                        .and_then(Node::as_str)
+                        == Some("=") =>
+            {
+                for (column, value) in [(expr.lexpr(), expr.rexpr()), (expr.rexpr(), expr.lexpr())]
+                {
+                    if let Node::ColumnRef(column) = column
+                        && Value::try_from(value).is_ok()
+                        && let Ok(column) = Column::try_from(column)
+                    {
+                        columns.push(column);
+                    }
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    cfg_select! {
+        not(feature = "new_parser") => {
+            pub(super) fn single_shard_point_read(
+                &self,
+                stmt: &SelectStmt,
+                context: &QueryParserContext,
+            ) -> bool {
+                self.single_shard_fast_path(context) && Self::primary_key_lookup(stmt, context)
+            }
+
+            /// The query reads one row from one table, by its primary key.
+            fn primary_key_lookup(stmt: &SelectStmt, context: &QueryParserContext) -> bool {
+                let [PgNode { node: Some(NodeEnum::RangeVar(range_var)) }] =
+                    stmt.from_clause.as_slice()
+                else {
+                    return false;
+                };
+
+                let mut columns = vec![];
+                if let Some(where_clause) = stmt.where_clause.as_deref() {
+                    Self::equalities(where_clause, &mut columns);
+                }
+
+                Self::unique_key(Table::from(range_var), &columns, context)
+            }
+
+            /// Columns compared to a value with `=`, e.g. `id = $1`,
+            /// that all have to match.
+            fn equalities<'a>(node: &'a PgNode, columns: &mut Vec<Column<'a>>) {
+                match node.node.as_ref() {
+                    Some(NodeEnum::BoolExpr(expr)) if expr.boolop() == BoolExprType::AndExpr => {
+                        for arg in &expr.args {
+                            Self::equalities(arg, columns);
+                        }
+                    }
+
+                    Some(NodeEnum::AExpr(expr))
+                        if expr.kind() == AExprKind::AexprOp
+                            && expr.name.len() == 1
+                            && matches!(
+                                expr.name.first().and_then(|name| name.node.as_ref()),
+                                Some(NodeEnum::String(name)) if name.sval == "="
+                            ) =>
+                    {
+                        let (Some(left), Some(right)) = (expr.lexpr.as_deref(), expr.rexpr.as_deref())
+                        else {
+                            return;
+                        };
+
+                        for (column, value) in [(left, right), (right, left)] {
+                            if matches!(column.node, Some(NodeEnum::ColumnRef(_)))
+                                && Value::try_from(&value.node).is_ok()
+                                && let Ok(column) = Column::try_from(&column.node)
+                            {
+                                columns.push(column);
+                            }
+                        }
+                    }
+
+                    _ => (),
+                }
+            }
+        }
+        _ => {}
+    }
+
+    /// Every column of the table's primary key is in `columns`.
+    /// Needs the schema, so this is never true without `load_schema`.
+    fn unique_key(table: Table<'_>, columns: &[Column<'_>], context: &QueryParserContext) -> bool {
+        let Some(relation) = context.router_context.schema.table(
+            table,
+            context.router_context.cluster.user(),
+            context.router_context.parameter_hints.search_path,
+        ) else {
+            return false;
+        };
+
+        let mut primary_key = relation
+            .columns()
+            .values()
+            .filter(|column| column.is_primary_key)
+            .peekable();
+
+        primary_key.peek().is_some()
+            && primary_key.all(|key| {
+                columns.iter().any(|column| {
+                    column.name == key.column_name
+                        && column
+                            .schema
+                            .is_none_or(|schema| Some(schema) == table.schema)
+                        && column
+                            .table
+                            .is_none_or(|name| name == table.name || Some(name) == table.alias)
+                })
+            })
+    }
+
+    fn single_shard_fast_path(&self, context: &QueryParserContext) -> bool {
+        context.shards == 1
+            && !context.dry_run
+            && self.explain_recorder.is_none()
+            // Routing to analytics replicas needs the full cost estimate.
+            && context.analytics_query_cost == 0
+    }
+}
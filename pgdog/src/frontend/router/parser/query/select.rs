@@ -68,6 +68,14 @@ impl QueryParser {
 
         let writes = writes || !advisory_locks.is_empty();
 
+        if self.single_shard_point_read(stmt, context) {
+            context
+                .shards_calculator
+                .push(ShardWithPriority::new_override_only_one_shard(
+                    Shard::Direct(0),
+                ));
+        }
+
         // Early return for any direct-to-shard queries.
         if context.shards_calculator.shard().is_direct() {
             return Ok(Command::Query(
@@ -109,6 +117,26 @@ impl QueryParser {
             shards.insert(shard);
         }
 
+        // SELECT pg_advisory_lock(1234)
+        if shards.is_empty()
+            && stmt.from_clause().is_empty()
+            && let Some(shard) = advisory_locks.shard(context.shards)
+        {
+            if let Some(recorder) = self.recorder_mut() {
+                recorder.record_entry(Some(shard.clone()), "advisory lock key".to_string());
+            }
+
+            context
+                .shards_calculator
+                .push(ShardWithPriority::new_advisory_lock(shard));
+
+            return Ok(Command::Query(
+                Route::read(context.shards_calculator.shard().clone())
+                    .with_read(!writes)
+                    .with_advisory_locks(advisory_locks),
+            ));
+        }
+
         // SELECT NOW(), SELECT 1
         if shards.is_empty() && stmt.from_clause().is_empty() {
             let shard = Shard::Direct(round_robin::next() % context.shards);
@@ -162,6 +190,8 @@ impl QueryParser {
         let aggregates = Aggregate::parse(stmt, &context.router_context.schema);
         let limit = LimitClause::new(stmt, context.router_context.bind).limit_offset()?;
         let distinct = Distinct::new(stmt).distinct();
+        let analytics = context.analytics_query_cost > 0
+            && Cost::new(stmt, &aggregates).estimate() >= context.analytics_query_cost;
 
         if let Some(shard) = shard {
             debug!("direct-to-shard {}", shard);
@@ -208,6 +238,14 @@ impl QueryParser {
                         == Some(true)
                 });
 
+                // System catalogs are the same on all shards. Send them to the
+                // same shard for each client, so ORMs introspecting the database
+                // see one consistent catalog.
+                let sticky = sticky
+                    || (context.sharding_schema.tables().is_system_catalog_sticky()
+                        && !tables.is_empty()
+                        && tables.iter().all(|table| table.is_system_catalog()));
+
                 let (rr_index, explain) = if sticky
                     || context
                         .sharding_schema
@@ -252,6 +290,7 @@ impl QueryParser {
         Ok(Command::Query(
             query
                 .with_read(!writes)
+                .with_analytics(analytics)
                 .with_omnisharded(omnisharded)
                 .with_advisory_locks(advisory_locks),
         ))
@@ -294,6 +333,12 @@ impl QueryParser {
 
                 let writes = writes || !advisory_locks.is_empty();
 
+                if self.single_shard_point_read(stmt_old, context) {
+                    context
+                        .shards_calculator
+                        .push(ShardWithPriority::new_override_only_one_shard(Shard::Direct(0)));
+                }
+
                 // Early return for any direct-to-shard queries.
                 if context.shards_calculator.shard().is_direct() {
                     return Ok(Command::Query(
@@ -335,6 +380,26 @@ impl QueryParser {
                     shards.insert(shard);
                 }
 
+                // SELECT pg_advisory_lock(1234)
+                if shards.is_empty()
+                    && stmt_old.from_clause.is_empty()
+                    && let Some(shard) = advisory_locks.shard(context.shards)
+                {
+                    if let Some(recorder) = self.recorder_mut() {
+                        recorder.record_entry(Some(shard.clone()), "advisory lock key".to_string());
+                    }
+
+                    context
+                        .shards_calculator
+                        .push(ShardWithPriority::new_advisory_lock(shard));
+
+                    return Ok(Command::Query(
+                        Route::read(context.shards_calculator.shard().clone())
+                            .with_read(!writes)
+                            .with_advisory_locks(advisory_locks),
+                    ));
+                }
+
                 // SELECT NOW(), SELECT 1
                 if shards.is_empty() && stmt_old.from_clause.is_empty() {
                     let shard = Shard::Direct(round_robin::next() % context.shards);
@@ -387,6 +452,8 @@ impl QueryParser {
                 let aggregates = Aggregate::parse(stmt, &context.router_context.schema);
                 let limit = LimitClause::new(stmt, context.router_context.bind).limit_offset()?;
                 let distinct = Distinct::new(stmt_old).distinct()?;
+                let analytics = context.analytics_query_cost > 0
+                    && Cost::new(stmt, &aggregates).estimate() >= context.analytics_query_cost;
 
                 if let Some(shard) = shard {
                     debug!("direct-to-shard {}", shard);
@@ -433,6 +500,14 @@ impl QueryParser {
                                 == Some(true)
                         });
 
+                        // System catalogs are the same on all shards. Send them to the
+                        // same shard for each client, so ORMs introspecting the database
+                        // see one consistent catalog.
+                        let sticky = sticky
+                            || (context.sharding_schema.tables().is_system_catalog_sticky()
+                                && !tables.is_empty()
+                                && tables.iter().all(|table| table.is_system_catalog()));
+
                         let (rr_index, explain) = if sticky
                             || context
                                 .sharding_schema
@@ -477,6 +552,7 @@ impl QueryParser {
                 Ok(Command::Query(
                     query
                         .with_read(!writes)
+                        .with_analytics(analytics)
                         .with_omnisharded(omnisharded)
                         .with_advisory_locks(advisory_locks),
                 ))
@@ -1,8 +1,23 @@
 use super::*;
+use crate::{config::config, net::parameter::is_tracked};
 #[cfg(feature = "new_parser")]
 use pg_raw_parse::{Node, nodes, nodes::VariableSetKind::*};
 
 impl QueryParser {
+    /// Make sure the client is allowed to change these parameters
+    /// on the server. Resetting a parameter is always allowed.
+    pub(super) fn check_set_params(params: &[SetParam]) -> Result<(), Error> {
+        let config = config();
+
+        for param in params.iter().filter(|param| param.value.is_some()) {
+            if is_tracked(&param.name) && !config.config.general.parameter_allowed(&param.name) {
+                return Err(Error::ParameterNotAllowed(param.name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Handle the SET command.
     ///
     /// We allow setting shard/sharding key manually outside
@@ -15,6 +15,10 @@ impl QueryParser {
                 value: context.shards.to_string(),
             }),
             Some("pgdog.unique_id") => Ok(Command::UniqueId),
+            Some("pgdog.last_route") => Ok(Command::InternalField {
+                name: "last_route".into(),
+                value: context.router_context.last_route.unwrap_or_default().into(),
+            }),
             _ => {
                 context
                     .shards_calculator
@@ -41,6 +45,14 @@ impl QueryParser {
                         value: context.shards.to_string(),
                     }),
                     "pgdog.unique_id" => Ok(Command::UniqueId),
+                    "pgdog.last_route" => Ok(Command::InternalField {
+                        name: "last_route".into(),
+                        value: context
+                            .router_context
+                            .last_route
+                            .unwrap_or_default()
+                            .into(),
+                    }),
                     _ => {
                         context
                             .shards_calculator
@@ -62,6 +74,7 @@ impl QueryParser {
 mod test_show {
     use crate::backend::Cluster;
     use crate::config::config;
+    use crate::frontend::Command;
     use crate::frontend::client::Sticky;
     use crate::frontend::router::QueryParser;
     use crate::frontend::router::parser::{AstContext, Cache, Shard};
@@ -107,4 +120,36 @@ mod test_show {
         // Round robin shard routing
         assert!(second_shard != first_shard);
     }
+
+    #[test]
+    fn show_last_route() {
+        let c = Cluster::new_test(&config());
+        let params = Parameters::default();
+        let ctx = AstContext::from_cluster(&c, &params);
+
+        let query = "SHOW pgdog.last_route";
+        let buffered = BufferedQuery::Query(Query::new(query));
+        let ast = Cache::get()
+            .query(&buffered, &ctx, &mut PreparedStatements::default())
+            .unwrap();
+        let mut buffer = ClientRequest::from(vec![Query::new(query).into()]);
+        buffer.ast = Some(ast);
+
+        for (last_route, expected) in [
+            (None, ""),
+            (Some("shard=1, role=primary"), "shard=1, role=primary"),
+        ] {
+            let mut context =
+                RouterContext::new(&buffer, &c, &params, None, Sticky::new()).unwrap();
+            context.last_route = last_route;
+
+            match QueryParser::default().parse(context).unwrap() {
+                Command::InternalField { name, value } => {
+                    assert_eq!(name, "last_route");
+                    assert_eq!(value, expected);
+                }
+                command => panic!("expected InternalField, got {command:?}"),
+            }
+        }
+    }
 }
@@ -29,6 +29,10 @@ pub mod test_dml;
 pub mod test_explain;
 pub mod test_functions;
 pub mod test_insert;
+pub mod test_multi_statement;
+pub mod test_multi_tenant;
+pub mod test_parse_failures;
+pub mod test_point_read;
 pub mod test_prefer_primary;
 pub mod test_rr;
 pub mod test_schema_sharding;
@@ -40,6 +44,7 @@ pub mod test_sharding;
 pub mod test_special;
 pub mod test_subqueries;
 pub mod test_transaction;
+pub mod test_unknown_statements;
 
 fn parse_query(query: &str) -> Command {
     let mut query_parser = QueryParser::default();
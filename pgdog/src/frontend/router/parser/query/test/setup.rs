@@ -1,10 +1,12 @@
 use std::ops::Deref;
 
-use pgdog_config::{ConfigAndUsers, ReadWriteSplit};
+use pgdog_config::{
+    ConfigAndUsers, MultiStatementQueries, ParseFailures, ReadWriteSplit, UnknownStatements,
+};
 
 use crate::{
-    backend::Cluster,
-    config::{self, ReadWriteStrategy, config},
+    backend::{Cluster, Schema},
+    config::{self, MultiTenant, ReadWriteStrategy, config},
     frontend::{
         ClientRequest, Command, PreparedStatements, RouterContext,
         client::{Sticky, TransactionType},
@@ -27,6 +29,7 @@ pub(crate) struct QueryParserTest {
     prepared: PreparedStatements,
     pub(crate) parser: QueryParser,
     last_parse: Option<String>,
+    connected_shard: Option<usize>,
 }
 
 impl QueryParserTest {
@@ -47,6 +50,7 @@ impl QueryParserTest {
             parser: QueryParser::default(),
             prepared: PreparedStatements::new(),
             last_parse: None,
+            connected_shard: None,
         }
     }
 
@@ -62,9 +66,18 @@ impl QueryParserTest {
             parser: QueryParser::default(),
             prepared: PreparedStatements::new(),
             last_parse: None,
+            connected_shard: None,
         }
     }
 
+    /// Create a test using an existing cluster.
+    pub(crate) fn new_with_cluster(cluster: Cluster) -> Self {
+        let mut me = Self::new_with_config(&config());
+        me.cluster = cluster;
+
+        me
+    }
+
     pub(crate) fn new_single_primary(config: &ConfigAndUsers) -> Self {
         let mut me = Self::new_with_config(config);
         me.cluster = Cluster::new_test_single_primary(config);
@@ -96,6 +109,12 @@ impl QueryParserTest {
         self
     }
 
+    /// Set the shard the client is connected to.
+    pub(crate) fn with_connected_shard(mut self, shard: usize) -> Self {
+        self.connected_shard = Some(shard);
+        self
+    }
+
     /// Set the read/write strategy on the cluster.
     pub(crate) fn with_read_write_strategy(mut self, strategy: ReadWriteStrategy) -> Self {
         self.cluster.set_read_write_strategy(strategy);
@@ -108,6 +127,34 @@ impl QueryParserTest {
         self
     }
 
+    /// Set how queries with multiple statements are routed.
+    pub(crate) fn with_multi_statement_queries(
+        mut self,
+        multi_statement_queries: MultiStatementQueries,
+    ) -> Self {
+        self.cluster
+            .set_multi_statement_queries(multi_statement_queries);
+        self
+    }
+
+    /// Set how statements we don't recognize are routed.
+    pub(crate) fn with_unknown_statements(mut self, unknown_statements: UnknownStatements) -> Self {
+        self.cluster.set_unknown_statements(unknown_statements);
+        self
+    }
+
+    /// Set how queries we fail to parse are routed.
+    pub(crate) fn with_parse_failures(mut self, parse_failures: ParseFailures) -> Self {
+        self.cluster.set_parse_failures(parse_failures);
+        self
+    }
+
+    /// Enable multi-tenant checks, using the given database schema.
+    pub(crate) fn with_multi_tenant(mut self, multi_tenant: MultiTenant, schema: Schema) -> Self {
+        self.cluster.set_multi_tenant(multi_tenant, schema);
+        self
+    }
+
     /// Enable expanded explain for this test.
     pub(crate) fn with_expanded_explain(mut self) -> Self {
         let mut updated = config().deref().clone();
@@ -173,14 +220,21 @@ impl QueryParserTest {
             // Some requests (like Close) don't have a query
             if let Ok(Some(buffered_query)) = request.query() {
                 let ctx = AstContext::from_cluster(&self.cluster, &self.params);
-                let ast = Cache::get()
-                    .query(&buffered_query, &ctx, &mut self.prepared)
-                    .unwrap();
-                request.ast = Some(ast);
+                match Cache::get().query(&buffered_query, &ctx, &mut self.prepared) {
+                    Ok(ast) => request.ast = Some(ast),
+                    // Same as the query engine.
+                    Err(err)
+                        if err.is_parse()
+                            && self.cluster.parse_failures() != ParseFailures::Reject =>
+                    {
+                        request.parse_failed = true
+                    }
+                    Err(err) => return Err(err),
+                }
             }
         }
 
-        let router_ctx = RouterContext::new(
+        let mut router_ctx = RouterContext::new(
             &request,
             &self.cluster,
             &self.params,
@@ -188,6 +242,7 @@ impl QueryParserTest {
             self.sticky,
         )
         .unwrap();
+        router_ctx.connected_shard = self.connected_shard;
 
         let command = self.parser.parse(router_ctx)?;
         Ok(command.clone())
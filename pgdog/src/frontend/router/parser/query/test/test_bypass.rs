@@ -3,11 +3,15 @@
 //!
 //! QueryParser::query_parser_bypass.
 //!
-use pgdog_config::{QueryParserLevel, ReadWriteSplit};
+use pgdog_config::{ConfigAndUsers, Database, QueryParserLevel, ReadWriteSplit, Role, User};
 
 use crate::{
+    backend::databases::from_config,
     config::config,
-    frontend::router::parser::{Error, Shard},
+    frontend::{
+        ClientRequest,
+        router::parser::{Error, Shard},
+    },
     net::Query,
 };
 
@@ -167,3 +171,49 @@ async fn test_sharded_no_hints() {
         assert!(matches!(result, Error::QueryParserRequired));
     }
 }
+
+#[tokio::test]
+async fn test_user_query_parser_off() {
+    let mut config = ConfigAndUsers::default();
+    config.config.general.query_parser = QueryParserLevel::Auto;
+    config.config.databases = vec![
+        Database {
+            name: "pgdog".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Primary,
+            ..Default::default()
+        },
+        Database {
+            name: "pgdog".into(),
+            host: "127.0.0.1".into(),
+            role: Role::Replica,
+            ..Default::default()
+        },
+    ];
+    config.users.users = vec![
+        User {
+            query_parser: Some(QueryParserLevel::Off),
+            ..User::new("no_parser", "pgdog", "pgdog")
+        },
+        User::new("parser", "pgdog", "pgdog"),
+    ];
+
+    let databases = from_config(&config);
+    let request = ClientRequest::from(vec![Query::new("SELECT 1").into()]);
+
+    // Replicas make the parser necessary for read/write splitting.
+    let cluster = databases.cluster(("parser", "pgdog")).unwrap();
+    assert!(cluster.use_query_parser(&request));
+
+    let cluster = databases.cluster(("no_parser", "pgdog")).unwrap();
+    assert_eq!(cluster.query_parser(), QueryParserLevel::Off);
+    assert!(!cluster.use_query_parser(&request));
+
+    let mut test = QueryParserTest::new_with_cluster(cluster);
+
+    for query in QUERIES {
+        let result = test.try_execute(vec![Query::new(query).into()]).unwrap();
+        assert!(result.route().is_write());
+        assert_eq!(result.route().shard(), &Shard::Direct(0))
+    }
+}
@@ -24,6 +24,26 @@ fn test_comment_pgdog_shard() {
     assert_eq!(command.route().shard(), &Shard::Direct(1234));
 }
 
+#[test]
+fn test_comment_tag_does_not_affect_routing() {
+    let mut test = QueryParserTest::new();
+
+    let untagged = test.execute(vec![
+        Query::new("/* pgdog_shard: 1 */ SELECT * FROM sharded").into(),
+    ]);
+    let tagged = test.execute(vec![
+        Query::new("/* pgdog_shard: 1 tag: checkout-flow */ SELECT * FROM sharded").into(),
+    ]);
+
+    assert_eq!(tagged.route().shard(), untagged.route().shard());
+    assert_eq!(tagged.route().is_read(), untagged.route().is_read());
+
+    let tagged = test.execute(vec![Query::new("/* tag: checkout-flow */ SELECT 1").into()]);
+    let untagged = test.execute(vec![Query::new("SELECT 1").into()]);
+    assert_eq!(tagged.route().shard(), untagged.route().shard());
+    assert_eq!(tagged.route().is_read(), untagged.route().is_read());
+}
+
 #[test]
 fn test_comment_pgdog_shard_extended() {
     let mut test = QueryParserTest::new();
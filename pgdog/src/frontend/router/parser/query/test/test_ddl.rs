@@ -66,6 +66,7 @@ fn test_truncate() {
     let command = test.execute(vec![Query::new("TRUNCATE TABLE sharded").into()]);
 
     assert!(command.route().is_write());
+    assert!(command.route().is_truncate());
     assert_eq!(command.route().shard(), &Shard::All);
 }
 
@@ -86,3 +86,24 @@ fn test_delete_using_join() {
     assert!(command.route().is_write());
     assert_eq!(command.route().shard(), &Shard::All);
 }
+
+#[test]
+fn test_delete_using_with_key_in_join() {
+    let mut test = QueryParserTest::new();
+
+    let command = test.execute(vec![
+        Parse::named(
+            "__test_delete_using",
+            "DELETE FROM sharded \
+             USING other_table o JOIN sharded s ON s.id = o.sharded_id AND s.id = $1 \
+             WHERE sharded.id = s.id",
+        )
+        .into(),
+        Bind::new_params("__test_delete_using", &[Parameter::new(b"5")]).into(),
+        Execute::new().into(),
+        Sync.into(),
+    ]);
+
+    assert!(command.route().is_write());
+    assert!(matches!(command.route().shard(), Shard::Direct(_)));
+}
@@ -1,4 +1,6 @@
-use crate::frontend::router::parser::Shard;
+use std::ops::Deref;
+
+use crate::frontend::router::parser::{Error, Shard};
 use crate::net::messages::Parameter;
 
 use super::setup::*;
@@ -86,3 +88,67 @@ fn test_update_is_not_distinct_from_routes_to_shard() {
     assert!(matches!(command.route().shard(), Shard::Direct(_)));
     assert!(command.route().is_write());
 }
+
+#[test]
+fn test_update_from_with_key_in_join() {
+    let mut test = QueryParserTest::new();
+    let command = test.execute(vec![
+        Parse::named(
+            "__test_update_from",
+            "UPDATE sharded SET email = o.email \
+             FROM other_table o JOIN sharded s ON s.id = o.sharded_id AND s.id = $1 \
+             WHERE sharded.id = s.id",
+        )
+        .into(),
+        Bind::new_params("__test_update_from", &[Parameter::new(b"1")]).into(),
+        Execute::new().into(),
+        Sync.into(),
+    ]);
+    assert!(matches!(command.route().shard(), Shard::Direct(_)));
+    assert!(command.route().is_write());
+}
+
+#[test]
+fn test_require_sharding_key() {
+    let mut config = crate::config::config().deref().clone();
+    config.config.general.require_sharding_key = true;
+
+    for q in [
+        "INSERT INTO sharded (email) VALUES ('test@test.com')",
+        "UPDATE sharded SET email = 'test@test.com' WHERE email = 'test'",
+        "DELETE FROM sharded WHERE email = 'test'",
+    ] {
+        let mut test = QueryParserTest::new_with_config(&config);
+        let result = test.try_execute(vec![Query::new(q).into()]);
+        assert!(
+            matches!(result, Err(Error::ShardingKeyRequired(_))),
+            "{}: {:?}",
+            q,
+            result
+        );
+    }
+
+    // With the sharding key.
+    let mut test = QueryParserTest::new_with_config(&config);
+    let command = test.execute(vec![
+        Query::new("INSERT INTO sharded (id, email) VALUES (1, 'test@test.com')").into(),
+    ]);
+    assert!(matches!(command.route().shard(), Shard::Direct(_)));
+
+    // Tables that aren't sharded aren't checked.
+    let mut test = QueryParserTest::new_with_config(&config);
+    let command = test.execute(vec![
+        Query::new("INSERT INTO sharded_omni (id, value) VALUES (1, 'test')").into(),
+    ]);
+    assert_eq!(command.route().shard(), &Shard::All);
+}
+
+#[test]
+fn test_require_sharding_key_disabled() {
+    let mut test = QueryParserTest::new();
+    let command = test.execute(vec![
+        Query::new("INSERT INTO sharded (email) VALUES ('test@test.com')").into(),
+    ]);
+    assert!(command.route().is_write());
+    assert_eq!(command.route().shard(), &Shard::All);
+}
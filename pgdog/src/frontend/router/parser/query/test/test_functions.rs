@@ -1,5 +1,13 @@
 use bytes::Bytes;
 
+use crate::frontend::router::{
+    parser::{
+        Shard,
+        route::{OverrideReason, ShardSource},
+    },
+    sharding::bigint,
+};
+
 use super::setup::*;
 
 #[test]
@@ -12,6 +20,39 @@ fn test_write_function_advisory_lock() {
     assert!(command.route().is_lock_session());
 }
 
+#[test]
+fn test_advisory_lock_routed_by_key() {
+    let mut test = QueryParserTest::new();
+
+    for key in [123_i64, 456, 789] {
+        let shard = Shard::Direct(bigint(key) as usize % 2);
+
+        for query in [
+            format!("SELECT pg_advisory_lock({})", key),
+            format!("SELECT pg_try_advisory_lock({})", key),
+            format!("SELECT pg_advisory_unlock({})", key),
+        ] {
+            // Same key lands on the same shard every time.
+            for _ in 0..3 {
+                let command = test.execute(vec![Query::new(query.as_str()).into()]);
+                assert_eq!(command.route().shard(), &shard, "{}", query);
+                assert_eq!(
+                    command.route().shard_with_priority().source(),
+                    &ShardSource::AdvisoryLock
+                );
+            }
+        }
+    }
+
+    // Locks can be on any shard.
+    let command = test.execute(vec![Query::new("SELECT pg_advisory_unlock_all()").into()]);
+    assert_eq!(command.route().shard(), &Shard::All);
+    assert_eq!(
+        command.route().shard_with_priority().source(),
+        &ShardSource::Override(OverrideReason::CrossShardFunction)
+    );
+}
+
 #[test]
 fn test_write_functions_prepared() {
     let mut test = QueryParserTest::new();
@@ -0,0 +1,98 @@
+use pgdog_config::MultiStatementQueries;
+
+use crate::frontend::Command;
+use crate::frontend::router::parser::{Error, Shard};
+
+use super::setup::*;
+
+fn test(policy: MultiStatementQueries) -> QueryParserTest {
+    QueryParserTest::new().with_multi_statement_queries(policy)
+}
+
+/// Find two sharding keys that map to different shards.
+fn keys_on_different_shards() -> (i64, i64) {
+    let mut test = test(MultiStatementQueries::FirstStatement);
+    let shard = |test: &mut QueryParserTest, id: i64| {
+        test.execute(vec![
+            Query::new(format!("INSERT INTO sharded (id) VALUES ({id})")).into(),
+        ])
+        .route()
+        .shard()
+        .clone()
+    };
+
+    let first = shard(&mut test, 1);
+    let other = (2..100)
+        .find(|id| shard(&mut test, *id) != first)
+        .expect("keys on different shards");
+
+    (1, other)
+}
+
+#[test]
+fn test_multi_statement_first_statement() {
+    let (a, b) = keys_on_different_shards();
+    let mut test = test(MultiStatementQueries::FirstStatement);
+
+    let command = test.execute(vec![
+        Query::new(format!(
+            "INSERT INTO sharded (id) VALUES ({a}); INSERT INTO sharded (id) VALUES ({b})"
+        ))
+        .into(),
+    ]);
+
+    assert!(matches!(command.route().shard(), Shard::Direct(_)));
+}
+
+#[test]
+fn test_multi_statement_reject() {
+    let mut test = test(MultiStatementQueries::Reject);
+
+    let result = test.try_execute(vec![
+        Query::new("SELECT 1; INSERT INTO sharded (id) VALUES (1)").into(),
+    ]);
+    assert!(matches!(result, Err(Error::MultiStatementRejected)));
+
+    // Single statements are unaffected.
+    let command = test.execute(vec![Query::new("SELECT 1").into()]);
+    assert!(matches!(command, Command::Query(_)));
+}
+
+#[test]
+fn test_multi_statement_same_shard() {
+    let (a, _) = keys_on_different_shards();
+
+    let expected = test(MultiStatementQueries::FirstStatement)
+        .execute(vec![
+            Query::new(format!("INSERT INTO sharded (id) VALUES ({a})")).into(),
+        ])
+        .route()
+        .shard()
+        .clone();
+
+    let mut test = test(MultiStatementQueries::SameShard);
+    let command = test.execute(vec![
+        Query::new(format!(
+            "SELECT 1; INSERT INTO sharded (id) VALUES ({a}); SELECT * FROM sharded WHERE id = {a}"
+        ))
+        .into(),
+    ]);
+
+    let route = command.route();
+    assert_eq!(route.shard(), &expected);
+    assert!(route.is_write());
+}
+
+#[test]
+fn test_multi_statement_cross_shard() {
+    let (a, b) = keys_on_different_shards();
+    let mut test = test(MultiStatementQueries::SameShard);
+
+    let result = test.try_execute(vec![
+        Query::new(format!(
+            "SELECT 1; INSERT INTO sharded (id) VALUES ({a}); INSERT INTO sharded (id) VALUES ({b})"
+        ))
+        .into(),
+    ]);
+    assert!(matches!(result, Err(Error::MultiStatementCrossShard)));
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::backend::schema::{Relation, Schema, columns::StatsColumn as Column};
+use crate::config::MultiTenant;
+use crate::frontend::router::parser::Error;
+
+use super::setup::*;
+
+fn schema() -> Schema {
+    let mut columns = IndexMap::new();
+    columns.insert(
+        "tenant_id".to_string(),
+        Column {
+            table_catalog: "catalog".into(),
+            table_schema: "public".into(),
+            table_name: "accounts".into(),
+            column_name: "tenant_id".into(),
+            column_default: String::new(),
+            is_nullable: false,
+            data_type: "bigint".into(),
+            ordinal_position: 1,
+            is_primary_key: false,
+            foreign_keys: Vec::new(),
+        }
+        .into(),
+    );
+
+    let relation = Relation::test_table("public", "accounts", columns);
+    let relations = HashMap::from([(("public".into(), "accounts".into()), relation)]);
+
+    Schema::from_parts(vec!["$user".into(), "public".into()], relations)
+}
+
+fn multi_tenant(filter: bool) -> MultiTenant {
+    MultiTenant {
+        column: "tenant_id".into(),
+        filter,
+    }
+}
+
+#[test]
+fn test_tenant_filter_added_to_read() {
+    let mut test = QueryParserTest::new()
+        .with_multi_tenant(multi_tenant(true), schema())
+        .with_param("pgdog.sharding_key", "1");
+
+    let command = test.execute(vec![Query::new("SELECT * FROM accounts").into()]);
+    assert_eq!(
+        command.route().tenant_filter(),
+        Some("SELECT * FROM accounts WHERE tenant_id = '1'")
+    );
+    assert!(command.route().shard().is_direct());
+
+    let command = test.execute(vec![
+        Query::new("SELECT * FROM accounts WHERE name = 'test' OR name IS NULL").into(),
+    ]);
+    assert_eq!(
+        command.route().tenant_filter(),
+        Some("SELECT * FROM accounts WHERE tenant_id = '1' AND (name = 'test' OR name IS NULL)")
+    );
+    assert!(command.route().shard().is_direct());
+
+    // Already filtered by tenant.
+    let command = test.execute(vec![
+        Query::new("SELECT * FROM accounts WHERE tenant_id = 1").into(),
+    ]);
+    assert!(command.route().tenant_filter().is_none());
+
+    // Only reads are rewritten.
+    let result = test.try_execute(vec![Query::new("UPDATE accounts SET name = 'test'").into()]);
+    assert!(matches!(result, Err(Error::MultiTenantId)));
+}
+
+#[test]
+fn test_tenant_filter_requires_tenant() {
+    let mut test = QueryParserTest::new().with_multi_tenant(multi_tenant(true), schema());
+
+    let result = test.try_execute(vec![Query::new("SELECT * FROM accounts").into()]);
+    assert!(matches!(result, Err(Error::MultiTenantId)));
+}
+
+#[test]
+fn test_tenant_filter_disabled() {
+    let mut test = QueryParserTest::new()
+        .with_multi_tenant(multi_tenant(false), schema())
+        .with_param("pgdog.sharding_key", "1");
+
+    let result = test.try_execute(vec![Query::new("SELECT * FROM accounts").into()]);
+    assert!(matches!(result, Err(Error::MultiTenantId)));
+}
+
+#[test]
+fn test_tenant_filter_extended_protocol_rejected() {
+    let mut test = QueryParserTest::new()
+        .with_multi_tenant(multi_tenant(true), schema())
+        .with_param("pgdog.sharding_key", "1");
+
+    let result = test.try_execute(vec![
+        Parse::new_anonymous("SELECT * FROM accounts").into(),
+        Bind::new_statement("").into(),
+        Execute::new().into(),
+        Sync.into(),
+    ]);
+    assert!(matches!(result, Err(Error::MultiTenantId)));
+}
@@ -0,0 +1,80 @@
+use pgdog_config::{ParseFailures, UnknownStatements};
+
+use crate::frontend::router::parser::{
+    Cache, Error, Shard,
+    route::{RoundRobinReason, ShardSource},
+};
+
+use super::setup::*;
+
+/// Stands in for syntax added in a newer version of PostgreSQL
+/// than the parser supports.
+const UNPARSABLE: &str = "SELEKT * FROM sharded WHERE id = 1";
+
+fn test(policy: ParseFailures) -> QueryParserTest {
+    QueryParserTest::new().with_parse_failures(policy)
+}
+
+#[test]
+fn test_parse_failure_reject() {
+    let mut test = test(ParseFailures::Reject);
+
+    let failures = Cache::stats().0.parse_failures;
+    let result = test.try_execute(vec![Query::new(UNPARSABLE).into()]);
+
+    assert!(result.is_err_and(|err| err.is_parse()));
+    assert!(Cache::stats().0.parse_failures > failures);
+}
+
+#[test]
+fn test_parse_failure_primary() {
+    let mut test = test(ParseFailures::Primary);
+
+    let failures = Cache::stats().0.parse_failures;
+    let command = test.execute(vec![Query::new(UNPARSABLE).into()]);
+
+    assert_eq!(command.route().shard(), &Shard::Direct(0));
+    assert!(command.route().is_write());
+    assert!(Cache::stats().0.parse_failures > failures);
+}
+
+#[test]
+fn test_parse_failure_round_robin() {
+    let mut test = test(ParseFailures::RoundRobin);
+
+    let mut shards = vec![];
+    for _ in 0..4 {
+        let command = test.execute(vec![Query::new(UNPARSABLE).into()]);
+
+        assert!(command.route().is_write());
+        assert_eq!(
+            command.route().shard_with_priority().source(),
+            &ShardSource::RoundRobin(RoundRobinReason::ParseFailure)
+        );
+        match command.route().shard() {
+            Shard::Direct(shard) => shards.push(*shard),
+            shard => panic!("expected a direct shard, got {:?}", shard),
+        }
+    }
+
+    // Round robin is global, so other tests can move it too.
+    assert!(shards.iter().all(|shard| *shard < 2));
+}
+
+#[test]
+fn test_parse_failure_fallback_ignores_valid_queries() {
+    let mut test = test(ParseFailures::Primary);
+
+    let command = test.execute(vec![
+        Query::new("SELECT * FROM sharded WHERE id = 1").into(),
+    ]);
+    assert!(command.route().is_read());
+    assert!(command.route().shard().is_direct());
+
+    // Other errors are still returned.
+    let mut test = test.with_unknown_statements(UnknownStatements::Reject);
+    let result = test.try_execute(vec![
+        Query::new("ALTER DATABASE pgdog REFRESH COLLATION VERSION").into(),
+    ]);
+    assert!(matches!(result, Err(Error::UnknownStatementRejected)));
+}
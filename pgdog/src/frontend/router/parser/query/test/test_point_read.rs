@@ -0,0 +1,161 @@
+//! Point reads on single-shard clusters skip most of the SELECT routing.
+//!
+//! QueryParser::single_shard_point_read.
+//!
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::backend::schema::{Relation, Schema, columns::StatsColumn};
+use crate::config::config;
+use crate::frontend::Command;
+use crate::frontend::router::parser::Shard;
+use crate::frontend::router::parser::route::{OverrideReason, ShardSource};
+use crate::net::messages::Parameter;
+
+use super::setup::*;
+
+/// Point reads, by primary key.
+const POINT_READS: &[&str] = &[
+    "SELECT * FROM sharded WHERE id = $1",
+    "SELECT id, email FROM users WHERE id = $1 LIMIT 1",
+    "SELECT * FROM sharded WHERE id = $1 FOR UPDATE",
+    "SELECT * FROM sharded s WHERE $1 = s.id AND s.value IS NOT NULL ORDER BY id",
+];
+
+/// Not point reads, routed the usual way.
+const QUERIES: &[&str] = &[
+    "SELECT count(*) FROM sharded",
+    "SELECT * FROM sharded WHERE value = $1",
+    "SELECT * FROM sharded WHERE id = $1 OR value = 'test'",
+    "SELECT * FROM sharded WHERE id > $1",
+    "SELECT * FROM sharded s JOIN users u ON u.id = s.id WHERE s.id = $1",
+];
+
+fn table(name: &str, columns: &[(&str, bool)]) -> Relation {
+    let columns = columns
+        .iter()
+        .enumerate()
+        .map(|(position, (column, primary_key))| {
+            (
+                column.to_string(),
+                StatsColumn {
+                    table_catalog: "pgdog".into(),
+                    table_schema: "public".into(),
+                    table_name: name.into(),
+                    column_name: column.to_string(),
+                    column_default: String::new(),
+                    is_nullable: !primary_key,
+                    data_type: "bigint".into(),
+                    ordinal_position: position as i32 + 1,
+                    is_primary_key: *primary_key,
+                    foreign_keys: Vec::new(),
+                }
+                .into(),
+            )
+        })
+        .collect::<IndexMap<_, _>>();
+
+    Relation::test_table("public", name, columns)
+}
+
+fn schema() -> Schema {
+    let relations = HashMap::from([
+        (
+            ("public".into(), "sharded".into()),
+            table("sharded", &[("id", true), ("value", false)]),
+        ),
+        (
+            ("public".into(), "users".into()),
+            table("users", &[("id", true), ("email", false)]),
+        ),
+    ]);
+
+    Schema::from_parts(vec!["$user".into(), "public".into()], relations)
+}
+
+/// Single-shard cluster with (`fast_path`) or without the fast path.
+///
+/// The fast path is disabled when routing to analytics replicas, since it
+/// needs the cost of every query.
+fn setup(fast_path: bool) -> QueryParserTest {
+    let mut config = (*config()).clone();
+    config.config.general.analytics_query_cost = if fast_path { 0 } else { usize::MAX };
+    let test = QueryParserTest::new_single_shard(&config);
+    test.cluster().set_schema(schema());
+    test
+}
+
+fn fast_path(command: &Command) -> bool {
+    command.route().shard_with_priority().source()
+        == &ShardSource::Override(OverrideReason::OnlyOneShard)
+}
+
+fn route(test: &mut QueryParserTest, query: &str) -> Command {
+    test.execute(vec![
+        Parse::new_anonymous(query).into(),
+        Bind::new_params("", &[Parameter::new(b"1")]).into(),
+        Execute::new().into(),
+        Sync.into(),
+    ])
+}
+
+#[test]
+fn test_point_read_fast_path_same_route() {
+    let mut fast = setup(true);
+    let mut normal = setup(false);
+
+    for query in POINT_READS.iter().chain(QUERIES) {
+        let expected = route(&mut normal, query);
+        let actual = route(&mut fast, query);
+
+        assert_eq!(actual.route().shard(), &Shard::Direct(0), "{}", query);
+        assert_eq!(
+            actual.route().shard(),
+            expected.route().shard(),
+            "{}",
+            query
+        );
+        assert_eq!(
+            actual.route().is_read(),
+            expected.route().is_read(),
+            "{}",
+            query
+        );
+        assert!(!actual.route().should_buffer(), "{}", query);
+        assert!(!actual.route().is_analytics(), "{}", query);
+    }
+
+    assert!(route(&mut fast, POINT_READS[0]).route().is_read());
+    assert!(route(&mut fast, POINT_READS[2]).route().is_write());
+}
+
+#[test]
+fn test_point_read_fast_path_primary_key() {
+    let mut fast = setup(true);
+    let mut normal = setup(false);
+
+    for query in POINT_READS {
+        assert!(fast_path(&route(&mut fast, query)), "{}", query);
+    }
+
+    for query in QUERIES {
+        assert_eq!(
+            route(&mut fast, query).route().shard_with_priority(),
+            route(&mut normal, query).route().shard_with_priority(),
+            "{}",
+            query
+        );
+    }
+
+    // Without the schema, we don't know the primary key.
+    let mut no_schema = QueryParserTest::new_single_shard(&config());
+    assert_eq!(
+        route(&mut no_schema, POINT_READS[0])
+            .route()
+            .shard_with_priority(),
+        route(&mut normal, POINT_READS[0])
+            .route()
+            .shard_with_priority(),
+    );
+}
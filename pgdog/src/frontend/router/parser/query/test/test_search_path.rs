@@ -1,5 +1,10 @@
+use std::ops::Deref;
+
+use pgdog_config::SearchPathConflict;
+
+use crate::frontend::Command;
 use crate::frontend::router::parser::route::RoundRobinReason;
-use crate::frontend::router::parser::{Shard, route::ShardSource};
+use crate::frontend::router::parser::{Error, Shard, route::ShardSource};
 use crate::net::parameter::ParameterValue;
 
 use super::setup::{QueryParserTest, *};
@@ -303,3 +308,45 @@ fn test_search_path_driven_flag_not_set_for_no_sharded_schema() {
 
     assert!(!command.route().is_search_path_driven());
 }
+
+// --- search_path_conflict = "error" ---
+
+#[test]
+fn test_search_path_conflict_error_only_for_routed_queries() {
+    let mut config = crate::config::config().deref().clone();
+    config.config.general.search_path_conflict = SearchPathConflict::Error;
+    let mut test = QueryParserTest::new_with_config(&config).with_param(
+        "search_path",
+        ParameterValue::Tuple(vec!["shard_0".into(), "shard_1".into()]),
+    );
+
+    let result = test.try_execute(vec![Query::new("SELECT * FROM users WHERE id = 1").into()]);
+    assert!(
+        matches!(result, Err(Error::SearchPathConflict(ref schemas)) if schemas == "shard_0,shard_1"),
+        "{result:?}"
+    );
+
+    // The client can still fix its search_path.
+    let command = test.execute(vec![Query::new("RESET search_path").into()]);
+    assert!(
+        matches!(command, Command::Set { .. }),
+        "expected Command::Set, got {command:#?}",
+    );
+    let command = test.execute(vec![Query::new("SET search_path TO shard_1").into()]);
+    assert!(
+        matches!(command, Command::Set { .. }),
+        "expected Command::Set, got {command:#?}",
+    );
+
+    // Transaction control doesn't depend on search_path.
+    let command = test.execute(vec![Query::new("BEGIN").into()]);
+    assert!(
+        matches!(command, Command::StartTransaction { .. }),
+        "expected Command::StartTransaction, got {command:#?}",
+    );
+    let command = test.execute(vec![Query::new("ROLLBACK").into()]);
+    assert!(
+        matches!(command, Command::RollbackTransaction { .. }),
+        "expected Command::RollbackTransaction, got {command:#?}",
+    );
+}
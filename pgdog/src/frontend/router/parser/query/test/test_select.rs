@@ -299,6 +299,62 @@ fn test_system_catalog_omnisharded_default() {
     config::set(updated).unwrap();
 }
 
+/// Column introspection query sent by ActiveRecord on connect.
+const ORM_INTROSPECTION: &str = "SELECT a.attname, format_type(a.atttypid, a.atttypmod),
+    pg_get_expr(d.adbin, d.adrelid), a.attnotnull, a.atttypid, a.atttypmod,
+    c.collname, col_description(a.attrelid, a.attnum) AS comment
+FROM pg_attribute a
+LEFT JOIN pg_attrdef d ON a.attrelid = d.adrelid AND a.attnum = d.adnum
+LEFT JOIN pg_type t ON a.atttypid = t.oid
+LEFT JOIN pg_collation c ON a.attcollation = c.oid AND a.attcollation <> t.typcollation
+WHERE a.attrelid = '\"sharded\"'::regclass AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY a.attnum";
+
+#[test]
+fn test_system_catalog_introspection_single_shard() {
+    let mut config = config().deref().clone();
+    config.config.general.system_catalogs = pgdog_config::SystemCatalogsBehavior::OmnishardedSticky;
+    config.config.general.omnisharded_sticky = false;
+
+    let mut test = QueryParserTest::new_with_config(&config);
+
+    let queries = [
+        ORM_INTROSPECTION,
+        "SELECT table_name, column_name FROM information_schema.columns WHERE table_schema = 'public'",
+        "SELECT c.relname FROM pg_catalog.pg_class c JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace",
+    ];
+
+    let mut shards = HashSet::new();
+    for _ in 0..10 {
+        for query in queries {
+            let command = test.execute(vec![Query::new(query).into()]);
+            assert!(
+                matches!(command.route().shard(), Shard::Direct(_)),
+                "catalog query should go to one shard: {}",
+                query
+            );
+            assert!(command.route().is_omnisharded());
+            shards.insert(command.route().shard().clone());
+        }
+    }
+
+    assert_eq!(
+        shards.len(),
+        1,
+        "catalog queries from the same client should use the same shard"
+    );
+
+    // User tables are still load balanced.
+    let mut shards = HashSet::new();
+    for _ in 0..10 {
+        let command = test.execute(vec![
+            Query::new("SELECT * FROM pg_class, sharded_omni").into(),
+        ]);
+        shards.insert(command.route().shard().clone());
+    }
+    assert_eq!(shards.len(), 2);
+}
+
 /// A SELECT against a table explicitly configured as omnisharded routes to a
 /// single shard and is flagged as omnisharded.
 #[test]
@@ -408,3 +464,34 @@ fn test_no_table_select_is_not_omnisharded() {
         assert!(!command.route().is_omnisharded(), "query: {}", q);
     }
 }
+
+#[test]
+fn test_analytics_query_cost() {
+    let mut updated = config().deref().clone();
+    updated.config.general.analytics_query_cost = 3;
+
+    let mut test = QueryParserTest::new_with_config(&updated);
+
+    let command = test.execute(vec![
+        Query::new(
+            "SELECT u.country, count(*) FROM users u JOIN orders o ON o.user_id = u.id GROUP BY u.country",
+        )
+        .into(),
+    ]);
+    assert!(command.route().is_read());
+    assert!(command.route().is_analytics());
+
+    let command = test.execute(vec![Query::new("SELECT * FROM users WHERE id = 1").into()]);
+    assert!(command.route().is_read());
+    assert!(!command.route().is_analytics());
+
+    // Disabled by default.
+    let mut test = QueryParserTest::new();
+    let command = test.execute(vec![
+        Query::new(
+            "SELECT u.country, count(*) FROM users u JOIN orders o ON o.user_id = u.id GROUP BY u.country",
+        )
+        .into(),
+    ]);
+    assert!(!command.route().is_analytics());
+}
@@ -150,6 +150,25 @@ fn test_omni_flag_set_for_insert() {
     assert!(command.route().is_omnisharded());
 }
 
+#[test]
+fn test_omni_writes_broadcast_reads_one_shard() {
+    for q in [
+        "INSERT INTO sharded_omni (id, value) VALUES (1, 'test')",
+        "UPDATE sharded_omni SET value = 'test' WHERE id = 1",
+        "DELETE FROM sharded_omni WHERE id = 1",
+    ] {
+        let mut test = QueryParserTest::new();
+        let command = test.execute(vec![Query::new(q).into()]);
+        assert!(command.route().is_write(), "{}", q);
+        assert_eq!(command.route().shard(), &Shard::All, "{}", q);
+    }
+
+    let mut test = QueryParserTest::new();
+    let command = test.execute(vec![Query::new("SELECT * FROM sharded_omni").into()]);
+    assert!(command.route().is_read());
+    assert!(matches!(command.route().shard(), Shard::Direct(_)));
+}
+
 #[test]
 fn test_omni_flag_not_set_for_regular_sharded() {
     let mut test = QueryParserTest::new();
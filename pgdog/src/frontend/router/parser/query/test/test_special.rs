@@ -368,3 +368,28 @@ fn test_empty_string_param() {
 
     assert!(command.route().is_read());
 }
+
+// --- Fastpath function calls ---
+
+#[test]
+fn test_fastpath_function_call() {
+    let mut test = QueryParserTest::new();
+
+    // version()
+    let command = test.execute(vec![Fastpath::new(89).into()]);
+
+    assert!(matches!(command, Command::Query(_)));
+    assert_eq!(command.route().shard(), &Shard::Direct(0));
+    assert!(command.route().is_write());
+}
+
+#[test]
+fn test_fastpath_function_call_connected_shard() {
+    let mut test = QueryParserTest::new()
+        .in_transaction(true)
+        .with_connected_shard(1);
+
+    let command = test.execute(vec![Fastpath::new(89).into()]);
+
+    assert_eq!(command.route().shard(), &Shard::Direct(1));
+}
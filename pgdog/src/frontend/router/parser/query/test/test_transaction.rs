@@ -1,5 +1,6 @@
 use crate::config::ReadWriteStrategy;
 use crate::frontend::Command;
+use crate::frontend::router::parser::route::{OverrideReason, ShardSource};
 use crate::net::parameter::ParameterValue;
 
 use super::setup::*;
@@ -96,3 +97,31 @@ fn test_set_application_name_in_transaction() {
 
     assert!(command.route().shard().is_all());
 }
+
+#[test]
+fn test_savepoint_in_transaction() {
+    let mut test = QueryParserTest::new().in_transaction(true);
+
+    for (query, rollback_savepoint) in [
+        ("SAVEPOINT sp", false),
+        ("RELEASE SAVEPOINT sp", false),
+        ("ROLLBACK TO SAVEPOINT sp", true),
+    ] {
+        let command = test.execute(vec![Query::new(query).into()]);
+
+        match command {
+            Command::Query(route) => {
+                assert!(route.is_write(), "{}", query);
+                assert!(route.shard().is_all(), "{}", query);
+                assert_eq!(route.rollback_savepoint(), rollback_savepoint, "{}", query);
+                assert_eq!(
+                    route.shard_with_priority().source(),
+                    &ShardSource::Override(OverrideReason::Transaction),
+                    "{}",
+                    query
+                );
+            }
+            _ => panic!("expected Query, got {command:?}"),
+        }
+    }
+}
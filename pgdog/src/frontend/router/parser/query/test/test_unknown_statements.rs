@@ -0,0 +1,61 @@
+use pgdog_config::UnknownStatements;
+
+use crate::frontend::router::parser::{Cache, Error, Shard};
+
+use super::setup::*;
+
+/// Statement the query router doesn't know how to route.
+const UNKNOWN: &str = "ALTER DATABASE pgdog REFRESH COLLATION VERSION";
+
+fn test(policy: UnknownStatements) -> QueryParserTest {
+    QueryParserTest::new().with_unknown_statements(policy)
+}
+
+#[test]
+fn test_unknown_statement_broadcast() {
+    let mut test = test(UnknownStatements::Broadcast);
+
+    let unknown = Cache::stats().0.unknown;
+    let command = test.execute(vec![Query::new(UNKNOWN).into()]);
+
+    assert_eq!(command.route().shard(), &Shard::All);
+    assert!(command.route().is_write());
+    assert_eq!(Cache::stats().0.unknown, unknown + 1);
+}
+
+#[test]
+fn test_unknown_statement_primary() {
+    let mut test = test(UnknownStatements::Primary);
+
+    let command = test.execute(vec![Query::new(UNKNOWN).into()]);
+
+    assert_eq!(command.route().shard(), &Shard::Direct(0));
+    assert!(command.route().is_write());
+}
+
+#[test]
+fn test_unknown_statement_reject() {
+    let mut test = test(UnknownStatements::Reject);
+
+    let result = test.try_execute(vec![Query::new(UNKNOWN).into()]);
+    assert!(matches!(result, Err(Error::UnknownStatementRejected)));
+
+    // DDL we know about is unaffected.
+    let command = test.execute(vec![
+        Query::new("CREATE TABLE unknown_statements (id BIGINT)").into(),
+    ]);
+    assert_eq!(command.route().shard(), &Shard::All);
+
+    for query in [
+        "GRANT SELECT ON sharded TO pgdog",
+        "IMPORT FOREIGN SCHEMA remote FROM SERVER other INTO public",
+        "CREATE PUBLICATION unknown_statements FOR ALL TABLES",
+        "ALTER SYSTEM SET work_mem TO '64MB'",
+        "SECURITY LABEL ON TABLE sharded IS 'secret'",
+        "CREATE CAST (BIGINT AS TEXT) WITH INOUT",
+        "MERGE INTO sharded USING sharded s ON sharded.id = s.id WHEN MATCHED THEN DO NOTHING",
+    ] {
+        let command = test.execute(vec![Query::new(query).into()]);
+        assert_eq!(command.route().shard(), &Shard::All, "{}", query);
+    }
+}
@@ -20,6 +20,7 @@ impl QueryParser {
     ) -> Result<Command, Error> {
         let extended = !context.query()?.simple();
         let mut rollback_savepoint = false;
+        let mut savepoint = false;
 
         if context.rw_conservative() && !context.read_only {
             self.write_override = true;
@@ -42,7 +43,11 @@ impl QueryParser {
                         .with_read(transaction_type == TransactionType::ReadOnly),
                 });
             }
-            TRANS_STMT_ROLLBACK_TO => rollback_savepoint = true,
+            TRANS_STMT_ROLLBACK_TO => {
+                rollback_savepoint = true;
+                savepoint = true;
+            }
+            TRANS_STMT_SAVEPOINT | TRANS_STMT_RELEASE => savepoint = true,
             TRANS_STMT_PREPARE | TRANS_STMT_COMMIT_PREPARED | TRANS_STMT_ROLLBACK_PREPARED
                 if context.router_context.two_pc =>
             {
@@ -51,9 +56,12 @@ impl QueryParser {
             _ => (),
         }
 
-        context
-            .shards_calculator
-            .push(ShardWithPriority::new_table(Shard::All));
+        // Savepoints go to the same connections as the rest of the transaction.
+        context.shards_calculator.push(if savepoint {
+            ShardWithPriority::new_override_transaction(Shard::All)
+        } else {
+            ShardWithPriority::new_table(Shard::All)
+        });
 
         Ok(Command::Query(
             Route::write(context.shards_calculator.shard())
@@ -70,6 +78,7 @@ impl QueryParser {
             ) -> Result<Command, Error> {
                 let extended = !context.query()?.simple();
                 let mut rollback_savepoint = false;
+                let mut savepoint = false;
 
                 if context.rw_conservative() && !context.read_only {
                     self.write_override = true;
@@ -92,7 +101,12 @@ impl QueryParser {
                                 .with_read(transaction_type == TransactionType::ReadOnly),
                         });
                     }
-                    TransactionStmtKind::TransStmtRollbackTo => rollback_savepoint = true,
+                    TransactionStmtKind::TransStmtRollbackTo => {
+                        rollback_savepoint = true;
+                        savepoint = true;
+                    }
+                    TransactionStmtKind::TransStmtSavepoint
+                    | TransactionStmtKind::TransStmtRelease => savepoint = true,
                     TransactionStmtKind::TransStmtPrepare
                     | TransactionStmtKind::TransStmtCommitPrepared
                     | TransactionStmtKind::TransStmtRollbackPrepared
@@ -103,9 +117,12 @@ impl QueryParser {
                     _ => (),
                 }
 
-                context
-                    .shards_calculator
-                    .push(ShardWithPriority::new_table(Shard::All));
+                // Savepoints go to the same connections as the rest of the transaction.
+                context.shards_calculator.push(if savepoint {
+                    ShardWithPriority::new_override_transaction(Shard::All)
+                } else {
+                    ShardWithPriority::new_table(Shard::All)
+                });
 
                 Ok(Command::Query(
                     Route::write(context.shards_calculator.shard())
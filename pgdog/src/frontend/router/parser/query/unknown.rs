@@ -0,0 +1,63 @@
+//! Statements the query router doesn't recognize.
+use pgdog_config::{ParseFailures, UnknownStatements};
+
+use super::*;
+
+impl QueryParser {
+    /// Route a statement that isn't a query or a DDL/utility
+    /// command we know about, according to the configured policy.
+    pub(super) fn unknown_statement(
+        context: &mut QueryParserContext<'_>,
+    ) -> Result<Command, Error> {
+        Cache::record_unknown();
+        debug!(
+            "unknown statement type, routing policy: {:?}",
+            context.unknown_statements
+        );
+
+        let shard = match context.unknown_statements {
+            UnknownStatements::Broadcast => Shard::All,
+            UnknownStatements::Primary => Shard::Direct(0),
+            UnknownStatements::Reject => return Err(Error::UnknownStatementRejected),
+        };
+
+        context
+            .shards_calculator
+            .push(ShardWithPriority::new_table(shard));
+
+        Ok(Command::Query(Route::write(
+            context.shards_calculator.shard(),
+        )))
+    }
+
+    /// Route a query the parser failed to parse, according to the configured policy.
+    /// We don't know what the query does, so it's always sent to a primary.
+    pub(super) fn parse_failure(context: &mut QueryParserContext<'_>) -> Result<Command, Error> {
+        debug!(
+            "query failed to parse, routing policy: {:?}",
+            context.parse_failures
+        );
+
+        match context.parse_failures {
+            ParseFailures::Primary => {
+                context
+                    .shards_calculator
+                    .push(ShardWithPriority::new_table(Shard::Direct(0)));
+            }
+
+            ParseFailures::RoundRobin => {
+                context
+                    .shards_calculator
+                    .push(ShardWithPriority::new_rr_parse_failure(Shard::Direct(
+                        round_robin::next() % context.shards,
+                    )));
+            }
+
+            ParseFailures::Reject => return Err(Error::ParseFailureRejected),
+        }
+
+        Ok(Command::Query(Route::write(
+            context.shards_calculator.shard(),
+        )))
+    }
+}
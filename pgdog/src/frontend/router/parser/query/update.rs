@@ -50,6 +50,8 @@ impl QueryParser {
             }
         }
 
+        Self::check_sharding_key(context, is_sharded, "UPDATE")?;
+
         Ok(Command::Query(
             Route::write(context.shards_calculator.shard()).with_omnisharded(omnisharded),
         ))
@@ -45,7 +45,7 @@ impl AggregatesRewrite {
                     panic!("Previously parsed function was not a FuncCall");
                 };
 
-                Self::helper_specs(func_call, target.function(), mem)
+                Self::helper_specs(func_call, target.function(), target.is_distinct(), mem)
                     .into_iter()
                     .map(move |spec| (target, spec))
             })
@@ -223,6 +223,23 @@ impl AggregatesRewrite {
         }
     }
 
+    #[cfg(not(feature = "new_parser"))]
+    fn build_distinct_values_func(original: &FuncCall) -> FuncCall {
+        FuncCall {
+            funcname: vec![pg_string("array_agg")],
+            args: original.args.clone(),
+            agg_order: vec![],
+            agg_filter: original.agg_filter.clone(),
+            over: original.over.clone(),
+            agg_within_group: false,
+            agg_star: false,
+            agg_distinct: true,
+            func_variadic: original.func_variadic,
+            funcformat: original.funcformat,
+            location: original.location,
+        }
+    }
+
     #[cfg(feature = "new_parser")]
     fn build_sum_of_squares_func<'a>(
         original: &nodes::FuncCall,
@@ -321,9 +338,17 @@ impl AggregatesRewrite {
     fn helper_specs<'a>(
         func_call: &nodes::FuncCall,
         function: &AggregateFunction,
+        distinct: bool,
         mem: make::MemoryToken<'a>,
     ) -> Vec<HelperSpec<'a>> {
         match function {
+            // The same value can be present on multiple shards.
+            AggregateFunction::Count if distinct => {
+                vec![HelperSpec {
+                    func: Self::copy_and_rename_function(func_call, "array_agg", mem),
+                    kind: HelperKind::DistinctValues,
+                }]
+            }
             AggregateFunction::Avg => {
                 vec![HelperSpec {
                     func: Self::copy_and_rename_function(func_call, "count", mem),
@@ -378,6 +403,11 @@ impl AggregatesRewrite {
         distinct: bool,
     ) -> Vec<HelperSpec> {
         match function {
+            // The same value can be present on multiple shards.
+            AggregateFunction::Count if distinct => vec![HelperSpec {
+                func: Self::build_distinct_values_func(func_call),
+                kind: HelperKind::DistinctValues,
+            }],
             AggregateFunction::Avg => vec![HelperSpec {
                 func: Self::build_count_func(func_call, distinct),
                 kind: HelperKind::Count,
@@ -594,4 +624,25 @@ mod tests {
         #[cfg(not(feature = "new_parser"))]
         assert_eq!(select(&mut ast).target_list.len(), 4);
     }
+
+    #[test]
+    fn rewrite_engine_count_distinct_helper() {
+        let (mut ast, output) = rewrite("SELECT COUNT(DISTINCT price), COUNT(price) FROM menu");
+        assert_eq!(output.plan.drop_columns().collect::<Vec<_>>(), &[2]);
+        assert_eq!(output.plan.helpers().len(), 1);
+
+        let helper = &output.plan.helpers()[0];
+        assert_eq!(helper.target_column, 0);
+        assert_eq!(helper.helper_column, 2);
+        assert!(helper.distinct);
+        assert_eq!(helper.kind, HelperKind::DistinctValues);
+        assert_eq!(helper.alias, "__pgdog_distinct_col0");
+
+        // ARRAY_AGG isn't an aggregate we merge ourselves.
+        #[cfg(feature = "new_parser")]
+        let aggregate = Aggregate::parse(&ast, &Default::default());
+        #[cfg(not(feature = "new_parser"))]
+        let aggregate = Aggregate::parse(select(&mut ast), &Default::default());
+        assert_eq!(aggregate.targets().len(), 2);
+    }
 }
@@ -3,6 +3,7 @@ mod plan;
 
 use super::{Error, RewritePlan, StatementRewrite};
 use crate::backend::schema::Schema;
+use crate::frontend::router::parser::Having;
 use crate::frontend::router::parser::aggregate::Aggregate;
 #[cfg(not(feature = "new_parser"))]
 use pg_query::NodeEnum;
@@ -14,6 +15,9 @@ pub(crate) use plan::{AggregateRewritePlan, HelperKind, HelperMapping, RewriteOu
 
 impl StatementRewrite<'_> {
     /// Add missing COUNT(*) and other helps when using aggregates.
+    ///
+    /// The HAVING clause is moved into the plan. If we can evaluate it, the caller
+    /// removes it from the query, otherwise cross-shard queries are rejected.
     #[cfg(feature = "new_parser")]
     pub(super) fn rewrite_aggregates<'a>(
         &mut self,
@@ -27,11 +31,21 @@ impl StatementRewrite<'_> {
         }
 
         let aggregate = Aggregate::parse(&select, schema);
+        let having = Having::parse(&select);
         if aggregate.is_empty() {
+            // Without aggregates in the select list,
+            // we can't evaluate HAVING after merging groups.
+            if having.is_some() {
+                plan.aggregates.set_having(Having::unsupported());
+            }
             return Ok(());
         }
 
-        let output = AggregatesRewrite::rewrite_select(select, mem, &aggregate);
+        let mut output = AggregatesRewrite::rewrite_select(select, mem, &aggregate);
+        if let Some(having) = having {
+            output.plan.set_having(having);
+        }
+
         if output.plan.is_noop() {
             return Ok(());
         }
@@ -64,11 +78,21 @@ impl StatementRewrite<'_> {
         };
 
         let aggregate = Aggregate::parse(select, schema);
+        let having = Having::parse(select);
         if aggregate.is_empty() {
+            // Without aggregates in the select list,
+            // we can't evaluate HAVING after merging groups.
+            if having.is_some() {
+                plan.aggregates.set_having(Having::unsupported());
+            }
             return Ok(());
         }
 
-        let output = AggregatesRewrite.rewrite_select(select, &aggregate);
+        let mut output = AggregatesRewrite.rewrite_select(select, &aggregate);
+        if let Some(having) = having {
+            output.plan.set_having(having);
+        }
+
         if output.plan.is_noop() {
             return Ok(());
         }
@@ -77,4 +101,18 @@ impl StatementRewrite<'_> {
         self.rewritten = true;
         Ok(())
     }
+
+    /// Remove the HAVING clause from the statement.
+    #[cfg(not(feature = "new_parser"))]
+    pub(super) fn remove_having(&mut self) {
+        if let Some(NodeEnum::SelectStmt(select)) = self
+            .stmt
+            .stmts
+            .first_mut()
+            .and_then(|raw_stmt| raw_stmt.stmt.as_mut())
+            .and_then(|stmt| stmt.node.as_mut())
+        {
+            select.having_clause = None;
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::frontend::router::parser::Having;
+
 /// Type of aggregate function added to the result set.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum HelperKind {
@@ -7,6 +9,8 @@ pub(crate) enum HelperKind {
     Sum,
     /// SUM(POWER(column, 2))
     SumSquares,
+    /// ARRAY_AGG(DISTINCT column)
+    DistinctValues,
 }
 
 impl HelperKind {
@@ -16,6 +20,7 @@ impl HelperKind {
             HelperKind::Count => "count",
             HelperKind::Sum => "sum",
             HelperKind::SumSquares => "sumsq",
+            HelperKind::DistinctValues => "distinct",
         }
     }
 }
@@ -34,6 +39,8 @@ pub(crate) struct HelperMapping {
 #[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct AggregateRewritePlan {
     helpers: Vec<HelperMapping>,
+    /// HAVING clause removed from the query, applied after merging groups.
+    having: Option<Having>,
 }
 
 impl AggregateRewritePlan {
@@ -41,12 +48,13 @@ impl AggregateRewritePlan {
     pub(crate) fn new() -> Self {
         Self {
             helpers: Vec::new(),
+            having: None,
         }
     }
 
     /// Is this plan a no-op? Doesn't do anything.
     pub(crate) fn is_noop(&self) -> bool {
-        self.helpers.is_empty()
+        self.helpers.is_empty() && self.having.is_none()
     }
 
     pub(crate) fn drop_columns(&self) -> impl Iterator<Item = usize> + '_ {
@@ -60,6 +68,22 @@ impl AggregateRewritePlan {
     pub(crate) fn add_helper(&mut self, mapping: HelperMapping) {
         self.helpers.push(mapping);
     }
+
+    pub(crate) fn having(&self) -> Option<&Having> {
+        self.having.as_ref()
+    }
+
+    pub(crate) fn set_having(&mut self, having: Having) {
+        self.having = Some(having);
+    }
+
+    /// The HAVING clause was evaluated by each shard,
+    /// so the merged groups can't be filtered correctly.
+    pub(crate) fn reject_having(&mut self) {
+        if self.having.is_some() {
+            self.having = Some(Having::unsupported());
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -116,6 +116,8 @@ impl InsertSplit {
                 .ok_or(Error::MissingParameter(*param))?;
             new.push_param(param.parameter().clone(), param.format());
         }
+        // RETURNING rows from each split are sent to the client as-is.
+        new.copy_results(bind);
 
         Ok(new)
     }
@@ -150,7 +152,11 @@ impl InsertSplit {
                     .as_deref()
                     .unwrap_or_else(|| bind.statement());
 
-                Ok(Bind::new_params_codes(statement_name, &params, &codes))
+                let mut new = Bind::new_params_codes(statement_name, &params, &codes);
+                // RETURNING rows from each split are sent to the client as-is.
+                new.copy_results(bind);
+
+                Ok(new)
             }
         }
         _ => {}
@@ -642,6 +648,22 @@ mod tests {
         assert_eq!(extracted.format_codes_raw()[0], Format::Binary);
     }
 
+    #[test]
+    fn test_extract_bind_params_keeps_result_formats() {
+        let splits = parse_and_split("INSERT INTO t (a) VALUES ($1), ($2) RETURNING a");
+        let bind = Bind::new_params_codes_results(
+            "test",
+            &[Parameter::new(b"p0"), Parameter::new(b"p1")],
+            &[],
+            &[1],
+        );
+
+        for split in &splits {
+            let extracted = split.extract_bind_params(&bind).unwrap();
+            assert_eq!(extracted.result_formats(), vec![Format::Binary]);
+        }
+    }
+
     #[test]
     fn test_extract_bind_params_mixed_params_and_literals() {
         let splits = parse_and_split("INSERT INTO t (a, b) VALUES ($1, 'lit1'), ($2, 'lit2')");
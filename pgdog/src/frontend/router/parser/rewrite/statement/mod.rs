@@ -12,7 +12,7 @@ use pgdog_config::QueryParserEngine;
 use crate::backend::ShardingSchema;
 use crate::backend::schema::Schema;
 use crate::frontend::PreparedStatements;
-use crate::frontend::router::parser::AstContext;
+use crate::frontend::router::parser::{AstContext, Having};
 use crate::net::parameter::ParameterValue;
 
 pub mod aggregate;
@@ -178,6 +178,14 @@ impl<'a> StatementRewrite<'a> {
             self.limit_offset(&select, &mut plan);
         }
 
+        // HAVING is applied after merging groups from all shards.
+        if plan.aggregates.having().is_some_and(Having::supported) {
+            plan.direct_stmt = Some(pg_raw_parse::deparse(&*stmt)?.as_str().to_owned());
+            if let NodeMut::SelectStmt(mut select) = stmt.stmt_mut() {
+                select.set_having_clause(mem.none());
+            }
+        }
+
         if self.rewritten {
             plan.stmt = Some(pg_raw_parse::deparse(&*stmt)?.as_str().to_owned());
         }
@@ -231,11 +239,14 @@ impl<'a> StatementRewrite<'a> {
         self.rewrite_aggregates(&mut plan, self.db_schema)?;
         self.limit_offset(&mut plan)?;
 
+        // HAVING is applied after merging groups from all shards.
+        if plan.aggregates.having().is_some_and(Having::supported) {
+            plan.direct_stmt = Some(self.deparse()?);
+            self.remove_having();
+        }
+
         if self.rewritten {
-            plan.stmt = Some(match self.schema.query_parser_engine {
-                QueryParserEngine::PgQueryProtobuf => self.stmt.deparse(),
-                QueryParserEngine::PgQueryRaw => self.stmt.deparse_raw(),
-            }?);
+            plan.stmt = Some(self.deparse()?);
         }
 
         self.split_insert(&mut plan)?;
@@ -243,4 +254,12 @@ impl<'a> StatementRewrite<'a> {
 
         Ok(plan)
     }
+
+    #[cfg(not(feature = "new_parser"))]
+    fn deparse(&self) -> Result<String, Error> {
+        Ok(match self.schema.query_parser_engine {
+            QueryParserEngine::PgQueryProtobuf => self.stmt.deparse(),
+            QueryParserEngine::PgQueryRaw => self.stmt.deparse_raw(),
+        }?)
+    }
 }
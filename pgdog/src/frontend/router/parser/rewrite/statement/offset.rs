@@ -16,11 +16,23 @@ use super::*;
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct OffsetPlan {
     pub(crate) limit: Limit,
+    /// Parameter number of LIMIT, 0 if it's a literal or there is no LIMIT.
     pub(crate) limit_param: usize,
     pub(crate) offset_param: usize,
 }
 
 impl OffsetPlan {
+    fn new(limit: Option<LimitValueInfo>, offset: LimitValueInfo) -> Self {
+        Self {
+            limit: Limit {
+                limit: limit.as_ref().and_then(LimitValueInfo::literal),
+                offset: offset.literal(),
+            },
+            limit_param: limit.as_ref().map_or(0, LimitValueInfo::param_index),
+            offset_param: offset.param_index(),
+        }
+    }
+
     pub(super) fn apply_after_parser(&self, request: &mut ClientRequest) -> Result<(), Error> {
         let route = match request.route.as_mut() {
             Some(route) => route,
@@ -37,7 +49,7 @@ impl OffsetPlan {
 
         for message in request.messages.iter_mut() {
             if let ProtocolMessage::Bind(bind) = message {
-                if limit_val.is_none() {
+                if limit_val.is_none() && self.limit_param > 0 {
                     let idx = self.limit_param - 1;
                     limit_val = Some(
                         bind.parameter(idx)?
@@ -61,7 +73,7 @@ impl OffsetPlan {
                 let new_limit = limit_val.unwrap_or(0) + offset_val.unwrap_or(0);
 
                 // Overwrite parameterized limit.
-                if self.limit.limit.is_none() {
+                if self.limit.limit.is_none() && self.limit_param > 0 {
                     let idx = self.limit_param - 1;
                     let fmt = bind.parameter_format(idx)?;
                     let param = match fmt {
@@ -87,9 +99,10 @@ impl OffsetPlan {
             }
         }
 
-        // Rewrite SQL if any value was a literal.
+        // Rewrite SQL if any value was a literal. Without LIMIT,
+        // each shard returns all rows and we only remove the OFFSET.
         if self.limit.limit.is_some() || self.limit.offset.is_some() {
-            let new_limit = (limit_val.unwrap_or(0) + offset_val.unwrap_or(0)) as i32;
+            let new_limit = limit_val.map(|limit| (limit + offset_val.unwrap_or(0)) as i32);
             let ast = request.ast.as_ref().ok_or(Error::MissingAst)?;
             #[cfg(not(feature = "new_parser"))]
             let mut protobuf = ast.ast.protobuf.clone();
@@ -178,16 +191,21 @@ fn extract_limit_value(node: &Option<pg_query::NodeEnum>) -> Option<LimitValueIn
 }
 
 #[cfg(feature = "new_parser")]
-fn rewrite_ast_limit_offset(ast: &StmtList, new_limit: i32) -> Option<Owned<nodes::SelectStmt>> {
+fn rewrite_ast_limit_offset(
+    ast: &StmtList,
+    new_limit: Option<i32>,
+) -> Option<Owned<nodes::SelectStmt>> {
     let Some(Node::SelectStmt(select)) = ast.stmts().next() else {
         return None;
     };
 
     Some(make::owned(|mem| {
         let mut select = mem.make_unique(select);
-        select
-            .as_mut()
-            .set_limit_count(mem.make_a_const(ConstValue::Integer(new_limit)).uncast());
+        if let Some(new_limit) = new_limit {
+            select
+                .as_mut()
+                .set_limit_count(mem.make_a_const(ConstValue::Integer(new_limit)).uncast());
+        }
         select.as_mut().set_limit_offset(mem.none());
         select
     }))
@@ -195,7 +213,7 @@ fn rewrite_ast_limit_offset(ast: &StmtList, new_limit: i32) -> Option<Owned<node
 
 cfg_select! {
     not(feature = "new_parser") => {
-        fn rewrite_ast_limit_offset(ast: &mut ParseResult, new_limit: i32) -> bool {
+        fn rewrite_ast_limit_offset(ast: &mut ParseResult, new_limit: Option<i32>) -> bool {
             let raw_stmt = match ast.stmts.first_mut() {
                 Some(s) => s,
                 None => return false,
@@ -209,13 +227,15 @@ cfg_select! {
                 _ => return false,
             };
 
-            select.limit_count = Some(Box::new(pg_query::Node {
-                node: Some(NodeEnum::AConst(AConst {
-                    val: Some(Val::Ival(Integer { ival: new_limit })),
-                    isnull: false,
-                    location: -1i32,
-                })),
-            }));
+            if let Some(new_limit) = new_limit {
+                select.limit_count = Some(Box::new(pg_query::Node {
+                    node: Some(NodeEnum::AConst(AConst {
+                        val: Some(Val::Ival(Integer { ival: new_limit })),
+                        isnull: false,
+                        location: -1i32,
+                    })),
+                }));
+            }
 
             select.limit_offset = Some(Box::new(pg_query::Node {
                 node: Some(NodeEnum::AConst(AConst {
@@ -238,21 +258,19 @@ impl StatementRewrite<'_> {
             return;
         }
 
-        let Some(limit_info) = extract_limit_value(select.limit_count()) else {
-            return;
-        };
         let Some(offset_info) = extract_limit_value(select.limit_offset()) else {
             return;
         };
-
-        plan.offset = Some(OffsetPlan {
-            limit: Limit {
-                limit: limit_info.literal(),
-                offset: offset_info.literal(),
+        // OFFSET without LIMIT.
+        let limit_info = match select.limit_count() {
+            Node::None => None,
+            node => match extract_limit_value(node) {
+                Some(info) => Some(info),
+                None => return,
             },
-            limit_param: limit_info.param_index(),
-            offset_param: offset_info.param_index(),
-        });
+        };
+
+        plan.offset = Some(OffsetPlan::new(limit_info, offset_info));
     }
 
     #[cfg(not(feature = "new_parser"))]
@@ -278,27 +296,21 @@ impl StatementRewrite<'_> {
             Some(node) => node,
             None => return Ok(()),
         };
-        let limit_node = match &select.limit_count {
-            Some(node) => node,
+        let offset_info = match extract_limit_value(&offset_node.node) {
+            Some(info) => info,
             None => return Ok(()),
         };
 
-        let limit_info = extract_limit_value(&limit_node.node);
-        let offset_info = extract_limit_value(&offset_node.node);
-
-        let (limit_info, offset_info) = match (limit_info, offset_info) {
-            (Some(l), Some(o)) => (l, o),
-            _ => return Ok(()),
+        // OFFSET without LIMIT.
+        let limit_info = match &select.limit_count {
+            Some(node) => match extract_limit_value(&node.node) {
+                Some(info) => Some(info),
+                None => return Ok(()),
+            },
+            None => None,
         };
 
-        plan.offset = Some(OffsetPlan {
-            limit: Limit {
-                limit: limit_info.literal(),
-                offset: offset_info.literal(),
-            },
-            limit_param: limit_info.param_index(),
-            offset_param: offset_info.param_index(),
-        });
+        plan.offset = Some(OffsetPlan::new(limit_info, offset_info));
 
         Ok(())
     }
@@ -443,9 +455,12 @@ mod tests {
     }
 
     #[test]
-    fn test_limit_offset_skipped_no_limit() {
+    fn test_limit_offset_detection_no_limit() {
         let plan = run_limit_offset("SELECT * FROM t OFFSET 5", &sharded_schema());
-        assert!(plan.offset.is_none());
+        let offset = plan.offset.unwrap();
+        assert_eq!(offset.limit.limit, None);
+        assert_eq!(offset.limit.offset, Some(5));
+        assert_eq!(offset.limit_param, 0);
     }
 
     #[test]
@@ -572,4 +587,62 @@ mod tests {
         assert_eq!(route.limit().limit, Some(10));
         assert_eq!(route.limit().offset, Some(5));
     }
+
+    #[test]
+    fn test_apply_after_parser_offset_without_limit() {
+        let plan = OffsetPlan {
+            limit: Limit {
+                limit: None,
+                offset: Some(5),
+            },
+            limit_param: 0,
+            offset_param: 0,
+        };
+        let mut request = ClientRequest::from(vec![ProtocolMessage::Query(Query::new(
+            "SELECT * FROM t ORDER BY id OFFSET 5",
+        ))]);
+        request.route = Some(cross_shard_route());
+        request.ast = Some(make_ast("SELECT * FROM t ORDER BY id OFFSET 5"));
+
+        plan.apply_after_parser(&mut request).unwrap();
+
+        let query = match &request.messages[0] {
+            ProtocolMessage::Query(q) => q.query().to_owned(),
+            _ => panic!("expected Query"),
+        };
+        assert_eq!(query, "SELECT * FROM t ORDER BY id");
+
+        let route = request.route.unwrap();
+        assert_eq!(route.limit().limit, None);
+        assert_eq!(route.limit().offset, Some(5));
+    }
+
+    #[test]
+    fn test_apply_after_parser_offset_param_without_limit() {
+        let plan = OffsetPlan {
+            limit: Limit {
+                limit: None,
+                offset: None,
+            },
+            limit_param: 0,
+            offset_param: 1,
+        };
+        let mut request = ClientRequest::from(vec![ProtocolMessage::Bind(Bind::new_params(
+            "",
+            &[Parameter::new(b"5")],
+        ))]);
+        request.route = Some(cross_shard_route());
+
+        plan.apply_after_parser(&mut request).unwrap();
+
+        if let ProtocolMessage::Bind(bind) = &request.messages[0] {
+            assert_eq!(bind.params_raw()[0].data.as_ref(), b"0");
+        } else {
+            panic!("expected Bind");
+        }
+
+        let route = request.route.unwrap();
+        assert_eq!(route.limit().limit, None);
+        assert_eq!(route.limit().offset, Some(5));
+    }
 }
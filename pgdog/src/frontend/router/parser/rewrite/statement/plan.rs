@@ -28,6 +28,10 @@ pub struct RewritePlan {
     /// Rewritten SQL statement.
     pub(crate) stmt: Option<String>,
 
+    /// Rewritten SQL statement that kept its HAVING clause. Used instead
+    /// of `stmt` if the query goes to only one shard.
+    pub(crate) direct_stmt: Option<String>,
+
     /// Prepared statements to prepend to the client request.
     /// Each tuple contains (name, statement) for ProtocolMessage::Prepare.
     pub(crate) prepares: Vec<(String, String)>,
@@ -50,7 +54,12 @@ pub struct RewritePlan {
 
 #[derive(Debug, Clone)]
 pub(crate) enum RewriteResult {
-    InPlace { offset: Option<OffsetPlan> },
+    InPlace {
+        offset: Option<OffsetPlan>,
+        direct_stmt: Option<String>,
+        /// Request uses a named prepared statement.
+        named: bool,
+    },
     InsertSplit(Vec<ClientRequest>),
     ShardingKeyUpdate(ShardingKeyUpdate),
 }
@@ -59,11 +68,54 @@ impl RewriteResult {
     pub(crate) fn apply_after_parser(&self, request: &mut ClientRequest) -> Result<(), Error> {
         match self {
             Self::InPlace {
-                offset: Some(offset),
-            } => offset.apply_after_parser(request),
+                offset,
+                direct_stmt,
+                named,
+            } => {
+                if let Some(direct_stmt) = direct_stmt {
+                    Self::apply_direct_stmt(direct_stmt, *named, request);
+                }
+
+                match offset {
+                    Some(offset) => offset.apply_after_parser(request),
+                    None => Ok(()),
+                }
+            }
             _ => Ok(()),
         }
     }
+
+    /// Put the HAVING clause back if the query isn't cross-shard,
+    /// since we won't be merging groups.
+    ///
+    /// Named prepared statements kept their HAVING clause when they were registered,
+    /// so they can't be filtered again after merging.
+    fn apply_direct_stmt(direct_stmt: &str, named: bool, request: &mut ClientRequest) {
+        let Some(route) = request.route.as_mut() else {
+            return;
+        };
+
+        if route.is_cross_shard() {
+            if named {
+                let mut plan = route.aggregate_rewrite_plan().clone();
+                plan.reject_having();
+                route.set_rewrite_plan(plan);
+            }
+            return;
+        }
+
+        if named {
+            return;
+        }
+
+        for message in request.messages.iter_mut() {
+            match message {
+                ProtocolMessage::Query(query) => query.set_query(direct_stmt),
+                ProtocolMessage::Parse(parse) => parse.set_query(direct_stmt),
+                _ => {}
+            }
+        }
+    }
 }
 
 impl RewritePlan {
@@ -74,6 +126,7 @@ impl RewritePlan {
         self.unique_ids == 0
             && self.auto_id_injected == 0
             && self.stmt.is_none()
+            && self.direct_stmt.is_none()
             && self.prepares.is_empty()
             && self.insert_split.is_empty()
             && self.aggregates.is_noop()
@@ -99,8 +152,17 @@ impl RewritePlan {
     }
 
     /// Apply the rewrite plan to a Parse message by updating the SQL.
+    ///
+    /// Named statements are shared between servers, so their SQL can't depend
+    /// on where the query is routed. They keep their HAVING clause.
     pub(crate) fn apply_parse(&self, parse: &mut Parse) {
-        if let Some(ref stmt) = self.stmt {
+        let stmt = if parse.anonymous() {
+            self.stmt.as_ref()
+        } else {
+            self.direct_stmt.as_ref().or(self.stmt.as_ref())
+        };
+
+        if let Some(stmt) = stmt {
             parse.set_query(stmt);
             if !parse.anonymous() {
                 PreparedStatements::global().write().rewrite(parse);
@@ -155,8 +217,16 @@ impl RewritePlan {
             ));
         }
 
+        let named = request.messages.iter().any(|message| match message {
+            ProtocolMessage::Parse(parse) => !parse.anonymous(),
+            ProtocolMessage::Bind(bind) => !bind.anonymous(),
+            _ => false,
+        });
+
         Ok(RewriteResult::InPlace {
             offset: self.offset.clone(),
+            direct_stmt: self.direct_stmt.clone(),
+            named,
         })
     }
 }
@@ -289,4 +359,25 @@ mod tests {
         let text = std::str::from_utf8(&bind.params_raw()[2].data).unwrap();
         let _: i64 = text.parse().expect("should be valid i64");
     }
+
+    #[test]
+    fn test_apply_parse_named_keeps_having() {
+        let plan = RewritePlan {
+            stmt: Some("SELECT a, COUNT(*) FROM t GROUP BY a".into()),
+            direct_stmt: Some("SELECT a, COUNT(*) FROM t GROUP BY a HAVING COUNT(*) > 1".into()),
+            ..Default::default()
+        };
+
+        let mut parse = Parse::new_anonymous("SELECT 1");
+        plan.apply_parse(&mut parse);
+        assert_eq!(parse.query(), "SELECT a, COUNT(*) FROM t GROUP BY a");
+
+        // Named statements get the same SQL wherever they are routed.
+        let mut parse = Parse::named("test_having", "SELECT 1");
+        plan.apply_parse(&mut parse);
+        assert_eq!(
+            parse.query(),
+            "SELECT a, COUNT(*) FROM t GROUP BY a HAVING COUNT(*) > 1"
+        );
+    }
 }
@@ -92,6 +92,9 @@ pub struct Route {
     shard: ShardWithPriority,
     /// Is this query a read, e.g. SELECT.
     read: bool,
+    /// Send this read to the primary anyway. It's still
+    /// counted and treated as a read otherwise.
+    primary: bool,
     /// `ORDER BY` clause, transformed into something
     /// we can quickly use to sort the result.
     order_by: Vec<OrderBy>,
@@ -112,6 +115,9 @@ pub struct Route {
     /// Our query explain plan. We attach
     /// this to the `EXPLAIN` output.
     explain: Option<ExplainTrace>,
+    /// This query is an `EXPLAIN ANALYZE` and its
+    /// output from multiple shards should be combined.
+    explain_analyze: bool,
     /// This query is a `ROLLBACK SAVEPOINT` command.
     /// Nasty one.
     rollback_savepoint: bool,
@@ -121,9 +127,23 @@ pub struct Route {
     /// This query is a DDL statement. We will need to
     /// reload the schema from Postgres once this runs.
     schema_changed: bool,
+    /// This query is a `TRUNCATE`.
+    truncate: bool,
+    /// This query creates a temporary table, which only exists
+    /// on the server connection that created it.
+    temporary_table: bool,
     /// This query is only touching omnisharded tables
     /// and requires special checks to be executed.
     omnisharded: bool,
+    /// Replica requested with a `pgdog_replica` comment.
+    /// Only reads are sent to it.
+    replica: Option<String>,
+    /// This read is expensive and should go
+    /// to an analytics replica, if there is one.
+    analytics: bool,
+    /// Query with the tenant filter added to it,
+    /// sent instead of the client's query.
+    tenant_filter: Option<String>,
 }
 
 impl Display for Route {
@@ -132,7 +152,11 @@ impl Display for Route {
             f,
             "shard={}, role={}",
             self.shard.deref(),
-            if self.read { "replica" } else { "primary" }
+            if self.use_replica() {
+                "replica"
+            } else {
+                "primary"
+            }
         )
     }
 }
@@ -261,6 +285,24 @@ impl Route {
         self
     }
 
+    pub fn is_truncate(&self) -> bool {
+        self.truncate
+    }
+
+    pub fn with_truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    pub fn is_temporary_table(&self) -> bool {
+        self.temporary_table
+    }
+
+    pub fn with_temporary_table(mut self, temporary_table: bool) -> Self {
+        self.temporary_table = temporary_table;
+        self
+    }
+
     pub fn set_search_path_driven(&mut self, schema_driven: bool) {
         self.search_path_driven = schema_driven;
     }
@@ -282,6 +324,7 @@ impl Route {
     pub fn should_buffer(&self) -> bool {
         !self.order_by().is_empty()
             || !self.aggregate().is_empty()
+            || !self.aggregate().group_by().is_empty()
             || self.distinct().is_some()
             || self.limit().offset.is_some()
     }
@@ -303,6 +346,58 @@ impl Route {
         self.read = read;
     }
 
+    /// Send this query to the primary, even if it's a read.
+    pub fn set_primary(&mut self, primary: bool) {
+        self.primary = primary;
+    }
+
+    pub fn with_primary(mut self, primary: bool) -> Self {
+        self.set_primary(primary);
+        self
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    /// This query should be sent to a replica.
+    pub fn use_replica(&self) -> bool {
+        self.read && !self.primary
+    }
+
+    /// Replica this read is pinned to, if any.
+    pub fn replica(&self) -> Option<&str> {
+        if self.use_replica() {
+            self.replica.as_deref()
+        } else {
+            None
+        }
+    }
+
+    pub fn set_replica(&mut self, replica: Option<String>) {
+        self.replica = replica;
+    }
+
+    /// Send this query instead of the client's query.
+    pub fn set_tenant_filter(&mut self, query: Option<String>) {
+        self.tenant_filter = query;
+    }
+
+    /// Query with the tenant filter added to it, if it needed one.
+    pub fn tenant_filter(&self) -> Option<&str> {
+        self.tenant_filter.as_deref()
+    }
+
+    /// Expensive read that should go to an analytics replica.
+    pub fn is_analytics(&self) -> bool {
+        self.use_replica() && self.analytics
+    }
+
+    pub fn with_analytics(mut self, analytics: bool) -> Self {
+        self.analytics = analytics;
+        self
+    }
+
     pub fn explain(&self) -> Option<&ExplainTrace> {
         self.explain.as_ref()
     }
@@ -315,6 +410,14 @@ impl Route {
         self.explain.take()
     }
 
+    pub fn set_explain_analyze(&mut self, analyze: bool) {
+        self.explain_analyze = analyze;
+    }
+
+    pub fn is_explain_analyze(&self) -> bool {
+        self.explain_analyze
+    }
+
     pub fn with_rollback_savepoint(mut self, rollback: bool) -> Self {
         self.rollback_savepoint = rollback;
         self
@@ -389,6 +492,7 @@ pub enum ShardSource {
     #[default]
     DefaultUnset,
     Table(TableReason),
+    AdvisoryLock,
     RoundRobin(RoundRobinReason),
     SearchPath(String),
     Set,
@@ -410,6 +514,7 @@ pub enum RoundRobinReason {
     NotExecutable,
     NoTable,
     EmptyQuery,
+    ParseFailure,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
@@ -420,6 +525,7 @@ pub enum OverrideReason {
     OnlyOneShard,
     RewriteUpdate,
     CrossShardFunction,
+    Fastpath,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
@@ -458,6 +564,14 @@ impl ShardWithPriority {
         }
     }
 
+    /// Create new shard picked by the advisory lock key.
+    pub fn new_advisory_lock(shard: Shard) -> Self {
+        Self {
+            shard,
+            source: ShardSource::AdvisoryLock,
+        }
+    }
+
     pub fn new_table_omni(shard: Shard) -> Self {
         Self {
             shard,
@@ -487,6 +601,13 @@ impl ShardWithPriority {
         }
     }
 
+    pub fn new_override_fastpath(shard: Shard) -> Self {
+        Self {
+            shard,
+            source: ShardSource::Override(OverrideReason::Fastpath),
+        }
+    }
+
     pub fn new_override_dry_run(shard: Shard) -> Self {
         Self {
             shard,
@@ -550,6 +671,13 @@ impl ShardWithPriority {
         }
     }
 
+    pub fn new_rr_parse_failure(shard: Shard) -> Self {
+        Self {
+            shard,
+            source: ShardSource::RoundRobin(RoundRobinReason::ParseFailure),
+        }
+    }
+
     /// New SET-based routing.
     pub fn new_set(shard: Shard) -> Self {
         Self {
@@ -624,6 +752,20 @@ impl ShardsWithPriority {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_primary_read() {
+        let mut route = Route::read(ShardWithPriority::new_default_unset(Shard::Direct(0)))
+            .with_analytics(true);
+        assert!(route.use_replica());
+        assert!(route.is_analytics());
+
+        route.set_primary(true);
+        assert!(route.is_read());
+        assert!(!route.use_replica());
+        assert!(!route.is_analytics());
+        assert_eq!(route.to_string(), "shard=0, role=primary");
+    }
+
     #[test]
     fn test_shard_ord() {
         assert!(Shard::Direct(0) < Shard::All);
@@ -686,6 +828,18 @@ mod test {
         assert!(route.should_buffer());
     }
 
+    #[test]
+    fn test_should_buffer_group_by_only() {
+        let route = Route::select(
+            ShardWithPriority::new_table(Shard::All),
+            vec![],
+            Aggregate::new_group_by(&[0]),
+            Limit::default(),
+            None,
+        );
+        assert!(route.should_buffer());
+    }
+
     #[test]
     fn test_should_buffer_limit_only() {
         let route = Route::select(
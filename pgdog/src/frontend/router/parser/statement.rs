@@ -16,8 +16,8 @@ use pg_query::protobuf::RawStmt;
 use pg_query::{
     NodeEnum,
     protobuf::{
-        self, AConst, AExprKind, BoolExprType, DeleteStmt, FuncCall, InsertStmt, Integer, RangeVar,
-        SelectStmt, UpdateStmt, a_const::Val,
+        self, AConst, AExprKind, BoolExprType, DeleteStmt, FuncCall, InsertStmt, Integer, JoinType,
+        RangeVar, SelectStmt, UpdateStmt, a_const::Val,
     },
 };
 #[cfg(feature = "new_parser")]
@@ -420,6 +420,31 @@ impl AdvisoryLocks {
     pub fn has_unlock(&self) -> bool {
         self.locks.iter().any(|l| l.unlock)
     }
+
+    /// True if the statement releases locks without a known key,
+    /// e.g. `pg_advisory_unlock_all()`.
+    pub fn has_unlock_any(&self) -> bool {
+        self.locks.iter().any(|l| l.unlock && l.id.is_none())
+    }
+
+    /// Shard the locks are taken on, so the same key always
+    /// ends up on the same shard and locks work across the cluster.
+    ///
+    /// `None` if a key isn't known or the keys are on different shards.
+    pub fn shard(&self, shards: usize) -> Option<Shard> {
+        let mut shard = None;
+
+        for lock in &self.locks {
+            let lock_shard = bigint(lock.id?) as usize % shards;
+            match shard {
+                None => shard = Some(lock_shard),
+                Some(shard) if shard != lock_shard => return None,
+                Some(_) => (),
+            }
+        }
+
+        shard.map(Shard::Direct)
+    }
 }
 
 /// Accumulator shared across statement walkers — lets a single traversal
@@ -434,7 +459,7 @@ use crate::{
     frontend::router::{
         parser::{Shard, ee::ParserHooks},
         round_robin,
-        sharding::{ContextBuilder, SchemaSharder, ShardedTable, Tables},
+        sharding::{ContextBuilder, SchemaSharder, ShardedTable, Tables, bigint},
     },
     net::{Bind, parameter::ParameterValue},
 };
@@ -489,6 +514,14 @@ impl<'a> SearchContext<'a> {
         }
     }
 
+    /// Add aliases from tables joined with `UPDATE ... FROM` or `DELETE ... USING`.
+    #[cfg(feature = "new_parser")]
+    fn extract_aliases(&mut self, nodes: &'a list::NodeList) {
+        for node in nodes {
+            Self::extract_alias_from_node(&mut self.aliases, node);
+        }
+    }
+
     #[cfg(feature = "new_parser")]
     fn extract_alias_from_node(aliases: &mut HashMap<&'a str, Table<'a>>, node: Node<'a>) {
         match node {
@@ -1229,7 +1262,8 @@ impl<'a, 'b: 'a, 'c> StatementParser<'a, 'b, 'c> {
 
     #[cfg(not(feature = "new_parser"))]
     fn shard_update(&mut self, stmt: &'a UpdateStmt) -> Result<Option<Shard>, Error> {
-        let ctx = self.context_from_relation_old(&stmt.relation);
+        let mut ctx = self.context_from_relation_old(&stmt.relation);
+        ctx.extract_aliases(&stmt.from_clause);
         let result = self.search_update_stmt(stmt, &ctx)?;
 
         match result {
@@ -1241,7 +1275,8 @@ impl<'a, 'b: 'a, 'c> StatementParser<'a, 'b, 'c> {
 
     #[cfg(not(feature = "new_parser"))]
     fn shard_delete(&mut self, stmt: &'a DeleteStmt) -> Result<Option<Shard>, Error> {
-        let ctx = self.context_from_relation_old(&stmt.relation);
+        let mut ctx = self.context_from_relation_old(&stmt.relation);
+        ctx.extract_aliases(&stmt.using_clause);
         let result = self.search_delete_stmt(stmt, &ctx)?;
 
         match result {
@@ -1578,6 +1613,14 @@ impl<'a, 'b: 'a, 'c> StatementParser<'a, 'b, 'c> {
                 if let Some(ref right) = join.rarg {
                     results.push(self.select_search(right, ctx)?);
                 }
+                // Conditions of an inner join filter rows just like WHERE does,
+                // e.g. in UPDATE ... FROM and DELETE ... USING. Outer joins
+                // keep rows that don't match, so they can't pick a shard.
+                if join.jointype() == JoinType::JoinInner
+                    && let Some(ref quals) = join.quals
+                {
+                    results.push(self.select_search(quals, ctx)?);
+                }
 
                 results.retain(|result| result.is_match());
 
@@ -1629,8 +1672,16 @@ impl<'a, 'b: 'a, 'c> StatementParser<'a, 'b, 'c> {
 
         let ctx = match stmt {
             Node::SelectStmt(s) => SearchContext::from_from_clause(s.from_clause()),
-            Node::UpdateStmt(s) => self.context_from_relation(s.relation()),
-            Node::DeleteStmt(s) => self.context_from_relation(s.relation()),
+            Node::UpdateStmt(s) => {
+                let mut ctx = self.context_from_relation(s.relation());
+                ctx.extract_aliases(s.from_clause());
+                ctx
+            }
+            Node::DeleteStmt(s) => {
+                let mut ctx = self.context_from_relation(s.relation());
+                ctx.extract_aliases(s.using_clause());
+                ctx
+            }
             Node::InsertStmt(s) => {
                 return match self.search_insert_stmt(s).break_err()? {
                     Some(shard) => ControlFlow::Break(Ok(shard)),
@@ -2850,6 +2901,24 @@ mod test {
         assert!(result.unwrap().is_some());
     }
 
+    #[test]
+    fn test_update_from_with_key_in_join() {
+        let result = run_test(
+            "UPDATE sharded SET name = o.name FROM other o JOIN sharded s ON s.id = o.sharded_id AND s.id = 1 WHERE sharded.id = s.id",
+            None,
+        );
+        assert!(result.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_update_from_with_alias() {
+        let result = run_test(
+            "UPDATE sharded SET name = s.name FROM sharded s WHERE sharded.id = s.id AND s.id = 1",
+            None,
+        );
+        assert!(result.unwrap().is_some());
+    }
+
     // DELETE statement tests
 
     #[test]
@@ -2889,6 +2958,16 @@ mod test {
         assert!(result.unwrap().is_some());
     }
 
+    #[test]
+    fn test_delete_using_with_key_in_join() {
+        let bind = Bind::new_params("", &[Parameter::new(b"1")]);
+        let result = run_test(
+            "DELETE FROM sharded USING other o JOIN sharded s ON s.id = o.sharded_id AND s.id = $1 WHERE sharded.id = s.id",
+            Some(&bind),
+        );
+        assert!(result.unwrap().is_some());
+    }
+
     #[test]
     fn test_delete_with_cte() {
         let result = run_test(
@@ -12,6 +12,7 @@ use pg_raw_parse::{Node, nodes};
 
 use super::{Error, Schema};
 use crate::util::escape_identifier;
+use pgdog_config::system_catalogs;
 
 /// Table name in a query.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Hash, Eq)]
@@ -47,6 +48,14 @@ impl<'a> Table<'a> {
     pub(crate) fn schema(&self) -> Option<Schema<'a>> {
         self.schema.map(|s| s.into())
     }
+
+    /// Table is a system catalog, e.g. `pg_class` or `information_schema.columns`.
+    pub(crate) fn is_system_catalog(&self) -> bool {
+        match self.schema {
+            Some(schema) => matches!(schema, "pg_catalog" | "information_schema"),
+            None => system_catalogs().contains(self.name),
+        }
+    }
 }
 
 #[cfg(feature = "new_parser")]
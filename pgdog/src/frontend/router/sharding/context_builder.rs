@@ -4,11 +4,7 @@
 //! to a shard number, given a sharded mapping in pgdog.toml.
 //!
 use crate::frontend::router::sharding::mapping::MappingResolver;
-use crate::{
-    backend::ShardingSchema,
-    config::{DataType, Hasher as HasherConfig},
-    frontend::router::sharding::ShardedTable,
-};
+use crate::{backend::ShardingSchema, config::DataType, frontend::router::sharding::ShardedTable};
 
 use super::{Centroids, Context, Data, Error, Hasher, Operator, Value};
 
@@ -38,10 +34,7 @@ impl<'a> ContextBuilder<'a> {
             probes: table.centroid_probes,
             operator: None,
             value: None,
-            hasher: match table.hasher {
-                HasherConfig::Sha1 => Hasher::Sha1,
-                HasherConfig::Postgres => Hasher::Postgres,
-            },
+            hasher: Hasher::new(&table.hasher, table.hasher_seed),
             mapping: MappingResolver::new(&table.mapping),
         }
     }
@@ -63,7 +56,7 @@ impl<'a> ContextBuilder<'a> {
                     probes: 0,
                     centroids: None,
                     operator: None,
-                    hasher: Hasher::Postgres,
+                    hasher: Hasher::new(&common_mapping.hasher, common_mapping.hasher_seed),
                     mapping: MappingResolver::new(&common_mapping.mapping),
                 })
             }
@@ -158,7 +151,10 @@ mod test {
 
     use crate::{
         backend::ShardedTables,
-        config::{FlexibleType, ShardedMappingConfig, ShardedMappingList, ShardedMappingRange},
+        config::{
+            FlexibleType, Hasher as HasherConfig, ShardedMappingConfig, ShardedMappingList,
+            ShardedMappingRange,
+        },
         frontend::router::parser::Shard,
     };
 
@@ -190,6 +186,34 @@ mod test {
         assert_eq!(shard, Shard::Direct(1));
     }
 
+    #[test]
+    fn test_hasher_seed() {
+        for (seed, expected) in [(0, 0), (20141025, 1)] {
+            let schema = ShardingSchema {
+                shards: 2,
+                tables: ShardedTables::new(
+                    vec![ShardedTable {
+                        data_type: DataType::Bigint,
+                        hasher: HasherConfig::Xxhash,
+                        hasher_seed: seed,
+                        ..Default::default()
+                    }],
+                    vec![],
+                    false,
+                    SystemCatalogsBehavior::default(),
+                ),
+                ..Default::default()
+            };
+
+            let ctx = ContextBuilder::infer_from_from_and_config("1", &schema)
+                .unwrap()
+                .shards(2)
+                .build()
+                .unwrap();
+            assert_eq!(ctx.apply().unwrap(), Shard::Direct(expected));
+        }
+    }
+
     #[test]
     fn test_range() {
         let schema = ShardingSchema {
@@ -2,34 +2,84 @@
 
 use sha1::{Digest, Sha1};
 use uuid::Uuid;
+use xxhash_rust::xxh64::xxh64;
 
-use super::{bigint, uuid, varchar};
+use super::{array, bigint, uuid, varchar};
+use crate::config::Hasher as HasherConfig;
+use crate::net::messages::{Array, Format};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Hasher {
     Postgres,
     Sha1,
+    Modulo,
+    Crc32,
+    /// xxHash64 with the seed.
+    Xxhash(u64),
 }
 
 impl Hasher {
+    /// Hasher configured for a sharded table.
+    pub fn new(config: &HasherConfig, seed: u64) -> Self {
+        match config {
+            HasherConfig::Postgres => Hasher::Postgres,
+            HasherConfig::Sha1 => Hasher::Sha1,
+            HasherConfig::Modulo => Hasher::Modulo,
+            HasherConfig::Crc32 => Hasher::Crc32,
+            HasherConfig::Xxhash => Hasher::Xxhash(seed),
+        }
+    }
+
     pub fn bigint(&self, value: i64) -> u64 {
         match self {
             Hasher::Postgres => bigint(value),
             Hasher::Sha1 => Self::sha1(itoa::Buffer::new().format(value).as_bytes()),
+            // Taken modulo the number of shards by the caller.
+            Hasher::Modulo => value.unsigned_abs(),
+            Hasher::Crc32 | Hasher::Xxhash(_) => {
+                self.bytes(itoa::Buffer::new().format(value).as_bytes())
+            }
         }
     }
 
     pub fn uuid(&self, value: Uuid) -> u64 {
         match self {
-            Hasher::Postgres => uuid(value),
+            Hasher::Postgres | Hasher::Modulo => uuid(value),
             Hasher::Sha1 => Self::sha1(value.as_bytes()),
+            // Hashed as text, the way applications usually see UUIDs.
+            Hasher::Crc32 | Hasher::Xxhash(_) => self.bytes(
+                value
+                    .hyphenated()
+                    .encode_lower(&mut Uuid::encode_buffer())
+                    .as_bytes(),
+            ),
         }
     }
 
     pub fn varchar(&self, value: &[u8]) -> u64 {
         match self {
-            Hasher::Postgres => varchar(value),
-            Hasher::Sha1 => Self::sha1(value),
+            Hasher::Postgres | Hasher::Modulo => varchar(value),
+            Hasher::Sha1 | Hasher::Crc32 | Hasher::Xxhash(_) => self.bytes(value),
+        }
+    }
+
+    pub fn array(&self, value: &Array) -> Option<u64> {
+        match self {
+            Hasher::Postgres | Hasher::Modulo => array(value),
+            Hasher::Sha1 | Hasher::Crc32 | Hasher::Xxhash(_) => value
+                .encode(Format::Text)
+                .ok()
+                .map(|text| self.bytes(&text)),
+        }
+    }
+
+    /// Hash bytes with `sha1`, `crc32` or `xxhash`, which
+    /// don't depend on the data type.
+    fn bytes(&self, bytes: &[u8]) -> u64 {
+        match self {
+            Hasher::Crc32 => crc32fast::hash(bytes) as u64,
+            Hasher::Xxhash(seed) => xxh64(bytes, *seed),
+            _ => Self::sha1(bytes),
         }
     }
 
@@ -61,4 +111,55 @@ mod test {
             assert_eq!(shard, *expected as u64);
         }
     }
+
+    #[test]
+    fn test_crc32_hash() {
+        // zlib.crc32(str(id).encode()) % 12
+        let shards = [
+            5, 11, 1, 7, 4, 10, 4, 6, 11, 9, 9, 3, 9, 11, 0, 10, 0, 2, 3, 1,
+        ];
+
+        for (id, expected) in shards.iter().enumerate() {
+            assert_eq!(Hasher::Crc32.bigint(id as i64) % 12, *expected);
+        }
+
+        assert_eq!(Hasher::Crc32.varchar(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            Hasher::Crc32.varchar(b"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"),
+            0xAD7E_0440
+        );
+
+        // UUIDs hash the same as their text.
+        let uuid = Uuid::parse_str("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11").unwrap();
+        assert_eq!(Hasher::Crc32.uuid(uuid), 0xAD7E_0440);
+    }
+
+    #[test]
+    fn test_xxhash_hash() {
+        // xxhash.xxh64_intdigest(str(id), seed) % 12
+        let unseeded = [8, 0, 3, 8, 1, 1, 3, 3, 0, 6, 3, 10, 2, 8, 2, 8, 6, 8, 2, 3];
+        let seeded = [8, 7, 6, 2, 5, 1, 6, 6, 3, 8, 5, 7, 10, 0, 5, 5, 0, 9, 1, 6];
+
+        for (id, expected) in unseeded.iter().enumerate() {
+            assert_eq!(Hasher::Xxhash(0).bigint(id as i64) % 12, *expected);
+        }
+
+        for (id, expected) in seeded.iter().enumerate() {
+            assert_eq!(Hasher::Xxhash(20141025).bigint(id as i64) % 12, *expected);
+        }
+
+        let text = "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11";
+        let uuid = Uuid::parse_str(text).unwrap();
+        assert_eq!(
+            Hasher::Xxhash(7).uuid(uuid),
+            Hasher::Xxhash(7).varchar(text.as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_modulo_hash() {
+        for id in 1..100i64 {
+            assert_eq!(Hasher::Modulo.bigint(id) % 3, id as u64 % 3);
+        }
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
     backend::ShardingSchema,
     config::DataType,
     net::{
-        messages::{Format, FromDataType, ParameterWithFormat, Vector},
+        messages::{Array, Datum, Format, FromDataType, ParameterWithFormat, Vector},
         vector::str_to_vector,
     },
 };
@@ -60,6 +60,28 @@ pub fn varchar(s: &[u8]) -> u64 {
     unsafe { ffi::hash_combine64(0, ffi::hash_bytes_extended(s.as_ptr(), s.len() as i64)) }
 }
 
+/// Hash a one-dimensional `BIGINT[]` or `TEXT[]`, the same way
+/// Postgres hashes arrays for hash partitioning.
+pub fn array(array: &Array) -> Option<u64> {
+    let mut hash: u64 = 1;
+
+    for element in array.elements() {
+        let element = match element {
+            Datum::Null => 0,
+            Datum::Bigint(value) => unsafe { ffi::hashint8extended(*value) },
+            Datum::Integer(value) => unsafe { ffi::hashint8extended(*value as i64) },
+            Datum::SmallInt(value) => unsafe { ffi::hashint8extended(*value as i64) },
+            Datum::Text(value) => unsafe {
+                ffi::hash_bytes_extended(value.as_ptr(), value.len() as i64)
+            },
+            _ => return None,
+        };
+        hash = (hash << 5).wrapping_sub(hash).wrapping_add(element);
+    }
+
+    Some(unsafe { ffi::hash_combine64(0, hash) })
+}
+
 /// Shard a string value, parsing out a BIGINT, UUID, or vector.
 ///
 /// TODO: This is really not great, we should pass in the type oid
@@ -112,6 +134,13 @@ pub(crate) fn shard_value(
             })
             .unwrap_or(Shard::All),
         DataType::Varchar => Shard::Direct(varchar(value.as_bytes()) as usize % shards),
+        DataType::BigintArray | DataType::VarcharArray => Value::new(value, *data_type)
+            .array()
+            .ok()
+            .flatten()
+            .and_then(|v| array(&v))
+            .map(|hash| Shard::Direct(hash as usize % shards))
+            .unwrap_or(Shard::All),
     }
 }
 
@@ -140,6 +169,13 @@ pub(crate) fn shard_binary(
             })
             .unwrap_or(Shard::All),
         DataType::Varchar => Shard::Direct(varchar(bytes) as usize % shards),
+        DataType::BigintArray | DataType::VarcharArray => Value::new(bytes, *data_type)
+            .array()
+            .ok()
+            .flatten()
+            .and_then(|v| array(&v))
+            .map(|hash| Shard::Direct(hash as usize % shards))
+            .unwrap_or(Shard::All),
     }
 }
 
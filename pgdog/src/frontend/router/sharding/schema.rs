@@ -9,6 +9,8 @@ pub struct SchemaSharder {
     catch_all: bool,
     current: Option<Shard>,
     schema: Option<String>,
+    /// Every sharded (non-default) schema resolved, in order.
+    resolved: Vec<(Shard, String)>,
 }
 
 impl SchemaSharder {
@@ -21,6 +23,10 @@ impl SchemaSharder {
         let check = schemas.get(schema);
         if let Some(schema) = check {
             let catch_all = schema.is_default();
+            if !catch_all {
+                self.resolved
+                    .push((schema.shard().into(), schema.name().to_owned()));
+            }
             let set =
                 catch_all && self.current.is_none() || self.catch_all || self.current.is_none();
             if set {
@@ -54,6 +60,40 @@ impl SchemaSharder {
         }
     }
 
+    /// Sharded schemas resolved so far map to different shards.
+    ///
+    /// Returns the shards they map to and their names, separated by commas.
+    pub fn conflict(&self) -> Option<(Shard, String)> {
+        let (first, _) = self.resolved.first()?;
+        if self.resolved.iter().all(|(shard, _)| shard == first) {
+            return None;
+        }
+
+        let shard = if self.resolved.iter().any(|(shard, _)| shard.is_all()) {
+            Shard::All
+        } else {
+            let mut shards = self
+                .resolved
+                .iter()
+                .filter_map(|(shard, _)| match shard {
+                    Shard::Direct(shard) => Some(*shard),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            shards.sort_unstable();
+            shards.dedup();
+            Shard::Multi(shards)
+        };
+        let schemas = self
+            .resolved
+            .iter()
+            .map(|(_, schema)| schema.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Some((shard, schemas))
+    }
+
     pub fn get(&self) -> Option<(Shard, &str)> {
         if let Some(current) = self.current.as_ref()
             && let Some(schema) = self.schema.as_ref()
@@ -147,5 +187,37 @@ mod tests {
         );
         assert_eq!(result.as_ref().map(|(_, name)| *name), Some("sales"));
         assert!(!sharder.catch_all);
+        assert!(sharder.conflict().is_none());
+    }
+
+    #[test]
+    fn test_conflict_between_sharded_schemas() {
+        let schema = |name: &str, shard| ShardedSchema {
+            database: "test".to_string(),
+            name: Some(name.to_string()),
+            shard,
+            all: false,
+        };
+        let schemas = ShardedSchemas::new(vec![
+            schema("sales", 1),
+            schema("reports", 1),
+            schema("inventory", 2),
+        ]);
+
+        let mut sharder = SchemaSharder::default();
+        sharder.resolve(Some(Schema { name: "sales" }), &schemas);
+        sharder.resolve(Some(Schema { name: "reports" }), &schemas);
+        assert!(sharder.conflict().is_none());
+
+        sharder.resolve(Some(Schema { name: "inventory" }), &schemas);
+        assert_eq!(
+            sharder.conflict(),
+            Some((
+                Shard::Multi(vec![1, 2]),
+                "sales,reports,inventory".to_string()
+            ))
+        );
+        // The first one is still used by default.
+        assert_eq!(sharder.get(), Some((Shard::Direct(1), "sales")));
     }
 }
@@ -20,6 +20,7 @@ pub struct ShardedTable {
     pub data_type: DataType,
     pub centroid_probes: usize,
     pub hasher: Hasher,
+    pub hasher_seed: u64,
     pub mapping: Option<Mapping>,
 }
 
@@ -5,7 +5,7 @@ use uuid::Uuid;
 use super::{Error, Hasher};
 use crate::{
     config::DataType,
-    net::{Format, FromDataType, ParameterWithFormat, Vector},
+    net::{Array, Format, FromDataType, ParameterWithFormat, Vector},
 };
 use bytes::Bytes;
 
@@ -98,6 +98,9 @@ impl<'a> Value<'a> {
                 Data::Binary(data) => from_utf8(data).is_ok(),
                 Data::Integer(_) => false,
             },
+            DataType::BigintArray | DataType::VarcharArray => {
+                matches!(self.array(), Ok(Some(_)))
+            }
 
             _ => false,
         }
@@ -153,6 +156,24 @@ impl<'a> Value<'a> {
         Ok(Some(uuid))
     }
 
+    /// Decode a one-dimensional array.
+    pub fn array(&self) -> Result<Option<Array>, Error> {
+        let element_oid = match self.data_type {
+            DataType::BigintArray => 20,
+            DataType::VarcharArray => 25,
+            _ => return Ok(None),
+        };
+
+        let array = match self.data {
+            Data::Text(text) => Array::decode_typed(text.as_bytes(), Format::Text, element_oid)?,
+            // Element type is in the header, e.g. INTEGER[] or VARCHAR[].
+            Data::Binary(data) => Array::decode_typed(data, Format::Binary, 0)?,
+            Data::Integer(_) => return Ok(None),
+        };
+
+        Ok(Some(array))
+    }
+
     pub fn hash(&self, hasher: Hasher) -> Result<Option<u64>, Error> {
         match self.data_type {
             DataType::Bigint => match self.data {
@@ -183,6 +204,9 @@ impl<'a> Value<'a> {
                 Data::Text(s) => Ok(Some(hasher.varchar(s.as_bytes()))),
                 Data::Integer(_) => Ok(None),
             },
+            DataType::BigintArray | DataType::VarcharArray => {
+                Ok(self.array()?.and_then(|array| hasher.array(&array)))
+            }
         }
     }
 }
@@ -205,4 +229,32 @@ mod tests {
         assert_eq!(value.uuid()?, Some(expected_uuid));
         Ok(())
     }
+
+    #[test]
+    fn test_array_hash() -> Result<(), Error> {
+        for (text, data_type, expected) in [
+            ("{1,2,3}", DataType::BigintArray, 9099747140596091126),
+            ("{a,b,c}", DataType::VarcharArray, 6246287783669008941),
+        ] {
+            let value = Value::new(text, data_type);
+            assert!(value.valid());
+            let array = value.array()?.unwrap();
+            assert_eq!(array.elements().len(), 3);
+
+            let binary = array.encode(Format::Binary)?;
+            let from_binary = Value::new(&binary, data_type);
+            assert_eq!(from_binary.array()?, Some(array.clone()));
+            assert_eq!(array.encode(Format::Text)?, text.as_bytes());
+
+            assert_eq!(value.hash(Hasher::Postgres)?, Some(expected));
+            assert_eq!(from_binary.hash(Hasher::Postgres)?, Some(expected));
+            assert_eq!(value.hash(Hasher::Sha1)?, from_binary.hash(Hasher::Sha1)?);
+        }
+
+        // Multi-dimensional arrays aren't supported.
+        assert!(!Value::new("{{1,2},{3,4}}", DataType::BigintArray).valid());
+        assert!(Value::new("{1,2,3}", DataType::Bigint).array()?.is_none());
+
+        Ok(())
+    }
 }
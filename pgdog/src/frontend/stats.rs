@@ -64,6 +64,10 @@ impl Stats {
         self.state = State::Idle;
     }
 
+    pub(super) fn slow_query(&mut self) {
+        self.slow_queries += 1;
+    }
+
     pub(super) fn query(&mut self) {
         let now = Instant::now();
         self.queries += 1;
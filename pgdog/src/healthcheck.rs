@@ -1,5 +1,5 @@
+use std::collections::HashMap;
 use std::convert::Infallible;
-use std::net::SocketAddr;
 
 use http_body_util::Full;
 use hyper::body::Bytes;
@@ -12,12 +12,12 @@ use tokio::select;
 use tracing::info;
 
 use crate::backend::databases::{Databases, databases};
+use crate::config::Role;
 use crate::tasks;
 
-pub async fn server(port: u16) -> std::io::Result<()> {
-    info!("healthcheck endpoint http://0.0.0.0:{}", port);
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    let listener = TcpListener::bind(addr).await?;
+pub async fn server(host: &str, port: u16) -> std::io::Result<()> {
+    info!("healthcheck endpoint http://{}:{}", host, port);
+    let listener = TcpListener::bind((host, port)).await?;
     let shutdown = tasks::shutdown_signal();
 
     loop {
@@ -46,13 +46,9 @@ pub async fn server(port: u16) -> std::io::Result<()> {
 }
 
 async fn healthcheck(
-    _: Request<hyper::body::Incoming>,
+    request: Request<hyper::body::Incoming>,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
-    let databases = databases();
-    let broken = broken(&databases);
-
-    let response = if broken { "down" } else { "up" };
-    let status = if broken { 502 } else { 200 };
+    let (status, response) = status(request.uri().path(), &databases());
 
     let response = Response::builder()
         .header(hyper::header::CONTENT_TYPE, "text/plain; charset=utf-8")
@@ -63,6 +59,54 @@ async fn healthcheck(
     Ok(response)
 }
 
+/// Status code and body returned for the requested path.
+fn status(path: &str, databases: &Databases) -> (u16, &'static str) {
+    match path {
+        // The process is running and serving HTTP.
+        "/health/live" => (200, "live"),
+        "/health/ready" => {
+            if ready(databases) {
+                (200, "ready")
+            } else {
+                (503, "not ready")
+            }
+        }
+        _ => {
+            if broken(databases) {
+                (502, "down")
+            } else {
+                (200, "up")
+            }
+        }
+    }
+}
+
+/// Every configured primary has at least one healthy pool.
+/// Multiple users connecting to the same primary get their own pools.
+fn ready(databases: &Databases) -> bool {
+    let mut primaries: HashMap<(String, u16, String), bool> = HashMap::new();
+
+    for shard in databases
+        .all()
+        .values()
+        .flat_map(|cluster| cluster.shards())
+    {
+        for (role, pool) in shard.pools_with_roles() {
+            if role != Role::Primary {
+                continue;
+            }
+
+            let addr = pool.addr();
+            let healthy = primaries
+                .entry((addr.host.clone(), addr.port, addr.database_name.clone()))
+                .or_default();
+            *healthy |= pool.healthy();
+        }
+    }
+
+    primaries.values().all(|healthy| *healthy)
+}
+
 fn broken(databases: &Databases) -> bool {
     let mut pools = databases
         .all()
@@ -77,9 +121,44 @@ fn broken(databases: &Databases) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::databases::from_config;
+    use crate::config::{config, load_test};
 
     #[test]
     fn no_pools_is_healthy() {
         assert!(!broken(&Databases::default()));
+        assert!(ready(&Databases::default()));
+    }
+
+    fn toggle(databases: &Databases, healthy: bool) {
+        for shard in databases
+            .all()
+            .values()
+            .flat_map(|cluster| cluster.shards())
+        {
+            for pool in shard.pools() {
+                pool.inner().health.toggle(healthy);
+            }
+        }
+    }
+
+    #[test]
+    fn readiness_follows_primary_pools() {
+        load_test();
+        let databases = from_config(&config());
+
+        assert_eq!(status("/health/live", &databases), (200, "live"));
+        assert_eq!(status("/health/ready", &databases), (200, "ready"));
+
+        toggle(&databases, false);
+
+        assert_eq!(status("/health/live", &databases), (200, "live"));
+        assert_eq!(status("/health/ready", &databases), (503, "not ready"));
+        assert_eq!(status("/", &databases), (502, "down"));
+
+        toggle(&databases, true);
+
+        assert_eq!(status("/health/ready", &databases), (200, "ready"));
+        assert_eq!(status("/", &databases), (200, "up"));
     }
 }
@@ -34,9 +34,10 @@ use pgdog_config::{General, LogFormat};
 use tracing::level_filters::LevelFilter;
 use tracing::subscriber::Interest;
 use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::layer::{Context, Filter};
 use tracing_subscriber::registry::LookupSpan;
-use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+use tracing_subscriber::{EnvFilter, Layer, fmt, prelude::*};
 use tracing_throttle::{Policy, SuppressionSummary, TracingRateLimitLayer};
 
 #[cfg(test)]
@@ -184,33 +185,111 @@ fn init_logger(general: Option<&General>) {
         .map(|general| general.log_format)
         .unwrap_or_default();
 
+    let format = format_layer(log_format, std::io::stderr, std::io::stderr().is_terminal())
+        .with_filter(throttle);
+
+    let _ = tracing_subscriber::registry()
+        .with(format)
+        .with(filter)
+        .try_init();
+}
+
+/// Build the log formatter for the configured format.
+///
+/// Event fields, e.g. `database` or `client_addr`, are written
+/// as separate JSON keys when using one of the JSON formats.
+fn format_layer<S, W>(
+    log_format: LogFormat,
+    writer: W,
+    ansi: bool,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
     let format = fmt::layer()
-        .with_ansi(std::io::stderr().is_terminal())
-        .with_writer(std::io::stderr)
+        .with_ansi(ansi)
+        .with_writer(writer)
         .with_file(false);
     #[cfg(not(debug_assertions))]
     let format = format.with_target(false);
 
     match log_format {
-        LogFormat::Text => {
-            let format = format.with_filter(throttle);
-            let _ = tracing_subscriber::registry()
-                .with(format)
-                .with(filter)
-                .try_init();
+        LogFormat::Text => format.boxed(),
+        LogFormat::Json => format.json().with_current_span(false).boxed(),
+        LogFormat::JsonFlattened => format
+            .json()
+            .with_current_span(false)
+            .flatten_event(true)
+            .boxed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
         }
-        LogFormat::Json | LogFormat::JsonFlattened => {
-            let format = format.json().with_current_span(false);
-            let format = match log_format {
-                LogFormat::JsonFlattened => format.flatten_event(true),
-                _ => format,
-            };
-            let format = format.with_filter(throttle);
-
-            let _ = tracing_subscriber::registry()
-                .with(format)
-                .with(filter)
-                .try_init();
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
         }
     }
+
+    fn capture(log_format: LogFormat) -> serde_json::Value {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(format_layer(
+            log_format,
+            move || writer.clone(),
+            false,
+        ));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(
+                duration_ms = 12.5,
+                shard = "0",
+                database = "pgdog",
+                client_addr = "127.0.0.1:5432",
+                "slow query"
+            );
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line = output.lines().next().expect("log line");
+        serde_json::from_str(line).unwrap()
+    }
+
+    #[test]
+    fn test_json_log_format() {
+        let line = capture(LogFormat::Json);
+        assert_eq!(line["level"], "WARN");
+        assert!(line["timestamp"].is_string());
+
+        let fields = &line["fields"];
+        assert_eq!(fields["message"], "slow query");
+        assert_eq!(fields["duration_ms"], 12.5);
+        assert_eq!(fields["shard"], "0");
+        assert_eq!(fields["database"], "pgdog");
+        assert_eq!(fields["client_addr"], "127.0.0.1:5432");
+    }
+
+    #[test]
+    fn test_json_flattened_log_format() {
+        let line = capture(LogFormat::JsonFlattened);
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["message"], "slow query");
+        assert_eq!(line["database"], "pgdog");
+        assert!(line.get("fields").is_none());
+    }
 }
@@ -5,7 +5,7 @@ use std::path::Path;
 use std::process::exit;
 
 use clap::Parser;
-use pgdog::backend::databases;
+use pgdog::backend::{databases, service_discovery};
 use pgdog::cli::{self, Commands};
 use pgdog::config::{self, config};
 use pgdog::frontend::client::query_engine::two_pc::Manager;
@@ -113,6 +113,9 @@ async fn pgdog(command: Option<Commands>) -> Result<(), Box<dyn std::error::Erro
     // are async, so doing this after Tokio launched seems prudent.
     net::tls::load()?;
 
+    // Find shards using DNS SRV records.
+    service_discovery::init().await?;
+
     // Load databases and connect if needed.
     databases::init()?;
 
@@ -135,8 +138,9 @@ async fn pgdog(command: Option<Commands>) -> Result<(), Box<dyn std::error::Erro
     }
 
     if let Some(healthcheck_port) = general.healthcheck_port {
+        let healthcheck_host = general.healthcheck_host.clone();
         pgdog::tasks::spawn("http healthcheck server", async move {
-            healthcheck::server(healthcheck_port).await
+            healthcheck::server(&healthcheck_host, healthcheck_port).await
         });
     }
 
@@ -157,7 +161,9 @@ async fn pgdog(command: Option<Commands>) -> Result<(), Box<dyn std::error::Erro
                 if let Some(ref wal_dir) = general.two_phase_commit_wal_dir {
                     Manager::get().enable_wal(wal_dir).await;
                 } else {
-                    warn!("[2pc] wal disabled, 2pc will run without durability")
+                    warn!(
+                        "[2pc] wal disabled, 2pc will run without durability and orphaned transactions won't be recovered"
+                    )
                 }
             }
 
@@ -22,6 +22,8 @@ impl From<&RowDescription> for Decoder {
 pub struct Decoder {
     formats: Vec<Format>,
     rd: RowDescription,
+    /// Result formats were set by Bind.
+    bound: bool,
 }
 
 impl Decoder {
@@ -30,13 +32,12 @@ impl Decoder {
         Self::default()
     }
 
-    /// Infer types from Bind, if any provided.
+    /// Infer types and result formats from Bind.
     pub fn bind(&mut self, bind: &Bind) {
-        // Only override RowDescription formats if
-        // Bind specifies formats.
-        if !bind.codes().is_empty() {
-            self.formats = bind.codes().to_vec();
-        }
+        // Bind decides the result formats. RowDescription returned
+        // by Describe for a statement always reports text.
+        self.formats = bind.result_formats();
+        self.bound = true;
 
         if self.rd.is_empty()
             && let Some(rd) = PreparedStatements::global()
@@ -49,8 +50,9 @@ impl Decoder {
 
     /// Infer types from RowDescription, if any.
     pub fn row_description(&mut self, rd: &RowDescription) {
-        let formats = rd.fields.iter().map(|f| f.format()).collect();
-        self.formats = formats;
+        if !self.bound {
+            self.formats = rd.fields.iter().map(|f| f.format()).collect();
+        }
         self.rd = rd.clone();
     }
 
@@ -67,3 +69,37 @@ impl Decoder {
         &self.rd
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::net::Field;
+
+    #[test]
+    fn test_bind_result_formats() {
+        // Describe for a statement always reports text.
+        let rd = RowDescription::new(&[Field::bigint("id"), Field::numeric("sum")]);
+        let bind = Bind::new_params_codes_results("", &[], &[Format::Text], &[1]);
+
+        let mut decoder = Decoder::from(&bind);
+        decoder.row_description(&rd);
+
+        assert_eq!(decoder.format(0), Format::Binary);
+        assert_eq!(decoder.format(1), Format::Binary);
+
+        let bind = Bind::new_params_codes_results("", &[], &[Format::Binary], &[0, 1]);
+        let mut decoder = Decoder::from(&rd);
+        decoder.bind(&bind);
+
+        assert_eq!(decoder.format(0), Format::Text);
+        assert_eq!(decoder.format(1), Format::Binary);
+    }
+
+    #[test]
+    fn test_row_description_formats() {
+        let rd = RowDescription::new(&[Field::bigint("id")]);
+        let decoder = Decoder::from(&rd);
+
+        assert_eq!(decoder.format(0), Format::Text);
+    }
+}
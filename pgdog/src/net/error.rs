@@ -49,6 +49,9 @@ pub enum Error {
     #[error("{0}")]
     Rustls(#[from] rustls::Error),
 
+    #[error("{0}")]
+    InvalidDnsName(#[from] rustls::pki_types::InvalidDnsNameError),
+
     #[error("\"{0}\" parameter is missing")]
     MissingParameter(String),
 
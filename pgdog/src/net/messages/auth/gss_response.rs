@@ -0,0 +1,45 @@
+//! GSSResponse (F) message.
+
+use super::super::code;
+use super::super::prelude::*;
+
+/// GSSAPI token sent by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GssResponse {
+    /// Token produced by the client's GSSAPI library.
+    pub token: Bytes,
+}
+
+impl GssResponse {
+    /// Create new GSSAPI response.
+    pub fn new(token: impl Into<Bytes>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+impl FromBytes for GssResponse {
+    fn from_bytes(mut bytes: Bytes) -> Result<Self, Error> {
+        code!(bytes, 'p');
+        let _len = bytes.get_i32();
+
+        Ok(Self {
+            token: bytes.copy_to_bytes(bytes.remaining()),
+        })
+    }
+}
+
+impl ToBytes for GssResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut payload = Payload::named(self.code());
+        payload.put(self.token.clone());
+        payload.freeze()
+    }
+}
+
+impl Protocol for GssResponse {
+    fn code(&self) -> char {
+        'p'
+    }
+}
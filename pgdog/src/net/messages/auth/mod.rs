@@ -6,7 +6,9 @@ use super::{code, prelude::*};
 
 use super::FromBytes;
 
+pub mod gss_response;
 pub mod password;
+pub use gss_response::GssResponse;
 pub use password::Password;
 
 /// Authentication messages.
@@ -24,6 +26,12 @@ pub enum Authentication {
     Md5(Bytes),
     /// AuthenticationCleartextPassword (B).
     ClearTextPassword,
+    /// AuthenticationGSS (B).
+    Gssapi,
+    /// AuthenticationGSSContinue (B).
+    GssapiContinue(Bytes),
+    /// AuthenticationSSPI (B).
+    Sspi,
 }
 
 impl Authentication {
@@ -56,6 +64,11 @@ impl FromBytes for Authentication {
                 bytes.copy_to_slice(&mut salt);
                 Ok(Authentication::Md5(Bytes::from(salt)))
             }
+            7 => Ok(Authentication::Gssapi),
+            8 => Ok(Authentication::GssapiContinue(
+                bytes.copy_to_bytes(bytes.remaining()),
+            )),
+            9 => Ok(Authentication::Sspi),
             10 => {
                 let mechanism = c_string_buf(&mut bytes);
                 Ok(Authentication::Sasl(mechanism))
@@ -102,6 +115,23 @@ impl ToBytes for Authentication {
                 payload.freeze()
             }
 
+            Authentication::Gssapi => {
+                payload.put_i32(7);
+                payload.freeze()
+            }
+
+            Authentication::GssapiContinue(data) => {
+                payload.put_i32(8);
+                payload.put(data.clone());
+
+                payload.freeze()
+            }
+
+            Authentication::Sspi => {
+                payload.put_i32(9);
+                payload.freeze()
+            }
+
             Authentication::Sasl(mechanism) => {
                 payload.put_i32(10);
                 payload.put_string(mechanism);
@@ -126,3 +156,23 @@ impl ToBytes for Authentication {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gssapi_round_trip() {
+        let auth = Authentication::from_bytes(Authentication::Gssapi.to_bytes()).unwrap();
+        assert!(matches!(auth, Authentication::Gssapi));
+
+        let auth = Authentication::from_bytes(Authentication::Sspi.to_bytes()).unwrap();
+        assert!(matches!(auth, Authentication::Sspi));
+
+        let token = Bytes::from_static(&[0x60, 0x00, 0x01, 0xff]);
+        let auth =
+            Authentication::from_bytes(Authentication::GssapiContinue(token.clone()).to_bytes())
+                .unwrap();
+        assert!(matches!(auth, Authentication::GssapiContinue(data) if data == token));
+    }
+}
@@ -209,6 +209,43 @@ impl Bind {
         &self.codes
     }
 
+    /// Formats requested by the client for result columns.
+    pub fn result_formats(&self) -> Vec<Format> {
+        self.results
+            .chunks_exact(2)
+            .map(|code| match i16::from_be_bytes([code[0], code[1]]) {
+                0 => Format::Text,
+                _ => Format::Binary,
+            })
+            .collect()
+    }
+
+    /// Request all result columns in binary format, if the client
+    /// already wants them that way. Returns true if it does.
+    pub fn binary_results(&mut self) -> bool {
+        let formats = self.result_formats();
+
+        if formats.is_empty() || formats.contains(&Format::Text) {
+            return false;
+        }
+
+        // A single format code applies to all columns.
+        if formats.len() > 1 {
+            self.results = Bytes::from_static(&[0, 1]);
+            self.original = None;
+        }
+
+        true
+    }
+
+    /// Use the same portal and result formats as another Bind message,
+    /// so rows returned by the statement look the same to the client.
+    pub(crate) fn copy_results(&mut self, other: &Bind) {
+        self.portal = other.portal.clone();
+        self.results = other.results.clone();
+        self.original = None;
+    }
+
     pub fn new_statement(name: &str) -> Self {
         Self {
             statement: Bytes::from(name.to_string() + "\0"),
@@ -546,6 +583,25 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_binary_results() {
+        let mut bind = Bind::new_params_codes_results("", &[], &[], &[1, 1, 1]);
+        assert!(bind.binary_results());
+        assert_eq!(bind.result_formats(), vec![Format::Binary]);
+        let bytes = bind.to_bytes();
+        assert_eq!(
+            Bind::from_bytes(bytes).unwrap().result_formats(),
+            vec![Format::Binary]
+        );
+
+        for results in [&[][..], &[0], &[1, 0]] {
+            let mut bind = Bind::new_params_codes_results("", &[], &[], results);
+            let before = bind.to_bytes();
+            assert!(!bind.binary_results());
+            assert_eq!(bind.to_bytes(), before);
+        }
+    }
+
     #[test]
     fn test_set_param() {
         let mut bind = Bind::new_params("test", &[Parameter::new(b"10"), Parameter::new(b"5")]);
@@ -2,6 +2,8 @@
 //! read buffer for Postgres messages.
 
 use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 use bytes::{Buf, BytesMut};
 use pgdog_stats::MessageBufferStats;
@@ -12,10 +14,23 @@ use crate::net::stream::eof;
 use crate::util::sanitize_log_sample;
 
 use super::{Error, Message};
-use tracing::error;
+use tracing::{error, warn};
 
 const HEADER_SIZE: usize = 5;
 
+/// Reallocations by all message buffers.
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+/// Warn if a buffer is reallocated this many times
+/// within [`REALLOC_WARN_WINDOW`].
+const REALLOC_WARN_COUNT: usize = 100;
+const REALLOC_WARN_WINDOW: Duration = Duration::from_secs(60);
+
+/// Total number of reallocations by all message buffers.
+pub fn reallocs() -> usize {
+    REALLOCS.load(Ordering::Relaxed)
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct MessageBuffer {
     buffer: BytesMut,
@@ -24,6 +39,8 @@ pub struct MessageBuffer {
     /// If specified the messages exceeding this number
     /// will be rejected and cause fatal abruption.
     size_limit_block: Option<usize>,
+    /// Reallocations since the start of the current warning window.
+    recent_reallocs: Option<(Instant, usize)>,
 }
 
 impl MessageBuffer {
@@ -38,6 +55,7 @@ impl MessageBuffer {
                 ..Default::default()
             },
             size_limit_block,
+            recent_reallocs: None,
         }
     }
 
@@ -169,12 +187,37 @@ impl MessageBuffer {
             self.buffer = buffer;
             self.stats.reallocs += 1;
             self.stats.bytes_alloc = self.capacity; // Possibly undercounting.
+            REALLOCS.fetch_add(1, Ordering::Relaxed);
+            self.warn_reallocs(Instant::now());
             true
         } else {
             false
         }
     }
 
+    /// Buffer reallocated frequently means most messages don't fit
+    /// into its initial capacity.
+    fn warn_reallocs(&mut self, now: Instant) {
+        let (start, count) = match self.recent_reallocs {
+            Some((start, count)) if now.duration_since(start) < REALLOC_WARN_WINDOW => {
+                (start, count + 1)
+            }
+            _ => (now, 1),
+        };
+
+        if count >= REALLOC_WARN_COUNT {
+            warn!(
+                "message buffer reallocated {} times in {:.0?}, consider increasing memory.message_buffer [capacity: {}B]",
+                count,
+                now.duration_since(start),
+                self.capacity,
+            );
+            self.recent_reallocs = None;
+        } else {
+            self.recent_reallocs = Some((start, count));
+        }
+    }
+
     /// Get buffer stats.
     pub fn stats(&self) -> &MessageBufferStats {
         &self.stats
@@ -404,6 +447,60 @@ mod test {
         assert_eq!(buf.stats.reclaims, frees_before);
     }
 
+    async fn read_large_messages(capacity: usize) -> MessageBuffer {
+        let mut stream_data = Vec::new();
+        for i in 0..10 {
+            let query = "SELECT * FROM ".to_string() + &"x".repeat(10_000);
+            let msg = Parse::named(format!("large_{}", i), &query).to_bytes();
+            stream_data.extend_from_slice(&msg);
+        }
+
+        let mut cursor = Cursor::new(stream_data);
+        let mut buf = MessageBuffer::new(capacity, None);
+
+        for _ in 0..10 {
+            let msg = buf.read(&mut cursor).await.unwrap();
+            assert_eq!(msg.code(), 'P');
+            buf.shrink_to_fit();
+        }
+
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_reallocs_large_messages() {
+        let before = reallocs();
+
+        let small = read_large_messages(4096).await;
+        assert_eq!(small.stats().reallocs, 10);
+        assert!(reallocs() >= before + 10);
+
+        // Messages fit into the buffer, so it's never reallocated.
+        let large = read_large_messages(64 * 1024).await;
+        assert_eq!(large.stats().reallocs, 0);
+    }
+
+    #[test]
+    fn test_realloc_warning_window() {
+        let mut buf = MessageBuffer::new(4096, None);
+        let start = Instant::now();
+
+        for _ in 0..REALLOC_WARN_COUNT - 1 {
+            buf.warn_reallocs(start);
+        }
+        assert_eq!(buf.recent_reallocs, Some((start, REALLOC_WARN_COUNT - 1)));
+
+        // Window expired, counting starts over.
+        buf.warn_reallocs(start + REALLOC_WARN_WINDOW);
+        assert_eq!(buf.recent_reallocs, Some((start + REALLOC_WARN_WINDOW, 1)));
+
+        // Warned, counting starts over.
+        for _ in 0..REALLOC_WARN_COUNT - 1 {
+            buf.warn_reallocs(start + REALLOC_WARN_WINDOW);
+        }
+        assert_eq!(buf.recent_reallocs, None);
+    }
+
     #[tokio::test]
     async fn test_size_limit() {
         let large_query = "SELECT * FROM ".to_string() + &"x".repeat(10_000);
@@ -12,6 +12,7 @@ pub mod timestamptz;
 pub mod uuid;
 pub mod vector;
 
+pub use array::Array;
 pub use double::Double;
 pub use float::Float;
 pub use interval::Interval;
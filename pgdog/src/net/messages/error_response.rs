@@ -7,6 +7,7 @@ use super::prelude::*;
 use crate::{net::c_string_buf, state::State};
 
 use crate::frontend::Error as FrontendError;
+use crate::frontend::router::parser::Shard;
 
 /// ErrorResponse (B) message.
 #[derive(Debug, Clone)]
@@ -40,6 +41,12 @@ impl ErrorResponse {
         self.code == "28P01"
     }
 
+    /// True if Postgres refused the connection because it reached
+    /// `max_connections` (SQLSTATE 53300).
+    pub fn is_too_many_connections(&self) -> bool {
+        self.code == "53300"
+    }
+
     /// Authentication error.
     pub fn auth(user: &str, database: &str) -> ErrorResponse {
         ErrorResponse {
@@ -86,6 +93,76 @@ impl ErrorResponse {
         }
     }
 
+    pub fn cross_shard_transaction(
+        transaction_shard: Option<usize>,
+        shard: &Shard,
+        query: Option<&str>,
+    ) -> ErrorResponse {
+        let shard = match shard {
+            Shard::Direct(shard) => format!("shard {}", shard),
+            Shard::Multi(shards) => format!("shards {:?}", shards),
+            Shard::All => "all shards".into(),
+        };
+
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "58000".into(),
+            message: "cross-shard transactions are disabled".into(),
+            detail: Some(format!(
+                "{}query requires {}{}",
+                if let Some(transaction_shard) = transaction_shard {
+                    format!("transaction is using shard {} but the ", transaction_shard)
+                } else {
+                    "".into()
+                },
+                shard,
+                if let Some(query) = query {
+                    format!(": {}", query)
+                } else {
+                    "".into()
+                }
+            )),
+            context: None,
+            file: None,
+            routine: None,
+        }
+    }
+
+    pub fn sharding_key_required(command: &str, query: Option<&str>) -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "58000".into(),
+            message: format!(
+                "{} on a sharded table must specify the sharding key",
+                command
+            ),
+            detail: query.map(|query| format!("query would be sent to all shards: {}", query)),
+            routine: Some("client::QueryEngine::route_query".into()),
+            ..Default::default()
+        }
+    }
+
+    /// Explain a routing decision to the client. Sent as a `NoticeResponse`.
+    pub fn routing_notice(message: impl ToString) -> ErrorResponse {
+        ErrorResponse {
+            severity: "NOTICE".into(),
+            code: "00000".into(),
+            message: message.to_string(),
+            routine: Some("client::QueryEngine::route_query".into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn discard_all_in_transaction() -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "25001".into(),
+            message: "DISCARD ALL cannot run inside a transaction block".into(),
+            routine: Some("client::QueryEngine::discard".into()),
+            ..Default::default()
+        }
+    }
+
     pub fn set_shard_after_connect(name: &str) -> ErrorResponse {
         ErrorResponse {
             severity: "ERROR".into(),
@@ -100,6 +177,16 @@ impl ErrorResponse {
         }
     }
 
+    pub fn parameter_not_allowed(name: &str) -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "42501".into(),
+            message: format!("parameter \"{}\" is not allowed", name),
+            routine: Some("client::QueryEngine::route_query".into()),
+            ..Default::default()
+        }
+    }
+
     pub fn omni_in_direct_to_shard() -> ErrorResponse {
         ErrorResponse {
             severity: "ERROR".into(),
@@ -120,6 +207,20 @@ impl ErrorResponse {
         }
     }
 
+    pub fn advisory_lock_shard_mismatch(lock_shard: usize, shard: usize) -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "58000".into(),
+            message: "advisory lock key belongs to a different shard".into(),
+            detail: Some(format!(
+                "lock is taken on shard {} but the connection is using shard {}",
+                lock_shard, shard
+            )),
+            routine: Some("client::QueryEngine::route_query".into()),
+            ..Default::default()
+        }
+    }
+
     pub fn transaction_statement_mode() -> ErrorResponse {
         ErrorResponse {
             severity: "ERROR".into(),
@@ -130,10 +231,38 @@ impl ErrorResponse {
         }
     }
 
+    pub fn temporary_table() -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "0A000".into(),
+            message: "temporary tables are not supported in transaction mode".into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn rate_limited(user: &str, database: &str) -> ErrorResponse {
+        ErrorResponse {
+            severity: "ERROR".into(),
+            code: "53400".into(),
+            message: format!(
+                r#"rate limit exceeded for user "{}" and database "{}""#,
+                user, database
+            ),
+            ..Default::default()
+        }
+    }
+
     pub fn client_idle_timeout(duration: Duration, state: &State) -> ErrorResponse {
         ErrorResponse {
             severity: "FATAL".into(),
-            code: "57P05".into(),
+            // Same codes as idle_in_transaction_session_timeout and idle_session_timeout,
+            // so clients handle them the same way whether Postgres or PgDog closed the connection.
+            code: if state == &State::IdleInTransaction {
+                "25P03"
+            } else {
+                "57P05"
+            }
+            .into(),
             message: format!(
                 "disconnecting {} client",
                 if state == &State::IdleInTransaction {
@@ -157,6 +286,22 @@ impl ErrorResponse {
         }
     }
 
+    pub fn transaction_timeout(duration: Duration) -> ErrorResponse {
+        ErrorResponse {
+            severity: "FATAL".into(),
+            // Same code as transaction_timeout in Postgres.
+            code: "25P04".into(),
+            message: "terminating connection due to transaction timeout".into(),
+            detail: Some(format!(
+                "max_transaction_duration of {}ms expired, the transaction was rolled back",
+                duration.as_millis()
+            )),
+            context: None,
+            file: None,
+            routine: None,
+        }
+    }
+
     /// Connection error.
     pub fn connection(user: &str, database: &str) -> ErrorResponse {
         ErrorResponse {
@@ -173,6 +318,17 @@ impl ErrorResponse {
         }
     }
 
+    /// Client address is quarantined.
+    pub fn client_quarantined() -> ErrorResponse {
+        ErrorResponse {
+            severity: "FATAL".into(),
+            code: "08004".into(),
+            message: "client address is quarantined".into(),
+            detail: Some("too many protocol errors, try again later".into()),
+            ..Default::default()
+        }
+    }
+
     /// Pooler is shutting down.
     pub fn shutting_down() -> ErrorResponse {
         ErrorResponse {
@@ -239,6 +395,8 @@ impl ErrorResponse {
         use crate::backend::Error as BackendError;
         if let FrontendError::Backend(BackendError::ExecutionError(err)) = err {
             *(err.clone())
+        } else if let FrontendError::TransactionTimeout(duration) = err {
+            Self::transaction_timeout(*duration)
         } else {
             Self {
                 severity: "FATAL".into(),
@@ -268,6 +426,24 @@ impl ErrorResponse {
         )
     }
 
+    /// The prepared statement can't be executed because the schema
+    /// of a table it references changed, or it's no longer prepared on the server.
+    ///
+    /// The message is translated with `lc_messages`, so we don't look at it.
+    pub fn is_schema_changed(&self) -> bool {
+        match self.code.as_str() {
+            // invalid_sql_statement_name
+            "26000" => true,
+            // feature_not_supported, raised by the plan cache
+            // if the statement's result type changed.
+            "0A000" => self
+                .routine
+                .as_deref()
+                .is_none_or(|routine| routine == "RevalidateCachedQuery"),
+            _ => false,
+        }
+    }
+
     pub fn no_transaction() -> Self {
         Self {
             severity: "WARNING".into(),
@@ -290,6 +466,19 @@ impl ErrorResponse {
         }
     }
 
+    pub fn prepared_statements_client_limit(limit: usize) -> Self {
+        Self {
+            severity: "ERROR".into(),
+            code: "53400".into(),
+            message: "too many prepared statements".into(),
+            detail: Some(format!(
+                "prepared_statements_client_limit is {}, close unused prepared statements",
+                limit
+            )),
+            ..Default::default()
+        }
+    }
+
     pub fn query_too_large(size: usize, limit: usize) -> Self {
         Self {
             severity: "FATAL".into(),
@@ -10,6 +10,20 @@ pub struct Fastpath {
 }
 
 impl Fastpath {
+    /// Call a function that doesn't take arguments,
+    /// with the result returned in text format.
+    pub fn new(function: i32) -> Self {
+        let mut body = bytes::BytesMut::new();
+        body.put_i32(function);
+        body.put_i16(0); // Argument format codes.
+        body.put_i16(0); // Arguments.
+        body.put_i16(0); // Result format.
+
+        Self {
+            body: body.freeze(),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.body.len()
     }
@@ -89,31 +89,8 @@ impl Startup {
                         // normal startup parameters.
                         unrecognized_options.push(name);
                     } else if name == "search_path" {
-                        let value = search_path(&value);
+                        let value = ParameterValue::search_path(&value);
                         params.insert(name, value);
-                    } else if name == "options" {
-                        let value = value.replace('+', " ");
-                        let kvs = value.split("-c");
-                        for kv in kvs {
-                            let mut nvs = kv.split("=");
-                            let name = nvs.next();
-                            let value = nvs.next();
-
-                            if let Some(name) = name
-                                && let Some(value) = value
-                            {
-                                let name = name.trim().to_string();
-                                let value = value.trim().to_string();
-                                if !name.is_empty() && !value.is_empty() {
-                                    let value = if name == "search_path" {
-                                        search_path(&value)
-                                    } else {
-                                        ParameterValue::from(value)
-                                    };
-                                    params.insert(name, value);
-                                }
-                            }
-                        }
                     } else {
                         params.insert(name, value);
                     }
@@ -287,14 +264,6 @@ impl FromBytes for SslReply {
     }
 }
 
-fn search_path(value: &str) -> ParameterValue {
-    let value = value
-        .split(",")
-        .map(|value| value.to_string())
-        .collect::<Vec<_>>();
-    ParameterValue::Tuple(value)
-}
-
 #[cfg(test)]
 mod test {
     use crate::net::FrontendPid;
@@ -441,9 +410,10 @@ mod test {
     async fn test_options_space_encoded() {
         // options=-c pgdog.role=replica  (space decoded from %20 by libpq)
         let startup = startup_with_options("-c pgdog.role=replica").await;
-        let Startup::Startup { params, .. } = startup else {
+        let Startup::Startup { mut params, .. } = startup else {
             panic!("expected startup message");
         };
+        params.apply_options();
         assert_eq!(
             params.get("pgdog.role").and_then(|v| v.as_str()),
             Some("replica")
@@ -454,9 +424,10 @@ mod test {
     async fn test_options_plus_encoded() {
         // options=-c+pgdog.role=replica  (+ not decoded to space by libpq)
         let startup = startup_with_options("-c+pgdog.role=replica").await;
-        let Startup::Startup { params, .. } = startup else {
+        let Startup::Startup { mut params, .. } = startup else {
             panic!("expected startup message");
         };
+        params.apply_options();
         assert_eq!(
             params.get("pgdog.role").and_then(|v| v.as_str()),
             Some("replica")
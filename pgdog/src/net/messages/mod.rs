@@ -39,7 +39,7 @@ pub mod row_description;
 pub mod sync;
 pub mod terminate;
 
-pub use auth::{Authentication, Password};
+pub use auth::{Authentication, GssResponse, Password};
 pub use backend_key::BackendKeyData;
 pub use backend_pid::BackendPid;
 pub use bind::{Bind, Format, Parameter, ParameterWithFormat};
@@ -69,6 +69,46 @@ impl ParameterStatus {
             },
         ]
     }
+
+    /// Report a different server version to the client
+    /// than the one reported by the server.
+    pub fn override_server_version(params: &mut Vec<ParameterStatus>, version: &str) {
+        params.retain(|param| param.name != "server_version");
+        params.push(ParameterStatus {
+            name: "server_version".into(),
+            value: version.into(),
+        });
+
+        if let Some(num) = server_version_num(version) {
+            for param in params
+                .iter_mut()
+                .filter(|param| param.name == "server_version_num")
+            {
+                param.value = num.clone().into();
+            }
+        }
+    }
+}
+
+/// Convert a version, e.g. `16.4`, into the
+/// `server_version_num` format, e.g. `160004`.
+fn server_version_num(version: &str) -> Option<String> {
+    let mut parts = version
+        .split_whitespace()
+        .next()?
+        .split('.')
+        .map(|part| part.parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+
+    let num = if major >= 10 {
+        major * 10_000 + minor
+    } else {
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        major * 10_000 + minor * 100 + patch
+    };
+
+    Some(num.to_string())
 }
 
 impl ToBytes for ParameterStatus {
@@ -174,6 +214,39 @@ mod test {
         assert_eq!(parsed.value, original.value);
     }
 
+    #[test]
+    fn test_override_server_version() {
+        let mut params = vec![
+            ParameterStatus::from(("server_version", "15.2")),
+            ParameterStatus::from(("server_version_num", "150002")),
+            ParameterStatus::from(("client_encoding", "UTF8")),
+        ];
+
+        ParameterStatus::override_server_version(&mut params, "16.4");
+
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|param| param.name == name)
+                .map(|param| param.value.clone())
+        };
+        assert_eq!(get("server_version"), Some("16.4".into()));
+        assert_eq!(get("server_version_num"), Some("160004".into()));
+        assert_eq!(get("client_encoding"), Some("UTF8".into()));
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn test_server_version_num() {
+        assert_eq!(server_version_num("16.4").as_deref(), Some("160004"));
+        assert_eq!(
+            server_version_num("17.0 (Debian)").as_deref(),
+            Some("170000")
+        );
+        assert_eq!(server_version_num("9.6.24").as_deref(), Some("90624"));
+        assert_eq!(server_version_num("PgDog"), None);
+    }
+
     #[test]
     fn test_from_tuple() {
         let status: ParameterStatus = ("test_name", "test_value").into();
@@ -6,6 +6,8 @@ use std::mem::size_of;
 use std::str::from_utf8;
 use std::str::from_utf8_unchecked;
 
+use bytes::BytesMut;
+
 use super::code;
 use super::prelude::*;
 
@@ -112,6 +114,19 @@ impl Parse {
         self.data_types.clone()
     }
 
+    /// Declare parameter data types.
+    pub fn with_data_types(mut self, data_types: &[i32]) -> Self {
+        let mut buf =
+            BytesMut::with_capacity(size_of::<i16>() + data_types.len() * size_of::<i32>());
+        buf.put_i16(data_types.len() as i16);
+        for data_type in data_types {
+            buf.put_i32(*data_type);
+        }
+        self.data_types = buf.freeze();
+        self.original = None;
+        self
+    }
+
     /// Update the SQL for this prepared statement.
     pub fn set_query(&mut self, query: &str) {
         self.query = Bytes::from(query.to_string() + "\0");
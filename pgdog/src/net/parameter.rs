@@ -40,6 +40,76 @@ static UNTRACKED_PARAMS: Lazy<Vec<String>> = Lazy::new(|| {
     ])
 });
 
+/// Parameter is synchronized with server connections.
+pub fn is_tracked(name: &str) -> bool {
+    !UNTRACKED_PARAMS
+        .iter()
+        .any(|param| param.eq_ignore_ascii_case(name))
+}
+
+/// Split the `options` startup parameter into settings.
+///
+/// Follows the same rules as Postgres: arguments are separated by whitespace,
+/// which can be escaped with a backslash, and settings are passed as
+/// `-c name=value`, `-cname=value` or `--name=value`.
+fn startup_options(options: &str) -> Vec<(String, String)> {
+    let mut args = vec![];
+    let mut arg = String::new();
+    let mut escaped = false;
+
+    for c in options.chars() {
+        if escaped {
+            arg.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c.is_whitespace() || c == '+' {
+            // Some clients don't decode `+` into a space.
+            if !arg.is_empty() {
+                args.push(std::mem::take(&mut arg));
+            }
+        } else {
+            arg.push(c);
+        }
+    }
+
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+
+    let mut settings = vec![];
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (setting, long) = if arg == "-c" {
+            (args.next(), false)
+        } else if let Some(setting) = arg.strip_prefix("--") {
+            (Some(setting.to_string()), true)
+        } else {
+            (arg.strip_prefix("-c").map(|s| s.to_string()), false)
+        };
+
+        let Some((name, value)) = setting.as_deref().and_then(|s| s.split_once('=')) else {
+            debug!("ignoring startup option \"{}\"", arg);
+            continue;
+        };
+
+        if name.is_empty() || value.is_empty() {
+            continue;
+        }
+
+        let name = if long {
+            name.replace('-', "_")
+        } else {
+            name.to_string()
+        };
+
+        settings.push((name, value.to_string()));
+    }
+
+    settings
+}
+
 /// Startup parameter.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Parameter {
@@ -167,6 +237,11 @@ impl From<String> for ParameterValue {
 }
 
 impl ParameterValue {
+    /// Parse a comma-separated `search_path`.
+    pub fn search_path(value: &str) -> Self {
+        Self::Tuple(value.split(',').map(|value| value.to_string()).collect())
+    }
+
     pub fn as_str(&self) -> Option<&str> {
         match self {
             Self::String(s) => Some(s.as_str()),
@@ -281,6 +356,45 @@ impl Parameters {
         self.transaction_local_params.remove(&name);
     }
 
+    /// Replace the `options` startup parameter with the settings it contains,
+    /// e.g. `-c search_path=shard_2`, as if they were passed individually.
+    pub fn apply_options(&mut self) {
+        let Some(options) = self.params.remove("options") else {
+            return;
+        };
+
+        if let Some(options) = options.as_str() {
+            for (name, value) in startup_options(options) {
+                let value = if name.eq_ignore_ascii_case("search_path") {
+                    ParameterValue::search_path(&value)
+                } else {
+                    ParameterValue::from(value)
+                };
+                self.params.insert(name.to_lowercase(), value);
+            }
+        }
+
+        self.hash = Self::compute_hash(&self.params);
+    }
+
+    /// Remove tracked parameters that aren't allowed
+    /// and return their names.
+    pub fn remove_tracked(&mut self, allowed: impl Fn(&str) -> bool) -> Vec<String> {
+        let removed = self
+            .params
+            .keys()
+            .filter(|name| is_tracked(name) && !allowed(name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !removed.is_empty() {
+            self.params.retain(|name, _| !removed.contains(name));
+            self.hash = Self::compute_hash(&self.params);
+        }
+
+        removed
+    }
+
     /// Reset all tracked parameters.
     pub fn reset_all(&mut self) {
         let mut keys: Vec<String> = self.params.keys().cloned().collect();
@@ -1068,4 +1182,67 @@ mod test {
         assert_eq!(params.get("search_path"), None);
         assert_eq!(params.get("timezone"), None);
     }
+
+    #[test]
+    fn test_remove_tracked() {
+        let mut params = Parameters::default();
+        params.insert("user", "pgdog");
+        params.insert("timezone", "UTC");
+        params.insert("session_preload_libraries", "auto_explain");
+
+        let removed = params.remove_tracked(|name| name != "session_preload_libraries");
+
+        assert_eq!(removed, vec!["session_preload_libraries".to_string()]);
+        assert_eq!(params.get("session_preload_libraries"), None);
+        assert!(params.get("timezone").is_some());
+        assert!(params.get("user").is_some());
+        assert_eq!(params.tracked().len(), 1);
+    }
+
+    #[test]
+    fn test_startup_options() {
+        assert_eq!(
+            startup_options("-csearch_path=shard_2"),
+            vec![("search_path".to_string(), "shard_2".to_string())]
+        );
+        assert_eq!(
+            startup_options(r"-c statement_timeout=5s -c application_name=my\ app"),
+            vec![
+                ("statement_timeout".to_string(), "5s".to_string()),
+                ("application_name".to_string(), "my app".to_string()),
+            ]
+        );
+        assert_eq!(
+            startup_options("--search-path=a-c,b --lock_timeout=1s"),
+            vec![
+                ("search_path".to_string(), "a-c,b".to_string()),
+                ("lock_timeout".to_string(), "1s".to_string()),
+            ]
+        );
+        assert!(startup_options("-c -d search_path").is_empty());
+    }
+
+    #[test]
+    fn test_apply_options() {
+        let mut params = Parameters::default();
+        params.insert("user", "pgdog");
+        params.insert(
+            "options",
+            "-c search_path=shard_2,public -c pgdog.role=replica",
+        );
+        params.apply_options();
+
+        assert!(params.get("options").is_none());
+        assert_eq!(
+            params.search_path(),
+            Some(&ParameterValue::Tuple(vec![
+                "shard_2".into(),
+                "public".into()
+            ]))
+        );
+        assert_eq!(
+            params.get("pgdog.role").and_then(|v| v.as_str()),
+            Some("replica")
+        );
+    }
 }
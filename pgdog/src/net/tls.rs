@@ -10,14 +10,15 @@ use std::{
 
 use crate::config::TlsVerifyMode;
 use arc_swap::ArcSwapOption;
-use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_rustls::rustls::{
     self, ClientConfig,
     client::danger::{ServerCertVerified, ServerCertVerifier},
     pki_types::pem::PemObject,
     server::{ServerConnection, WebPkiClientVerifier, danger::ClientCertVerifier},
 };
-use tokio_rustls::{TlsAcceptor, TlsConnector};
+use tokio_rustls::{Connect, TlsAcceptor, TlsConnector};
 use tracing::{debug, info, warn};
 use x509_parser::prelude::FromDer;
 
@@ -419,6 +420,27 @@ pub fn connector_with_verify_mode(
     Ok(connector)
 }
 
+/// Start the TLS handshake with a server.
+///
+/// The host is sent to the server using SNI, which cloud providers use to route
+/// connections, and must match the server certificate in `verify-full` mode.
+/// IP addresses are never sent using SNI, as required by RFC 6066.
+pub fn connect<IO>(
+    stream: IO,
+    host: &str,
+    mode: TlsVerifyMode,
+    ca_cert_path: Option<&PathBuf>,
+) -> Result<Connect<IO>, Error>
+where
+    IO: AsyncRead + AsyncWrite + Unpin,
+{
+    let connector = connector_with_verify_mode(mode, ca_cert_path)?;
+    let server_name = ServerName::try_from(host.to_owned())?;
+    debug!("connecting with TLS to server name: {:?}", server_name);
+
+    Ok(connector.connect(server_name, stream))
+}
+
 /// Certificate verifier that validates certificates but skips hostname verification
 #[derive(Debug)]
 struct NoHostnameVerifier {
@@ -706,6 +728,106 @@ mod tests {
         assert!(result.is_ok(), "Should succeed with valid cert file");
     }
 
+    /// Handshake with an in-process server presenting a certificate for `localhost`.
+    /// Returns whether the client accepted the server and the SNI the server received.
+    async fn backend_handshake(host: &str, mode: TlsVerifyMode) -> (bool, Option<String>) {
+        let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/tls");
+        let cert = CertificateDer::from_pem_file(base.join("backend_localhost.pem")).unwrap();
+        let key = PrivateKeyDer::from_pem_file(base.join("backend_localhost_key.pem")).unwrap();
+        let config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], key)
+            .unwrap();
+
+        let (client, server) = tokio::io::duplex(16 * 1024);
+
+        let server = tokio::spawn(async move {
+            let start =
+                tokio_rustls::LazyConfigAcceptor::new(rustls::server::Acceptor::default(), server)
+                    .await
+                    .unwrap();
+            let sni = start.client_hello().server_name().map(String::from);
+            let _ = start.into_stream(Arc::new(config)).await;
+            sni
+        });
+
+        let ca = base.join("backend_ca.pem");
+        let connected = super::connect(client, host, mode, Some(&ca))
+            .unwrap()
+            .await
+            .is_ok();
+
+        (connected, server.await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_connect_verify_full() {
+        assert_eq!(
+            backend_handshake("localhost", TlsVerifyMode::VerifyFull).await,
+            (true, Some("localhost".into()))
+        );
+
+        // Certificate is for another host.
+        assert_eq!(
+            backend_handshake("db.example.com", TlsVerifyMode::VerifyFull).await,
+            (false, Some("db.example.com".into()))
+        );
+
+        // IP addresses aren't sent using SNI and aren't in the certificate.
+        assert_eq!(
+            backend_handshake("127.0.0.1", TlsVerifyMode::VerifyFull).await,
+            (false, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_verify_ca_and_prefer() {
+        assert_eq!(
+            backend_handshake("db.example.com", TlsVerifyMode::VerifyCa).await,
+            (true, Some("db.example.com".into()))
+        );
+        assert_eq!(
+            backend_handshake("db.example.com", TlsVerifyMode::Prefer).await,
+            (true, Some("db.example.com".into()))
+        );
+    }
+
+    #[test]
+    fn test_connector_sends_sni() {
+        let ca = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/tls/backend_ca.pem");
+
+        for mode in [
+            TlsVerifyMode::Prefer,
+            TlsVerifyMode::VerifyCa,
+            TlsVerifyMode::VerifyFull,
+        ] {
+            let config = build_connector(&ConnectorConfigKey::new(mode, Some(&ca))).unwrap();
+            assert!(config.enable_sni, "{:?}", mode);
+        }
+
+        let (stream, _) = tokio::io::duplex(1);
+        assert!(super::connect(stream, "", TlsVerifyMode::VerifyFull, None).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres server with a publicly trusted certificate at PGDOG_TEST_TLS_HOST"]
+    async fn test_connect_verify_full_real_endpoint() {
+        use crate::net::{Startup, ToBytes};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let host = std::env::var("PGDOG_TEST_TLS_HOST").unwrap();
+        let mut stream = tokio::net::TcpStream::connect((host.as_str(), 5432))
+            .await
+            .unwrap();
+        stream.write_all(&Startup::tls().to_bytes()).await.unwrap();
+        assert_eq!(stream.read_u8().await.unwrap(), b'S');
+
+        super::connect(stream, &host, TlsVerifyMode::VerifyFull, None)
+            .unwrap()
+            .await
+            .unwrap();
+    }
+
     #[test]
     fn identity_from_test_cert() {
         let pem = include_str!("../../tests/tls/cert.pem");
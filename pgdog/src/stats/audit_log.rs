@@ -0,0 +1,60 @@
+//! Audit log metrics.
+
+use crate::frontend::client::query_engine::audit_log::dropped;
+
+use super::{Measurement, Metric, OpenMetric};
+
+pub struct AuditLog {
+    dropped_total: u64,
+}
+
+impl AuditLog {
+    pub fn load() -> Metric {
+        Metric::new(Self {
+            dropped_total: dropped(),
+        })
+    }
+}
+
+impl OpenMetric for AuditLog {
+    fn name(&self) -> String {
+        "audit_log_dropped_total".into()
+    }
+
+    fn metric_type(&self) -> String {
+        "counter".into()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("Audit log records dropped because they couldn't be written fast enough.".into())
+    }
+
+    fn measurements(&self) -> Vec<Measurement> {
+        vec![Measurement {
+            labels: vec![],
+            measurement: self.dropped_total.into(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_audit_log() {
+        let metric = Metric::new(AuditLog { dropped_total: 5 }).to_string();
+        let mut lines = metric.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "# TYPE audit_log_dropped_total counter"
+        );
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("# HELP audit_log_dropped_total")
+        );
+        assert_eq!(lines.next().unwrap(), "audit_log_dropped_total 5");
+    }
+}
@@ -0,0 +1,63 @@
+//! Message buffer metrics.
+
+use crate::net::messages::buffer::reallocs;
+
+use super::{Measurement, Metric, OpenMetric};
+
+pub struct Buffers {
+    reallocs_total: usize,
+}
+
+impl Buffers {
+    pub fn load() -> Metric {
+        Metric::new(Self {
+            reallocs_total: reallocs(),
+        })
+    }
+}
+
+impl OpenMetric for Buffers {
+    fn name(&self) -> String {
+        "buffer_reallocs_total".into()
+    }
+
+    fn metric_type(&self) -> String {
+        "counter".into()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "Total number of message buffer reallocations. If this grows quickly, increase memory.message_buffer."
+                .into(),
+        )
+    }
+
+    fn measurements(&self) -> Vec<Measurement> {
+        vec![Measurement {
+            labels: vec![],
+            measurement: self.reallocs_total.into(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buffers() {
+        let metric = Metric::new(Buffers { reallocs_total: 5 }).to_string();
+        let mut lines = metric.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "# TYPE buffer_reallocs_total counter"
+        );
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("# HELP buffer_reallocs_total")
+        );
+        assert_eq!(lines.next().unwrap(), "buffer_reallocs_total 5");
+    }
+}
@@ -0,0 +1,242 @@
+//! Errors returned to clients, counted by category
+//! for each user and database over a rolling window.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::net::messages::ErrorResponse;
+
+static ERRORS: Lazy<Errors> = Lazy::new(Errors::default);
+
+/// Errors are counted over this window.
+pub const ERRORS_WINDOW: Duration = Duration::from_secs(300);
+
+/// Errors are counted in buckets of this size,
+/// so the window moves forward one bucket at a time.
+const BUCKET: Duration = Duration::from_secs(10);
+
+/// Get errors counted by all clients.
+pub fn errors() -> &'static Errors {
+    &ERRORS
+}
+
+/// What kind of error the client received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Couldn't connect to the database or the connection was lost.
+    Connection,
+    /// Timed out waiting for a connection or for a query to finish.
+    Timeout,
+    /// Database returned an error for the query.
+    Query,
+    /// Unexpected or malformed message.
+    Protocol,
+}
+
+impl ErrorCategory {
+    const ALL: [ErrorCategory; 4] = [
+        ErrorCategory::Connection,
+        ErrorCategory::Timeout,
+        ErrorCategory::Query,
+        ErrorCategory::Protocol,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connection => write!(f, "connection"),
+            Self::Timeout => write!(f, "timeout"),
+            Self::Query => write!(f, "query"),
+            Self::Protocol => write!(f, "protocol"),
+        }
+    }
+}
+
+impl From<&ErrorResponse> for ErrorCategory {
+    fn from(error: &ErrorResponse) -> Self {
+        match error.code.as_str() {
+            "08P01" => Self::Protocol,
+            // query_canceled (statement_timeout), lock_not_available,
+            // idle_in_transaction_session_timeout, transaction_timeout.
+            "57014" | "55P03" | "25P03" | "25P04" => Self::Timeout,
+            // admin_shutdown, crash_shutdown, cannot_connect_now, too_many_connections.
+            "57P01" | "57P02" | "57P03" | "53300" => Self::Connection,
+            code if code.starts_with("08") => Self::Connection,
+            _ => Self::Query,
+        }
+    }
+}
+
+/// Error counts for a user and database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorCounts {
+    pub user: String,
+    pub database: String,
+    pub connection: usize,
+    pub timeout: usize,
+    pub query: usize,
+    pub protocol: usize,
+}
+
+impl ErrorCounts {
+    fn add(&mut self, counts: &[usize; 4]) {
+        self.connection += counts[ErrorCategory::Connection.index()];
+        self.timeout += counts[ErrorCategory::Timeout.index()];
+        self.query += counts[ErrorCategory::Query.index()];
+        self.protocol += counts[ErrorCategory::Protocol.index()];
+    }
+
+    /// Count of errors in the given category.
+    pub fn get(&self, category: ErrorCategory) -> usize {
+        match category {
+            ErrorCategory::Connection => self.connection,
+            ErrorCategory::Timeout => self.timeout,
+            ErrorCategory::Query => self.query,
+            ErrorCategory::Protocol => self.protocol,
+        }
+    }
+
+    /// Errors in all categories.
+    pub fn total(&self) -> usize {
+        ErrorCategory::ALL
+            .iter()
+            .map(|category| self.get(*category))
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    start: Instant,
+    counts: [usize; 4],
+}
+
+/// Errors counted by all clients.
+#[derive(Debug, Default)]
+pub struct Errors {
+    buckets: Mutex<HashMap<(String, String), VecDeque<Bucket>>>,
+}
+
+impl Errors {
+    /// Count an error returned to a client.
+    pub fn record(&self, user: &str, database: &str, category: ErrorCategory) {
+        self.record_at(user, database, category, Instant::now());
+    }
+
+    fn record_at(&self, user: &str, database: &str, category: ErrorCategory, now: Instant) {
+        let mut guard = self.buckets.lock();
+        let buckets = guard
+            .entry((user.to_owned(), database.to_owned()))
+            .or_default();
+
+        Self::expire(buckets, now);
+
+        match buckets.back_mut() {
+            Some(bucket) if now.saturating_duration_since(bucket.start) < BUCKET => {
+                bucket.counts[category.index()] += 1;
+            }
+            _ => {
+                let mut counts = [0; 4];
+                counts[category.index()] += 1;
+                buckets.push_back(Bucket { start: now, counts });
+            }
+        }
+    }
+
+    /// Error counts for each user and database, over the last [`ERRORS_WINDOW`].
+    pub fn counts(&self) -> Vec<ErrorCounts> {
+        self.counts_at(Instant::now())
+    }
+
+    fn counts_at(&self, now: Instant) -> Vec<ErrorCounts> {
+        let mut guard = self.buckets.lock();
+        guard.retain(|_, buckets| {
+            Self::expire(buckets, now);
+            !buckets.is_empty()
+        });
+
+        let mut counts = guard
+            .iter()
+            .map(|((user, database), buckets)| {
+                let mut counts = ErrorCounts {
+                    user: user.clone(),
+                    database: database.clone(),
+                    ..Default::default()
+                };
+                for bucket in buckets {
+                    counts.add(&bucket.counts);
+                }
+                counts
+            })
+            .collect::<Vec<_>>();
+        counts.sort_by(|a, b| (&a.database, &a.user).cmp(&(&b.database, &b.user)));
+
+        counts
+    }
+
+    fn expire(buckets: &mut VecDeque<Bucket>, now: Instant) {
+        while buckets
+            .front()
+            .is_some_and(|bucket| now.saturating_duration_since(bucket.start) >= ERRORS_WINDOW)
+        {
+            buckets.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_categorize_error_response() {
+        let category = |code: &str| {
+            ErrorCategory::from(&ErrorResponse {
+                code: code.into(),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(category("08006"), ErrorCategory::Connection);
+        assert_eq!(category("57P01"), ErrorCategory::Connection);
+        assert_eq!(category("57014"), ErrorCategory::Timeout);
+        assert_eq!(category("08P01"), ErrorCategory::Protocol);
+        assert_eq!(category("42P01"), ErrorCategory::Query);
+    }
+
+    #[test]
+    fn test_errors_rolling_window() {
+        let errors = Errors::default();
+        let start = Instant::now();
+
+        errors.record_at("alice", "app", ErrorCategory::Query, start);
+        errors.record_at("alice", "app", ErrorCategory::Query, start);
+        errors.record_at("alice", "app", ErrorCategory::Timeout, start + BUCKET * 3);
+        errors.record_at("bob", "app", ErrorCategory::Connection, start);
+
+        let counts = errors.counts_at(start + BUCKET * 3);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].user, "alice");
+        assert_eq!(counts[0].query, 2);
+        assert_eq!(counts[0].timeout, 1);
+        assert_eq!(counts[0].total(), 3);
+        assert_eq!(counts[1].connection, 1);
+
+        // The first bucket is outside the window now.
+        let counts = errors.counts_at(start + ERRORS_WINDOW + BUCKET);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].query, 0);
+        assert_eq!(counts[0].timeout, 1);
+
+        assert!(errors.counts_at(start + ERRORS_WINDOW * 2).is_empty());
+    }
+}
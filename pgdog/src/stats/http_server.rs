@@ -11,7 +11,7 @@ use tokio::net::TcpListener;
 use tokio::select;
 use tracing::{info, warn};
 
-use super::{Clients, Listeners, MirrorStatsMetrics, Pools, QueryCache, TwoPc};
+use super::{AuditLog, Buffers, Clients, Listeners, MirrorStatsMetrics, Pools, QueryCache, TwoPc};
 use crate::tasks;
 
 async fn metrics(_: Request<hyper::body::Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
@@ -34,6 +34,8 @@ async fn metrics(_: Request<hyper::body::Incoming>) -> Result<Response<Full<Byte
         .collect();
     let query_cache = query_cache.join("\n");
     let two_pc = TwoPc::load();
+    let buffers = Buffers::load();
+    let audit_log = AuditLog::load();
     let metrics_data = clients.to_string()
         + "\n"
         + &pools.to_string()
@@ -44,7 +46,11 @@ async fn metrics(_: Request<hyper::body::Incoming>) -> Result<Response<Full<Byte
         + "\n"
         + &query_cache
         + "\n"
-        + &two_pc.to_string();
+        + &two_pc.to_string()
+        + "\n"
+        + &buffers.to_string()
+        + "\n"
+        + &audit_log.to_string();
     let response = Response::builder()
         .header(
             hyper::header::CONTENT_TYPE,
@@ -13,12 +13,14 @@ impl MirrorStatsMetrics {
         let mut dropped_count_measurements = vec![];
         let mut error_count_measurements = vec![];
         let mut queue_length_measurements = vec![];
+        let mut mismatch_count_measurements = vec![];
 
         let mut global_total = 0usize;
         let mut global_mirrored = 0usize;
         let mut global_dropped = 0usize;
         let mut global_error = 0usize;
         let mut global_queue_length = 0usize;
+        let mut global_mismatch = 0usize;
 
         // Iterate through all clusters and collect their mirror stats
         for (user, cluster) in databases().all() {
@@ -57,12 +59,18 @@ impl MirrorStatsMetrics {
                 measurement: counts.queue_length.into(),
             });
 
+            mismatch_count_measurements.push(Measurement {
+                labels: labels.clone(),
+                measurement: counts.mismatch_count.into(),
+            });
+
             // Accumulate for global metrics
             global_total += counts.total_count;
             global_mirrored += counts.mirrored_count;
             global_dropped += counts.dropped_count;
             global_error += counts.error_count;
             global_queue_length += counts.queue_length;
+            global_mismatch += counts.mismatch_count;
         }
 
         // Add global measurements (no labels)
@@ -91,6 +99,11 @@ impl MirrorStatsMetrics {
             measurement: global_queue_length.into(),
         });
 
+        mismatch_count_measurements.push(Measurement {
+            labels: vec![],
+            measurement: global_mismatch.into(),
+        });
+
         // Create metrics
         metrics.push(Metric::new(MirrorStatsMetric {
             name: "mirror_total_count".into(),
@@ -127,6 +140,13 @@ impl MirrorStatsMetrics {
             metric_type: "gauge".into(),
         }));
 
+        metrics.push(Metric::new(MirrorStatsMetric {
+            name: "mirror_mismatch_count".into(),
+            measurements: mismatch_count_measurements,
+            help: "Total number of mirrored requests that returned different rows than the source database.".into(),
+            metric_type: "counter".into(),
+        }));
+
         metrics
     }
 }
@@ -1,5 +1,8 @@
 //! Statistics.
+pub mod audit_log;
+pub mod buffers;
 pub mod clients;
+pub mod errors;
 pub mod http_server;
 pub mod mirror_stats;
 pub mod open_metric;
@@ -11,8 +14,11 @@ pub mod listeners;
 pub mod logger;
 pub mod memory;
 pub mod query_cache;
+pub mod tags;
 pub mod two_pc;
 
+pub use audit_log::AuditLog;
+pub use buffers::Buffers;
 pub use clients::Clients;
 pub use listeners::Listeners;
 pub use logger::Logger as StatsLogger;
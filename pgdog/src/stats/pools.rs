@@ -86,6 +86,7 @@ impl Pools {
         let mut total_sv_xact_idle = vec![];
         let mut total_auth_attempts = vec![];
         let mut avg_auth_attempts = vec![];
+        let mut total_out_of_connections = vec![];
 
         let general = &crate::config::config().config.general;
 
@@ -315,6 +316,11 @@ impl Pools {
                         labels: labels.clone(),
                         measurement: averages.auth_attempts.into(),
                     });
+
+                    total_out_of_connections.push(Measurement {
+                        labels: labels.clone(),
+                        measurement: totals.out_of_connections.into(),
+                    });
                 }
             }
         }
@@ -684,6 +690,14 @@ impl Pools {
             metric_type: None,
         }));
 
+        metrics.push(Metric::new(PoolMetric {
+            name: "total_out_of_connections".into(),
+            measurements: total_out_of_connections,
+            help: "Total number of server connections refused because Postgres reached max_connections.".into(),
+            unit: None,
+            metric_type: Some("counter".into()),
+        }));
+
         Pools { metrics }
     }
 
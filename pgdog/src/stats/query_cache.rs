@@ -66,6 +66,30 @@ impl QueryCache {
                 value: self.stats.multi,
                 gauge: false,
             }),
+            Metric::new(QueryCacheMetric {
+                name: "query_cache_evictions".into(),
+                help: "Queries evicted from the query cache to make room for new ones".into(),
+                value: self.stats.evictions,
+                gauge: false,
+            }),
+            Metric::new(QueryCacheMetric {
+                name: "query_cache_expired".into(),
+                help: "Queries removed from the query cache after reaching the TTL".into(),
+                value: self.stats.expired,
+                gauge: false,
+            }),
+            Metric::new(QueryCacheMetric {
+                name: "query_cache_unknown".into(),
+                help: "Statements of a type the query router doesn't recognize".into(),
+                value: self.stats.unknown,
+                gauge: false,
+            }),
+            Metric::new(QueryCacheMetric {
+                name: "query_cache_parse_failures".into(),
+                help: "Queries the query parser failed to parse".into(),
+                value: self.stats.parse_failures,
+                gauge: false,
+            }),
             Metric::new(QueryCacheMetric {
                 name: "query_cache_size".into(),
                 help: "Number of queries in the cache".into(),
@@ -170,6 +194,10 @@ mod tests {
                 multi: 4,
                 parse_time: Duration::ZERO,
                 fingerprints: 8,
+                evictions: 9,
+                expired: 10,
+                unknown: 11,
+                parse_failures: 12,
             },
             len: 5,
             prepared_statements: 6,
@@ -185,6 +213,10 @@ mod tests {
                 "query_cache_misses".to_string(),
                 "query_cache_direct".to_string(),
                 "query_cache_cross".to_string(),
+                "query_cache_evictions".to_string(),
+                "query_cache_expired".to_string(),
+                "query_cache_unknown".to_string(),
+                "query_cache_parse_failures".to_string(),
                 "query_cache_size".to_string(),
                 "query_cache_parse_time".to_string(),
                 "query_cache_fingerprints".to_string(),
@@ -0,0 +1,171 @@
+//! Queries counted by the tag clients set in a query comment,
+//! e.g. `/* tag: checkout-flow */`, for each user and database.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+
+static TAGS: Lazy<Tags> = Lazy::new(Tags::default);
+
+/// Tags are set by clients, so we cap how many we keep track of.
+/// Queries with tags seen after the limit is reached aren't counted.
+pub const MAX_TAGS: usize = 1000;
+
+/// Get queries counted by tag.
+pub fn tags() -> &'static Tags {
+    &TAGS
+}
+
+/// Query count for a tag, user and database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagCounts {
+    pub tag: String,
+    pub user: String,
+    pub database: String,
+    pub queries: usize,
+}
+
+/// Queries counted by tag.
+#[derive(Debug, Default)]
+pub struct Tags {
+    counts: DashMap<(String, String, String), Arc<AtomicUsize>>,
+}
+
+impl Tags {
+    /// Counter for the tag, user and database, unless
+    /// we're already keeping track of too many tags.
+    pub fn counter(&self, user: &str, database: &str, tag: &str) -> Option<Arc<AtomicUsize>> {
+        let key = (tag.to_owned(), user.to_owned(), database.to_owned());
+
+        if let Some(counter) = self.counts.get(&key) {
+            return Some(counter.clone());
+        }
+
+        if self.counts.len() >= MAX_TAGS {
+            return None;
+        }
+
+        Some(self.counts.entry(key).or_default().clone())
+    }
+
+    /// Count a query tagged by the client.
+    pub fn record(&self, user: &str, database: &str, tag: &str) {
+        if let Some(counter) = self.counter(user, database, tag) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Query counts for each tag, user and database.
+    pub fn counts(&self) -> Vec<TagCounts> {
+        let mut counts = self
+            .counts
+            .iter()
+            .map(|entry| {
+                let (tag, user, database) = entry.key();
+                TagCounts {
+                    tag: tag.clone(),
+                    user: user.clone(),
+                    database: database.clone(),
+                    queries: entry.value().load(Ordering::Relaxed),
+                }
+            })
+            .collect::<Vec<_>>();
+        counts.sort_by(|a, b| (&a.tag, &a.database, &a.user).cmp(&(&b.tag, &b.database, &b.user)));
+
+        counts
+    }
+}
+
+/// Counters for the tags used by one client, so counting
+/// a query is an atomic increment without touching the shared map.
+#[derive(Debug, Default)]
+pub struct ClientTags {
+    counters: HashMap<String, Option<Arc<AtomicUsize>>>,
+}
+
+impl ClientTags {
+    /// Count a query tagged by the client.
+    pub fn record(&mut self, user: &str, database: &str, tag: &str) {
+        if let Some(counter) = self.counters.get(tag) {
+            if let Some(counter) = counter {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+            return;
+        }
+
+        let counter = tags().counter(user, database, tag);
+        if let Some(ref counter) = counter {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if self.counters.len() < MAX_TAGS {
+            self.counters.insert(tag.to_owned(), counter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tags_counted() {
+        let tags = Tags::default();
+
+        tags.record("alice", "app", "checkout-flow");
+        tags.record("alice", "app", "checkout-flow");
+        tags.record("bob", "app", "checkout-flow");
+        tags.record("alice", "app", "billing");
+
+        let counts = tags.counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[0].tag, "billing");
+        assert_eq!(counts[0].queries, 1);
+        assert_eq!(counts[1].tag, "checkout-flow");
+        assert_eq!(counts[1].user, "alice");
+        assert_eq!(counts[1].queries, 2);
+        assert_eq!(counts[2].user, "bob");
+        assert_eq!(counts[2].queries, 1);
+    }
+
+    #[test]
+    fn test_tags_limit() {
+        let tags = Tags::default();
+
+        for i in 0..MAX_TAGS + 10 {
+            tags.record("alice", "app", &format!("tag_{}", i));
+        }
+        assert_eq!(tags.counts().len(), MAX_TAGS);
+
+        // Tags we already track are still counted.
+        tags.record("alice", "app", "tag_0");
+        let counts = tags.counts();
+        let tag = counts.iter().find(|counts| counts.tag == "tag_0").unwrap();
+        assert_eq!(tag.queries, 2);
+    }
+
+    #[test]
+    fn test_client_tags_counted() {
+        let mut first = ClientTags::default();
+        let mut second = ClientTags::default();
+
+        first.record("alice", "test_client_tags_counted", "checkout-flow");
+        first.record("alice", "test_client_tags_counted", "checkout-flow");
+        second.record("alice", "test_client_tags_counted", "checkout-flow");
+
+        let counts = tags()
+            .counts()
+            .into_iter()
+            .filter(|counts| counts.database == "test_client_tags_counted")
+            .collect::<Vec<_>>();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].queries, 3);
+    }
+}